@@ -1,10 +1,14 @@
 use phase2_coordinator::{
     authentication::{KeyPair, Production, Signature},
-    commands::{Computation, RandomSource, SEED_LENGTH},
+    commands::{verify_response, Computation, RandomSource, SEED_LENGTH},
     io::{self, verify_signature, KeyPairUser},
-    objects::{ContributionFileSignature, ContributionInfo, ContributionState, TrimmedContributionInfo},
-    rest_utils::{ContributorStatus, PostChunkRequest, TOKENS_ZIP_FILE, UPDATE_TIME},
-    storage::Object,
+    objects::{
+        ContributionFileSignature, ContributionInfo, ContributionState, NamadaAddressBinding, TrimmedContributionInfo,
+    },
+    rest_utils::{
+        challenge_message, verification_result_message, ContributorStatus, CoordinatorIdentity, LockedChunk,
+        PostChunkRequest, Session, VerificationResult, VerificationTask, TOKENS_ZIP_FILE, UPDATE_TIME,
+    },
 };
 
 use reqwest::{Client, Url};
@@ -20,7 +24,9 @@ use futures_util::StreamExt;
 use phase2_cli::{
     ascii_logo::{ASCII_CONTRIBUTION_DONE, ASCII_LOGO},
     keys::{self, EncryptedKeypair, TomlConfig},
-    requests, CeremonyOpt, CoordinatorUrl, Token, VerifySignatureContribution,
+    requests,
+    signer::{self, signer},
+    AirGappedChallenge, AirGappedContribution, CeremonyOpt, CoordinatorUrl, Token, VerifySignatureContribution,
 };
 use serde_json;
 use setup_utils::calculate_hash;
@@ -28,8 +34,7 @@ use structopt::StructOpt;
 
 use std::{
     collections::HashMap,
-    fs::{self, File, OpenOptions},
-    io::Read,
+    fs::{self, OpenOptions},
     process,
     sync::Arc,
     time::{Duration, Instant, UNIX_EPOCH},
@@ -50,6 +55,8 @@ use bs58;
 
 const OFFLINE_CONTRIBUTION_FILE_NAME: &str = "contribution.params";
 const OFFLINE_CHALLENGE_FILE_NAME: &str = "challenge.params";
+const OFFLINE_CHALLENGE_METADATA_FILE_NAME: &str = "challenge.json";
+const OFFLINE_CONTRIBUTION_METADATA_FILE_NAME: &str = "contribution.json";
 
 const CUSTOM_SEED_MSG_NO: &str = "Enter a variable-length random string to be used as entropy in combination with your OS randomness.\nYou can type frenetically, smash your keyboard, or enter a string representation of your alternative source of entropy.\nThe only limitation is your terminal’s max command length.\nThis string will be hashed together with your OS randomness to produce the seed for ChaCha RNG";
 const CUSTOM_SEED_MSG_YES: &str = "Provide your custom random seed to initialize the ChaCha random number generator.\nYou seed might come you from an external source of randomness like atmospheric noise, radioactive elements, lava lite etc. or an airgapped machine.";
@@ -96,23 +103,6 @@ fn initialize_contribution() -> Result<ContributionInfo> {
     Ok(contrib_info)
 }
 
-#[inline(always)]
-fn get_file_as_byte_vec(filename: &str, round_height: u64, contribution_id: u64) -> Result<Vec<u8>> {
-    let mut f = File::open(filename)?;
-    let metadata = fs::metadata(filename)?;
-
-    let anoma_file_size: u64 = Object::anoma_contribution_file_size(round_height, contribution_id);
-    let mut buffer = vec![0; anoma_file_size as usize];
-    debug!(
-        "namada_contribution_file_size: round_height {}, contribution_id {}",
-        round_height, contribution_id
-    );
-    debug!("metadata file length {}", metadata.len());
-    f.read(&mut buffer)?;
-
-    Ok(buffer)
-}
-
 fn get_progress_bar(len: u64) -> ProgressBar {
     let progress_bar = ProgressBar::new(len);
     progress_bar.set_style(
@@ -184,6 +174,14 @@ fn compute_contribution_offline() -> Result<()> {
     Ok(())
 }
 
+/// Bounds the number of threads of the computation, if requested
+fn set_contribution_threads(threads: Option<usize>) {
+    if let Some(threads) = threads {
+        Computation::set_threads(threads)
+            .expect(&format!("{}", "Error while setting the number of threads".red().bold()));
+    }
+}
+
 /// Computes randomness
 fn compute_contribution(custom_seed: bool, challenge: &[u8], filename: &str) -> Result<()> {
     let rand_source = if custom_seed {
@@ -224,18 +222,17 @@ fn compute_contribution(custom_seed: bool, challenge: &[u8], filename: &str) ->
     Ok(())
 }
 
-/// Performs the contribution sequence. Returns the round height of the contribution.
-#[inline(always)]
-async fn contribute(
+/// Locks a chunk and downloads its challenge
+async fn download_challenge(
     client: &Client,
     coordinator: &Url,
     keypair: &KeyPair,
-    mut contrib_info: ContributionInfo,
-    heartbeat_handle: &JoinHandle<()>,
-) -> Result<u64> {
+    identity: &CoordinatorIdentity,
+    contrib_info: &mut ContributionInfo,
+) -> Result<(LockedChunk, Vec<u8>)> {
     // Get the necessary info to compute the contribution
     println!("{} Locking chunk", "[4/11]".bold().dimmed());
-    let locked_locators = requests::get_lock_chunk(client, coordinator, keypair).await?;
+    let locked_chunk = requests::get_lock_chunk(client, coordinator, keypair).await?;
     contrib_info.timestamps.challenge_locked = Utc::now();
     let end_lock_time = contrib_info.timestamps.challenge_locked + chrono::Duration::minutes(20);
     println!(
@@ -244,8 +241,7 @@ async fn contribute(
         contrib_info.timestamps.challenge_locked.to_rfc2822(),
         end_lock_time.to_rfc2822()).bright_cyan()
     );
-    let response_locator = locked_locators.next_contribution();
-    let round_height = response_locator.round_height();
+    let round_height = locked_chunk.locators.next_contribution().round_height();
     contrib_info.ceremony_round = round_height;
 
     let challenge_url = requests::get_challenge_url(client, coordinator, keypair, &round_height).await?;
@@ -261,6 +257,155 @@ async fn contribute(
     progress_bar.finish();
     contrib_info.timestamps.challenge_downloaded = Utc::now();
 
+    // Older coordinators don't provide the hash of the challenge
+    if let Some(challenge_hash) = &locked_chunk.challenge_hash {
+        if hex::encode(calculate_hash(&challenge)) != *challenge_hash {
+            anyhow::bail!("The downloaded challenge doesn't match its hash");
+        }
+    }
+
+    // The hash checked above must be signed by the coordinator, if it signs the challenges
+    if identity.signed_challenges {
+        let signed = match (&locked_chunk.challenge_hash, &locked_chunk.challenge_signature) {
+            (Some(hash), Some(signature)) => Production.verify(
+                &identity.coordinator_public_key,
+                &challenge_message(round_height, hash),
+                signature,
+            ),
+            _ => false,
+        };
+        if !signed {
+            anyhow::bail!("The downloaded challenge is not signed by the coordinator");
+        }
+    }
+
+    Ok((locked_chunk, challenge))
+}
+
+/// Signs the contribution, filling its hashes and signatures in the contribution info
+fn sign_contribution(
+    keypair: &KeyPair,
+    contrib_info: &mut ContributionInfo,
+    challenge_hash: &[u8],
+    contribution: &[u8],
+) -> Result<ContributionFileSignature> {
+    let contribution_file_hash = calculate_hash(contribution);
+    let contribution_file_hash_str = hex::encode(contribution_file_hash);
+    debug!("Contribution hash is {}", contribution_file_hash_str);
+    debug!("Contribution length: {}", contribution.len());
+    contrib_info.contribution_file_hash = contribution_file_hash_str;
+    contrib_info.contribution_file_signature =
+        signer().sign(keypair.sigkey(), contrib_info.contribution_file_hash.as_str())?;
+    contrib_info.contribution_hash = hex::encode(calculate_hash(&contribution[challenge_hash.len()..]));
+    contrib_info.contribution_hash_signature =
+        signer().sign(keypair.sigkey(), contrib_info.contribution_hash.as_str())?;
+
+    let contribution_state = ContributionState::new(challenge_hash.to_vec(), contribution_file_hash.to_vec(), None)?;
+    let signature = signer().sign(keypair.sigkey(), &contribution_state.signature_message()?)?;
+
+    Ok(ContributionFileSignature::new(signature, contribution_state)?)
+}
+
+/// Uploads a signed contribution and notifies the coordinator. Returns the round height of the contribution.
+async fn upload_contribution(
+    client: &Client,
+    coordinator: &Url,
+    keypair: &KeyPair,
+    mut contrib_info: ContributionInfo,
+    locked_chunk: LockedChunk,
+    contrib_filename: &str,
+    contribution_file_signature: &ContributionFileSignature,
+) -> Result<u64> {
+    let LockedChunk {
+        locators: locked_locators,
+        upload_urls,
+        ..
+    } = locked_chunk;
+    let round_height = locked_locators.next_contribution().round_height();
+
+    // The upload urls are provided at lock time if the coordinator allows direct upload
+    let (contribution_url, contribution_signature_url) = match upload_urls {
+        Some(urls) => urls,
+        None => requests::get_contribution_url(client, coordinator, keypair, &round_height).await?,
+    };
+    println!("{} Uploading contribution", "[9/11]".bold().dimmed());
+    let contrib_file = async_fs::File::open(contrib_filename).await?;
+    let contrib_size = async_fs::metadata(contrib_filename).await?.len();
+    let mut stream = ReaderStream::new(contrib_file);
+    let pb = get_progress_bar(contrib_size);
+    let pb_clone = pb.clone();
+
+    let contrib_stream = try_stream! {
+        while let Some(b) = stream.next().await {
+            let b = b?;
+            pb.inc(b.len() as u64);
+            yield b;
+        }
+    };
+
+    requests::upload_chunk(
+        client,
+        contribution_url.as_str(),
+        contribution_signature_url.as_str(),
+        contrib_stream,
+        contrib_size,
+        contribution_file_signature,
+    )
+    .await?;
+    pb_clone.finish();
+    contrib_info.timestamps.end_contribution = Utc::now();
+
+    // Compute signature of contributor info
+    contrib_info
+        .try_sign(signer(), keypair)
+        .expect(&format!("{}", "Error while signing the contribution info".red().bold()));
+
+    // Write contribution info file and send it to the Coordinator
+    println!("{} Uploading contribution info", "[10/11]".bold().dimmed());
+    async_fs::write(
+        format!("namada_contributor_info_round_{}.json", contrib_info.ceremony_round),
+        &serde_json::to_vec(&contrib_info)?,
+    )
+    .await?;
+    requests::post_contribution_info(client, coordinator, keypair, &contrib_info).await?;
+
+    // Notify contribution to the coordinator for the verification
+    println!(
+        "{} Notifying the coordinator of your uploaded contribution.\nYour contribution is being processed... This might take a minute...",
+        "[11/11]".bold().dimmed()
+    );
+    let post_chunk_req = PostChunkRequest::new(
+        round_height,
+        locked_locators.next_contribution(),
+        locked_locators.next_contribution_file_signature(),
+    );
+    let receipt = requests::post_contribute_chunk(client, coordinator, keypair, &post_chunk_req).await?;
+
+    // Keep the receipt signed by the coordinator as a proof of the contribution
+    async_fs::write(
+        format!("namada_contribution_receipt_round_{}.json", receipt.round_height),
+        &serde_json::to_vec(&receipt)?,
+    )
+    .await?;
+
+    Ok(round_height)
+}
+
+/// Performs the contribution sequence. Returns the round height of the contribution.
+#[inline(always)]
+async fn contribute(
+    client: &Client,
+    coordinator: &Url,
+    keypair: &KeyPair,
+    identity: &CoordinatorIdentity,
+    mut contrib_info: ContributionInfo,
+    heartbeat_handle: &JoinHandle<()>,
+) -> Result<u64> {
+    let (locked_chunk, challenge) =
+        download_challenge(client, coordinator, keypair, identity, &mut contrib_info).await?;
+    let response_locator = locked_chunk.locators.next_contribution();
+    let round_height = response_locator.round_height();
+
     // Saves the challenge locally, in case the contributor is paranoid and wants to double check himself. It is also used in the offline contrib path
     let challenge_filename = if contrib_info.is_another_machine {
         OFFLINE_CHALLENGE_FILE_NAME.to_string()
@@ -308,14 +453,7 @@ async fn contribute(
         .await??;
     }
     let contrib_filename_copy = contrib_filename.clone();
-    let contribution = tokio::task::spawn_blocking(move || {
-        get_file_as_byte_vec(
-            contrib_filename_copy.as_str(),
-            round_height,
-            response_locator.contribution_id(),
-        )
-    })
-    .await??;
+    let contribution = tokio::task::spawn_blocking(move || fs::read(contrib_filename_copy.as_str())).await??;
 
     contrib_info.timestamps.end_computation = Utc::now();
     trace!("Response writer {:?}", response_writer);
@@ -331,78 +469,19 @@ async fn contribute(
 
     // Update contribution info
     println!("{} Updating contribution info", "[8/11]".bold().dimmed());
-    let contribution_file_hash = calculate_hash(contribution.as_ref());
-    let contribution_file_hash_str = hex::encode(contribution_file_hash);
-    debug!("Contribution hash is {}", contribution_file_hash_str);
-    debug!("Contribution length: {}", contribution.len());
-    contrib_info.contribution_file_hash = contribution_file_hash_str;
-    contrib_info.contribution_file_signature =
-        Production.sign(keypair.sigkey(), contrib_info.contribution_file_hash.as_str())?;
-    let challenge_hash_len = challenge_hash.len();
-    contrib_info.contribution_hash = hex::encode(calculate_hash(&contribution[challenge_hash_len..]));
-    contrib_info.contribution_hash_signature =
-        Production.sign(keypair.sigkey(), contrib_info.contribution_hash.as_str())?;
+    let contribution_file_signature = sign_contribution(keypair, &mut contrib_info, &challenge_hash, &contribution)?;
 
     // Send contribution to the coordinator
-    let contribution_state = ContributionState::new(challenge_hash.to_vec(), contribution_file_hash.to_vec(), None)?;
-
-    let signature = Production.sign(keypair.sigkey(), &contribution_state.signature_message()?)?;
-    let contribution_file_signature = ContributionFileSignature::new(signature, contribution_state)?;
-
-    let (contribution_url, contribution_signature_url) =
-        requests::get_contribution_url(client, coordinator, keypair, &round_height).await?;
-    println!("{} Uploading contribution", "[9/11]".bold().dimmed());
-    let contrib_file = async_fs::File::open(contrib_filename.as_str()).await?;
-    let contrib_size = async_fs::metadata(contrib_filename.as_str()).await?.len();
-    let mut stream = ReaderStream::new(contrib_file);
-    let pb = get_progress_bar(contrib_size);
-    let pb_clone = pb.clone();
-
-    let contrib_stream = try_stream! {
-        while let Some(b) = stream.next().await {
-            let b = b?;
-            pb.inc(b.len() as u64);
-            yield b;
-        }
-    };
-
-    requests::upload_chunk(
+    upload_contribution(
         client,
-        contribution_url.as_str(),
-        contribution_signature_url.as_str(),
-        contrib_stream,
-        contrib_size,
+        coordinator,
+        keypair,
+        contrib_info,
+        locked_chunk,
+        contrib_filename.as_str(),
         &contribution_file_signature,
     )
     .await?;
-    pb_clone.finish();
-    contrib_info.timestamps.end_contribution = Utc::now();
-
-    // Compute signature of contributor info
-    contrib_info
-        .try_sign(keypair)
-        .expect(&format!("{}", "Error while signing the contribution info".red().bold()));
-
-    // Write contribution info file and send it to the Coordinator
-    println!("{} Uploading contribution info", "[10/11]".bold().dimmed());
-    async_fs::write(
-        format!("namada_contributor_info_round_{}.json", contrib_info.ceremony_round),
-        &serde_json::to_vec(&contrib_info)?,
-    )
-    .await?;
-    requests::post_contribution_info(client, coordinator, keypair, &contrib_info).await?;
-
-    // Notify contribution to the coordinator for the verification
-    println!(
-        "{} Notifying the coordinator of your uploaded contribution.\nYour contribution is being processed... This might take a minute...",
-        "[11/11]".bold().dimmed()
-    );
-    let post_chunk_req = PostChunkRequest::new(
-        round_height,
-        locked_locators.next_contribution(),
-        locked_locators.next_contribution_file_signature(),
-    );
-    requests::post_contribute_chunk(client, coordinator, keypair, &post_chunk_req).await?;
 
     // Interrupt heartbeat, to prevent heartbeating during verification
     // NOTE: need to manually cancel the heartbeat task because, by default, async runtimes use detach on drop strategy
@@ -416,20 +495,177 @@ async fn contribute(
     Ok(round_height)
 }
 
+/// Exports the challenge and its metadata for an air-gapped contribution. Returns the round height of the contribution.
+async fn export_challenge(
+    client: &Client,
+    coordinator: &Url,
+    keypair: &KeyPair,
+    identity: &CoordinatorIdentity,
+    mut contrib_info: ContributionInfo,
+) -> Result<u64> {
+    let (locked_chunk, challenge) =
+        download_challenge(client, coordinator, keypair, identity, &mut contrib_info).await?;
+    let round_height = contrib_info.ceremony_round;
+
+    println!("{} Exporting challenge", "[6/11]".bold().dimmed());
+    async_fs::write(OFFLINE_CHALLENGE_FILE_NAME, &challenge).await?;
+    let metadata = AirGappedChallenge {
+        locked_chunk,
+        challenge_hash: hex::encode(calculate_hash(&challenge)),
+        contrib_info,
+    };
+    async_fs::write(
+        OFFLINE_CHALLENGE_METADATA_FILE_NAME,
+        &serde_json::to_vec_pretty(&metadata)?,
+    )
+    .await?;
+
+    println!(
+        "{}",
+        format!(
+            "The challenge has been exported to \"{}\" and \"{}\". To contribute:\n{:4}1) Copy both files to the air-gapped machine and run \"namada-ts contribute air-gapped\" in their directory\n{:4}2) Copy back the \"{}\" and \"{}\" files it produces to this directory\n{:4}3) Run \"namada-ts contribute upload\" in this directory\nKeep this command running until the upload, it keeps your session alive",
+            OFFLINE_CHALLENGE_FILE_NAME,
+            OFFLINE_CHALLENGE_METADATA_FILE_NAME,
+            "",
+            "",
+            OFFLINE_CONTRIBUTION_FILE_NAME,
+            OFFLINE_CONTRIBUTION_METADATA_FILE_NAME,
+            "",
+        )
+        .bright_cyan()
+    );
+
+    Ok(round_height)
+}
+
+/// Asks the contributor for the mnemonic of the keypair generated at the beginning of the contribution
+fn keypair_from_user_mnemonic() -> Result<KeyPair> {
+    let mnemonic = rpassword::prompt_password(
+        "Enter your 24 words mnemonic, in the format it was displayed (\"1. word 2. word ...\"): ".bright_yellow(),
+    )?;
+    let seed = io::seed_from_string(mnemonic.as_str())?;
+
+    Ok(KeyPair::try_from_seed(&seed)?)
+}
+
+/// Recovers the keypair of an air-gapped contribution: from the Ledger device if a derivation path is given, from
+/// the mnemonic otherwise
+fn air_gapped_keypair(derivation_path: Option<String>) -> Result<KeyPair> {
+    match derivation_path {
+        Some(derivation_path) => signer::use_ledger(&derivation_path),
+        None => keypair_from_user_mnemonic(),
+    }
+}
+
+/// Computes and signs the contribution to an exported challenge, without accessing the network
+fn compute_air_gapped_contribution(custom_seed: bool, derivation_path: Option<String>) -> Result<()> {
+    println!("{} Reading challenge", "[1/3]".bold().dimmed());
+    let metadata: AirGappedChallenge = serde_json::from_slice(&fs::read(OFFLINE_CHALLENGE_METADATA_FILE_NAME)?)?;
+    let challenge = fs::read(OFFLINE_CHALLENGE_FILE_NAME)?;
+    let challenge_hash = calculate_hash(&challenge);
+    if hex::encode(challenge_hash) != metadata.challenge_hash {
+        anyhow::bail!("The challenge file doesn't match the exported challenge hash");
+    }
+
+    let keypair = air_gapped_keypair(derivation_path)?;
+    if keypair.pubkey() != metadata.contrib_info.public_key {
+        anyhow::bail!("The key doesn't match the keypair of the exported challenge");
+    }
+
+    println!("{} Computing contribution", "[2/3]".bold().dimmed());
+    if custom_seed {
+        println!("{}", CUSTOM_SEED_MSG_YES.bright_cyan());
+    } else {
+        println!("{}", CUSTOM_SEED_MSG_NO.bright_cyan());
+    }
+    let mut contrib_info = metadata.contrib_info;
+    contrib_info.timestamps.start_computation = Utc::now();
+    fs::write(OFFLINE_CONTRIBUTION_FILE_NAME, challenge_hash.as_slice())?;
+    compute_contribution(custom_seed, &challenge, OFFLINE_CONTRIBUTION_FILE_NAME)?;
+    contrib_info.timestamps.end_computation = Utc::now();
+
+    println!("{} Signing contribution", "[3/3]".bold().dimmed());
+    let contribution = fs::read(OFFLINE_CONTRIBUTION_FILE_NAME)?;
+    let contribution_file_signature = sign_contribution(&keypair, &mut contrib_info, &challenge_hash, &contribution)?;
+    let contribution_metadata = AirGappedContribution {
+        contrib_info,
+        contribution_file_signature,
+    };
+    fs::write(
+        OFFLINE_CONTRIBUTION_METADATA_FILE_NAME,
+        &serde_json::to_vec_pretty(&contribution_metadata)?,
+    )?;
+
+    println!(
+        "{}",
+        format!(
+            "Copy the \"{}\" and \"{}\" files back to the machine running \"namada-ts contribute export\"",
+            OFFLINE_CONTRIBUTION_FILE_NAME, OFFLINE_CONTRIBUTION_METADATA_FILE_NAME
+        )
+        .bright_cyan()
+    );
+    Ok(())
+}
+
+/// Uploads the contribution computed on an air-gapped machine
+async fn upload_air_gapped_contribution(
+    client: &Client,
+    coordinator: &Url,
+    coordinator_public_key: &str,
+    derivation_path: Option<String>,
+) -> Result<u64> {
+    // Check that the coordinator is the one of the ceremony before sending it the contribution
+    requests::get_coordinator_identity(client, coordinator, coordinator_public_key).await?;
+
+    let challenge: AirGappedChallenge =
+        serde_json::from_slice(&async_fs::read(OFFLINE_CHALLENGE_METADATA_FILE_NAME).await?)?;
+    let contribution: AirGappedContribution =
+        serde_json::from_slice(&async_fs::read(OFFLINE_CONTRIBUTION_METADATA_FILE_NAME).await?)?;
+
+    let keypair = tokio::task::spawn_blocking(move || air_gapped_keypair(derivation_path)).await??;
+    if keypair.pubkey() != contribution.contrib_info.public_key {
+        anyhow::bail!("The key doesn't match the keypair of the contribution");
+    }
+
+    upload_contribution(
+        client,
+        coordinator,
+        &keypair,
+        contribution.contrib_info,
+        challenge.locked_chunk,
+        OFFLINE_CONTRIBUTION_FILE_NAME,
+        &contribution.contribution_file_signature,
+    )
+    .await
+}
+
 /// Waits in line until it's time to contribute
 #[inline(always)]
 async fn contribution_loop(
     client: Arc<Client>,
     coordinator: Arc<Url>,
     keypair: Arc<KeyPair>,
+    identity: CoordinatorIdentity,
     token: String,
+    invite_code: Option<String>,
+    priority_token: Option<String>,
+    email: Option<String>,
     mut contrib_info: ContributionInfo,
+    air_gapped: bool,
 ) {
     println!("{} Joining queue", "[3/11]".bold().dimmed());
 
-    let cohort = requests::post_join_queue(&client, &coordinator, &keypair, &token)
-        .await
-        .expect(&format!("{}", "Couldn't join the queue".red().bold()));
+    let cohort = requests::post_join_queue(
+        &client,
+        &coordinator,
+        &keypair,
+        &token,
+        invite_code.as_deref(),
+        priority_token.as_deref(),
+        email.as_deref(),
+    )
+    .await
+    .expect(&format!("{}", "Couldn't join the queue".red().bold()));
     contrib_info.timestamps.joined_queue = Utc::now();
     contrib_info.joined_cohort = cohort;
 
@@ -440,9 +676,38 @@ async fn contribution_loop(
     let coordinator_cnt = coordinator.clone();
     let keypair_cnt = keypair.clone();
 
+    // With a Ledger device the heartbeats are sent in a session, renewed before it expires, for the user not to confirm
+    // each of them on the device. They are signed if the coordinator doesn't open sessions
     let heartbeat_handle = tokio::task::spawn(async move {
+        let mut login = signer::uses_ledger();
+        let mut session: Option<Session> = None;
         loop {
-            if let Err(e) = requests::post_heartbeat(&client_cnt, &coordinator_cnt, &keypair_cnt).await {
+            let expiring = session
+                .as_ref()
+                .map_or(true, |s| s.expires_at <= Utc::now() + chrono::Duration::minutes(1));
+            if login && expiring {
+                println!(
+                    "{}",
+                    "Confirm the login on your Ledger device, to keep your session alive".bright_cyan()
+                );
+                session = match requests::post_login(&client_cnt, &coordinator_cnt, &keypair_cnt).await {
+                    Ok(session) => Some(session),
+                    Err(e) => {
+                        eprintln!(
+                            "{}",
+                            format!("{}: {}", "Login error".red().bold(), e.to_string().red().bold())
+                        );
+                        login = false;
+                        None
+                    }
+                };
+            }
+
+            let result = match session.as_ref() {
+                Some(session) => requests::post_heartbeat_in_session(&client_cnt, &coordinator_cnt, session).await,
+                None => requests::post_heartbeat(&client_cnt, &coordinator_cnt, &keypair_cnt).await,
+            };
+            if let Err(e) = result {
                 eprintln!(
                     "{}",
                     format!("{}: {}", "Heartbeat error".red().bold(), e.to_string().red().bold())
@@ -453,10 +718,11 @@ async fn contribution_loop(
     });
 
     let mut round_height = 0;
+    let mut exported = false;
     let mut status_count = 1;
     let queue_timer = Instant::now();
 
-    let init_queue_status = requests::get_contributor_queue_status(&client, &coordinator, &keypair)
+    let (init_queue_status, _) = requests::get_contributor_queue_status(&client, &coordinator, &keypair)
         .await
         .expect(&format!("{}", "Couldn't get the status of contributor".red().bold()));
     let mut init_queue_position = 0;
@@ -469,17 +735,23 @@ async fn contribution_loop(
 
     loop {
         // Check the contributor's position in the queue
-        let queue_status = requests::get_contributor_queue_status(&client, &coordinator, &keypair)
-            .await
-            .expect(&format!("{}", "Couldn't get the status of contributor".red().bold()));
+        let (queue_status, estimated_wait_seconds) =
+            requests::get_contributor_queue_status(&client, &coordinator, &keypair)
+                .await
+                .expect(&format!("{}", "Couldn't get the status of contributor".red().bold()));
 
         match queue_status {
             ContributorStatus::Queue(position, size) => {
+                // The coordinator estimates the wait from the duration of the recent contributions, once there are some
+                let expected_wait = match estimated_wait_seconds {
+                    Some(seconds) => seconds / 60,
+                    None => init_queue_position * 4,
+                };
                 let msg = format!(
                     "Queue position: {}\nQueue size: {}\nExpected waiting time: {} min\nMax waiting time: {} min\nElapsed time in queue: {} min",
                     position,
                     size,
-                    init_queue_position * 4,
+                    expected_wait,
                     init_queue_position * 20,
                     queue_timer.elapsed().as_secs() / 60
                 );
@@ -497,12 +769,29 @@ async fn contribution_loop(
                 );
                 status_count += 1;
             }
+            // The air-gapped contribution is uploaded by another command, meanwhile the heartbeat keeps the session alive
+            ContributorStatus::Round if air_gapped => {
+                if !exported {
+                    round_height = export_challenge(&client, &coordinator, &keypair, &identity, contrib_info.clone())
+                        .await
+                        .expect(&format!("{}", "Challenge export failed".red().bold()));
+                    exported = true;
+                }
+            }
             ContributorStatus::Round => {
-                round_height = contribute(&client, &coordinator, &keypair, contrib_info.clone(), &heartbeat_handle)
-                    .await
-                    .expect(&format!("{}", "Contribution failed".red().bold()));
+                round_height = contribute(
+                    &client,
+                    &coordinator,
+                    &keypair,
+                    &identity,
+                    contrib_info.clone(),
+                    &heartbeat_handle,
+                )
+                .await
+                .expect(&format!("{}", "Contribution failed".red().bold()));
             }
             ContributorStatus::Finished => {
+                heartbeat_handle.abort();
                 let content = fs::read(&format!("namada_contributor_info_round_{}.json", round_height))
                     .expect(&format!("{}", "Couldn't read the contributor info file".red().bold()));
                 let contrib_info: ContributionInfo = serde_json::from_slice(&content).unwrap();
@@ -516,6 +805,30 @@ async fn contribution_loop(
                                 );
                 println!("{}\n", ASCII_CONTRIBUTION_DONE.bright_yellow());
 
+                // Optional binding to a Namada address
+                if "y"
+                    == io::get_user_input(
+                        "Would you like to bind a Namada address to your contribution? [y/n]".bright_yellow(),
+                        Some(&Regex::new(r"^(?i)[yn]$").unwrap()),
+                    )
+                    .unwrap()
+                {
+                    loop {
+                        let address =
+                            io::get_user_input("Please enter your Namada address:".bright_yellow(), None).unwrap();
+                        let binding = NamadaAddressBinding::try_new(signer(), &keypair, address)
+                            .expect(&format!("{}", "Failed to sign the address binding".red().bold()));
+
+                        if binding.verify(keypair.pubkey()) {
+                            requests::post_namada_address(&client, &coordinator, &keypair, &(round_height, binding))
+                                .await
+                                .expect(&format!("{}", "Failed address binding upload".red().bold()));
+                            break;
+                        }
+                        println!("{}", "The provided address is not a valid Namada address".red());
+                    }
+                }
+
                 // Attestation
                 if "n"
                     == io::get_user_input(
@@ -526,6 +839,18 @@ async fn contribution_loop(
                 {
                     break;
                 } else {
+                    // Provide the signed canonical message so that the coordinator can automatically verify the attestation
+                    let attestation_message = contrib_info.attestation_message();
+                    let attestation_signature = signer()
+                        .sign(keypair.sigkey(), &attestation_message)
+                        .expect(&format!("{}", "Failed to sign the attestation message".red().bold()));
+                    println!(
+                        "{}\n\n{} {}\n",
+                        "To have your attestation verified, include the following signed message in it:".bright_cyan(),
+                        attestation_message,
+                        attestation_signature
+                    );
+
                     loop {
                         let attestation_url = io::get_user_input(
                             "Please enter a valid url for your attestation (starting with http or https):"
@@ -608,6 +933,62 @@ async fn verify_contributions(client: &Client, coordinator: &Url, keypair: &KeyP
     }
 }
 
+/// Verifies the locked contribution and sends the signed result, returns whether the contribution is valid.
+async fn verify_task(client: &Client, coordinator: &Url, keypair: &KeyPair, task: VerificationTask) -> Result<bool> {
+    let (challenge, challenge_hash) = requests::get_verification_file(client, coordinator, keypair, false).await?;
+    let (response, response_hash) = requests::get_verification_file(client, coordinator, keypair, true).await?;
+    if hex::encode(calculate_hash(&challenge)) != challenge_hash
+        || hex::encode(calculate_hash(&response)) != response_hash
+    {
+        return Err(anyhow::anyhow!("The downloaded files don't match their hashes"));
+    }
+
+    let verified = tokio::task::spawn_blocking(move || verify_response(&challenge, &response).is_ok()).await?;
+    let signature = Production.sign(
+        keypair.sigkey(),
+        &verification_result_message(&task, &response_hash, verified),
+    )?;
+    let result = VerificationResult {
+        task,
+        response_hash,
+        verified,
+        signature,
+    };
+    requests::post_verification_result(client, coordinator, keypair, &result).await?;
+
+    Ok(verified)
+}
+
+#[inline(always)]
+async fn run_verifier(client: &Client, coordinator: &Url, keypair: &KeyPair) {
+    loop {
+        let task = match requests::get_lock_verification_task(client, coordinator, keypair).await {
+            Ok(Some(task)) => task,
+            Ok(None) => {
+                time::sleep(UPDATE_TIME).await;
+                continue;
+            }
+            Err(e) => {
+                eprintln!("{}", e.to_string().red().bold());
+                process::exit(1);
+            }
+        };
+
+        println!(
+            "Verifying the contribution {} to chunk {} of round {}",
+            task.contribution_id, task.chunk_id, task.round_height
+        );
+        match verify_task(client, coordinator, keypair, task).await {
+            Ok(true) => println!("{}", "The contribution is valid".green().bold()),
+            Ok(false) => println!("{}", "The contribution is not valid".red().bold()),
+            Err(e) => {
+                eprintln!("{}", e.to_string().red().bold());
+                time::sleep(UPDATE_TIME).await;
+            }
+        }
+    }
+}
+
 #[cfg(debug_assertions)]
 #[inline(always)]
 async fn update_coordinator(client: &Client, coordinator: &Url, keypair: &KeyPair) {
@@ -632,11 +1013,21 @@ async fn update_cohorts(client: &Client, coordinator: &Url, keypair: &KeyPair) {
 enum Branch {
     AnotherMachine,
     Default(bool),
+    Export,
 }
 
 /// Performs the entire contribution cycle
 #[inline(always)]
-async fn contribution_prelude(url: CoordinatorUrl, token: String, branch: Branch) {
+async fn contribution_prelude(
+    url: CoordinatorUrl,
+    coordinator_public_key: String,
+    token: String,
+    invite_code: Option<String>,
+    priority_token: Option<String>,
+    email: Option<String>,
+    branch: Branch,
+    derivation_path: Option<String>,
+) {
     // Check that the passed-in coordinator url is correct
     let client = Client::new();
     requests::ping_coordinator(&client, &url.coordinator)
@@ -647,6 +1038,15 @@ async fn contribution_prelude(url: CoordinatorUrl, token: String, branch: Branch
                 .red()
                 .bold()
         ));
+    // Check that the coordinator is the one of the ceremony before trusting its challenges
+    let identity = requests::get_coordinator_identity(&client, &url.coordinator, &coordinator_public_key)
+        .await
+        .expect(&format!(
+            "{}",
+            "ERROR: the Coordinator is not signing with the public key you provided"
+                .red()
+                .bold()
+        ));
 
     let decoded_bytes = bs58::decode(token.clone()).into_vec();
     if let Ok(token_bytes) = decoded_bytes {
@@ -687,6 +1087,11 @@ async fn contribution_prelude(url: CoordinatorUrl, token: String, branch: Branch
             "DISCLAIMER".bright_red().underline().bold(),
             "The \"--custom-seed\" flag is active.\nThis feature is designed for advanced users that want to give a custom random seed for the ChaCha RNG.\n".bright_red()
         ),
+        Branch::Export => println!(
+            "{}\n{}",
+            "DISCLAIMER".bright_red().underline().bold(),
+            "The air-gapped contribution is active.\nThis feature is designed for advanced users that want to compute the contribution on a machine without network access.\n".bright_red()
+        ),
         _ => ()
     }
 
@@ -699,16 +1104,33 @@ async fn contribution_prelude(url: CoordinatorUrl, token: String, branch: Branch
     println!("{} Generating keypair", "[2/11]".bold().dimmed());
 
     match branch {
-        Branch::AnotherMachine => contrib_info.is_another_machine = true,
+        Branch::AnotherMachine | Branch::Export => contrib_info.is_another_machine = true,
         Branch::Default(custom_seed) if custom_seed => contrib_info.is_own_seed_of_randomness = true,
         _ => (),
     }
 
-    io::get_user_input("Press enter to generate a keypair".bright_yellow(), None).unwrap();
-    let keypair = tokio::task::spawn_blocking(move || io::generate_keypair(KeyPairUser::Contributor))
-        .await
-        .unwrap()
-        .expect(&format!("{}", "Error while generating the keypair".red().bold()));
+    let keypair = match derivation_path {
+        Some(derivation_path) => {
+            println!(
+                "{}",
+                "Using the key of your Ledger device, confirm every signature on the device when asked".bright_cyan()
+            );
+            tokio::task::spawn_blocking(move || signer::use_ledger(&derivation_path))
+                .await
+                .unwrap()
+                .expect(&format!(
+                    "{}",
+                    "Error while connecting to the Ledger device".red().bold()
+                ))
+        }
+        None => {
+            io::get_user_input("Press enter to generate a keypair".bright_yellow(), None).unwrap();
+            tokio::task::spawn_blocking(move || io::generate_keypair(KeyPairUser::Contributor))
+                .await
+                .unwrap()
+                .expect(&format!("{}", "Error while generating the keypair".red().bold()))
+        }
+    };
 
     contrib_info.timestamps.start_contribution = Utc::now();
     contrib_info.public_key = keypair.pubkey().to_string();
@@ -717,8 +1139,13 @@ async fn contribution_prelude(url: CoordinatorUrl, token: String, branch: Branch
         Arc::new(client),
         Arc::new(url.coordinator),
         Arc::new(keypair),
+        identity,
         token,
+        invite_code,
+        priority_token,
+        email,
         contrib_info,
+        matches!(branch, Branch::Export),
     )
     .await;
 }
@@ -731,13 +1158,103 @@ async fn main() {
     match opt {
         CeremonyOpt::Contribute(branch) => {
             match branch {
-                phase2_cli::Branches::AnotherMachine { request } => {
-                    contribution_prelude(request.url, request.token, Branch::AnotherMachine).await
+                phase2_cli::Branches::AnotherMachine {
+                    request,
+                    coordinator_public_key,
+                    invite_code,
+                    priority_token,
+                    email,
+                    ledger,
+                } => {
+                    contribution_prelude(
+                        request.url,
+                        coordinator_public_key,
+                        request.token,
+                        invite_code,
+                        priority_token,
+                        email,
+                        Branch::AnotherMachine,
+                        ledger.derivation_path(),
+                    )
+                    .await
                 }
-                phase2_cli::Branches::Default { request, custom_seed } => {
-                    contribution_prelude(request.url, request.token, Branch::Default(custom_seed)).await
+                phase2_cli::Branches::Default {
+                    request,
+                    coordinator_public_key,
+                    invite_code,
+                    priority_token,
+                    email,
+                    custom_seed,
+                    threads,
+                    ledger,
+                } => {
+                    set_contribution_threads(threads);
+                    contribution_prelude(
+                        request.url,
+                        coordinator_public_key,
+                        request.token,
+                        invite_code,
+                        priority_token,
+                        email,
+                        Branch::Default(custom_seed),
+                        ledger.derivation_path(),
+                    )
+                    .await
+                }
+                phase2_cli::Branches::Export {
+                    request,
+                    coordinator_public_key,
+                    invite_code,
+                    priority_token,
+                    email,
+                    ledger,
+                } => {
+                    contribution_prelude(
+                        request.url,
+                        coordinator_public_key,
+                        request.token,
+                        invite_code,
+                        priority_token,
+                        email,
+                        Branch::Export,
+                        ledger.derivation_path(),
+                    )
+                    .await
                 }
-                phase2_cli::Branches::Offline { custom_seed } => {
+                phase2_cli::Branches::AirGapped {
+                    custom_seed,
+                    threads,
+                    ledger,
+                } => {
+                    set_contribution_threads(threads);
+                    let derivation_path = ledger.derivation_path();
+                    tokio::task::spawn_blocking(move || compute_air_gapped_contribution(custom_seed, derivation_path))
+                        .await
+                        .unwrap()
+                        .expect(&format!("{}", "Error in computing the contribution".red().bold()));
+                }
+                phase2_cli::Branches::Upload {
+                    url,
+                    coordinator_public_key,
+                    ledger,
+                } => {
+                    let round_height = upload_air_gapped_contribution(
+                        &Client::new(),
+                        &url.coordinator,
+                        &coordinator_public_key,
+                        ledger.derivation_path(),
+                    )
+                    .await
+                    .expect(&format!("{}", "Contribution upload failed".red().bold()));
+                    println!(
+                        "{}",
+                        format!("Uploaded your contribution to round {}", round_height)
+                            .green()
+                            .bold()
+                    );
+                }
+                phase2_cli::Branches::Offline { custom_seed, threads } => {
+                    set_contribution_threads(threads);
                     if custom_seed {
                         println!(
                     "{}\n{}",
@@ -842,6 +1359,15 @@ async fn main() {
             let secret = state.token.as_str();
             get_coordinator_state(&state.url.coordinator, secret).await;
         }
+        CeremonyOpt::RunVerifier(url) => {
+            let keypair = tokio::task::spawn_blocking(|| io::keypair_from_mnemonic())
+                .await
+                .unwrap()
+                .expect(&format!("{}", "Error while generating the keypair".red().bold()));
+
+            let client = Client::new();
+            run_verifier(&client, &url.coordinator, &keypair).await;
+        }
         CeremonyOpt::UpdateCohorts(url) => {
             let keypair = tokio::task::spawn_blocking(|| io::keypair_from_mnemonic())
                 .await
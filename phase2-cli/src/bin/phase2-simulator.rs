@@ -0,0 +1,89 @@
+use phase2_cli::{
+    requests,
+    simulation::{self, SimulationConfig},
+    CoordinatorUrl,
+};
+use phase2_coordinator::io;
+
+use owo_colors::OwoColorize;
+use reqwest::Client;
+use structopt::StructOpt;
+
+use std::{path::PathBuf, process, sync::Arc, time::Duration};
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "phase2-simulator",
+    about = "Rehearse a ceremony against a running coordinator with synthetic contributors"
+)]
+struct SimulatorOpt {
+    #[structopt(flatten)]
+    url: CoordinatorUrl,
+    #[structopt(long, help = "The path to the json file with the tokens of the current cohort", parse(from_os_str))]
+    tokens: PathBuf,
+    #[structopt(long, default_value = "3", help = "The number of contributors waiting in the queue at the same time")]
+    contributors: usize,
+    #[structopt(long, default_value = "10", help = "The number of rounds to contribute to")]
+    rounds: u64,
+    #[structopt(
+        long,
+        help = "Trigger the updates and verifications of the coordinator, with the keypair from the mnemonic file of the coordinator"
+    )]
+    drive_coordinator: bool,
+    #[structopt(long, default_value = "5", help = "The interval, in seconds, between two polls of the queue status")]
+    poll_interval: u64,
+    #[structopt(
+        long,
+        default_value = "600",
+        help = "The time, in seconds, without any completed contribution after which the ceremony is considered stalled"
+    )]
+    stall_timeout: u64,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+    let opt = SimulatorOpt::from_args();
+
+    let tokens = std::fs::read(&opt.tokens)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| simulation::parse_tokens(&content))
+        .expect(&format!("{}", "Couldn't read the tokens file".red().bold()));
+
+    let client = Arc::new(Client::new());
+    let coordinator = Arc::new(opt.url.coordinator);
+    requests::ping_coordinator(&client, &coordinator).await.expect(&format!(
+        "{}",
+        "ERROR: could not contact the Coordinator, please check the url you provided"
+            .red()
+            .bold()
+    ));
+
+    let coordinator_keypair = if opt.drive_coordinator {
+        Some(
+            tokio::task::spawn_blocking(|| io::keypair_from_mnemonic())
+                .await
+                .unwrap()
+                .expect(&format!("{}", "Error while generating the keypair".red().bold())),
+        )
+    } else {
+        None
+    };
+
+    let config = SimulationConfig {
+        contributors: opt.contributors,
+        rounds: opt.rounds,
+        tokens,
+        coordinator_keypair,
+        poll_interval: Duration::from_secs(opt.poll_interval),
+        stall_timeout: Duration::from_secs(opt.stall_timeout),
+    };
+
+    match simulation::simulate(client, coordinator, config).await {
+        Ok(report) => println!("{}\n{}", "Simulation completed".green().bold(), report),
+        Err(e) => {
+            eprintln!("{}", format!("Simulation failed: {}", e).red().bold());
+            process::exit(1);
+        }
+    }
+}
@@ -6,10 +6,13 @@ use std::path::PathBuf;
 pub mod ascii_logo;
 pub mod keys;
 pub mod requests;
+pub mod signer;
+pub mod simulation;
 
 use phase2_coordinator::{
-    objects::round::LockedLocators,
-    rest_utils::{ContributorStatus, PostChunkRequest},
+    authentication::DEFAULT_DERIVATION_PATH,
+    objects::{ContributionFileSignature, ContributionInfo},
+    rest_utils::{ContributorStatus, LockedChunk, PostChunkRequest},
 };
 
 use reqwest::Url;
@@ -37,6 +40,33 @@ pub struct RequestWithToken {
     pub token: String,
 }
 
+/// Signs with the key held by a Ledger device in place of a generated keypair
+#[derive(Debug, StructOpt)]
+pub struct LedgerKey {
+    #[structopt(
+        long,
+        help = "Sign with the key held by your Ledger device instead of generating a keypair. Every signature, including the ones of the requests to the Coordinator, has to be confirmed on the device"
+    )]
+    pub ledger: bool,
+    #[structopt(
+        long,
+        default_value = DEFAULT_DERIVATION_PATH,
+        help = "The derivation path of the key on the Ledger device"
+    )]
+    pub derivation_path: String,
+}
+
+impl LedgerKey {
+    /// Returns the derivation path of the key, if the Ledger device is used
+    pub fn derivation_path(self) -> Option<String> {
+        if self.ledger {
+            Some(self.derivation_path)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, StructOpt)]
 pub struct MnemonicPath {
     #[structopt(help = "The path to the mnemonic file", required = true, parse(try_from_str))]
@@ -64,16 +94,100 @@ pub enum Branches {
     AnotherMachine {
         #[structopt(flatten)]
         request: RequestWithToken,
+        #[structopt(
+            long,
+            env = "NAMADA_COORDINATOR_PUBLIC_KEY",
+            help = "The public key of the coordinator published by the organizers of the ceremony"
+        )]
+        coordinator_public_key: String,
+        #[structopt(long, help = "The one-time invite code, required during the invite-only phases of the ceremony")]
+        invite_code: Option<String>,
+        #[structopt(long, help = "The priority token granting a reserved slot in the queue during its validity window")]
+        priority_token: Option<String>,
+        #[structopt(long, help = "The email address notified when your turn is getting close")]
+        email: Option<String>,
+        #[structopt(flatten)]
+        ledger: LedgerKey,
     },
     #[structopt(about = "The default contribution path, executes both communication and computation on this machine")]
     Default {
         #[structopt(flatten)]
         request: RequestWithToken,
+        #[structopt(
+            long,
+            env = "NAMADA_COORDINATOR_PUBLIC_KEY",
+            help = "The public key of the coordinator published by the organizers of the ceremony"
+        )]
+        coordinator_public_key: String,
+        #[structopt(long, help = "The one-time invite code, required during the invite-only phases of the ceremony")]
+        invite_code: Option<String>,
+        #[structopt(long, help = "The priority token granting a reserved slot in the queue during its validity window")]
+        priority_token: Option<String>,
+        #[structopt(long, help = "The email address notified when your turn is getting close")]
+        email: Option<String>,
         #[structopt(
             long,
             help = "Give a custom random seed (32 bytes / 64 characters in hexadecimal) for the ChaCha RNG"
         )]
         custom_seed: bool,
+        #[structopt(
+            long,
+            env = "NAMADA_CONTRIBUTION_THREADS",
+            help = "Bound the number of threads used to compute the contribution, all the available cores by default"
+        )]
+        threads: Option<usize>,
+        #[structopt(flatten)]
+        ledger: LedgerKey,
+    },
+    #[structopt(
+        about = "Exports the challenge to compute the contribution on an air-gapped machine with \"namada-ts contribute air-gapped\", then keeps the session alive until \"namada-ts contribute upload\" has uploaded it"
+    )]
+    Export {
+        #[structopt(flatten)]
+        request: RequestWithToken,
+        #[structopt(
+            long,
+            env = "NAMADA_COORDINATOR_PUBLIC_KEY",
+            help = "The public key of the coordinator published by the organizers of the ceremony"
+        )]
+        coordinator_public_key: String,
+        #[structopt(long, help = "The one-time invite code, required during the invite-only phases of the ceremony")]
+        invite_code: Option<String>,
+        #[structopt(long, help = "The priority token granting a reserved slot in the queue during its validity window")]
+        priority_token: Option<String>,
+        #[structopt(long, help = "The email address notified when your turn is getting close")]
+        email: Option<String>,
+        #[structopt(flatten)]
+        ledger: LedgerKey,
+    },
+    #[structopt(about = "Computes and signs the contribution to an exported challenge, without any network access")]
+    AirGapped {
+        #[structopt(
+            long,
+            help = "Give a custom random seed (32 bytes / 64 characters in hexadecimal) for the ChaCha RNG"
+        )]
+        custom_seed: bool,
+        #[structopt(
+            long,
+            env = "NAMADA_CONTRIBUTION_THREADS",
+            help = "Bound the number of threads used to compute the contribution, all the available cores by default"
+        )]
+        threads: Option<usize>,
+        #[structopt(flatten)]
+        ledger: LedgerKey,
+    },
+    #[structopt(about = "Uploads the contribution computed by \"namada-ts contribute air-gapped\"")]
+    Upload {
+        #[structopt(flatten)]
+        url: CoordinatorUrl,
+        #[structopt(
+            long,
+            env = "NAMADA_COORDINATOR_PUBLIC_KEY",
+            help = "The public key of the coordinator published by the organizers of the ceremony"
+        )]
+        coordinator_public_key: String,
+        #[structopt(flatten)]
+        ledger: LedgerKey,
     },
     #[structopt(
         about = "Performs only the computation of the contribution, to be used in conjunction with \"namada-ts contribute another-machine\" on a separate machine"
@@ -84,9 +198,32 @@ pub enum Branches {
             help = "Give a custom random seed (32 bytes / 64 characters in hexadecimal) for the ChaCha RNG"
         )]
         custom_seed: bool,
+        #[structopt(
+            long,
+            env = "NAMADA_CONTRIBUTION_THREADS",
+            help = "Bound the number of threads used to compute the contribution, all the available cores by default"
+        )]
+        threads: Option<usize>,
     },
 }
 
+/// The metadata of a challenge exported by "namada-ts contribute export", with everything needed to compute the
+/// contribution on an air-gapped machine and to upload it afterwards
+#[derive(Deserialize, Serialize)]
+pub struct AirGappedChallenge {
+    pub locked_chunk: LockedChunk,
+    /// Hex encoded hash of the challenge file
+    pub challenge_hash: String,
+    pub contrib_info: ContributionInfo,
+}
+
+/// The metadata of a contribution computed and signed by "namada-ts contribute air-gapped"
+#[derive(Deserialize, Serialize)]
+pub struct AirGappedContribution {
+    pub contrib_info: ContributionInfo,
+    pub contribution_file_signature: ContributionFileSignature,
+}
+
 pub enum TokenCohort {
     Finished,
     InProgress,
@@ -143,6 +280,8 @@ pub enum CeremonyOpt {
     #[cfg(debug_assertions)]
     #[structopt(about = "Verify the pending contributions")]
     VerifyContributions(CoordinatorUrl),
+    #[structopt(about = "Verify the contributions as an external verifier")]
+    RunVerifier(CoordinatorUrl),
     #[structopt(about = "Update the cohorts' tokens")]
     UpdateCohorts(CoordinatorUrl),
     #[cfg(debug_assertions)]
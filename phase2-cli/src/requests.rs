@@ -3,11 +3,15 @@
 use bytes::Bytes;
 use futures_util::Stream;
 use phase2_coordinator::{
-    authentication::{KeyPair, Production, Signature},
-    objects::ContributionInfo,
+    authentication::{ContributionReceipt, KeyPair, Production, Signature},
+    object_storage::azure::BLOB_TYPE_HEADER,
+    objects::{ContributionInfo, NamadaAddressBinding},
     rest_utils::{
-        RequestContent, SignatureHeaders, ACCESS_SECRET_HEADER, BODY_DIGEST_HEADER, CONTENT_LENGTH_HEADER,
-        PUBKEY_HEADER, SIGNATURE_HEADER,
+        coordinator_identity_message, login_message, versioned_endpoint, CoordinatorIdentity, LoginRequest,
+        RequestContent, Session, SignatureHeaders, VerificationResult, VerificationTask, ACCESS_SECRET_HEADER,
+        API_VERSION, API_VERSION_HEADER, BODY_DIGEST_HEADER, CHALLENGE_HASH_HEADER, CONTENT_LENGTH_HEADER,
+        EMAIL_HEADER, ESTIMATED_WAIT_HEADER, INVITE_CODE_HEADER, PRIORITY_TOKEN_HEADER, PUBKEY_HEADER,
+        SESSION_TOKEN_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
     },
     ContributionFileSignature,
 };
@@ -17,11 +21,14 @@ use reqwest::{
 };
 use serde::Serialize;
 use sha2::{Digest, Sha256};
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    time::Duration,
+};
 use thiserror::Error;
-use tracing::debug;
+use tracing::{debug, warn};
 
-use crate::{ContributorStatus, LockedLocators, PostChunkRequest};
+use crate::{signer::signer, ContributorStatus, LockedChunk, PostChunkRequest};
 
 /// Error returned from a request.
 #[derive(Debug, Error)]
@@ -42,9 +49,15 @@ pub enum RequestError {
     SigningError,
     #[error("Server-side error: {0}")]
     Server(String),
+    #[error("The coordinator is not signing with the expected public key")]
+    UntrustedCoordinator,
 }
 
 type Result<T> = std::result::Result<T, RequestError>;
+
+/// Number of times a request is resubmitted when the coordinator can't be reached, before giving up
+const MAX_RETRIES: u32 = 5;
+
 /// Wrapper type to convert [`SignatureHeaders`] into [`HeaderMap`]
 struct HeaderWrap(HeaderMap);
 
@@ -60,6 +73,7 @@ impl TryFrom<SignatureHeaders<'_>> for HeaderWrap {
     fn try_from(value: SignatureHeaders) -> std::result::Result<Self, Self::Error> {
         let mut result = HeaderMap::new();
         result.insert(PUBKEY_HEADER, HeaderValue::from_str(value.pubkey)?);
+        result.insert(TIMESTAMP_HEADER, value.timestamp.into());
 
         if let Some(sig) = value.signature {
             result.insert(SIGNATURE_HEADER, HeaderValue::from_str(&sig)?);
@@ -75,22 +89,15 @@ impl TryFrom<SignatureHeaders<'_>> for HeaderWrap {
     }
 }
 
-trait Sign {
-    fn try_sign(&mut self, sigkey: &str) -> Result<()>;
-}
-
-impl Sign for SignatureHeaders<'_> {
-    fn try_sign(&mut self, sigkey: &str) -> Result<()> {
-        let msg = self.to_string();
-        self.signature = Some(
-            Production
-                .sign(sigkey, &msg)
-                .map_err(|_| RequestError::SigningError)?
-                .into(),
-        );
+/// Signs the message with the signer of the contributor, on a blocking thread since a Ledger device waits for the
+/// confirmation of the user.
+async fn sign(sigkey: &str, message: String) -> Result<String> {
+    let sigkey = sigkey.to_owned();
 
-        Ok(())
-    }
+    tokio::task::spawn_blocking(move || signer().sign(&sigkey, &message))
+        .await
+        .map_err(|_| RequestError::SigningError)?
+        .map_err(|_| RequestError::SigningError)
 }
 
 enum Request<'a, T: Serialize> {
@@ -111,9 +118,14 @@ where
     T: Serialize,
 {
     let address = coordinator_address
-        .join(endpoint)
+        .join(&versioned_endpoint(endpoint))
         .map_err(|_| RequestError::AddressParseError)?;
-    let mut content: Option<RequestContent> = None;
+    let path = match address.query() {
+        Some(query) => format!("{}?{}", address.path(), query),
+        None => address.path().to_string(),
+    };
+    let is_get = matches!(request, Request::Get);
+    let mut content = None;
 
     let mut req = match request {
         Request::Get => client.get(address),
@@ -125,7 +137,7 @@ where
                 hasher.update(&json_body);
                 let digest = hasher.finalize();
 
-                content = Some(RequestContent::new(json_body.len(), digest));
+                content = Some((json_body.len(), digest));
                 client
                     .post(address)
                     .body(json_body)
@@ -135,32 +147,67 @@ where
         },
     };
 
-    // Generate signatures headers if required
-    if let Some(kp) = keypair {
-        let mut headers = SignatureHeaders::new(kp.pubkey(), content, None);
-        headers.try_sign(kp.sigkey())?;
-        let header_map: HeaderWrap = headers.try_into()?;
-        req = req.headers(header_map.into());
-    }
+    req = req.header(API_VERSION_HEADER, API_VERSION);
 
     // Add custom headers if required
     if let Some(header_map) = custom_headers {
         req = req.headers(header_map);
     }
 
+    let mut retries = 0;
+
     loop {
-        let response = req.try_clone().expect("Expected request not stream").send().await?;
+        let mut attempt = req.try_clone().expect("Expected request not stream");
+
+        // Generate signatures headers if required, at every attempt since the coordinator rejects replayed signatures
+        if let Some(kp) = keypair {
+            let method = if is_get { "GET" } else { "POST" };
+            let timestamp = chrono::Utc::now().timestamp();
+            let content = content.map(|(len, digest)| RequestContent::new(len, digest));
+            let mut headers =
+                SignatureHeaders::new(method, path.as_str().into(), timestamp, kp.pubkey(), content, None);
+            headers.signature = Some(sign(kp.sigkey(), headers.to_string()).await?.into());
+            let header_map: HeaderWrap = headers.try_into()?;
+            attempt = attempt.headers(header_map.into());
+        }
+
+        let result = match attempt.send().await {
+            Ok(response) => decapsulate_response(response).await,
+            Err(e) => Err(e.into()),
+        };
 
-        match decapsulate_response(response).await {
+        match result {
             Ok(response) => return Ok(response),
-            Err(e) => match e {
-                RequestError::Proxy(_) => debug!("CDN timeout expired, resubmitting the request..."),
-                _ => return Err(e),
-            },
+            Err(RequestError::Proxy(_)) => debug!("CDN timeout expired, resubmitting the request..."),
+            // A request that couldn't reach the coordinator has no effect, while only GET requests can be safely
+            // resubmitted after a timeout or a failure of the coordinator
+            Err(e) if retries < MAX_RETRIES && is_transient(&e, is_get) => {
+                retries += 1;
+                let backoff = Duration::from_secs(2u64.pow(retries));
+                warn!(
+                    "Request to {} failed: {}. Retrying in {}s ({}/{})...",
+                    endpoint,
+                    e,
+                    backoff.as_secs(),
+                    retries,
+                    MAX_RETRIES
+                );
+                tokio::time::sleep(backoff).await;
+            }
+            Err(e) => return Err(e),
         }
     }
 }
 
+/// Returns `true` if the request failed because of a temporary problem of the network or of the coordinator.
+fn is_transient(error: &RequestError, is_get: bool) -> bool {
+    match error {
+        RequestError::Reqwest(e) => e.is_connect() || (is_get && e.is_timeout()),
+        RequestError::Server(_) => is_get,
+        _ => false,
+    }
+}
+
 /// Decapsulate the response and, if error, maps [`Response`] error to [`RequestError`].
 async fn decapsulate_response(response: Response) -> Result<Response> {
     let status = response.status();
@@ -184,13 +231,28 @@ pub async fn post_join_queue(
     coordinator_address: &Url,
     keypair: &KeyPair,
     token: &String,
+    invite_code: Option<&str>,
+    priority_token: Option<&str>,
+    email: Option<&str>,
 ) -> Result<u64> {
+    let mut header = HeaderMap::new();
+    if let Some(code) = invite_code {
+        header.insert(INVITE_CODE_HEADER, HeaderValue::from_str(code)?);
+    }
+    if let Some(priority_token) = priority_token {
+        header.insert(PRIORITY_TOKEN_HEADER, HeaderValue::from_str(priority_token)?);
+    }
+    if let Some(email) = email {
+        header.insert(EMAIL_HEADER, HeaderValue::from_str(email)?);
+    }
+    let header = if header.is_empty() { None } else { Some(header) };
+
     let response = submit_request::<String>(
         client,
         coordinator_address,
         "contributor/join_queue",
         Some(keypair),
-        None,
+        header,
         Request::Post(Some(token)),
     )
     .await?;
@@ -199,7 +261,7 @@ pub async fn post_join_queue(
 }
 
 /// Send a request to the [Coordinator](`phase2-coordinator::Coordinator`) to lock the next [Chunk](`phase2-coordinator::objects::Chunk`).
-pub async fn get_lock_chunk(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<LockedLocators> {
+pub async fn get_lock_chunk(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<LockedChunk> {
     let response = submit_request::<String>(
         client,
         coordinator_address,
@@ -210,7 +272,22 @@ pub async fn get_lock_chunk(client: &Client, coordinator_address: &Url, keypair:
     )
     .await?;
 
-    Ok(response.json::<LockedLocators>().await?)
+    Ok(response.json::<LockedChunk>().await?)
+}
+
+/// Send a request to the [Coordinator](`phase2-coordinator::Coordinator`) to wait for the turn of the contributor, then lock the next [Chunk](`phase2-coordinator::objects::Chunk`).
+pub async fn get_await_turn(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<LockedChunk> {
+    let response = submit_request::<String>(
+        client,
+        coordinator_address,
+        "contributor/await_turn",
+        Some(keypair),
+        None,
+        Request::Get,
+    )
+    .await?;
+
+    Ok(response.json::<LockedChunk>().await?)
 }
 
 /// Send a request to the [Coordinator](`phase2-coordinator::Coordinator`) to get the next challenge's key.
@@ -265,7 +342,7 @@ pub async fn get_contribution_url(
     Ok(response.json().await?)
 }
 
-/// Upload a gneric object to S3.
+/// Upload a gneric object to the object storage.
 async fn upload_object(req: RequestBuilder) -> Result<()> {
     let response = req.send().await?;
     decapsulate_response(response).await?;
@@ -273,7 +350,7 @@ async fn upload_object(req: RequestBuilder) -> Result<()> {
     Ok(())
 }
 
-/// Upload a contribution and its signature to Amazon S3.
+/// Upload a contribution and its signature to the object storage of the ceremony, through the presigned urls.
 pub async fn upload_chunk<S>(
     client: &Client,
     contrib_url: &str,
@@ -286,15 +363,18 @@ where
     S: Stream<Item = std::result::Result<Bytes, std::io::Error>> + std::marker::Send + std::marker::Sync + 'static,
 {
     let json_sig = serde_json::to_vec(&contribution_signature)?;
+    // Azure Blob Storage requires the type of the uploaded blobs, the other services ignore the header
     let contrib_req = client
         .put(contrib_url)
         .body(reqwest::Body::wrap_stream(contribution_stream))
         .header(CONTENT_TYPE, "application/octet-stream")
-        .header(CONTENT_LENGTH_HEADER, contribution_len);
+        .header(CONTENT_LENGTH_HEADER, contribution_len)
+        .header(BLOB_TYPE_HEADER, "BlockBlob");
     let contrib_sig_req = client
         .put(contrib_sig_url)
         .body(json_sig)
-        .header(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+        .header(BLOB_TYPE_HEADER, "BlockBlob");
 
     tokio::try_join!(upload_object(contrib_req), upload_object(contrib_sig_req))?;
 
@@ -302,13 +382,14 @@ where
 }
 
 /// Send a request to notify the [Coordinator](`phase2-coordinator::Coordinator`) of an uploaded contribution.
+/// Returns the receipt of the contribution signed by the coordinator.
 pub async fn post_contribute_chunk(
     client: &Client,
     coordinator_address: &Url,
     keypair: &KeyPair,
     request_body: &PostChunkRequest,
-) -> Result<()> {
-    submit_request(
+) -> Result<ContributionReceipt> {
+    let response = submit_request(
         client,
         coordinator_address,
         "contributor/contribute_chunk",
@@ -318,7 +399,7 @@ pub async fn post_contribute_chunk(
     )
     .await?;
 
-    Ok(())
+    Ok(response.json::<ContributionReceipt>().await?)
 }
 
 /// Let the [Coordinator](`phase2-coordinator::Coordinator`) know that the contributor is still alive.
@@ -336,6 +417,58 @@ pub async fn post_heartbeat(client: &Client, coordinator_address: &Url, keypair:
     Ok(())
 }
 
+/// Open a session by signing a nonce issued by the [Coordinator](`phase2-coordinator::Coordinator`). Until it
+/// expires, the token of the session authenticates the requests of the contributor in place of their signature.
+pub async fn post_login(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<Session> {
+    let nonce: String = submit_request::<()>(
+        client,
+        coordinator_address,
+        "contributor/login",
+        Some(keypair),
+        None,
+        Request::Get,
+    )
+    .await?
+    .json()
+    .await?;
+
+    let request = LoginRequest {
+        pubkey: keypair.pubkey().to_owned(),
+        signature: sign(keypair.sigkey(), login_message(keypair.pubkey(), &nonce)).await?,
+        nonce,
+    };
+    let response = submit_request(
+        client,
+        coordinator_address,
+        "contributor/login",
+        None,
+        None,
+        Request::Post(Some(&request)),
+    )
+    .await?;
+
+    Ok(response.json::<Session>().await?)
+}
+
+/// Let the [Coordinator](`phase2-coordinator::Coordinator`) know that the contributor is still alive, authenticating
+/// the request with the token of a session opened by [`post_login`].
+pub async fn post_heartbeat_in_session(client: &Client, coordinator_address: &Url, session: &Session) -> Result<()> {
+    let mut header = HeaderMap::new();
+    header.insert(SESSION_TOKEN_HEADER, HeaderValue::from_str(&session.token)?);
+
+    submit_request::<String>(
+        client,
+        coordinator_address,
+        "contributor/heartbeat",
+        None,
+        Some(header),
+        Request::Post(None),
+    )
+    .await?;
+
+    Ok(())
+}
+
 /// Request an update of the [Coordinator](`phase2-coordinator::Coordinator`) state.
 #[cfg(debug_assertions)]
 pub async fn get_update(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<()> {
@@ -375,12 +508,77 @@ pub async fn get_verify_chunks(client: &Client, coordinator_address: &Url, keypa
     Ok(())
 }
 
-/// Get Contributor queue status.
+/// Lock the next contribution to verify as an external verifier, if any.
+pub async fn get_lock_verification_task(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &KeyPair,
+) -> Result<Option<VerificationTask>> {
+    let response = submit_request::<()>(
+        client,
+        coordinator_address,
+        "verifier/lock_task",
+        Some(keypair),
+        None,
+        Request::Get,
+    )
+    .await?;
+
+    Ok(response.json::<Option<VerificationTask>>().await?)
+}
+
+/// Download the challenge, or the response if `response` is set, of the contribution locked by the external verifier,
+/// together with its hash.
+pub async fn get_verification_file(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &KeyPair,
+    response: bool,
+) -> Result<(Bytes, String)> {
+    let endpoint = if response {
+        "verifier/response"
+    } else {
+        "verifier/challenge"
+    };
+    let response =
+        submit_request::<()>(client, coordinator_address, endpoint, Some(keypair), None, Request::Get).await?;
+    let hash = response
+        .headers()
+        .get(CHALLENGE_HASH_HEADER)
+        .and_then(|hash| hash.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    Ok((response.bytes().await?, hash))
+}
+
+/// Send the signed result of the verification of the locked contribution.
+pub async fn post_verification_result(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &KeyPair,
+    request_body: &VerificationResult,
+) -> Result<()> {
+    submit_request::<VerificationResult>(
+        client,
+        coordinator_address,
+        "verifier/verification_result",
+        Some(keypair),
+        None,
+        Request::Post(Some(request_body)),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Get Contributor queue status, with the estimated wait in seconds before the turn of the contributor if the
+/// [Coordinator](`phase2-coordinator::Coordinator`) can tell it yet.
 pub async fn get_contributor_queue_status(
     client: &Client,
     coordinator_address: &Url,
     keypair: &KeyPair,
-) -> Result<ContributorStatus> {
+) -> Result<(ContributorStatus, Option<u64>)> {
     let response = submit_request::<()>(
         client,
         coordinator_address,
@@ -390,8 +588,12 @@ pub async fn get_contributor_queue_status(
         Request::Get,
     )
     .await?;
+    let estimated_wait = response
+        .headers()
+        .get(ESTIMATED_WAIT_HEADER)
+        .and_then(|value| value.to_str().ok()?.parse().ok());
 
-    Ok(response.json::<ContributorStatus>().await?)
+    Ok((response.json::<ContributorStatus>().await?, estimated_wait))
 }
 
 /// Send [`ContributionInfo`] to the Coordinator.
@@ -434,6 +636,26 @@ pub async fn post_attestation(
     Ok(())
 }
 
+/// Send the binding of a Namada address to the contribution to the Coordinator.
+pub async fn post_namada_address(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &KeyPair,
+    request_body: &(u64, NamadaAddressBinding),
+) -> Result<()> {
+    submit_request::<(u64, NamadaAddressBinding)>(
+        client,
+        coordinator_address,
+        "/contributor/namada_address",
+        Some(keypair),
+        None,
+        Request::Post(Some(request_body)),
+    )
+    .await?;
+
+    Ok(())
+}
+
 /// Query health endpoint of the Coordinator to check the connection
 pub async fn ping_coordinator(client: &Client, coordinator_address: &Url) -> Result<()> {
     submit_request::<()>(client, coordinator_address, "/healthcheck", None, None, Request::Get).await?;
@@ -441,6 +663,35 @@ pub async fn ping_coordinator(client: &Client, coordinator_address: &Url) -> Res
     Ok(())
 }
 
+/// Retrieve the identity of the coordinator, checking that it's signed by the given public key, published by the
+/// organizers of the ceremony
+pub async fn get_coordinator_identity(
+    client: &Client,
+    coordinator_address: &Url,
+    coordinator_public_key: &str,
+) -> Result<CoordinatorIdentity> {
+    let response = submit_request::<()>(
+        client,
+        coordinator_address,
+        "/coordinator/identity",
+        None,
+        None,
+        Request::Get,
+    )
+    .await?;
+    let identity: CoordinatorIdentity = response.json().await?;
+
+    match identity.coordinator_public_key == coordinator_public_key
+        && Production.verify(
+            coordinator_public_key,
+            &coordinator_identity_message(&identity),
+            &identity.signature,
+        ) {
+        true => Ok(identity),
+        false => Err(RequestError::UntrustedCoordinator),
+    }
+}
+
 /// Retrieve the list of contributions, json encoded
 #[cfg(debug_assertions)]
 pub async fn get_contributions_info(coordinator_address: &Url) -> Result<Vec<u8>> {
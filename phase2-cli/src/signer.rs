@@ -0,0 +1,48 @@
+//! The signature scheme of the keypair of the contributor, used for both the contribution and the requests to the
+//! [Coordinator](`phase2-coordinator::Coordinator`).
+//!
+//! Keypairs are signed with [`Production`] unless [`use_ledger`] was called, in which case the signing key of the
+//! keypair is a derivation path and every signature goes through the Ledger device.
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::OnceCell;
+use phase2_coordinator::authentication::{KeyPair, Production, Signature};
+
+#[cfg(feature = "ledger")]
+use phase2_coordinator::authentication::{HidTransport, Ledger};
+
+static SIGNER: OnceCell<Box<dyn Signature>> = OnceCell::new();
+
+/// Returns the signature scheme of the keypair of the contributor.
+pub fn signer() -> &'static dyn Signature {
+    match SIGNER.get() {
+        Some(signer) => signer.as_ref(),
+        None => &Production,
+    }
+}
+
+/// Returns `true` if the signatures go through the Ledger device, each of them waiting for the confirmation of the user.
+pub fn uses_ledger() -> bool {
+    SIGNER.get().is_some()
+}
+
+/// Connects to the Ledger device and returns the keypair of the key at the derivation path: from now on every
+/// signature is computed by the device. Fails if the device is unreachable or if the signer was already set.
+#[cfg(feature = "ledger")]
+pub fn use_ledger(derivation_path: &str) -> Result<KeyPair> {
+    let ledger = Ledger::new(HidTransport::new()?);
+    let keypair = ledger.keypair(derivation_path)?;
+    SIGNER
+        .set(Box::new(ledger))
+        .map_err(|_| anyhow!("The signature scheme is already set"))?;
+
+    Ok(keypair)
+}
+
+/// Always fails, Ledger devices are supported only with the `ledger` feature.
+#[cfg(not(feature = "ledger"))]
+pub fn use_ledger(_derivation_path: &str) -> Result<KeyPair> {
+    Err(anyhow!(
+        "This binary was built without the support of Ledger devices, rebuild it with the \"ledger\" feature"
+    ))
+}
@@ -0,0 +1,383 @@
+//! Simulation of a ceremony with synthetic contributors, to rehearse a ceremony against a running
+//! [Coordinator](`phase2-coordinator::Coordinator`) without real participants.
+//!
+//! Every synthetic contributor goes through the same REST flow as `namada-ts contribute default`, with a freshly
+//! generated keypair and a random seed: it joins the queue, waits for its turn, computes the contribution on the
+//! challenge and uploads it. Contributors are spawned as soon as a previous one has finished so that the queue always
+//! holds the requested amount of contributors, until the requested amount of rounds has been contributed.
+
+use crate::requests;
+use phase2_coordinator::{
+    authentication::{KeyPair, Production, Signature},
+    commands::{Computation, RandomSource},
+    objects::ContributionInfo,
+    rest_utils::{ContributorStatus, LockedChunk, PostChunkRequest},
+    ContributionFileSignature, ContributionState,
+};
+
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use futures_util::{
+    stream::{self, FuturesUnordered},
+    StreamExt,
+};
+use rand::Rng;
+use reqwest::{Client, Url};
+use setup_utils::calculate_hash;
+use std::{
+    io::Write,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::time;
+use tracing::{debug, info};
+
+/// Parameters of a simulation.
+pub struct SimulationConfig {
+    /// Number of synthetic contributors kept in the queue at the same time
+    pub contributors: usize,
+    /// Number of contributions, and so of rounds, after which the simulation stops
+    pub rounds: u64,
+    /// Tokens of the current cohort, assigned in turn to the synthetic contributors. If the coordinator runs with
+    /// `TOKEN_BLACKLIST`, at least one distinct token per contribution is needed.
+    pub tokens: Vec<String>,
+    /// Keypair of the coordinator. When given, the simulation also triggers the updates and the verifications of
+    /// the coordinator, which is required if the coordinator doesn't run its periodic tasks (debug builds only).
+    pub coordinator_keypair: Option<KeyPair>,
+    /// Interval between two polls of the queue status
+    pub poll_interval: Duration,
+    /// Maximum time without any completed contribution before the ceremony is considered stalled
+    pub stall_timeout: Duration,
+}
+
+/// Timings of the contribution of a synthetic contributor.
+#[derive(Debug)]
+pub struct ContributorRun {
+    pub public_key: String,
+    pub round_height: u64,
+    /// Time spent in the queue, from joining to locking the chunk
+    pub queue_time: Duration,
+    /// Time spent contributing, from locking the chunk to the notification of the contribution
+    pub contribution_time: Duration,
+}
+
+/// Outcome of a simulation.
+#[derive(Debug)]
+pub struct SimulationReport {
+    pub runs: Vec<ContributorRun>,
+    pub elapsed: Duration,
+}
+
+impl SimulationReport {
+    /// Returns the number of contributions completed per minute.
+    pub fn throughput(&self) -> f64 {
+        self.runs.len() as f64 * 60.0 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Returns the average time spent in the queue and contributing.
+    pub fn average_times(&self) -> (Duration, Duration) {
+        let count = self.runs.len().max(1) as u32;
+        let queue: Duration = self.runs.iter().map(|run| run.queue_time).sum();
+        let contribution: Duration = self.runs.iter().map(|run| run.contribution_time).sum();
+
+        (queue / count, contribution / count)
+    }
+}
+
+impl std::fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (queue_time, contribution_time) = self.average_times();
+        writeln!(f, "Completed contributions: {}", self.runs.len())?;
+        writeln!(f, "Elapsed time: {:?}", self.elapsed)?;
+        writeln!(f, "Throughput: {:.2} contributions/min", self.throughput())?;
+        writeln!(f, "Average time in queue: {:?}", queue_time)?;
+        write!(f, "Average contribution time: {:?}", contribution_time)
+    }
+}
+
+/// Runs a simulation against the coordinator at the given address.
+///
+/// Fails as soon as a synthetic contributor fails, or if no contribution completes within the stall timeout of the
+/// configuration, which surfaces deadlocks of the coordinator.
+pub async fn simulate(
+    client: Arc<Client>,
+    coordinator: Arc<Url>,
+    config: SimulationConfig,
+) -> Result<SimulationReport> {
+    let SimulationConfig {
+        contributors,
+        rounds,
+        tokens,
+        coordinator_keypair,
+        poll_interval,
+        stall_timeout,
+    } = config;
+    if contributors == 0 {
+        return Err(anyhow!("The simulation requires at least one contributor"));
+    }
+    if tokens.is_empty() {
+        return Err(anyhow!("The simulation requires at least one token"));
+    }
+
+    let driver = match coordinator_keypair {
+        #[cfg(debug_assertions)]
+        Some(keypair) => Some(tokio::spawn(drive_coordinator(
+            client.clone(),
+            coordinator.clone(),
+            keypair,
+            poll_interval,
+        ))),
+        #[cfg(not(debug_assertions))]
+        Some(_) => return Err(anyhow!("The coordinator can only be driven by the simulation in debug builds")),
+        None => None,
+    };
+
+    let start = Instant::now();
+    let mut runs = Vec::new();
+    let mut spawned = 0;
+    let mut pending = FuturesUnordered::new();
+    let spawn_contributor = |index: u64| {
+        tokio::spawn(simulate_contributor(
+            client.clone(),
+            coordinator.clone(),
+            assign_token(&tokens, index).to_owned(),
+            poll_interval,
+        ))
+    };
+
+    while spawned < rounds.min(contributors as u64) {
+        pending.push(spawn_contributor(spawned));
+        spawned += 1;
+    }
+
+    let result = loop {
+        let run = match time::timeout(stall_timeout, pending.next()).await {
+            Ok(Some(run)) => run,
+            Ok(None) => break Ok(()),
+            Err(_) => {
+                break Err(anyhow!(
+                    "No contribution completed in {:?}, the ceremony is stalled after {} contributions",
+                    stall_timeout,
+                    runs.len()
+                ))
+            }
+        };
+
+        match run.map_err(|e| anyhow!("Synthetic contributor panicked: {}", e)) {
+            Ok(Ok(run)) => {
+                info!(
+                    "Contributor {} contributed to round {} in {:?}, after {:?} in queue",
+                    run.public_key, run.round_height, run.contribution_time, run.queue_time
+                );
+                runs.push(run);
+            }
+            Ok(Err(e)) | Err(e) => break Err(e),
+        }
+
+        if spawned < rounds {
+            pending.push(spawn_contributor(spawned));
+            spawned += 1;
+        }
+    };
+
+    // Interrupt the remaining contributors and the driver, tokio detaches the tasks on drop
+    for handle in pending.iter() {
+        handle.abort();
+    }
+    if let Some(driver) = driver {
+        driver.abort();
+    }
+
+    result.map(|_| SimulationReport {
+        runs,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Parses the tokens of the current cohort from the content of a json file holding a list of tokens, which can't be
+/// empty.
+pub fn parse_tokens(content: &[u8]) -> Result<Vec<String>> {
+    let tokens: Vec<String> = serde_json::from_slice(content)?;
+    if tokens.is_empty() {
+        return Err(anyhow!("The tokens file holds no token"));
+    }
+
+    Ok(tokens)
+}
+
+/// Returns the token of the synthetic contributor with the given index, the tokens being assigned in turn. The tokens
+/// are checked not to be empty by [simulate].
+fn assign_token(tokens: &[String], index: u64) -> &str {
+    &tokens[index as usize % tokens.len()]
+}
+
+/// Periodically updates the coordinator and verifies the pending contributions, in place of the periodic tasks of
+/// the coordinator.
+#[cfg(debug_assertions)]
+async fn drive_coordinator(client: Arc<Client>, coordinator: Arc<Url>, keypair: KeyPair, interval: Duration) {
+    loop {
+        if let Err(e) = requests::get_verify_chunks(&client, &coordinator, &keypair).await {
+            debug!("Verification of the contributions failed: {}", e);
+        }
+        if let Err(e) = requests::get_update(&client, &coordinator, &keypair).await {
+            debug!("Update of the coordinator failed: {}", e);
+        }
+        time::sleep(interval).await;
+    }
+}
+
+/// Joins the queue with a new keypair and contributes once it's the turn of the contributor.
+async fn simulate_contributor(
+    client: Arc<Client>,
+    coordinator: Arc<Url>,
+    token: String,
+    poll_interval: Duration,
+) -> Result<ContributorRun> {
+    let keypair = KeyPair::new();
+    let joined = Instant::now();
+    requests::post_join_queue(&client, &coordinator, &keypair, &token, None, None, None).await?;
+
+    let mut contribution = None;
+    loop {
+        requests::post_heartbeat(&client, &coordinator, &keypair).await?;
+
+        let (status, _) = requests::get_contributor_queue_status(&client, &coordinator, &keypair).await?;
+        match status {
+            ContributorStatus::Queue(position, size) => {
+                debug!("Contributor {} at position {} of {}", keypair.pubkey(), position, size)
+            }
+            ContributorStatus::Round if contribution.is_none() => {
+                let locked = Instant::now();
+                let round_height = contribute(&client, &coordinator, &keypair).await?;
+                contribution = Some((round_height, locked));
+            }
+            // Waiting for the verification of the contribution
+            ContributorStatus::Round => (),
+            ContributorStatus::Finished => {
+                let (round_height, locked) = contribution
+                    .ok_or_else(|| anyhow!("Contributor {} finished without contributing", keypair.pubkey()))?;
+
+                return Ok(ContributorRun {
+                    public_key: keypair.pubkey().to_owned(),
+                    round_height,
+                    queue_time: locked.duration_since(joined),
+                    contribution_time: locked.elapsed(),
+                });
+            }
+            ContributorStatus::Banned => return Err(anyhow!("Contributor {} has been banned", keypair.pubkey())),
+            ContributorStatus::Other => {
+                return Err(anyhow!("Contributor {} has been dropped", keypair.pubkey()));
+            }
+        }
+
+        time::sleep(poll_interval).await;
+    }
+}
+
+/// Computes and uploads a contribution with a random seed. Returns the round height of the contribution.
+async fn contribute(client: &Client, coordinator: &Url, keypair: &KeyPair) -> Result<u64> {
+    let LockedChunk {
+        locators, upload_urls, ..
+    } = requests::get_lock_chunk(client, coordinator, keypair).await?;
+    let response_locator = locators.next_contribution();
+    let round_height = response_locator.round_height();
+
+    let challenge_url = requests::get_challenge_url(client, coordinator, keypair, &round_height).await?;
+    let mut challenge_stream = requests::get_challenge(client, challenge_url.as_str()).await?;
+    let mut challenge = Vec::new();
+    while let Some(b) = challenge_stream.0.next().await {
+        challenge.extend_from_slice(&b?);
+    }
+
+    let challenge_hash = calculate_hash(challenge.as_ref());
+    let contribution = tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+        let mut contribution = Vec::new();
+        contribution.write_all(challenge_hash.as_slice())?;
+
+        let rand_source = RandomSource::Seed(rand::thread_rng().gen());
+        #[cfg(debug_assertions)]
+        Computation::contribute_test_masp(&challenge, &mut contribution, &rand_source);
+        #[cfg(not(debug_assertions))]
+        Computation::contribute_masp(&challenge, &mut contribution, &rand_source);
+
+        Ok(contribution)
+    })
+    .await??;
+
+    let contribution_file_hash = calculate_hash(contribution.as_ref());
+    let contribution_state = ContributionState::new(challenge_hash.to_vec(), contribution_file_hash.to_vec(), None)?;
+    let signature = Production.sign(keypair.sigkey(), &contribution_state.signature_message()?)?;
+    let contribution_file_signature = ContributionFileSignature::new(signature, contribution_state)?;
+
+    let (contribution_url, contribution_signature_url) = match upload_urls {
+        Some(urls) => urls,
+        None => requests::get_contribution_url(client, coordinator, keypair, &round_height).await?,
+    };
+    let contribution_size = contribution.len() as u64;
+    let contribution_stream = stream::iter(std::iter::once(Ok(Bytes::from(contribution))));
+    requests::upload_chunk(
+        client,
+        contribution_url.as_str(),
+        contribution_signature_url.as_str(),
+        contribution_stream,
+        contribution_size,
+        &contribution_file_signature,
+    )
+    .await?;
+
+    let mut contrib_info = ContributionInfo::default();
+    contrib_info.public_key = keypair.pubkey().to_owned();
+    contrib_info.ceremony_round = round_height;
+    contrib_info.contribution_file_hash = hex::encode(contribution_file_hash);
+    contrib_info.try_sign(&Production, keypair)?;
+    requests::post_contribution_info(client, coordinator, keypair, &contrib_info).await?;
+
+    let post_chunk_req = PostChunkRequest::new(
+        round_height,
+        locators.next_contribution(),
+        locators.next_contribution_file_signature(),
+    );
+    requests::post_contribute_chunk(client, coordinator, keypair, &post_chunk_req).await?;
+
+    Ok(round_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tokens() {
+        assert_eq!(parse_tokens(br#"["a", "b"]"#).unwrap(), vec!["a", "b"]);
+        assert!(parse_tokens(b"[]").is_err());
+        assert!(parse_tokens(br#"{"tokens": ["a"]}"#).is_err());
+        assert!(parse_tokens(b"").is_err());
+    }
+
+    #[test]
+    fn test_assign_token() {
+        let tokens = vec!["a".to_owned(), "b".to_owned(), "c".to_owned()];
+        let assigned: Vec<&str> = (0..7).map(|index| assign_token(&tokens, index)).collect();
+        assert_eq!(assigned, vec!["a", "b", "c", "a", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_simulate_without_tokens() {
+        let config = SimulationConfig {
+            contributors: 1,
+            rounds: 1,
+            tokens: vec![],
+            coordinator_keypair: None,
+            poll_interval: Duration::from_secs(1),
+            stall_timeout: Duration::from_secs(1),
+        };
+        let simulation = simulate(
+            Arc::new(Client::new()),
+            Arc::new(Url::parse("http://localhost:8080").unwrap()),
+            config,
+        );
+
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        assert!(runtime.block_on(simulation).is_err());
+    }
+}
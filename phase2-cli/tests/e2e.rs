@@ -1,8 +1,7 @@
-//  NOTE: these tests must be run with --test-threads=1 due to the disk storage
-//	being stored at the same path for all the test instances causing a conflict.
-//	It could be possible to define a separate location (base_dir) for every test
-//	but it's simpler to just run the tests sequentially.
-//  NOTE: these tests require the phase1radix files to be placed in the phase2-cli folder
+//  NOTE: these tests must be run with --test-threads=1 since every test launches
+//	the rocket server on the address and port of the Rocket.toml file.
+//  NOTE: these tests require the phase1radix files, either placed in the phase2-cli folder
+//	or in the directory given by the NAMADA_MPC_PHASE1RADIX_PATH env variable
 
 use std::{io::Write, net::IpAddr, sync::Arc};
 
@@ -13,9 +12,9 @@ use phase2_coordinator::{
     environment::Testing,
     objects::{ContributionInfo, LockedLocators, TrimmedContributionInfo},
     rest,
-    rest_utils::{self, PostChunkRequest, TOKENS_ZIP_FILE},
+    rest_utils::{self, ApiVersion, IpAccess, PostChunkRequest, API_PREFIX, TOKENS_ZIP_FILE},
     storage::{ContributionLocator, ContributionSignatureLocator, Object},
-    testing::coordinator,
+    testing::coordinator::{self, TestEnvironment},
     ContributionFileSignature, ContributionState, Coordinator, Participant,
 };
 use rocket::{
@@ -33,7 +32,10 @@ use async_stream::try_stream;
 use futures_util::StreamExt;
 use toml::Value;
 
-use phase2_cli::requests;
+use phase2_cli::{
+    requests,
+    simulation::{self, SimulationConfig},
+};
 use reqwest::{Client, Url};
 use zip::write::FileOptions;
 
@@ -53,10 +55,18 @@ struct TestCtx {
     coordinator_url: String,
     // Keep TempDir in scope for some tests
     _tokens_tmp_dir: tempfile::TempDir,
+    // Keep the storage of the test in scope
+    environment: TestEnvironment,
 }
 
 /// Launch the rocket server for testing with the proper configuration as a separate async Task.
 async fn test_prelude() -> (TestCtx, JoinHandle<Result<Rocket<Ignite>, Error>>) {
+    launch_coordinator(true).await
+}
+
+/// Launch the rocket server as [test_prelude] does, with the first contributor holding the lock of round 1 if
+/// `lock_contributor`, or else with the ceremony at round 0 and an empty queue.
+async fn launch_coordinator(lock_contributor: bool) -> (TestCtx, JoinHandle<Result<Rocket<Ignite>, Error>>) {
     std::env::set_var("TOKEN_BLACKLIST", "true");
     // NOTE: never set NAMADA_MPC_IP_BAN here because we cannot test the IPs here (cannot mock them)
 
@@ -83,7 +93,7 @@ async fn test_prelude() -> (TestCtx, JoinHandle<Result<Rocket<Ignite>, Error>>)
     std::env::set_var("TOKENS_FILE_PREFIX", "namada_tokens_cohort");
 
     // Instantiate the coordinator
-    let mut coordinator = Coordinator::new(environment, Arc::new(Production)).unwrap();
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Production)).unwrap();
 
     let keypair1 = KeyPair::new();
     let keypair2 = KeyPair::new();
@@ -120,43 +130,49 @@ async fn test_prelude() -> (TestCtx, JoinHandle<Result<Rocket<Ignite>, Error>>)
         locked_locators: None,
     };
 
-    coordinator
-        .add_to_queue(
-            contributor1.clone(),
-            Some(contributor1_ip),
-            String::from("9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C"),
-            10,
-        )
-        .unwrap();
-    coordinator.update().unwrap();
+    let locked_locators = if lock_contributor {
+        coordinator
+            .add_to_queue(
+                contributor1.clone(),
+                Some(contributor1_ip),
+                String::from("9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C"),
+                10,
+            )
+            .unwrap();
+        coordinator.update().unwrap();
 
-    let (_, locked_locators) = coordinator.try_lock(&contributor1).unwrap();
+        Some(coordinator.try_lock(&contributor1).unwrap().1)
+    } else {
+        None
+    };
 
     let coordinator: Arc<RwLock<Coordinator>> = Arc::new(RwLock::new(coordinator));
 
+    let routes = routes![
+        rest::join_queue,
+        rest::lock_chunk,
+        rest::contribute_chunk,
+        rest::update_coordinator,
+        rest::heartbeat,
+        rest::stop_coordinator,
+        rest::verify_chunks,
+        rest::get_contributor_queue_status,
+        rest::post_contribution_info,
+        rest::get_contributions_info,
+        rest::get_healthcheck,
+        rest::get_contribution_url,
+        rest::get_challenge_url,
+        rest::get_coordinator_state,
+        rest::get_coordinator_identity,
+        rest::update_cohorts,
+        rest::post_attestation
+    ];
     let build = rocket::build()
-        .mount(
-            "/",
-            routes![
-                rest::join_queue,
-                rest::lock_chunk,
-                rest::contribute_chunk,
-                rest::update_coordinator,
-                rest::heartbeat,
-                rest::stop_coordinator,
-                rest::verify_chunks,
-                rest::get_contributor_queue_status,
-                rest::post_contribution_info,
-                rest::get_contributions_info,
-                rest::get_healthcheck,
-                rest::get_contribution_url,
-                rest::get_challenge_url,
-                rest::get_coordinator_state,
-                rest::update_cohorts,
-                rest::post_attestation
-            ],
-        )
+        .mount(API_PREFIX, routes.clone())
+        .mount("/", routes)
         .manage(coordinator)
+        .manage(IpAccess::from_environment(&environment))
+        .attach(ApiVersion)
         .register(
             "/",
             catchers![
@@ -177,7 +193,7 @@ async fn test_prelude() -> (TestCtx, JoinHandle<Result<Rocket<Ignite>, Error>>)
         _inner: contributor1,
         _address: contributor1_ip,
         keypair: keypair1,
-        locked_locators: Some(locked_locators),
+        locked_locators,
     };
     let test_participant2 = TestParticipant {
         _inner: contributor2,
@@ -198,6 +214,7 @@ async fn test_prelude() -> (TestCtx, JoinHandle<Result<Rocket<Ignite>, Error>>)
         coordinator: coord_verifier,
         coordinator_url,
         _tokens_tmp_dir: tmp_dir,
+        environment,
     };
 
     (ctx, handle)
@@ -328,6 +345,29 @@ async fn get_status() {
     handle.abort()
 }
 
+#[tokio::test]
+async fn get_coordinator_identity() {
+    let client = Client::new();
+    // Spawn the server and get the test context
+    let (ctx, handle) = test_prelude().await;
+    // Wait for server startup
+    time::sleep(Duration::from_secs(1)).await;
+
+    // Signed by the published key of the coordinator
+    let url = Url::parse(&ctx.coordinator_url).unwrap();
+    let identity = requests::get_coordinator_identity(&client, &url, ctx.coordinator.keypair.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(identity.coordinator_public_key, ctx.coordinator.keypair.pubkey());
+
+    // Wrong, another published key
+    let response = requests::get_coordinator_identity(&client, &url, ctx.contributors[0].keypair.pubkey()).await;
+    assert!(matches!(response, Err(requests::RequestError::UntrustedCoordinator)));
+
+    // Drop the server
+    handle.abort()
+}
+
 #[tokio::test]
 async fn get_contributor_queue_status() {
     let client = Client::new();
@@ -435,6 +475,9 @@ async fn join_queue() {
         &url,
         &ctx.unknown_participant.keypair,
         &String::from("9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7er"),
+        None,
+        None,
+        None,
     )
     .await;
     assert!(response.is_err());
@@ -445,6 +488,9 @@ async fn join_queue() {
         &url,
         &ctx.unknown_participant.keypair,
         &String::from("9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"),
+        None,
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -455,6 +501,9 @@ async fn join_queue() {
         &url,
         &ctx.contributors[1].keypair,
         &String::from("9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"),
+        None,
+        None,
+        None,
     )
     .await;
     assert!(response.is_err());
@@ -465,6 +514,9 @@ async fn join_queue() {
         &url,
         &ctx.unknown_participant.keypair,
         &String::from("9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2"),
+        None,
+        None,
+        None,
     )
     .await;
     assert!(response.is_err());
@@ -618,9 +670,11 @@ async fn contribution() {
     let seed = RandomSource::Seed(rand::thread_rng().gen::<[u8; 32]>());
     Computation::contribute_test_masp(&challenge, &mut contribution, &seed);
 
-    // Initial contribution size is 2332 but the Coordinator expect ANOMA_BASE_FILE_SIZE. Extend to this size with trailing 0s
-    let contrib_size = Object::anoma_contribution_file_size(ROUND_HEIGHT, 1);
-    contribution.resize(contrib_size as usize, 0);
+    // The contribution has the size the coordinator expects, without padding
+    assert_eq!(
+        contribution.len() as u64,
+        Object::anoma_contribution_file_size(&ctx.environment, ROUND_HEIGHT, 1)
+    );
 
     let contribution_file_signature_locator = ContributionSignatureLocator::new(ROUND_HEIGHT, 0, 1, false);
 
@@ -665,7 +719,9 @@ async fn contribution() {
         .unwrap()
         .current_contribution()
         .round_height();
-    contrib_info.try_sign(&ctx.contributors[0].keypair).unwrap();
+    contrib_info
+        .try_sign(&Production, &ctx.contributors[0].keypair)
+        .unwrap();
 
     requests::post_contribution_info(&client, &url, &ctx.contributors[0].keypair, &contrib_info)
         .await
@@ -708,6 +764,9 @@ async fn contribution() {
         &url,
         &ctx.unknown_participant.keypair,
         &String::from("9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C"),
+        None,
+        None,
+        None,
     )
     .await;
     assert!(response.is_err());
@@ -721,6 +780,9 @@ async fn contribution() {
         &url,
         &ctx.contributors[1].keypair,
         &String::from("9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C"),
+        None,
+        None,
+        None,
     )
     .await;
     assert!(response.is_err());
@@ -773,6 +835,9 @@ async fn contribution() {
         &url,
         &ctx.unknown_participant.keypair,
         &String::from("9nFeNpukSn1eVwNc2vkfP8TAaw6DXNAgCNpxiQc437BxT3iF2xUMdo6wYQjqwxHwAZjVhQzdH3QMpJSbXvaDcnkVu6Ktt22AfYDypK2h72vuQK9fGNp"),
+        None,
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -780,3 +845,35 @@ async fn contribution() {
     // Drop the server
     handle.abort()
 }
+
+/// Runs a synthetic contributor of the simulation through round 1, the simulation driving the coordinator.
+#[tokio::test]
+async fn simulation() {
+    let (ctx, handle) = launch_coordinator(false).await;
+    // Wait for server startup
+    time::sleep(Duration::from_secs(1)).await;
+    let url = Arc::new(Url::parse(&ctx.coordinator_url).unwrap());
+
+    let config = SimulationConfig {
+        contributors: 1,
+        rounds: 1,
+        tokens: vec![String::from("9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek")],
+        coordinator_keypair: Some(ctx.coordinator.keypair.clone()),
+        poll_interval: Duration::from_secs(1),
+        stall_timeout: Duration::from_secs(120),
+    };
+    let report = simulation::simulate(Arc::new(Client::new()), url.clone(), config)
+        .await
+        .unwrap();
+    assert_eq!(report.runs.len(), 1);
+    assert_eq!(report.runs[0].round_height, ROUND_HEIGHT);
+
+    // The contribution was verified and recorded by the coordinator
+    let summary_bytes = requests::get_contributions_info(&url).await.unwrap();
+    let summary: Vec<TrimmedContributionInfo> = serde_json::from_slice(&summary_bytes).unwrap();
+    assert_eq!(summary.len(), 1);
+    assert_eq!(summary[0].public_key(), report.runs[0].public_key);
+
+    // Drop the server
+    handle.abort()
+}
@@ -0,0 +1,887 @@
+//! Typed async client of the coordinator REST API.
+
+use crate::{
+    attestation_statement_message, challenge_message, coordinator_identity_message, header_digest, login_message,
+    namada_address_binding_message, signature_message, verification_result_message, versioned_endpoint,
+    AttestationStatement, CeremonyStats, CeremonyStatus, ContentEncoding, ContributionReceipt, ContributionRejection,
+    ContributorStatus, CoordinatorIdentity, ErrorResponse, LockedChunk, LoginRequest, NamadaAddressBinding,
+    PostChunkRequest, Session, TrimmedContributionInfo, UploadSession, VerificationResult, VerificationTask,
+    ACCEPT_ENCODING_HEADER, API_VERSION, API_VERSION_HEADER, BODY_DIGEST_HEADER, CHALLENGE_HASH_HEADER,
+    CHALLENGE_SIGNATURE_HEADER, CONTENT_ENCODING_HEADER, CONTENT_LENGTH_HEADER, CONTENT_RANGE_HEADER,
+    CONTRIBUTION_SIGNATURE_HEADER, EMAIL_HEADER, ESTIMATED_WAIT_HEADER, INVITE_CODE_HEADER, PRIORITY_TOKEN_HEADER,
+    PUBKEY_HEADER, SESSION_TOKEN_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use ed25519_compact::{KeyPair as EdKeyPair, Noise, PublicKey, SecretKey, Signature};
+use reqwest::{
+    header::{HeaderValue, CONTENT_TYPE},
+    Client, Method, RequestBuilder, Response, StatusCode, Url,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::ops::Deref;
+use thiserror::Error;
+
+/// Error returned from a request.
+#[derive(Debug, Error)]
+pub enum RequestError {
+    #[error("Error while parsing the coordinator url")]
+    AddressParseError,
+    #[error("Client-side error: {0}")]
+    Client(String),
+    #[error("Invalid header value: {0}")]
+    InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
+    #[error("Json serialization of body failed")]
+    JsonError(#[from] serde_json::Error),
+    #[error("CDN Error: {0}")]
+    Proxy(String),
+    #[error("Request error: {0}")]
+    Reqwest(#[from] reqwest::Error),
+    #[error("Error while signing the request")]
+    SigningError,
+    #[error("Server-side error: {0}")]
+    Server(String),
+    #[error("The coordinator is not signing with the expected public key")]
+    UntrustedCoordinator,
+}
+
+impl RequestError {
+    /// Returns the structured error sent by the coordinator, if any.
+    pub fn error_response(&self) -> Option<ErrorResponse> {
+        match self {
+            Self::Client(body) | Self::Server(body) => serde_json::from_str(body).ok(),
+            _ => None,
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, RequestError>;
+
+/// An ed25519 keypair, [`hex`] encoded, used to sign the requests of a participant.
+#[derive(Clone, Debug)]
+pub struct KeyPair {
+    pubkey: String,
+    sigkey: String,
+}
+
+impl KeyPair {
+    /// Generate a random keypair
+    pub fn new() -> Self {
+        let keypair = EdKeyPair::generate();
+
+        Self {
+            pubkey: hex::encode(keypair.pk.deref()),
+            sigkey: hex::encode(keypair.sk.deref()),
+        }
+    }
+
+    /// Build a keypair from its [`hex`] encoded keys
+    pub fn from_hex(pubkey: String, sigkey: String) -> Self {
+        Self { pubkey, sigkey }
+    }
+
+    /// Get a reference to the key pair's pubkey.
+    pub fn pubkey(&self) -> &str {
+        self.pubkey.as_ref()
+    }
+
+    /// Sign the message and return the [`hex`] encoded signature.
+    pub fn sign(&self, message: &str) -> Result<String> {
+        let sigkey = hex::decode(&self.sigkey).map_err(|_| RequestError::SigningError)?;
+        let sigkey = SecretKey::from_slice(&sigkey).map_err(|_| RequestError::SigningError)?;
+
+        Ok(hex::encode(sigkey.sign(message, Some(Noise::generate()))))
+    }
+}
+
+impl Default for KeyPair {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns `true` if the message has been signed by the given public key of the coordinator, the key and the signature
+/// being [`hex`] encoded. Used to check the identity and the challenges of the coordinator.
+pub fn verify_coordinator_signature(coordinator_public_key: &str, message: &str, signature: &str) -> bool {
+    let public_key = hex::decode(coordinator_public_key)
+        .ok()
+        .and_then(|key| PublicKey::from_slice(&key).ok());
+    let signature = hex::decode(signature)
+        .ok()
+        .and_then(|signature| Signature::from_slice(&signature).ok());
+
+    match (public_key, signature) {
+        (Some(public_key), Some(signature)) => public_key.verify(message, &signature).is_ok(),
+        _ => false,
+    }
+}
+
+/// A request to the coordinator, signed again at every submission.
+struct SignableRequest {
+    builder: RequestBuilder,
+    method: Method,
+    /// Path of the request, including the query
+    path: String,
+    /// Length and [`base64`] encoded sha-256 digest of the body
+    content: Option<(usize, String)>,
+    /// [`header_digest`] of the [`CONTRIBUTION_SIGNATURE_HEADER`], if any
+    contribution_signature: Option<String>,
+    /// Whether the endpoint accepts the session token in place of the signature
+    accepts_session: bool,
+}
+
+impl SignableRequest {
+    fn header(mut self, key: &'static str, value: HeaderValue) -> Self {
+        self.builder = self.builder.header(key, value);
+        self
+    }
+
+    /// Attaches the base64 encoded json signature of the contribution, signed together with the request.
+    fn contribution_signature(mut self, encoded_signature: &str) -> Result<Self> {
+        self.contribution_signature = Some(header_digest(encoded_signature));
+        Ok(self.header(CONTRIBUTION_SIGNATURE_HEADER, HeaderValue::from_str(encoded_signature)?))
+    }
+}
+
+/// Endpoints always requiring a signed request, the coordinator refusing the session tokens on them.
+const SIGNED_ONLY_ENDPOINTS: [&str; 2] = ["contributor/join_queue", "verifier/"];
+
+/// Client of the coordinator, signing the requests with the keypair of the participant. After a
+/// [`login`](Self::login), the requests to the contributor endpoints carry the session token instead.
+///
+/// Once the identity of the coordinator is checked with [`coordinator_identity`](Self::coordinator_identity), the
+/// challenges are refused unless signed by its key, if it signs them.
+pub struct CeremonyClient {
+    client: Client,
+    coordinator_address: Url,
+    keypair: KeyPair,
+    /// Token of the current session, if any
+    session: Option<String>,
+    /// Identity of the coordinator, once checked
+    identity: Option<CoordinatorIdentity>,
+}
+
+impl CeremonyClient {
+    pub fn new(coordinator_address: Url, keypair: KeyPair) -> Self {
+        Self::with_client(Client::new(), coordinator_address, keypair)
+    }
+
+    /// Build the client on top of an already configured [`Client`].
+    pub fn with_client(client: Client, coordinator_address: Url, keypair: KeyPair) -> Self {
+        Self {
+            client,
+            coordinator_address,
+            keypair,
+            session: None,
+            identity: None,
+        }
+    }
+
+    /// Get a reference to the keypair of the participant.
+    pub fn keypair(&self) -> &KeyPair {
+        &self.keypair
+    }
+
+    fn url(&self, endpoint: &str) -> Result<Url> {
+        self.coordinator_address
+            .join(&versioned_endpoint(endpoint))
+            .map_err(|_| RequestError::AddressParseError)
+    }
+
+    /// Build the request to the given endpoint, with the json body, if any, to be signed when sent.
+    fn build<T: Serialize>(&self, method: Method, endpoint: &str, body: Option<&T>) -> Result<SignableRequest> {
+        let body = match body {
+            Some(b) => Some((serde_json::to_vec(b)?, "application/json")),
+            None => None,
+        };
+
+        self.build_bytes(method, endpoint, body)
+    }
+
+    /// Build the request to the given endpoint, with the raw body, if any, with its content type, to be signed
+    /// when sent.
+    fn build_bytes(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Option<(Vec<u8>, &'static str)>,
+    ) -> Result<SignableRequest> {
+        let url = self.url(endpoint)?;
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        let mut builder = self
+            .client
+            .request(method.clone(), url)
+            .header(API_VERSION_HEADER, API_VERSION);
+        let mut content = None;
+
+        if let Some((bytes, content_type)) = body {
+            let encoded_digest = base64::encode(Sha256::digest(&bytes));
+
+            builder = builder
+                .header(CONTENT_LENGTH_HEADER, bytes.len())
+                .header(BODY_DIGEST_HEADER, HeaderValue::from_str(&format!("sha-256={}", encoded_digest))?)
+                .header(CONTENT_TYPE, HeaderValue::from_static(content_type));
+            content = Some((bytes.len(), encoded_digest));
+            builder = builder.body(bytes);
+        }
+
+        Ok(SignableRequest {
+            builder,
+            method,
+            path,
+            content,
+            contribution_signature: None,
+            accepts_session: !SIGNED_ONLY_ENDPOINTS.iter().any(|e| endpoint.starts_with(e)),
+        })
+    }
+
+    /// Attach the session token or, without a session or when the endpoint doesn't accept it, the signature headers
+    /// computed at the current time, to a copy of the request.
+    fn signed(&self, req: &SignableRequest) -> Result<RequestBuilder> {
+        if let Some(token) = self.session.as_ref().filter(|_| req.accepts_session) {
+            return Ok(req
+                .builder
+                .try_clone()
+                .expect("Expected request not stream")
+                .header(SESSION_TOKEN_HEADER, HeaderValue::from_str(token)?));
+        }
+
+        let timestamp = chrono::Utc::now().timestamp();
+        let message = signature_message(
+            req.method.as_str(),
+            &req.path,
+            timestamp,
+            self.keypair.pubkey(),
+            req.content.as_ref().map(|(l, d)| (*l, d.as_str())),
+            req.contribution_signature.as_deref(),
+        );
+        let signature = self.keypair.sign(&message)?;
+
+        Ok(req
+            .builder
+            .try_clone()
+            .expect("Expected request not stream")
+            .header(PUBKEY_HEADER, HeaderValue::from_str(self.keypair.pubkey())?)
+            .header(TIMESTAMP_HEADER, timestamp)
+            .header(SIGNATURE_HEADER, HeaderValue::from_str(&signature)?))
+    }
+
+    /// Send the request, resubmitting it on CDN timeouts.
+    async fn send(req: RequestBuilder) -> Result<Response> {
+        loop {
+            let response = req.try_clone().expect("Expected request not stream").send().await?;
+
+            match decapsulate_response(response).await {
+                Err(RequestError::Proxy(_)) => continue,
+                result => return result,
+            }
+        }
+    }
+
+    /// Sign and send the request, resubmitting it with a fresh signature on CDN timeouts since the coordinator
+    /// rejects the signatures it has already received.
+    async fn send_signed(&self, req: SignableRequest) -> Result<Response> {
+        loop {
+            let response = self.signed(&req)?.send().await?;
+
+            match decapsulate_response(response).await {
+                Err(RequestError::Proxy(_)) => continue,
+                result => return result,
+            }
+        }
+    }
+
+    async fn get<R: DeserializeOwned>(&self, endpoint: &str) -> Result<R> {
+        let req = self.build::<()>(Method::GET, endpoint, None)?;
+
+        Ok(self.send_signed(req).await?.json().await?)
+    }
+
+    async fn post<T: Serialize>(&self, endpoint: &str, body: Option<&T>) -> Result<Response> {
+        let req = self.build(Method::POST, endpoint, body)?;
+
+        self.send_signed(req).await
+    }
+
+    /// Open a session by signing a nonce issued by the coordinator. Until the session expires, the following
+    /// requests carry the session token instead of being signed one by one, except the ones to join the queue and
+    /// to verify the contributions. Call it again to renew the session.
+    pub async fn login(&mut self) -> Result<Session> {
+        self.session = None;
+        let nonce: String = self.get("contributor/login").await?;
+        let request = LoginRequest {
+            pubkey: self.keypair.pubkey().to_string(),
+            signature: self.keypair.sign(&login_message(self.keypair.pubkey(), &nonce))?,
+            nonce,
+        };
+        let req = self.build(Method::POST, "contributor/login", Some(&request))?;
+        let session: Session = Self::send(req.builder).await?.json().await?;
+        self.session = Some(session.token.clone());
+
+        Ok(session)
+    }
+
+    /// Close the session, the following requests are signed again.
+    pub fn logout(&mut self) {
+        self.session = None;
+    }
+
+    /// Join the queue of contributors with the given ceremony token, returns the cohort of the token.
+    pub async fn join_queue(&self, token: &str) -> Result<u64> {
+        Ok(self.post("contributor/join_queue", Some(&token)).await?.json().await?)
+    }
+
+    /// Join the queue of contributors redeeming a one-time invite code, required during the invite-only phases of the ceremony.
+    pub async fn join_queue_with_invite_code(&self, token: &str, invite_code: &str) -> Result<u64> {
+        let req = self
+            .build(Method::POST, "contributor/join_queue", Some(&token))?
+            .header(INVITE_CODE_HEADER, HeaderValue::from_str(invite_code)?);
+
+        Ok(self.send_signed(req).await?.json().await?)
+    }
+
+    /// Join the queue of contributors redeeming a priority token issued by the coordinator, which grants a reserved
+    /// slot during its validity window.
+    pub async fn join_queue_with_priority_token(&self, token: &str, priority_token: &str) -> Result<u64> {
+        let req = self
+            .build(Method::POST, "contributor/join_queue", Some(&token))?
+            .header(PRIORITY_TOKEN_HEADER, HeaderValue::from_str(priority_token)?);
+
+        Ok(self.send_signed(req).await?.json().await?)
+    }
+
+    /// Join the queue of contributors registering an email address, notified by the coordinator when the turn of the
+    /// participant is getting close and again when it locks the challenge.
+    pub async fn join_queue_with_email(&self, token: &str, email: &str) -> Result<u64> {
+        let req = self
+            .build(Method::POST, "contributor/join_queue", Some(&token))?
+            .header(EMAIL_HEADER, HeaderValue::from_str(email)?);
+
+        Ok(self.send_signed(req).await?.json().await?)
+    }
+
+    /// Get the status of the participant in the queue.
+    pub async fn queue_status(&self) -> Result<ContributorStatus> {
+        self.get("contributor/queue_status").await
+    }
+
+    /// Get the status of the participant in the queue, with the estimated wait in seconds before its turn once the
+    /// coordinator can tell it, from the duration of the recent contributions.
+    pub async fn queue_status_with_wait(&self) -> Result<(ContributorStatus, Option<u64>)> {
+        let req = self.build::<()>(Method::GET, "contributor/queue_status", None)?;
+        let response = self.send_signed(req).await?;
+        let estimated_wait = response
+            .headers()
+            .get(ESTIMATED_WAIT_HEADER)
+            .and_then(|value| value.to_str().ok()?.parse().ok());
+
+        Ok((response.json().await?, estimated_wait))
+    }
+
+    /// Get the contributions of the participant rejected at their verification, the oldest first.
+    pub async fn rejections(&self) -> Result<Vec<ContributionRejection>> {
+        self.get("contributor/rejections").await
+    }
+
+    /// Lock the challenge once it's the participant's turn.
+    pub async fn lock_chunk(&self) -> Result<LockedChunk> {
+        self.check_locked_chunk(self.get("contributor/lock_chunk").await?)
+    }
+
+    /// Wait for the participant's turn, then lock the challenge. Fails with
+    /// [`ErrorCode::NotYourTurn`](crate::ErrorCode::NotYourTurn) if the turn didn't come while the request was
+    /// held open by the coordinator, in which case the call can be repeated.
+    pub async fn await_turn(&self) -> Result<LockedChunk> {
+        self.check_locked_chunk(self.get("contributor/await_turn").await?)
+    }
+
+    /// Check the signature of the hash of the locked challenge.
+    fn check_locked_chunk(&self, locked_chunk: LockedChunk) -> Result<LockedChunk> {
+        self.check_challenge_signature(
+            locked_chunk.locators.next_contribution.round_height,
+            locked_chunk.challenge_hash.as_deref(),
+            locked_chunk.challenge_signature.as_deref(),
+        )?;
+
+        Ok(locked_chunk)
+    }
+
+    /// Check that the hash of the challenge of the given round is signed by the coordinator, if its identity has been
+    /// checked and it signs the challenges. Fails with [`RequestError::UntrustedCoordinator`] otherwise.
+    fn check_challenge_signature(
+        &self,
+        round_height: u64,
+        challenge_hash: Option<&str>,
+        signature: Option<&str>,
+    ) -> Result<()> {
+        let identity = match &self.identity {
+            Some(identity) if identity.signed_challenges => identity,
+            _ => return Ok(()),
+        };
+
+        match (challenge_hash, signature) {
+            (Some(hash), Some(signature))
+                if verify_coordinator_signature(
+                    &identity.coordinator_public_key,
+                    &challenge_message(round_height, hash),
+                    signature,
+                ) =>
+            {
+                Ok(())
+            }
+            _ => Err(RequestError::UntrustedCoordinator),
+        }
+    }
+
+    /// Check the signature of the challenge of the given round downloaded from the coordinator, carried by its headers.
+    fn check_downloaded_challenge(&self, round_height: u64, response: Response) -> Result<Response> {
+        let header = |name| response.headers().get(name).and_then(|value| value.to_str().ok());
+        self.check_challenge_signature(
+            round_height,
+            header(CHALLENGE_HASH_HEADER),
+            header(CHALLENGE_SIGNATURE_HEADER),
+        )?;
+
+        Ok(response)
+    }
+
+    /// Get the url from which to download the challenge of the given round.
+    pub async fn challenge_url(&self, round_height: u64) -> Result<String> {
+        Ok(self
+            .post("contributor/challenge", Some(&round_height))
+            .await?
+            .json()
+            .await?)
+    }
+
+    /// Download the challenge of the given round directly from the coordinator. The challenge is returned as a
+    /// [`Response`] to be consumed in chunks with [`Response::chunk`], its expected hash is carried by the
+    /// [`CHALLENGE_HASH_HEADER`](crate::CHALLENGE_HASH_HEADER).
+    pub async fn download_challenge(&self, round_height: u64) -> Result<Response> {
+        let endpoint = format!("contributor/challenge/{}", round_height);
+        let req = self.build::<()>(Method::GET, &endpoint, None)?;
+
+        self.check_downloaded_challenge(round_height, self.send_signed(req).await?)
+    }
+
+    /// Download the challenge of the given round directly from the coordinator, compressed with one of the `accepted`
+    /// encodings. The coordinator may still send the challenge uncompressed: the encoding of the body is given by the
+    /// [`CONTENT_ENCODING_HEADER`](crate::CONTENT_ENCODING_HEADER) of the response and the
+    /// [`CHALLENGE_HASH_HEADER`](crate::CHALLENGE_HASH_HEADER) is the hash of the decompressed challenge.
+    pub async fn download_compressed_challenge(
+        &self,
+        round_height: u64,
+        accepted: &[ContentEncoding],
+    ) -> Result<Response> {
+        let endpoint = format!("contributor/challenge/{}", round_height);
+        let accept_encoding = accepted.iter().map(|e| e.as_str()).collect::<Vec<_>>().join(", ");
+        let req = self
+            .build::<()>(Method::GET, &endpoint, None)?
+            .header(ACCEPT_ENCODING_HEADER, HeaderValue::from_str(&accept_encoding)?);
+
+        self.check_downloaded_challenge(round_height, self.send_signed(req).await?)
+    }
+
+    /// Get the urls where to upload the contribution and its signature, if not already provided by [`lock_chunk`](Self::lock_chunk).
+    pub async fn contribution_urls(&self, round_height: u64) -> Result<(String, String)> {
+        Ok(self.post("upload/chunk", Some(&round_height)).await?.json().await?)
+    }
+
+    /// Notify the coordinator of an uploaded contribution, returns the receipt signed by the coordinator.
+    pub async fn contribute_chunk(&self, request: &PostChunkRequest) -> Result<ContributionReceipt> {
+        Ok(self
+            .post("contributor/contribute_chunk", Some(request))
+            .await?
+            .json()
+            .await?)
+    }
+
+    /// Upload the contribution and its signature directly to the coordinator, as an alternative to the upload on S3
+    /// followed by [`contribute_chunk`](Self::contribute_chunk). Returns the receipt signed by the coordinator.
+    pub async fn upload_chunk<T: Serialize>(
+        &self,
+        contribution: Vec<u8>,
+        contribution_signature: &T,
+    ) -> Result<ContributionReceipt> {
+        self.upload_encoded_chunk(contribution, None, contribution_signature)
+            .await
+    }
+
+    /// Upload a contribution already compressed with the given encoding, see [`upload_chunk`](Self::upload_chunk).
+    /// The signature refers to the decompressed contribution.
+    pub async fn upload_compressed_chunk<T: Serialize>(
+        &self,
+        compressed_contribution: Vec<u8>,
+        encoding: ContentEncoding,
+        contribution_signature: &T,
+    ) -> Result<ContributionReceipt> {
+        self.upload_encoded_chunk(compressed_contribution, Some(encoding), contribution_signature)
+            .await
+    }
+
+    async fn upload_encoded_chunk<T: Serialize>(
+        &self,
+        contribution: Vec<u8>,
+        encoding: Option<ContentEncoding>,
+        contribution_signature: &T,
+    ) -> Result<ContributionReceipt> {
+        let encoded_signature = base64::encode(serde_json::to_vec(contribution_signature)?);
+        let mut req = self
+            .build_bytes(
+                Method::POST,
+                "contributor/upload_chunk",
+                Some((contribution, "application/octet-stream")),
+            )?
+            .contribution_signature(&encoded_signature)?;
+        if let Some(encoding) = encoding {
+            req = req.header(CONTENT_ENCODING_HEADER, HeaderValue::from_static(encoding.as_str()));
+        }
+
+        Ok(self.send_signed(req).await?.json().await?)
+    }
+
+    /// Get the progress of the resumable upload of the contribution to the current round.
+    pub async fn upload_session(&self) -> Result<UploadSession> {
+        self.get("contributor/upload_chunk/session").await
+    }
+
+    /// Upload the next part of the contribution, starting at the offset of the given session. Returns the updated
+    /// session.
+    pub async fn upload_part(&self, session: &UploadSession, part: Vec<u8>) -> Result<UploadSession> {
+        let content_range = session.content_range(part.len() as u64);
+        let req = self
+            .build_bytes(
+                Method::POST,
+                "contributor/upload_chunk/part",
+                Some((part, "application/octet-stream")),
+            )?
+            .header(CONTENT_RANGE_HEADER, HeaderValue::from_str(&content_range)?);
+
+        Ok(self.send_signed(req).await?.json().await?)
+    }
+
+    /// Complete the resumable upload of the contribution with its signature. The coordinator checks the assembled
+    /// contribution against the signature, returns the receipt signed by the coordinator.
+    pub async fn complete_upload<T: Serialize>(&self, contribution_signature: &T) -> Result<ContributionReceipt> {
+        let encoded_signature = base64::encode(serde_json::to_vec(contribution_signature)?);
+        let req = self
+            .build::<()>(Method::POST, "contributor/upload_chunk/complete", None)?
+            .contribution_signature(&encoded_signature)?;
+
+        Ok(self.send_signed(req).await?.json().await?)
+    }
+
+    /// Upload the contribution in parts of `part_size` bytes, as an alternative to [`upload_chunk`](Self::upload_chunk)
+    /// over unreliable connections. The upload resumes from the bytes already received by the coordinator, so that
+    /// calling this function again after a failure doesn't restart the upload from zero.
+    pub async fn upload_chunk_resumable<T: Serialize>(
+        &self,
+        contribution: &[u8],
+        contribution_signature: &T,
+        part_size: usize,
+    ) -> Result<ContributionReceipt> {
+        let mut session = self.upload_session().await?;
+        if session.total != contribution.len() as u64 || session.received > session.total {
+            return Err(RequestError::Client(format!(
+                "Contribution of {} bytes doesn't match the upload session {:?}",
+                contribution.len(),
+                session
+            )));
+        }
+
+        while !session.is_complete() {
+            let start = session.received as usize;
+            let end = contribution.len().min(start + part_size.max(1));
+            session = self.upload_part(&session, contribution[start..end].to_vec()).await?;
+        }
+
+        self.complete_upload(contribution_signature).await
+    }
+
+    /// Let the coordinator know that the participant is still alive.
+    pub async fn heartbeat(&self) -> Result<()> {
+        self.post::<()>("contributor/heartbeat", None).await?;
+
+        Ok(())
+    }
+
+    /// Give up on the contribution, releasing the locks for the coordinator to move on, and go back to
+    /// the end of the queue.
+    pub async fn abandon(&self) -> Result<()> {
+        self.post::<()>("contributor/abandon", None).await?;
+
+        Ok(())
+    }
+
+    /// Send the signed summary of the contribution. Since its signature covers
+    /// all of its fields, the summary is provided as an already built json value.
+    pub async fn contribution_info<T: Serialize>(&self, contribution_info: &T) -> Result<()> {
+        self.post("contributor/contribution_info", Some(contribution_info))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Send the url of a public attestation of the contribution of the given round.
+    pub async fn attestation(&self, round_height: u64, attestation_url: &str) -> Result<()> {
+        self.post("contributor/attestation", Some(&(round_height, attestation_url)))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Send a signed free-text attestation of the contribution of the given round, optionally linking a public post.
+    pub async fn attestation_statement(&self, round_height: u64, statement: &str, url: Option<&str>) -> Result<()> {
+        let attestation = AttestationStatement {
+            statement: statement.to_string(),
+            url: url.map(str::to_string),
+            signature: self.keypair.sign(&attestation_statement_message(
+                self.keypair.pubkey(),
+                round_height,
+                statement,
+            ))?,
+        };
+        self.post("contributor/attestation", Some(&(round_height, attestation)))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Bind a Namada address to the contribution of the given round.
+    pub async fn namada_address(&self, round_height: u64, address: &str) -> Result<()> {
+        let binding = NamadaAddressBinding {
+            address: address.to_string(),
+            signature: self
+                .keypair
+                .sign(&namada_address_binding_message(self.keypair.pubkey(), address))?,
+        };
+        self.post("contributor/namada_address", Some(&(round_height, binding)))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the message that the participant is expected to sign and publish in the attestation.
+    pub async fn attestation_message(&self) -> Result<String> {
+        let endpoint = format!("contributor/attestation_message/{}", self.keypair.pubkey());
+
+        self.get(&endpoint).await
+    }
+
+    /// Lock the next contribution to verify as an external verifier, if any is waiting for a verification.
+    pub async fn lock_verification_task(&self) -> Result<Option<VerificationTask>> {
+        self.get("verifier/lock_task").await
+    }
+
+    /// Download the challenge of the verification task locked by the verifier. Its hash is carried by the
+    /// [`CHALLENGE_HASH_HEADER`](crate::CHALLENGE_HASH_HEADER).
+    pub async fn download_verification_challenge(&self) -> Result<Response> {
+        let req = self.build::<()>(Method::GET, "verifier/challenge", None)?;
+
+        self.send_signed(req).await
+    }
+
+    /// Download the response of the verification task locked by the verifier. Its hash is carried by the
+    /// [`CHALLENGE_HASH_HEADER`](crate::CHALLENGE_HASH_HEADER) and must be signed in the result.
+    pub async fn download_verification_response(&self) -> Result<Response> {
+        let req = self.build::<()>(Method::GET, "verifier/response", None)?;
+
+        self.send_signed(req).await
+    }
+
+    /// Send the signed result of the verification of the response with the given hash.
+    pub async fn verification_result(&self, task: VerificationTask, response_hash: &str, verified: bool) -> Result<()> {
+        let result = VerificationResult {
+            task,
+            response_hash: response_hash.to_string(),
+            verified,
+            signature: self
+                .keypair
+                .sign(&verification_result_message(&task, response_hash, verified))?,
+        };
+        self.post("verifier/verification_result", Some(&result)).await?;
+
+        Ok(())
+    }
+
+    /// Get the public summaries of all the contributions.
+    pub async fn contributions(&self) -> Result<Vec<TrimmedContributionInfo>> {
+        self.get("contribution_info").await
+    }
+
+    /// Get the public summary of the progress of the ceremony.
+    pub async fn ceremony_status(&self) -> Result<CeremonyStatus> {
+        Ok(Self::send(self.client.get(self.url("ceremony/status")?))
+            .await?
+            .json()
+            .await?)
+    }
+
+    /// Get the aggregates of the ceremony displayed by the dashboard.
+    pub async fn ceremony_stats(&self) -> Result<CeremonyStats> {
+        Ok(Self::send(self.client.get(self.url("stats")?)).await?.json().await?)
+    }
+
+    /// Get the identity of the coordinator, checking that it's signed by the given public key, published by the
+    /// organizers of the ceremony. Fails with [`RequestError::UntrustedCoordinator`] otherwise. The challenges
+    /// downloaded from then on are checked against it.
+    pub async fn coordinator_identity(&mut self, coordinator_public_key: &str) -> Result<CoordinatorIdentity> {
+        let identity: CoordinatorIdentity = Self::send(self.client.get(self.url("coordinator/identity")?))
+            .await?
+            .json()
+            .await?;
+
+        match identity.coordinator_public_key == coordinator_public_key
+            && verify_coordinator_signature(
+                coordinator_public_key,
+                &coordinator_identity_message(&identity),
+                &identity.signature,
+            ) {
+            true => {
+                self.identity = Some(identity.clone());
+                Ok(identity)
+            }
+            false => Err(RequestError::UntrustedCoordinator),
+        }
+    }
+
+    /// Check the connection with the coordinator.
+    pub async fn healthcheck(&self) -> Result<()> {
+        Self::send(self.client.get(self.url("healthcheck")?)).await?;
+
+        Ok(())
+    }
+}
+
+/// Decapsulate the response and, if error, maps [`Response`] error to [`RequestError`].
+async fn decapsulate_response(response: Response) -> Result<Response> {
+    let status = response.status();
+
+    if status.is_success() {
+        Ok(response)
+    } else if status.is_client_error() {
+        Err(RequestError::Client(response.text().await?))
+    } else if status == StatusCode::GATEWAY_TIMEOUT {
+        Err(RequestError::Proxy(response.text().await?))
+    } else {
+        Err(RequestError::Server(response.text().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_request_message() {
+        let keypair = KeyPair::new();
+        let message = signature_message(
+            "POST",
+            "/contributor/heartbeat",
+            1000,
+            keypair.pubkey(),
+            Some((4, "ZGlnZXN0")),
+            None,
+        );
+        assert_eq!(
+            message,
+            format!("POST\n/contributor/heartbeat\n1000\n{}\n4\nZGlnZXN0", keypair.pubkey())
+        );
+
+        let signature = hex::decode(keypair.sign(&message).unwrap()).unwrap();
+        let pubkey = PublicKey::from_slice(&hex::decode(keypair.pubkey()).unwrap()).unwrap();
+        assert!(pubkey
+            .verify(&message, &Signature::from_slice(&signature).unwrap())
+            .is_ok());
+
+        // The signature of the contribution is signed with the request
+        let message = signature_message(
+            "POST",
+            "/contributor/upload_chunk",
+            1000,
+            keypair.pubkey(),
+            None,
+            Some("c2ln"),
+        );
+        assert_eq!(
+            message,
+            format!(
+                "POST\n/contributor/upload_chunk\n1000\n{}\n{}: c2ln",
+                keypair.pubkey(),
+                CONTRIBUTION_SIGNATURE_HEADER
+            )
+        );
+    }
+
+    #[test]
+    fn verify_coordinator_message() {
+        let keypair = KeyPair::new();
+        let message = crate::challenge_message(1, "abcd");
+        let signature = keypair.sign(&message).unwrap();
+        assert!(verify_coordinator_signature(keypair.pubkey(), &message, &signature));
+
+        // Wrong, another coordinator
+        assert!(!verify_coordinator_signature(
+            KeyPair::new().pubkey(),
+            &message,
+            &signature
+        ));
+
+        // Wrong, another challenge
+        assert!(!verify_coordinator_signature(
+            keypair.pubkey(),
+            &crate::challenge_message(2, "abcd"),
+            &signature
+        ));
+
+        // Wrong, malformed signature
+        assert!(!verify_coordinator_signature(
+            keypair.pubkey(),
+            &message,
+            "not_a_signature"
+        ));
+    }
+
+    #[test]
+    fn check_challenge_signature() {
+        let coordinator = KeyPair::new();
+        let mut client = CeremonyClient::new(Url::parse("http://0.0.0.0:8080").unwrap(), KeyPair::new());
+        let signature = coordinator.sign(&crate::challenge_message(1, "abcd")).unwrap();
+
+        // Not checked until the identity of the coordinator is
+        assert!(client.check_challenge_signature(1, Some("abcd"), None).is_ok());
+
+        client.identity = Some(CoordinatorIdentity {
+            coordinator_public_key: coordinator.pubkey().to_string(),
+            parameters: crate::CeremonyParameters {
+                contribution_mode: "Chunked".to_string(),
+                proving_system: "Groth16".to_string(),
+                curve: "Bls12_381".to_string(),
+                power: 10,
+                batch_size: 64,
+                chunk_size: 512,
+                number_of_chunks: 1,
+            },
+            signed_challenges: true,
+            timestamp: 0,
+            signature: String::new(),
+        });
+        assert!(client
+            .check_challenge_signature(1, Some("abcd"), Some(&signature))
+            .is_ok());
+
+        // Wrong, missing signature
+        assert!(matches!(
+            client.check_challenge_signature(1, Some("abcd"), None),
+            Err(RequestError::UntrustedCoordinator)
+        ));
+
+        // Wrong, another challenge
+        assert!(matches!(
+            client.check_challenge_signature(1, Some("abce"), Some(&signature)),
+            Err(RequestError::UntrustedCoordinator)
+        ));
+    }
+}
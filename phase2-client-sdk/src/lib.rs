@@ -0,0 +1,8 @@
+// Documentation
+#![doc = include_str!("../README.md")]
+
+pub mod client;
+pub use client::*;
+
+pub mod types;
+pub use types::*;
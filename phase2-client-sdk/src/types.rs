@@ -0,0 +1,786 @@
+//! Request and response types exchanged with the coordinator.
+//!
+//! The json encoding of these types is the one produced and expected by the REST API of the coordinator.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub const BODY_DIGEST_HEADER: &str = "Digest";
+pub const PUBKEY_HEADER: &str = "ATS-Pubkey";
+pub const SIGNATURE_HEADER: &str = "ATS-Signature";
+pub const CONTENT_LENGTH_HEADER: &str = "Content-Length";
+pub const ACCESS_SECRET_HEADER: &str = "Access-Secret";
+pub const INVITE_CODE_HEADER: &str = "ATS-Invite-Code";
+pub const PRIORITY_TOKEN_HEADER: &str = "ATS-Priority-Token";
+pub const EMAIL_HEADER: &str = "ATS-Email";
+pub const CONTRIBUTION_SIGNATURE_HEADER: &str = "ATS-Contribution-Signature";
+pub const CHALLENGE_HASH_HEADER: &str = "ATS-Challenge-Hash";
+pub const CHALLENGE_SIGNATURE_HEADER: &str = "ATS-Challenge-Signature";
+pub const ESTIMATED_WAIT_HEADER: &str = "ATS-Estimated-Wait";
+pub const TIMESTAMP_HEADER: &str = "ATS-Timestamp";
+pub const CONTENT_RANGE_HEADER: &str = "Content-Range";
+pub const ACCEPT_ENCODING_HEADER: &str = "Accept-Encoding";
+pub const CONTENT_ENCODING_HEADER: &str = "Content-Encoding";
+pub const API_VERSION_HEADER: &str = "ATS-API-Version";
+pub const SESSION_TOKEN_HEADER: &str = "ATS-Session-Token";
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Version of the REST API spoken by this crate, sent in the [`API_VERSION_HEADER`] of the requests and responses.
+pub const API_VERSION: u64 = 1;
+
+/// Prefix of the paths of the current version of the REST API.
+pub const API_PREFIX: &str = "/v1";
+
+/// Maximum difference, in seconds, between the timestamp of a signed request and the clock of the coordinator.
+pub const MAX_REQUEST_AGE: i64 = 300;
+
+/// Produces the message signed by a participant for a request to `path` (including the query, if any), sent
+/// at the unix `timestamp` in seconds. `content` holds the length and the [`base64`] encoded sha-256 digest
+/// of the body, if any, and `contribution_signature` the [`header_digest`] of the
+/// [`CONTRIBUTION_SIGNATURE_HEADER`], if the request carries it.
+pub fn signature_message(
+    method: &str,
+    path: &str,
+    timestamp: i64,
+    pubkey: &str,
+    content: Option<(usize, &str)>,
+    contribution_signature: Option<&str>,
+) -> String {
+    let message = format!("{}\n{}\n{}\n{}", method, path, timestamp, pubkey);
+    let message = match content {
+        Some((len, digest)) => format!("{}\n{}\n{}", message, len, digest),
+        None => message,
+    };
+
+    match contribution_signature {
+        Some(digest) => format!("{}\n{}: {}", message, CONTRIBUTION_SIGNATURE_HEADER, digest),
+        None => message,
+    }
+}
+
+/// Returns the [`base64`] encoded sha-256 digest of the value of a header signed with the request, see
+/// [`signature_message`].
+pub fn header_digest(value: &str) -> String {
+    base64::encode(Sha256::digest(value.as_bytes()))
+}
+
+/// Returns the path of `endpoint` in the current version of the REST API. Relative endpoints stay relative to the
+/// address of the coordinator.
+pub fn versioned_endpoint(endpoint: &str) -> String {
+    match endpoint.strip_prefix('/') {
+        Some(path) => format!("{}/{}", API_PREFIX, path),
+        None => format!("{}/{}", API_PREFIX.trim_start_matches('/'), endpoint),
+    }
+}
+
+/// Produces the message signed by a participant to log in with the `nonce` issued by the coordinator.
+pub fn login_message(pubkey: &str, nonce: &str) -> String {
+    format!("login:{}:{}", pubkey, nonce)
+}
+
+/// Produces the message signed by a contributor to bind its ceremony public key to a Namada address.
+pub fn namada_address_binding_message(pubkey: &str, address: &str) -> String {
+    format!(
+        "I bind my Namada Trusted Setup public key {} to the Namada address {}",
+        pubkey, address
+    )
+}
+
+/// Produces the message signed by a contributor to attest its contribution at the given round with a statement.
+pub fn attestation_statement_message(pubkey: &str, round_height: u64, statement: &str) -> String {
+    format!(
+        "I attest my contribution to the Namada Trusted Setup ceremony at round {} with public key {}:\n{}",
+        round_height, pubkey, statement
+    )
+}
+
+/// Produces the message signed by an external verifier for its result on a contribution, identified by the hash of
+/// the response that was verified.
+pub fn verification_result_message(task: &VerificationTask, response_hash: &str, verified: bool) -> String {
+    format!(
+        "{}:{}:{}:{}:{}",
+        task.round_height, task.chunk_id, task.contribution_id, response_hash, verified
+    )
+}
+
+/// Produces the message signed by the coordinator for the challenge of the given round, identified by its hash, if
+/// it signs the challenges.
+pub fn challenge_message(round_height: u64, challenge_hash: &str) -> String {
+    format!("challenge:{}:{}", round_height, challenge_hash)
+}
+
+/// Produces the message signed by the coordinator for its identity, covering all the fields but the signature.
+pub fn coordinator_identity_message(identity: &CoordinatorIdentity) -> String {
+    let parameters = &identity.parameters;
+    format!(
+        "identity:{}:{}:{}:{}:{}:{}:{}:{}:{}:{}",
+        identity.coordinator_public_key,
+        parameters.contribution_mode,
+        parameters.proving_system,
+        parameters.curve,
+        parameters.power,
+        parameters.batch_size,
+        parameters.chunk_size,
+        parameters.number_of_chunks,
+        identity.signed_challenges,
+        identity.timestamp
+    )
+}
+
+/// The status of the contributor related to the current round.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum ContributorStatus {
+    /// Position of the contributor in the queue and size of the queue. The estimated wait, in seconds, before the turn
+    /// of the contributor is carried by the [`ESTIMATED_WAIT_HEADER`] of the response, once it can be told
+    Queue(u64, u64),
+    Round,
+    Finished,
+    Banned,
+    Other,
+}
+
+/// Cause of the rejection of a contribution, to let contributors react programmatically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionReason {
+    /// The external verifiers agreed against the contribution
+    RejectedByVerifiers,
+    /// The contribution failed the verification of the coordinator
+    VerificationFailed,
+}
+
+/// A contribution rejected after its upload, as notified to its contributor.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ContributionRejection {
+    pub round_height: u64,
+    pub reason: RejectionReason,
+    /// Human-readable detail of the failure
+    pub message: String,
+    /// Unix timestamp, in seconds, of the rejection
+    pub rejected_at: i64,
+}
+
+/// Stable identifier of the cause of a failed request, to let clients react programmatically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    AlreadyContributed,
+    AlreadyInQueue,
+    Banned,
+    BlacklistedToken,
+    CeremonyIsOver,
+    /// The coordinator is shutting down once the contribution in flight completes, no new contribution is accepted
+    /// until it restarts
+    Draining,
+    Dropped,
+    Internal,
+    InvalidContribution,
+    InvalidInviteCode,
+    /// The priority token is malformed, outside of its validity window or already redeemed
+    InvalidPriorityToken,
+    InvalidRequest,
+    /// The session token is invalid or expired, the participant must log in again
+    InvalidSession,
+    InvalidSignature,
+    InvalidToken,
+    LockAlreadyHeld,
+    /// The lock of the challenge expired before the contribution was uploaded, the contributor was dropped
+    LockExpired,
+    NotCurrentContributor,
+    /// The participant must wait for its turn, see [`ErrorResponse::retry_after`]
+    NotYourTurn,
+    /// The body of the request exceeds the limit of the coordinator for its route
+    PayloadTooLarge,
+    /// Too many requests from the client, see [`ErrorResponse::retry_after`]
+    RateLimited,
+    /// The contribution was computed against another challenge than the one issued with the lock
+    StaleChallenge,
+    /// The storage of the coordinator is running out of space, the request can be retried once it's freed
+    StorageUnavailable,
+    /// The participant was dropped for its repeated failures and can't join the queue again for a while
+    Suspended,
+    TokenAlreadyInUse,
+    Unauthorized,
+    UnknownContributor,
+    /// The requested round hasn't started yet
+    UnknownRound,
+    /// The requested version of the REST API isn't served by the coordinator
+    UnsupportedApiVersion,
+}
+
+/// Body of the responses to failed requests.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ErrorResponse {
+    pub code: ErrorCode,
+    pub message: String,
+    /// Seconds after which the request can be retried, if the failure is temporary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+    /// Id of the failed request, to be quoted when reporting the failure, see [`REQUEST_ID_HEADER`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// Public summary of the progress of the ceremony.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CeremonyStatus {
+    pub round_height: u64,
+    pub number_of_chunks: u64,
+    /// Number of participants waiting in the queue
+    pub queue_length: u64,
+    /// Public key of the participant contributing to the current round, if any
+    pub current_contributor: Option<String>,
+    pub verified_contributions: u64,
+}
+
+/// Number of contributions finished during an hour of the ceremony.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HourlyContributions {
+    /// Unix timestamp of the start of the hour
+    pub hour: i64,
+    pub contributions: u64,
+}
+
+/// Aggregates of the ceremony displayed by the dashboard.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CeremonyStats {
+    /// Finished contributions by hour, the oldest first, the hours without any being left out
+    pub contributions_per_hour: Vec<HourlyContributions>,
+    /// Average duration, in seconds, of the finished contributions
+    pub average_contribution_seconds: Option<u64>,
+    /// Median duration, in seconds, of the finished contributions
+    pub median_contribution_seconds: Option<u64>,
+    /// Number of countries the contributions came from, if the coordinator geolocates the contributors
+    pub unique_countries: Option<u64>,
+    /// Share, between 0 and 1, of the started contributions that were dropped
+    pub dropout_rate: f64,
+    /// Number of contributions waiting for their verification
+    pub verification_backlog: u64,
+}
+
+/// A contributor of the ceremony, as listed on the public roll call.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PublicContributor {
+    /// Public key of the contributor, hex encoded
+    pub public_key: String,
+    /// Name chosen by the contributor to appear under, if any
+    pub display_name: Option<String>,
+    pub round_height: u64,
+    /// Blake2b-512, hex encoded, hash of the contribution file
+    pub contribution_hash: String,
+}
+
+/// A page of the contributors of the ceremony, ordered by round height.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ContributorsPage {
+    /// Number of the page, starting from 1
+    pub page: u64,
+    pub per_page: u64,
+    /// Number of contributors across all the pages
+    pub total: u64,
+    pub contributors: Vec<PublicContributor>,
+}
+
+/// Event of the ceremony, published by the coordinator on its `/events` stream with the name of the variant as
+/// event name.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum CeremonyEvent {
+    /// The ceremony advanced to a new round
+    RoundAdvanced { round_height: u64 },
+    /// The contributor uploaded its contribution, which now waits for its verification
+    ContributionAccepted { round_height: u64, public_key: String },
+    /// The participant has been dropped from the current round or from the queue
+    ContributorDropped { public_key: String },
+    /// The verification of a contribution finished, successfully if `verified`
+    VerificationFinished {
+        round_height: u64,
+        contribution_id: u64,
+        verified: bool,
+    },
+    /// The contribution failed its verification, the round restarts with the next contributor
+    ContributionRejected {
+        round_height: u64,
+        public_key: String,
+        reason: RejectionReason,
+    },
+    /// The free space of the storage fell below its low-water mark, new files are refused until it's freed
+    StorageLow { available_bytes: u64 },
+    /// The last contributor waiting in the queue has been assigned to the round, the ceremony stalls after it
+    QueueEmpty { round_height: u64 },
+}
+
+impl CeremonyEvent {
+    /// Returns the name of the event, as serialized in its json encoding.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CeremonyEvent::RoundAdvanced { .. } => "round_advanced",
+            CeremonyEvent::ContributionAccepted { .. } => "contribution_accepted",
+            CeremonyEvent::ContributorDropped { .. } => "contributor_dropped",
+            CeremonyEvent::VerificationFinished { .. } => "verification_finished",
+            CeremonyEvent::ContributionRejected { .. } => "contribution_rejected",
+            CeremonyEvent::StorageLow { .. } => "storage_low",
+            CeremonyEvent::QueueEmpty { .. } => "queue_empty",
+        }
+    }
+}
+
+/// Locator of a contribution file.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ContributionLocator {
+    pub round_height: u64,
+    pub chunk_id: u64,
+    pub contribution_id: u64,
+    pub is_verified: bool,
+}
+
+impl ContributionLocator {
+    pub fn new(round_height: u64, chunk_id: u64, contribution_id: u64, is_verified: bool) -> Self {
+        Self {
+            round_height,
+            chunk_id,
+            contribution_id,
+            is_verified,
+        }
+    }
+
+    pub fn round_height(&self) -> u64 {
+        self.round_height
+    }
+
+    pub fn chunk_id(&self) -> u64 {
+        self.chunk_id
+    }
+
+    pub fn contribution_id(&self) -> u64 {
+        self.contribution_id
+    }
+
+    pub fn is_verified(&self) -> bool {
+        self.is_verified
+    }
+}
+
+/// Locator of the signature of a contribution file.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ContributionSignatureLocator {
+    pub round_height: u64,
+    pub chunk_id: u64,
+    pub contribution_id: u64,
+    pub is_verified: bool,
+}
+
+impl ContributionSignatureLocator {
+    pub fn new(round_height: u64, chunk_id: u64, contribution_id: u64, is_verified: bool) -> Self {
+        Self {
+            round_height,
+            chunk_id,
+            contribution_id,
+            is_verified,
+        }
+    }
+
+    pub fn round_height(&self) -> u64 {
+        self.round_height
+    }
+
+    pub fn chunk_id(&self) -> u64 {
+        self.chunk_id
+    }
+
+    pub fn contribution_id(&self) -> u64 {
+        self.contribution_id
+    }
+
+    pub fn is_verified(&self) -> bool {
+        self.is_verified
+    }
+}
+
+/// The locators assigned to the contributor when locking the challenge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedLocators {
+    pub previous_contribution: ContributionLocator,
+    pub current_contribution: ContributionLocator,
+    pub next_contribution: ContributionLocator,
+    pub next_contribution_file_signature: ContributionSignatureLocator,
+    /// Unix timestamp, in seconds, at which the lock expires. The contribution must be uploaded before it, or the
+    /// contributor is dropped from the round
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+}
+
+impl LockedLocators {
+    /// Get a reference previous contribution's locator.
+    pub fn previous_contribution(&self) -> ContributionLocator {
+        self.previous_contribution
+    }
+
+    /// Get a reference current contribution's locator.
+    pub fn current_contribution(&self) -> ContributionLocator {
+        self.current_contribution
+    }
+
+    /// Get a reference next contribution's locator.
+    pub fn next_contribution(&self) -> ContributionLocator {
+        self.next_contribution
+    }
+
+    /// Get a reference next contribution's signtature locator.
+    pub fn next_contribution_file_signature(&self) -> ContributionSignatureLocator {
+        self.next_contribution_file_signature
+    }
+
+    /// Get the unix timestamp at which the lock expires, if any.
+    pub fn expires_at(&self) -> Option<i64> {
+        self.expires_at
+    }
+}
+
+/// Response to the lock of the challenge. The upload urls are provided only if the coordinator allows direct upload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedChunk {
+    #[serde(flatten)]
+    pub locators: LockedLocators,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_urls: Option<(String, String)>,
+    /// Hash of the challenge, hex encoded, to check its download against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_hash: Option<String>,
+    /// Signature of the [`challenge_message`] of the challenge by the coordinator, hex encoded, if it signs the
+    /// challenges
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_signature: Option<String>,
+}
+
+/// Progress of the resumable upload of a contribution to the coordinator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UploadSession {
+    /// Number of bytes received, the upload resumes at this offset
+    pub received: u64,
+    /// Size in bytes of the whole contribution
+    pub total: u64,
+}
+
+impl UploadSession {
+    /// Returns the value of the [`CONTENT_RANGE_HEADER`] of the part of `len` bytes uploaded next.
+    pub fn content_range(&self, len: u64) -> String {
+        format!("bytes {}-{}/{}", self.received, self.received + len - 1, self.total)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.received == self.total
+    }
+}
+
+/// Compression of a challenge or of a contribution transferred directly with the coordinator. The hashes and the
+/// signatures always refer to the decompressed file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Zstd,
+    Gzip,
+}
+
+impl ContentEncoding {
+    /// Returns the token of the encoding in the [`ACCEPT_ENCODING_HEADER`] and [`CONTENT_ENCODING_HEADER`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Zstd => "zstd",
+            Self::Gzip => "gzip",
+        }
+    }
+
+    /// Picks the encoding of a response among the ones accepted in an [`ACCEPT_ENCODING_HEADER`], preferring zstd.
+    /// Returns [`None`] if the response must not be compressed.
+    pub fn negotiate(accept_encoding: &str) -> Option<Self> {
+        let accepted: Vec<Self> = accept_encoding
+            .split(',')
+            .filter_map(|item| {
+                let mut params = item.split(';').map(str::trim);
+                let encoding = params.next()?.parse().ok()?;
+                let refused = params.any(|param| {
+                    param
+                        .strip_prefix("q=")
+                        .and_then(|q| q.parse::<f32>().ok())
+                        .map_or(false, |q| q <= 0.0)
+                });
+
+                if refused {
+                    None
+                } else {
+                    Some(encoding)
+                }
+            })
+            .collect();
+
+        [Self::Zstd, Self::Gzip]
+            .iter()
+            .find(|encoding| accepted.contains(encoding))
+            .copied()
+    }
+}
+
+impl std::str::FromStr for ContentEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "zstd" => Ok(Self::Zstd),
+            "gzip" => Ok(Self::Gzip),
+            other => Err(format!("Unsupported content encoding {}", other)),
+        }
+    }
+}
+
+/// Request to notify the coordinator of an uploaded contribution.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PostChunkRequest {
+    pub round_height: u64,
+    pub contribution_locator: ContributionLocator,
+    pub contribution_signature_locator: ContributionSignatureLocator,
+}
+
+impl PostChunkRequest {
+    pub fn new(
+        round_height: u64,
+        contribution_locator: ContributionLocator,
+        contribution_signature_locator: ContributionSignatureLocator,
+    ) -> Self {
+        Self {
+            round_height,
+            contribution_locator,
+            contribution_signature_locator,
+        }
+    }
+}
+
+impl From<LockedLocators> for PostChunkRequest {
+    fn from(locators: LockedLocators) -> Self {
+        Self {
+            round_height: locators.next_contribution.round_height,
+            contribution_locator: locators.next_contribution,
+            contribution_signature_locator: locators.next_contribution_file_signature,
+        }
+    }
+}
+
+/// Request to open a session, proving the ownership of the public key by signing the nonce issued by the
+/// coordinator.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LoginRequest {
+    pub pubkey: String,
+    pub nonce: String,
+    /// Signature of the [`login_message`], hex encoded
+    pub signature: String,
+}
+
+/// A session opened by the coordinator. The token is sent in the [`SESSION_TOKEN_HEADER`] in place of the signature
+/// headers of the requests, until it expires.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Session {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A binding between the ceremony public key of a contributor and a Namada address.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NamadaAddressBinding {
+    pub address: String,
+    /// Signature of the [`namada_address_binding_message`], hex encoded
+    pub signature: String,
+}
+
+/// Proof, signed by the coordinator, that a contribution has been accepted for a chunk of a round.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ContributionReceipt {
+    pub round_height: u64,
+    pub chunk_id: u64,
+    /// Blake2b-512, hex encoded, hash of the contribution file
+    pub contribution_hash: String,
+    /// Unix timestamp, in seconds, at which the contribution has been accepted
+    pub timestamp: i64,
+    /// Public key of the coordinator that signed the receipt
+    pub coordinator_public_key: String,
+    /// Signature of the [`message`](Self::message), hex encoded
+    pub signature: String,
+}
+
+impl ContributionReceipt {
+    /// Returns the message signed by the coordinator, `<round_height>:<chunk_id>:<contribution_hash>:<timestamp>`.
+    pub fn message(&self) -> String {
+        format!(
+            "{}:{}:{}:{}",
+            self.round_height, self.chunk_id, self.contribution_hash, self.timestamp
+        )
+    }
+
+    /// Returns `true` if the receipt has been signed by the given public key of the coordinator.
+    /// The public key should be obtained out of band and not trusted from the receipt itself.
+    pub fn verify(&self, coordinator_public_key: &str) -> bool {
+        self.coordinator_public_key == coordinator_public_key
+            && crate::verify_coordinator_signature(coordinator_public_key, &self.message(), &self.signature)
+    }
+}
+
+/// Parameters of the ceremony run by the coordinator, which the contributions are computed for.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CeremonyParameters {
+    pub contribution_mode: String,
+    pub proving_system: String,
+    pub curve: String,
+    pub power: u64,
+    pub batch_size: u64,
+    pub chunk_size: u64,
+    pub number_of_chunks: u64,
+}
+
+/// Public key of the coordinator and parameters of its ceremony, signed by that key. The key must be checked against
+/// the one published by the organizers of the ceremony, not trusted from the response.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CoordinatorIdentity {
+    pub coordinator_public_key: String,
+    pub parameters: CeremonyParameters,
+    /// Whether the coordinator signs the challenges, see [`challenge_message`]
+    pub signed_challenges: bool,
+    /// Unix timestamp, in seconds, at which the identity has been signed
+    pub timestamp: i64,
+    /// Signature of the [`coordinator_identity_message`], hex encoded
+    pub signature: String,
+}
+
+/// A contribution waiting for the results of the external verifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VerificationTask {
+    pub round_height: u64,
+    pub chunk_id: u64,
+    pub contribution_id: u64,
+}
+
+/// The result of the verification of a [`VerificationTask`] by an external verifier.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VerificationResult {
+    pub task: VerificationTask,
+    /// [`hex`] encoded Blake2b-512 hash of the verified response
+    pub response_hash: String,
+    pub verified: bool,
+    /// Signature of the [`verification_result_message`], hex encoded
+    pub signature: String,
+}
+
+/// A free-text attestation of a contribution, with an optional url of a public post.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AttestationStatement {
+    pub statement: String,
+    pub url: Option<String>,
+    /// Signature of the [`attestation_statement_message`], hex encoded
+    pub signature: String,
+}
+
+/// Timestamps of a contribution, as published by the coordinator.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrimmedContributionTimeStamps {
+    pub start_contribution: DateTime<Utc>,
+    pub end_contribution: DateTime<Utc>,
+}
+
+/// Public summary of a contribution, as published by the coordinator.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrimmedContributionInfo {
+    pub full_name: Option<String>,
+    pub public_key: String,
+    pub is_another_machine: bool,
+    pub is_own_seed_of_randomness: bool,
+    pub joined_cohort: u64,
+    pub ceremony_round: u64,
+    pub contribution_hash: String,
+    pub contribution_hash_signature: String,
+    pub attestation: Option<String>,
+    #[serde(default)]
+    pub attestation_verified: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation_statement: Option<AttestationStatement>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namada_address: Option<NamadaAddressBinding>,
+    pub timestamps: TrimmedContributionTimeStamps,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn post_chunk_request_from_locators() {
+        let locator = |contribution_id, is_verified| ContributionLocator {
+            round_height: 1,
+            chunk_id: 0,
+            contribution_id,
+            is_verified,
+        };
+        let locators: LockedLocators = serde_json::from_value(serde_json::json!({
+            "previous_contribution": locator(0, true),
+            "current_contribution": locator(0, true),
+            "next_contribution": locator(1, false),
+            "next_contribution_file_signature": {
+                "round_height": 1,
+                "chunk_id": 0,
+                "contribution_id": 1,
+                "is_verified": false
+            }
+        }))
+        .unwrap();
+
+        let request = PostChunkRequest::from(locators);
+        assert_eq!(request.round_height, 1);
+        assert_eq!(request.contribution_locator, locator(1, false));
+        assert_eq!(request.contribution_signature_locator.contribution_id, 1);
+    }
+
+    #[test]
+    fn ceremony_event_name() {
+        let event = CeremonyEvent::ContributorDropped {
+            public_key: "pubkey".to_string(),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], event.name());
+        assert_eq!(json["public_key"], "pubkey");
+    }
+
+    #[test]
+    fn versioned_endpoints() {
+        assert_eq!(
+            versioned_endpoint("/contributor/heartbeat"),
+            "/v1/contributor/heartbeat"
+        );
+        assert_eq!(versioned_endpoint("contributor/heartbeat"), "v1/contributor/heartbeat");
+    }
+
+    #[test]
+    fn upload_session_content_range() {
+        let session = UploadSession { received: 0, total: 10 };
+        assert_eq!(session.content_range(4), "bytes 0-3/10");
+
+        let session = UploadSession { received: 4, total: 10 };
+        assert_eq!(session.content_range(6), "bytes 4-9/10");
+        assert!(!session.is_complete());
+    }
+
+    #[test]
+    fn content_encoding_negotiation() {
+        assert_eq!(
+            ContentEncoding::negotiate("gzip, deflate, br"),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(
+            ContentEncoding::negotiate("gzip;q=0.5, zstd"),
+            Some(ContentEncoding::Zstd)
+        );
+        assert_eq!(
+            ContentEncoding::negotiate("zstd;q=0, gzip"),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(ContentEncoding::negotiate("br, identity"), None);
+        assert_eq!(ContentEncoding::negotiate(""), None);
+        assert!("deflate".parse::<ContentEncoding>().is_err());
+    }
+}
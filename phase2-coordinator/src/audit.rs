@@ -0,0 +1,487 @@
+//! The audit log of the ceremony: an append-only record of the security-relevant events, such as the joins of the
+//! queue, the locks, the uploads, the outcome of the verifications, the bans and the actions of the operators.
+//!
+//! Each [`AuditRecord`] holds the hash of the previous one and is signed by the coordinator, so that a copy of the log
+//! handed to a third party can be checked with [`verify_chain`]: a record can't be altered, removed or inserted
+//! without breaking the chain.
+//!
+//! The records appended while serving a REST request carry the id of the request, see [`REQUEST_ID`], for the
+//! entries to be matched with the logs and with the errors reported by the contributors.
+
+use crate::{
+    authentication::{verify_hex_signature, Signature},
+    CoordinatorError,
+};
+
+use blake2::{Blake2b512, Digest};
+use fs_err::{self as fs, File, OpenOptions};
+use phase2_client_sdk::RejectionReason;
+use rocket::FromFormField;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+use tracing::{trace, warn};
+
+/// Name of the file of the audit log, in the base directory
+pub const AUDIT_LOG_FILE: &str = "audit.log";
+
+rocket::tokio::task_local! {
+    /// Id of the REST request served by the task, scoped by the handlers of the routes
+    pub static REQUEST_ID: String;
+}
+
+/// Returns the id of the REST request being served, if any.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// A security-relevant event of the ceremony.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEvent {
+    QueueJoin {
+        participant: String,
+        ip: Option<String>,
+    },
+    Lock {
+        participant: String,
+        chunk_id: u64,
+    },
+    Upload {
+        participant: String,
+        chunk_id: u64,
+        contribution_id: u64,
+    },
+    ContributionAccepted {
+        verifier: String,
+        chunk_id: u64,
+        contribution_id: u64,
+    },
+    ContributionRejected {
+        participant: String,
+        round_height: u64,
+        reason: RejectionReason,
+    },
+    Ban {
+        participant: Option<String>,
+        ip: Option<String>,
+    },
+    Unban {
+        participant: Option<String>,
+        ip: Option<String>,
+    },
+    /// An action of an operator through the admin endpoints
+    AdminAction {
+        /// Public key of the operator
+        operator: String,
+        action: String,
+    },
+}
+
+impl AuditEvent {
+    /// Returns the name of the event, as serialized in its json encoding.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuditEvent::QueueJoin { .. } => "queue_join",
+            AuditEvent::Lock { .. } => "lock",
+            AuditEvent::Upload { .. } => "upload",
+            AuditEvent::ContributionAccepted { .. } => "contribution_accepted",
+            AuditEvent::ContributionRejected { .. } => "contribution_rejected",
+            AuditEvent::Ban { .. } => "ban",
+            AuditEvent::Unban { .. } => "unban",
+            AuditEvent::AdminAction { .. } => "admin_action",
+        }
+    }
+}
+
+/// A record of the [AuditLog], chained to the previous one by its hash.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Position of the record in the log, starting at 0
+    pub sequence: u64,
+    /// Unix timestamp, in seconds, of the event
+    pub timestamp: i64,
+    pub event: AuditEvent,
+    /// Id of the REST request that caused the event, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Hash of the previous record, empty for the first one
+    pub previous_hash: String,
+    /// Blake2b-512, hex encoded, hash of the [`digest`](Self::digest) of the record
+    pub hash: String,
+    /// Public key of the coordinator that signed the record
+    pub coordinator_public_key: String,
+    /// Signature of the [`message`](Self::message), hex encoded
+    pub signature: String,
+}
+
+impl AuditRecord {
+    /// Signs a new record with the given signature scheme and signing key of the coordinator.
+    pub fn try_new(
+        signature: &dyn Signature,
+        signing_key: &str,
+        coordinator_public_key: &str,
+        sequence: u64,
+        timestamp: i64,
+        event: AuditEvent,
+        request_id: Option<String>,
+        previous_hash: String,
+    ) -> anyhow::Result<Self> {
+        let mut record = Self {
+            sequence,
+            timestamp,
+            event,
+            request_id,
+            previous_hash,
+            hash: String::new(),
+            coordinator_public_key: coordinator_public_key.to_string(),
+            signature: String::new(),
+        };
+        record.hash = record.digest()?;
+        record.signature = signature.sign(signing_key, &record.message())?;
+
+        Ok(record)
+    }
+
+    /// Returns the hash of the content of the record, covering the previous hash. The request id is only hashed when
+    /// present, for the records written before its introduction to keep their hashes.
+    pub fn digest(&self) -> anyhow::Result<String> {
+        let mut hasher = Blake2b512::new();
+        hasher.update(format!("{}:{}:{}:", self.sequence, self.timestamp, self.previous_hash));
+        hasher.update(serde_json::to_vec(&self.event)?);
+        if let Some(request_id) = &self.request_id {
+            hasher.update(format!(":{}", request_id));
+        }
+
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Returns the message signed by the coordinator.
+    pub fn message(&self) -> String {
+        format!("audit:{}", self.hash)
+    }
+
+    /// Returns `true` if the hash of the record matches its content and the record has been signed by its
+    /// coordinator key.
+    pub fn verify(&self) -> bool {
+        if !matches!(self.digest(), Ok(hash) if hash == self.hash) {
+            return false;
+        }
+
+        verify_hex_signature(&self.coordinator_public_key, &self.message(), &self.signature)
+    }
+}
+
+/// Checks that the records form an unbroken chain from the start of the log, each of them being signed by the
+/// coordinator. Returns the sequence of the first invalid record, if any.
+///
+/// The keys of the records should be checked out of band against the keys of the coordinator, see
+/// [`verify_rotation_chain`](crate::authentication::verify_rotation_chain).
+pub fn verify_chain(records: &[AuditRecord]) -> Result<(), u64> {
+    let mut previous_hash = "";
+    for (sequence, record) in records.iter().enumerate() {
+        if record.sequence != sequence as u64 || record.previous_hash != previous_hash || !record.verify() {
+            return Err(sequence as u64);
+        }
+        previous_hash = &record.hash;
+    }
+
+    Ok(())
+}
+
+/// Format of an export of the audit log, see [`export`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromFormField)]
+pub enum AuditExportFormat {
+    /// One line per record, the fields of the event being json encoded in the `details` column
+    Csv,
+    /// A json array of the records
+    Json,
+}
+
+/// Header of the csv export of the audit log
+const CSV_HEADER: &str =
+    "sequence,timestamp,event,details,request_id,previous_hash,hash,coordinator_public_key,signature";
+
+/// Encodes the records in the given format. The records keep their hashes and signatures: each of them can be checked
+/// on its own with [`AuditRecord::verify`], and a whole log with [`verify_chain`].
+pub fn export(records: &[AuditRecord], format: AuditExportFormat) -> Result<Vec<u8>, CoordinatorError> {
+    match format {
+        AuditExportFormat::Json => Ok(serde_json::to_vec(records)?),
+        AuditExportFormat::Csv => {
+            let mut csv = format!("{}\n", CSV_HEADER);
+            for record in records {
+                let details = match serde_json::to_value(&record.event)? {
+                    serde_json::Value::Object(mut event) => event.remove(record.event.name()).unwrap_or_default(),
+                    _ => serde_json::Value::Null,
+                };
+                let fields = [
+                    record.sequence.to_string(),
+                    record.timestamp.to_string(),
+                    record.event.name().to_string(),
+                    details.to_string(),
+                    record.request_id.clone().unwrap_or_default(),
+                    record.previous_hash.clone(),
+                    record.hash.clone(),
+                    record.coordinator_public_key.clone(),
+                    record.signature.clone(),
+                ];
+                let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                csv.push_str(&row.join(","));
+                csv.push('\n');
+            }
+
+            Ok(csv.into_bytes())
+        }
+    }
+}
+
+/// Quotes the field if it holds a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    match field.contains(&[',', '"', '\n', '\r'][..]) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+/// An append-only log of the [AuditRecord]s, one JSON record per line. Each record is synced to disk before the
+/// action it describes is acknowledged.
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    file: File,
+    /// Sequence of the next record
+    sequence: u64,
+    /// Hash of the last record
+    last_hash: String,
+}
+
+impl AuditLog {
+    /// Opens the log at the given path, creating it if needed. A record torn by a crash while being appended is
+    /// discarded.
+    pub fn open(path: &Path) -> Result<Self, CoordinatorError> {
+        let mut file = OpenOptions::new().read(true).write(true).create(true).open(path)?;
+        let mut bytes = vec![];
+        file.read_to_end(&mut bytes)?;
+
+        let mut last: Option<AuditRecord> = None;
+        let mut len = 0;
+        while let Some(end) = bytes[len..].iter().position(|b| *b == b'\n') {
+            match serde_json::from_slice(&bytes[len..len + end]) {
+                Ok(record) => last = Some(record),
+                Err(_) => break,
+            }
+            len += end + 1;
+        }
+        if len < bytes.len() {
+            warn!("Discarding the tail of the audit log after {} bytes", len);
+            file.set_len(len as u64)?;
+        }
+        file.seek(SeekFrom::Start(len as u64))?;
+
+        let (sequence, last_hash) = match last {
+            Some(record) => (record.sequence + 1, record.hash),
+            None => (0, String::new()),
+        };
+        trace!("Opened the audit log with {} records", sequence);
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            sequence,
+            last_hash,
+        })
+    }
+
+    /// Signs the event as the next record of the log, appends it and syncs it to disk. The record carries the id of
+    /// the request being served, if any.
+    pub fn append(
+        &mut self,
+        signature: &dyn Signature,
+        signing_key: &str,
+        coordinator_public_key: &str,
+        timestamp: i64,
+        event: AuditEvent,
+    ) -> Result<AuditRecord, CoordinatorError> {
+        let record = AuditRecord::try_new(
+            signature,
+            signing_key,
+            coordinator_public_key,
+            self.sequence,
+            timestamp,
+            event,
+            current_request_id(),
+            self.last_hash.clone(),
+        )?;
+
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        self.file.sync_data()?;
+        self.sequence += 1;
+        self.last_hash = record.hash.clone();
+
+        Ok(record)
+    }
+
+    /// Reads all the records of the log, the oldest first.
+    pub fn records(&self) -> Result<Vec<AuditRecord>, CoordinatorError> {
+        let bytes = fs::read(&self.path)?;
+        let mut records = vec![];
+        for line in bytes.split(|b| *b == b'\n').filter(|line| !line.is_empty()) {
+            records.push(serde_json::from_slice(line)?);
+        }
+
+        Ok(records)
+    }
+
+    /// Returns the number of records in the log.
+    pub fn len(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns `true` if the log holds no record.
+    pub fn is_empty(&self) -> bool {
+        self.sequence == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::{KeyPair, Production};
+
+    #[test]
+    fn test_audit_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AUDIT_LOG_FILE);
+        let keypair = KeyPair::new();
+        let events = vec![
+            AuditEvent::QueueJoin {
+                participant: String::from("contributor"),
+                ip: None,
+            },
+            AuditEvent::Lock {
+                participant: String::from("contributor"),
+                chunk_id: 0,
+            },
+            AuditEvent::Ban {
+                participant: Some(String::from("contributor")),
+                ip: None,
+            },
+        ];
+
+        let mut log = AuditLog::open(&path).unwrap();
+        assert!(log.is_empty());
+        let (sigkey, pubkey) = (keypair.sigkey(), keypair.pubkey());
+        for (timestamp, event) in events.iter().enumerate() {
+            log.append(&Production, sigkey, pubkey, timestamp as i64, event.clone())
+                .unwrap();
+        }
+        drop(log);
+
+        // The chain goes on after a restart, a record torn by a crash being discarded
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"sequence\":3,\"tim")
+            .unwrap();
+        let mut log = AuditLog::open(&path).unwrap();
+        assert_eq!(log.len(), 3);
+
+        // The record appended while serving a request carries its id
+        REQUEST_ID
+            .sync_scope(String::from("request-3"), || {
+                log.append(
+                    &Production,
+                    sigkey,
+                    pubkey,
+                    3,
+                    AuditEvent::AdminAction {
+                        operator: pubkey.to_string(),
+                        action: String::from("rotate_key"),
+                    },
+                )
+            })
+            .unwrap();
+
+        let records = log.records().unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].request_id, None);
+        assert_eq!(records[3].request_id.as_deref(), Some("request-3"));
+        let logged: Vec<AuditEvent> = records.iter().take(3).map(|record| record.event.clone()).collect();
+        assert_eq!(logged, events);
+        assert_eq!(verify_chain(&records), Ok(()));
+
+        // Wrong, tampered event
+        let mut tampered = records.clone();
+        tampered[1].event = AuditEvent::Lock {
+            participant: String::from("other"),
+            chunk_id: 0,
+        };
+        assert_eq!(verify_chain(&tampered), Err(1));
+
+        // Wrong, removed record
+        let mut removed = records.clone();
+        removed.remove(2);
+        assert_eq!(verify_chain(&removed), Err(2));
+
+        // Wrong, record signed by another key
+        let mut forged = records;
+        let other = KeyPair::new();
+        forged[3] = AuditRecord::try_new(
+            &Production,
+            other.sigkey(),
+            pubkey,
+            3,
+            3,
+            forged[3].event.clone(),
+            None,
+            forged[2].hash.clone(),
+        )
+        .unwrap();
+        assert_eq!(verify_chain(&forged), Err(3));
+    }
+
+    #[test]
+    fn test_audit_export() {
+        let keypair = KeyPair::new();
+        let event = AuditEvent::AdminAction {
+            operator: keypair.pubkey().to_string(),
+            action: String::from("mint 2 invite codes, \"cohort\" 1"),
+        };
+        let record = AuditRecord::try_new(
+            &Production,
+            keypair.sigkey(),
+            keypair.pubkey(),
+            0,
+            10,
+            event,
+            Some(String::from("c0ffee")),
+            String::new(),
+        )
+        .unwrap();
+
+        let json = export(&[record.clone()], AuditExportFormat::Json).unwrap();
+        let parsed: Vec<AuditRecord> = serde_json::from_slice(&json).unwrap();
+        assert_eq!(parsed, vec![record.clone()]);
+
+        // The details holding separators and quotes are quoted
+        let csv = String::from_utf8(export(&[record.clone()], AuditExportFormat::Csv).unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        let details = format!(
+            r#""{{""action"":""mint 2 invite codes, \""cohort\"" 1"",""operator"":""{}""}}""#,
+            keypair.pubkey()
+        );
+        assert_eq!(
+            lines[1],
+            format!(
+                "0,10,admin_action,{},c0ffee,,{},{},{}",
+                details, record.hash, record.coordinator_public_key, record.signature
+            )
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use crate::authentication::Signature;
+use anyhow::anyhow;
+use blst::{
+    min_pk::{AggregateSignature, PublicKey, SecretKey, Signature as BlsSignature},
+    BLST_ERROR,
+};
+use std::collections::HashSet;
+
+/// Domain separation tag of the basic scheme of the IETF BLS signature draft, with the signatures in G2
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// Length of a compressed public key, in G1
+pub const BLS_PUBLIC_KEY_LENGTH: usize = 48;
+
+/// The authentication based on BLS12-381 signatures, whose signatures can be aggregated into a single one.
+///
+/// Signing keys are expected to be the [`hex`] encoded 32 bytes scalar, public keys the [`hex`] encoded compressed
+/// points of G1 and signatures the [`hex`] encoded compressed points of G2. As in the basic scheme, an aggregate is
+/// only valid over distinct messages, which prevents rogue key attacks without proofs of possession.
+pub struct Bls;
+
+impl Bls {
+    /// Derives a signing key from at least 32 bytes of input key material, returns it [`hex`] encoded.
+    pub fn signing_key(ikm: &[u8]) -> anyhow::Result<String> {
+        let signing_key = SecretKey::key_gen(ikm, &[]).map_err(|e| anyhow!("Invalid key material: {:?}", e))?;
+
+        Ok(hex::encode(signing_key.to_bytes()))
+    }
+
+    /// Returns the [`hex`] encoded public key of the given signing key.
+    pub fn public_key(signing_key: &str) -> anyhow::Result<String> {
+        Ok(hex::encode(parse_signing_key(signing_key)?.sk_to_pk().compress()))
+    }
+
+    /// Aggregates the given [`hex`] encoded signatures, returns the aggregate [`hex`] encoded.
+    pub fn aggregate(signatures: &[&str]) -> anyhow::Result<String> {
+        let signatures = signatures
+            .iter()
+            .map(|s| parse_signature(s).ok_or_else(|| anyhow!("Invalid BLS signature {}", s)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let signatures: Vec<&BlsSignature> = signatures.iter().collect();
+
+        let aggregate =
+            AggregateSignature::aggregate(&signatures, true).map_err(|e| anyhow!("Aggregation failed: {:?}", e))?;
+
+        Ok(hex::encode(aggregate.to_signature().compress()))
+    }
+
+    /// Verifies an aggregate signature of the given messages, each signed by the public key with the same index.
+    pub fn aggregate_verify(public_keys: &[&str], messages: &[&str], aggregate: &str) -> bool {
+        if public_keys.is_empty() || public_keys.len() != messages.len() {
+            return false;
+        }
+        if messages.iter().collect::<HashSet<_>>().len() != messages.len() {
+            return false;
+        }
+
+        let public_keys: Option<Vec<PublicKey>> = public_keys.iter().map(|k| parse_public_key(k)).collect();
+        let public_keys = match public_keys {
+            Some(public_keys) => public_keys,
+            None => return false,
+        };
+        let aggregate = match parse_signature(aggregate) {
+            Some(aggregate) => aggregate,
+            None => return false,
+        };
+        let public_keys: Vec<&PublicKey> = public_keys.iter().collect();
+        let messages: Vec<&[u8]> = messages.iter().map(|m| m.as_bytes()).collect();
+
+        aggregate.aggregate_verify(true, &messages, DST, &public_keys, true) == BLST_ERROR::BLST_SUCCESS
+    }
+}
+
+fn parse_signing_key(signing_key: &str) -> anyhow::Result<SecretKey> {
+    SecretKey::from_bytes(&hex::decode(signing_key)?).map_err(|e| anyhow!("Invalid BLS signing key: {:?}", e))
+}
+
+fn parse_public_key(public_key: &str) -> Option<PublicKey> {
+    hex::decode(public_key)
+        .ok()
+        .and_then(|bytes| PublicKey::from_bytes(&bytes).ok())
+}
+
+fn parse_signature(signature: &str) -> Option<BlsSignature> {
+    hex::decode(signature)
+        .ok()
+        .and_then(|bytes| BlsSignature::from_bytes(&bytes).ok())
+}
+
+impl Signature for Bls {
+    /// Returns the name of the signature scheme.
+    fn name(&self) -> String {
+        String::from("Bls")
+    }
+
+    /// Returns `true` if the signature scheme is safe for use in production.
+    fn is_secure(&self) -> bool {
+        true
+    }
+
+    /// Signs the given message using the given signing key,
+    /// and returns the signature as a [`hex`] encoded string.
+    fn sign(&self, signing_key: &str, message: &str) -> anyhow::Result<String> {
+        let signature = parse_signing_key(signing_key)?.sign(message.as_bytes(), DST, &[]);
+
+        Ok(hex::encode(signature.compress()))
+    }
+
+    /// Verifies the given signature for the given message and public key,
+    /// and returns `true` if the signature is valid. Malformed keys and signatures are not valid.
+    fn verify(&self, public_key: &str, message: &str, signature: &str) -> bool {
+        match (parse_public_key(public_key), parse_signature(signature)) {
+            (Some(public_key), Some(signature)) => {
+                signature.verify(true, message.as_bytes(), DST, &[], &public_key, true) == BLST_ERROR::BLST_SUCCESS
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(n: u8) -> Vec<(String, String)> {
+        (0..n)
+            .map(|i| {
+                let signing_key = Bls::signing_key(&[i; 32]).unwrap();
+                let public_key = Bls::public_key(&signing_key).unwrap();
+                (signing_key, public_key)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let (signing_key, public_key) = keys(1).pop().unwrap();
+        let msg = "This is the message to sign";
+        let signature = Bls.sign(&signing_key, msg).unwrap();
+
+        assert!(Bls.verify(&public_key, msg, &signature));
+        assert!(!Bls.verify(&public_key, "Another message", &signature));
+        assert!(!Bls.verify("00", msg, &signature));
+    }
+
+    #[test]
+    fn aggregate_and_verify() {
+        let keys = keys(3);
+        let messages: Vec<String> = (0..keys.len()).map(|i| format!("message {}", i)).collect();
+        let signatures: Vec<String> = keys
+            .iter()
+            .zip(&messages)
+            .map(|((signing_key, _), message)| Bls.sign(signing_key, message).unwrap())
+            .collect();
+        let aggregate = Bls::aggregate(&signatures.iter().map(String::as_str).collect::<Vec<_>>()).unwrap();
+
+        let public_keys: Vec<&str> = keys.iter().map(|(_, public_key)| public_key.as_str()).collect();
+        let messages: Vec<&str> = messages.iter().map(String::as_str).collect();
+        assert!(Bls::aggregate_verify(&public_keys, &messages, &aggregate));
+
+        // Messages swapped between signers
+        assert!(!Bls::aggregate_verify(
+            &public_keys,
+            &[messages[1], messages[0], messages[2]],
+            &aggregate
+        ));
+        // Missing signer
+        assert!(!Bls::aggregate_verify(&public_keys[..2], &messages[..2], &aggregate));
+        // Duplicated messages
+        assert!(!Bls::aggregate_verify(&public_keys, &[messages[0]; 3], &aggregate));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::authentication::{verify_hex_signature, Signature};
+use serde::{Deserialize, Serialize};
+
+/// Hand-off statement, signed by the previous key of the coordinator, that the given new key replaces it from the
+/// given round on.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct KeyRotation {
+    /// Height of the round during which the new key has been put in use
+    pub round_height: u64,
+    pub old_public_key: String,
+    pub new_public_key: String,
+    /// Unix timestamp, in seconds, of the rotation
+    pub timestamp: i64,
+    /// Signature of the [`message`](Self::message) by the old key, hex encoded
+    pub signature: String,
+}
+
+impl KeyRotation {
+    /// Signs a new hand-off statement with the given signature scheme and the signing key of the old key.
+    pub fn try_new(
+        signature: &dyn Signature,
+        old_signing_key: &str,
+        old_public_key: &str,
+        new_public_key: &str,
+        round_height: u64,
+        timestamp: i64,
+    ) -> anyhow::Result<Self> {
+        let mut rotation = Self {
+            round_height,
+            old_public_key: old_public_key.to_string(),
+            new_public_key: new_public_key.to_string(),
+            timestamp,
+            signature: String::new(),
+        };
+        rotation.signature = signature.sign(old_signing_key, &rotation.message())?;
+
+        Ok(rotation)
+    }
+
+    /// Returns the message signed by the old key.
+    pub fn message(&self) -> String {
+        format!(
+            "key-rotation:{}:{}:{}:{}",
+            self.round_height, self.old_public_key, self.new_public_key, self.timestamp
+        )
+    }
+
+    /// Returns `true` if the statement has been signed by its old key.
+    pub fn verify(&self) -> bool {
+        verify_hex_signature(&self.old_public_key, &self.message(), &self.signature)
+    }
+}
+
+/// Returns the public keys of the coordinator active during the given round: the key in use at its beginning,
+/// followed by the ones rotated in during the round. `initial_public_key` is the key used before any rotation.
+pub fn active_public_keys<'a>(
+    initial_public_key: &'a str,
+    rotations: &'a [KeyRotation],
+    round_height: u64,
+) -> Vec<&'a str> {
+    let start_key = rotations
+        .iter()
+        .rev()
+        .find(|r| r.round_height < round_height)
+        .map(|r| r.new_public_key.as_str())
+        .unwrap_or(initial_public_key);
+
+    std::iter::once(start_key)
+        .chain(
+            rotations
+                .iter()
+                .filter(|r| r.round_height == round_height)
+                .map(|r| r.new_public_key.as_str()),
+        )
+        .collect()
+}
+
+/// Returns `true` if each statement is validly signed and hands off from the key of the previous one.
+pub fn verify_rotation_chain(rotations: &[KeyRotation]) -> bool {
+    rotations.iter().all(KeyRotation::verify)
+        && rotations
+            .windows(2)
+            .all(|w| w[0].new_public_key == w[1].old_public_key && w[0].round_height <= w[1].round_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::{KeyPair, Production};
+
+    fn rotate(old: &KeyPair, new: &KeyPair, round_height: u64) -> KeyRotation {
+        KeyRotation::try_new(&Production, old.sigkey(), old.pubkey(), new.pubkey(), round_height, 10).unwrap()
+    }
+
+    #[test]
+    fn verify_rotation() {
+        let (old, new) = (KeyPair::new(), KeyPair::new());
+        let rotation = rotate(&old, &new, 3);
+        assert!(rotation.verify());
+
+        // Wrong, tampered statement
+        let mut tampered = rotation.clone();
+        tampered.new_public_key = KeyPair::new().pubkey().to_string();
+        assert!(!tampered.verify());
+
+        // Wrong, signed by the new key
+        let mut tampered = rotation.clone();
+        tampered.signature = Production.sign(new.sigkey(), &rotation.message()).unwrap();
+        assert!(!tampered.verify());
+
+        // Wrong, malformed signature
+        tampered.signature = String::from("not_a_signature");
+        assert!(!tampered.verify());
+    }
+
+    #[test]
+    fn active_keys_by_round() {
+        let keys: Vec<KeyPair> = (0..3).map(|_| KeyPair::new()).collect();
+        let rotations = vec![rotate(&keys[0], &keys[1], 2), rotate(&keys[1], &keys[2], 4)];
+        assert!(verify_rotation_chain(&rotations));
+
+        assert_eq!(
+            active_public_keys(keys[0].pubkey(), &rotations, 1),
+            vec![keys[0].pubkey()]
+        );
+        assert_eq!(
+            active_public_keys(keys[0].pubkey(), &rotations, 2),
+            vec![keys[0].pubkey(), keys[1].pubkey()]
+        );
+        assert_eq!(
+            active_public_keys(keys[0].pubkey(), &rotations, 3),
+            vec![keys[1].pubkey()]
+        );
+        assert_eq!(
+            active_public_keys(keys[0].pubkey(), &rotations, 5),
+            vec![keys[2].pubkey()]
+        );
+
+        // Wrong, broken chain
+        assert!(!verify_rotation_chain(&[rotations[1].clone(), rotations[0].clone()]));
+    }
+}
@@ -0,0 +1,362 @@
+use crate::authentication::{KeyPair, Production, Signature};
+use anyhow::anyhow;
+use std::sync::{Mutex, MutexGuard};
+
+/// The default BIP44 derivation path of the signing key on the device, with the coin type of Namada
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/877'/0'/0'/0'";
+
+/// Class of the APDU commands of the signing app
+const CLA: u8 = 0x57;
+/// Returns the ed25519 public key at the derivation path
+const INS_GET_PUBKEY: u8 = 0x01;
+/// Signs the message with the ed25519 key at the derivation path, after the confirmation of the user
+const INS_SIGN_MESSAGE: u8 = 0x04;
+
+/// First packet of a signing request, carrying the derivation path
+const P1_INIT: u8 = 0x00;
+/// Intermediate packet of the message
+const P1_ADD: u8 = 0x01;
+/// Last packet of the message, the device answers with the signature
+const P1_LAST: u8 = 0x02;
+
+/// Maximum length of the data of a single APDU command
+const CHUNK_SIZE: usize = 250;
+
+/// Status word of a successful command
+const SW_OK: u16 = 0x9000;
+/// Status word of a command rejected by the user on the device
+const SW_REJECTED: u16 = 0x6986;
+
+const HARDENED: u32 = 0x8000_0000;
+const PUBLIC_KEY_LENGTH: usize = 32;
+const SIGNATURE_LENGTH: usize = 64;
+
+/// An APDU command sent to the signing app of the device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApduCommand {
+    pub cla: u8,
+    pub ins: u8,
+    pub p1: u8,
+    pub p2: u8,
+    pub data: Vec<u8>,
+}
+
+/// The channel to a Ledger device.
+pub trait LedgerTransport: Send + Sync {
+    /// Sends the command to the device and returns the data of its answer along with the status word.
+    fn exchange(&self, command: &ApduCommand) -> anyhow::Result<(Vec<u8>, u16)>;
+}
+
+/// The transport to a Ledger device connected over USB HID.
+#[cfg(feature = "ledger")]
+pub struct HidTransport(ledger_transport_hid::TransportNativeHID);
+
+#[cfg(feature = "ledger")]
+impl HidTransport {
+    /// Connects to the first Ledger device found.
+    pub fn new() -> anyhow::Result<Self> {
+        let api = ledger_transport_hid::hidapi::HidApi::new()?;
+
+        Ok(Self(ledger_transport_hid::TransportNativeHID::new(&api)?))
+    }
+}
+
+#[cfg(feature = "ledger")]
+impl LedgerTransport for HidTransport {
+    fn exchange(&self, command: &ApduCommand) -> anyhow::Result<(Vec<u8>, u16)> {
+        let answer = self.0.exchange(&ledger_transport::APDUCommand {
+            cla: command.cla,
+            ins: command.ins,
+            p1: command.p1,
+            p2: command.p2,
+            data: command.data.as_slice(),
+        })?;
+
+        Ok((answer.data().to_vec(), answer.retcode()))
+    }
+}
+
+/// The authentication based on an ed25519 key held by a Ledger device. The signing key passed to
+/// [`Signature::sign`] is the BIP44 derivation path of the key on the device (see [`DEFAULT_DERIVATION_PATH`]):
+/// the key never leaves the device and every signature has to be confirmed by the user.
+///
+/// Signatures are encoded like the ones of [`Production`], which is used for verification.
+///
+/// A signature spans several commands: they are sent while holding the device, so that the commands of concurrent
+/// signatures don't interleave on it. [`Signature::sign`] blocks until the user confirms on the device, and must be
+/// called outside of the async runtimes.
+pub struct Ledger<T: LedgerTransport> {
+    transport: T,
+    /// Held while a sequence of commands is sent to the device
+    device: Mutex<()>,
+}
+
+impl<T: LedgerTransport> Ledger<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            device: Mutex::new(()),
+        }
+    }
+
+    /// Waits for the device to be free of the commands of other requests.
+    fn hold_device(&self) -> MutexGuard<'_, ()> {
+        // The device is reset by the first command of every request, even after a request failed midway
+        self.device.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Sends a command to the signing app and returns the data of the answer, failing on any status but [`SW_OK`].
+    fn send(&self, ins: u8, p1: u8, data: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        let (answer, status) = self.transport.exchange(&ApduCommand {
+            cla: CLA,
+            ins,
+            p1,
+            p2: 0,
+            data,
+        })?;
+
+        match status {
+            SW_OK => Ok(answer),
+            SW_REJECTED => Err(anyhow!("The request was rejected on the Ledger device")),
+            status => Err(anyhow!("The Ledger device answered with status {:#06x}", status)),
+        }
+    }
+
+    /// Returns the [`hex`] encoded public key of the key at the given derivation path.
+    pub fn public_key(&self, derivation_path: &str) -> anyhow::Result<String> {
+        let _device = self.hold_device();
+        let public_key = self.send(INS_GET_PUBKEY, 0, serialize_path(derivation_path)?)?;
+
+        if public_key.len() != PUBLIC_KEY_LENGTH {
+            return Err(anyhow!("Unexpected public key length from the Ledger device"));
+        }
+
+        Ok(hex::encode(public_key))
+    }
+
+    /// Returns the keypair of the key at the given derivation path, to be signed with through this [`Ledger`].
+    pub fn keypair(&self, derivation_path: &str) -> anyhow::Result<KeyPair> {
+        Ok(KeyPair::on_device(
+            self.public_key(derivation_path)?,
+            derivation_path.to_owned(),
+        ))
+    }
+}
+
+impl<T: LedgerTransport> Signature for Ledger<T> {
+    /// Returns the name of the signature scheme.
+    fn name(&self) -> String {
+        String::from("Ledger")
+    }
+
+    /// Returns `true` if the signature scheme is safe for use in production.
+    fn is_secure(&self) -> bool {
+        true
+    }
+
+    /// Signs the given message with the key at the derivation path `signing_key`,
+    /// and returns the signature as a [`hex`] encoded string.
+    fn sign(&self, signing_key: &str, message: &str) -> anyhow::Result<String> {
+        let _device = self.hold_device();
+        self.send(INS_SIGN_MESSAGE, P1_INIT, serialize_path(signing_key)?)?;
+
+        let mut chunks: Vec<&[u8]> = message.as_bytes().chunks(CHUNK_SIZE).collect();
+        if chunks.is_empty() {
+            chunks.push(&[]);
+        }
+        let mut signature = Vec::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let p1 = if i + 1 == chunks.len() { P1_LAST } else { P1_ADD };
+            signature = self.send(INS_SIGN_MESSAGE, p1, chunk.to_vec())?;
+        }
+
+        if signature.len() != SIGNATURE_LENGTH {
+            return Err(anyhow!("Unexpected signature length from the Ledger device"));
+        }
+
+        Ok(hex::encode(signature))
+    }
+
+    /// Verifies the given signature for the given message and public key,
+    /// and returns `true` if the signature is valid.
+    fn verify(&self, public_key: &str, message: &str, signature: &str) -> bool {
+        Production.verify(public_key, message, signature)
+    }
+}
+
+/// Serializes a derivation path like `m/44'/877'/0'/0'/0'` as its number of components followed by each component
+/// in big endian.
+fn serialize_path(path: &str) -> anyhow::Result<Vec<u8>> {
+    let components = path
+        .strip_prefix("m/")
+        .ok_or_else(|| anyhow!("Invalid derivation path {}", path))?
+        .split('/')
+        .map(|component| {
+            let (index, hardened) = match component.strip_suffix('\'') {
+                Some(index) => (index, HARDENED),
+                None => (component, 0),
+            };
+            let index: u32 = index.parse().map_err(|_| anyhow!("Invalid derivation path {}", path))?;
+            if index >= HARDENED {
+                return Err(anyhow!("Invalid derivation path {}", path));
+            }
+
+            Ok(index | hardened)
+        })
+        .collect::<anyhow::Result<Vec<u32>>>()?;
+
+    let mut serialized = vec![components.len() as u8];
+    for component in components {
+        serialized.extend_from_slice(&component.to_be_bytes());
+    }
+
+    Ok(serialized)
+}
+
+/// A transport emulating the signing app of a Ledger device with the given keypair, for tests.
+#[cfg(any(test, feature = "testing"))]
+pub struct MockTransport {
+    keypair: KeyPair,
+    /// Whether the user rejects the signing requests on the device
+    reject: bool,
+    /// The message being received by the device
+    message: Mutex<Option<Vec<u8>>>,
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl MockTransport {
+    pub fn new(keypair: KeyPair) -> Self {
+        Self {
+            keypair,
+            reject: false,
+            message: Mutex::new(None),
+        }
+    }
+
+    /// A transport whose user rejects every signing request.
+    pub fn rejecting(keypair: KeyPair) -> Self {
+        Self {
+            reject: true,
+            ..Self::new(keypair)
+        }
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl LedgerTransport for MockTransport {
+    fn exchange(&self, command: &ApduCommand) -> anyhow::Result<(Vec<u8>, u16)> {
+        const SW_WRONG_DATA: u16 = 0x6a80;
+        const SW_INS_NOT_SUPPORTED: u16 = 0x6d00;
+        const SW_CLA_NOT_SUPPORTED: u16 = 0x6e00;
+
+        if command.cla != CLA {
+            return Ok((Vec::new(), SW_CLA_NOT_SUPPORTED));
+        }
+
+        let mut message = self.message.lock().unwrap();
+        match (command.ins, command.p1) {
+            (INS_GET_PUBKEY, _) => Ok((hex::decode(self.keypair.pubkey())?, SW_OK)),
+            (INS_SIGN_MESSAGE, P1_INIT) => {
+                *message = Some(Vec::new());
+                Ok((Vec::new(), SW_OK))
+            }
+            (INS_SIGN_MESSAGE, P1_ADD) | (INS_SIGN_MESSAGE, P1_LAST) => {
+                let buffer = match message.as_mut() {
+                    Some(buffer) => buffer,
+                    None => return Ok((Vec::new(), SW_WRONG_DATA)),
+                };
+                buffer.extend_from_slice(&command.data);
+                if command.p1 == P1_ADD {
+                    return Ok((Vec::new(), SW_OK));
+                }
+
+                let buffer = message.take().unwrap();
+                if self.reject {
+                    return Ok((Vec::new(), SW_REJECTED));
+                }
+                let signature = Production.sign(self.keypair.sigkey(), std::str::from_utf8(&buffer)?)?;
+
+                Ok((hex::decode(signature)?, SW_OK))
+            }
+            _ => Ok((Vec::new(), SW_INS_NOT_SUPPORTED)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::{ContributionInfo, ContributionState};
+
+    #[test]
+    fn sign_and_verify() {
+        let ledger = Ledger::new(MockTransport::new(KeyPair::new()));
+        let public_key = ledger.public_key(DEFAULT_DERIVATION_PATH).unwrap();
+
+        // Spans several APDU commands
+        let state = ContributionState::new(vec![1; 64], vec![2; 64], None).unwrap();
+        let message = state.signature_message().unwrap();
+        assert!(message.len() > CHUNK_SIZE);
+
+        let signature = ledger.sign(DEFAULT_DERIVATION_PATH, &message).unwrap();
+        assert!(ledger.verify(&public_key, &message, &signature));
+        assert!(Production.verify(&public_key, &message, &signature));
+    }
+
+    #[test]
+    fn sign_contribution_info() {
+        let ledger = Ledger::new(MockTransport::new(KeyPair::new()));
+        let keypair = ledger.keypair(DEFAULT_DERIVATION_PATH).unwrap();
+        assert_eq!(keypair.pubkey(), ledger.public_key(DEFAULT_DERIVATION_PATH).unwrap());
+        assert_eq!(keypair.sigkey(), DEFAULT_DERIVATION_PATH);
+
+        let mut info = ContributionInfo::default();
+        info.public_key = keypair.pubkey().to_owned();
+        info.try_sign(&ledger, &keypair).unwrap();
+        assert!(info.verify_signature().unwrap());
+
+        // The derivation path isn't a signing key outside of the device
+        assert!(info.try_sign(&Production, &keypair).is_err());
+    }
+
+    #[test]
+    fn concurrent_signatures() {
+        let ledger = std::sync::Arc::new(Ledger::new(MockTransport::new(KeyPair::new())));
+        let public_key = ledger.public_key(DEFAULT_DERIVATION_PATH).unwrap();
+
+        // The commands of the signatures must not interleave on the device
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let ledger = ledger.clone();
+                std::thread::spawn(move || {
+                    let message = format!("{}", i).repeat(CHUNK_SIZE * 3);
+                    let signature = ledger.sign(DEFAULT_DERIVATION_PATH, &message).unwrap();
+                    (message, signature)
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let (message, signature) = handle.join().unwrap();
+            assert!(ledger.verify(&public_key, &message, &signature));
+        }
+    }
+
+    #[test]
+    fn rejected_on_device() {
+        let ledger = Ledger::new(MockTransport::rejecting(KeyPair::new()));
+
+        assert!(ledger.sign(DEFAULT_DERIVATION_PATH, "message").is_err());
+    }
+
+    #[test]
+    fn derivation_path() {
+        assert_eq!(
+            serialize_path("m/44'/877'/0'/0/1").unwrap(),
+            vec![5, 0x80, 0, 0, 44, 0x80, 0, 0x03, 0x6d, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+        );
+        assert!(serialize_path("44'/877'").is_err());
+        assert!(serialize_path("m/44'/abc").is_err());
+        assert!(serialize_path("m/2147483648").is_err());
+    }
+}
@@ -1,8 +1,32 @@
+pub mod bls;
+pub use bls::*;
+
 pub mod dummy;
 pub use dummy::*;
 
+pub mod key_rotation;
+pub use key_rotation::*;
+
+pub mod ledger;
+pub use ledger::*;
+
+pub mod priority_token;
+pub use priority_token::*;
+
 pub mod production;
 pub use production::*;
 
+pub mod receipt;
+pub use receipt::*;
+
+pub mod secp256k1;
+pub use secp256k1::*;
+
+pub mod session;
+pub use session::*;
+
 pub mod signature;
 pub use signature::*;
+
+pub mod vault;
+pub use vault::*;
@@ -0,0 +1,115 @@
+use crate::authentication::{verify_hex_signature, Signature};
+
+/// Token issued ahead of time by the coordinator, e.g. to a partner organization, granting a reliability score in the
+/// queue to the contributor redeeming it at `join_queue` during its validity window. Single-use, encoded as
+/// `<id>.<reliability>.<not_before>.<expires_at>.<signature>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PriorityToken {
+    /// Random identifier, hex encoded, recorded once the token is redeemed
+    pub id: String,
+    /// Reliability score granted in the queue, the contributors with the highest score being served first
+    pub reliability: u8,
+    /// Unix timestamp, in seconds, from which the token can be redeemed
+    pub not_before: i64,
+    /// Unix timestamp, in seconds, at which the token expires
+    pub expires_at: i64,
+    /// Signature of the [`message`](Self::message), hex encoded
+    pub signature: String,
+}
+
+impl PriorityToken {
+    /// Signs a new token with the given signature scheme and signing key of the coordinator.
+    pub fn try_new(
+        signature: &dyn Signature,
+        signing_key: &str,
+        id: &str,
+        reliability: u8,
+        not_before: i64,
+        expires_at: i64,
+    ) -> anyhow::Result<Self> {
+        let mut token = Self {
+            id: id.to_string(),
+            reliability,
+            not_before,
+            expires_at,
+            signature: String::new(),
+        };
+        token.signature = signature.sign(signing_key, &token.message())?;
+
+        Ok(token)
+    }
+
+    /// Returns the message signed by the coordinator.
+    pub fn message(&self) -> String {
+        format!(
+            "priority:{}:{}:{}:{}",
+            self.id, self.reliability, self.not_before, self.expires_at
+        )
+    }
+
+    /// Returns the token as sent by the participants.
+    pub fn encode(&self) -> String {
+        format!(
+            "{}.{}.{}.{}.{}",
+            self.id, self.reliability, self.not_before, self.expires_at, self.signature
+        )
+    }
+
+    /// Parses an encoded token, returns `None` if malformed.
+    pub fn decode(token: &str) -> Option<Self> {
+        let parts: Vec<&str> = token.split('.').collect();
+        if parts.len() != 5 {
+            return None;
+        }
+
+        Some(Self {
+            id: parts[0].to_string(),
+            reliability: parts[1].parse().ok()?,
+            not_before: parts[2].parse().ok()?,
+            expires_at: parts[3].parse().ok()?,
+            signature: parts[4].to_string(),
+        })
+    }
+
+    /// Returns `true` if the token has been signed by the given public key of the coordinator.
+    pub fn verify(&self, coordinator_public_key: &str) -> bool {
+        verify_hex_signature(coordinator_public_key, &self.message(), &self.signature)
+    }
+
+    /// Returns `true` if the unix timestamp `now` falls within the validity window of the token.
+    pub fn is_active(&self, now: i64) -> bool {
+        self.not_before <= now && now < self.expires_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::{KeyPair, Production};
+
+    #[test]
+    fn verify_priority_token() {
+        let coordinator = KeyPair::new();
+        let token = PriorityToken::try_new(&Production, coordinator.sigkey(), "00ff", 20, 100, 200).unwrap();
+        let decoded = PriorityToken::decode(&token.encode()).unwrap();
+        assert_eq!(decoded, token);
+        assert!(decoded.verify(coordinator.pubkey()));
+        assert!(decoded.is_active(100));
+
+        // Wrong, outside of the validity window
+        assert!(!decoded.is_active(99));
+        assert!(!decoded.is_active(200));
+
+        // Wrong, signed by another coordinator
+        assert!(!decoded.verify(KeyPair::new().pubkey()));
+
+        // Wrong, raised reliability
+        let mut tampered = token.clone();
+        tampered.reliability = 255;
+        assert!(!tampered.verify(coordinator.pubkey()));
+
+        // Wrong, malformed tokens
+        assert!(PriorityToken::decode("00ff.high.100.200.signature").is_none());
+        assert!(PriorityToken::decode(&format!("{}.extra", token.encode())).is_none());
+    }
+}
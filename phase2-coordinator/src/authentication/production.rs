@@ -1,7 +1,17 @@
 use crate::authentication::Signature as SigTrait;
+use anyhow::anyhow;
+use bip39::{Language, Mnemonic};
 use ed25519_compact::{Error, KeyPair as EdKeyPair, Noise, PublicKey, SecretKey, Seed, Signature};
+use orion::{aead, kdf};
 use serde::{Deserialize, Serialize};
-use std::ops::Deref;
+use std::{fs, io::Write, ops::Deref, path::Path};
+
+/// Iterations of the Argon2i KDF of [`KeyPair::save_encrypted`]
+const KDF_ITERATIONS: u32 = 3;
+/// Memory, in KiB, of the Argon2i KDF of [`KeyPair::save_encrypted`]
+const KDF_MEMORY: u32 = 1 << 17;
+/// Length of the salt of the KDF, prepended to the encrypted keypair
+const KDF_SALT_LENGTH: usize = 16;
 
 /// A private/public key couple encoded in [`base64`]
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -22,6 +32,15 @@ impl KeyPair {
         })
     }
 
+    /// Derive the keypair from an English BIP39 mnemonic and a passphrase (empty for none), from the same seed as
+    /// the one of the mnemonic shown to the contributors. Both are expected to be in NFKD normal form, which is the
+    /// case of any ASCII string.
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> anyhow::Result<Self> {
+        let mnemonic = Mnemonic::parse_in_normalized(Language::English, phrase)?;
+
+        Ok(Self::try_from_seed(&mnemonic.to_seed_normalized(passphrase))?)
+    }
+
     /// Generate a random keypair
     pub fn new() -> Self {
         let keypair = EdKeyPair::generate();
@@ -32,12 +51,53 @@ impl KeyPair {
         }
     }
 
+    /// A keypair whose signing key is held by a Ledger device, with the derivation path of the key on the device
+    /// in place of the signing key (see [`Ledger`](crate::authentication::Ledger)).
+    pub(crate) fn on_device(pubkey: String, derivation_path: String) -> Self {
+        KeyPair {
+            pubkey,
+            sigkey: derivation_path,
+        }
+    }
+
     /// Custom keypair available only in test
     #[cfg(debug_assertions)]
     pub fn custom_new(sigkey: String, pubkey: String) -> Self {
         KeyPair { pubkey, sigkey }
     }
 
+    /// Encrypts the keypair with a key derived from the passphrase and saves it at `path`, readable only by its
+    /// owner. The key is derived with Argon2i and the keypair sealed with XChaCha20-Poly1305, as for the keys of the
+    /// Namada wallet: the file holds the [`hex`] encoded salt followed by the ciphertext.
+    pub fn save_encrypted(&self, path: impl AsRef<Path>, passphrase: &str) -> anyhow::Result<()> {
+        let salt = kdf::Salt::generate(KDF_SALT_LENGTH)?;
+        let ciphertext = aead::seal(&encryption_key(&salt, passphrase)?, &serde_json::to_vec(self)?)?;
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(path)?;
+        file.write_all(hex::encode([salt.as_ref(), &ciphertext].concat()).as_bytes())?;
+
+        Ok(file.sync_all()?)
+    }
+
+    /// Loads a keypair saved with [`save_encrypted`](Self::save_encrypted).
+    pub fn load_encrypted(path: impl AsRef<Path>, passphrase: &str) -> anyhow::Result<Self> {
+        let content = hex::decode(fs::read_to_string(path)?.trim())?;
+        if content.len() < KDF_SALT_LENGTH {
+            return Err(anyhow!("The encrypted keypair is too short"));
+        }
+
+        let (salt, ciphertext) = content.split_at(KDF_SALT_LENGTH);
+        let plaintext = aead::open(&encryption_key(&kdf::Salt::from_slice(salt)?, passphrase)?, ciphertext)
+            .map_err(|_| anyhow!("Wrong passphrase or corrupted keypair"))?;
+
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
     /// Get a reference to the key pair's pubkey.
     #[must_use]
     pub fn pubkey(&self) -> &str {
@@ -51,6 +111,13 @@ impl KeyPair {
     }
 }
 
+/// Derives the key encrypting a keypair from the passphrase
+fn encryption_key(salt: &kdf::Salt, passphrase: &str) -> anyhow::Result<kdf::SecretKey> {
+    let password = kdf::Password::from_slice(passphrase.as_bytes())?;
+
+    Ok(kdf::derive_key(&password, salt, KDF_ITERATIONS, KDF_MEMORY, 32)?)
+}
+
 /// The authentication to be used in production, based on [`ed25519_compact`]
 pub struct Production;
 
@@ -104,4 +171,70 @@ mod tests {
 
         assert!(sig_scheme.verify(keypair.pubkey(), msg, signature.as_ref()));
     }
+
+    #[test]
+    fn keypair_from_mnemonic() {
+        const ABANDON: &str =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        const SCHEME: &str = "scheme drift lava crystal miracle average admit tuna all initial seat crash mask depend \
+                              kangaroo dove olive pumpkin trap minute history enter immense settle";
+
+        // Keys of the first 32 bytes of the seeds of the BIP39 test vectors
+        for (phrase, passphrase, pubkey) in [
+            (
+                ABANDON,
+                "TREZOR",
+                "51425909c1e61287d378cf7af24fed87fa767e19a3462f7a01c93f95d73c465b",
+            ),
+            (
+                SCHEME,
+                "",
+                "519cf246ec7813de95a797e2dd255577b22252d42282a2cc7ce50e318e836ddc",
+            ),
+            (
+                SCHEME,
+                "namada",
+                "2fb41e8268f96dd4efcaca0067bb388f5c0579ae35975bf3862731b51d04a923",
+            ),
+        ] {
+            let keypair = KeyPair::from_mnemonic(phrase, passphrase).unwrap();
+            assert_eq!(keypair.pubkey(), pubkey);
+
+            let signature = Production.sign(keypair.sigkey(), "message").unwrap();
+            assert!(Production.verify(pubkey, "message", &signature));
+        }
+
+        // Same key as the one derived from the mnemonic shown to the contributors
+        let numbered: String = SCHEME
+            .split_whitespace()
+            .enumerate()
+            .map(|(i, word)| format!("{}. {} ", i + 1, word))
+            .collect();
+        let seed = crate::io::seed_from_string(&numbered).unwrap();
+        assert_eq!(
+            KeyPair::try_from_seed(&seed).unwrap().pubkey(),
+            KeyPair::from_mnemonic(SCHEME, "").unwrap().pubkey()
+        );
+
+        assert!(KeyPair::from_mnemonic("abandon abandon abandon", "").is_err());
+        assert!(KeyPair::from_mnemonic(&ABANDON.replace("about", "abandon"), "").is_err());
+    }
+
+    #[test]
+    fn save_and_load_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keypair");
+        let keypair = KeyPair::new();
+        keypair.save_encrypted(&path, "passphrase").unwrap();
+
+        // The signing key is not written in clear
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains(keypair.sigkey()));
+
+        let loaded = KeyPair::load_encrypted(&path, "passphrase").unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+        assert_eq!(loaded.sigkey(), keypair.sigkey());
+
+        assert!(KeyPair::load_encrypted(&path, "wrong passphrase").is_err());
+    }
 }
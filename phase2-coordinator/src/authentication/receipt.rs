@@ -0,0 +1,36 @@
+/// Proof, signed by the coordinator, that a contribution has been accepted for a chunk of a round.
+pub use phase2_client_sdk::ContributionReceipt;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::{KeyPair, Production, Signature};
+
+    #[test]
+    fn verify_receipt() {
+        let keypair = KeyPair::new();
+        let mut receipt = ContributionReceipt {
+            round_height: 1,
+            chunk_id: 0,
+            contribution_hash: "abcd".into(),
+            timestamp: 10,
+            coordinator_public_key: keypair.pubkey().to_owned(),
+            signature: String::new(),
+        };
+        receipt.signature = Production.sign(keypair.sigkey(), &receipt.message()).unwrap();
+        assert!(receipt.verify(keypair.pubkey()));
+
+        // Wrong, receipt from another coordinator
+        let other_keypair = KeyPair::new();
+        assert!(!receipt.verify(other_keypair.pubkey()));
+
+        // Wrong, tampered receipt
+        let mut tampered = receipt.clone();
+        tampered.round_height = 2;
+        assert!(!tampered.verify(keypair.pubkey()));
+
+        // Wrong, malformed signature
+        tampered.signature = String::from("not_a_signature");
+        assert!(!tampered.verify(keypair.pubkey()));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::authentication::Signature;
+use k256::ecdsa::{
+    signature::{Signer, Verifier},
+    Signature as EcdsaSignature,
+    SigningKey,
+    VerifyingKey,
+};
+use std::convert::TryFrom;
+
+/// The authentication based on ECDSA over secp256k1 with SHA-256, so that participants can reuse keys they already
+/// control and signatures can be checked with standard tooling.
+///
+/// Signing keys are expected to be the [`hex`] encoded 32 bytes scalar, public keys the [`hex`] encoded SEC1 point
+/// (compressed or not) and signatures the [`hex`] encoded 64 bytes `r || s`.
+pub struct Secp256k1;
+
+impl Secp256k1 {
+    /// Returns the [`hex`] encoded compressed public key of the given signing key.
+    pub fn public_key(signing_key: &str) -> anyhow::Result<String> {
+        let signing_key = SigningKey::from_bytes(&hex::decode(signing_key)?)?;
+
+        Ok(hex::encode(signing_key.verifying_key().to_encoded_point(true).as_bytes()))
+    }
+}
+
+impl Signature for Secp256k1 {
+    /// Returns the name of the signature scheme.
+    fn name(&self) -> String {
+        String::from("Secp256k1")
+    }
+
+    /// Returns `true` if the signature scheme is safe for use in production.
+    fn is_secure(&self) -> bool {
+        true
+    }
+
+    /// Signs the given message using the given signing key,
+    /// and returns the signature as a [`hex`] encoded string.
+    fn sign(&self, signing_key: &str, message: &str) -> anyhow::Result<String> {
+        let signing_key = SigningKey::from_bytes(&hex::decode(signing_key)?)?;
+        let signature: EcdsaSignature = signing_key.sign(message.as_bytes());
+
+        Ok(hex::encode(signature.as_ref()))
+    }
+
+    /// Verifies the given signature for the given message and public key,
+    /// and returns `true` if the signature is valid. Malformed keys and signatures are not valid.
+    fn verify(&self, public_key: &str, message: &str, signature: &str) -> bool {
+        let public_key = match hex::decode(public_key).map(|k| VerifyingKey::from_sec1_bytes(&k)) {
+            Ok(Ok(public_key)) => public_key,
+            _ => return false,
+        };
+        let signature = match hex::decode(signature).map(|s| EcdsaSignature::try_from(s.as_slice())) {
+            Ok(Ok(signature)) => signature,
+            _ => return false,
+        };
+
+        public_key.verify(message.as_bytes(), &signature).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNING_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    #[test]
+    fn sign_and_verify() {
+        let public_key = Secp256k1::public_key(SIGNING_KEY).unwrap();
+        let msg = "This is the message to sign";
+        let signature = Secp256k1.sign(SIGNING_KEY, msg).unwrap();
+
+        assert!(Secp256k1.verify(&public_key, msg, &signature));
+        assert!(!Secp256k1.verify(&public_key, "Another message", &signature));
+        assert!(!Secp256k1.verify(&public_key, msg, "00"));
+        assert!(!Secp256k1.verify("00", msg, &signature));
+    }
+}
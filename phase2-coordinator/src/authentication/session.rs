@@ -0,0 +1,96 @@
+use crate::authentication::{verify_hex_signature, Signature};
+
+/// Token of a session, signed by the coordinator, identifying the participant with the given public key until the
+/// expiration of the session. Encoded as `<pubkey>.<expires_at>.<signature>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionToken {
+    pub pubkey: String,
+    /// Unix timestamp, in seconds, at which the session expires
+    pub expires_at: i64,
+    /// Signature of the [`message`](Self::message), hex encoded
+    pub signature: String,
+}
+
+impl SessionToken {
+    /// Signs a new token with the given signature scheme and signing key of the coordinator.
+    pub fn try_new(
+        signature: &dyn Signature,
+        signing_key: &str,
+        pubkey: &str,
+        expires_at: i64,
+    ) -> anyhow::Result<Self> {
+        let mut token = Self {
+            pubkey: pubkey.to_string(),
+            expires_at,
+            signature: String::new(),
+        };
+        token.signature = signature.sign(signing_key, &token.message())?;
+
+        Ok(token)
+    }
+
+    /// Returns the message signed by the coordinator.
+    pub fn message(&self) -> String {
+        format!("session:{}:{}", self.pubkey, self.expires_at)
+    }
+
+    /// Returns the token as sent by the participants.
+    pub fn encode(&self) -> String {
+        format!("{}.{}.{}", self.pubkey, self.expires_at, self.signature)
+    }
+
+    /// Parses an encoded token, returns `None` if malformed.
+    pub fn decode(token: &str) -> Option<Self> {
+        let mut parts = token.split('.');
+        let (pubkey, expires_at, signature) = (parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            pubkey: pubkey.to_string(),
+            expires_at: expires_at.parse().ok()?,
+            signature: signature.to_string(),
+        })
+    }
+
+    /// Returns `true` if the token has been signed by the given public key of the coordinator and has not expired
+    /// at the unix timestamp `now`.
+    pub fn verify(&self, coordinator_public_key: &str, now: i64) -> bool {
+        if now >= self.expires_at {
+            return false;
+        }
+
+        verify_hex_signature(coordinator_public_key, &self.message(), &self.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::{KeyPair, Production};
+
+    #[test]
+    fn verify_session_token() {
+        let (coordinator, participant) = (KeyPair::new(), KeyPair::new());
+        let token = SessionToken::try_new(&Production, coordinator.sigkey(), participant.pubkey(), 100).unwrap();
+        let decoded = SessionToken::decode(&token.encode()).unwrap();
+        assert_eq!(decoded, token);
+        assert!(decoded.verify(coordinator.pubkey(), 99));
+
+        // Wrong, expired
+        assert!(!decoded.verify(coordinator.pubkey(), 100));
+
+        // Wrong, signed by another coordinator
+        assert!(!decoded.verify(participant.pubkey(), 99));
+
+        // Wrong, extended expiration
+        let mut tampered = token.clone();
+        tampered.expires_at = 1000;
+        assert!(!tampered.verify(coordinator.pubkey(), 99));
+
+        // Wrong, malformed tokens
+        assert!(SessionToken::decode("pubkey.abc.signature").is_none());
+        assert!(SessionToken::decode(&format!("{}.extra", token.encode())).is_none());
+    }
+}
@@ -1,3 +1,6 @@
+use crate::authentication::{Bls, Production, Secp256k1, BLS_PUBLIC_KEY_LENGTH};
+use serde::{Deserialize, Serialize};
+
 /// A core structure for authentication of contributions.
 pub trait Signature: Send + Sync {
     /// Returns the name of the signature scheme.
@@ -14,3 +17,120 @@ pub trait Signature: Send + Sync {
     /// and returns `true` if the signature is valid.
     fn verify(&self, public_key: &str, message: &str, signature: &str) -> bool;
 }
+
+/// Length of a [`hex`] encoded ed25519 public key
+const ED25519_PUBLIC_KEY_HEX_LENGTH: usize = 64;
+/// Length in bytes of an ed25519 signature
+const ED25519_SIGNATURE_LENGTH: usize = 64;
+
+/// Verifies the [`hex`] encoded ed25519 signature of the message with [`Production`]. Malformed public keys and
+/// signatures, which would make the verifier panic, are discarded.
+pub fn verify_hex_signature(public_key: &str, message: &str, signature: &str) -> bool {
+    if public_key.len() != ED25519_PUBLIC_KEY_HEX_LENGTH || hex::decode(public_key).is_err() {
+        return false;
+    }
+
+    match hex::decode(signature) {
+        Ok(bytes) if bytes.len() == ED25519_SIGNATURE_LENGTH => Production.verify(public_key, message, signature),
+        _ => false,
+    }
+}
+
+/// The signature scheme of the keys of the contributors. Keys encoded as ed25519 public keys, like the ones of the
+/// verifiers, are always verified with [`Production`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SignatureScheme {
+    /// The ed25519 keys of [`Production`]
+    Ed25519,
+    /// The secp256k1 keys of [`Secp256k1`]
+    Secp256k1,
+    /// The BLS12-381 keys of [`Bls`], whose signatures are aggregated per round in the transcript
+    Bls,
+}
+
+impl Default for SignatureScheme {
+    fn default() -> Self {
+        Self::Ed25519
+    }
+}
+
+impl SignatureScheme {
+    /// Reads the scheme from the `NAMADA_MPC_SIGNATURE_SCHEME` env variable, `ed25519` (default), `secp256k1` or
+    /// `bls`.
+    pub fn from_env() -> Self {
+        match std::env::var("NAMADA_MPC_SIGNATURE_SCHEME").as_deref() {
+            Ok("secp256k1") => Self::Secp256k1,
+            Ok("bls") => Self::Bls,
+            _ => Self::Ed25519,
+        }
+    }
+
+    /// Returns `true` if the public key is a key of this scheme rather than an ed25519 one.
+    pub fn is_scheme_key(&self, public_key: &str) -> bool {
+        match self {
+            Self::Ed25519 => false,
+            Self::Secp256k1 => public_key.len() != ED25519_PUBLIC_KEY_HEX_LENGTH,
+            Self::Bls => public_key.len() == 2 * BLS_PUBLIC_KEY_LENGTH,
+        }
+    }
+
+    /// Verifies the signature of a participant with the scheme matching its public key.
+    pub fn verify(&self, public_key: &str, message: &str, signature: &str) -> bool {
+        self.verify_with(&Production, public_key, message, signature)
+    }
+
+    /// Verifies the signature of a participant with the scheme matching its public key, falling back to `ed25519`
+    /// for the keys which aren't keys of the scheme.
+    pub fn verify_with(&self, ed25519: &dyn Signature, public_key: &str, message: &str, signature: &str) -> bool {
+        match self {
+            Self::Secp256k1 if self.is_scheme_key(public_key) => Secp256k1.verify(public_key, message, signature),
+            Self::Bls if self.is_scheme_key(public_key) => Bls.verify(public_key, message, signature),
+            _ => ed25519.verify(public_key, message, signature),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::KeyPair;
+
+    #[test]
+    fn scheme_verifies_both_key_types() {
+        let msg = "This is the message to sign";
+        let keypair = KeyPair::new();
+        let ed_signature = Production.sign(keypair.sigkey(), msg).unwrap();
+
+        let secp_signing_key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+        let secp_public_key = Secp256k1::public_key(secp_signing_key).unwrap();
+        let secp_signature = Secp256k1.sign(secp_signing_key, msg).unwrap();
+        assert!(SignatureScheme::Secp256k1.verify(&secp_public_key, msg, &secp_signature));
+        assert!(SignatureScheme::Secp256k1.verify(keypair.pubkey(), msg, &ed_signature));
+
+        let bls_signing_key = Bls::signing_key(&[1; 32]).unwrap();
+        let bls_public_key = Bls::public_key(&bls_signing_key).unwrap();
+        let bls_signature = Bls.sign(&bls_signing_key, msg).unwrap();
+        assert!(SignatureScheme::Bls.verify(&bls_public_key, msg, &bls_signature));
+        assert!(SignatureScheme::Bls.verify(keypair.pubkey(), msg, &ed_signature));
+
+        assert!(SignatureScheme::Ed25519.verify(keypair.pubkey(), msg, &ed_signature));
+    }
+
+    #[test]
+    fn hex_signature() {
+        let msg = "This is the message to sign";
+        let keypair = KeyPair::new();
+        let signature = Production.sign(keypair.sigkey(), msg).unwrap();
+        assert!(verify_hex_signature(keypair.pubkey(), msg, &signature));
+
+        // Wrong, signature of another message or from another key
+        assert!(!verify_hex_signature(keypair.pubkey(), "Another message", &signature));
+        assert!(!verify_hex_signature(KeyPair::new().pubkey(), msg, &signature));
+
+        // Wrong, malformed signature or public key
+        assert!(!verify_hex_signature(keypair.pubkey(), msg, "not_a_signature"));
+        assert!(!verify_hex_signature(keypair.pubkey(), msg, &signature[..64]));
+        assert!(!verify_hex_signature("not_a_public_key", msg, &signature));
+        assert!(!verify_hex_signature(&keypair.pubkey()[..32], msg, &signature));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::authentication::{Production, Signature};
+use anyhow::anyhow;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::{mpsc, Arc, Mutex};
+
+/// Prefix of the signatures produced by the transit secrets engine of Vault
+const VAULT_SIGNATURE_PREFIX: &str = "vault:v";
+
+type Job = Box<dyn FnOnce(&Client) + Send>;
+
+/// The backend holding the signing key of the coordinator.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SigningBackend {
+    /// The key is derived from the mnemonic of the coordinator and held in memory
+    Local,
+    /// The key is an ed25519 key of the transit secrets engine of HashiCorp Vault, mounted at `mount`.
+    /// The token to access Vault is read from the `VAULT_TOKEN` env variable, so that it never ends up in the
+    /// serialized environment.
+    Vault {
+        address: String,
+        mount: String,
+        key_name: String,
+    },
+}
+
+impl Default for SigningBackend {
+    fn default() -> Self {
+        Self::Local
+    }
+}
+
+impl SigningBackend {
+    /// Reads the backend from the env: Vault is used if `NAMADA_MPC_VAULT_ADDR` and `NAMADA_MPC_VAULT_KEY` are set,
+    /// with the transit engine mounted at `NAMADA_MPC_VAULT_MOUNT` (defaults to `transit`).
+    pub fn from_env() -> Self {
+        match (std::env::var("NAMADA_MPC_VAULT_ADDR"), std::env::var("NAMADA_MPC_VAULT_KEY")) {
+            (Ok(address), Ok(key_name)) => Self::Vault {
+                address,
+                mount: std::env::var("NAMADA_MPC_VAULT_MOUNT").unwrap_or_else(|_| "transit".to_string()),
+                key_name,
+            },
+            _ => Self::Local,
+        }
+    }
+
+    /// Returns the [`hex`] encoded public key of the key held by the backend, `None` for the local backend.
+    pub fn public_key(&self) -> anyhow::Result<Option<String>> {
+        match self {
+            Self::Local => Ok(None),
+            Self::Vault {
+                address,
+                mount,
+                key_name,
+            } => Ok(Some(Vault::new(address, mount)?.public_key(key_name)?)),
+        }
+    }
+
+    /// Returns the [`Signature`] implementation to be used by the coordinator with this backend.
+    pub fn signature(&self) -> anyhow::Result<Arc<dyn Signature>> {
+        match self {
+            Self::Local => Ok(Arc::new(Production)),
+            Self::Vault { address, mount, .. } => Ok(Arc::new(Vault::new(address, mount)?)),
+        }
+    }
+}
+
+/// The authentication based on ed25519 keys held by the transit secrets engine of HashiCorp Vault. The signing key
+/// passed to [`Signature::sign`] is the name of the key in Vault: the key never leaves Vault.
+///
+/// Signatures are encoded like the ones of [`Production`], which is used for verification.
+///
+/// The blocking client runs its own runtime, which panics when built, used or dropped inside the runtime of the
+/// server: the requests are therefore sent by a dedicated thread owning the client, and the signer can be called from
+/// anywhere. The caller still blocks until Vault answers.
+pub struct Vault {
+    jobs: Mutex<mpsc::Sender<Job>>,
+    /// Base url of the transit engine
+    url: String,
+    token: String,
+}
+
+impl Vault {
+    /// Connects to the transit engine mounted at `mount` of the Vault server at `address`.
+    pub fn new(address: &str, mount: &str) -> anyhow::Result<Self> {
+        let token = std::env::var("VAULT_TOKEN").map_err(|_| anyhow!("Missing required env VAULT_TOKEN"))?;
+        let (jobs, receiver) = mpsc::channel::<Job>();
+
+        std::thread::spawn(move || {
+            let client = Client::new();
+
+            // Stops once the signer, and so the sender, is dropped
+            for job in receiver {
+                job(&client);
+            }
+        });
+
+        Ok(Self {
+            jobs: Mutex::new(jobs),
+            url: format!("{}/v1/{}", address.trim_end_matches('/'), mount),
+            token,
+        })
+    }
+
+    /// Sends an authenticated request to the given endpoint of the transit engine and returns the `data` field of
+    /// the response.
+    fn request(&self, endpoint: &str, body: Option<Value>) -> anyhow::Result<Value> {
+        let url = format!("{}/{}", self.url, endpoint);
+        let token = self.token.clone();
+        let (sender, receiver) = mpsc::channel();
+        self.jobs
+            .lock()
+            .map_err(|_| anyhow!("Vault client poisoned"))?
+            .send(Box::new(move |client| {
+                let _ = sender.send(send_request(client, url, &token, body));
+            }))
+            .map_err(|_| anyhow!("The Vault client is closed"))?;
+
+        receiver.recv().map_err(|_| anyhow!("The Vault client is closed"))?
+    }
+
+    /// Returns the [`hex`] encoded public key of the latest version of the given key.
+    pub fn public_key(&self, key_name: &str) -> anyhow::Result<String> {
+        let data = self.request(&format!("keys/{}", key_name), None)?;
+
+        if data["type"] != "ed25519" {
+            return Err(anyhow!("Vault key {} is not an ed25519 key", key_name));
+        }
+
+        let latest_version = data["latest_version"]
+            .as_u64()
+            .ok_or_else(|| anyhow!("Missing latest version of Vault key {}", key_name))?;
+        let public_key = data["keys"][latest_version.to_string()]["public_key"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Missing public key of Vault key {}", key_name))?;
+
+        Ok(hex::encode(base64::decode(public_key)?))
+    }
+}
+
+/// Sends the request to the given url of Vault and returns the `data` field of the response.
+fn send_request(client: &Client, url: String, token: &str, body: Option<Value>) -> anyhow::Result<Value> {
+    let request = match body {
+        Some(body) => client.post(url).body(body.to_string()),
+        None => client.get(url),
+    };
+
+    let response: Value = serde_json::from_str(
+        &request
+            .header("X-Vault-Token", token)
+            .send()?
+            .error_for_status()?
+            .text()?,
+    )?;
+
+    response
+        .get("data")
+        .cloned()
+        .ok_or_else(|| anyhow!("Missing data in the response of Vault"))
+}
+
+impl Signature for Vault {
+    /// Returns the name of the signature scheme.
+    fn name(&self) -> String {
+        String::from("Vault")
+    }
+
+    /// Returns `true` if the signature scheme is safe for use in production.
+    fn is_secure(&self) -> bool {
+        true
+    }
+
+    /// Signs the given message with the Vault key named `signing_key`,
+    /// and returns the signature as a [`hex`] encoded string.
+    fn sign(&self, signing_key: &str, message: &str) -> anyhow::Result<String> {
+        let data = self.request(
+            &format!("sign/{}", signing_key),
+            Some(json!({ "input": base64::encode(message) })),
+        )?;
+
+        // Signatures are formatted as vault:v<key version>:<base64 signature>
+        let signature = data["signature"]
+            .as_str()
+            .filter(|s| s.starts_with(VAULT_SIGNATURE_PREFIX))
+            .and_then(|s| s.rsplit(':').next())
+            .ok_or_else(|| anyhow!("Unexpected signature format from Vault"))?;
+
+        Ok(hex::encode(base64::decode(signature)?))
+    }
+
+    /// Verifies the given signature for the given message and public key,
+    /// and returns `true` if the signature is valid.
+    fn verify(&self, public_key: &str, message: &str, signature: &str) -> bool {
+        Production.verify(public_key, message, signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    #[test]
+    #[serial]
+    fn signing_backend_from_env() {
+        std::env::remove_var("NAMADA_MPC_VAULT_ADDR");
+        std::env::set_var("NAMADA_MPC_VAULT_KEY", "coordinator");
+        assert_eq!(SigningBackend::from_env(), SigningBackend::Local);
+
+        std::env::set_var("NAMADA_MPC_VAULT_ADDR", "http://127.0.0.1:8200");
+        assert_eq!(
+            SigningBackend::from_env(),
+            SigningBackend::Vault {
+                address: String::from("http://127.0.0.1:8200"),
+                mount: String::from("transit"),
+                key_name: String::from("coordinator"),
+            }
+        );
+
+        std::env::remove_var("NAMADA_MPC_VAULT_ADDR");
+        std::env::remove_var("NAMADA_MPC_VAULT_KEY");
+    }
+
+    /// Answers the next request on the listener with the given `data`, and returns the request.
+    fn serve_once(listener: TcpListener, data: Value) -> std::thread::JoinHandle<String> {
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut buffer = [0; 1024];
+            while !String::from_utf8_lossy(&request).contains("\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let body = json!({ "data": data }).to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            String::from_utf8_lossy(&request).into_owned()
+        })
+    }
+
+    #[test]
+    #[serial]
+    fn sign_inside_runtime() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let signature = [7u8; 64];
+        let server = serve_once(
+            listener,
+            json!({ "signature": format!("vault:v1:{}", base64::encode(signature)) }),
+        );
+        std::env::set_var("VAULT_TOKEN", "token");
+
+        // Neither building, using nor dropping the signer panics inside the runtime
+        let runtime = rocket::tokio::runtime::Builder::new_current_thread().build().unwrap();
+        runtime.block_on(async {
+            let vault = Vault::new(&address, "transit").unwrap();
+            assert_eq!(vault.sign("coordinator", "message").unwrap(), hex::encode(signature));
+        });
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /v1/transit/sign/coordinator "));
+        assert!(request.to_lowercase().contains("x-vault-token: token"));
+
+        std::env::remove_var("VAULT_TOKEN");
+    }
+}
@@ -14,6 +14,7 @@ impl Aggregation {
     /// Runs aggregation for a given environment, storage, and round.
     /// NOTE: The Namada Trusted Setup runs only phase 2 and doesn't chunk the contributions. The aggregation has been left as a placeholder to avoid breaking the whole library logic. In practice, there is no aggregation.
     #[inline]
+    #[tracing::instrument(skip(environment, storage, round), fields(round_height = round.round_height()))]
     pub(crate) fn run(environment: &Environment, storage: &mut Disk, round: &Round) -> anyhow::Result<()> {
         let start = Instant::now();
 
@@ -0,0 +1,132 @@
+use crate::{objects::BeaconContribution, CoordinatorError};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[cfg(any(test, feature = "operator"))]
+use crate::{
+    authentication::Signature,
+    commands::{Computation, RandomSource},
+    storage::{ContributionLocator, Disk, Locator, StorageObject},
+};
+#[cfg(any(test, feature = "operator"))]
+use setup_utils::{beacon_randomness, calculate_hash};
+#[cfg(any(test, feature = "operator"))]
+use std::{io::Write, sync::Arc, time::Instant};
+#[cfg(any(test, feature = "operator"))]
+use tracing::info;
+
+/// Name of the file holding the final parameters produced by the beacon.
+pub const BEACON_RESPONSE_FILE: &str = "response";
+/// Name of the file describing the beacon contribution.
+pub const BEACON_CONTRIBUTION_FILE: &str = "beacon.json";
+
+pub(crate) struct Beacon;
+
+impl Beacon {
+    ///
+    /// Applies the public random `beacon` as the final contribution to the verified challenge of
+    /// the given round height, writing the final parameters and the signed description of the
+    /// contribution to `output_dir`.
+    ///
+    /// The response has the same layout as the contribution files: the hash of the challenge
+    /// followed by the updated masp-mpc parameters.
+    ///
+    #[cfg(any(test, feature = "operator"))]
+    #[tracing::instrument(skip(storage, signature, signing_key, beacon, output_dir))]
+    pub(crate) fn run(
+        storage: &Disk,
+        signature: Arc<dyn Signature>,
+        signing_key: &str,
+        round_height: u64,
+        beacon: [u8; 32],
+        output_dir: &Path,
+    ) -> Result<BeaconContribution, CoordinatorError> {
+        info!("Applying the random beacon to the challenge of round {}", round_height);
+        let start = Instant::now();
+
+        // Since we don't chunk the parameters, the latest parameters are stored in the first verified contribution of the round
+        let challenge_locator = Locator::ContributionFile(ContributionLocator::new(round_height, 0, 0, true));
+        let challenge_reader = storage.reader(&challenge_locator)?;
+        let challenge_hash = calculate_hash(challenge_reader.as_ref());
+
+        let mut response = Vec::new();
+        response.write_all(challenge_hash.as_slice())?;
+
+        let rand_source = RandomSource::Seed(beacon_randomness(beacon));
+        #[cfg(debug_assertions)]
+        Computation::contribute_test_masp(challenge_reader.as_ref(), &mut response, &rand_source);
+        #[cfg(not(debug_assertions))]
+        Computation::contribute_masp(challenge_reader.as_ref(), &mut response, &rand_source);
+
+        let mut contribution = BeaconContribution {
+            round_height,
+            beacon: hex::encode(beacon),
+            challenge_hash: hex::encode(challenge_hash),
+            response_hash: hex::encode(calculate_hash(&response)),
+            signature: String::new(),
+        };
+        contribution.signature = signature.sign(signing_key, &contribution.message())?;
+
+        fs::create_dir_all(output_dir)?;
+        fs::write(output_dir.join(BEACON_RESPONSE_FILE), &response)?;
+        fs::write(
+            output_dir.join(BEACON_CONTRIBUTION_FILE),
+            serde_json::to_vec_pretty(&contribution)?,
+        )?;
+
+        let elapsed = Instant::now().duration_since(start);
+        info!("Completed the beacon contribution in {:?}", elapsed);
+        Ok(contribution)
+    }
+
+    /// Returns the beacon contribution stored in `dir`, if the beacon has been applied.
+    pub(crate) fn load(dir: &Path) -> Result<Option<(BeaconContribution, PathBuf)>, CoordinatorError> {
+        let contribution_path = dir.join(BEACON_CONTRIBUTION_FILE);
+        if !contribution_path.exists() {
+            return Ok(None);
+        }
+
+        let contribution = serde_json::from_slice(&fs::read(contribution_path)?)?;
+        Ok(Some((contribution, dir.join(BEACON_RESPONSE_FILE))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        authentication::{Dummy, Signature},
+        commands::{Beacon, Initialization},
+        testing::prelude::*,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    #[serial]
+    fn test_beacon_run() {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        // Initialize the round 0 challenge
+        let mut storage = test_storage(&TEST_ENVIRONMENT_ANOMA);
+        Initialization::run(&TEST_ENVIRONMENT_ANOMA, &mut storage, 0, 0).unwrap();
+
+        let output_dir = tempfile::tempdir().unwrap();
+        assert!(Beacon::load(output_dir.path()).unwrap().is_none());
+
+        let contribution = Beacon::run(&storage, Arc::new(Dummy), "secret", 0, [1u8; 32], output_dir.path()).unwrap();
+        assert_eq!(contribution.round_height, 0);
+        assert_eq!(contribution.beacon, hex::encode([1u8; 32]));
+        assert!(Dummy.verify("public", &contribution.message(), &contribution.signature));
+
+        let (loaded, response_path) = Beacon::load(output_dir.path()).unwrap().unwrap();
+        assert_eq!(loaded, contribution);
+        assert!(response_path.exists());
+
+        // The beacon is deterministic
+        let other_dir = tempfile::tempdir().unwrap();
+        let other = Beacon::run(&storage, Arc::new(Dummy), "secret", 0, [1u8; 32], other_dir.path()).unwrap();
+        assert_eq!(other.response_hash, contribution.response_hash);
+    }
+}
@@ -15,21 +15,136 @@ use tracing::{debug, error, info, trace};
 pub const SEED_LENGTH: usize = 32;
 pub type Seed = [u8; SEED_LENGTH];
 
+/// The environment variable from which bellman sizes the thread pool of its multicore operations
+const BELLMAN_NUM_CPUS: &str = "BELLMAN_NUM_CPUS";
+
 use blake2::{Blake2b512, Digest};
 use itertools::Itertools;
 use masp_phase2::MPCParameters;
+use rand::{rngs::OsRng, RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+use std::convert::TryInto;
+
+/// The domain separation tag of the KDF deriving the seed of a contribution from its sources of entropy
+pub const ENTROPY_KDF_DOMAIN: &[u8] = b"namada-trusted-setup/contribution-seed/v1";
 
 /// Sources of randomness
 pub enum RandomSource {
-    /// A string to be used as entropy
+    /// A string to be used as entropy, mixed with the OS and hardware randomness by [`Computation::entropy_seed`]
     Entropy(String),
     /// A [`Seed`] of 32 bytes for rng
     Seed(Seed),
 }
 
+impl RandomSource {
+    /// Creates the RNG of a contribution from this source of randomness.
+    pub fn rng(&self) -> ChaChaRng {
+        match self {
+            RandomSource::Entropy(e) => ChaChaRng::from_seed(Computation::entropy_seed(e)),
+            RandomSource::Seed(s) => ChaChaRng::from_seed(*s),
+        }
+    }
+}
+
 pub struct Computation;
 
 impl Computation {
+    ///
+    /// Derives the seed of a contribution from 64 bytes of the OS RNG, the entropy
+    /// given by the user and, if the CPU provides one, 32 bytes of the hardware RNG.
+    /// See [`Computation::mix_entropy`] for the KDF.
+    ///
+    pub fn entropy_seed(user_entropy: &str) -> Seed {
+        let mut os_entropy = [0u8; 64];
+        OsRng.fill_bytes(&mut os_entropy);
+        let hardware_entropy = Self::hardware_entropy();
+
+        Self::mix_entropy(
+            &os_entropy,
+            user_entropy.as_bytes(),
+            hardware_entropy.as_ref().map(|entropy| &entropy[..]),
+        )
+    }
+
+    ///
+    /// Mixes the given sources of entropy into a seed with the KDF
+    ///
+    /// `seed = Blake2b-512(D || E(os) || E(user) || E(hardware))[0..32]`
+    ///
+    /// where `D` is [`ENTROPY_KDF_DOMAIN`] and `E(x)` is the length of `x` as a
+    /// little-endian `u64` followed by `x`. A missing hardware entropy is encoded as
+    /// the empty string. The seed is unpredictable as long as one of the sources is.
+    ///
+    pub fn mix_entropy(os_entropy: &[u8], user_entropy: &[u8], hardware_entropy: Option<&[u8]>) -> Seed {
+        let mut h = Blake2b512::new();
+        h.update(ENTROPY_KDF_DOMAIN);
+        for entropy in [os_entropy, user_entropy, hardware_entropy.unwrap_or_default()] {
+            h.update(&(entropy.len() as u64).to_le_bytes());
+            h.update(entropy);
+        }
+        let digest = h.finalize();
+
+        digest[0..SEED_LENGTH].try_into().unwrap()
+    }
+
+    ///
+    /// Returns 32 bytes from the RDRAND instruction of the CPU, or `None` if the CPU doesn't support it.
+    ///
+    #[cfg(target_arch = "x86_64")]
+    pub fn hardware_entropy() -> Option<[u8; 32]> {
+        #[target_feature(enable = "rdrand")]
+        unsafe fn rdrand64() -> Option<u64> {
+            let mut value = 0;
+            // RDRAND can transiently run out of entropy, Intel recommends 10 retries
+            for _ in 0..10 {
+                if std::arch::x86_64::_rdrand64_step(&mut value) == 1 {
+                    return Some(value);
+                }
+            }
+            None
+        }
+
+        if !is_x86_feature_detected!("rdrand") {
+            return None;
+        }
+
+        let mut entropy = [0u8; 32];
+        for chunk in entropy.chunks_mut(8) {
+            // Safe because the support of RDRAND has been checked above
+            chunk.copy_from_slice(&unsafe { rdrand64() }?.to_le_bytes());
+        }
+        Some(entropy)
+    }
+
+    ///
+    /// Returns 32 bytes from the hardware RNG, which is only supported on x86-64.
+    ///
+    #[cfg(not(target_arch = "x86_64"))]
+    pub fn hardware_entropy() -> Option<[u8; 32]> {
+        None
+    }
+
+    ///
+    /// Bounds the number of threads used by the `contribute_*` functions in this process, which
+    /// otherwise use one thread per available core.
+    ///
+    /// The thread pools are sized on their first use, so this function must be called before
+    /// the first contribution.
+    ///
+    pub fn set_threads(threads: usize) -> Result<(), CoordinatorError> {
+        if threads == 0 {
+            return Err(CoordinatorError::Error(anyhow::anyhow!(
+                "the number of threads must be positive"
+            )));
+        }
+
+        std::env::set_var(BELLMAN_NUM_CPUS, threads.to_string());
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|e| CoordinatorError::Error(e.into()))
+    }
+
     ///
     /// Runs computation for a given environment, storage writer, challenge locator,
     /// response locator, and contribution file signature locator.
@@ -38,6 +153,7 @@ impl Computation {
     /// and response file have been initialized, typically as part of a call to
     /// `Coordinator::try_lock` to lock the contribution chunk.
     ///
+    #[tracing::instrument(skip_all, fields(challenge = ?challenge_locator, response = ?response_locator))]
     pub fn run(
         environment: &Environment,
         storage: &mut Disk,
@@ -163,34 +279,7 @@ impl Computation {
     // The masp-mpc commands are executed at offset 64 of the [`ContributionFile`]
     #[cfg(not(debug_assertions))]
     pub fn contribute_masp<W: Write>(challenge_reader: &[u8], mut response_writer: W, rand_source: &RandomSource) {
-        // Create an RNG as following:
-        //  - if the user provides a seed, create the rng from that seed
-        //  - if the user provides entropy, create the rng from the combination of OS randomness and user entropy
-        let mut rng = {
-            use rand::{Rng, SeedableRng};
-            use rand_chacha::ChaChaRng;
-            use std::convert::TryInto;
-
-            match rand_source {
-                RandomSource::Entropy(e) => {
-                    let mut system_rng = rand::rngs::OsRng;
-                    let mut h = Blake2b512::new();
-
-                    // Gather 1024 bytes of entropy from the system
-                    for _ in 0..1024 {
-                        let r: u8 = system_rng.gen();
-                        h.update(&[r]);
-                    }
-
-                    // Hash it all up to make a seed
-                    h.update(e.as_bytes());
-                    let digest = h.finalize();
-
-                    ChaChaRng::from_seed(digest[0..32].try_into().unwrap())
-                }
-                RandomSource::Seed(s) => ChaChaRng::from_seed(*s),
-            }
-        };
+        let mut rng = rand_source.rng();
 
         let mut masp_challenge_reader = &challenge_reader[64..];
         //
@@ -263,34 +352,7 @@ impl Computation {
 
     #[cfg(debug_assertions)]
     pub fn contribute_test_masp<W: Write>(challenge_reader: &[u8], mut response_writer: W, rand_source: &RandomSource) {
-        // Create an RNG as following:
-        //  - if the user provides a seed, create the rng from that seed
-        //  - if the user provides entropy, create the rng from the combination of OS randomness and user entropy
-        let mut rng = {
-            use rand::{Rng, SeedableRng};
-            use rand_chacha::ChaChaRng;
-            use std::convert::TryInto;
-
-            match rand_source {
-                RandomSource::Entropy(e) => {
-                    let mut system_rng = rand::rngs::OsRng;
-                    let mut h = Blake2b512::new();
-
-                    // Gather 1024 bytes of entropy from the system
-                    for _ in 0..1024 {
-                        let r: u8 = system_rng.gen();
-                        h.update(&[r]);
-                    }
-
-                    // Hash it all up to make a seed
-                    h.update(e.as_bytes());
-                    let digest = h.finalize();
-
-                    ChaChaRng::from_seed(digest[0..32].try_into().unwrap())
-                }
-                RandomSource::Seed(s) => ChaChaRng::from_seed(*s),
-            }
-        };
+        let mut rng = rand_source.rng();
 
         let mut test_params =
             MPCParameters::read(&challenge_reader[64..], false).expect("unable to read MASP Test params");
@@ -332,6 +394,23 @@ mod tests {
 
     use itertools::Itertools;
 
+    #[test]
+    fn test_mix_entropy() {
+        let seed = Computation::mix_entropy(b"os", b"user", None);
+        assert_eq!(seed, Computation::mix_entropy(b"os", b"user", None));
+
+        // Every source changes the seed, and the sources can't be shifted into each other
+        assert_ne!(seed, Computation::mix_entropy(b"os", b"user", Some(&b"hardware"[..])));
+        assert_ne!(seed, Computation::mix_entropy(b"os", b"resu", None));
+        assert_ne!(seed, Computation::mix_entropy(b"osu", b"ser", None));
+        assert_ne!(seed, Computation::entropy_seed("user"));
+    }
+
+    #[test]
+    fn test_set_zero_threads() {
+        assert!(Computation::set_threads(0).is_err());
+    }
+
     #[test]
     #[serial]
     fn test_computation_run() {
@@ -372,7 +451,7 @@ mod tests {
             );
 
             if !storage.exists(response_locator) {
-                let expected_filesize = Object::anoma_contribution_file_size(round_height, 1);
+                let expected_filesize = Object::anoma_contribution_file_size(&TEST_ENVIRONMENT_ANOMA, round_height, 1);
                 storage.initialize(response_locator.clone(), expected_filesize).unwrap();
             }
             if !storage.exists(contribution_file_signature_locator) {
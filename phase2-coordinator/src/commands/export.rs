@@ -0,0 +1,132 @@
+use crate::{
+    storage::{ContributionLocator, Disk, Locator, StorageObject},
+    CoordinatorError,
+};
+
+use blake2::{Blake2b512, Digest};
+use masp_phase2::MPCParameters;
+use serde::{Deserialize, Serialize};
+
+use std::{fs, io::Write, path::Path, time::Instant};
+use tracing::{debug, info};
+
+/// Name of the file listing the exported parameters and their hashes.
+pub const EXPORT_MANIFEST_FILE: &str = "params_manifest.json";
+
+/// File names of the exported parameters, matching the ones expected by `masp_proofs`.
+#[cfg(not(debug_assertions))]
+const MASP_PARAMS_FILES: [&str; 3] = ["masp-spend.params", "masp-output.params", "masp-convert.params"];
+#[cfg(debug_assertions)]
+const MASP_PARAMS_FILES: [&str; 1] = ["masp-test.params"];
+
+/// A parameter file produced by the export, together with its [`Blake2b512`] hash,
+/// hex encoded as done by `masp_proofs` when loading the parameters.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExportedParameter {
+    pub file_name: String,
+    pub hash: String,
+}
+
+/// Summary of a parameter export.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ParameterExport {
+    pub round_height: u64,
+    pub parameters: Vec<ExportedParameter>,
+}
+
+pub(crate) struct Export;
+
+impl Export {
+    ///
+    /// Converts the verified challenge of the given round height into the bellman
+    /// parameter files expected by `masp_proofs` and writes them to `output_dir`, along
+    /// with a manifest containing the expected hash of every file.
+    ///
+    pub(crate) fn run(storage: &Disk, round_height: u64, output_dir: &Path) -> Result<ParameterExport, CoordinatorError> {
+        // Since we don't chunk the parameters, the latest parameters are stored in the first verified contribution of the round
+        let locator = Locator::ContributionFile(ContributionLocator::new(round_height, 0, 0, true));
+        let reader = storage.reader(&locator)?;
+
+        Self::run_from_contribution(reader.as_ref(), round_height, output_dir)
+    }
+
+    ///
+    /// Converts the given contribution file, coming from the given round height, into the
+    /// bellman parameter files expected by `masp_proofs`. See [`run`](Self::run).
+    ///
+    pub(crate) fn run_from_contribution(
+        contribution: &[u8],
+        round_height: u64,
+        output_dir: &Path,
+    ) -> Result<ParameterExport, CoordinatorError> {
+        info!("Starting export of the parameters of round {}", round_height);
+        let start = Instant::now();
+
+        // The masp-mpc parameters are located at offset 64 of the contribution file
+        let mut params_reader = contribution.get(64..).ok_or(CoordinatorError::StorageReaderFailed)?;
+        fs::create_dir_all(output_dir)?;
+
+        let mut parameters = Vec::with_capacity(MASP_PARAMS_FILES.len());
+        for file_name in MASP_PARAMS_FILES {
+            let mpc_params = MPCParameters::read(&mut params_reader, false).map_err(|e| {
+                CoordinatorError::ParameterExportFailed(format!("couldn't deserialize {}: {}", file_name, e))
+            })?;
+
+            let mut params_bytes = Vec::new();
+            mpc_params.get_params().write(&mut params_bytes)?;
+
+            let mut hasher = Blake2b512::new();
+            hasher.update(&params_bytes);
+            let hash = hex::encode(hasher.finalize());
+            debug!("Exported {} with hash {}", file_name, hash);
+
+            let mut file = fs::File::create(output_dir.join(file_name))?;
+            file.write_all(&params_bytes)?;
+            file.flush()?;
+
+            parameters.push(ExportedParameter {
+                file_name: file_name.to_string(),
+                hash,
+            });
+        }
+
+        let export = ParameterExport {
+            round_height,
+            parameters,
+        };
+        fs::write(output_dir.join(EXPORT_MANIFEST_FILE), serde_json::to_vec_pretty(&export)?)?;
+
+        let elapsed = Instant::now().duration_since(start);
+        info!("Completed export of the parameters of round {} in {:?}", round_height, elapsed);
+        Ok(export)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        commands::{Export, Initialization, EXPORT_MANIFEST_FILE},
+        testing::prelude::*,
+    };
+
+    #[test]
+    #[serial]
+    fn test_export_run() {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        // Initialize the round 0 challenge
+        let mut storage = test_storage(&TEST_ENVIRONMENT_ANOMA);
+        Initialization::run(&TEST_ENVIRONMENT_ANOMA, &mut storage, 0, 0).unwrap();
+
+        let output_dir = tempfile::tempdir().unwrap();
+        let export = Export::run(&storage, 0, output_dir.path()).unwrap();
+
+        assert_eq!(export.round_height, 0);
+        assert!(!export.parameters.is_empty());
+        for parameter in &export.parameters {
+            assert!(output_dir.path().join(&parameter.file_name).exists());
+            assert_eq!(parameter.hash.len(), 128);
+        }
+        assert!(output_dir.path().join(EXPORT_MANIFEST_FILE).exists());
+    }
+}
@@ -38,6 +38,7 @@ impl Initialization {
     /// Executes the round initialization on a given chunk ID.
     ///
     #[inline]
+    #[tracing::instrument(skip(environment, storage))]
     pub(crate) fn run(
         environment: &Environment,
         storage: &mut Disk,
@@ -48,7 +49,7 @@ impl Initialization {
         let start = Instant::now();
 
         // Determine the expected challenge size.
-        let expected_challenge_size = Object::anoma_contribution_file_size(0, 0);
+        let expected_challenge_size = Object::anoma_contribution_file_size(environment, 0, 0);
         trace!("Expected challenge file size is {}", expected_challenge_size);
 
         // Initialize and fetch a writer for the contribution locator so the output is saved.
@@ -0,0 +1,220 @@
+use crate::{
+    authentication::Signature,
+    storage::{ContributionLocator, ContributionSignatureLocator, Disk, Locator, StorageLocator},
+    CoordinatorError,
+};
+
+use blake2::{Blake2b512, Digest};
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+    sync::Arc,
+    time::Instant,
+};
+use tracing::{debug, info};
+
+/// Name of the index of the bundle.
+pub const MIRROR_INDEX_FILE: &str = "index.json";
+/// Name of the file explaining how to verify the bundle.
+pub const MIRROR_INSTRUCTIONS_FILE: &str = "VERIFY.md";
+
+const CONTRIBUTIONS_FILE: &str = "contributions.json";
+
+const INSTRUCTIONS: &str = r#"# Namada Trusted Setup transcript
+
+This directory is a static mirror of the transcript of the ceremony. It can be served as is by any HTTP server or shared via torrent.
+
+`index.json` lists every artifact of the bundle together with its size, its Blake2b-512 hash (hex encoded) and the ed25519 signature of the coordinator over the message `<path>:<hash>`, hex encoded. The public key of the coordinator is stored in the `coordinator_public_key` field.
+
+To verify the bundle:
+
+1. For every artifact of the index, compute the Blake2b-512 hash of the file at `path` (e.g. `b2sum -l 512 <path>`) and compare it with `hash`.
+2. Verify `signature` against the message `<path>:<hash>` with the public key of the coordinator, to ensure the artifact was published by the coordinator.
+3. Verify the challenges themselves, e.g. with `namada-ts verify-contribution` for the signatures of the contributions listed in `contributions.json`.
+
+The challenge of round `n` contains the parameters produced by the contribution of round `n - 1`, the contribution hash at its head matches the one published for that contribution.
+"#;
+
+/// An artifact of the mirror bundle.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MirrorArtifact {
+    /// Round to which the artifact relates, if any
+    pub round_height: Option<u64>,
+    /// Path of the artifact, relative to the root of the bundle
+    pub path: String,
+    pub size: u64,
+    /// Blake2b-512, hex encoded, hash of the artifact
+    pub hash: String,
+    /// Signature of the coordinator over `<path>:<hash>`
+    pub signature: String,
+}
+
+/// The index of the mirror bundle.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MirrorIndex {
+    pub coordinator_public_key: String,
+    /// Latest round whose challenge is part of the bundle
+    pub latest_round_height: Option<u64>,
+    pub artifacts: Vec<MirrorArtifact>,
+}
+
+impl MirrorIndex {
+    /// Returns `true` if the artifact at the given path is already part of the bundle.
+    fn contains(&self, path: &str) -> bool {
+        self.artifacts.iter().any(|a| a.path == path)
+    }
+}
+
+pub(crate) struct Mirror;
+
+impl Mirror {
+    ///
+    /// Updates the static mirror bundle in `output_dir` with the challenges of the rounds up to
+    /// `round_height` included, signing every artifact with the coordinator key.
+    ///
+    /// Challenges and signatures already part of the bundle are left untouched, so that the bundle
+    /// can be regenerated incrementally as new rounds are finalized. The contributions summary is
+    /// always refreshed.
+    ///
+    pub(crate) fn run(
+        storage: &Disk,
+        signature: Arc<dyn Signature>,
+        signing_key: &str,
+        public_key: &str,
+        round_height: u64,
+        output_dir: &Path,
+    ) -> Result<MirrorIndex, CoordinatorError> {
+        info!("Starting update of the mirror bundle up to round {}", round_height);
+        let start = Instant::now();
+
+        let index_path = output_dir.join(MIRROR_INDEX_FILE);
+        let mut index: MirrorIndex = match index_path.exists() {
+            true => serde_json::from_slice(&fs::read(&index_path)?)?,
+            false => MirrorIndex::default(),
+        };
+        index.coordinator_public_key = public_key.to_string();
+
+        let add_artifact = |index: &mut MirrorIndex,
+                            round: Option<u64>,
+                            locator: &Locator,
+                            path: String|
+         -> Result<(), CoordinatorError> {
+            let target = output_dir.join(&path);
+            if index.contains(&path) && target.exists() {
+                return Ok(());
+            }
+
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            // Stream the plaintext of the artifact to the bundle, hashing it on the way
+            let mut reader = storage.open(locator)?;
+            let mut writer = io::BufWriter::new(fs::File::create(&target)?);
+            let mut hasher = Blake2b512::new();
+            let mut buffer = vec![0; 1 << 16];
+            let mut size = 0;
+            loop {
+                let read = reader.read(&mut buffer)?;
+                if read == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..read]);
+                writer.write_all(&buffer[..read])?;
+                size += read as u64;
+            }
+            writer.flush()?;
+
+            let hash = hex::encode(hasher.finalize());
+            let artifact_signature = signature.sign(signing_key, &format!("{}:{}", path, hash))?;
+            debug!("Added {} with hash {} to the mirror bundle", path, hash);
+
+            index.artifacts.retain(|a| a.path != path);
+            index.artifacts.push(MirrorArtifact {
+                round_height: round,
+                path,
+                size,
+                hash,
+                signature: artifact_signature,
+            });
+
+            Ok(())
+        };
+
+        for height in 0..=round_height {
+            let challenge = Locator::ContributionFile(ContributionLocator::new(height, 0, 0, true));
+            if !storage.exists(&challenge) {
+                continue;
+            }
+            add_artifact(&mut index, Some(height), &challenge, format!("round_{}/challenge", height))?;
+
+            let challenge_signature =
+                Locator::ContributionFileSignature(ContributionSignatureLocator::new(height, 0, 0, true));
+            if storage.exists(&challenge_signature) {
+                add_artifact(
+                    &mut index,
+                    Some(height),
+                    &challenge_signature,
+                    format!("round_{}/challenge.signature", height),
+                )?;
+            }
+
+            index.latest_round_height = Some(height);
+        }
+
+        // The summary changes with every contribution, always publish the latest one
+        if storage.exists(&Locator::ContributionsInfoSummary) {
+            index.artifacts.retain(|a| a.path != CONTRIBUTIONS_FILE);
+            add_artifact(
+                &mut index,
+                None,
+                &Locator::ContributionsInfoSummary,
+                CONTRIBUTIONS_FILE.to_string(),
+            )?;
+        }
+
+        fs::write(output_dir.join(MIRROR_INSTRUCTIONS_FILE), INSTRUCTIONS)?;
+        fs::write(&index_path, serde_json::to_vec_pretty(&index)?)?;
+
+        let elapsed = Instant::now().duration_since(start);
+        info!("Completed update of the mirror bundle in {:?}", elapsed);
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        authentication::{Dummy, Signature},
+        commands::{Initialization, Mirror, MIRROR_INDEX_FILE, MIRROR_INSTRUCTIONS_FILE},
+        testing::prelude::*,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    #[serial]
+    fn test_mirror_run() {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        // Initialize the round 0 challenge
+        let mut storage = test_storage(&TEST_ENVIRONMENT_ANOMA);
+        Initialization::run(&TEST_ENVIRONMENT_ANOMA, &mut storage, 0, 0).unwrap();
+
+        let output_dir = tempfile::tempdir().unwrap();
+        let index = Mirror::run(&storage, Arc::new(Dummy), "secret", "public", 0, output_dir.path()).unwrap();
+
+        assert_eq!(index.latest_round_height, Some(0));
+        let challenge = index.artifacts.iter().find(|a| a.path == "round_0/challenge").unwrap();
+        assert!(Dummy.verify("public", &format!("{}:{}", challenge.path, challenge.hash), &challenge.signature));
+        assert!(output_dir.path().join(&challenge.path).exists());
+        assert!(output_dir.path().join(MIRROR_INDEX_FILE).exists());
+        assert!(output_dir.path().join(MIRROR_INSTRUCTIONS_FILE).exists());
+
+        // A second run doesn't duplicate the artifacts
+        let second_index = Mirror::run(&storage, Arc::new(Dummy), "secret", "public", 0, output_dir.path()).unwrap();
+        assert_eq!(second_index.artifacts.len(), index.artifacts.len());
+    }
+}
@@ -1,6 +1,12 @@
 pub(crate) mod aggregation;
 pub(crate) use aggregation::*;
 
+pub mod beacon;
+pub use beacon::*;
+
+pub mod export;
+pub use export::*;
+
 #[cfg(any(test, feature = "operator"))]
 pub mod computation;
 #[cfg(any(test, feature = "operator"))]
@@ -9,16 +15,27 @@ pub use computation::*;
 pub(crate) mod initialization;
 pub(crate) use initialization::*;
 
+pub mod mirror;
+pub use mirror::*;
+
+pub mod ptau;
+pub use ptau::*;
+
+pub mod replication;
+pub use replication::*;
+
 #[cfg(any(test, feature = "operator"))]
 pub(crate) mod verification;
 #[cfg(any(test, feature = "operator"))]
 pub(crate) use verification::*;
+#[cfg(any(test, feature = "operator"))]
+pub use verification::verify_response;
 
 #[cfg(any(test, feature = "operator"))]
 use crate::{
     authentication::Signature,
     objects::{ContributionFileSignature, ContributionState},
-    storage::{Disk, Locator, StorageLocator, StorageObject},
+    storage::{Disk, Locator, Object, StorageLocator, StorageObject},
     CoordinatorError,
 };
 
@@ -26,7 +43,7 @@ use crate::{
 use setup_utils::calculate_hash;
 
 #[cfg(any(test, feature = "operator"))]
-use std::{io::Write, sync::Arc};
+use std::sync::Arc;
 
 #[cfg(any(test, feature = "operator"))]
 pub type SigningKey = String;
@@ -55,8 +72,6 @@ pub(crate) fn write_contribution_file_signature(
     next_challenge_locator: Option<&Locator>,
     contribution_file_signature_locator: &Locator,
 ) -> Result<(), CoordinatorError> {
-    use crate::storage::ObjectWriter;
-
     // Calculate the challenge hash.
     let challenge_reader = storage.reader(challenge_locator)?;
     let challenge_hash = calculate_hash(challenge_reader.as_ref()).to_vec();
@@ -84,20 +99,16 @@ pub(crate) fn write_contribution_file_signature(
 
     // Construct the contribution file signature.
     let contribution_file_signature = ContributionFileSignature::new(contribution_signature, contribution_state)?;
-    let contribution_file_signature_bytes = serde_json::to_vec_pretty(&contribution_file_signature)?;
 
-    // Write the contribution file signature.
-    let mut contribution_file_signature_writer = storage.writer(contribution_file_signature_locator)?;
+    // Write the contribution file signature, encrypted if the storage is.
     tracing::debug!(
-        "Writing contribution file signature of size {} to {}",
-        contribution_file_signature_bytes.len(),
+        "Writing contribution file signature to {}",
         &storage.to_path(&contribution_file_signature_locator)?
     );
-
-    contribution_file_signature_writer
-        .as_mut()
-        .write_all(&contribution_file_signature_bytes[..])?;
-    contribution_file_signature_writer.flush()?;
+    storage.update(
+        contribution_file_signature_locator,
+        Object::ContributionFileSignature(contribution_file_signature),
+    )?;
 
     Ok(())
 }
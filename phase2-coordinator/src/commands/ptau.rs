@@ -0,0 +1,473 @@
+use crate::CoordinatorError;
+
+use bellman::{
+    domain::{EvaluationDomain, Point},
+    multicore::Worker,
+};
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use ff::Field;
+use group::{prime::PrimeCurveAffine, Curve, Group};
+use memmap::Mmap;
+use num_bigint::BigUint;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    time::Instant,
+};
+use tracing::{debug, info};
+
+/// Modulus of the base field of BLS12-381, the only curve supported for the import.
+const BLS12_381_MODULUS: &str = "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+/// Size in bytes of an element of the base field.
+const FIELD_SIZE: usize = 48;
+const G1_SIZE: usize = 2 * FIELD_SIZE;
+const G2_SIZE: usize = 4 * FIELD_SIZE;
+/// Highest power of the transcripts supported for the import, the one of the largest public ceremonies.
+const MAX_POWER: u32 = 28;
+
+// Sections of a .ptau file
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+const ALPHA_TAU_G1_SECTION: u32 = 4;
+const BETA_TAU_G1_SECTION: u32 = 5;
+const BETA_G2_SECTION: u32 = 6;
+
+fn import_error<S: ToString>(msg: S) -> CoordinatorError {
+    CoordinatorError::PtauImportFailed(msg.to_string())
+}
+
+/// Converts field elements from the little-endian Montgomery encoding used by snarkjs to the canonical big-endian one.
+struct FieldDecoder {
+    modulus: BigUint,
+    r_inv: BigUint,
+}
+
+impl FieldDecoder {
+    fn new() -> Self {
+        let modulus = BigUint::parse_bytes(BLS12_381_MODULUS.as_bytes(), 16).unwrap();
+        let r = BigUint::from(1u8) << (8 * FIELD_SIZE);
+        let r_inv = r.modpow(&(&modulus - 2u8), &modulus);
+
+        Self { modulus, r_inv }
+    }
+
+    fn decode(&self, bytes: &[u8], output: &mut [u8]) {
+        let value = (BigUint::from_bytes_le(bytes) * &self.r_inv) % &self.modulus;
+        let value = value.to_bytes_be();
+        output.iter_mut().for_each(|b| *b = 0);
+        output[FIELD_SIZE - value.len()..].copy_from_slice(&value);
+    }
+
+    fn decode_g1(&self, bytes: &[u8]) -> Result<G1Affine, CoordinatorError> {
+        if bytes.iter().all(|b| *b == 0) {
+            return Ok(G1Affine::identity());
+        }
+
+        let mut uncompressed = [0u8; G1_SIZE];
+        self.decode(&bytes[..FIELD_SIZE], &mut uncompressed[..FIELD_SIZE]);
+        self.decode(&bytes[FIELD_SIZE..], &mut uncompressed[FIELD_SIZE..]);
+
+        // Checks that the point is on the curve and in the correct subgroup
+        Option::from(G1Affine::from_uncompressed(&uncompressed)).ok_or_else(|| import_error("invalid G1 point"))
+    }
+
+    fn decode_g2(&self, bytes: &[u8]) -> Result<G2Affine, CoordinatorError> {
+        if bytes.iter().all(|b| *b == 0) {
+            return Ok(G2Affine::identity());
+        }
+
+        // Snarkjs stores (x.c0, x.c1, y.c0, y.c1), the zcash encoding expects (x.c1, x.c0, y.c1, y.c0)
+        let mut uncompressed = [0u8; G2_SIZE];
+        for (from, to) in [(0, 1), (1, 0), (2, 3), (3, 2)] {
+            self.decode(
+                &bytes[from * FIELD_SIZE..(from + 1) * FIELD_SIZE],
+                &mut uncompressed[to * FIELD_SIZE..(to + 1) * FIELD_SIZE],
+            );
+        }
+
+        Option::from(G2Affine::from_uncompressed(&uncompressed)).ok_or_else(|| import_error("invalid G2 point"))
+    }
+}
+
+/// The accumulator contained in a .ptau file.
+struct Accumulator {
+    power: u32,
+    tau_g1: Vec<G1Affine>,
+    tau_g2: Vec<G2Affine>,
+    alpha_tau_g1: Vec<G1Affine>,
+    beta_tau_g1: Vec<G1Affine>,
+    beta_g2: G2Affine,
+}
+
+impl Accumulator {
+    /// Parses a snarkjs .ptau file.
+    fn read(ptau: &[u8]) -> Result<Self, CoordinatorError> {
+        let read_u32 = |offset: usize| -> Result<u32, CoordinatorError> {
+            Ok(u32::from_le_bytes(
+                ptau.get(offset..offset + 4)
+                    .ok_or_else(|| import_error("unexpected end of file"))?
+                    .try_into()?,
+            ))
+        };
+
+        if ptau.get(..4) != Some(b"ptau".as_ref()) {
+            return Err(import_error("not a ptau file"));
+        }
+
+        // Locate the sections of the file
+        let number_of_sections = read_u32(8)?;
+        let mut sections: HashMap<u32, &[u8]> = HashMap::new();
+        let mut offset = 12;
+        for _ in 0..number_of_sections {
+            let section_type = read_u32(offset)?;
+            let size = u64::from_le_bytes(
+                ptau.get(offset + 4..offset + 12)
+                    .ok_or_else(|| import_error("unexpected end of file"))?
+                    .try_into()?,
+            ) as usize;
+            let data = ptau
+                .get(offset + 12..offset + 12 + size)
+                .ok_or_else(|| import_error("unexpected end of file"))?;
+            sections.entry(section_type).or_insert(data);
+            offset += 12 + size;
+        }
+
+        let section = |section_type: u32| {
+            sections
+                .get(&section_type)
+                .copied()
+                .ok_or_else(|| import_error(format!("missing section {}", section_type)))
+        };
+
+        // Check the curve of the file
+        let header = section(HEADER_SECTION)?;
+        if header.len() < 12 + FIELD_SIZE {
+            return Err(import_error("bad header"));
+        }
+        let field_size = u32::from_le_bytes(header[..4].try_into()?) as usize;
+        let modulus = BigUint::from_bytes_le(&header[4..4 + FIELD_SIZE]);
+        if field_size != FIELD_SIZE || modulus != BigUint::parse_bytes(BLS12_381_MODULUS.as_bytes(), 16).unwrap() {
+            return Err(import_error("only BLS12-381 ptau files are supported"));
+        }
+        let power = u32::from_le_bytes(header[4 + FIELD_SIZE..8 + FIELD_SIZE].try_into()?);
+        if power == 0 || power > MAX_POWER {
+            return Err(import_error(format!(
+                "unsupported power {}, expected between 1 and {}",
+                power, MAX_POWER
+            )));
+        }
+        let length = 1usize << power;
+
+        let decoder = FieldDecoder::new();
+        let read_g1 = |section_type: u32, count: usize| -> Result<Vec<G1Affine>, CoordinatorError> {
+            let data = section(section_type)?;
+            if data.len() != count * G1_SIZE {
+                return Err(import_error(format!("unexpected size of section {}", section_type)));
+            }
+            data.chunks(G1_SIZE).map(|p| decoder.decode_g1(p)).collect()
+        };
+        let read_g2 = |section_type: u32, count: usize| -> Result<Vec<G2Affine>, CoordinatorError> {
+            let data = section(section_type)?;
+            if data.len() != count * G2_SIZE {
+                return Err(import_error(format!("unexpected size of section {}", section_type)));
+            }
+            data.chunks(G2_SIZE).map(|p| decoder.decode_g2(p)).collect()
+        };
+
+        Ok(Self {
+            power,
+            tau_g1: read_g1(TAU_G1_SECTION, 2 * length - 1)?,
+            tau_g2: read_g2(TAU_G2_SECTION, length)?,
+            alpha_tau_g1: read_g1(ALPHA_TAU_G1_SECTION, length)?,
+            beta_tau_g1: read_g1(BETA_TAU_G1_SECTION, length)?,
+            beta_g2: read_g2(BETA_G2_SECTION, 1)?[0],
+        })
+    }
+
+    /// Checks that the accumulator is made of consistent powers of the same tau, alpha and beta.
+    fn verify(&self) -> Result<(), CoordinatorError> {
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+
+        if self.tau_g1[0] != g1 || self.tau_g2[0] != g2 {
+            return Err(import_error("first powers of tau are not the generators"));
+        }
+
+        // e(tau * G1, G2) == e(G1, tau * G2)
+        if pairing(&self.tau_g1[1], &g2) != pairing(&g1, &self.tau_g2[1]) {
+            return Err(import_error("tau in G1 and G2 differ"));
+        }
+
+        // e(beta * G1, G2) == e(G1, beta * G2)
+        if pairing(&self.beta_tau_g1[0], &g2) != pairing(&g1, &self.beta_g2) {
+            return Err(import_error("beta in G1 and G2 differ"));
+        }
+
+        // Check that every sequence is made of successive powers of tau by means of a random linear combination
+        let mut rng = ChaCha20Rng::from_entropy();
+        let tau_g2 = self.tau_g2[1];
+        for (name, powers) in [
+            ("tau G1", &self.tau_g1),
+            ("alpha tau G1", &self.alpha_tau_g1),
+            ("beta tau G1", &self.beta_tau_g1),
+        ] {
+            let (left, right) = Self::random_linear_combination(powers, &mut rng);
+            if pairing(&left, &tau_g2) != pairing(&right, &g2) {
+                return Err(import_error(format!("{} are not consecutive powers of tau", name)));
+            }
+        }
+
+        let (left, right) = Self::random_linear_combination(&self.tau_g2, &mut rng);
+        if pairing(&self.tau_g1[1], &left) != pairing(&g1, &right) {
+            return Err(import_error("tau G2 are not consecutive powers of tau"));
+        }
+
+        Ok(())
+    }
+
+    /// Returns (sum r_i * P_i, sum r_i * P_(i+1)) for random r_i
+    fn random_linear_combination<G>(powers: &[G], rng: &mut ChaCha20Rng) -> (G, G)
+    where
+        G: PrimeCurveAffine<Scalar = Scalar>,
+    {
+        let mut left = G::Curve::identity();
+        let mut right = G::Curve::identity();
+
+        for window in powers.windows(2) {
+            let r = Scalar::random(&mut *rng);
+            left += window[0] * r;
+            right += window[1] * r;
+        }
+
+        (left.to_affine(), right.to_affine())
+    }
+
+    /// Writes the phase1radix2m{exp} file expected by `masp_phase2` for a domain of size 2^exp.
+    fn write_radix<W: Write>(&self, exp: u32, writer: &mut W) -> Result<(), CoordinatorError> {
+        let m = 1usize << exp;
+        let worker = Worker::new();
+
+        // Lagrange coefficients are obtained through an inverse FFT of the powers of tau
+        let lagrange_g1 = Self::lagrange_coefficients(&self.tau_g1[..m], &worker)?;
+        let lagrange_g2 = Self::lagrange_coefficients(&self.tau_g2[..m], &worker)?;
+        let lagrange_alpha_g1 = Self::lagrange_coefficients(&self.alpha_tau_g1[..m], &worker)?;
+        let lagrange_beta_g1 = Self::lagrange_coefficients(&self.beta_tau_g1[..m], &worker)?;
+
+        // Bases for the H query: tau^i * (tau^m - 1) = tau^(i + m) - tau^i for i in 0..(m - 1)
+        let h: Vec<G1Projective> = (0..m - 1)
+            .map(|i| G1Projective::from(self.tau_g1[i + m]) - G1Projective::from(self.tau_g1[i]))
+            .collect();
+        let mut h_affine = vec![G1Affine::identity(); h.len()];
+        G1Projective::batch_normalize(&h, &mut h_affine);
+
+        writer.write_all(self.alpha_tau_g1[0].to_uncompressed().as_ref())?;
+        writer.write_all(self.beta_tau_g1[0].to_uncompressed().as_ref())?;
+        writer.write_all(self.beta_g2.to_uncompressed().as_ref())?;
+        for p in &lagrange_g1 {
+            writer.write_all(p.to_uncompressed().as_ref())?;
+        }
+        for p in &lagrange_g2 {
+            writer.write_all(p.to_uncompressed().as_ref())?;
+        }
+        for p in lagrange_alpha_g1.iter().chain(lagrange_beta_g1.iter()).chain(h_affine.iter()) {
+            writer.write_all(p.to_uncompressed().as_ref())?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    fn lagrange_coefficients<G>(powers: &[G], worker: &Worker) -> Result<Vec<G>, CoordinatorError>
+    where
+        G: PrimeCurveAffine<Scalar = Scalar>,
+    {
+        let coeffs = powers.iter().map(|p| Point(p.to_curve())).collect();
+        let mut domain = EvaluationDomain::from_coeffs(coeffs).map_err(|e| import_error(e.to_string()))?;
+        domain.ifft(worker);
+
+        let projective: Vec<G::Curve> = domain.into_coeffs().into_iter().map(|p| p.0).collect();
+        let mut affine = vec![G::identity(); projective.len()];
+        G::Curve::batch_normalize(&projective, &mut affine);
+
+        Ok(affine)
+    }
+}
+
+pub struct PtauImport;
+
+impl PtauImport {
+    ///
+    /// Imports an existing Powers of Tau transcript in snarkjs `.ptau` format (BLS12-381 only).
+    ///
+    /// The transcript is verified and converted into the `phase1radix2m{exp}` files, for every
+    /// exponent up to the power of the transcript, that are used by `masp_phase2` to generate
+    /// the round 0 challenge of the ceremony. The files are written to the given directory and
+    /// linked into the working directory, where `masp_phase2` looks for them. Returns the power
+    /// of the imported transcript.
+    ///
+    pub fn run(ptau_path: &Path, output_dir: &Path) -> Result<u32, CoordinatorError> {
+        info!("Starting import of {}", ptau_path.display());
+        let start = Instant::now();
+
+        let file = File::open(ptau_path)?;
+        let ptau = unsafe { Mmap::map(&file)? };
+
+        let accumulator = Accumulator::read(&ptau)?;
+        debug!("Parsed ptau file of power {}", accumulator.power);
+
+        accumulator.verify()?;
+        info!("Verified ptau file of power {}", accumulator.power);
+
+        std::fs::create_dir_all(output_dir)?;
+        for exp in 0..=accumulator.power {
+            let path = output_dir.join(format!("phase1radix2m{}", exp));
+            let mut writer = BufWriter::new(File::create(&path)?);
+            accumulator.write_radix(exp, &mut writer)?;
+            debug!("Written {}", path.display());
+            Self::link_to_working_directory(&path)?;
+        }
+
+        let elapsed = Instant::now().duration_since(start);
+        info!("Completed import of {} in {:?}", ptau_path.display(), elapsed);
+        Ok(accumulator.power)
+    }
+
+    /// Links the given file into the working directory, replacing the file of the same name if any.
+    fn link_to_working_directory(path: &Path) -> Result<(), CoordinatorError> {
+        let target = std::fs::canonicalize(path)?;
+        let link = match path.file_name() {
+            Some(name) => Path::new(name),
+            None => return Err(import_error(format!("invalid output file {}", path.display()))),
+        };
+        if link == target {
+            return Ok(());
+        }
+        if link.symlink_metadata().is_ok() {
+            std::fs::remove_file(link)?;
+        }
+
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&target, link)?;
+        #[cfg(not(unix))]
+        std::fs::copy(&target, link)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes a canonical big-endian field element in the little-endian Montgomery form used by snarkjs.
+    fn encode(value: &[u8], output: &mut Vec<u8>) {
+        let modulus = BigUint::parse_bytes(BLS12_381_MODULUS.as_bytes(), 16).unwrap();
+        let r = BigUint::from(1u8) << (8 * FIELD_SIZE);
+        let mut bytes = ((BigUint::from_bytes_be(value) * r) % modulus).to_bytes_le();
+        bytes.resize(FIELD_SIZE, 0);
+        output.extend(bytes);
+    }
+
+    fn encode_g1(p: &G1Affine, output: &mut Vec<u8>) {
+        let bytes = p.to_uncompressed();
+        encode(&bytes[..FIELD_SIZE], output);
+        encode(&bytes[FIELD_SIZE..], output);
+    }
+
+    fn encode_g2(p: &G2Affine, output: &mut Vec<u8>) {
+        let bytes = p.to_uncompressed();
+        for i in [1, 0, 3, 2] {
+            encode(&bytes[i * FIELD_SIZE..(i + 1) * FIELD_SIZE], output);
+        }
+    }
+
+    fn section(section_type: u32, data: Vec<u8>, output: &mut Vec<u8>) {
+        output.extend(section_type.to_le_bytes());
+        output.extend((data.len() as u64).to_le_bytes());
+        output.extend(data);
+    }
+
+    /// Builds a ptau file of the given power, for the given secrets.
+    fn test_ptau(power: u32, tau: Scalar, alpha: Scalar, beta: Scalar) -> Vec<u8> {
+        let length = 1usize << power;
+        let powers: Vec<Scalar> = std::iter::successors(Some(Scalar::one()), |p| Some(p * tau))
+            .take(2 * length - 1)
+            .collect();
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+
+        let mut header = Vec::new();
+        header.extend((FIELD_SIZE as u32).to_le_bytes());
+        let mut modulus = BigUint::parse_bytes(BLS12_381_MODULUS.as_bytes(), 16).unwrap().to_bytes_le();
+        modulus.resize(FIELD_SIZE, 0);
+        header.extend(modulus);
+        header.extend(power.to_le_bytes());
+        header.extend(power.to_le_bytes());
+
+        let mut tau_g1 = Vec::new();
+        powers.iter().for_each(|p| encode_g1(&(g1 * p).to_affine(), &mut tau_g1));
+        let mut tau_g2 = Vec::new();
+        powers[..length].iter().for_each(|p| encode_g2(&(g2 * p).to_affine(), &mut tau_g2));
+        let mut alpha_tau_g1 = Vec::new();
+        powers[..length].iter().for_each(|p| encode_g1(&(g1 * (p * alpha)).to_affine(), &mut alpha_tau_g1));
+        let mut beta_tau_g1 = Vec::new();
+        powers[..length].iter().for_each(|p| encode_g1(&(g1 * (p * beta)).to_affine(), &mut beta_tau_g1));
+        let mut beta_g2 = Vec::new();
+        encode_g2(&(g2 * beta).to_affine(), &mut beta_g2);
+
+        let mut ptau = b"ptau".to_vec();
+        ptau.extend(1u32.to_le_bytes());
+        ptau.extend(6u32.to_le_bytes());
+        section(HEADER_SECTION, header, &mut ptau);
+        section(TAU_G1_SECTION, tau_g1, &mut ptau);
+        section(TAU_G2_SECTION, tau_g2, &mut ptau);
+        section(ALPHA_TAU_G1_SECTION, alpha_tau_g1, &mut ptau);
+        section(BETA_TAU_G1_SECTION, beta_tau_g1, &mut ptau);
+        section(BETA_G2_SECTION, beta_g2, &mut ptau);
+
+        ptau
+    }
+
+    #[test]
+    fn test_ptau_import() {
+        let power = 3;
+        let tau = Scalar::from(7u64);
+        let ptau = test_ptau(power, tau, Scalar::from(11u64), Scalar::from(13u64));
+
+        let accumulator = Accumulator::read(&ptau).unwrap();
+        assert_eq!(accumulator.power, power);
+        assert_eq!(accumulator.tau_g1[1], (G1Affine::generator() * tau).to_affine());
+        accumulator.verify().unwrap();
+
+        // phase1radix2m{exp} is made of 2 G1 + 1 G2 + m G1 + m G2 + 2m G1 + (m - 1) G1
+        for exp in 0..=power {
+            let m = 1usize << exp;
+            let mut radix = Vec::new();
+            accumulator.write_radix(exp, &mut radix).unwrap();
+            assert_eq!(radix.len(), (2 + m + 2 * m + m - 1) * G1_SIZE + (1 + m) * G2_SIZE);
+        }
+
+        // Corrupted transcript: tau G2 doesn't match tau G1
+        let mut corrupted = Accumulator::read(&ptau).unwrap();
+        corrupted.tau_g2[1] = (G2Affine::generator() * Scalar::from(8u64)).to_affine();
+        assert!(corrupted.verify().is_err());
+
+        // Not a ptau file
+        assert!(Accumulator::read(b"not a ptau file").is_err());
+
+        // Unsupported powers, the power following the modulus in the header section
+        let power_offset = 12 + 12 + 4 + FIELD_SIZE;
+        for power in [0u32, MAX_POWER + 1, 64] {
+            let mut unsupported = ptau.clone();
+            unsupported[power_offset..power_offset + 4].copy_from_slice(&power.to_le_bytes());
+            assert!(Accumulator::read(&unsupported).is_err());
+        }
+    }
+}
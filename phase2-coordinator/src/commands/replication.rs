@@ -0,0 +1,265 @@
+use crate::{
+    objects::Round,
+    storage::{Disk, Locator, LocatorPath, Object, ReplicaCopy, StorageLocator},
+    CoordinatorError,
+};
+
+use serde::{Deserialize, Serialize};
+use setup_utils::calculate_hash;
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tracing::{debug, info, warn};
+
+/// Name of the index of the replica.
+pub const REPLICATION_INDEX_FILE: &str = "replication.json";
+
+/// The index of the replica, with the files it holds.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ReplicationIndex {
+    /// Height of the current round as of the last replication
+    pub round_height: Option<u64>,
+    /// Hash, hex encoded, of each replicated file by its path in the storage
+    pub files: BTreeMap<String, String>,
+}
+
+/// The outcome of a run of the [Replication].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ReplicationReport {
+    pub round_height: u64,
+    /// Number of files found missing or outdated in the replica at the start of the run
+    pub pending_files: u64,
+    /// Number of files copied by the run, all the pending ones unless it failed
+    pub replicated_files: u64,
+    pub replicated_bytes: u64,
+    /// Number of files held by the replica
+    pub total_files: u64,
+}
+
+/// The files a run of the [Replication] has to copy to the replica, collected by [Replication::prepare] and copied by
+/// [PendingReplication::run] without holding the storage.
+pub struct PendingReplication {
+    round_height: u64,
+    output_dir: PathBuf,
+    index: ReplicationIndex,
+    /// Copy of each file, with its path in the storage and its hash
+    copies: Vec<(ReplicaCopy, String, String)>,
+}
+
+pub(crate) struct Replication;
+
+impl Replication {
+    ///
+    /// Collects the files to update the replica in `output_dir` with: the accepted contributions
+    /// and their signatures, the verified ones included, and a checkpoint of the state of the
+    /// ceremony, up to `round_height` included.
+    ///
+    /// A file is copied again only if it changed since the last run, which the index of the
+    /// replica tells. The state objects and signatures are read right away, while the sealed
+    /// contributions, which never change, are only copied by [PendingReplication::run].
+    ///
+    pub(crate) fn prepare(
+        storage: &Disk,
+        round_height: u64,
+        output_dir: &Path,
+    ) -> Result<PendingReplication, CoordinatorError> {
+        let index_path = output_dir.join(REPLICATION_INDEX_FILE);
+        let index: ReplicationIndex = match index_path.exists() {
+            true => serde_json::from_slice(&fs::read(&index_path)?)?,
+            false => ReplicationIndex::default(),
+        };
+
+        // Collect the files to replicate before copying any of them, for the lag to be known
+        let mut copies = vec![];
+        for locator in Self::locators(storage, round_height)? {
+            let path = storage.to_path(&locator)?.to_string();
+            let hash = Self::hash(storage, &locator)?;
+            if index.files.get(&path) != Some(&hash) {
+                copies.push((storage.replica_copy(&locator, output_dir)?, path, hash));
+            }
+        }
+
+        Ok(PendingReplication {
+            round_height,
+            output_dir: output_dir.to_path_buf(),
+            index,
+            copies,
+        })
+    }
+
+    /// Returns the locators of the files to replicate: the state objects, then the files of the accepted
+    /// contributions of each round.
+    fn locators(storage: &Disk, round_height: u64) -> Result<Vec<Locator>, CoordinatorError> {
+        let mut locators = vec![Locator::CoordinatorState, Locator::RoundHeight];
+        for height in 0..=round_height {
+            let state = Locator::RoundState { round_height: height };
+            if !storage.exists(&state) {
+                continue;
+            }
+            let round = match storage.get(&state)? {
+                Object::RoundState(round) => round,
+                _ => return Err(CoordinatorError::StorageFailed),
+            };
+            locators.push(state);
+            locators.extend(Self::contribution_locators(storage, &round)?);
+        }
+
+        Ok(locators.into_iter().filter(|locator| storage.exists(locator)).collect())
+    }
+
+    /// Returns the locators of the contribution files and signatures of the round, once accepted.
+    fn contribution_locators(storage: &Disk, round: &Round) -> Result<Vec<Locator>, CoordinatorError> {
+        let mut locators = vec![];
+        for chunk in round.chunks() {
+            for contribution in chunk.get_contributions().values() {
+                // A contribution is accepted once both its file and its signature are in the storage
+                let paths: [&Option<LocatorPath>; 4] = [
+                    contribution.get_contributed_location(),
+                    contribution.get_contributed_signature_location(),
+                    contribution.get_verified_location(),
+                    contribution.get_verified_signature_location(),
+                ];
+                for pair in paths.chunks(2) {
+                    if let (Some(file), Some(signature)) = (pair[0], pair[1]) {
+                        let file = storage.to_locator(file)?;
+                        let signature = storage.to_locator(signature)?;
+                        if storage.exists(&file) && storage.exists(&signature) {
+                            locators.push(file);
+                            locators.push(signature);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(locators)
+    }
+
+    /// Returns the [`hex`] encoded hash of the file at the given locator, known without reading it once sealed.
+    fn hash(storage: &Disk, locator: &Locator) -> Result<String, CoordinatorError> {
+        match storage.content_hash(locator)? {
+            Some(hash) => Ok(hash),
+            None => Ok(hex::encode(calculate_hash(&storage.read(locator)?))),
+        }
+    }
+}
+
+impl PendingReplication {
+    ///
+    /// Copies the collected files to the replica, under the same paths as in the storage, and
+    /// encrypted the same way, so that the replica can take the place of a lost storage. Files
+    /// removed from the storage, such as a dropped contribution, are kept in the replica, and a
+    /// file released since it was collected is left pending for the next run.
+    ///
+    pub fn run(self) -> Result<ReplicationReport, CoordinatorError> {
+        info!("Starting replication of the storage up to round {}", self.round_height);
+        let start = Instant::now();
+
+        let mut index = self.index;
+        let mut report = ReplicationReport {
+            round_height: self.round_height,
+            pending_files: self.copies.len() as u64,
+            ..Default::default()
+        };
+        let mut result = Ok(());
+        let mut is_complete = true;
+        for (copy, path, hash) in self.copies {
+            match copy.run() {
+                Ok(Some(size)) => {
+                    debug!("Replicated {} with hash {}", path, hash);
+                    index.files.insert(path, hash);
+                    report.replicated_files += 1;
+                    report.replicated_bytes += size;
+                }
+                Ok(None) => {
+                    warn!("{} was released from the storage before being replicated", path);
+                    is_complete = false;
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        if result.is_ok() && is_complete {
+            index.round_height = Some(self.round_height);
+        }
+        report.total_files = index.files.len() as u64;
+
+        // The files copied before a failure are recorded, not to be copied again by the next run
+        fs::create_dir_all(&self.output_dir)?;
+        fs::write(
+            self.output_dir.join(REPLICATION_INDEX_FILE),
+            serde_json::to_vec_pretty(&index)?,
+        )?;
+        result?;
+
+        let elapsed = Instant::now().duration_since(start);
+        info!(
+            "Completed replication of {} files in {:?}",
+            report.replicated_files, elapsed
+        );
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        commands::{Initialization, Replication, REPLICATION_INDEX_FILE},
+        objects::Round,
+        storage::{Locator, Object, StorageLocator},
+        testing::prelude::*,
+    };
+
+    #[test]
+    #[serial]
+    fn test_replication_run() {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        // Initialize the round 0 challenge
+        let mut storage = test_storage(&TEST_ENVIRONMENT_ANOMA);
+        Initialization::run(&TEST_ENVIRONMENT_ANOMA, &mut storage, 0, 0).unwrap();
+        let round = Round::new(&TEST_ENVIRONMENT_ANOMA, &mut storage, 0, *TEST_STARTED_AT, vec![]).unwrap();
+        storage.insert(Locator::RoundHeight, Object::RoundHeight(0)).unwrap();
+        storage
+            .insert(Locator::RoundState { round_height: 0 }, Object::RoundState(round))
+            .unwrap();
+
+        let output_dir = tempfile::tempdir().unwrap();
+        let report = Replication::prepare(&storage, 0, output_dir.path())
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(report.pending_files, report.replicated_files);
+        assert_eq!(report.total_files, report.replicated_files);
+        assert!(output_dir.path().join(REPLICATION_INDEX_FILE).exists());
+
+        let round_height = storage.to_path(&Locator::RoundHeight).unwrap();
+        let base = std::path::Path::new(TEST_ENVIRONMENT_ANOMA.local_base_directory());
+        let replica = output_dir
+            .path()
+            .join(round_height.as_path().strip_prefix(base).unwrap());
+        assert_eq!(std::fs::read(replica).unwrap(), b"0");
+
+        // A second run only copies the files which changed
+        let second_report = Replication::prepare(&storage, 0, output_dir.path())
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(second_report.replicated_files, 0);
+
+        storage.update(&Locator::RoundHeight, Object::RoundHeight(1)).unwrap();
+        let third_report = Replication::prepare(&storage, 0, output_dir.path())
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(third_report.replicated_files, 1);
+        assert_eq!(third_report.total_files, report.total_files);
+    }
+}
@@ -19,13 +19,27 @@ use masp_phase2::{verify_contribution, MPCParameters};
 
 pub(crate) struct Verification;
 
+///
+/// Verifies the response against its challenge, as the coordinator does, without touching the storage.
+/// Used by the external verifiers. On success, returns the hash of the response.
+///
+pub fn verify_response(challenge: &[u8], response: &[u8]) -> Result<GenericArray<u8, U64>, CoordinatorError> {
+    // The verification of the MASP parameters panics on invalid contributions
+    std::panic::catch_unwind(|| Verification::transform_pok_and_correctness(challenge, response))
+        .unwrap_or(Err(CoordinatorError::VerificationFailed))
+}
+
 impl Verification {
     ///
     /// Runs verification for a given environment, storage,
     /// round height, chunk ID, and contribution ID of the
     /// unverified response file.
     ///
+    /// If the response was already checked with [`verify_response`], its hash can be passed
+    /// as `checked_response_hash` to skip the expensive check.
+    ///
     #[inline]
+    #[tracing::instrument(skip(environment, storage, signature, signing_key, checked_response_hash))]
     pub(crate) fn run(
         environment: &Environment,
         storage: &mut Disk,
@@ -35,6 +49,7 @@ impl Verification {
         chunk_id: u64,
         current_contribution_id: u64,
         is_final_contribution: bool,
+        checked_response_hash: Option<GenericArray<u8, U64>>,
     ) -> Result<(), CoordinatorError> {
         info!(
             "Starting verification of round {} chunk {} contribution {}",
@@ -111,6 +126,7 @@ impl Verification {
             next_challenge_locator.clone(),
             round_height,
             current_contribution_id,
+            checked_response_hash,
         ) {
             error!("Verification failed with {}", error);
             return Err(error);
@@ -162,6 +178,7 @@ impl Verification {
         next_challenge_locator: Locator,
         round_height: u64,
         contribution_id: u64,
+        checked_response_hash: Option<GenericArray<u8, U64>>,
     ) -> Result<(), CoordinatorError> {
         // Check that the previous and current locators exist in storage.
         if !storage.exists(&challenge_locator) || !storage.exists(&response_locator) {
@@ -170,19 +187,22 @@ impl Verification {
 
         // Execute ceremony verification on chunk.
         let settings = environment.parameters();
-        let result = match settings.curve() {
-            CurveKind::Bls12_381 => Self::transform_pok_and_correctness(
-                storage.reader(&challenge_locator)?.as_ref(),
-                storage.reader(&response_locator)?.as_ref(),
-            ),
-            CurveKind::Bls12_377 => Self::transform_pok_and_correctness(
-                storage.reader(&challenge_locator)?.as_ref(),
-                storage.reader(&response_locator)?.as_ref(),
-            ),
-            CurveKind::BW6 => Self::transform_pok_and_correctness(
-                storage.reader(&challenge_locator)?.as_ref(),
-                storage.reader(&response_locator)?.as_ref(),
-            ),
+        let result = match checked_response_hash {
+            Some(response_hash) => Ok(response_hash),
+            None => match settings.curve() {
+                CurveKind::Bls12_381 => Self::transform_pok_and_correctness(
+                    storage.reader(&challenge_locator)?.as_ref(),
+                    storage.reader(&response_locator)?.as_ref(),
+                ),
+                CurveKind::Bls12_377 => Self::transform_pok_and_correctness(
+                    storage.reader(&challenge_locator)?.as_ref(),
+                    storage.reader(&response_locator)?.as_ref(),
+                ),
+                CurveKind::BW6 => Self::transform_pok_and_correctness(
+                    storage.reader(&challenge_locator)?.as_ref(),
+                    storage.reader(&response_locator)?.as_ref(),
+                ),
+            },
         };
         let response_hash = match result {
             Ok(response_hash) => response_hash,
@@ -213,7 +233,7 @@ impl Verification {
             if !storage.exists(&next_challenge_locator) {
                 storage.initialize(
                     next_challenge_locator.clone(),
-                    Object::anoma_contribution_file_size(round_height, contribution_id),
+                    Object::anoma_contribution_file_size(environment, round_height, contribution_id),
                 )?;
             }
 
@@ -477,7 +497,7 @@ mod tests {
 
             if !storage.exists(response_locator) {
                 // let expected_filesize = Object::contribution_file_size(&TEST_ENVIRONMENT_ANOMA, chunk_id, false);
-                let expected_filesize = Object::anoma_contribution_file_size(round_height, 1);
+                let expected_filesize = Object::anoma_contribution_file_size(&TEST_ENVIRONMENT_ANOMA, round_height, 1);
                 storage.initialize(response_locator.clone(), expected_filesize).unwrap();
             }
             if !storage.exists(contribution_file_signature_locator) {
@@ -512,6 +532,7 @@ mod tests {
                 chunk_id,
                 1,
                 is_final,
+                None,
             )
             .unwrap();
 
@@ -0,0 +1,144 @@
+//! Compression of the challenges and contributions transferred directly between the contributors and the
+//! coordinator. The parameters files are padded with trailing zeros to their full size, so they compress well.
+
+use crate::CoordinatorError;
+
+pub use phase2_client_sdk::ContentEncoding;
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+use tempfile::NamedTempFile;
+
+/// Level of the zstd compression, the default of the zstd cli.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Returns the path of a copy of the file at `source` compressed with `encoding`, stored in `directory` under `name`
+/// followed by the extension of the encoding. The copy is created on the first call and recreated whenever the source
+/// file is more recent.
+pub(crate) fn compressed_copy(
+    source: &Path,
+    directory: &Path,
+    name: &str,
+    encoding: ContentEncoding,
+) -> Result<PathBuf, CoordinatorError> {
+    let destination = directory.join(format!("{}.{}", name, encoding.as_str()));
+    let directory = destination.parent().unwrap_or(directory);
+    fs::create_dir_all(directory)?;
+
+    let is_fresh = match (fs::metadata(source)?.modified(), fs::metadata(&destination)) {
+        (Ok(source_time), Ok(metadata)) => metadata.modified().map_or(false, |time| time >= source_time),
+        _ => false,
+    };
+    if is_fresh {
+        return Ok(destination);
+    }
+
+    // Compress to a temporary file renamed at the end, so that concurrent downloads never read a partial copy
+    let compressed = NamedTempFile::new_in(directory)?;
+    let mut reader = BufReader::new(File::open(source)?);
+    compress(&mut reader, BufWriter::new(compressed.reopen()?), encoding)?;
+
+    compressed.persist(&destination).map_err(|e| e.error)?;
+
+    Ok(destination)
+}
+
+/// Compresses with `encoding` everything read from `reader` into `writer`.
+pub(crate) fn compress<R: Read + ?Sized, W: Write>(
+    reader: &mut R,
+    writer: W,
+    encoding: ContentEncoding,
+) -> Result<(), CoordinatorError> {
+    match encoding {
+        ContentEncoding::Zstd => {
+            let mut encoder = zstd::Encoder::new(writer, ZSTD_LEVEL)?;
+            io::copy(reader, &mut encoder)?;
+            encoder.finish()?.flush()?;
+        }
+        ContentEncoding::Gzip => {
+            let mut encoder = GzEncoder::new(writer, Compression::default());
+            io::copy(reader, &mut encoder)?;
+            encoder.finish()?.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns a reader compressing with `encoding` everything read from `reader`, such as a file decrypted as it's read.
+pub(crate) fn encoder(
+    reader: Box<dyn Read + Send>,
+    encoding: ContentEncoding,
+) -> Result<Box<dyn Read + Send>, CoordinatorError> {
+    match encoding {
+        ContentEncoding::Zstd => Ok(Box::new(zstd::stream::read::Encoder::new(reader, ZSTD_LEVEL)?)),
+        ContentEncoding::Gzip => Ok(Box::new(flate2::read::GzEncoder::new(reader, Compression::default()))),
+    }
+}
+
+/// Decompresses the file at `source`, encoded with `encoding`, into a temporary file. Fails with
+/// [`CoordinatorError::ContributionFileSizeMismatch`] if it decompresses to more than `limit` bytes.
+pub(crate) fn decompress_file(
+    source: &Path,
+    encoding: ContentEncoding,
+    limit: u64,
+) -> Result<NamedTempFile, CoordinatorError> {
+    let reader = BufReader::new(File::open(source)?);
+    let decompressed = NamedTempFile::new()?;
+    let mut writer = BufWriter::new(decompressed.reopen()?);
+
+    // Read one byte past the limit to detect a file decompressing to a bigger size
+    let written = match encoding {
+        ContentEncoding::Zstd => io::copy(&mut zstd::Decoder::new(reader)?.take(limit + 1), &mut writer)?,
+        ContentEncoding::Gzip => io::copy(&mut GzDecoder::new(reader).take(limit + 1), &mut writer)?,
+    };
+    writer.flush()?;
+
+    if written > limit {
+        return Err(CoordinatorError::ContributionFileSizeMismatch);
+    }
+
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let copies = tempfile::tempdir().unwrap();
+        let source = dir.path().join("contribution_0.verified");
+        let name = "chunk_0/contribution_0.verified";
+        let mut content = b"challenge".to_vec();
+        content.resize(1 << 16, 0);
+        fs::write(&source, &content).unwrap();
+
+        for encoding in [ContentEncoding::Zstd, ContentEncoding::Gzip].iter().copied() {
+            let compressed = compressed_copy(&source, copies.path(), name, encoding).unwrap();
+            assert!(compressed.starts_with(copies.path().join("chunk_0")));
+            assert_eq!(
+                compressed_copy(&source, copies.path(), name, encoding).unwrap(),
+                compressed
+            );
+            assert!(fs::metadata(&compressed).unwrap().len() < content.len() as u64);
+
+            let decompressed = decompress_file(&compressed, encoding, content.len() as u64).unwrap();
+            assert_eq!(fs::read(decompressed.path()).unwrap(), content);
+
+            // Decompressing past the limit fails
+            assert!(matches!(
+                decompress_file(&compressed, encoding, content.len() as u64 - 1),
+                Err(CoordinatorError::ContributionFileSizeMismatch)
+            ));
+        }
+
+        // Nothing is written next to the source
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
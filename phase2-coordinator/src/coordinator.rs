@@ -3,32 +3,55 @@
 //! [CoordinatorState] object.
 
 use crate::{
-    authentication::Signature,
-    commands::{Aggregation, Initialization},
+    audit::{self, AuditEvent, AuditExportFormat, AuditRecord},
+    authentication::{
+        verify_hex_signature, Bls, ContributionReceipt, KeyPair, KeyRotation, PriorityToken, SessionToken, Signature,
+        SignatureScheme, SigningBackend,
+    },
+    commands::{
+        Aggregation, Beacon, Export, Initialization, Mirror, MirrorIndex, ParameterExport, PendingReplication,
+        Replication,
+    },
+    compression::ContentEncoding,
     coordinator_state::{
-        CeremonyStorageAction, CoordinatorState, DropParticipant, ParticipantInfo, ResetCurrentRoundStorageAction,
-        RoundMetrics, IP_BAN, TOKEN_BLACKLIST,
+        CeremonyStorageAction, CoordinatorState, DropParticipant, ExternalVerificationStatus, InviteCode,
+        ParticipantInfo, ResetCurrentRoundStorageAction, RoundMetrics, IP_BAN, TOKEN_BLACKLIST,
     },
+    email::EmailNotification,
     environment::{Deployment, Environment},
     objects::{
-        participant::*, task::TaskInitializationError, ContributionFileSignature, ContributionInfo, LockedLocators,
-        Round, Task, TrimmedContributionInfo,
+        participant::*, task::TaskInitializationError, AttestationStatement, Chunk, ContributionFileSignature,
+        ContributionInfo, ContributionRecord, IdentityProof, LockedLocators, NamadaAddressBinding, Round, RoundChunk,
+        RoundInfo, Task, TimestampProof, Transcript, TranscriptChunk, TranscriptContribution, TranscriptRound,
+        TrimmedContributionInfo,
     },
     storage::{
-        ContributionLocator, ContributionSignatureLocator, Disk, Locator, LocatorPath, Object, StorageAction,
-        StorageLocator, StorageObject, UpdateAction,
+        AuditIssue, ContributionLocator, ContributionSignatureLocator, Disk, Locator, LocatorPath, Object,
+        PendingSnapshot, PrunedArchive, RoundCompression, ServedFile, SnapshotManifest, StateTransition, StorageAction,
+        StorageAudit, StorageLocator, StorageObject, StoredFile, UpdateAction,
     },
 };
+use blake2::{Blake2b512, Digest};
+use phase2_client_sdk::{
+    challenge_message, coordinator_identity_message, login_message, verification_result_message, CeremonyEvent,
+    CeremonyParameters, ContributionRejection, ContributorsPage, CoordinatorIdentity, PublicContributor,
+    RejectionReason, VerificationResult, VerificationTask,
+};
+use rand::RngCore;
+use rocket::tokio::sync::broadcast;
 use setup_utils::calculate_hash;
 
 use std::{
-    collections::HashSet,
-    fmt,
+    collections::{BTreeSet, HashSet},
+    fmt, fs,
+    io::{self, Read, Seek, Write},
     net::IpAddr,
+    path::{Path, PathBuf},
     sync::{Arc, RwLock},
 };
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
 use tracing::*;
+use zip::{result::ZipError, write::FileOptions, CompressionMethod, ZipWriter};
 
 #[cfg(any(test, feature = "operator"))]
 use std::collections::HashMap;
@@ -36,7 +59,9 @@ use std::collections::HashMap;
 #[derive(Debug)]
 pub enum CoordinatorError {
     AggregateContributionFileSizeMismatch,
+    BeaconAlreadyApplied,
     CeremonyIsOver,
+    ChallengeHashMismatch,
     ChallengeHashSizeInvalid,
     ChunkAlreadyComplete,
     ChunkAlreadyVerified,
@@ -45,6 +70,7 @@ pub enum CoordinatorError {
     ChunkIdMismatch,
     ChunkIdMissing,
     ChunkLockAlreadyAcquired,
+    ChunkLockExpired,
     ChunkLockLimitReached,
     ChunkMissing,
     ChunkMissingVerification,
@@ -73,11 +99,13 @@ pub enum CoordinatorError {
     ContributionShouldNotExist,
     ContributionSignatureFileSizeMismatch,
     ContributionSignatureSizeMismatch,
+    ContributionUploadIncomplete,
     ContributionsComplete,
     ContributorAlreadyContributed,
     ContributorSignatureInvalid,
     ContributorsMissing,
     CoordinatorContributorMissing,
+    CoordinatorDraining,
     CoordinatorStateNotInitialized,
     CurrentRoundAggregating,
     CurrentRoundAggregated,
@@ -91,6 +119,10 @@ pub enum CoordinatorError {
     InitializationFailed,
     InitializationTranscriptsDiffer,
     Integer(std::num::ParseIntError),
+    InviteCodeAlreadyRedeemed,
+    InviteCodeExpirationInvalid,
+    InviteCodeExpired,
+    InviteCodeInvalid,
     IOError(std::io::Error),
     Hex(hex::FromHexError),
     JsonError(serde_json::Error),
@@ -103,6 +135,9 @@ pub enum CoordinatorError {
     LocatorFileNotOpen,
     LocatorFileShouldBeOpen,
     LocatorSerializationFailed,
+    LoginDisabled,
+    LoginNonceInvalid,
+    LoginSignatureInvalid,
     NextChallengeHashAlreadyExists,
     NextChallengeHashSizeInvalid,
     NextChallengeHashMissing,
@@ -110,6 +145,7 @@ pub enum CoordinatorError {
     NextRoundShouldBeEmpty,
     NumberOfChunksInvalid,
     NumberOfContributionsDiffer,
+    ParameterExportFailed(String),
     ParticipantAlreadyAdded,
     ParticipantAlreadyAddedChunk,
     ParticipantAlreadyBanned,
@@ -130,6 +166,7 @@ pub enum CoordinatorError {
     ParticipantHasNotStarted,
     ParticipantHasNoRemainingTasks,
     ParticipantHasRemainingTasks,
+    ParticipantInCooldown,
     ParticipantInCurrentRoundCannotJoinQueue,
     ParticipantIpAlreadyAdded,
     ParticipantLockedChunkWithManyContributions,
@@ -146,13 +183,19 @@ pub enum CoordinatorError {
     ParticipantStillHasLocks,
     ParticipantStillHasTaskAsAssigned,
     ParticipantStillHasTaskAsPending,
+    ParticipantSuspended,
     ParticipantUnauthorized,
     ParticipantUnauthorizedForChunkId { chunk_id: u64 },
     ParticipantWasDropped,
     PendingTasksMustContainResponseTask { response_task: Task },
     Phase2Setup(setup_utils::Error),
+    PriorityTokenAlreadyRedeemed,
+    PriorityTokenInactive,
+    PriorityTokenInvalid,
+    PtauImportFailed(String),
     QueueIsEmpty,
     QueueWaitTimeIncomplete,
+    RequestReplayed,
     ResponseHashSizeInvalid,
     RoundAggregationFailed,
     RoundAlreadyInitialized,
@@ -182,7 +225,13 @@ pub enum CoordinatorError {
     RoundVerifiersMissing,
     RoundVerifiersNotUnique,
     SignatureSchemeIsInsecure,
+    SnapshotInvalid,
+    StateDatabaseUnsupported,
+    StorageContentCorrupted,
     StorageCopyFailed,
+    StorageEncryptionKeyInvalid,
+    StorageEncryptionKeyMissing,
+    StorageFileUnencrypted,
     StorageFailed,
     StorageInitializationFailed,
     StorageLocatorAlreadyExists,
@@ -193,6 +242,7 @@ pub enum CoordinatorError {
     StorageLockFailed,
     StorageReaderFailed,
     StorageSizeLookupFailed,
+    StorageSpaceLow,
     StorageUpdateFailed,
     TaskInitializationFailed(TaskInitializationError),
     PreviousContributionMissing { current_task: Task },
@@ -206,6 +256,31 @@ pub enum CoordinatorError {
     VerifiersMissing,
 }
 
+impl CoordinatorError {
+    /// Returns `true` if the error comes from the storage of the coordinator itself, rather than from the
+    /// files it was handling, for the operation to be retried instead of blamed on a contributor.
+    pub fn is_storage_failure(&self) -> bool {
+        match self {
+            CoordinatorError::IOError(error) => {
+                !matches!(error.kind(), io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof)
+            }
+            CoordinatorError::StorageCopyFailed
+            | CoordinatorError::StorageFailed
+            | CoordinatorError::StorageInitializationFailed
+            | CoordinatorError::StorageLockFailed
+            | CoordinatorError::StorageSizeLookupFailed
+            | CoordinatorError::StorageSpaceLow
+            | CoordinatorError::StorageUpdateFailed => true,
+            _ => false,
+        }
+    }
+}
+
+/// A [CoordinatorError::is_storage_failure] error, kept recognizable once converted to an [anyhow::Error].
+#[derive(Debug, thiserror::Error)]
+#[error("storage failure: {0}")]
+pub struct StorageFailure(pub String);
+
 impl From<TaskInitializationError> for CoordinatorError {
     fn from(error: TaskInitializationError) -> Self {
         Self::TaskInitializationFailed(error)
@@ -266,6 +341,7 @@ impl From<CoordinatorError> for anyhow::Error {
         error!("{}", error);
         match error {
             CoordinatorError::Error(anyhow_error) => anyhow_error,
+            _ if error.is_storage_failure() => Self::new(StorageFailure(error.to_string())),
             _ => Self::msg(error.to_string()),
         }
     }
@@ -327,6 +403,216 @@ impl TimeSource for MockTimeSource {
     }
 }
 
+/// The repairs made by [Coordinator::recover] to the round interrupted by a restart.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Recovery {
+    /// Participants dropped for having been unreachable during the downtime
+    pub dropped: Vec<Participant>,
+    /// Chunks whose lock was acquired in the round but never recorded in the state
+    pub released_locks: Vec<u64>,
+    /// Responses left over by uploads never accepted as contributions
+    pub removed_responses: Vec<ContributionLocator>,
+    /// Accepted contributions whose verification was never scheduled
+    pub requeued_verifications: Vec<Task>,
+}
+
+impl Recovery {
+    /// Returns `true` if the round was left in a consistent state.
+    pub fn is_empty(&self) -> bool {
+        self.dropped.is_empty()
+            && self.released_locks.is_empty()
+            && self.removed_responses.is_empty()
+            && self.requeued_verifications.is_empty()
+    }
+}
+
+/// The work on the files of the completed rounds left by a round advance, see [Coordinator::take_maintenance], to be
+/// run once the coordinator is released.
+pub struct StorageMaintenance {
+    compression: RoundCompression,
+    archive: Option<PrunedArchive>,
+}
+
+impl StorageMaintenance {
+    /// Compresses the files of the completed round and archives the pruned ones, logging the errors, the files being
+    /// left as they are on failure.
+    pub fn run(self) {
+        if let Err(error) = self.compression.run() {
+            warn!("Could not compress the files of the completed round: {}", error);
+        }
+        if let Some(Err(error)) = self.archive.map(PrunedArchive::run) {
+            warn!("Could not archive the pruned files: {}", error);
+        }
+    }
+}
+
+/// A file of a contribution collected by [Coordinator::audit_storage], with its path recorded in the round.
+struct AuditedFile {
+    path: Option<LocatorPath>,
+    /// The file, if it's in storage
+    file: Option<(StoredFile, Locator)>,
+}
+
+/// The content of a signature file collected by [Coordinator::audit_storage].
+enum SignatureContent {
+    Missing,
+    Malformed,
+    Parsed(ContributionFileSignature),
+}
+
+/// A signature of a contribution collected by [Coordinator::audit_storage], with its path and its signer recorded in
+/// the round.
+struct AuditedSignature {
+    signer: Option<Participant>,
+    path: Option<LocatorPath>,
+    content: SignatureContent,
+}
+
+/// A contribution of a completed round collected by [Coordinator::audit_storage].
+struct AuditedContribution {
+    round_height: u64,
+    task: Task,
+    challenge: AuditedFile,
+    response: AuditedFile,
+    contributor: AuditedSignature,
+    /// The next challenge and the signature of the verifier, once verified
+    verification: Option<(AuditedFile, AuditedSignature)>,
+}
+
+/// The artifacts of the completed rounds collected by [Coordinator::audit_storage], to be checked once the coordinator
+/// is released.
+pub struct PendingAudit {
+    rounds: Vec<u64>,
+    contributions: Vec<AuditedContribution>,
+    scheme: SignatureScheme,
+    signature: Arc<dyn Signature>,
+}
+
+impl PendingAudit {
+    /// Recomputes the hash of each challenge and response file, and verifies the signatures of the contributor and of
+    /// the verifier of each contribution against them, with the public keys recorded in the round. Returns the
+    /// discrepancies found, if any.
+    pub fn run(self) -> Result<StorageAudit, CoordinatorError> {
+        let mut audit = StorageAudit {
+            rounds: self.rounds.clone(),
+            ..Default::default()
+        };
+        let mut hashes = HashMap::new();
+
+        for contribution in &self.contributions {
+            let (round_height, task) = (contribution.round_height, &contribution.task);
+            let challenge_hash = Self::hash_file(&mut audit, &mut hashes, round_height, task, &contribution.challenge)?;
+            let response_hash = Self::hash_file(&mut audit, &mut hashes, round_height, task, &contribution.response)?;
+            let mut files = vec![
+                (contribution.challenge.path.as_ref(), challenge_hash),
+                (contribution.response.path.as_ref(), response_hash),
+            ];
+            self.check_signature(&mut audit, round_height, task, &contribution.contributor, &files)?;
+
+            if let Some((next_challenge, verifier)) = &contribution.verification {
+                let next_challenge_hash = Self::hash_file(&mut audit, &mut hashes, round_height, task, next_challenge)?;
+                files.push((next_challenge.path.as_ref(), next_challenge_hash));
+                self.check_signature(&mut audit, round_height, task, verifier, &files)?;
+            }
+        }
+
+        match audit.is_consistent() {
+            true => info!("Audited the storage of rounds {:?}", audit.rounds),
+            false => error!("Found discrepancies in the storage: {:?}", audit.discrepancies),
+        }
+        Ok(audit)
+    }
+
+    /// Returns the hash of the file, or `None` if it's missing from storage.
+    fn hash_file(
+        audit: &mut StorageAudit,
+        hashes: &mut HashMap<Locator, Vec<u8>>,
+        round_height: u64,
+        task: &Task,
+        file: &AuditedFile,
+    ) -> Result<Option<Vec<u8>>, CoordinatorError> {
+        let (stored, locator) = match &file.file {
+            Some(file) => file,
+            None => {
+                audit.report(round_height, task, file.path.as_ref(), AuditIssue::Missing);
+                return Ok(None);
+            }
+        };
+
+        // The verified response of a contribution is the challenge of the next one
+        if let Some(hash) = hashes.get(locator) {
+            return Ok(Some(hash.clone()));
+        }
+        // The file is hashed as stored
+        let mut hasher = Blake2b512::new();
+        io::copy(&mut stored.open()?, &mut hasher)?;
+        let hash = hasher.finalize().to_vec();
+        audit.files_checked += 1;
+        hashes.insert(locator.clone(), hash.clone());
+
+        Ok(Some(hash))
+    }
+
+    /// Checks the signature, then the hashes it signs against the ones of the given files: the challenge, the
+    /// response and, for a verifier, the next challenge.
+    fn check_signature(
+        &self,
+        audit: &mut StorageAudit,
+        round_height: u64,
+        task: &Task,
+        signature: &AuditedSignature,
+        files: &[(Option<&LocatorPath>, Option<Vec<u8>>)],
+    ) -> Result<(), CoordinatorError> {
+        let location = signature.path.as_ref();
+        let content = match &signature.content {
+            SignatureContent::Parsed(content) => content,
+            SignatureContent::Malformed => {
+                audit.report(round_height, task, location, AuditIssue::SignatureMalformed);
+                return Ok(());
+            }
+            SignatureContent::Missing => {
+                audit.report(round_height, task, location, AuditIssue::Missing);
+                return Ok(());
+            }
+        };
+
+        // The verifiers panic on malformed keys and signatures
+        let message = content.get_state().signature_message()?;
+        let verified = signature.signer.as_ref().map_or(false, |signer| {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                self.scheme.verify_with(
+                    self.signature.as_ref(),
+                    &signer.address(),
+                    &message,
+                    content.get_signature(),
+                )
+            }))
+            .unwrap_or(false)
+        });
+        if !verified {
+            audit.report(round_height, task, location, AuditIssue::SignatureInvalid);
+        }
+
+        let signed_hashes = [
+            Some(content.get_challenge_hash()),
+            Some(content.get_response_hash()),
+            content.get_next_challenge_hash().as_deref(),
+        ];
+        for ((path, hash), signed_hash) in files.iter().zip(signed_hashes.iter()) {
+            if let (Some(hash), Some(signed_hash)) = (hash, signed_hash) {
+                if hex::encode(hash) != *signed_hash {
+                    audit.report(round_height, task, *path, AuditIssue::HashMismatch);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Number of events buffered for the subscribers of the [Coordinator] before the slowest ones skip events.
+const EVENTS_CAPACITY: usize = 256;
+
 /// A core structure for operating the Phase 1 ceremony. This struct
 /// is designed to be [Send] + [Sync]. The state of the ceremony is
 /// stored in a [CoordinatorState] object.
@@ -343,6 +629,16 @@ pub struct Coordinator {
     time: Arc<dyn TimeSource>,
     /// Callback to call after aggregation is done
     aggregation_callback: Arc<dyn Fn(Vec<Participant>) -> () + Send + Sync>,
+    /// Sender of the events of the ceremony to the subscribers
+    events: broadcast::Sender<CeremonyEvent>,
+    /// Whether the coordinator is draining before a shutdown, refusing the new contributors
+    draining: bool,
+    /// Whether the random beacon has been applied, closing the ceremony
+    finalized: bool,
+    /// The work on the storage left by the round advances, see [Self::take_maintenance]
+    maintenance: Vec<StorageMaintenance>,
+    /// The identity of the coordinator, signed when first requested and again after a key rotation
+    identity: Option<CoordinatorIdentity>,
 }
 
 impl Coordinator {
@@ -367,20 +663,39 @@ impl Coordinator {
     ) -> Result<Self, CoordinatorError> {
         // Load an instance of storage.
         let storage = environment.storage()?;
+        Self::new_with_storage(environment, signature, time, storage)
+    }
+
+    /// Constructor running the coordinator on the given storage, such as a [Disk] keeping its state in a
+    /// [MemoryStorage](crate::storage::MemoryStorage).
+    pub fn new_with_storage(
+        environment: Environment,
+        signature: Arc<dyn Signature>,
+        time: Arc<dyn TimeSource>,
+        storage: Disk,
+    ) -> Result<Self, CoordinatorError> {
         // Load an instance of coordinator self.
         let state = match storage.get(&Locator::CoordinatorState)? {
             Object::CoordinatorState(state) => state,
             _ => return Err(CoordinatorError::StorageFailed),
         };
 
-        Ok(Self {
+        let mut coordinator = Self {
             environment: environment.clone(),
             signature,
             storage,
             state,
             time,
             aggregation_callback: Arc::new(|_| ()),
-        })
+            events: broadcast::channel(EVENTS_CAPACITY).0,
+            draining: false,
+            finalized: false,
+            maintenance: vec![],
+            identity: None,
+        };
+        coordinator.finalized = Beacon::load(&coordinator.beacon_directory())?.is_some();
+
+        Ok(coordinator)
     }
 
     ///
@@ -391,6 +706,85 @@ impl Coordinator {
     pub fn set_aggregation_callback(&mut self, callback: Arc<dyn Fn(Vec<Participant>) -> () + Send + Sync>) {
         self.aggregation_callback = callback;
     }
+
+    ///
+    /// Subscribes to the events of the ceremony emitted from now on.
+    ///
+    pub fn subscribe(&self) -> broadcast::Receiver<CeremonyEvent> {
+        self.events.subscribe()
+    }
+
+    /// Publishes the event to the current subscribers, if any.
+    fn emit(&self, event: CeremonyEvent) {
+        debug!("Emitting event {:?}", event);
+        let _ = self.events.send(event);
+    }
+
+    /// Publishes the drop of the participant, if a contributor.
+    fn emit_drop(&self, drop: &DropParticipant) {
+        if drop.participant().is_contributor() {
+            self.emit(CeremonyEvent::ContributorDropped {
+                public_key: drop.participant().address(),
+            });
+        }
+    }
+
+    /// Appends the event to the audit log, signed with the key of the coordinator.
+    fn audit(&mut self, event: AuditEvent) -> Result<(), CoordinatorError> {
+        let public_key = self
+            .environment
+            .coordinator_verifiers()
+            .first()
+            .ok_or(CoordinatorError::VerifierMissing)?
+            .address();
+        let timestamp = self.time.now_utc().unix_timestamp();
+        self.storage.log_audit_event(
+            self.signature.as_ref(),
+            &self.environment.default_verifier_signing_key(),
+            &public_key,
+            timestamp,
+            event,
+        )?;
+
+        Ok(())
+    }
+
+    ///
+    /// Records in the audit log the given action of an operator through the admin endpoints.
+    ///
+    pub fn record_admin_action(&mut self, operator: &str, action: String) -> Result<(), CoordinatorError> {
+        self.audit(AuditEvent::AdminAction {
+            operator: operator.to_string(),
+            action,
+        })
+    }
+
+    ///
+    /// Returns the records of the audit log, the oldest first.
+    ///
+    pub fn audit_records(&self) -> Result<Vec<AuditRecord>, CoordinatorError> {
+        self.storage.audit_records()
+    }
+
+    ///
+    /// Exports in the given format the records of the audit log logged from `from` on and
+    /// before `to`, unix timestamps in seconds, if any.
+    ///
+    pub fn export_audit_log(
+        &self,
+        format: AuditExportFormat,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Vec<u8>, CoordinatorError> {
+        let records: Vec<AuditRecord> = self
+            .audit_records()?
+            .into_iter()
+            .filter(|record| from.map_or(true, |from| record.timestamp >= from))
+            .filter(|record| to.map_or(true, |to| record.timestamp < to))
+            .collect();
+
+        audit::export(&records, format)
+    }
 }
 
 impl Coordinator {
@@ -418,6 +812,9 @@ impl Coordinator {
                 // Initialize the coordinator state to round 0.
                 self.state.initialize(round_height);
                 self.save_state()?;
+            } else {
+                // Repair the round left in flight by the previous run.
+                self.recover()?;
             }
         }
 
@@ -432,6 +829,144 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Returns `true` if the ceremony has been initialized, its round 0 being stored.
+    ///
+    pub fn is_initialized(&self) -> bool {
+        Self::load_current_round_height(&self.storage).is_ok()
+    }
+
+    ///
+    /// Restores the current round to a consistent state after a restart. The participants unreachable for longer
+    /// than the timeouts are dropped as in [Self::update], requeued or not according to the environment, the locks
+    /// and responses left over by the interrupted operations are removed, and the contributions accepted but not
+    /// yet verified are scheduled for verification again.
+    ///
+    pub fn recover(&mut self) -> Result<Recovery, CoordinatorError> {
+        let mut recovery = Recovery::default();
+        if Self::load_current_round_height(&self.storage).is_err() {
+            return Ok(recovery);
+        }
+
+        for drop in self.state.update_dropped_participants(self.time.as_ref())? {
+            self.drop_participant_from_storage(&drop)?;
+            self.emit_drop(&drop);
+            recovery.dropped.push(drop.participant().clone());
+        }
+        self.save_state()?;
+
+        let mut round = Self::load_current_round(&self.storage)?;
+        let round_height = round.round_height();
+
+        // Locks acquired in the round by a lock request whose state update was lost
+        let orphan_locks: Vec<(u64, Participant)> = round
+            .chunks()
+            .iter()
+            .filter_map(|chunk| {
+                let holder = chunk.lock_holder().as_ref()?;
+                let recorded = self
+                    .state
+                    .current_participant_info(holder)
+                    .map_or(false, |info| info.locked_chunks().contains_key(&chunk.chunk_id()));
+                (!recorded).then(|| (chunk.chunk_id(), holder.clone()))
+            })
+            .collect();
+        for (chunk_id, holder) in orphan_locks {
+            warn!("Releasing the lock of {} on chunk {}", holder, chunk_id);
+            if round.is_contributor(&holder) {
+                round.remove_locks_unsafe(&mut self.storage, &holder, &[chunk_id])?;
+            } else {
+                round.chunk_mut(chunk_id)?.set_lock_holder_unsafe(None);
+            }
+            recovery.released_locks.push(chunk_id);
+        }
+
+        // Responses uploaded to chunks nobody holds anymore
+        let expected_contributions = round.expected_number_of_contributions();
+        for chunk in round.chunks().iter().filter(|chunk| chunk.is_unlocked()) {
+            let contribution_id = match chunk.next_contribution_id(expected_contributions) {
+                Ok(contribution_id) => contribution_id,
+                Err(_) => continue,
+            };
+            let response = ContributionLocator::new(round_height, chunk.chunk_id(), contribution_id, false);
+            let signature = Locator::ContributionFileSignature(ContributionSignatureLocator::new(
+                round_height,
+                chunk.chunk_id(),
+                contribution_id,
+                false,
+            ));
+            if self.storage.exists(&Locator::ContributionFile(response)) {
+                warn!("Removing the leftover response {:?}", response);
+                self.storage.remove(&Locator::ContributionFile(response))?;
+                if self.storage.exists(&signature) {
+                    self.storage.remove(&signature)?;
+                }
+                recovery.removed_responses.push(response);
+            }
+        }
+
+        // Contributions accepted by the round whose verification was lost with the state
+        for chunk in round.chunks() {
+            let contribution_id = chunk.current_contribution_id();
+            let contribution = chunk.current_contribution()?;
+            let task = Task::new(chunk.chunk_id(), contribution_id);
+            if contribution_id == 0
+                || contribution.is_verified()
+                || self.state.get_pending_verifications().contains_key(&task)
+            {
+                continue;
+            }
+
+            warn!("Scheduling the verification of {}", task);
+            match contribution.get_contributor() {
+                Some(contributor)
+                    if self.state.current_participant_info(contributor).map_or(false, |info| {
+                        info.locked_chunks().contains_key(&task.chunk_id()) && info.pending_tasks().contains(&task)
+                    }) =>
+                {
+                    self.state.completed_task(contributor, &task, self.time.as_ref())?
+                }
+                _ => self.state.add_pending_verification(&task)?,
+            }
+            recovery.requeued_verifications.push(task);
+        }
+
+        self.storage
+            .update(&Locator::RoundState { round_height }, Object::RoundState(round))?;
+        self.save_state()?;
+
+        if recovery.is_empty() {
+            info!("Round {} is consistent", round_height);
+        } else {
+            info!("Recovered round {}: {:?}", round_height, recovery);
+        }
+
+        Ok(recovery)
+    }
+
+    ///
+    /// Collects a snapshot of the coordinator state and the round files, to be written once the
+    /// coordinator is released, see [Disk::snapshot].
+    ///
+    pub fn snapshot(&self) -> Result<PendingSnapshot, CoordinatorError> {
+        self.storage.snapshot(self.time.now_utc())
+    }
+
+    ///
+    /// Replaces the ceremony with a snapshot written by [Self::snapshot], possibly on another machine, then
+    /// recovers the round during which it was taken.
+    ///
+    pub fn restore<R: Read>(&mut self, reader: R) -> Result<SnapshotManifest, CoordinatorError> {
+        let manifest = self.storage.restore(reader)?;
+        self.state = match self.storage.get(&Locator::CoordinatorState)? {
+            Object::CoordinatorState(state) => state,
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
+        self.recover()?;
+
+        Ok(manifest)
+    }
+
     /// Save the current state of the coordinator to storage.
     pub fn save_state(&mut self) -> Result<(), CoordinatorError> {
         self.state.save(&mut self.storage)
@@ -441,7 +976,14 @@ impl Coordinator {
     /// Runs a set of operations to update the coordinator state to reflect
     /// newly finished, dropped, or banned participants.
     ///
+    #[tracing::instrument(skip(self))]
     pub fn update(&mut self) -> Result<(), CoordinatorError> {
+        // Once the beacon is applied, shut the coordinator down
+        if self.finalized {
+            info!("The random beacon has been applied, the ceremony is over");
+            return Err(CoordinatorError::CeremonyIsOver);
+        }
+
         // Process ceremony updates for the current round and queue.
         let (is_current_round_finished, is_current_round_aggregated) = {
             // Acquire the state write lock.
@@ -459,12 +1001,20 @@ impl Coordinator {
             for drop in self.state.update_dropped_participants(self.time.as_ref())? {
                 // Update the round to reflect the coordinator state changes.
                 self.drop_participant_from_storage(&drop)?;
+                self.emit_drop(&drop);
             }
             self.save_state()?;
 
-            self.state.update_dropped_queued_participants(self.time.as_ref())?;
+            for drop in self.state.update_dropped_queued_participants(self.time.as_ref())? {
+                self.emit_drop(&drop);
+            }
             self.save_state()?;
 
+            // The uploads of the dropped participants can't be completed anymore
+            if let Err(error) = self.remove_stale_upload_sessions() {
+                warn!("Could not remove the stale upload sessions: {}", error);
+            }
+
             // Ban any participants who meet the coordinator criteria.
             self.state.update_banned_participants()?;
             self.save_state()?;
@@ -544,6 +1094,47 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Starts draining the coordinator before a shutdown: contributors can no longer join
+    /// the queue nor lock a chunk, while the contribution in flight and its verification
+    /// complete. The draining ends with the process, it isn't saved in the state.
+    ///
+    pub fn start_draining(&mut self) {
+        if !self.draining {
+            warn!("Coordinator is draining, new contributors are refused until the shutdown");
+            self.draining = true;
+        }
+    }
+
+    ///
+    /// Returns `true` once the random beacon has been applied: the ceremony is over and
+    /// no contribution is accepted anymore.
+    ///
+    pub fn is_finalized(&self) -> bool {
+        self.finalized
+    }
+
+    ///
+    /// Returns `true` if the coordinator is draining before a shutdown.
+    ///
+    pub fn is_draining(&self) -> bool {
+        self.draining
+    }
+
+    ///
+    /// Returns `true` once the coordinator is draining and no contribution is in flight:
+    /// no chunk is locked and no contribution is waiting for its verification.
+    ///
+    pub fn is_drained(&self) -> bool {
+        let chunk_locked = self
+            .state
+            .current_contributors()
+            .iter()
+            .any(|(_, info)| !info.locked_chunks().is_empty());
+
+        self.draining && !chunk_locked && self.state.get_pending_verifications().is_empty()
+    }
+
     ///
     /// Initializes a listener to handle the shutdown signal.
     ///
@@ -564,1192 +1155,3025 @@ impl Coordinator {
     }
 
     ///
-    /// Updates the set of tokens for the ceremony
-    ///
-    pub fn update_tokens(&mut self, tokens: Vec<HashSet<String>>) {
-        self.state.update_tokens(tokens)
-    }
-
+    /// Exports the latest verified parameters of the ceremony to the given directory,
+    /// in the formats expected by `masp_proofs`.
     ///
-    /// Returns `true` if the given participant is a contributor in the queue.
+    /// Once the random beacon has been applied, the final parameters produced by the beacon are exported instead.
     ///
-    #[inline]
-    pub fn is_queue_contributor(&self, participant: &Participant) -> bool {
-        self.state.is_queue_contributor(&participant)
+    pub fn export_parameters(&self, output_dir: &Path) -> Result<ParameterExport, CoordinatorError> {
+        if let Some((beacon, response_path)) = Beacon::load(&self.beacon_directory())? {
+            return Export::run_from_contribution(&fs::read(response_path)?, beacon.round_height, output_dir);
+        }
+
+        Export::run(&self.storage, self.latest_verified_round_height()?, output_dir)
     }
 
     ///
-    /// Returns the total number of contributors currently in the queue.
+    /// Returns the height of the round whose challenge holds the latest verified parameters.
     ///
-    #[inline]
-    pub fn number_of_queue_contributors(&self) -> usize {
-        self.state.number_of_queue_contributors()
+    fn latest_verified_round_height(&self) -> Result<u64, CoordinatorError> {
+        let current_round_height = self.current_round_height()?;
+
+        // The verified contribution of the current round, if any, is stored as the challenge of the next round
+        let next_challenge_locator =
+            Locator::ContributionFile(ContributionLocator::new(current_round_height + 1, 0, 0, true));
+        match self.storage.exists(&next_challenge_locator) {
+            true => Ok(current_round_height + 1),
+            false => Ok(current_round_height),
+        }
     }
 
     ///
-    /// Returns a list of the contributors currently in the queue.
+    /// Returns the directory holding the output of the random beacon.
     ///
-    #[inline]
-    pub fn queue_contributors(&self) -> Vec<(Participant, (u8, Option<u64>, OffsetDateTime, OffsetDateTime))> {
-        self.state.queue_contributors()
+    fn beacon_directory(&self) -> PathBuf {
+        Path::new(self.environment.local_base_directory()).join("beacon")
     }
 
     ///
-    /// Returns a list of the contributors currently in the round.
+    /// Updates the static mirror bundle of the transcript in the given directory with
+    /// the rounds finalized since the last update.
     ///
-    #[inline]
-    pub fn current_contributors(&self) -> Vec<(Participant, ParticipantInfo)> {
-        self.state.current_contributors()
+    pub fn update_mirror_bundle(&self, output_dir: &Path) -> Result<MirrorIndex, CoordinatorError> {
+        let current_round_height = self.current_round_height()?;
+        let public_key = self
+            .environment
+            .coordinator_verifiers()
+            .first()
+            .ok_or(CoordinatorError::VerifierMissing)?
+            .address();
+
+        // Include the verified contribution of the current round, if any
+        Mirror::run(
+            &self.storage,
+            self.signature.clone(),
+            &self.environment.default_verifier_signing_key(),
+            &public_key,
+            current_round_height + 1,
+            output_dir,
+        )
     }
 
     ///
-    /// Returns a list of participants that were dropped from the current round.
+    /// Collects the contributions accepted and the state changed since the last update of the
+    /// replica of the storage in the given directory, to be copied there once the coordinator
+    /// is released.
     ///
-    #[inline]
-    pub fn dropped_participants(&self) -> Vec<ParticipantInfo> {
-        self.state.dropped_participants()
+    pub fn replicate(&self, output_dir: &Path) -> Result<PendingReplication, CoordinatorError> {
+        Replication::prepare(&self.storage, self.current_round_height()?, output_dir)
     }
 
     ///
-    /// Returns the metrics for the current round and current round participants.
+    /// Returns a receipt of the contribution stored at the given locator, signed with the key
+    /// of the default verifier of the coordinator.
     ///
-    #[inline]
-    pub fn current_round_metrics(&self) -> Option<RoundMetrics> {
-        self.state.current_round_metrics()
+    pub fn contribution_receipt(&self, locator: &ContributionLocator) -> Result<ContributionReceipt, CoordinatorError> {
+        let reader = self.storage.reader(&Locator::ContributionFile(locator.clone()))?;
+        let contribution_hash = hex::encode(calculate_hash(reader.as_ref()));
+
+        self.sign_receipt(
+            locator.round_height(),
+            locator.chunk_id(),
+            contribution_hash,
+            self.time.now_utc().unix_timestamp(),
+        )
     }
 
     ///
-    /// Adds the given participant to the queue if they are permitted to participate.
+    /// Returns a receipt of the contribution of the participant stored at the given locator,
+    /// as [`contribution_receipt`](Self::contribution_receipt), and records it to be listed in
+    /// the [`contribution_history`](Self::contribution_history) of the participant.
     ///
-    #[inline]
-    pub fn add_to_queue(
+    pub fn issue_receipt(
         &mut self,
-        participant: Participant,
-        participant_ip: Option<IpAddr>,
-        token: String,
-        reliability_score: u8,
-    ) -> Result<(), CoordinatorError> {
-        // Attempt to add the participant to the next round.
-        self.state.add_to_queue(
-            participant,
-            participant_ip,
-            token,
-            reliability_score,
-            self.time.as_ref(),
-        )?;
+        participant: &Participant,
+        locator: &ContributionLocator,
+    ) -> Result<ContributionReceipt, CoordinatorError> {
+        let receipt = self.contribution_receipt(locator)?;
+        self.state.add_receipt(participant, receipt.clone());
 
         // Save the coordinator state in storage.
         self.save_state()?;
 
-        Ok(())
+        Ok(receipt)
     }
 
-    ///
-    /// Removes the given participant from the queue if they are in the queue.
-    ///
-    #[inline]
-    pub fn remove_from_queue(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
-        // Attempt to remove the participant from the next round.
-        self.state.remove_from_queue(participant)?;
+    /// Signs a receipt of the contribution with the given hash, accepted at the given unix timestamp.
+    fn sign_receipt(
+        &self,
+        round_height: u64,
+        chunk_id: u64,
+        contribution_hash: String,
+        timestamp: i64,
+    ) -> Result<ContributionReceipt, CoordinatorError> {
+        let mut receipt = ContributionReceipt {
+            round_height,
+            chunk_id,
+            contribution_hash,
+            timestamp,
+            coordinator_public_key: self.coordinator_public_key()?,
+            signature: String::new(),
+        };
+        receipt.signature = self
+            .signature
+            .sign(&self.environment.default_verifier_signing_key(), &receipt.message())?;
 
-        // Save the coordinator state in storage.
-        self.save_state()?;
+        Ok(receipt)
+    }
 
-        Ok(())
+    /// Returns the public key of the default verifier of the coordinator, which signs its receipts,
+    /// tokens and identity.
+    fn coordinator_public_key(&self) -> Result<String, CoordinatorError> {
+        Ok(self
+            .environment
+            .coordinator_verifiers()
+            .first()
+            .ok_or(CoordinatorError::VerifierMissing)?
+            .address())
     }
 
     ///
-    /// Drops the given participant from the ceremony.
+    /// Returns the public key of the coordinator and the parameters of the ceremony, signed
+    /// with that key for the clients to check that they are talking to the genuine coordinator.
     ///
-    #[tracing::instrument(
-        skip(self, participant),
-        fields(participant = %participant)
-    )]
-    pub fn drop_participant(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
-        // Drop the participant from the ceremony.
-        let drop = self.state.drop_participant(participant, self.time.as_ref())?;
+    /// Returns `None` until the identity is signed by [Self::signed_identity].
+    ///
+    pub fn identity(&self) -> Option<&CoordinatorIdentity> {
+        self.identity.as_ref()
+    }
 
-        // Update the round to reflect the coordinator state change.
-        self.drop_participant_from_storage(&drop)?;
+    ///
+    /// Returns the identity of the coordinator, signing it on the first call and again after a
+    /// key rotation. The signing backend may block, so this must not be called from the async runtime.
+    ///
+    pub fn signed_identity(&mut self) -> Result<CoordinatorIdentity, CoordinatorError> {
+        if let Some(identity) = &self.identity {
+            return Ok(identity.clone());
+        }
 
-        // Save the coordinator state in storage.
-        self.save_state()?;
+        let identity = Self::sign_identity(&self.environment, self.signature.as_ref(), self.time.as_ref())?;
+        self.identity = Some(identity.clone());
 
-        Ok(())
+        Ok(identity)
     }
 
-    ///
-    /// Bans the given participant from the ceremony.
-    ///
-    #[inline]
-    pub fn ban_participant(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
-        // Ban the participant from the ceremony.
-        let drop = self.state.ban_participant(participant, self.time.as_ref())?;
+    /// Signs the identity of the coordinator with the key of its default verifier.
+    fn sign_identity(
+        environment: &Environment,
+        signature: &dyn Signature,
+        time: &dyn TimeSource,
+    ) -> Result<CoordinatorIdentity, CoordinatorError> {
+        let settings = environment.parameters();
+        let mut identity = CoordinatorIdentity {
+            coordinator_public_key: environment
+                .coordinator_verifiers()
+                .first()
+                .ok_or(CoordinatorError::VerifierMissing)?
+                .address(),
+            parameters: CeremonyParameters {
+                contribution_mode: format!("{:?}", settings.contribution_mode()),
+                proving_system: format!("{:?}", settings.proving_system()),
+                curve: format!("{:?}", settings.curve()),
+                power: settings.power() as u64,
+                batch_size: settings.batch_size() as u64,
+                chunk_size: settings.chunk_size() as u64,
+                number_of_chunks: environment.number_of_chunks(),
+            },
+            signed_challenges: environment.sign_challenges(),
+            timestamp: time.now_utc().unix_timestamp(),
+            signature: String::new(),
+        };
+        identity.signature = signature.sign(
+            &environment.default_verifier_signing_key(),
+            &coordinator_identity_message(&identity),
+        )?;
 
-        // Update the round on disk to reflect the coordinator state change.
-        self.drop_participant_from_storage(&drop)?;
+        Ok(identity)
+    }
 
-        // Save the coordinator state in storage.
-        self.save_state()?;
+    ///
+    /// Signs the challenge of the given round, identified by its hash, if the coordinator is
+    /// set to sign the challenges. Returns [`None`] otherwise.
+    ///
+    pub fn challenge_signature(
+        &self,
+        round_height: u64,
+        challenge_hash: &str,
+    ) -> Result<Option<String>, CoordinatorError> {
+        if !self.environment.sign_challenges() {
+            return Ok(None);
+        }
 
-        Ok(())
+        Ok(Some(self.signature.sign(
+            &self.environment.default_verifier_signing_key(),
+            &challenge_message(round_height, challenge_hash),
+        )?))
     }
 
     ///
-    /// Unbans the given participant from joining the queue.
+    /// Returns every contribution made with the given public key, ordered by round height,
+    /// chunk id and contribution id, each with the receipt issued when it was accepted.
     ///
-    #[inline]
-    pub fn unban_participant(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
-        // Unban the participant from the ceremony.
-        self.state.unban_participant(participant);
+    pub fn contribution_history(&self, public_key: &str) -> Result<Vec<ContributionRecord>, CoordinatorError> {
+        let receipts = self.state.receipts(&Participant::new_contributor(public_key));
+        if receipts.is_empty() {
+            return Ok(vec![]);
+        }
+        let summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary,
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
+        let to_time = |time: chrono::DateTime<chrono::Utc>| OffsetDateTime::from_unix_timestamp(time.timestamp()).ok();
+
+        let round_heights = receipts
+            .iter()
+            .map(|receipt| receipt.round_height)
+            .collect::<BTreeSet<_>>();
+        let mut history = vec![];
+        for round_height in round_heights {
+            let round = self.get_round(round_height)?;
+            let info = summary
+                .iter()
+                .find(|info| info.public_key() == public_key && info.ceremony_round() == round_height);
+            let started_at = info.and_then(|info| to_time(info.start_contribution()));
+            let completed_at = info.and_then(|info| to_time(info.end_contribution()));
+
+            for chunk in round.chunks() {
+                for contribution in self.transcript_contributions(chunk)? {
+                    if contribution.contributor_public_key != public_key {
+                        continue;
+                    }
+                    let receipt = match receipts.iter().find(|receipt| {
+                        receipt.round_height == round_height
+                            && receipt.chunk_id == chunk.chunk_id()
+                            && receipt.contribution_hash == contribution.response_hash
+                    }) {
+                        Some(receipt) => receipt.clone(),
+                        None => continue,
+                    };
 
-        // Save the coordinator state in storage.
-        self.save_state()?;
+                    history.push(ContributionRecord {
+                        round_height,
+                        chunk_id: chunk.chunk_id(),
+                        contribution_id: contribution.contribution_id,
+                        receipt,
+                        challenge_hash: contribution.challenge_hash,
+                        response_hash: contribution.response_hash,
+                        verified: contribution.verified,
+                        started_at,
+                        completed_at,
+                    });
+                }
+            }
+        }
 
-        Ok(())
+        Ok(history)
     }
 
     ///
-    /// Returns `true` if the manual lock for transitioning to the next round is enabled.
+    /// Exports the public transcript of the ceremony, listing for every round and chunk the
+    /// contributors together with the signed hashes of their challenges and responses.
     ///
-    #[inline]
-    pub fn is_manual_lock_enabled(&self) -> bool {
-        // Fetch the state of the manual lock.
-        self.state.is_manual_lock_enabled()
+    /// Contributions are ordered by round height, chunk id and contribution id, so that the
+    /// transcript is the same for the same state of the storage.
+    ///
+    pub fn export_transcript(&self) -> Result<Transcript, CoordinatorError> {
+        self.export_transcript_cached(&mut vec![])
     }
 
     ///
-    /// Sets the manual lock for transitioning to the next round to `true`.
+    /// Exports the public transcript of the ceremony as [Self::export_transcript], taking the
+    /// completed rounds from `completed_rounds` and appending to it the rounds completed since
+    /// the previous export. Only the current round is read again from the storage.
     ///
-    #[inline]
-    pub fn enable_manual_lock(&mut self) -> Result<(), CoordinatorError> {
-        // Sets the manual lock to `true`.
-        self.state.enable_manual_lock();
+    pub fn export_transcript_cached(
+        &self,
+        completed_rounds: &mut Vec<TranscriptRound>,
+    ) -> Result<Transcript, CoordinatorError> {
+        let current_round_height = self.current_round_height()?;
 
-        // Save the coordinator state in storage.
-        self.save_state()?;
+        // A restored snapshot may be behind the rounds already exported
+        completed_rounds.truncate(current_round_height as usize);
+        while (completed_rounds.len() as u64) < current_round_height {
+            completed_rounds.push(self.transcript_round(completed_rounds.len() as u64)?);
+        }
 
-        Ok(())
+        let mut rounds = completed_rounds.clone();
+        rounds.push(self.transcript_round(current_round_height)?);
+
+        Ok(Transcript {
+            coordinator_public_key: self
+                .environment
+                .coordinator_verifiers()
+                .first()
+                .ok_or(CoordinatorError::VerifierMissing)?
+                .address(),
+            rounds,
+            key_rotations: self.state.key_rotations().to_vec(),
+            beacon: Beacon::load(&self.beacon_directory())?.map(|(beacon, _)| beacon),
+        })
     }
 
-    ///
-    /// Sets the manual lock for transitioning to the next round to `false`.
-    ///
-    #[inline]
-    pub fn disable_manual_lock(&mut self) -> Result<(), CoordinatorError> {
-        // Sets the manual lock to `false`.
-        self.state.disable_manual_lock();
+    /// Returns the contributions of the round at the given height, as listed in the transcript.
+    fn transcript_round(&self, round_height: u64) -> Result<TranscriptRound, CoordinatorError> {
+        let round = self.get_round(round_height)?;
+        let mut chunks = Vec::with_capacity(round.chunks().len());
 
-        // Save the coordinator state in storage.
-        self.save_state()?;
+        for chunk in round.chunks() {
+            chunks.push(TranscriptChunk {
+                chunk_id: chunk.chunk_id(),
+                contributions: self.transcript_contributions(chunk)?,
+            });
+        }
 
-        Ok(())
+        // Replace the BLS signatures of the contributors by their aggregate
+        let mut aggregate_signature = None;
+        if self.environment.signature_scheme() == SignatureScheme::Bls {
+            let signatures: Vec<&mut String> = chunks
+                .iter_mut()
+                .flat_map(|chunk| chunk.contributions.iter_mut())
+                .filter(|c| SignatureScheme::Bls.is_scheme_key(&c.contributor_public_key))
+                .map(|c| &mut c.signature)
+                .collect();
+
+            if !signatures.is_empty() {
+                let aggregate = Bls::aggregate(&signatures.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+                    .map_err(CoordinatorError::Error)?;
+                signatures.into_iter().for_each(String::clear);
+                aggregate_signature = Some(aggregate);
+            }
+        }
+
+        Ok(TranscriptRound {
+            round_height,
+            chunks,
+            aggregate_signature,
+        })
     }
 
     ///
-    /// Returns `true` if the given participant is authorized as a
-    /// contributor and listed in the contributor IDs for this round.
-    ///
-    /// If the participant is not a contributor, or if there are
-    /// no prior rounds, returns `false`.
+    /// Returns the detail of the round at the given height: its participants, the state of its
+    /// chunks and their contributions with the signed hashes of their files.
     ///
-    #[inline]
-    pub fn is_current_contributor(&self, participant: &Participant) -> bool {
-        // Fetch the current round from storage.
-        let round = match Self::load_current_round(&self.storage) {
-            // Case 1 - This is a typical round of the ceremony.
-            Ok(round) => round,
-            // Case 2 - The ceremony has not started or storage has failed.
-            _ => return false,
+    pub fn round_info(&self, round_height: u64) -> Result<RoundInfo, CoordinatorError> {
+        let round = self.get_round(round_height)?;
+        let expected_contributions = round.expected_number_of_contributions();
+
+        let mut chunks = Vec::with_capacity(round.chunks().len());
+        for chunk in round.chunks() {
+            chunks.push(RoundChunk {
+                chunk_id: chunk.chunk_id(),
+                lock_holder: chunk.lock_holder().as_ref().map(|participant| participant.address()),
+                complete: chunk.is_complete(expected_contributions),
+                contributions: self.transcript_contributions(chunk)?,
+            });
+        }
+
+        Ok(RoundInfo {
+            round_height,
+            started_at: round.started_at(),
+            finished_at: round.finished_at(),
+            contributors: round.contributors().iter().map(Participant::address).collect(),
+            verifiers: round.verifiers().iter().map(Participant::address).collect(),
+            complete: round.is_complete(),
+            chunks,
+        })
+    }
+
+    /// Returns the contributions to the chunk whose contributor signed the hashes, with the
+    /// signed hashes of the verifier once verified. Contributions are ordered by contribution id.
+    fn transcript_contributions(&self, chunk: &Chunk) -> Result<Vec<TranscriptContribution>, CoordinatorError> {
+        let read_signature = |path: &LocatorPath| -> Result<Option<ContributionFileSignature>, CoordinatorError> {
+            let locator = self.storage.to_locator(path)?;
+            if !self.storage.exists(&locator) {
+                return Ok(None);
+            }
+
+            match self.storage.get(&locator)? {
+                Object::ContributionFileSignature(signature) => Ok(Some(signature)),
+                _ => Err(CoordinatorError::StorageFailed),
+            }
         };
 
-        // Check that the participant is a contributor for the given round height.
-        if !round.is_contributor(participant) {
-            return false;
+        let mut contributions = vec![];
+        for (contribution_id, contribution) in chunk.get_contributions() {
+            let (contributor, signature_path) = match (
+                contribution.get_contributor(),
+                contribution.get_contributed_signature_location(),
+            ) {
+                (Some(contributor), Some(path)) => (contributor, path),
+                _ => continue,
+            };
+            let contributor_signature = match read_signature(signature_path)? {
+                Some(signature) => signature,
+                None => continue,
+            };
+            let verifier_signature = match contribution.get_verified_signature_location() {
+                Some(path) if contribution.is_verified() => read_signature(path)?,
+                _ => None,
+            };
+
+            contributions.push(TranscriptContribution {
+                contribution_id: *contribution_id,
+                contributor_public_key: contributor.address(),
+                challenge_hash: contributor_signature.get_challenge_hash().to_string(),
+                response_hash: contributor_signature.get_response_hash().to_string(),
+                signature: contributor_signature.get_signature().to_string(),
+                verified: contribution.is_verified(),
+                verifier_public_key: contribution.get_verifier().as_ref().map(|v| v.address()),
+                next_challenge_hash: verifier_signature
+                    .as_ref()
+                    .and_then(|s| s.get_next_challenge_hash().clone()),
+                verifier_signature: verifier_signature.map(|s| s.get_signature().to_string()),
+            });
         }
 
-        // Check that the participant is a current contributor.
-        self.state.is_current_contributor(participant)
+        Ok(contributions)
     }
 
     ///
-    /// Returns `true` if the given participant has finished contributing in the provided round
+    /// Updates the set of tokens for the ceremony
     ///
-    pub fn is_finished_contributor_at_round(&self, participant: &Participant, round: u64) -> bool {
-        self.state.is_finished_contributor_at_round(participant, round)
+    pub fn update_tokens(&mut self, tokens: Vec<HashSet<String>>) {
+        self.state.update_tokens(tokens)
     }
 
     ///
-    /// Returns `true` if the given participant has finished contributing in the current round
+    /// Returns `true` if the given participant is a contributor in the queue.
     ///
     #[inline]
-    pub fn is_finished_contributor(&self, participant: &Participant) -> bool {
-        // Fetch the state of the current contributor.
-        self.state.is_finished_contributor(&participant)
+    pub fn is_queue_contributor(&self, participant: &Participant) -> bool {
+        self.state.is_queue_contributor(&participant)
     }
 
     ///
-    /// Returns `true` if the given participant has been banned from the ceremony
+    /// Returns the total number of contributors currently in the queue.
     ///
     #[inline]
-    pub fn is_banned_participant(&self, participant: &Participant) -> bool {
-        self.state.is_banned_participant(participant)
+    pub fn number_of_queue_contributors(&self) -> usize {
+        self.state.number_of_queue_contributors()
     }
 
     ///
-    /// Returns `true` if the given participant has been dropped from the ceremony,
-    /// `false` if it hasn't or if there's no info about the participant.
+    /// Returns the position of the given contributor in the queue, counted in rounds, and the
+    /// size of the queue, if the contributor is in the queue.
     ///
-    #[inline]
-    pub fn is_dropped_participant(&self, participant: &Participant) -> bool {
-        match self.state.is_dropped_participant(participant) {
-            Ok(dropped) => dropped,
-            Err(_) => false,
+    pub fn queue_position(&self, participant: &Participant) -> Option<(u64, u64)> {
+        let queue_size = self.number_of_queue_contributors() as u64;
+
+        match self.state.queue_contributor_info(participant)? {
+            (_, Some(round), _, _) => Some((round.saturating_sub(self.state.current_round_height()), queue_size)),
+            (_, None, _, _) => Some((queue_size, queue_size)),
         }
     }
 
     ///
-    /// Returns `true` if the given participant is a contributor managed
-    /// by the coordinator.
+    /// Returns the estimated number of seconds before the turn of the contributor at the given
+    /// position of the queue, once the duration of a contribution slot can be told.
     ///
     #[inline]
-    pub fn is_coordinator_contributor(&self, participant: &Participant) -> bool {
-        // Check that the participant is a coordinator contributor.
-        self.state.is_coordinator_contributor(&participant)
+    pub fn estimated_wait_seconds(&self, queue_position: u64) -> Option<u64> {
+        self.state.estimated_wait_seconds(queue_position, self.time.as_ref())
     }
 
     ///
-    /// Returns `true` if the given participant is a verifier managed
-    /// by the coordinator.
+    /// Registers the email address of the given contributor in the queue, to notify it when its
+    /// turn is getting close and when it locks its chunk, see [`Self::email_notifications`].
     ///
-    #[inline]
-    pub fn is_coordinator_verifier(&self, participant: &Participant) -> bool {
-        // Check that the participant is a coordinator verifier.
-        self.state.is_coordinator_verifier(&participant)
+    pub fn register_email(&mut self, participant: &Participant, address: String) -> Result<(), CoordinatorError> {
+        if !self.state.is_queue_contributor(participant) {
+            return Err(CoordinatorError::ParticipantMissing);
+        }
+        self.state.register_email(participant, address);
+
+        // Save the coordinator state in storage.
+        self.save_state()
     }
 
     ///
-    /// Returns the current round height of the ceremony from storage,
-    /// irrespective of the stage of its completion.
-    ///
-    /// When loading the current round height from storage, this function
-    /// checks that the corresponding round is in storage. Note that it
-    /// only checks for the existence of a round value and does not
-    /// check for its correctness.
-    ///
-    #[inline]
-    pub fn current_round_height(&self) -> Result<u64, CoordinatorError> {
-        trace!("Fetching the current round height from storage");
-
-        // Fetch the current round height from storage.
-        let current_round_height = Self::load_current_round_height(&self.storage)?;
+    /// Returns the email notifications due to the contributors who registered an address: once
+    /// they're at most `positions` rounds away from their turn, and once they locked their chunk.
+    /// The notifications are returned only once, and the addresses are forgotten after the last
+    /// one or when the contributor leaves the queue.
+    ///
+    pub fn email_notifications(&mut self, positions: u64) -> Result<Vec<EmailNotification>, CoordinatorError> {
+        let mut notifications = vec![];
+        let mut notified = vec![];
+        let mut unregistered = vec![];
+
+        for (participant, contact) in self.state.contact_emails() {
+            if contact.lock_acquired {
+                notifications.push(EmailNotification::LockAcquired {
+                    address: contact.address.clone(),
+                });
+                unregistered.push(participant.clone());
+            } else if let Some((queue_position, _)) = self.queue_position(participant) {
+                if !contact.approaching_notified && queue_position <= positions {
+                    notifications.push(EmailNotification::ApproachingSlot {
+                        address: contact.address.clone(),
+                        queue_position,
+                        estimated_wait_seconds: self.estimated_wait_seconds(queue_position),
+                    });
+                    notified.push(participant.clone());
+                }
+            } else if !self.state.is_current_contributor(participant) {
+                // The contributor left the queue or was dropped before locking its chunk
+                unregistered.push(participant.clone());
+            }
+        }
 
-        // Fetch the current round from storage.
-        match self.storage.exists(&Locator::RoundState {
-            round_height: current_round_height,
-        }) {
-            // Case 1 - This is a typical round of the ceremony.
-            true => Ok(current_round_height),
-            // Case 2 - Storage failed to locate the current round.
-            false => Err(CoordinatorError::StorageFailed),
+        if notified.is_empty() && unregistered.is_empty() {
+            return Ok(notifications);
+        }
+        for participant in &notified {
+            self.state.email_approaching_notified(participant);
         }
+        for participant in &unregistered {
+            self.state.unregister_email(participant);
+        }
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(notifications)
     }
 
     ///
-    /// Returns the current round state of the ceremony from storage,
-    /// irrespective of the stage of its completion.
-    ///
-    /// If there are no prior rounds in storage, returns `CoordinatorError`.
-    ///
-    /// When loading the current round from storage, this function
-    /// checks that the current round height matches the height
-    /// set in the returned `Round` instance.
+    /// Returns the reliability score of the given contributor in the queue, from its behaviour
+    /// in the previous rounds.
     ///
     #[inline]
-    pub fn current_round(&self) -> Result<Round, CoordinatorError> {
-        trace!("Fetching the current round from storage");
-
-        // Fetch the current round from storage.
-        Self::load_current_round(&self.storage)
+    pub fn reliability_score(&self, participant: &Participant) -> u8 {
+        self.state.reliability_score(participant)
     }
 
     ///
-    /// Returns the round state corresponding to the given height from storage.
-    ///
-    /// If there are no prior rounds, returns a `CoordinatorError`.
+    /// Sets the reliability score of the given contributor, which orders the queue. Returns its
+    /// previous score.
     ///
-    pub fn get_round(&self, round_height: u64) -> Result<Round, CoordinatorError> {
-        // Fetch the current round height from storage.
-        let current_round_height = Self::load_current_round_height(&self.storage)?;
+    pub fn set_reliability_score(&mut self, participant: &Participant, score: u8) -> Result<u8, CoordinatorError> {
+        let previous = self.state.set_reliability_score(participant, score)?;
 
-        // Check that the given round height is valid.
-        match round_height <= current_round_height {
-            // Fetch the round corresponding to the given round height from storage.
-            true => Ok(serde_json::from_slice(
-                self.storage.reader(&Locator::RoundState { round_height })?.as_ref(),
-            )?),
-            // The given round height does not exist.
-            false => Err(CoordinatorError::RoundDoesNotExist),
-        }
-    }
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-    /// Lets the coordinator know that the participant is still alive
-    /// and participating (or waiting to participate) in the ceremony.
-    pub fn heartbeat(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
-        self.state.heartbeat(participant, self.time.as_ref())
+        Ok(previous)
     }
 
     ///
-    /// Attempts to acquire the lock to a chunk for the given participant.
-    ///
-    /// On failure, this function returns a `CoordinatorError`.
+    /// Records the rejection of the contribution of the given contributor at the given round,
+    /// which failed the verification, and lowers its reliability score. The contributor is
+    /// notified through its list of rejections and the events of the ceremony.
     ///
-    #[tracing::instrument(
-        level = "error",
-        skip(self),
-        fields(participant = %participant),
-        err
-    )]
-    pub fn try_lock(&mut self, participant: &Participant) -> Result<(u64, LockedLocators), CoordinatorError> {
-        if participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedContributor);
-        }
+    #[tracing::instrument(skip(self, participant, message), fields(participant = %participant))]
+    pub fn reject_contribution(
+        &mut self,
+        participant: &Participant,
+        round_height: u64,
+        reason: RejectionReason,
+        message: String,
+    ) -> Result<(), CoordinatorError> {
+        let rejection = ContributionRejection {
+            round_height,
+            reason,
+            message,
+            rejected_at: self.time.now_utc().unix_timestamp(),
+        };
+        self.state.add_rejection(participant, rejection);
+        self.state.record_invalid_contribution(participant);
+        self.state.record_failure(participant);
+        self.audit(AuditEvent::ContributionRejected {
+            participant: participant.address(),
+            round_height,
+            reason,
+        })?;
 
-        // Check that the participant is in the current round, and has not been dropped or finished.
-        if !self.state.is_current_contributor(participant) {
-            return Err(CoordinatorError::ParticipantUnauthorized);
-        }
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-        // Check that the current round is not yet finished.
-        if self.state.is_current_round_finished() {
-            return Err(CoordinatorError::CurrentRoundFinished);
-        }
+        self.emit(CeremonyEvent::ContributionRejected {
+            round_height,
+            public_key: participant.address(),
+            reason,
+        });
 
-        // Check that the current round is not yet aggregating.
-        if self.state.is_current_round_aggregating() {
-            return Err(CoordinatorError::CurrentRoundAggregating);
-        }
+        Ok(())
+    }
 
-        // Check that the current round is not yet aggregated.
-        if self.state.is_current_round_aggregated() {
-            return Err(CoordinatorError::CurrentRoundAggregated);
+    ///
+    /// Counts a failed upload of the given contributor. Once its consecutive failures reach the
+    /// threshold of the environment, the contributor is dropped from the round and can't join the
+    /// queue again until the end of the failure ban, if any.
+    ///
+    pub fn record_failure(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        let failures = self.state.record_failure(participant);
+        let threshold = self.environment.contributor_failure_threshold();
+        if threshold == 0 || failures < threshold || !self.state.is_current_contributor(participant) {
+            // Save the coordinator state in storage.
+            return self.save_state();
         }
 
-        // Attempt to fetch the next chunk ID and contribution ID for the given participant.
-        let current_task = self.state.fetch_task(participant, self.time.as_ref())?;
-        trace!("Fetched task {} for {}", current_task, participant);
-
-        let round = Self::load_current_round(&self.storage)?;
-        let chunk = round.chunk(current_task.chunk_id())?;
-        if current_task.contribution_id() > (chunk.current_contribution_id() + 1) {
+        warn!("Dropping {} after {} consecutive failures", participant, failures);
+        self.state.clear_failures(participant);
+        let ban_duration = self.environment.contributor_failure_ban_duration();
+        if ban_duration > Duration::ZERO {
             self.state
-                .rollback_pending_task(participant, current_task, &*self.time)?;
-            return Err(CoordinatorError::PreviousContributionMissing { current_task });
+                .suspend_participant(participant, self.time.now_utc() + ban_duration);
         }
 
-        debug!("Locking chunk {} for {}", current_task.chunk_id(), participant);
-        match self.try_lock_chunk(current_task.chunk_id(), participant) {
-            // Case 1 - Participant acquired lock, return the locator.
-            Ok(locked_locators) => {
-                trace!("Incrementing the number of locks held by {}", participant);
-                self.state
-                    .acquired_lock(participant, current_task.chunk_id(), self.time.as_ref())?;
-
-                // Save the coordinator state in storage.
-                self.save_state()?;
+        self.drop_participant(participant)
+    }
 
-                info!("Acquired lock on chunk {} for {}", current_task.chunk_id(), participant);
-                Ok((current_task.chunk_id(), locked_locators))
+    ///
+    /// Returns the outcome of the checks of an upload of the given contributor, counting the
+    /// invalid contributions as failures of the contributor, see [`Self::record_failure`].
+    ///
+    pub fn record_upload_checks<T>(
+        &mut self,
+        participant: &Participant,
+        checks: Result<T, CoordinatorError>,
+    ) -> Result<T, CoordinatorError> {
+        if let Err(
+            CoordinatorError::ChallengeHashMismatch
+            | CoordinatorError::ContributionFileSizeMismatch
+            | CoordinatorError::ContributionHashMismatch
+            | CoordinatorError::ContributionSignatureFileSizeMismatch
+            | CoordinatorError::ContributorSignatureInvalid,
+        ) = &checks
+        {
+            if let Err(e) = self.record_failure(participant) {
+                error!("Failed to record the failed upload of {}: {}", participant, e);
             }
-            // Case 2 - Participant failed to acquire the lock, put the chunk ID back.
-            Err(error) => {
-                info!("Failed to acquire lock for {}", participant);
-
-                trace!("Adding task {} back to assigned tasks", current_task);
-                self.state
-                    .rollback_pending_task(participant, current_task, self.time.as_ref())?;
+        }
 
-                // Save the coordinator state in storage.
-                self.save_state()?;
+        checks
+    }
 
-                error!("{}", error);
-                return Err(error);
-            }
-        }
+    ///
+    /// Returns the contributions of the given participant rejected at their verification, the
+    /// oldest first.
+    ///
+    #[inline]
+    pub fn rejections(&self, participant: &Participant) -> Vec<ContributionRejection> {
+        self.state.rejections(participant).to_vec()
     }
 
-    /// Returns previous contribution, current contribution and next contribution paths
-    pub fn get_chunk_locators_for_verifier(
-        &self,
-        participant: &Participant,
-        chunk_id: u64,
-        contribution_id: u64,
-    ) -> Result<LockedLocators, CoordinatorError> {
-        if !participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedVerifier);
-        }
-        let round = Self::load_current_round(&self.storage)?;
-        round.get_chunk_locators_for_verifier(&self.storage, participant, chunk_id, contribution_id)
+    ///
+    /// Returns a list of the contributors currently in the queue.
+    ///
+    #[inline]
+    pub fn queue_contributors(&self) -> Vec<(Participant, (u8, Option<u64>, OffsetDateTime, OffsetDateTime))> {
+        self.state.queue_contributors()
     }
 
-    /// Initialize the files for the next challenge
-    pub fn initialize_verifier_response_files(
-        &mut self,
-        participant: &Participant,
-        chunk_id: u64,
-        locators: &LockedLocators,
-    ) -> Result<(), CoordinatorError> {
-        if !participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedVerifier);
-        }
-        let round = Self::load_current_round(&self.storage)?;
-        round.initialize_verifier_response_files(&self.environment, &mut self.storage, participant, chunk_id, locators)
+    ///
+    /// Returns a list of the contributors currently in the round.
+    ///
+    #[inline]
+    pub fn current_contributors(&self) -> Vec<(Participant, ParticipantInfo)> {
+        self.state.current_contributors()
     }
 
     ///
-    /// Attempts to add a contribution for the given chunk ID from the given participant.
+    /// Returns a list of participants that were dropped from the current round.
     ///
-    /// This function constructs the expected response locator for the given participant
-    /// and chunk ID, and checks that the participant has uploaded the response file and
-    /// contribution file signature prior to adding the unverified contribution to the
-    /// round self.
+    #[inline]
+    pub fn dropped_participants(&self) -> Vec<ParticipantInfo> {
+        self.state.dropped_participants()
+    }
+
     ///
-    /// On success, this function releases the lock from the contributor and returns
-    /// the response file locator.
+    /// Returns the metrics for the current round and current round participants.
     ///
-    /// On failure, it returns a `CoordinatorError`.
+    #[inline]
+    pub fn current_round_metrics(&self) -> Option<RoundMetrics> {
+        self.state.current_round_metrics()
+    }
+
     ///
-    #[tracing::instrument(
-        level = "error",
-        skip(self, participant, chunk_id),
-        fields(participant = %participant, chunk = chunk_id),
-        err
-    )]
-    pub fn try_contribute(
+    /// Adds the given participant to the queue if they are permitted to participate.
+    ///
+    #[inline]
+    #[tracing::instrument(skip_all, fields(participant = %participant))]
+    pub fn add_to_queue(
         &mut self,
-        participant: &Participant,
-        chunk_id: u64,
-    ) -> Result<ContributionLocator, CoordinatorError> {
-        // Check that the participant is a contributor.
-        if !participant.is_contributor() {
-            return Err(CoordinatorError::ExpectedContributor);
+        participant: Participant,
+        participant_ip: Option<IpAddr>,
+        token: String,
+        reliability_score: u8,
+    ) -> Result<(), CoordinatorError> {
+        if self.finalized {
+            return Err(CoordinatorError::CeremonyIsOver);
         }
-
-        // Check that the chunk ID is valid.
-        if chunk_id > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
+        if self.draining {
+            return Err(CoordinatorError::CoordinatorDraining);
         }
 
-        // Check that the participant is in the current round, and has not been dropped or finished.
-        if !self.state.is_current_contributor(participant) {
-            return Err(CoordinatorError::ParticipantUnauthorized);
-        }
+        let address = participant.address();
 
-        // Check that the current round is not yet finished.
-        if self.state.is_current_round_finished() {
-            return Err(CoordinatorError::CurrentRoundFinished);
-        }
+        // Attempt to add the participant to the next round.
+        self.state.add_to_queue(
+            participant,
+            participant_ip,
+            token,
+            reliability_score,
+            self.time.as_ref(),
+        )?;
+        self.storage.log_transition(StateTransition::QueueAdd {
+            participant: address.clone(),
+        })?;
+        self.audit(AuditEvent::QueueJoin {
+            participant: address,
+            ip: participant_ip.map(|ip| ip.to_string()),
+        })?;
 
-        // Check that the current round is not yet aggregating.
-        if self.state.is_current_round_aggregating() {
-            return Err(CoordinatorError::CurrentRoundAggregating);
-        }
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-        // Check that the current round is not yet aggregated.
-        if self.state.is_current_round_aggregated() {
-            return Err(CoordinatorError::CurrentRoundAggregated);
-        }
+        Ok(())
+    }
 
-        // Fetch the current round height from storage.
-        let round_height = Self::load_current_round_height(&self.storage)?;
-        trace!("Current round height in storage is {}", round_height);
+    ///
+    /// Adds the given participant to the queue, redeeming the invite code required during the
+    /// invite-only phases of the ceremony.
+    ///
+    #[inline]
+    #[tracing::instrument(skip_all, fields(participant = %participant))]
+    pub fn add_to_queue_with_invite_code(
+        &mut self,
+        participant: Participant,
+        participant_ip: Option<IpAddr>,
+        token: String,
+        invite_code: &str,
+        reliability_score: u8,
+    ) -> Result<(), CoordinatorError> {
+        if self.finalized {
+            return Err(CoordinatorError::CeremonyIsOver);
+        }
+        if self.draining {
+            return Err(CoordinatorError::CoordinatorDraining);
+        }
 
-        // Check if the participant should dispose the response being contributed.
-        if let Some(task) = self.state.lookup_disposing_task(participant, chunk_id)?.cloned() {
-            let contribution_id = task.contribution_id();
+        // Check the code first, to avoid adding the participant if it can't be redeemed
+        self.state.invite_code_checks(invite_code, self.time.as_ref())?;
 
-            // Move the task to the disposed tasks of the contributor.
-            self.state.disposed_task(participant, &task, self.time.as_ref())?;
+        self.state.add_to_queue(
+            participant.clone(),
+            participant_ip,
+            token,
+            reliability_score,
+            self.time.as_ref(),
+        )?;
+        self.state.redeem_invite_code(invite_code, &participant, self.time.as_ref())?;
+        self.storage.log_transition(StateTransition::QueueAdd {
+            participant: participant.address(),
+        })?;
+        self.audit(AuditEvent::QueueJoin {
+            participant: participant.address(),
+            ip: participant_ip.map(|ip| ip.to_string()),
+        })?;
 
-            // Remove the response file from storage.
-            let response = ContributionLocator::new(round_height, chunk_id, contribution_id, false);
-            self.storage.remove(&Locator::ContributionFile(response.clone()))?;
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-            // Blacklist participant's token and ip
-            if *TOKEN_BLACKLIST {
-                self.state.blacklist_participant_token(participant)?;
-            }
+        Ok(())
+    }
 
-            if *IP_BAN {
-                self.state.blacklist_participant_ip(participant)?;
-            }
+    ///
+    /// Mints `count` new invite codes, valid for the given duration if any.
+    ///
+    pub fn mint_invite_codes(
+        &mut self,
+        count: usize,
+        notes: Option<String>,
+        expires_in: Option<Duration>,
+    ) -> Result<Vec<InviteCode>, CoordinatorError> {
+        let expires_at = match expires_in {
+            Some(duration) => Some(
+                self.time
+                    .now_utc()
+                    .checked_add(duration)
+                    .ok_or(CoordinatorError::InviteCodeExpirationInvalid)?,
+            ),
+            None => None,
+        };
+        let invite_codes = self
+            .state
+            .mint_invite_codes(count, notes, expires_at, self.time.as_ref());
 
-            // Save the coordinator state in storage.
-            self.save_state()?;
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-            debug!("Removing lock for disposed task {} {}", chunk_id, contribution_id);
+        Ok(invite_codes)
+    }
 
-            // Fetch the current round from storage.
-            let mut round = Self::load_current_round(&self.storage)?;
+    ///
+    /// Issues `count` single-use priority tokens granting the given reliability score in the queue,
+    /// redeemable from the unix timestamp `not_before`, now by default, until `expires_at`, signed
+    /// with the key of the default verifier of the coordinator. The tokens aren't stored, only their
+    /// redemption is.
+    ///
+    pub fn issue_priority_tokens(
+        &self,
+        count: usize,
+        reliability: u8,
+        not_before: Option<i64>,
+        expires_at: i64,
+    ) -> Result<Vec<PriorityToken>, CoordinatorError> {
+        let not_before = not_before.unwrap_or_else(|| self.time.now_utc().unix_timestamp());
+
+        (0..count)
+            .map(|_| {
+                let mut id = [0u8; 16];
+                rand::thread_rng().fill_bytes(&mut id);
+
+                Ok(PriorityToken::try_new(
+                    self.signature.as_ref(),
+                    &self.environment.default_verifier_signing_key(),
+                    &hex::encode(id),
+                    reliability,
+                    not_before,
+                    expires_at,
+                )?)
+            })
+            .collect()
+    }
 
-            // TODO (raychu86): Move this unsafe call out of `try_contribute`.
-            // Release the lock on this chunk from the contributor.
-            round.chunk_mut(chunk_id)?.set_lock_holder_unsafe(None);
-
-            // Save the updated round to storage.
-            self.storage.update(
-                &Locator::RoundState {
-                    round_height: round.round_height(),
-                },
-                Object::RoundState(round),
-            )?;
+    ///
+    /// Checks that the encoded priority token has been signed by the coordinator, is within its
+    /// validity window and has not been redeemed yet. Returns the reliability score it grants.
+    ///
+    pub fn priority_token_checks(&self, priority_token: &str) -> Result<u8, CoordinatorError> {
+        let public_key = self
+            .environment
+            .coordinator_verifiers()
+            .first()
+            .ok_or(CoordinatorError::VerifierMissing)?
+            .address();
 
-            return Ok(response);
+        let token = PriorityToken::decode(priority_token).ok_or(CoordinatorError::PriorityTokenInvalid)?;
+        if !token.verify(&public_key) {
+            return Err(CoordinatorError::PriorityTokenInvalid);
+        }
+        if !token.is_active(self.time.now_utc().unix_timestamp()) {
+            return Err(CoordinatorError::PriorityTokenInactive);
         }
+        self.state.priority_token_checks(&token.id)?;
 
-        // Check if the participant has this chunk ID in a pending task.
-        if let Some(task) = self.state.lookup_pending_task(participant, chunk_id)?.cloned() {
-            debug!("Adding contribution for chunk");
+        Ok(token.reliability)
+    }
 
-            match self.add_contribution(chunk_id, participant) {
-                // Case 1 - Participant added contribution, return the response file locator.
-                Ok((locator, contribution_id)) => {
-                    trace!("Release the lock on chunk");
-                    let completed_task = Task::new(chunk_id, contribution_id);
-                    self.state
-                        .completed_task(participant, &completed_task, self.time.as_ref())?;
+    ///
+    /// Marks the encoded priority token as redeemed by the given participant.
+    ///
+    pub fn redeem_priority_token(
+        &mut self,
+        priority_token: &str,
+        participant: &Participant,
+    ) -> Result<(), CoordinatorError> {
+        self.priority_token_checks(priority_token)?;
 
-                    // Blacklist participant's token and ip
-                    if *TOKEN_BLACKLIST {
-                        self.state.blacklist_participant_token(participant)?;
-                    }
+        // Safe to unwrap, the token was decoded by the checks
+        let token = PriorityToken::decode(priority_token).unwrap();
+        self.state.redeem_priority_token(&token.id, participant)?;
 
-                    if *IP_BAN {
-                        self.state.blacklist_participant_ip(participant)?;
-                    }
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-                    // Save the coordinator state in storage.
-                    self.save_state()?;
+        Ok(())
+    }
 
-                    info!("Added contribution");
-                    return Ok(locator);
-                }
-                // Case 2 - Participant failed to add their contribution, remove the contribution file.
-                Err(error) => {
-                    info!("Failed to add a contribution and removing the contribution file");
-                    // Remove the invalid response file from storage.
-                    let response = Locator::ContributionFile(ContributionLocator::new(
-                        round_height,
-                        chunk_id,
-                        task.contribution_id(),
-                        false,
-                    ));
-                    self.storage.remove(&response)?;
+    ///
+    /// Replaces the key of the default verifier with the given keypair, from the current round on.
+    ///
+    /// The old key signs a hand-off statement to the new one, which is recorded in the coordinator
+    /// state and published in the transcript. The new key is held locally, whatever the backend of
+    /// the old one.
+    ///
+    pub fn rotate_signing_key(&mut self, keypair: &KeyPair) -> Result<KeyRotation, CoordinatorError> {
+        let old_public_key = self
+            .environment
+            .coordinator_verifiers()
+            .first()
+            .ok_or(CoordinatorError::VerifierMissing)?
+            .address();
+        let rotation = KeyRotation::try_new(
+            self.signature.as_ref(),
+            &self.environment.default_verifier_signing_key(),
+            &old_public_key,
+            keypair.pubkey(),
+            self.current_round_height()?,
+            self.time.now_utc().unix_timestamp(),
+        )?;
 
-                    error!("{}", error);
-                    return Err(error);
-                }
-            }
+        if *self.environment.signing_backend() != SigningBackend::Local {
+            self.signature = SigningBackend::Local.signature()?;
         }
+        self.environment.rotate_verifier_key(keypair);
+        self.state.rotate_verifier_key(rotation.clone(), keypair);
+        self.identity = None;
 
-        Err(CoordinatorError::ContributionFailed)
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        info!("Rotated the coordinator key from {} to {}", old_public_key, keypair.pubkey());
+
+        Ok(rotation)
     }
 
     ///
-    /// Attempts to add a verification for the given chunk ID from the given participant.
-    ///
-    /// This function constructs the expected next challenge locator for the given participant
-    /// and chunk ID, and checks that the participant has uploaded the next challenge file and
-    /// contribution file signature prior to adding the verified contribution to the round state.
+    /// Issues a single-use nonce to be signed by `pubkey` to log in with [`login`](Self::login).
     ///
-    /// On success, this function releases the lock from the verifier.
+    pub fn login_nonce(&mut self, pubkey: &str) -> Result<String, CoordinatorError> {
+        if self.environment.session_duration().is_zero() {
+            return Err(CoordinatorError::LoginDisabled);
+        }
+
+        Ok(self.state.issue_login_nonce(pubkey, self.time.as_ref()))
+    }
+
     ///
-    /// On failure, it returns a `CoordinatorError`.
+    /// Opens a session for `pubkey` once it signed the nonce issued by [`login_nonce`](Self::login_nonce),
+    /// returns the session token signed with the key of the default verifier of the coordinator.
     ///
-    #[tracing::instrument(
-        level = "error",
-        skip(self, task),
-        fields(participant = %participant),
-        err
-    )]
-    pub fn try_verify(&mut self, participant: &Participant, task: &Task) -> Result<(), CoordinatorError> {
-        // Check that the participant is a verifier.
-        if !participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedVerifier);
+    pub fn login(&mut self, pubkey: &str, nonce: &str, signature: &str) -> Result<SessionToken, CoordinatorError> {
+        if self.environment.session_duration().is_zero() {
+            return Err(CoordinatorError::LoginDisabled);
         }
-
-        // Check that the chunk ID is valid.
-        if task.chunk_id() > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
+        self.state.redeem_login_nonce(pubkey, nonce, self.time.as_ref())?;
+        if !self
+            .environment
+            .signature_scheme()
+            .verify(pubkey, &login_message(pubkey, nonce), signature)
+        {
+            return Err(CoordinatorError::LoginSignatureInvalid);
         }
 
-        // Check that the current round is not yet finished.
-        if self.state.is_current_round_finished() {
-            return Err(CoordinatorError::CurrentRoundFinished);
-        }
+        let expires_at = self.time.now_utc() + self.environment.session_duration();
 
-        // Check that the current round is not yet aggregating.
-        if self.state.is_current_round_aggregating() {
-            return Err(CoordinatorError::CurrentRoundAggregating);
-        }
+        Ok(SessionToken::try_new(
+            self.signature.as_ref(),
+            &self.environment.default_verifier_signing_key(),
+            pubkey,
+            expires_at.unix_timestamp(),
+        )?)
+    }
 
-        // Check that the current round is not yet aggregated.
-        if self.state.is_current_round_aggregated() {
-            return Err(CoordinatorError::CurrentRoundAggregated);
-        }
+    ///
+    /// Registers the signature of a request sent by `pubkey` at the unix `timestamp`, to reject its replays
+    /// during the `window` in which the timestamp is accepted. The state is saved right away, for the request
+    /// not to be replayed after a restart of the coordinator.
+    ///
+    pub fn register_request_signature(
+        &mut self,
+        pubkey: &str,
+        timestamp: i64,
+        signature: &str,
+        window: Duration,
+    ) -> Result<(), CoordinatorError> {
+        self.state
+            .register_request_signature(pubkey, timestamp, signature, window, self.time.as_ref())?;
+        self.save_state()
+    }
 
-        debug!(
-            "Adding verification from {} for chunk {} contribution {}",
-            participant,
-            task.chunk_id(),
-            task.contribution_id()
-        );
+    ///
+    /// Removes the given participant from the queue if they are in the queue.
+    ///
+    #[inline]
+    #[tracing::instrument(skip(self, participant), fields(participant = %participant))]
+    pub fn remove_from_queue(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Attempt to remove the participant from the next round.
+        self.state.remove_from_queue(participant)?;
 
-        match self.verify_contribution(task, participant) {
-            // Case 1 - Participant verified contribution, return the response file locator.
-            Ok(contribution_id) => {
-                if contribution_id != task.contribution_id() {
-                    return Err(CoordinatorError::ContributionIdMismatch);
-                }
-                self.state.completed_task(participant, task, self.time.as_ref())?;
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-                // Save the coordinator state in storage.
-                self.save_state()?;
+        Ok(())
+    }
 
-                info!("Added verification from {} for chunk {}", participant, task.chunk_id());
-                Ok(())
-            }
-            // Case 2 - Participant failed to add their contribution, remove the contribution file.
-            Err(error) => {
-                info!("Failed to add a verification and removing the contribution file");
+    ///
+    /// Drops the given participant from the ceremony.
+    ///
+    #[tracing::instrument(
+        skip(self, participant),
+        fields(participant = %participant)
+    )]
+    pub fn drop_participant(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Drop the participant from the ceremony.
+        let drop = self.state.drop_participant(participant, self.time.as_ref())?;
 
-                // Fetch the current round from storage.
-                let round = Self::load_current_round(&self.storage)?;
+        // Update the round to reflect the coordinator state change.
+        self.drop_participant_from_storage(&drop)?;
 
-                // Fetch the next challenge locator.
-                let is_final_contribution = task.contribution_id() == round.expected_number_of_contributions() - 1;
-                let next_challenge = match is_final_contribution {
-                    true => Locator::ContributionFile(ContributionLocator::new(
-                        round.round_height() + 1,
-                        task.chunk_id(),
-                        0,
-                        true,
-                    )),
-                    false => Locator::ContributionFile(ContributionLocator::new(
-                        round.round_height(),
-                        task.chunk_id(),
-                        task.contribution_id(),
-                        true,
-                    )),
-                };
+        // Save the coordinator state in storage.
+        self.save_state()?;
+        self.emit_drop(&drop);
 
-                // Remove the invalid next challenge file from storage.
-                if self.storage.exists(&next_challenge) {
-                    self.storage.remove(&next_challenge)?;
-                }
+        Ok(())
+    }
 
-                error!("{}", error);
-                Err(error)
-            }
-        }
+    ///
+    /// Releases the locks of the given contributor, which gave up on its contribution, and puts
+    /// it back at the end of the queue.
+    ///
+    #[tracing::instrument(
+        skip(self, participant),
+        fields(participant = %participant)
+    )]
+    pub fn abandon_contribution(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        let drop = self.state.abandon_contribution(participant, self.time.as_ref())?;
+
+        // Update the round to reflect the coordinator state change.
+        self.drop_participant_from_storage(&drop)?;
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+        self.emit_drop(&drop);
+
+        Ok(())
     }
 
     ///
-    /// Attempts to aggregate the contributions of the current round of the ceremony.
+    /// Bans the given participant from the ceremony.
     ///
     #[inline]
-    pub fn try_aggregate(&mut self) -> Result<(), CoordinatorError> {
-        // Check that the current round height matches in storage and self.
-        let current_round_height = {
-            // Fetch the current round height from storage.
-            let current_round_height_in_storage = Self::load_current_round_height(&self.storage)?;
-            trace!("Current round height in storage is {}", current_round_height_in_storage);
+    pub fn ban_participant(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Ban the participant from the ceremony.
+        let drop = self.state.ban_participant(participant, self.time.as_ref())?;
 
-            // Fetch the current round height from coordinator self.
-            let current_round_height = self.state.current_round_height();
-            trace!("Current round height in coordinator state is {}", current_round_height);
+        // Update the round on disk to reflect the coordinator state change.
+        if let Some(drop) = drop {
+            self.drop_participant_from_storage(&drop)?;
+        }
+        self.audit(AuditEvent::Ban {
+            participant: Some(participant.address()),
+            ip: None,
+        })?;
 
-            // Check that the current round height matches in storage and self.
-            match current_round_height_in_storage == current_round_height {
-                true => current_round_height,
-                false => return Err(CoordinatorError::RoundHeightMismatch),
-            }
-        };
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-        // Check that the current round is finished by contributors and verifiers.
-        if !self.state.is_current_round_finished() {
-            error!("Coordinator state shows round {} is not finished", current_round_height);
-            return Err(CoordinatorError::RoundNotReady);
+        Ok(())
+    }
+
+    ///
+    /// Drops the contributors of the current round, releasing their locks, and moves on to the
+    /// next contributors of the queue. The skipped contributors are banned, or given the
+    /// reliability score, if any. Returns the skipped contributors.
+    ///
+    pub fn skip_current_contributors(
+        &mut self,
+        ban: bool,
+        reliability: Option<u8>,
+    ) -> Result<Vec<Participant>, CoordinatorError> {
+        let skipped: Vec<Participant> = self
+            .state
+            .current_contributors()
+            .into_iter()
+            .map(|(participant, _)| participant)
+            .filter(|participant| !self.state.is_coordinator_contributor(participant))
+            .collect();
+        if skipped.is_empty() {
+            return Ok(skipped);
         }
 
-        // Check that the current round has not been aggregated.
-        if self.state.is_current_round_aggregated() {
-            error!("Coordinator state shows round {} was aggregated", current_round_height);
-            return Err(CoordinatorError::RoundAlreadyAggregated);
+        for participant in &skipped {
+            match ban {
+                true => self.ban_participant(participant)?,
+                false => self.drop_participant(participant)?,
+            }
+            if let Some(score) = reliability {
+                self.set_reliability_score(participant, score)?;
+            }
         }
 
-        // Update the coordinator state to set the start of aggregation for the current round.
-        self.state.aggregating_current_round(self.time.as_ref())?;
+        // Start the round over with the next contributors of the queue
+        self.update()?;
 
-        // Check if this is round 0, as coordinator may safely skip aggregation.
-        if current_round_height == 0 {
-            // Set the current round as aggregated in coordinator self.
-            self.state.aggregated_current_round(self.time.as_ref())?;
-
-            debug!("Coordinator is safely skipping aggregation for round 0");
-            return Ok(());
-        }
+        Ok(skipped)
+    }
 
-        // Attempt to aggregate the current round.
-        trace!("Trying to aggregate round {}", current_round_height);
-        match self.aggregate_contributions() {
-            // Case 1a - Coordinator aggregated the current round.
-            Ok(()) => {
-                info!("Coordinator has aggregated round {}", current_round_height);
+    ///
+    /// Unbans the given participant from joining the queue.
+    ///
+    #[inline]
+    pub fn unban_participant(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        // Unban the participant from the ceremony.
+        self.state.unban_participant(participant);
+        self.audit(AuditEvent::Unban {
+            participant: Some(participant.address()),
+            ip: None,
+        })?;
 
-                // Set the current round as aggregated in coordinator self.
-                self.state.aggregated_current_round(self.time.as_ref())?;
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-                // Check that the current round has now been aggregated.
-                if !self.state.is_current_round_aggregated() {
-                    error!("Coordinator state says round {} isn't aggregated", current_round_height);
-                    return Err(CoordinatorError::RoundAggregationFailed);
-                }
+        Ok(())
+    }
 
-                Ok(())
-            }
-            // Case 1b - Coordinator failed to aggregate the current round.
-            Err(error) => {
-                error!("Coordinator failed to aggregate the current round\n{}", error);
+    ///
+    /// Bans the given IP from joining the queue.
+    ///
+    #[inline]
+    pub fn ban_ip(&mut self, ip: IpAddr) -> Result<(), CoordinatorError> {
+        // Ban the IP and drop the participants of the queue that joined from it.
+        self.state.ban_ip(ip, self.time.as_ref())?;
+        self.audit(AuditEvent::Ban {
+            participant: None,
+            ip: Some(ip.to_string()),
+        })?;
 
-                // Rollback the current round aggregation.
-                self.state.rollback_aggregating_current_round()?;
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-                Err(error)
-            }
-        }
+        Ok(())
     }
 
     ///
-    /// Attempts to advance the ceremony to the next round.
+    /// Unbans the given IP from joining the queue.
     ///
-    #[tracing::instrument(skip(self, started_at))]
-    pub fn try_advance(&mut self, started_at: OffsetDateTime) -> Result<u64, CoordinatorError> {
-        tracing::debug!("Trying to advance to the next round.");
+    #[inline]
+    pub fn unban_ip(&mut self, ip: &IpAddr) -> Result<(), CoordinatorError> {
+        self.state.unban_ip(ip);
+        self.audit(AuditEvent::Unban {
+            participant: None,
+            ip: Some(ip.to_string()),
+        })?;
 
-        // Check that the current round height matches in storage and self.
-        let current_round_height = {
-            // Fetch the current round height from storage.
-            let current_round_height_in_storage = Self::load_current_round_height(&self.storage)?;
-            debug!("Current round height in storage is {}", current_round_height_in_storage);
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-            // Fetch the current round height from coordinator self.
-            let current_round_height = self.state.current_round_height();
-            debug!("Current round height in coordinator state is {}", current_round_height);
+        Ok(())
+    }
 
-            // Check that the current round height matches in storage and self.
-            if current_round_height_in_storage == current_round_height {
-                current_round_height
-            } else {
-                tracing::error!(
-                    "Round height in storage ({}) does not match the \
-                    round height in coordinator state ({})",
-                    current_round_height_in_storage,
-                    current_round_height,
-                );
-                return Err(CoordinatorError::RoundHeightMismatch);
-            }
-        };
+    ///
+    /// Returns `true` if the manual lock for transitioning to the next round is enabled.
+    ///
+    #[inline]
+    pub fn is_manual_lock_enabled(&self) -> bool {
+        // Fetch the state of the manual lock.
+        self.state.is_manual_lock_enabled()
+    }
 
-        // Check that the current round is finished.
-        if !self.state.is_current_round_finished() {
-            return Err(CoordinatorError::CurrentRoundNotFinished);
-        }
+    ///
+    /// Sets the manual lock for transitioning to the next round to `true`.
+    ///
+    #[inline]
+    pub fn enable_manual_lock(&mut self) -> Result<(), CoordinatorError> {
+        // Sets the manual lock to `true`.
+        self.state.enable_manual_lock();
 
-        // Check that the current round is aggregated, if this is not round 0.
-        if current_round_height > 0 && !self.state.is_current_round_aggregated() {
-            return Err(CoordinatorError::CurrentRoundNotAggregated);
-        }
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-        // Attempt to advance the round.
-        trace!("Running precommit for the next round");
-        let result = match self
-            .state
-            .precommit_next_round(current_round_height + 1, self.time.as_ref())
-        {
-            // Case 1 - Precommit succeed, attempt to advance the round.
-            Ok(contributors) => {
-                trace!("Trying to add advance to the next round");
-                match self.next_round(started_at, contributors) {
-                    // Case 1a - Coordinator advanced the round.
-                    Ok(next_round_height) => {
-                        // If success, update coordinator state to next round.
-                        info!("Coordinator has advanced to round {}", next_round_height);
-                        self.state.commit_next_round();
-                        Ok(next_round_height)
-                    }
-                    // Case 1b - Coordinator failed to advance the round.
-                    Err(error) => {
-                        // If failed, rollback coordinator state to the current round.
-                        error!("Coordinator failed to advance the next round, performing state rollback");
-                        self.state.rollback_next_round(self.time.as_ref());
-                        error!("{}", error);
-                        Err(error)
-                    }
-                }
-            }
-            // Case 2 - Precommit failed, roll back precommit.
-            Err(error) => {
-                // If failed, rollback coordinator state to the current round.
-                error!("Precommit for next round has failed, performing state rollback");
-                self.state.rollback_next_round(self.time.as_ref());
-                error!("{}", error);
-                Err(error)
-            }
-        };
+        Ok(())
+    }
+
+    ///
+    /// Sets the manual lock for transitioning to the next round to `false`.
+    ///
+    #[inline]
+    pub fn disable_manual_lock(&mut self) -> Result<(), CoordinatorError> {
+        // Sets the manual lock to `false`.
+        self.state.disable_manual_lock();
 
         // Save the coordinator state in storage.
         self.save_state()?;
 
-        result
+        Ok(())
     }
 
     ///
-    /// Returns the chunk ID from the given contribution file locator path.
+    /// Returns `true` if the given participant is authorized as a
+    /// contributor and listed in the contributor IDs for this round.
     ///
-    /// If the given locator path is invalid, returns a `CoordinatorError`.
+    /// If the participant is not a contributor, or if there are
+    /// no prior rounds, returns `false`.
     ///
     #[inline]
-    pub fn contribution_locator_to_chunk_id(&self, locator_path: &LocatorPath) -> Result<u64, CoordinatorError> {
-        // Fetch the chunk ID corresponding to the given locator path.
-        let locator = self.storage.to_locator(&locator_path)?;
-        match &locator {
-            Locator::ContributionFile(contribution_locator) => match self.storage.exists(&locator) {
-                true => Ok(contribution_locator.chunk_id()),
-                false => Err(CoordinatorError::ContributionLocatorMissing),
-            },
-            _ => Err(CoordinatorError::ContributionLocatorIncorrect),
+    pub fn is_current_contributor(&self, participant: &Participant) -> bool {
+        // Fetch the current round from storage.
+        let round = match Self::load_current_round(&self.storage) {
+            // Case 1 - This is a typical round of the ceremony.
+            Ok(round) => round,
+            // Case 2 - The ceremony has not started or storage has failed.
+            _ => return false,
+        };
+
+        // Check that the participant is a contributor for the given round height.
+        if !round.is_contributor(participant) {
+            return false;
         }
-    }
 
-    /// Convert a locator to a path string using the coordinator's
-    /// storage layer.
-    pub fn locator_to_path(&self, locator: Locator) -> Result<LocatorPath, CoordinatorError> {
-        self.storage.to_path(&locator)
+        // Check that the participant is a current contributor.
+        self.state.is_current_contributor(participant)
     }
 
     ///
-    /// Attempts to acquire the lock for a given chunk ID and
-    /// participant.
+    /// Returns `true` if the given participant has finished contributing in the provided round
     ///
-    /// **Important**: The returned next contribution locator does not
-    /// always match the current task being performed. If it is the
-    /// final verification for a chunk, the a [Participant::Verifier]
-    /// will receive a locator to the contribution 0 of the same chunk
-    /// in the next round.
+    pub fn is_finished_contributor_at_round(&self, participant: &Participant, round: u64) -> bool {
+        self.state.is_finished_contributor_at_round(participant, round)
+    }
+
     ///
-    /// On failure, this function returns a `CoordinatorError`.
+    /// Returns `true` if the given participant has finished contributing in the current round
     ///
-    pub(crate) fn try_lock_chunk(
-        &mut self,
-        chunk_id: u64,
-        participant: &Participant,
-    ) -> Result<LockedLocators, CoordinatorError> {
-        // Check that the chunk ID is valid.
-        if chunk_id > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
-        }
-
-        // Fetch the current round height from storage.
-        let current_round_height = Self::load_current_round_height(&self.storage)?;
-        trace!("Current round height from storage is {}", current_round_height);
-
-        // Fetch the current round from storage.
-        let mut round = Self::load_current_round(&self.storage)?;
-
-        // Attempt to acquire the chunk lock for participant.
-        trace!("Preparing to lock chunk {}", chunk_id);
-        let locked_locators = round.try_lock_chunk(&self.environment, &mut self.storage, chunk_id, &participant)?;
-        trace!("Participant {} locked chunk {}", participant, chunk_id);
-
-        // Add the updated round to storage.
-        match self.storage.update(
-            &Locator::RoundState {
-                round_height: current_round_height,
-            },
-            Object::RoundState(round),
-        ) {
-            Ok(_) => {
-                debug!("{} acquired lock on chunk {}", participant, chunk_id);
-                Ok(locked_locators)
-            }
-            _ => Err(CoordinatorError::StorageUpdateFailed),
-        }
+    #[inline]
+    pub fn is_finished_contributor(&self, participant: &Participant) -> bool {
+        // Fetch the state of the current contributor.
+        self.state.is_finished_contributor(&participant)
     }
 
     ///
-    /// Attempts to add a contribution for a given chunk ID from a given participant.
+    /// Returns `true` if the given participant has finished contributing in any round
     ///
-    /// This function checks that the participant is a contributor and has uploaded
-    /// a valid response file to the coordinator. The coordinator sanity checks
-    /// (however, does not verify) the contribution before accepting the response file.
+    #[inline]
+    pub fn has_contributed(&self, participant: &Participant) -> bool {
+        self.state.has_contributed(participant)
+    }
+
     ///
-    /// On success, this function releases the chunk lock from the contributor and
-    /// returns the response file locator and contribution ID of the response file.
+    /// Returns `true` if the given participant has been banned from the ceremony
     ///
-    /// On failure, it returns a `CoordinatorError`.
+    #[inline]
+    pub fn is_banned_participant(&self, participant: &Participant) -> bool {
+        self.state.is_banned_participant(participant)
+    }
+
+    ///
+    /// Returns `true` if the given participant has been dropped from the ceremony,
+    /// `false` if it hasn't or if there's no info about the participant.
     ///
     #[inline]
-    pub(crate) fn add_contribution(
+    pub fn is_dropped_participant(&self, participant: &Participant) -> bool {
+        match self.state.is_dropped_participant(participant) {
+            Ok(dropped) => dropped,
+            Err(_) => false,
+        }
+    }
+
+    ///
+    /// Returns `true` if the given participant is a contributor managed
+    /// by the coordinator.
+    ///
+    #[inline]
+    pub fn is_coordinator_contributor(&self, participant: &Participant) -> bool {
+        // Check that the participant is a coordinator contributor.
+        self.state.is_coordinator_contributor(&participant)
+    }
+
+    ///
+    /// Returns `true` if the given participant is a verifier managed
+    /// by the coordinator.
+    ///
+    #[inline]
+    pub fn is_coordinator_verifier(&self, participant: &Participant) -> bool {
+        // Check that the participant is a coordinator verifier.
+        self.state.is_coordinator_verifier(&participant)
+    }
+
+    ///
+    /// Returns the current round height of the ceremony from storage,
+    /// irrespective of the stage of its completion.
+    ///
+    /// When loading the current round height from storage, this function
+    /// checks that the corresponding round is in storage. Note that it
+    /// only checks for the existence of a round value and does not
+    /// check for its correctness.
+    ///
+    #[inline]
+    pub fn current_round_height(&self) -> Result<u64, CoordinatorError> {
+        trace!("Fetching the current round height from storage");
+
+        // Fetch the current round height from storage.
+        let current_round_height = Self::load_current_round_height(&self.storage)?;
+
+        // Fetch the current round from storage.
+        match self.storage.exists(&Locator::RoundState {
+            round_height: current_round_height,
+        }) {
+            // Case 1 - This is a typical round of the ceremony.
+            true => Ok(current_round_height),
+            // Case 2 - Storage failed to locate the current round.
+            false => Err(CoordinatorError::StorageFailed),
+        }
+    }
+
+    ///
+    /// Returns the current round state of the ceremony from storage,
+    /// irrespective of the stage of its completion.
+    ///
+    /// If there are no prior rounds in storage, returns `CoordinatorError`.
+    ///
+    /// When loading the current round from storage, this function
+    /// checks that the current round height matches the height
+    /// set in the returned `Round` instance.
+    ///
+    #[inline]
+    pub fn current_round(&self) -> Result<Round, CoordinatorError> {
+        trace!("Fetching the current round from storage");
+
+        // Fetch the current round from storage.
+        Self::load_current_round(&self.storage)
+    }
+
+    ///
+    /// Returns the round state corresponding to the given height from storage.
+    ///
+    /// If there are no prior rounds, returns a `CoordinatorError`.
+    ///
+    pub fn get_round(&self, round_height: u64) -> Result<Round, CoordinatorError> {
+        // Fetch the current round height from storage.
+        let current_round_height = Self::load_current_round_height(&self.storage)?;
+
+        // Check that the given round height is valid.
+        match round_height <= current_round_height {
+            // Fetch the round corresponding to the given round height from storage.
+            true => Ok(serde_json::from_slice(
+                self.storage.reader(&Locator::RoundState { round_height })?.as_ref(),
+            )?),
+            // The given round height does not exist.
+            false => Err(CoordinatorError::RoundDoesNotExist),
+        }
+    }
+
+    /// Lets the coordinator know that the participant is still alive
+    /// and participating (or waiting to participate) in the ceremony.
+    pub fn heartbeat(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        self.state.heartbeat(participant, self.time.as_ref())
+    }
+
+    ///
+    /// Attempts to acquire the lock to a chunk for the given participant.
+    ///
+    /// On failure, this function returns a `CoordinatorError`.
+    ///
+    #[tracing::instrument(
+        level = "error",
+        skip(self),
+        fields(participant = %participant),
+        err
+    )]
+    pub fn try_lock(&mut self, participant: &Participant) -> Result<(u64, LockedLocators), CoordinatorError> {
+        if participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedContributor);
+        }
+
+        // No new contribution starts once the beacon is applied, nor while draining
+        if self.finalized {
+            return Err(CoordinatorError::CeremonyIsOver);
+        }
+        if self.draining {
+            return Err(CoordinatorError::CoordinatorDraining);
+        }
+
+        // The contribution of the lock must fit in the storage
+        self.check_storage_space()?;
+
+        // Check that the participant is in the current round, and has not been dropped or finished.
+        if !self.state.is_current_contributor(participant) {
+            return Err(CoordinatorError::ParticipantUnauthorized);
+        }
+
+        // Check that the current round is not yet finished.
+        if self.state.is_current_round_finished() {
+            return Err(CoordinatorError::CurrentRoundFinished);
+        }
+
+        // Check that the current round is not yet aggregating.
+        if self.state.is_current_round_aggregating() {
+            return Err(CoordinatorError::CurrentRoundAggregating);
+        }
+
+        // Check that the current round is not yet aggregated.
+        if self.state.is_current_round_aggregated() {
+            return Err(CoordinatorError::CurrentRoundAggregated);
+        }
+
+        // Attempt to fetch the next chunk ID and contribution ID for the given participant.
+        let current_task = self.state.fetch_task(participant, self.time.as_ref())?;
+        trace!("Fetched task {} for {}", current_task, participant);
+
+        let round = Self::load_current_round(&self.storage)?;
+        let chunk = round.chunk(current_task.chunk_id())?;
+        if current_task.contribution_id() > (chunk.current_contribution_id() + 1) {
+            self.state
+                .rollback_pending_task(participant, current_task, &*self.time)?;
+            return Err(CoordinatorError::PreviousContributionMissing { current_task });
+        }
+
+        debug!("Locking chunk {} for {}", current_task.chunk_id(), participant);
+        match self.try_lock_chunk(current_task.chunk_id(), participant) {
+            // Case 1 - Participant acquired lock, return the locator.
+            Ok(mut locked_locators) => {
+                trace!("Incrementing the number of locks held by {}", participant);
+                self.state
+                    .acquired_lock(participant, current_task.chunk_id(), self.time.as_ref())?;
+                if let Some(expires_at) = self.state.lock_expiry(participant) {
+                    locked_locators.expires_at = Some(expires_at.unix_timestamp());
+                }
+
+                // Record the challenge issued with the lock, for the contribution to be checked against it
+                let (_, challenge_hash) = self.hashed_contribution_file(locked_locators.previous_contribution())?;
+                self.state
+                    .record_challenge_hash(participant, current_task.chunk_id(), challenge_hash)?;
+                self.storage.log_transition(StateTransition::Lock {
+                    participant: participant.address(),
+                    chunk_id: current_task.chunk_id(),
+                })?;
+                self.audit(AuditEvent::Lock {
+                    participant: participant.address(),
+                    chunk_id: current_task.chunk_id(),
+                })?;
+                self.state.email_lock_acquired(participant);
+
+                // Save the coordinator state in storage.
+                self.save_state()?;
+
+                info!("Acquired lock on chunk {} for {}", current_task.chunk_id(), participant);
+                Ok((current_task.chunk_id(), locked_locators))
+            }
+            // Case 2 - Participant failed to acquire the lock, put the chunk ID back.
+            Err(error) => {
+                info!("Failed to acquire lock for {}", participant);
+
+                trace!("Adding task {} back to assigned tasks", current_task);
+                self.state
+                    .rollback_pending_task(participant, current_task, self.time.as_ref())?;
+
+                // Save the coordinator state in storage.
+                self.save_state()?;
+
+                error!("{}", error);
+                return Err(error);
+            }
+        }
+    }
+
+    ///
+    /// Checks that the locks held by the given contributor haven't expired. Otherwise, the
+    /// contributor is dropped from the round, which moves on to the next contributors of the
+    /// queue, and [CoordinatorError::ChunkLockExpired] is returned.
+    ///
+    #[tracing::instrument(skip(self, participant), fields(participant = %participant))]
+    pub fn check_lock_expiry(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        if self.state.is_coordinator_contributor(participant) {
+            return Ok(());
+        }
+
+        match self.state.lock_expiry(participant) {
+            Some(expires_at) if self.time.now_utc() > expires_at => {
+                warn!("The lock held by {} expired at {}", participant, expires_at);
+
+                // The update drops the participants holding an expired lock
+                self.update()?;
+                Err(CoordinatorError::ChunkLockExpired)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// Returns the hash, hex encoded, of the challenge issued to the given contributor with its
+    /// lock on the given chunk ID, if any.
+    ///
+    pub fn issued_challenge_hash(&self, participant: &Participant, chunk_id: u64) -> Option<String> {
+        self.state
+            .issued_challenge_hash(participant, chunk_id)
+            .map(str::to_owned)
+    }
+
+    ///
+    /// Checks that the given contribution file signature was signed by the given contributor,
+    /// for nobody else to overwrite its pending contribution. Otherwise,
+    /// [CoordinatorError::ContributorSignatureInvalid] is returned.
+    ///
+    pub fn check_contribution_signature(
+        &self,
+        participant: &Participant,
+        signature: &ContributionFileSignature,
+    ) -> Result<(), CoordinatorError> {
+        let address = &participant.to_string();
+
+        let address = address
+            .split(".")
+            .next()
+            .expect("splitting a string should yield at least one item");
+
+        if !self.environment.signature_scheme().verify_with(
+            self.signature.as_ref(),
+            address,
+            &serde_json::to_string(&signature.get_state())?,
+            signature.get_signature(),
+        ) {
+            error!("Contribution file signature failed to verify for {}", participant);
+            return Err(CoordinatorError::ContributorSignatureInvalid);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Checks that the given contribution file signature of the contributor was computed against
+    /// the challenge issued with its lock on the given chunk ID, before any file is written.
+    /// Otherwise, [CoordinatorError::ChallengeHashMismatch] is returned.
+    ///
+    pub fn check_challenge_hash(
+        &self,
+        participant: &Participant,
+        chunk_id: u64,
+        signature: &ContributionFileSignature,
+    ) -> Result<(), CoordinatorError> {
+        let issued_challenge_hash = match self.state.issued_challenge_hash(participant, chunk_id) {
+            Some(challenge_hash) => hex::decode(challenge_hash)?,
+            None => return Ok(()),
+        };
+
+        if hex::decode(signature.get_challenge_hash())? != issued_challenge_hash {
+            error!(
+                "The signed challenge hash of {} does not match the challenge issued with its lock",
+                participant
+            );
+            return Err(CoordinatorError::ChallengeHashMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Returns previous contribution, current contribution and next contribution paths
+    pub fn get_chunk_locators_for_verifier(
+        &self,
+        participant: &Participant,
+        chunk_id: u64,
+        contribution_id: u64,
+    ) -> Result<LockedLocators, CoordinatorError> {
+        if !participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedVerifier);
+        }
+        let round = Self::load_current_round(&self.storage)?;
+        round.get_chunk_locators_for_verifier(&self.storage, participant, chunk_id, contribution_id)
+    }
+
+    /// Initialize the files for the next challenge
+    pub fn initialize_verifier_response_files(
+        &mut self,
+        participant: &Participant,
+        chunk_id: u64,
+        locators: &LockedLocators,
+    ) -> Result<(), CoordinatorError> {
+        if !participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedVerifier);
+        }
+        let round = Self::load_current_round(&self.storage)?;
+        round.initialize_verifier_response_files(&self.environment, &mut self.storage, participant, chunk_id, locators)
+    }
+
+    ///
+    /// Attempts to add a contribution for the given chunk ID from the given participant.
+    ///
+    /// This function constructs the expected response locator for the given participant
+    /// and chunk ID, and checks that the participant has uploaded the response file and
+    /// contribution file signature prior to adding the unverified contribution to the
+    /// round self.
+    ///
+    /// On success, this function releases the lock from the contributor and returns
+    /// the response file locator.
+    ///
+    /// On failure, it returns a `CoordinatorError`.
+    ///
+    #[tracing::instrument(
+        level = "error",
+        skip(self, participant, chunk_id),
+        fields(participant = %participant, chunk = chunk_id),
+        err
+    )]
+    pub fn try_contribute(
+        &mut self,
+        participant: &Participant,
+        chunk_id: u64,
+    ) -> Result<ContributionLocator, CoordinatorError> {
+        // Check that the participant is a contributor.
+        if !participant.is_contributor() {
+            return Err(CoordinatorError::ExpectedContributor);
+        }
+
+        // Check that the ceremony hasn't been closed by the beacon.
+        if self.finalized {
+            return Err(CoordinatorError::CeremonyIsOver);
+        }
+
+        // Check that the chunk ID is valid.
+        if chunk_id > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
+        }
+
+        // Check that the participant is in the current round, and has not been dropped or finished.
+        if !self.state.is_current_contributor(participant) {
+            return Err(CoordinatorError::ParticipantUnauthorized);
+        }
+
+        // Check that the current round is not yet finished.
+        if self.state.is_current_round_finished() {
+            return Err(CoordinatorError::CurrentRoundFinished);
+        }
+
+        // Check that the current round is not yet aggregating.
+        if self.state.is_current_round_aggregating() {
+            return Err(CoordinatorError::CurrentRoundAggregating);
+        }
+
+        // Check that the current round is not yet aggregated.
+        if self.state.is_current_round_aggregated() {
+            return Err(CoordinatorError::CurrentRoundAggregated);
+        }
+
+        // Fetch the current round height from storage.
+        let round_height = Self::load_current_round_height(&self.storage)?;
+        trace!("Current round height in storage is {}", round_height);
+
+        // Check if the participant should dispose the response being contributed.
+        if let Some(task) = self.state.lookup_disposing_task(participant, chunk_id)?.cloned() {
+            let contribution_id = task.contribution_id();
+
+            // Move the task to the disposed tasks of the contributor.
+            self.state.disposed_task(participant, &task, self.time.as_ref())?;
+
+            // Remove the response file from storage.
+            let response = ContributionLocator::new(round_height, chunk_id, contribution_id, false);
+            self.storage.remove(&Locator::ContributionFile(response.clone()))?;
+
+            // Blacklist participant's token and ip
+            if *TOKEN_BLACKLIST {
+                self.state.blacklist_participant_token(participant)?;
+            }
+
+            if *IP_BAN {
+                self.state.blacklist_participant_ip(participant)?;
+            }
+
+            // Save the coordinator state in storage.
+            self.save_state()?;
+
+            debug!("Removing lock for disposed task {} {}", chunk_id, contribution_id);
+
+            // Fetch the current round from storage.
+            let mut round = Self::load_current_round(&self.storage)?;
+
+            // TODO (raychu86): Move this unsafe call out of `try_contribute`.
+            // Release the lock on this chunk from the contributor.
+            round.chunk_mut(chunk_id)?.set_lock_holder_unsafe(None);
+
+            // Save the updated round to storage.
+            self.storage.update(
+                &Locator::RoundState {
+                    round_height: round.round_height(),
+                },
+                Object::RoundState(round),
+            )?;
+
+            return Ok(response);
+        }
+
+        // Check if the participant has this chunk ID in a pending task.
+        if let Some(task) = self.state.lookup_pending_task(participant, chunk_id)?.cloned() {
+            debug!("Adding contribution for chunk");
+
+            match self.add_contribution(chunk_id, participant) {
+                // Case 1 - Participant added contribution, return the response file locator.
+                Ok((locator, contribution_id)) => {
+                    trace!("Release the lock on chunk");
+                    let completed_task = Task::new(chunk_id, contribution_id);
+                    self.state
+                        .completed_task(participant, &completed_task, self.time.as_ref())?;
+                    self.state.record_contribution(participant, self.time.as_ref());
+                    self.state.clear_failures(participant);
+
+                    // Blacklist participant's token and ip
+                    if *TOKEN_BLACKLIST {
+                        self.state.blacklist_participant_token(participant)?;
+                    }
+
+                    if *IP_BAN {
+                        self.state.blacklist_participant_ip(participant)?;
+                    }
+                    self.storage.log_transition(StateTransition::ContributionAccepted {
+                        participant: participant.address(),
+                        chunk_id,
+                        contribution_id,
+                    })?;
+                    self.audit(AuditEvent::Upload {
+                        participant: participant.address(),
+                        chunk_id,
+                        contribution_id,
+                    })?;
+
+                    // Save the coordinator state in storage.
+                    self.save_state()?;
+                    self.emit(CeremonyEvent::ContributionAccepted {
+                        round_height,
+                        public_key: participant.address(),
+                    });
+
+                    info!("Added contribution");
+                    return Ok(locator);
+                }
+                // Case 2 - Participant failed to add their contribution, remove the contribution file.
+                Err(error) => {
+                    info!("Failed to add a contribution and removing the contribution file");
+                    // Remove the invalid response file from storage.
+                    let response = Locator::ContributionFile(ContributionLocator::new(
+                        round_height,
+                        chunk_id,
+                        task.contribution_id(),
+                        false,
+                    ));
+                    self.storage.remove(&response)?;
+
+                    error!("{}", error);
+                    return Err(error);
+                }
+            }
+        }
+
+        Err(CoordinatorError::ContributionFailed)
+    }
+
+    ///
+    /// Attempts to add a verification for the given chunk ID from the given participant.
+    ///
+    /// This function constructs the expected next challenge locator for the given participant
+    /// and chunk ID, and checks that the participant has uploaded the next challenge file and
+    /// contribution file signature prior to adding the verified contribution to the round state.
+    ///
+    /// On success, this function releases the lock from the verifier.
+    ///
+    /// On failure, it returns a `CoordinatorError`.
+    ///
+    #[tracing::instrument(
+        level = "error",
+        skip(self, task),
+        fields(participant = %participant),
+        err
+    )]
+    pub fn try_verify(&mut self, participant: &Participant, task: &Task) -> Result<(), CoordinatorError> {
+        // Check that the participant is a verifier.
+        if !participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedVerifier);
+        }
+
+        // Check that the chunk ID is valid.
+        if task.chunk_id() > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
+        }
+
+        // Check that the current round is not yet finished.
+        if self.state.is_current_round_finished() {
+            return Err(CoordinatorError::CurrentRoundFinished);
+        }
+
+        // Check that the current round is not yet aggregating.
+        if self.state.is_current_round_aggregating() {
+            return Err(CoordinatorError::CurrentRoundAggregating);
+        }
+
+        // Check that the current round is not yet aggregated.
+        if self.state.is_current_round_aggregated() {
+            return Err(CoordinatorError::CurrentRoundAggregated);
+        }
+
+        debug!(
+            "Adding verification from {} for chunk {} contribution {}",
+            participant,
+            task.chunk_id(),
+            task.contribution_id()
+        );
+
+        match self.verify_contribution(task, participant) {
+            // Case 1 - Participant verified contribution, return the response file locator.
+            Ok(contribution_id) => {
+                if contribution_id != task.contribution_id() {
+                    return Err(CoordinatorError::ContributionIdMismatch);
+                }
+                self.state.completed_task(participant, task, self.time.as_ref())?;
+                self.storage.log_transition(StateTransition::VerificationResult {
+                    verifier: participant.address(),
+                    chunk_id: task.chunk_id(),
+                    contribution_id,
+                    verified: true,
+                })?;
+                self.audit(AuditEvent::ContributionAccepted {
+                    verifier: participant.address(),
+                    chunk_id: task.chunk_id(),
+                    contribution_id,
+                })?;
+
+                // Save the coordinator state in storage.
+                self.save_state()?;
+                self.emit(CeremonyEvent::VerificationFinished {
+                    round_height: self.state.current_round_height(),
+                    contribution_id: task.contribution_id(),
+                    verified: true,
+                });
+
+                info!("Added verification from {} for chunk {}", participant, task.chunk_id());
+                Ok(())
+            }
+            // Case 2 - Participant failed to add their contribution, remove the contribution file.
+            Err(error) => {
+                info!("Failed to add a verification and removing the contribution file");
+
+                // Fetch the current round from storage.
+                let round = Self::load_current_round(&self.storage)?;
+
+                // Fetch the next challenge locator.
+                let is_final_contribution = task.contribution_id() == round.expected_number_of_contributions() - 1;
+                let next_challenge = match is_final_contribution {
+                    true => Locator::ContributionFile(ContributionLocator::new(
+                        round.round_height() + 1,
+                        task.chunk_id(),
+                        0,
+                        true,
+                    )),
+                    false => Locator::ContributionFile(ContributionLocator::new(
+                        round.round_height(),
+                        task.chunk_id(),
+                        task.contribution_id(),
+                        true,
+                    )),
+                };
+
+                // Remove the invalid next challenge file from storage.
+                if self.storage.exists(&next_challenge) {
+                    self.storage.remove(&next_challenge)?;
+                }
+                self.emit(CeremonyEvent::VerificationFinished {
+                    round_height: round.round_height(),
+                    contribution_id: task.contribution_id(),
+                    verified: false,
+                });
+
+                error!("{}", error);
+                Err(error)
+            }
+        }
+    }
+
+    ///
+    /// Attempts to aggregate the contributions of the current round of the ceremony.
+    ///
+    #[inline]
+    pub fn try_aggregate(&mut self) -> Result<(), CoordinatorError> {
+        // Check that the current round height matches in storage and self.
+        let current_round_height = {
+            // Fetch the current round height from storage.
+            let current_round_height_in_storage = Self::load_current_round_height(&self.storage)?;
+            trace!("Current round height in storage is {}", current_round_height_in_storage);
+
+            // Fetch the current round height from coordinator self.
+            let current_round_height = self.state.current_round_height();
+            trace!("Current round height in coordinator state is {}", current_round_height);
+
+            // Check that the current round height matches in storage and self.
+            match current_round_height_in_storage == current_round_height {
+                true => current_round_height,
+                false => return Err(CoordinatorError::RoundHeightMismatch),
+            }
+        };
+
+        // Check that the current round is finished by contributors and verifiers.
+        if !self.state.is_current_round_finished() {
+            error!("Coordinator state shows round {} is not finished", current_round_height);
+            return Err(CoordinatorError::RoundNotReady);
+        }
+
+        // Check that the current round has not been aggregated.
+        if self.state.is_current_round_aggregated() {
+            error!("Coordinator state shows round {} was aggregated", current_round_height);
+            return Err(CoordinatorError::RoundAlreadyAggregated);
+        }
+
+        // Update the coordinator state to set the start of aggregation for the current round.
+        self.state.aggregating_current_round(self.time.as_ref())?;
+
+        // Check if this is round 0, as coordinator may safely skip aggregation.
+        if current_round_height == 0 {
+            // Set the current round as aggregated in coordinator self.
+            self.state.aggregated_current_round(self.time.as_ref())?;
+
+            debug!("Coordinator is safely skipping aggregation for round 0");
+            return Ok(());
+        }
+
+        // Attempt to aggregate the current round.
+        trace!("Trying to aggregate round {}", current_round_height);
+        match self.aggregate_contributions() {
+            // Case 1a - Coordinator aggregated the current round.
+            Ok(()) => {
+                info!("Coordinator has aggregated round {}", current_round_height);
+
+                // Set the current round as aggregated in coordinator self.
+                self.state.aggregated_current_round(self.time.as_ref())?;
+
+                // Check that the current round has now been aggregated.
+                if !self.state.is_current_round_aggregated() {
+                    error!("Coordinator state says round {} isn't aggregated", current_round_height);
+                    return Err(CoordinatorError::RoundAggregationFailed);
+                }
+
+                Ok(())
+            }
+            // Case 1b - Coordinator failed to aggregate the current round.
+            Err(error) => {
+                error!("Coordinator failed to aggregate the current round\n{}", error);
+
+                // Rollback the current round aggregation.
+                self.state.rollback_aggregating_current_round()?;
+
+                Err(error)
+            }
+        }
+    }
+
+    ///
+    /// Attempts to advance the ceremony to the next round.
+    ///
+    #[tracing::instrument(skip(self, started_at))]
+    pub fn try_advance(&mut self, started_at: OffsetDateTime) -> Result<u64, CoordinatorError> {
+        tracing::debug!("Trying to advance to the next round.");
+
+        // Check that the current round height matches in storage and self.
+        let current_round_height = {
+            // Fetch the current round height from storage.
+            let current_round_height_in_storage = Self::load_current_round_height(&self.storage)?;
+            debug!("Current round height in storage is {}", current_round_height_in_storage);
+
+            // Fetch the current round height from coordinator self.
+            let current_round_height = self.state.current_round_height();
+            debug!("Current round height in coordinator state is {}", current_round_height);
+
+            // Check that the current round height matches in storage and self.
+            if current_round_height_in_storage == current_round_height {
+                current_round_height
+            } else {
+                tracing::error!(
+                    "Round height in storage ({}) does not match the \
+                    round height in coordinator state ({})",
+                    current_round_height_in_storage,
+                    current_round_height,
+                );
+                return Err(CoordinatorError::RoundHeightMismatch);
+            }
+        };
+
+        // Check that the current round is finished.
+        if !self.state.is_current_round_finished() {
+            return Err(CoordinatorError::CurrentRoundNotFinished);
+        }
+
+        // Check that the current round is aggregated, if this is not round 0.
+        if current_round_height > 0 && !self.state.is_current_round_aggregated() {
+            return Err(CoordinatorError::CurrentRoundNotAggregated);
+        }
+
+        // Attempt to advance the round.
+        trace!("Running precommit for the next round");
+        let result = match self
+            .state
+            .precommit_next_round(current_round_height + 1, self.time.as_ref())
+        {
+            // Case 1 - Precommit succeed, attempt to advance the round.
+            Ok(contributors) => {
+                trace!("Trying to add advance to the next round");
+                match self.next_round(started_at, contributors) {
+                    // Case 1a - Coordinator advanced the round.
+                    Ok(next_round_height) => {
+                        // If success, update coordinator state to next round.
+                        info!("Coordinator has advanced to round {}", next_round_height);
+                        self.state.commit_next_round();
+                        self.emit(CeremonyEvent::RoundAdvanced {
+                            round_height: next_round_height,
+                        });
+                        if self.state.number_of_queue_contributors() == 0 {
+                            self.emit(CeremonyEvent::QueueEmpty {
+                                round_height: next_round_height,
+                            });
+                        }
+
+                        // The old rounds are pruned before the completed one is compressed, which may be one of them
+                        if let Err(error) = self.prune_rounds() {
+                            warn!("Could not prune the files of the old rounds: {}", error);
+                        }
+                        // Only the files of the new round are downloaded from now on
+                        if let Err(error) = self.storage.prune_compressed_copies(next_round_height) {
+                            warn!("Could not prune the compressed copies of the old rounds: {}", error);
+                        }
+                        // The files of the completed round are only read again for the transcript
+                        match self.storage.compress_round(current_round_height) {
+                            Ok(compression) => self.maintenance.push(StorageMaintenance {
+                                compression,
+                                archive: self.storage.pruned_archive(),
+                            }),
+                            Err(error) => warn!(
+                                "Could not compress the files of round {}: {}",
+                                current_round_height, error
+                            ),
+                        }
+                        Ok(next_round_height)
+                    }
+                    // Case 1b - Coordinator failed to advance the round.
+                    Err(error) => {
+                        // If failed, rollback coordinator state to the current round.
+                        error!("Coordinator failed to advance the next round, performing state rollback");
+                        self.state.rollback_next_round(self.time.as_ref());
+                        error!("{}", error);
+                        Err(error)
+                    }
+                }
+            }
+            // Case 2 - Precommit failed, roll back precommit.
+            Err(error) => {
+                // If failed, rollback coordinator state to the current round.
+                error!("Precommit for next round has failed, performing state rollback");
+                self.state.rollback_next_round(self.time.as_ref());
+                error!("{}", error);
+                Err(error)
+            }
+        };
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        result
+    }
+
+    ///
+    /// Returns the chunk ID from the given contribution file locator path.
+    ///
+    /// If the given locator path is invalid, returns a `CoordinatorError`.
+    ///
+    #[inline]
+    pub fn contribution_locator_to_chunk_id(&self, locator_path: &LocatorPath) -> Result<u64, CoordinatorError> {
+        // Fetch the chunk ID corresponding to the given locator path.
+        let locator = self.storage.to_locator(&locator_path)?;
+        match &locator {
+            Locator::ContributionFile(contribution_locator) => match self.storage.exists(&locator) {
+                true => Ok(contribution_locator.chunk_id()),
+                false => Err(CoordinatorError::ContributionLocatorMissing),
+            },
+            _ => Err(CoordinatorError::ContributionLocatorIncorrect),
+        }
+    }
+
+    /// Convert a locator to a path string using the coordinator's
+    /// storage layer.
+    pub fn locator_to_path(&self, locator: Locator) -> Result<LocatorPath, CoordinatorError> {
+        self.storage.to_path(&locator)
+    }
+
+    ///
+    /// Attempts to acquire the lock for a given chunk ID and
+    /// participant.
+    ///
+    /// **Important**: The returned next contribution locator does not
+    /// always match the current task being performed. If it is the
+    /// final verification for a chunk, the a [Participant::Verifier]
+    /// will receive a locator to the contribution 0 of the same chunk
+    /// in the next round.
+    ///
+    /// On failure, this function returns a `CoordinatorError`.
+    ///
+    pub(crate) fn try_lock_chunk(
+        &mut self,
+        chunk_id: u64,
+        participant: &Participant,
+    ) -> Result<LockedLocators, CoordinatorError> {
+        // Check that the chunk ID is valid.
+        if chunk_id > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
+        }
+
+        // Fetch the current round height from storage.
+        let current_round_height = Self::load_current_round_height(&self.storage)?;
+        trace!("Current round height from storage is {}", current_round_height);
+
+        // Fetch the current round from storage.
+        let mut round = Self::load_current_round(&self.storage)?;
+
+        // Attempt to acquire the chunk lock for participant.
+        trace!("Preparing to lock chunk {}", chunk_id);
+        let locked_locators = round.try_lock_chunk(&self.environment, &mut self.storage, chunk_id, &participant)?;
+        trace!("Participant {} locked chunk {}", participant, chunk_id);
+
+        // Add the updated round to storage.
+        match self.storage.update(
+            &Locator::RoundState {
+                round_height: current_round_height,
+            },
+            Object::RoundState(round),
+        ) {
+            Ok(_) => {
+                debug!("{} acquired lock on chunk {}", participant, chunk_id);
+                Ok(locked_locators)
+            }
+            _ => Err(CoordinatorError::StorageUpdateFailed),
+        }
+    }
+
+    ///
+    /// Attempts to add a contribution for a given chunk ID from a given participant.
+    ///
+    /// This function checks that the participant is a contributor and has uploaded
+    /// a valid response file to the coordinator. The coordinator sanity checks
+    /// (however, does not verify) the contribution before accepting the response file.
+    ///
+    /// On success, this function releases the chunk lock from the contributor and
+    /// returns the response file locator and contribution ID of the response file.
+    ///
+    /// On failure, it returns a `CoordinatorError`.
+    ///
+    #[inline]
+    pub(crate) fn add_contribution(
+        &mut self,
+        chunk_id: u64,
+        participant: &Participant,
+    ) -> Result<(ContributionLocator, u64), CoordinatorError> {
+        debug!("Adding contribution from {} to chunk {}", participant, chunk_id);
+
+        // Fetch the current round height from storage.
+        let current_round_height = Self::load_current_round_height(&self.storage)?;
+        trace!("Current round height from storage is {}", current_round_height);
+
+        // Fetch the current round from storage.
+        let mut round = Self::load_current_round(&self.storage)?;
+        {
+            // Check that the participant is an authorized contributor to the current round.
+            if !round.is_contributor(participant) {
+                error!("{} is unauthorized to contribute to chunk {})", participant, chunk_id);
+                return Err(CoordinatorError::UnauthorizedChunkContributor);
+            }
+
+            // Check that the chunk lock is currently held by this contributor.
+            if !round.is_chunk_locked_by(chunk_id, participant) {
+                error!("{} should have lock on chunk {} but does not", participant, chunk_id);
+                return Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant);
+            }
+        }
+
+        // Fetch the expected number of contributions for the current round.
+        let expected_num_contributions = round.expected_number_of_contributions();
+        // Fetch the chunk corresponding to the given chunk ID.
+        let chunk = round.chunk(chunk_id)?;
+        // Fetch the next contribution ID of the chunk.
+        let contribution_id = chunk.next_contribution_id(expected_num_contributions)?;
+
+        // Check that the next contribution ID is one above the current contribution ID.
+        if !chunk.is_next_contribution_id(contribution_id, expected_num_contributions) {
+            return Err(CoordinatorError::ContributionIdMismatch);
+        }
+
+        // Fetch the challenge, response, and contribution file signature locators.
+        let challenge_file_locator = Locator::ContributionFile(ContributionLocator::new(
+            current_round_height,
+            chunk_id,
+            chunk.current_contribution_id(),
+            true,
+        ));
+        let response_file_locator = ContributionLocator::new(current_round_height, chunk_id, contribution_id, false);
+        let contribution_file_signature_locator = Locator::ContributionFileSignature(
+            ContributionSignatureLocator::new(current_round_height, chunk_id, contribution_id, false),
+        );
+
+        // Check the challenge-response hash chain.
+        let (challenge_hash, response_hash) = {
+            // Compute the challenge hash using the challenge file.
+            let challenge_reader = self.storage.reader(&challenge_file_locator)?;
+            let challenge_hash = calculate_hash(challenge_reader.as_ref());
+            info!(
+                "Challenge is located in {}",
+                self.storage.to_path(&challenge_file_locator)?
+            );
+
+            debug!("Challenge is {}", pretty_hash!(&challenge_reader));
+            debug!("Challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
+
+            // Compute the response hash.
+            let response_reader = self.storage.reader(&Locator::ContributionFile(response_file_locator))?;
+            let response_hash = calculate_hash(response_reader.as_ref());
+            info!(
+                "Response is located in {}",
+                self.storage
+                    .to_path(&Locator::ContributionFile(response_file_locator))?
+            );
+            debug!("Response is {}", pretty_hash!(&response_reader));
+            info!("Response hash is {}", pretty_hash!(&response_hash.as_slice()));
+
+            // Fetch the challenge hash from the response file.
+            let challenge_hash_in_response = &response_reader
+                .get(0..64)
+                .ok_or(CoordinatorError::StorageReaderFailed)?[..];
+            let pretty_hash = pretty_hash!(&challenge_hash_in_response);
+
+            // Check the starting hash in the response file is based on the challenge.
+            info!("The challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
+            info!("The challenge hash in response file is {}", pretty_hash);
+            if challenge_hash_in_response != challenge_hash.as_slice() {
+                error!("Challenge hash in response file does not match the expected challenge hash.");
+                return Err(CoordinatorError::ContributionHashMismatch);
+            }
+
+            (challenge_hash, response_hash)
+        };
+
+        // Check the challenge-response contribution signature.
+        {
+            // Fetch the stored contribution file signature.
+            let contribution_file_signature: ContributionFileSignature =
+                serde_json::from_slice(&*self.storage.reader(&contribution_file_signature_locator)?)?;
+
+            // Check that the contribution file signature is valid.
+            self.check_contribution_signature(participant, &contribution_file_signature)?;
+
+            // Check that the contribution file signature challenge hash is correct.
+            if hex::decode(contribution_file_signature.get_challenge_hash())? != challenge_hash.as_slice() {
+                error!("The signed challenge hash does not match the expected challenge hash.");
+                return Err(CoordinatorError::ContributionHashMismatch);
+            }
+
+            // Check that the contribution file signature response hash is correct.
+            if hex::decode(contribution_file_signature.get_response_hash())? != response_hash.as_slice() {
+                error!("The signed response hash does not match the expected response hash.");
+                return Err(CoordinatorError::ContributionHashMismatch);
+            }
+
+            // Check that the contribution file signature next challenge hash does not exist.
+            if contribution_file_signature.get_next_challenge_hash().is_some() {
+                error!("The signed next challenge hash should not exist");
+                return Err(CoordinatorError::NextChallengeHashAlreadyExists);
+            }
+        }
+
+        // Add the contribution response to the current chunk.
+        round.chunk_mut(chunk_id)?.add_contribution(
+            contribution_id,
+            participant,
+            self.storage
+                .to_path(&Locator::ContributionFile(response_file_locator))?,
+            self.storage.to_path(&contribution_file_signature_locator)?,
+        )?;
+
+        // Add the updated round to storage.
+        match self.storage.update(
+            &Locator::RoundState {
+                round_height: current_round_height,
+            },
+            Object::RoundState(round),
+        ) {
+            Ok(_) => {
+                debug!("Updated round {} in storage", current_round_height);
+                debug!("{} added a contribution to chunk {}", participant, chunk_id);
+                Ok((response_file_locator, contribution_id))
+            }
+            _ => Err(CoordinatorError::StorageUpdateFailed),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_challenge(
+        &self,
+        round_height: u64,
+        chunk_id: u64,
+        contribution_id: u64,
+        is_verified: bool,
+    ) -> Result<Vec<u8>, CoordinatorError> {
+        // Fetch the challenge, response, and contribution file signature locators.
+        let challenge_file_locator = Locator::ContributionFile(ContributionLocator::new(
+            round_height,
+            chunk_id,
+            contribution_id,
+            is_verified,
+        ));
+        // Get the challenge from the challenge file locator
+        let challenge_reader = self.storage.reader(&challenge_file_locator)?;
+
+        Ok(challenge_reader.to_vec())
+    }
+
+    /// Returns the path on disk of the challenge of the given round, together with its [`hex`] encoded
+    /// Blake2b-512 hash computed without loading the challenge in memory.
+    pub(crate) fn challenge_file(&self, round_height: u64) -> Result<(PathBuf, String), CoordinatorError> {
+        // Since we don't chunk the parameters, the challenge is always the first verified contribution of the round
+        self.hashed_contribution_file(ContributionLocator::new(round_height, 0, 0, true))
+    }
+
+    /// Returns the path on disk of the contribution file at the given locator, together with its [`hex`] encoded
+    /// Blake2b-512 hash, computed only if the file isn't stored under it.
+    fn hashed_contribution_file(&self, locator: ContributionLocator) -> Result<(PathBuf, String), CoordinatorError> {
+        let locator = Locator::ContributionFile(locator);
+        if !self.storage.exists(&locator) {
+            return Err(CoordinatorError::StorageLocatorMissing);
+        }
+
+        let path = self.storage.to_path(&locator)?.as_ref().to_path_buf();
+        if let Some(hash) = self.storage.content_hash(&locator)? {
+            return Ok((path, hash));
+        }
+        let mut hasher = Blake2b512::new();
+        io::copy(&mut self.storage.open(&locator)?, &mut hasher)?;
+
+        Ok((path, hex::encode(hasher.finalize())))
+    }
+
+    /// Opens the contribution file at the given path, returned by [Self::challenge_file] or
+    /// [Self::verification_file], to be streamed to a client, compressed with `encoding` if any.
+    pub(crate) fn serve_file(
+        &self,
+        path: &Path,
+        encoding: Option<ContentEncoding>,
+    ) -> Result<ServedFile, CoordinatorError> {
+        self.storage.serve_file(path, encoding)
+    }
+
+    ///
+    /// Locks the next contribution to verify for the given external verifier, if any.
+    ///
+    #[tracing::instrument(skip(self, verifier), fields(verifier = %verifier))]
+    pub fn lock_verification_task(
+        &mut self,
+        verifier: &Participant,
+    ) -> Result<Option<VerificationTask>, CoordinatorError> {
+        let round_height = self.current_round_height()?;
+        let task = match self.state.lock_verification_task(&verifier.address()) {
+            Some(task) => task,
+            None => return Ok(None),
+        };
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(Some(VerificationTask {
+            round_height,
+            chunk_id: task.chunk_id(),
+            contribution_id: task.contribution_id(),
+        }))
+    }
+
+    /// Returns the path on disk and the hash of the challenge, or of the response if `response` is set, of the task
+    /// locked by the given external verifier.
+    pub(crate) fn verification_file(
+        &self,
+        verifier: &Participant,
+        response: bool,
+    ) -> Result<(PathBuf, String), CoordinatorError> {
+        let round_height = self.current_round_height()?;
+        let task = self
+            .state
+            .locked_verification_task(&verifier.address())
+            .ok_or(CoordinatorError::ChunkNotLockedOrByWrongParticipant)?;
+
+        let locator = match response {
+            true => ContributionLocator::new(round_height, task.chunk_id(), task.contribution_id(), false),
+            false => ContributionLocator::new(round_height, task.chunk_id(), task.contribution_id() - 1, true),
+        };
+
+        self.hashed_contribution_file(locator)
+    }
+
+    ///
+    /// Records the signed result of the given external verifier on the task it locked. The result must refer
+    /// to the response currently stored by the coordinator.
+    ///
+    #[tracing::instrument(skip(self, verifier, result), fields(verifier = %verifier))]
+    pub fn add_verification_result(
+        &mut self,
+        verifier: &Participant,
+        result: &VerificationResult,
+    ) -> Result<(), CoordinatorError> {
+        let round_height = self.current_round_height()?;
+        if result.task.round_height != round_height {
+            return Err(CoordinatorError::RoundHeightMismatch);
+        }
+
+        let message = verification_result_message(&result.task, &result.response_hash, result.verified);
+        if !verify_hex_signature(&verifier.address(), &message, &result.signature) {
+            return Err(CoordinatorError::ContributorSignatureInvalid);
+        }
+
+        let (_, response_hash) = self.verification_file(verifier, true)?;
+        if response_hash != result.response_hash {
+            return Err(CoordinatorError::ContributionHashMismatch);
+        }
+
+        let task = Task::new(result.task.chunk_id, result.task.contribution_id);
+        self.state
+            .add_external_verification(&verifier.address(), &task, result.verified)?;
+        self.storage.log_transition(StateTransition::VerificationResult {
+            verifier: verifier.address(),
+            chunk_id: task.chunk_id(),
+            contribution_id: task.contribution_id(),
+            verified: result.verified,
+        })?;
+
+        // Save the coordinator state in storage.
+        self.save_state()
+    }
+
+    ///
+    /// Returns the agreement of the external verifiers on the given task.
+    ///
+    pub fn external_verification_status(&self, task: &Task) -> ExternalVerificationStatus {
+        self.state.external_verification_status(task)
+    }
+
+    /// Writes the bytes of a contribution to storage at the appropriate file
+    /// locator.
+    pub(crate) fn write_contribution<T>(
+        &mut self,
+        contribution_locator: ContributionLocator,
+        contribution: T,
+    ) -> Result<(), CoordinatorError>
+    where
+        T: Into<Vec<u8>>,
+    {
+        let contribution = contribution.into();
+        Self::check_contribution_size(&self.environment, &contribution_locator, contribution.len() as u64)?;
+        self.check_storage_space()?;
+
+        // Can use update instead of insert because the path is already initialized by other functions
+        self.storage.update(
+            &Locator::ContributionFile(contribution_locator),
+            Object::ContributionFile(contribution),
+        )
+    }
+
+    /// Writes the contribution stored in the file at the given path to storage at the appropriate locator,
+    /// without loading it in memory.
+    pub(crate) fn write_contribution_from_file(
+        &mut self,
+        contribution_locator: ContributionLocator,
+        path: &Path,
+    ) -> Result<(), CoordinatorError> {
+        Self::check_contribution_size(&self.environment, &contribution_locator, fs::metadata(path)?.len())?;
+        self.check_storage_space()?;
+        self.storage.update_from_file(&Locator::ContributionFile(contribution_locator), path)
+    }
+
+    /// Checks that an uploaded contribution has the size expected at the given locator, for a truncated or padded
+    /// file to be rejected before it's stored.
+    fn check_contribution_size(
+        environment: &Environment,
+        contribution_locator: &ContributionLocator,
+        size: u64,
+    ) -> Result<(), CoordinatorError> {
+        let expected_size = Object::anoma_contribution_file_size(
+            environment,
+            contribution_locator.round_height(),
+            contribution_locator.contribution_id(),
+        );
+        if size != expected_size {
+            error!("Contribution file size should be {} but found {}", expected_size, size);
+            return Err(CoordinatorError::ContributionFileSizeMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that an uploaded contribution has the size expected at the given locator and the response hash signed in
+    /// its contribution file signature, for a malformed upload to be rejected before anything is written to storage.
+    pub(crate) fn validate_contribution(
+        &self,
+        contribution_locator: &ContributionLocator,
+        contribution: &mut dyn io::Read,
+        signature: &ContributionFileSignature,
+    ) -> Result<(), CoordinatorError> {
+        let size = Self::check_response_hash(contribution, signature)?;
+        Self::check_contribution_size(&self.environment, contribution_locator, size)
+    }
+
+    /// Checks that the contribution read from `contribution` has the response hash signed in the contribution file
+    /// signature, returning its size.
+    fn check_response_hash(
+        contribution: &mut dyn io::Read,
+        signature: &ContributionFileSignature,
+    ) -> Result<u64, CoordinatorError> {
+        let mut hasher = Blake2b512::new();
+        let size = io::copy(contribution, &mut hasher)?;
+        if hex::encode(hasher.finalize()) != signature.get_response_hash() {
+            error!("The signed response hash does not match the hash of the uploaded contribution");
+            return Err(CoordinatorError::ContributionHashMismatch);
+        }
+
+        Ok(size)
+    }
+
+    /// Returns `true` if the storage is reachable and holds the state of the ceremony, for the readiness probe.
+    pub fn storage_reachable(&self) -> bool {
+        self.storage.available_space().is_ok() && self.storage.exists(&Locator::CoordinatorState)
+    }
+
+    /// Checks that the storage has more free space than the low-water mark of the environment, if any, for a new file
+    /// not to be left partially written. Publishes a [CeremonyEvent::StorageLow] alert otherwise.
+    pub(crate) fn check_storage_space(&self) -> Result<(), CoordinatorError> {
+        let low_water_mark = self.environment.storage_low_water_mark();
+        if low_water_mark == 0 {
+            return Ok(());
+        }
+
+        let available_bytes = self.storage.available_space()?;
+        if available_bytes < low_water_mark {
+            warn!(
+                "Only {} bytes are available in storage, below the low-water mark of {} bytes",
+                available_bytes, low_water_mark
+            );
+            self.emit(CeremonyEvent::StorageLow { available_bytes });
+            return Err(CoordinatorError::StorageSpaceLow);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the path of the file where the parts of the resumable upload of the contribution of the
+    /// given participant to the current round are assembled, together with the expected size of the contribution.
+    pub(crate) fn upload_session(&self, participant: &Participant) -> Result<(PathBuf, u64), CoordinatorError> {
+        self.check_storage_space()?;
+        let round_height = self.current_round_height()?;
+        fs::create_dir_all(self.uploads_directory())?;
+
+        Ok((
+            self.upload_session_path(round_height, participant),
+            Object::anoma_contribution_file_size(&self.environment, round_height, 1),
+        ))
+    }
+
+    /// Returns the directory where the resumable uploads are assembled.
+    fn uploads_directory(&self) -> PathBuf {
+        Path::new(self.environment.local_base_directory()).join("uploads")
+    }
+
+    /// Returns the path of the file of the resumable upload of the contribution of the given participant to the
+    /// given round.
+    fn upload_session_path(&self, round_height: u64, participant: &Participant) -> PathBuf {
+        self.uploads_directory()
+            .join(format!("round_{}_{}.part", round_height, participant.address()))
+    }
+
+    /// Checks that the resumable upload of the contribution of the given participant is complete and that it
+    /// matches the response hash of the signature. Returns the path of the assembled contribution.
+    ///
+    /// Returns `CoordinatorError::ContributionUploadIncomplete` if parts are still missing, the upload being resumable.
+    pub(crate) fn verify_upload_session(
+        &self,
+        participant: &Participant,
+        signature: &ContributionFileSignature,
+    ) -> Result<PathBuf, CoordinatorError> {
+        let (path, size) = self.upload_session(participant)?;
+        if fs::metadata(&path).map_or(0, |metadata| metadata.len()) != size {
+            return Err(CoordinatorError::ContributionUploadIncomplete);
+        }
+
+        Self::check_response_hash(&mut fs::File::open(&path)?, signature)?;
+
+        Ok(path)
+    }
+
+    /// Removes the file of the resumable upload of the contribution of the given participant to the current round, if
+    /// any, for the upload to restart from the first byte.
+    pub(crate) fn remove_upload_session(&self, participant: &Participant) -> Result<(), CoordinatorError> {
+        let path = self.upload_session_path(self.current_round_height()?, participant);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the files of the resumable uploads which can't be completed anymore: the ones of the previous rounds and
+    /// of the participants who are no longer contributing, dropped or whose lock has expired.
+    fn remove_stale_upload_sessions(&self) -> Result<(), CoordinatorError> {
+        let uploads_directory = self.uploads_directory();
+        if !uploads_directory.exists() {
+            return Ok(());
+        }
+
+        let round_height = self.current_round_height()?;
+        let sessions: HashSet<PathBuf> = self
+            .current_contributors()
+            .iter()
+            .map(|(participant, _)| self.upload_session_path(round_height, participant))
+            .collect();
+        for entry in fs::read_dir(&uploads_directory)? {
+            let path = entry?.path();
+            if !sessions.contains(&path) {
+                debug!("Removing the stale upload session {}", path.display());
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the contribution metadata to storage at the appropriate locator.
+    pub(crate) fn write_contribution_info(
+        &mut self,
+        contribution_info: ContributionInfo,
+    ) -> Result<(), CoordinatorError> {
+        self.storage.insert(
+            Locator::ContributionInfoFile {
+                round_height: contribution_info.ceremony_round,
+            },
+            Object::ContributionInfoFile(contribution_info),
+        )
+    }
+
+    /// Updates the contribution attestation and summary to storage at the appropriate locator.
+    pub(crate) fn update_contribution_info_attestation(
+        &mut self,
+        round: u64,
+        attestation: String,
+    ) -> Result<(), CoordinatorError> {
+        self.update_contribution_info(round, |info| ContributionInfo {
+            attestation: Some(attestation),
+            attestation_verified: false,
+            ..info
+        })
+    }
+
+    /// Records the signed attestation statement of the contribution at the given round, both in the contribution info
+    /// and in the summary. The url of the statement, if any, replaces the public attestation to be verified.
+    pub(crate) fn update_contribution_info_attestation_statement(
         &mut self,
-        chunk_id: u64,
-        participant: &Participant,
-    ) -> Result<(ContributionLocator, u64), CoordinatorError> {
-        debug!("Adding contribution from {} to chunk {}", participant, chunk_id);
+        round: u64,
+        attestation_statement: AttestationStatement,
+    ) -> Result<(), CoordinatorError> {
+        self.update_contribution_info(round, |info| ContributionInfo {
+            attestation: attestation_statement.url.clone().or(info.attestation),
+            attestation_verified: attestation_statement.url.is_none() && info.attestation_verified,
+            attestation_statement: Some(attestation_statement),
+            ..info
+        })
+    }
 
-        // Fetch the current round height from storage.
-        let current_round_height = Self::load_current_round_height(&self.storage)?;
-        trace!("Current round height from storage is {}", current_round_height);
+    /// Binds the Namada address to the contribution at the given round, both in the contribution info and in the summary.
+    pub(crate) fn update_contribution_info_namada_address(
+        &mut self,
+        round: u64,
+        binding: NamadaAddressBinding,
+    ) -> Result<(), CoordinatorError> {
+        self.update_contribution_info(round, |info| ContributionInfo {
+            namada_address: Some(binding),
+            ..info
+        })
+    }
 
-        // Fetch the current round from storage.
-        let mut round = Self::load_current_round(&self.storage)?;
+    /// Records the verified identity proof of the contribution at the given round, both in the contribution info and in the summary.
+    pub(crate) fn update_contribution_info_identity_proof(
+        &mut self,
+        round: u64,
+        identity_proof: IdentityProof,
+    ) -> Result<(), CoordinatorError> {
+        self.update_contribution_info(round, |info| ContributionInfo {
+            identity_proof: Some(identity_proof),
+            ..info
+        })
+    }
+
+    /// Records the timestamp proof of the contribution at the given round, both in the contribution info and in the summary.
+    pub(crate) fn update_contribution_info_timestamp_proof(
+        &mut self,
+        round: u64,
+        timestamp_proof: TimestampProof,
+    ) -> Result<(), CoordinatorError> {
+        self.update_contribution_info(round, |info| ContributionInfo {
+            timestamp_proof: Some(timestamp_proof),
+            ..info
+        })
+    }
+
+    /// Marks the attestation of the contribution at the given round as verified, both in the contribution info and in the summary.
+    pub(crate) fn set_contribution_attestation_verified(&mut self, round: u64) -> Result<(), CoordinatorError> {
+        self.update_contribution_info(round, |info| ContributionInfo {
+            attestation_verified: true,
+            ..info
+        })
+    }
+
+    /// Applies the provided update to the contribution info of the given round and persists the result
+    /// to both the contribution info file and the summary.
+    fn update_contribution_info<F>(&mut self, round: u64, update: F) -> Result<(), CoordinatorError>
+    where
+        F: FnOnce(ContributionInfo) -> ContributionInfo,
+    {
+        // Retrieve current file to update
+        let updated_info = match self
+            .storage
+            .get(&Locator::ContributionInfoFile { round_height: round })?
         {
-            // Check that the participant is an authorized contributor to the current round.
-            if !round.is_contributor(participant) {
-                error!("{} is unauthorized to contribute to chunk {})", participant, chunk_id);
-                return Err(CoordinatorError::UnauthorizedChunkContributor);
-            }
+            Object::ContributionInfoFile(info) => update(info),
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
 
-            // Check that the chunk lock is currently held by this contributor.
-            if !round.is_chunk_locked_by(chunk_id, participant) {
-                error!("{} should have lock on chunk {} but does not", participant, chunk_id);
-                return Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant);
-            }
-        }
+        // Persist update to storage
+        self.storage.update(
+            &Locator::ContributionInfoFile { round_height: round },
+            Object::ContributionInfoFile(updated_info.clone()),
+        )?;
 
-        // Fetch the expected number of contributions for the current round.
-        let expected_num_contributions = round.expected_number_of_contributions();
-        // Fetch the chunk corresponding to the given chunk ID.
-        let chunk = round.chunk(chunk_id)?;
-        // Fetch the next contribution ID of the chunk.
-        let contribution_id = chunk.next_contribution_id(expected_num_contributions)?;
+        // Update the summary
+        let mut summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary,
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
 
-        // Check that the next contribution ID is one above the current contribution ID.
-        if !chunk.is_next_contribution_id(contribution_id, expected_num_contributions) {
-            return Err(CoordinatorError::ContributionIdMismatch);
-        }
+        match summary.get_mut((round - 1) as usize) {
+            Some(t) => *t = updated_info.into(),
+            None => return Err(CoordinatorError::StorageFailed),
+        };
 
-        // Fetch the challenge, response, and contribution file signature locators.
-        let challenge_file_locator = Locator::ContributionFile(ContributionLocator::new(
-            current_round_height,
-            chunk_id,
-            chunk.current_contribution_id(),
-            true,
-        ));
-        let response_file_locator = ContributionLocator::new(current_round_height, chunk_id, contribution_id, false);
-        let contribution_file_signature_locator = Locator::ContributionFileSignature(
-            ContributionSignatureLocator::new(current_round_height, chunk_id, contribution_id, false),
-        );
+        // Persist update to storage
+        self.storage.update(
+            &Locator::ContributionsInfoSummary,
+            Object::ContributionsInfoSummary(summary),
+        )
+    }
 
-        // Check the challenge-response hash chain.
-        let (challenge_hash, response_hash) = {
-            // Compute the challenge hash using the challenge file.
-            let challenge_reader = self.storage.reader(&challenge_file_locator)?;
-            let challenge_hash = calculate_hash(challenge_reader.as_ref());
-            info!(
-                "Challenge is located in {}",
-                self.storage.to_path(&challenge_file_locator)?
-            );
+    /// Retrieves the contribution metadata of the contributor with the given public key, if any.
+    pub(crate) fn get_contribution_info(&self, public_key: &str) -> Result<Option<ContributionInfo>, CoordinatorError> {
+        // Find the round of the contribution from the summary
+        let summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary,
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
 
-            debug!("Challenge is {}", pretty_hash!(&challenge_reader));
-            debug!("Challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
+        let round_height = match summary.iter().find(|info| info.public_key() == public_key) {
+            Some(info) => info.ceremony_round(),
+            None => return Ok(None),
+        };
 
-            // Compute the response hash.
-            let response_reader = self.storage.reader(&Locator::ContributionFile(response_file_locator))?;
-            let response_hash = calculate_hash(response_reader.as_ref());
-            info!(
-                "Response is located in {}",
-                self.storage
-                    .to_path(&Locator::ContributionFile(response_file_locator))?
-            );
-            debug!("Response is {}", pretty_hash!(&response_reader));
-            info!("Response hash is {}", pretty_hash!(&response_hash.as_slice()));
+        match self.storage.get(&Locator::ContributionInfoFile { round_height })? {
+            Object::ContributionInfoFile(info) => Ok(Some(info)),
+            _ => Err(CoordinatorError::StorageFailed),
+        }
+    }
 
-            // Fetch the challenge hash from the response file.
-            let challenge_hash_in_response = &response_reader
-                .get(0..64)
-                .ok_or(CoordinatorError::StorageReaderFailed)?[..];
-            let pretty_hash = pretty_hash!(&challenge_hash_in_response);
+    ///
+    /// Returns the given page, numbered from 1, of the contributors of the ceremony ordered by
+    /// round height, with their chosen names and the hashes of their contributions.
+    ///
+    pub fn contributors_page(&self, page: u64, per_page: u64) -> Result<ContributorsPage, CoordinatorError> {
+        let summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary,
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
 
-            // Check the starting hash in the response file is based on the challenge.
-            info!("The challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
-            info!("The challenge hash in response file is {}", pretty_hash);
-            if challenge_hash_in_response != challenge_hash.as_slice() {
-                error!("Challenge hash in response file does not match the expected challenge hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
-            }
+        let contributors = summary
+            .iter()
+            .skip(page.saturating_sub(1).saturating_mul(per_page) as usize)
+            .take(per_page as usize)
+            .map(|info| PublicContributor {
+                public_key: info.public_key().to_owned(),
+                display_name: info.full_name().map(str::to_owned),
+                round_height: info.ceremony_round(),
+                contribution_hash: info.contribution_hash().to_owned(),
+            })
+            .collect();
+
+        Ok(ContributorsPage {
+            page,
+            per_page,
+            total: summary.len() as u64,
+            contributors,
+        })
+    }
 
-            (challenge_hash, response_hash)
+    ///
+    /// Writes to `writer` a zip archive with the artifacts of the contribution of the given contributor:
+    /// 1. Its summary, as `contribution.json`
+    /// 2. The hash of the challenge it contributed to, as `challenge.hash`
+    /// 3. The response file and its signature by the contributor
+    /// 4. The signature of the verifier, as `verification.signature`, if the contribution has been verified
+    ///
+    /// Returns `None` if the contributor didn't contribute to the ceremony.
+    ///
+    pub(crate) fn write_contributor_bundle<W: Write + Seek>(
+        &self,
+        public_key: &str,
+        writer: W,
+    ) -> Result<Option<W>, CoordinatorError> {
+        let summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary,
+            _ => return Err(CoordinatorError::StorageFailed),
         };
 
-        // Check the challenge-response contribution signature.
-        {
-            // Fetch the stored contribution file signature.
-            let contribution_file_signature: ContributionFileSignature =
-                serde_json::from_slice(&*self.storage.reader(&contribution_file_signature_locator)?)?;
+        let info = match summary.into_iter().find(|info| info.public_key() == public_key) {
+            Some(info) => info,
+            None => return Ok(None),
+        };
+        let round_height = info.ceremony_round();
 
-            // Check that the contribution file signature is valid.
-            let address = &participant.to_string();
+        let challenge = Locator::ContributionFile(ContributionLocator::new(round_height, 0, 0, true));
+        let response = Locator::ContributionFile(ContributionLocator::new(round_height, 0, 1, false));
+        let response_signature =
+            Locator::ContributionFileSignature(ContributionSignatureLocator::new(round_height, 0, 1, false));
+        let verification_signature =
+            Locator::ContributionFileSignature(ContributionSignatureLocator::new(round_height, 0, 1, true));
 
-            let address = address
-                .split(".")
-                .next()
-                .expect("splitting a string should yield at least one item");
+        // The response files are already compressed by nature, store them as they are
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        let mut zip = ZipWriter::new(writer);
+        let zip_error = |e: ZipError| CoordinatorError::Error(e.into());
 
-            if !self.signature.verify(
-                &address,
-                &serde_json::to_string(&contribution_file_signature.get_state())?,
-                contribution_file_signature.get_signature(),
-            ) {
-                error!("Contribution file signature failed to verify for {}", participant);
-                return Err(CoordinatorError::ContributorSignatureInvalid);
-            }
+        zip.start_file("contribution.json", options).map_err(zip_error)?;
+        zip.write_all(&serde_json::to_vec_pretty(&info)?)?;
 
-            // Check that the contribution file signature challenge hash is correct.
-            if hex::decode(contribution_file_signature.get_challenge_hash())? != challenge_hash.as_slice() {
-                error!("The signed challenge hash does not match the expected challenge hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
-            }
+        zip.start_file("challenge.hash", options).map_err(zip_error)?;
+        zip.write_all(hex::encode(calculate_hash(self.storage.reader(&challenge)?.as_ref())).as_bytes())?;
 
-            // Check that the contribution file signature response hash is correct.
-            if hex::decode(contribution_file_signature.get_response_hash())? != response_hash.as_slice() {
-                error!("The signed response hash does not match the expected response hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
-            }
+        for (name, locator) in [("response", &response), ("response.signature", &response_signature)] {
+            zip.start_file(name, options).map_err(zip_error)?;
+            zip.write_all(self.storage.reader(locator)?.as_ref())?;
+        }
 
-            // Check that the contribution file signature next challenge hash does not exist.
-            if contribution_file_signature.get_next_challenge_hash().is_some() {
-                error!("The signed next challenge hash should not exist");
-                return Err(CoordinatorError::NextChallengeHashAlreadyExists);
-            }
+        if self.storage.exists(&verification_signature) {
+            zip.start_file("verification.signature", options).map_err(zip_error)?;
+            zip.write_all(self.storage.reader(&verification_signature)?.as_ref())?;
         }
 
-        // Add the contribution response to the current chunk.
-        round.chunk_mut(chunk_id)?.add_contribution(
-            contribution_id,
-            participant,
-            self.storage
-                .to_path(&Locator::ContributionFile(response_file_locator))?,
-            self.storage.to_path(&contribution_file_signature_locator)?,
-        )?;
+        Ok(Some(zip.finish().map_err(zip_error)?))
+    }
 
-        // Add the updated round to storage.
-        match self.storage.update(
-            &Locator::RoundState {
-                round_height: current_round_height,
-            },
-            Object::RoundState(round),
-        ) {
-            Ok(_) => {
-                debug!("Updated round {} in storage", current_round_height);
-                debug!("{} added a contribution to chunk {}", participant, chunk_id);
-                Ok((response_file_locator, contribution_id))
-            }
-            _ => Err(CoordinatorError::StorageUpdateFailed),
-        }
+    ///
+    /// Returns the compression and the archiving of the files of the rounds completed since the
+    /// last call, for the caller to run them once the coordinator is released, without blocking
+    /// the ceremony.
+    ///
+    pub fn take_maintenance(&mut self) -> Vec<StorageMaintenance> {
+        std::mem::take(&mut self.maintenance)
     }
 
-    #[inline]
-    pub(crate) fn get_challenge(
-        &self,
-        round_height: u64,
-        chunk_id: u64,
-        contribution_id: u64,
-        is_verified: bool,
-    ) -> Result<Vec<u8>, CoordinatorError> {
-        // Fetch the challenge, response, and contribution file signature locators.
-        let challenge_file_locator = Locator::ContributionFile(ContributionLocator::new(
-            round_height,
-            chunk_id,
-            contribution_id,
-            is_verified,
-        ));
-        // Get the challenge from the challenge file locator
-        let challenge_reader = self.storage.reader(&challenge_file_locator)?;
+    ///
+    /// Removes, staging them to be archived if configured, the contribution files of the rounds
+    /// older than the ones retained by the environment, see [Environment::retained_rounds]. The
+    /// states and the signatures of the rounds are kept for the transcript. Returns the number of
+    /// files removed.
+    ///
+    fn prune_rounds(&mut self) -> Result<usize, CoordinatorError> {
+        let retained_rounds = self.environment.retained_rounds();
+        if retained_rounds == 0 {
+            return Ok(0);
+        }
 
-        Ok(challenge_reader.to_vec())
+        let mut pruned = 0;
+        for round_height in 1..=self.first_retained_round()?.saturating_sub(1) {
+            pruned += self.storage.prune_round(round_height)?;
+        }
+
+        Ok(pruned)
     }
 
-    /// Writes the bytes of a contribution to storage at the appropriate file
-    /// locator.
-    pub(crate) fn write_contribution<T>(
-        &mut self,
-        contribution_locator: ContributionLocator,
-        contribution: T,
-    ) -> Result<(), CoordinatorError>
-    where
-        T: Into<Vec<u8>>,
-    {
-        // Can use update instead of insert because the path is already initialized by other functions
-        self.storage.update(
-            &Locator::ContributionFile(contribution_locator),
-            Object::ContributionFile(contribution.into()),
-        )
+    ///
+    /// Returns the height of the oldest round whose contribution files are retained.
+    ///
+    fn first_retained_round(&self) -> Result<u64, CoordinatorError> {
+        let current_round_height = self.current_round_height()?;
+        match self.environment.retained_rounds() {
+            0 => Ok(1),
+            retained_rounds => Ok(current_round_height.saturating_sub(retained_rounds) + 1),
+        }
+    }
+
+    ///
+    /// Collects the artifacts of the completed rounds to be checked by [PendingAudit::run] once the
+    /// coordinator is released: the challenge and response files of each contribution, and the
+    /// signatures of its contributor and of its verifier, read right away. The rounds whose files
+    /// have been pruned, see [Environment::retained_rounds], are skipped.
+    ///
+    pub fn audit_storage(&self) -> Result<PendingAudit, CoordinatorError> {
+        let current_round_height = self.current_round_height()?;
+        let mut rounds = vec![];
+        let mut contributions = vec![];
+
+        for round_height in self.first_retained_round()?..=current_round_height {
+            let round = Self::load_round(&self.storage, round_height)?;
+            if round_height == current_round_height && !round.is_complete() {
+                break;
+            }
+            rounds.push(round_height);
+
+            for chunk in round.chunks() {
+                let chunk_contributions = chunk.get_contributions();
+                for (contribution_id, contribution) in chunk_contributions.iter().filter(|(id, _)| **id > 0) {
+                    let challenge = chunk_contributions
+                        .get(&(contribution_id - 1))
+                        .and_then(|previous| previous.get_verified_location().as_ref());
+                    let verification = match contribution.is_verified() {
+                        true => Some((
+                            self.audited_file(contribution.get_verified_location().as_ref())?,
+                            self.audited_signature(
+                                contribution.get_verifier().as_ref(),
+                                contribution.get_verified_signature_location().as_ref(),
+                            )?,
+                        )),
+                        false => None,
+                    };
+                    contributions.push(AuditedContribution {
+                        round_height,
+                        task: Task::new(chunk.chunk_id(), *contribution_id),
+                        challenge: self.audited_file(challenge)?,
+                        response: self.audited_file(contribution.get_contributed_location().as_ref())?,
+                        contributor: self.audited_signature(
+                            contribution.get_contributor().as_ref(),
+                            contribution.get_contributed_signature_location().as_ref(),
+                        )?,
+                        verification,
+                    });
+                }
+            }
+        }
+
+        Ok(PendingAudit {
+            rounds,
+            contributions,
+            scheme: self.environment.signature_scheme(),
+            signature: self.signature.clone(),
+        })
     }
 
-    /// Writes the contribution metadata to storage at the appropriate locator.
-    pub(crate) fn write_contribution_info(
-        &mut self,
-        contribution_info: ContributionInfo,
-    ) -> Result<(), CoordinatorError> {
-        self.storage.insert(
-            Locator::ContributionInfoFile {
-                round_height: contribution_info.ceremony_round,
-            },
-            Object::ContributionInfoFile(contribution_info),
-        )
+    /// Returns the file at the given location to be audited, if it's in storage.
+    fn audited_file(&self, location: Option<&LocatorPath>) -> Result<AuditedFile, CoordinatorError> {
+        let file = match location.map(|path| self.storage.to_locator(path)) {
+            Some(Ok(locator)) if self.storage.exists(&locator) => Some((self.storage.stored_file(&locator)?, locator)),
+            _ => None,
+        };
+
+        Ok(AuditedFile {
+            path: location.cloned(),
+            file,
+        })
     }
 
-    /// Updates the contribution attestation and summary to storage at the appropriate locator.
-    pub(crate) fn update_contribution_info_attestation(
-        &mut self,
-        round: u64,
-        attestation: String,
-    ) -> Result<(), CoordinatorError> {
-        // Retrieve current file to update
-        let updated_info = match self
-            .storage
-            .get(&Locator::ContributionInfoFile { round_height: round })?
-        {
-            Object::ContributionInfoFile(info) => ContributionInfo {
-                attestation: Some(attestation),
-                ..info
-            },
-            _ => return Err(CoordinatorError::StorageFailed),
+    /// Reads the signature at the given location to be audited, with its signer.
+    fn audited_signature(
+        &self,
+        signer: Option<&Participant>,
+        location: Option<&LocatorPath>,
+    ) -> Result<AuditedSignature, CoordinatorError> {
+        let content = match location.map(|path| self.storage.to_locator(path)) {
+            Some(Ok(locator)) if self.storage.exists(&locator) => {
+                match serde_json::from_slice(self.storage.reader(&locator)?.as_ref()) {
+                    Ok(signature) => SignatureContent::Parsed(signature),
+                    Err(_) => SignatureContent::Malformed,
+                }
+            }
+            _ => SignatureContent::Missing,
         };
 
-        // Persist update to storage
-        self.storage.update(
-            &Locator::ContributionInfoFile { round_height: round },
-            Object::ContributionInfoFile(updated_info.clone()),
-        )?;
+        Ok(AuditedSignature {
+            signer: signer.cloned(),
+            path: location.cloned(),
+            content,
+        })
+    }
 
-        // Update the summary
-        let mut summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+    /// Returns the contribution metadata of the contributions whose attestation has been provided but not yet verified.
+    pub(crate) fn get_unverified_attestations(&self) -> Result<Vec<ContributionInfo>, CoordinatorError> {
+        let summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
             Object::ContributionsInfoSummary(summary) => summary,
             _ => return Err(CoordinatorError::StorageFailed),
         };
 
-        match summary.get_mut((round - 1) as usize) {
-            Some(t) => *t = updated_info.into(),
-            None => return Err(CoordinatorError::StorageFailed),
+        summary
+            .iter()
+            .filter(|info| info.attestation().is_some() && !info.is_attestation_verified())
+            .map(|info| {
+                match self.storage.get(&Locator::ContributionInfoFile {
+                    round_height: info.ceremony_round(),
+                })? {
+                    Object::ContributionInfoFile(info) => Ok(info),
+                    _ => Err(CoordinatorError::StorageFailed),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the summaries of the contributions that have been verified, in round order.
+    pub(crate) fn get_verified_contributions(&self) -> Result<Vec<TrimmedContributionInfo>, CoordinatorError> {
+        let summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary,
+            _ => return Err(CoordinatorError::StorageFailed),
         };
 
-        // Persist update to storage
-        self.storage.update(
-            &Locator::ContributionsInfoSummary,
-            Object::ContributionsInfoSummary(summary),
-        )
+        // The verified contribution of a round is stored as the challenge of the next one
+        Ok(summary
+            .into_iter()
+            .filter(|info| {
+                self.storage.exists(&Locator::ContributionFile(ContributionLocator::new(
+                    info.ceremony_round() + 1,
+                    0,
+                    0,
+                    true,
+                )))
+            })
+            .collect())
     }
 
     /// Appends current round summary to storage at the appropriate locator.
@@ -1955,7 +4379,8 @@ impl Coordinator {
                 .next()
                 .expect("splitting a string should yield at least one item");
 
-            if !self.signature.verify(
+            if !self.environment.signature_scheme().verify_with(
+                self.signature.as_ref(),
                 &address,
                 &serde_json::to_string(&contribution_file_signature.get_state())?,
                 contribution_file_signature.get_signature(),
@@ -2524,7 +4949,14 @@ impl Coordinator {
 }
 
 #[cfg(any(test, feature = "operator"))]
-use crate::commands::{Computation, Seed, SigningKey, Verification};
+use crate::{
+    commands::{verify_response, Computation, Seed, SigningKey, Verification},
+    objects::BeaconContribution,
+};
+#[cfg(any(test, feature = "operator"))]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+#[cfg(any(test, feature = "operator"))]
+use setup_utils::{GenericArray, U64};
 
 #[cfg(any(test, feature = "operator"))]
 impl Coordinator {
@@ -2582,6 +5014,35 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Applies the public random `beacon` as the final contribution to the latest verified
+    /// parameters of the ceremony. The beacon can only be applied once, and closes the ceremony:
+    /// no contribution is accepted afterwards and the next update shuts the coordinator down.
+    ///
+    pub fn apply_beacon(&mut self, beacon: [u8; 32]) -> Result<BeaconContribution, CoordinatorError> {
+        let output_dir = self.beacon_directory();
+        if Beacon::load(&output_dir)?.is_some() {
+            return Err(CoordinatorError::BeaconAlreadyApplied);
+        }
+
+        let round_height = self.latest_verified_round_height()?;
+        let contribution = Beacon::run(
+            &self.storage,
+            self.signature.clone(),
+            &self.environment.default_verifier_signing_key(),
+            round_height,
+            beacon,
+            &output_dir,
+        )?;
+        self.finalized = true;
+        info!(
+            "Applied the random beacon to round {}, the ceremony is over",
+            round_height
+        );
+
+        Ok(contribution)
+    }
+
     pub fn get_pending_verifications(&self) -> &HashMap<Task, Participant> {
         self.state.get_pending_verifications()
     }
@@ -2604,15 +5065,100 @@ impl Coordinator {
         self.verify(&verifier, &sigkey, task)
     }
 
+    /// Verify several contributions using the coordinator's default verifier.
+    ///
+    /// The responses of up to [`Environment::verification_concurrency`] chunks are checked in
+    /// parallel, then the verified contributions are written to storage one after the other.
+    /// Returns the result of the verification of each task, in the given order.
+    pub fn default_verify_all(&mut self, tasks: &[Task]) -> Vec<anyhow::Result<()>> {
+        let threads = match self.environment.verification_concurrency() {
+            0 => rayon::current_num_threads(),
+            concurrency => concurrency,
+        }
+        .min(self.environment.number_of_chunks() as usize)
+        .min(tasks.len());
+
+        let (verifier, round_height) = match (
+            self.environment.coordinator_verifiers().first().cloned(),
+            self.current_round_height(),
+        ) {
+            (Some(verifier), Ok(round_height)) if threads > 1 => (verifier, round_height),
+            _ => return tasks.iter().map(|task| self.default_verify(task)).collect(),
+        };
+        let pool = match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+            Ok(pool) => pool,
+            Err(error) => {
+                warn!("Failed to build the verification thread pool: {}", error);
+                return tasks.iter().map(|task| self.default_verify(task)).collect();
+            }
+        };
+
+        debug!(
+            "Checking {} contributions of round {} on {} threads",
+            tasks.len(),
+            round_height,
+            threads
+        );
+        let storage = &self.storage;
+        let response_hashes: Vec<Result<_, CoordinatorError>> = pool.install(|| {
+            tasks
+                .par_iter()
+                .map(|task| {
+                    if task.contribution_id() == 0 {
+                        return Err(CoordinatorError::ContributionIdMustBeNonzero);
+                    }
+                    let challenge = storage.reader(&Locator::ContributionFile(ContributionLocator::new(
+                        round_height,
+                        task.chunk_id(),
+                        task.contribution_id() - 1,
+                        true,
+                    )))?;
+                    let response = storage.reader(&Locator::ContributionFile(ContributionLocator::new(
+                        round_height,
+                        task.chunk_id(),
+                        task.contribution_id(),
+                        false,
+                    )))?;
+
+                    verify_response(challenge.as_ref(), response.as_ref())
+                })
+                .collect()
+        });
+
+        let sigkey = self.environment.default_verifier_signing_key();
+        tasks
+            .iter()
+            .zip(response_hashes)
+            .map(|(task, response_hash)| match response_hash {
+                Ok(response_hash) => self.verify_checked(&verifier, &sigkey, task, Some(response_hash)),
+                Err(error) => {
+                    error!("Verification of {} failed with {}", task, error);
+                    Err(error.into())
+                }
+            })
+            .collect()
+    }
+
+    pub fn verify(
+        &mut self,
+        verifier: &Participant,
+        verifier_signing_key: &SigningKey,
+        task: &Task,
+    ) -> anyhow::Result<()> {
+        self.verify_checked(verifier, verifier_signing_key, task, None)
+    }
+
+    /// Verify a contribution, skipping the check of the response if its hash is already known.
     #[tracing::instrument(
-        skip(self, verifier, verifier_signing_key),
+        skip(self, verifier, verifier_signing_key, checked_response_hash),
         fields(verifier = %verifier),
     )]
-    pub fn verify(
+    fn verify_checked(
         &mut self,
         verifier: &Participant,
         verifier_signing_key: &SigningKey,
         task: &Task,
+        checked_response_hash: Option<GenericArray<u8, U64>>,
     ) -> anyhow::Result<()> {
         let round_height = self.current_round_height()?;
         debug!(
@@ -2620,7 +5166,13 @@ impl Coordinator {
             round_height,
             task.chunk_id()
         );
-        let _next_challenge = self.run_verification(round_height, task, verifier, verifier_signing_key)?;
+        let _next_challenge = self.run_checked_verification(
+            round_height,
+            task,
+            verifier,
+            verifier_signing_key,
+            checked_response_hash,
+        )?;
         self.try_verify(verifier, task)?;
         debug!(
             "Successful verification for round {} chunk {}",
@@ -2709,6 +5261,7 @@ impl Coordinator {
             contribution_file_signature_locator,
             participant_seed,
         )?;
+        self.storage.seal(response_locator)?;
         info!(
             "Completed computation on round {} chunk {} contribution {} as {}",
             round_height, chunk_id, contribution_id, participant
@@ -2733,6 +5286,17 @@ impl Coordinator {
         task: &Task,
         participant: &Participant,
         participant_signing_key: &SigningKey,
+    ) -> Result<LocatorPath, CoordinatorError> {
+        self.run_checked_verification(round_height, task, participant, participant_signing_key, None)
+    }
+
+    fn run_checked_verification(
+        &mut self,
+        round_height: u64,
+        task: &Task,
+        participant: &Participant,
+        participant_signing_key: &SigningKey,
+        checked_response_hash: Option<GenericArray<u8, U64>>,
     ) -> Result<LocatorPath, CoordinatorError> {
         let chunk_id = task.chunk_id();
         let contribution_id = task.contribution_id();
@@ -2804,6 +5368,7 @@ impl Coordinator {
             chunk_id,
             contribution_id,
             is_final_contribution,
+            checked_response_hash,
         )?;
         info!(
             "Completed verification on round {} chunk {} contribution {} as {}",
@@ -2816,6 +5381,7 @@ impl Coordinator {
             error!("Verified response file at {} is missing", verified_response);
             return Err(CoordinatorError::ContributionLocatorMissing);
         }
+        self.storage.seal(&verified_locator)?;
 
         Ok(self.storage.to_path(&verified_locator)?)
     }
@@ -2861,16 +5427,21 @@ impl Coordinator {
 #[cfg(test)]
 mod tests {
     use crate::{
+        audit::AuditEvent,
         authentication::Dummy,
         commands::{Seed, SigningKey, SEED_LENGTH},
+        email::EmailNotification,
         environment::*,
-        objects::{Participant, Task},
+        objects::{ContributionFileSignature, ContributionState, Participant, Task},
+        storage::{ContributionLocator, Locator, Object},
         testing::prelude::*,
-        Coordinator,
+        Coordinator, CoordinatorError,
     };
 
     use once_cell::sync::Lazy;
+    use phase2_client_sdk::{CeremonyEvent, RejectionReason};
     use rand::RngCore;
+    use setup_utils::calculate_hash;
     use std::{
         collections::HashMap,
         net::{IpAddr, Ipv4Addr},
@@ -3110,6 +5681,194 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[serial]
+    fn coordinator_recover() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+        assert!(coordinator.recover()?.is_empty());
+
+        // Crash after locking chunk 0 in the round, before saving the state.
+        let state = coordinator.state.clone();
+        coordinator.try_lock_chunk(0, contributor)?;
+        coordinator.state = state;
+        coordinator.save_state()?;
+
+        // Leftover response of an upload to chunk 1.
+        let response = ContributionLocator::new(1, 1, 1, false);
+        coordinator.storage.initialize(Locator::ContributionFile(response), 8)?;
+
+        let recovery = coordinator.recover()?;
+        assert!(recovery.dropped.is_empty());
+        assert_eq!(recovery.released_locks, vec![0]);
+        assert_eq!(recovery.removed_responses, vec![response]);
+        assert!(recovery.requeued_verifications.is_empty());
+
+        assert!(coordinator.current_round()?.chunk(0)?.is_unlocked());
+        assert!(!coordinator.storage.exists(&Locator::ContributionFile(response)));
+        assert!(coordinator.try_lock_chunk(0, contributor).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_events() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        let mut events = coordinator.subscribe();
+        initialize_coordinator(&mut coordinator)?;
+        assert_eq!(events.try_recv()?, CeremonyEvent::RoundAdvanced { round_height: 1 });
+        // Both contributors of the queue were assigned to the round
+        assert_eq!(events.try_recv()?, CeremonyEvent::QueueEmpty { round_height: 1 });
+
+        coordinator.drop_participant(contributor)?;
+        assert_eq!(
+            events.try_recv()?,
+            CeremonyEvent::ContributorDropped {
+                public_key: contributor.address(),
+            }
+        );
+        assert!(events.try_recv().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_reject_contribution() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+        let mut events = coordinator.subscribe();
+        assert!(coordinator.rejections(contributor).is_empty());
+
+        let reliability_score = coordinator.reliability_score(contributor);
+        let reason = RejectionReason::VerificationFailed;
+        coordinator.reject_contribution(contributor, 1, reason, "invalid response".to_string())?;
+        let rejections = coordinator.rejections(contributor);
+        assert_eq!(rejections.len(), 1);
+        assert_eq!((rejections[0].round_height, rejections[0].reason), (1, reason));
+        assert_eq!(rejections[0].message, "invalid response");
+        assert!(coordinator.reliability_score(contributor) < reliability_score);
+        assert_eq!(
+            events.try_recv()?,
+            CeremonyEvent::ContributionRejected {
+                round_height: 1,
+                public_key: contributor.address(),
+                reason,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_audit_log() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+        let start = coordinator.audit_records()?.len();
+
+        let participant = Participant::new_contributor("audited");
+        coordinator.ban_participant(&participant)?;
+        coordinator.record_admin_action("operator", String::from("ban"))?;
+        coordinator.unban_participant(&participant)?;
+
+        let records = coordinator.audit_records()?;
+        let events: Vec<AuditEvent> = records[start..].iter().map(|record| record.event.clone()).collect();
+        assert_eq!(
+            events,
+            vec![
+                AuditEvent::Ban {
+                    participant: Some(participant.address()),
+                    ip: None,
+                },
+                AuditEvent::AdminAction {
+                    operator: String::from("operator"),
+                    action: String::from("ban"),
+                },
+                AuditEvent::Unban {
+                    participant: Some(participant.address()),
+                    ip: None,
+                },
+            ]
+        );
+
+        // Each record is chained to the previous one
+        for (previous, record) in records.iter().zip(records.iter().skip(1)) {
+            assert_eq!(record.sequence, previous.sequence + 1);
+            assert_eq!(record.previous_hash, previous.hash);
+            assert_eq!(record.digest()?, record.hash);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_email_notifications() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+
+        // Wrong, the current contributor is not in the queue anymore
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        assert!(matches!(
+            coordinator.register_email(&contributor, String::from("contributor@example.com")),
+            Err(CoordinatorError::ParticipantMissing)
+        ));
+        coordinator
+            .state
+            .register_email(&contributor, String::from("contributor@example.com"));
+
+        let queued = Participant::new_contributor("queued");
+        coordinator.add_to_queue(queued.clone(), None, String::from("irrelevant_token"), 10)?;
+        coordinator.register_email(&queued, String::from("queued@example.com"))?;
+
+        // The queued contributor is notified once, when it gets close enough to its turn
+        assert!(coordinator.email_notifications(0)?.is_empty());
+        assert_eq!(
+            coordinator.email_notifications(1)?,
+            vec![EmailNotification::ApproachingSlot {
+                address: String::from("queued@example.com"),
+                queue_position: 1,
+                estimated_wait_seconds: None,
+            }]
+        );
+        assert!(coordinator.email_notifications(1)?.is_empty());
+
+        // The current contributor is notified of its lock, then forgotten
+        coordinator.try_lock(&contributor)?;
+        assert_eq!(
+            coordinator.email_notifications(1)?,
+            vec![EmailNotification::LockAcquired {
+                address: String::from("contributor@example.com"),
+            }]
+        );
+        assert!(!coordinator.state.contact_emails().contains_key(&contributor));
+
+        // The address of a contributor leaving the queue is forgotten too
+        coordinator.remove_from_queue(&queued)?;
+        assert!(coordinator.email_notifications(1)?.is_empty());
+        assert!(coordinator.state.contact_emails().is_empty());
+
+        Ok(())
+    }
+
     #[test]
     #[serial]
     fn coordinator_contributor_add_contribution() -> anyhow::Result<()> {
@@ -3174,6 +5933,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[serial]
+    fn coordinator_validate_contribution() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+        let coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+
+        let locator = ContributionLocator::new(1, 0, 1, false);
+        let contribution = vec![1u8; Object::anoma_contribution_file_size(&TEST_ENVIRONMENT_ANOMA, 1, 1) as usize];
+        let signature = |response: &[u8]| {
+            let state = ContributionState::new(vec![0; 64], calculate_hash(response).to_vec(), None).unwrap();
+            ContributionFileSignature::new(hex::encode([0u8; 64]), state).unwrap()
+        };
+        coordinator.validate_contribution(&locator, &mut contribution.as_slice(), &signature(&contribution))?;
+
+        // Wrong, truncated contribution
+        let truncated = &contribution[1..];
+        assert!(matches!(
+            coordinator.validate_contribution(&locator, &mut &truncated[..], &signature(truncated)),
+            Err(CoordinatorError::ContributionFileSizeMismatch)
+        ));
+
+        // Wrong, response hash of another contribution
+        let other = vec![2u8; contribution.len()];
+        assert!(matches!(
+            coordinator.validate_contribution(&locator, &mut contribution.as_slice(), &signature(&other)),
+            Err(CoordinatorError::ContributionHashMismatch)
+        ));
+
+        Ok(())
+    }
+
     #[test]
     #[serial]
     fn coordinator_verifier_verify_contribution() -> anyhow::Result<()> {
@@ -3230,6 +6020,93 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[serial]
+    fn coordinator_storage_low_water_mark() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .storage_low_water_mark(u64::MAX)
+        .into();
+        initialize_test_environment(&environment);
+
+        let coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        let mut events = coordinator.subscribe();
+        assert!(matches!(
+            coordinator.check_storage_space(),
+            Err(crate::CoordinatorError::StorageSpaceLow)
+        ));
+        assert!(matches!(events.try_recv(), Ok(CeremonyEvent::StorageLow { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_audit_storage() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+        let verifier = Lazy::force(&TEST_VERIFIER_ID);
+        let verifier_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        // The current round isn't audited until it's complete.
+        assert!(coordinator.audit_storage()?.run()?.rounds.is_empty());
+
+        // Complete round 1, with a single chunk and a single contribution.
+        let round_height = coordinator.current_round_height()?;
+        let task = Task::new(0, 1);
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.try_lock_chunk(task.chunk_id(), contributor)?;
+        coordinator.run_computation(
+            round_height,
+            task.chunk_id(),
+            task.contribution_id(),
+            contributor,
+            &contributor_signing_key,
+            &seed,
+        )?;
+        coordinator.add_contribution(task.chunk_id(), contributor)?;
+        coordinator.run_verification(round_height, &task, verifier, &verifier_signing_key)?;
+        coordinator.verify_contribution(&task, verifier)?;
+        assert!(coordinator.current_round()?.is_complete());
+
+        let audit = coordinator.audit_storage()?.run()?;
+        assert!(audit.is_consistent());
+        assert_eq!(audit.rounds, vec![round_height]);
+        assert_eq!(audit.files_checked, 3);
+
+        // Corrupt the response of the contribution, shared with the next challenge.
+        let response = coordinator
+            .current_round()?
+            .chunk(task.chunk_id())?
+            .get_contribution(task.contribution_id())?
+            .get_contributed_location()
+            .clone()
+            .unwrap();
+        std::fs::write(&response, b"corrupted")?;
+
+        let audit = coordinator.audit_storage()?.run()?;
+        assert!(!audit.is_consistent());
+        assert!(audit
+            .discrepancies
+            .iter()
+            .all(|discrepancy| discrepancy.issue == AuditIssue::HashMismatch));
+        assert!(audit
+            .discrepancies
+            .iter()
+            .any(|discrepancy| discrepancy.path == Some(response.to_string())));
+
+        Ok(())
+    }
+
     #[test]
     #[serial]
     // This test runs a round with a single coordinator and single verifier
@@ -3598,6 +6475,12 @@ mod tests {
             serde_json::to_string_pretty(&coordinator.current_round()?)?
         );
 
+        // Only the rounds before the current one are kept for the next exports of the transcript
+        let mut completed_rounds = vec![];
+        let transcript = coordinator.export_transcript_cached(&mut completed_rounds)?;
+        assert_eq!(transcript, coordinator.export_transcript()?);
+        assert_eq!(1, completed_rounds.len());
+
         {
             // Run aggregation for round 1.
             coordinator.aggregate_contributions()?;
@@ -3609,6 +6492,12 @@ mod tests {
         // Check that the ceremony has advanced to round 2.
         assert_eq!(2, coordinator.current_round_height()?);
 
+        // The completed round is added to the rounds kept for the transcript
+        let transcript = coordinator.export_transcript_cached(&mut completed_rounds)?;
+        assert_eq!(transcript, coordinator.export_transcript()?);
+        assert_eq!(2, completed_rounds.len());
+        assert_eq!(completed_rounds[1], transcript.rounds[1]);
+
         info!(
             "Finished aggregation with this transcript {}",
             serde_json::to_string_pretty(&coordinator.current_round()?)?
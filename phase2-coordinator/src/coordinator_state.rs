@@ -1,25 +1,50 @@
 use crate::{
+    authentication::{ContributionReceipt, KeyPair, KeyRotation},
     environment::Environment,
     objects::{
         participant::*,
         task::{initialize_tasks, Task},
     },
+    queue_policy::QueueEntry,
     storage::{Disk, Locator, Object},
     CoordinatorError, TimeSource,
 };
 use anyhow::anyhow;
 use lazy_static::lazy_static;
+use phase2_client_sdk::{CeremonyStats, ContributionRejection, HourlyContributions};
 
+use rand::RngCore;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet, LinkedList},
+    collections::{BTreeMap, HashMap, HashSet, LinkedList, VecDeque},
     iter::FromIterator,
     net::IpAddr,
 };
 use time::{Duration, OffsetDateTime};
 use tracing::*;
 
+/// Validity of the nonces issued to the participants to sign at login
+const LOGIN_NONCE_VALIDITY: Duration = Duration::minutes(5);
+
+/// Number of the most recent contribution slots the estimated wait in the queue is averaged over
+const SLOT_DURATION_SAMPLES: usize = 20;
+
+/// Reliability score in the queue of the contributors without a history in the ceremony
+pub const DEFAULT_RELIABILITY_SCORE: u8 = 10;
+
+/// Highest reliability score earned by completing contributions, for the priority tokens to keep their advantage
+const MAXIMUM_EARNED_RELIABILITY_SCORE: u8 = 15;
+
+/// Points of reliability score earned by completing a contribution
+const COMPLETION_REWARD: u8 = 1;
+
+/// Points of reliability score lost by a contributor dropped for missing its heartbeats or holding a lock too long
+const TIMEOUT_PENALTY: u8 = 3;
+
+/// Points of reliability score lost by a contributor whose contribution failed the verification
+const INVALID_CONTRIBUTION_PENALTY: u8 = 5;
+
 lazy_static! {
     pub static ref TOKENS_PATH: String = std::env::var("NAMADA_TOKENS_PATH").unwrap_or_else(|_| "./tokens".to_string());
     pub(crate) static ref TOKEN_BLACKLIST: bool = match std::env::var("TOKEN_BLACKLIST") {
@@ -30,6 +55,17 @@ lazy_static! {
         Ok(s) if s == "true" => true,
         _ => false,
     };
+    pub(crate) static ref INVITE_ONLY: bool = match std::env::var("NAMADA_MPC_INVITE_ONLY") {
+        Ok(s) if s == "true" => true,
+        _ => false,
+    };
+}
+
+/// Returns a new random seed, [`hex`] encoded, for the draws of the queue lottery.
+fn new_lottery_seed() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -50,6 +86,9 @@ pub struct ChunkLock {
     chunk_id: u64,
     /// The time that the chunk was locked.
     lock_time: OffsetDateTime,
+    /// The hash, hex encoded, of the challenge issued with the lock.
+    #[serde(default)]
+    challenge_hash: Option<String>,
 }
 
 impl ChunkLock {
@@ -59,6 +98,7 @@ impl ChunkLock {
         Self {
             chunk_id,
             lock_time: time.now_utc(),
+            challenge_hash: None,
         }
     }
 
@@ -71,6 +111,11 @@ impl ChunkLock {
     pub fn lock_time(&self) -> &OffsetDateTime {
         &self.lock_time
     }
+
+    /// The hash of the challenge issued with the lock, if recorded.
+    pub fn challenge_hash(&self) -> Option<&str> {
+        self.challenge_hash.as_deref()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -955,6 +1000,8 @@ struct RuntimeState {
     tokens_in_use: HashMap<String, Participant>,
     /// The map of ip addresses currently in ceremony
     current_ips: HashMap<IpAddr, Participant>,
+    /// The map of the nonces issued for login, with the public key they were issued to and their expiration
+    login_nonces: HashMap<String, (String, OffsetDateTime)>,
 }
 
 impl Default for RuntimeState {
@@ -965,10 +1012,47 @@ impl Default for RuntimeState {
             tokens: CoordinatorState::load_tokens(),
             tokens_in_use: Default::default(),
             current_ips: Default::default(),
+            login_nonces: Default::default(),
         }
     }
 }
 
+/// The agreement of the external verifiers on a task pending verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalVerificationStatus {
+    /// The quorum hasn't been reached yet
+    Pending,
+    /// The quorum of verifiers verified the contribution
+    Approved,
+    /// The quorum of verifiers rejected the contribution
+    Rejected,
+    /// Every verifier sent its result without reaching the quorum
+    Disputed,
+}
+
+/// A single-use code granting access to the queue during the invite-only phases of the ceremony.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InviteCode {
+    pub code: String,
+    /// Free text set by the coordinator when minting the code, e.g. the intended recipient
+    pub notes: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub expires_at: Option<OffsetDateTime>,
+    /// The participant who redeemed the code
+    pub redeemed_by: Option<Participant>,
+    pub redeemed_at: Option<OffsetDateTime>,
+}
+
+/// The email address registered by a contributor when joining the queue, with the notifications already sent to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactEmail {
+    pub address: String,
+    /// The contributor has been notified that its turn is getting close
+    pub approaching_notified: bool,
+    /// The contributor locked its chunk, the last notification is due
+    pub lock_acquired: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoordinatorState {
     /// The parameters and settings of this coordinator.
@@ -992,6 +1076,12 @@ pub struct CoordinatorState {
     current_verifiers: HashMap<Participant, ParticipantInfo>,
     /// The map of tasks pending verification in the current round.
     pending_verification: HashMap<Task, Participant>,
+    /// The results of the external verifiers on the tasks pending verification, by public key of the verifier
+    #[serde(default)]
+    external_verifications: HashMap<Task, HashMap<String, bool>>,
+    /// The task locked by each external verifier, by public key of the verifier
+    #[serde(default)]
+    verifier_locks: HashMap<String, Task>,
     /// The map of each round height to the corresponding contributors from that round.
     finished_contributors: HashMap<u64, HashMap<Participant, ParticipantInfo>>,
     /// The map of each round height to the corresponding verifiers from that round.
@@ -1000,6 +1090,15 @@ pub struct CoordinatorState {
     dropped: Vec<ParticipantInfo>,
     /// The list of participants that are banned from all current and future rounds.
     banned: HashSet<Participant>,
+    /// The IPs that are banned from joining the queue.
+    #[serde(default)]
+    banned_ips: HashSet<IpAddr>,
+    /// The round height and time of the last contribution, by public key of the contributor
+    #[serde(default)]
+    last_contributions: HashMap<String, (u64, OffsetDateTime)>,
+    /// The round height and time of the last contribution, by IP of the contributor
+    #[serde(default)]
+    last_ip_contributions: HashMap<IpAddr, (u64, OffsetDateTime)>,
     /// The manual lock to hold the coordinator from transitioning to the next round.
     manual_lock: bool,
     /// The ceremony start time.
@@ -1008,6 +1107,42 @@ pub struct CoordinatorState {
     cohort_duration: u64,
     /// Map of tokens which have been used in the ceremony
     blacklisted_tokens: HashMap<String, Participant>,
+    /// The map of minted invite codes, with their redemption
+    #[serde(default)]
+    invite_codes: HashMap<String, InviteCode>,
+    /// The participants who redeemed a priority token, by id of the token
+    #[serde(default)]
+    redeemed_priority_tokens: HashMap<String, Participant>,
+    /// The signatures of the recently received requests, with their timestamp, by public key of the signer
+    #[serde(default)]
+    request_signatures: HashMap<String, Vec<(i64, String)>>,
+    /// The hand-off statements of the rotations of the coordinator key, in order
+    #[serde(default)]
+    key_rotations: Vec<KeyRotation>,
+    /// Durations, in seconds, of the most recent contribution slots, finished or dropped, the oldest first
+    #[serde(default)]
+    slot_durations: VecDeque<u64>,
+    /// The reliability scores earned by the contributors in the previous rounds, by participant
+    #[serde(default)]
+    reliability_scores: HashMap<Participant, u8>,
+    /// The contributions rejected at their verification, by participant
+    #[serde(default)]
+    rejections: HashMap<Participant, Vec<ContributionRejection>>,
+    /// The receipts issued for the accepted contributions, by participant
+    #[serde(default)]
+    receipts: HashMap<Participant, Vec<ContributionReceipt>>,
+    /// The numbers of consecutive failed uploads or verifications, by participant
+    #[serde(default)]
+    failures: HashMap<Participant, u32>,
+    /// The contributors dropped for their failures, with the time until which they can't join the queue again
+    #[serde(default)]
+    suspensions: HashMap<Participant, OffsetDateTime>,
+    /// The email addresses registered by the contributors to be notified of their turn, by participant
+    #[serde(default)]
+    contact_emails: HashMap<Participant, ContactEmail>,
+    /// The secret seed of the draws of the contributors in the queue, when selected by lottery
+    #[serde(default = "new_lottery_seed")]
+    lottery_seed: String,
     /// Temporary runtime state, should not be persisted to storage to reset it in case of restart
     #[serde(skip)]
     runtime_state: RuntimeState,
@@ -1102,14 +1237,31 @@ impl CoordinatorState {
             blacklisted_ips: HashMap::default(),
             current_verifiers: HashMap::default(),
             pending_verification: HashMap::default(),
+            external_verifications: HashMap::default(),
+            verifier_locks: HashMap::default(),
             finished_contributors: HashMap::default(),
             finished_verifiers: HashMap::default(),
             dropped: Vec::new(),
             banned: HashSet::new(),
+            banned_ips: HashSet::new(),
+            last_contributions: HashMap::default(),
+            last_ip_contributions: HashMap::default(),
             manual_lock: false,
             ceremony_start_time,
             cohort_duration,
             blacklisted_tokens: HashMap::default(),
+            invite_codes: HashMap::default(),
+            redeemed_priority_tokens: HashMap::default(),
+            request_signatures: HashMap::default(),
+            key_rotations: Vec::new(),
+            slot_durations: VecDeque::new(),
+            reliability_scores: HashMap::default(),
+            rejections: HashMap::default(),
+            receipts: HashMap::default(),
+            failures: HashMap::default(),
+            suspensions: HashMap::default(),
+            contact_emails: HashMap::default(),
+            lottery_seed: new_lottery_seed(),
             runtime_state: RuntimeState::default(),
         }
     }
@@ -1221,7 +1373,22 @@ impl CoordinatorState {
                 blacklisted_ips: std::mem::take(&mut self.blacklisted_ips),
                 queue,
                 banned: std::mem::take(&mut self.banned),
+                banned_ips: std::mem::take(&mut self.banned_ips),
+                last_contributions: std::mem::take(&mut self.last_contributions),
+                last_ip_contributions: std::mem::take(&mut self.last_ip_contributions),
                 blacklisted_tokens: std::mem::take(&mut self.blacklisted_tokens),
+                invite_codes: std::mem::take(&mut self.invite_codes),
+                redeemed_priority_tokens: std::mem::take(&mut self.redeemed_priority_tokens),
+                request_signatures: std::mem::take(&mut self.request_signatures),
+                key_rotations: std::mem::take(&mut self.key_rotations),
+                slot_durations: std::mem::take(&mut self.slot_durations),
+                reliability_scores: std::mem::take(&mut self.reliability_scores),
+                rejections: std::mem::take(&mut self.rejections),
+                receipts: std::mem::take(&mut self.receipts),
+                failures: std::mem::take(&mut self.failures),
+                suspensions: std::mem::take(&mut self.suspensions),
+                contact_emails: std::mem::take(&mut self.contact_emails),
+                lottery_seed: std::mem::take(&mut self.lottery_seed),
                 runtime_state: std::mem::take(&mut self.runtime_state),
                 ..Self::new(self.environment.clone())
             };
@@ -1266,8 +1433,23 @@ impl CoordinatorState {
                 blacklisted_ips: std::mem::take(&mut self.blacklisted_ips),
                 queue: std::mem::take(&mut self.queue),
                 banned: std::mem::take(&mut self.banned),
+                banned_ips: std::mem::take(&mut self.banned_ips),
+                last_contributions: std::mem::take(&mut self.last_contributions),
+                last_ip_contributions: std::mem::take(&mut self.last_ip_contributions),
                 dropped: std::mem::take(&mut self.dropped),
                 blacklisted_tokens: std::mem::take(&mut self.blacklisted_tokens),
+                invite_codes: std::mem::take(&mut self.invite_codes),
+                redeemed_priority_tokens: std::mem::take(&mut self.redeemed_priority_tokens),
+                request_signatures: std::mem::take(&mut self.request_signatures),
+                key_rotations: std::mem::take(&mut self.key_rotations),
+                slot_durations: std::mem::take(&mut self.slot_durations),
+                reliability_scores: std::mem::take(&mut self.reliability_scores),
+                rejections: std::mem::take(&mut self.rejections),
+                receipts: std::mem::take(&mut self.receipts),
+                failures: std::mem::take(&mut self.failures),
+                suspensions: std::mem::take(&mut self.suspensions),
+                contact_emails: std::mem::take(&mut self.contact_emails),
+                lottery_seed: std::mem::take(&mut self.lottery_seed),
                 runtime_state: std::mem::take(&mut self.runtime_state),
                 ..Self::new(self.environment.clone())
             };
@@ -1344,6 +1526,26 @@ impl CoordinatorState {
         self.banned.contains(participant)
     }
 
+    ///
+    /// Returns the banned participants, sorted by address.
+    ///
+    pub fn banned_participants(&self) -> Vec<&Participant> {
+        let mut participants: Vec<&Participant> = self.banned.iter().collect();
+        participants.sort_by_key(|p| p.address());
+
+        participants
+    }
+
+    ///
+    /// Returns the banned IPs, sorted.
+    ///
+    pub fn banned_ips(&self) -> Vec<&IpAddr> {
+        let mut ips: Vec<&IpAddr> = self.banned_ips.iter().collect();
+        ips.sort();
+
+        ips
+    }
+
     ///
     /// Returns `true` if the given participant is dropped.
     ///
@@ -1384,6 +1586,263 @@ impl CoordinatorState {
                 .contains_key(participant)
     }
 
+    ///
+    /// Returns `true` if the given participant has finished contributing
+    /// in any round.
+    ///
+    pub fn has_contributed(&self, participant: &Participant) -> bool {
+        participant.is_contributor()
+            && self
+                .finished_contributors
+                .values()
+                .any(|contributors| contributors.contains_key(participant))
+    }
+
+    ///
+    /// Returns the average duration, in seconds, of the most recent contribution slots,
+    /// if any slot ended yet.
+    ///
+    pub fn average_slot_duration(&self) -> Option<u64> {
+        match self.slot_durations.len() {
+            0 => None,
+            samples => Some(self.slot_durations.iter().sum::<u64>() / samples as u64),
+        }
+    }
+
+    ///
+    /// Returns the estimated number of seconds before the turn of the contributor at the given
+    /// position of the queue, from the average duration of the recent contribution slots.
+    ///
+    pub fn estimated_wait_seconds(&self, queue_position: u64, time: &dyn TimeSource) -> Option<u64> {
+        let average = self.average_slot_duration()?;
+
+        // The slot of the current contributor is partly over already
+        let now = time.now_utc();
+        let elapsed = self
+            .current_contributors
+            .values()
+            .filter_map(|info| info.started_at)
+            .map(|started_at| (now - started_at).whole_seconds().max(0) as u64)
+            .max()
+            .unwrap_or_default();
+
+        Some(
+            average
+                .saturating_mul(queue_position)
+                .saturating_sub(elapsed.min(average)),
+        )
+    }
+
+    ///
+    /// Records the duration of the slot of the given contributor, once it finished or was dropped.
+    ///
+    fn record_slot_duration(&mut self, participant_info: &ParticipantInfo) {
+        let ended_at = participant_info.finished_at.or(participant_info.dropped_at);
+        if let (Some(started_at), Some(ended_at)) = (participant_info.started_at, ended_at) {
+            self.slot_durations
+                .push_back((ended_at - started_at).whole_seconds().max(0) as u64);
+            if self.slot_durations.len() > SLOT_DURATION_SAMPLES {
+                self.slot_durations.pop_front();
+            }
+        }
+    }
+
+    ///
+    /// Returns the aggregates of the ceremony displayed by the dashboard. The countries of the
+    /// contributors are left to the caller, see [`Self::contribution_ips`].
+    ///
+    pub fn ceremony_stats(&self) -> CeremonyStats {
+        let finished: Vec<&ParticipantInfo> = self
+            .finished_contributors
+            .values()
+            .flat_map(|contributors| contributors.values())
+            .collect();
+
+        let mut contributions_per_hour = BTreeMap::new();
+        for finished_at in finished.iter().filter_map(|info| info.finished_at) {
+            let hour = finished_at.unix_timestamp() - finished_at.unix_timestamp().rem_euclid(3600);
+            *contributions_per_hour.entry(hour).or_default() += 1;
+        }
+
+        let mut durations: Vec<u64> = finished
+            .iter()
+            .filter_map(|info| Some((info.finished_at? - info.started_at?).whole_seconds().max(0) as u64))
+            .collect();
+        durations.sort_unstable();
+        let (average, median) = match durations.len() {
+            0 => (None, None),
+            n if n % 2 == 0 => (
+                Some(durations.iter().sum::<u64>() / n as u64),
+                Some((durations[n / 2 - 1] + durations[n / 2]) / 2),
+            ),
+            n => (Some(durations.iter().sum::<u64>() / n as u64), Some(durations[n / 2])),
+        };
+
+        let dropped = self.dropped.iter().filter(|info| info.id.is_contributor()).count();
+        let dropout_rate = match finished.len() + dropped {
+            0 => 0.0,
+            started => dropped as f64 / started as f64,
+        };
+
+        CeremonyStats {
+            contributions_per_hour: contributions_per_hour
+                .into_iter()
+                .map(|(hour, contributions)| HourlyContributions { hour, contributions })
+                .collect(),
+            average_contribution_seconds: average,
+            median_contribution_seconds: median,
+            unique_countries: None,
+            dropout_rate,
+            verification_backlog: self.pending_verification.len() as u64,
+        }
+    }
+
+    ///
+    /// Returns the addresses the contributions came from.
+    ///
+    pub fn contribution_ips(&self) -> impl Iterator<Item = &IpAddr> {
+        self.last_ip_contributions.keys()
+    }
+
+    ///
+    /// Returns the reliability score of the contributor in the queue, raised by the contributions
+    /// it completed and lowered by its timeouts and invalid contributions.
+    ///
+    pub fn reliability_score(&self, participant: &Participant) -> u8 {
+        self.reliability_scores
+            .get(participant)
+            .copied()
+            .unwrap_or(DEFAULT_RELIABILITY_SCORE)
+    }
+
+    ///
+    /// Sets the reliability score of the contributor, in the queue right away if it's waiting
+    /// there. Returns its previous score.
+    ///
+    pub(super) fn set_reliability_score(
+        &mut self,
+        participant: &Participant,
+        score: u8,
+    ) -> Result<u8, CoordinatorError> {
+        if !participant.is_contributor() {
+            return Err(CoordinatorError::ExpectedContributor);
+        }
+
+        let previous = self.reliability_score(participant);
+        self.reliability_scores.insert(participant.clone(), score);
+        match self.queue.get_mut(participant) {
+            Some((reliability, _, _, _)) => {
+                let previous = std::mem::replace(reliability, score);
+                if self.current_round_height.is_some() {
+                    self.update_queue()?;
+                }
+                Ok(previous)
+            }
+            None => Ok(previous),
+        }
+    }
+
+    ///
+    /// Lowers the reliability score of the contributor whose contribution failed the verification.
+    ///
+    pub(super) fn record_invalid_contribution(&mut self, participant: &Participant) {
+        self.lower_reliability_score(participant, INVALID_CONTRIBUTION_PENALTY);
+    }
+
+    ///
+    /// Returns the contributions of the participant rejected at their verification, the oldest first.
+    ///
+    pub fn rejections(&self, participant: &Participant) -> &[ContributionRejection] {
+        self.rejections.get(participant).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    ///
+    /// Records the rejection of the contribution of the participant.
+    ///
+    pub(super) fn add_rejection(&mut self, participant: &Participant, rejection: ContributionRejection) {
+        self.rejections.entry(participant.clone()).or_default().push(rejection);
+    }
+
+    ///
+    /// Returns the receipts issued for the accepted contributions of the participant, the oldest first.
+    ///
+    pub fn receipts(&self, participant: &Participant) -> &[ContributionReceipt] {
+        self.receipts.get(participant).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    ///
+    /// Records the receipt issued for the accepted contribution of the participant.
+    ///
+    pub(super) fn add_receipt(&mut self, participant: &Participant, receipt: ContributionReceipt) {
+        self.receipts.entry(participant.clone()).or_default().push(receipt);
+    }
+
+    ///
+    /// Returns the email addresses registered by the contributors, by participant.
+    ///
+    pub fn contact_emails(&self) -> &HashMap<Participant, ContactEmail> {
+        &self.contact_emails
+    }
+
+    ///
+    /// Registers the email address of the contributor, replacing the previous one if any.
+    ///
+    pub(super) fn register_email(&mut self, participant: &Participant, address: String) {
+        self.contact_emails.insert(
+            participant.clone(),
+            ContactEmail {
+                address,
+                approaching_notified: false,
+                lock_acquired: false,
+            },
+        );
+    }
+
+    ///
+    /// Removes the email address of the contributor, once it's no longer needed.
+    ///
+    pub(super) fn unregister_email(&mut self, participant: &Participant) {
+        self.contact_emails.remove(participant);
+    }
+
+    ///
+    /// Records that the contributor has been notified that its turn is getting close.
+    ///
+    pub(super) fn email_approaching_notified(&mut self, participant: &Participant) {
+        if let Some(contact) = self.contact_emails.get_mut(participant) {
+            contact.approaching_notified = true;
+        }
+    }
+
+    ///
+    /// Records that the contributor locked its chunk, for the last notification to be sent.
+    ///
+    pub(super) fn email_lock_acquired(&mut self, participant: &Participant) {
+        if let Some(contact) = self.contact_emails.get_mut(participant) {
+            contact.lock_acquired = true;
+        }
+    }
+
+    ///
+    /// Raises the reliability score of the contributor by the given points, up to the highest earned score.
+    ///
+    fn raise_reliability_score(&mut self, participant: &Participant, points: u8) {
+        let score = self.reliability_score(participant);
+        if score < MAXIMUM_EARNED_RELIABILITY_SCORE {
+            let score = score.saturating_add(points).min(MAXIMUM_EARNED_RELIABILITY_SCORE);
+            self.reliability_scores.insert(participant.clone(), score);
+        }
+    }
+
+    ///
+    /// Lowers the reliability score of the contributor by the given points, down to zero.
+    ///
+    fn lower_reliability_score(&mut self, participant: &Participant, points: u8) {
+        let score = self.reliability_score(participant).saturating_sub(points);
+        debug!("Lowering the reliability score of {} to {}", participant, score);
+        self.reliability_scores.insert(participant.clone(), score);
+    }
+
     pub fn current_round_finished_contributors(&self) -> anyhow::Result<Vec<Participant>> {
         let current_round_height = self
             .current_round_height
@@ -1537,6 +1996,91 @@ impl CoordinatorState {
         &self.runtime_state.tokens_in_use
     }
 
+    ///
+    /// Checks that the given participant, or its IP if the cool-down applies to the
+    /// IPs too, hasn't contributed during the cool-down configured in the environment.
+    ///
+    pub(crate) fn cooldown_checks(
+        &self,
+        participant: &Participant,
+        participant_ip: Option<&IpAddr>,
+        time: &dyn TimeSource,
+    ) -> Result<(), CoordinatorError> {
+        let round_height = self.current_round_height.unwrap_or_default();
+        let now = time.now_utc();
+        let in_cooldown = |(contribution_round_height, contribution_time): &(u64, OffsetDateTime)| {
+            round_height < contribution_round_height + self.environment.contributor_cooldown_rounds()
+                || now < *contribution_time + self.environment.contributor_cooldown_duration()
+        };
+
+        if self.last_contributions.get(&participant.address()).map_or(false, &in_cooldown) {
+            return Err(CoordinatorError::ParticipantInCooldown);
+        }
+
+        if let Some(ip) = participant_ip.filter(|_| self.environment.contributor_cooldown_ip()) {
+            if self.last_ip_contributions.get(ip).map_or(false, &in_cooldown) {
+                return Err(CoordinatorError::ParticipantInCooldown);
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Checks that the given participant hasn't been dropped for its failures during the
+    /// failure ban configured in the environment.
+    ///
+    fn suspension_checks(&self, participant: &Participant, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+        match self.suspensions.get(participant) {
+            Some(until) if time.now_utc() < *until => Err(CoordinatorError::ParticipantSuspended),
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// Counts a failed upload or verification of the given participant. Returns its number
+    /// of consecutive failures.
+    ///
+    pub(super) fn record_failure(&mut self, participant: &Participant) -> u32 {
+        let failures = self.failures.entry(participant.clone()).or_default();
+        *failures += 1;
+        *failures
+    }
+
+    ///
+    /// Resets the count of consecutive failures of the given participant.
+    ///
+    pub(super) fn clear_failures(&mut self, participant: &Participant) {
+        self.failures.remove(participant);
+    }
+
+    ///
+    /// Prevents the given participant, dropped for its failures, from joining the queue again
+    /// until the given time.
+    ///
+    pub(super) fn suspend_participant(&mut self, participant: &Participant, until: OffsetDateTime) {
+        debug!("Suspending {} until {}", participant, until);
+        self.suspensions.insert(participant.clone(), until);
+    }
+
+    ///
+    /// Records the round height and time of the contribution of the given participant,
+    /// for the cool-down checks.
+    ///
+    pub(super) fn record_contribution(&mut self, participant: &Participant, time: &dyn TimeSource) {
+        let contribution = (self.current_round_height.unwrap_or_default(), time.now_utc());
+
+        if let Some(ip) = self
+            .runtime_state
+            .current_ips
+            .iter()
+            .find_map(|(ip, part)| if part == participant { Some(*ip) } else { None })
+        {
+            self.last_ip_contributions.insert(ip, contribution);
+        }
+        self.last_contributions.insert(participant.address(), contribution);
+    }
+
     ///
     /// Moves the token from the list of currently in use to the black list
     ///
@@ -1607,6 +2151,204 @@ impl CoordinatorState {
         self.blacklisted_tokens.contains_key(token)
     }
 
+    ///
+    /// Returns the minted invite codes, sorted by creation time.
+    ///
+    pub fn invite_codes(&self) -> Vec<&InviteCode> {
+        let mut codes: Vec<&InviteCode> = self.invite_codes.values().collect();
+        codes.sort_by_key(|c| c.created_at);
+
+        codes
+    }
+
+    ///
+    /// Mints `count` new invite codes, tagging them with the optional notes and expiration time.
+    ///
+    pub(super) fn mint_invite_codes(
+        &mut self,
+        count: usize,
+        notes: Option<String>,
+        expires_at: Option<OffsetDateTime>,
+        time: &dyn TimeSource,
+    ) -> Vec<InviteCode> {
+        let mut minted = Vec::with_capacity(count);
+
+        while minted.len() < count {
+            let mut bytes = [0u8; 16];
+            rand::thread_rng().fill_bytes(&mut bytes);
+            let code = hex::encode(bytes);
+
+            if self.invite_codes.contains_key(&code) {
+                continue;
+            }
+
+            let invite_code = InviteCode {
+                code: code.clone(),
+                notes: notes.clone(),
+                created_at: time.now_utc(),
+                expires_at,
+                redeemed_by: None,
+                redeemed_at: None,
+            };
+            self.invite_codes.insert(code, invite_code.clone());
+            minted.push(invite_code);
+        }
+
+        minted
+    }
+
+    ///
+    /// Returns the hand-off statements of the rotations of the coordinator key, in order.
+    ///
+    pub fn key_rotations(&self) -> &[KeyRotation] {
+        &self.key_rotations
+    }
+
+    ///
+    /// Records the hand-off statement and replaces the default verifier with the new keypair.
+    ///
+    pub(super) fn rotate_verifier_key(&mut self, rotation: KeyRotation, keypair: &KeyPair) {
+        self.environment.rotate_verifier_key(keypair);
+        self.key_rotations.push(rotation);
+    }
+
+    ///
+    /// Checks that the invite code exists, has not expired and has not been redeemed yet.
+    ///
+    pub fn invite_code_checks(&self, code: &str, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+        let invite_code = self.invite_codes.get(code).ok_or(CoordinatorError::InviteCodeInvalid)?;
+
+        if invite_code.redeemed_by.is_some() {
+            return Err(CoordinatorError::InviteCodeAlreadyRedeemed);
+        }
+
+        if let Some(expires_at) = invite_code.expires_at {
+            if time.now_utc() > expires_at {
+                return Err(CoordinatorError::InviteCodeExpired);
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Marks the invite code as redeemed by the given participant.
+    ///
+    pub(super) fn redeem_invite_code(
+        &mut self,
+        code: &str,
+        participant: &Participant,
+        time: &dyn TimeSource,
+    ) -> Result<(), CoordinatorError> {
+        self.invite_code_checks(code, time)?;
+
+        // Safe to unwrap, the code was checked above
+        let invite_code = self.invite_codes.get_mut(code).unwrap();
+        invite_code.redeemed_by = Some(participant.clone());
+        invite_code.redeemed_at = Some(time.now_utc());
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the participants who redeemed a priority token, by id of the token.
+    ///
+    pub fn redeemed_priority_tokens(&self) -> &HashMap<String, Participant> {
+        &self.redeemed_priority_tokens
+    }
+
+    ///
+    /// Checks that the priority token with the given id has not been redeemed yet.
+    ///
+    pub fn priority_token_checks(&self, id: &str) -> Result<(), CoordinatorError> {
+        match self.redeemed_priority_tokens.contains_key(id) {
+            true => Err(CoordinatorError::PriorityTokenAlreadyRedeemed),
+            false => Ok(()),
+        }
+    }
+
+    ///
+    /// Marks the priority token with the given id as redeemed by the given participant.
+    ///
+    pub(super) fn redeem_priority_token(
+        &mut self,
+        id: &str,
+        participant: &Participant,
+    ) -> Result<(), CoordinatorError> {
+        self.priority_token_checks(id)?;
+        self.redeemed_priority_tokens.insert(id.to_owned(), participant.clone());
+
+        Ok(())
+    }
+
+    ///
+    /// Registers the signature of a request sent by `pubkey` at the unix `timestamp`. The signatures
+    /// older than `window` are forgotten, as their requests are rejected anyway because of the timestamp.
+    /// The key and the signature are registered in lowercase, as their hex encoding is decoded whatever its
+    /// case: a replay can't change it to pass as a new request.
+    ///
+    /// Returns `CoordinatorError::RequestReplayed` if the signature has already been registered.
+    ///
+    pub(super) fn register_request_signature(
+        &mut self,
+        pubkey: &str,
+        timestamp: i64,
+        signature: &str,
+        window: Duration,
+        time: &dyn TimeSource,
+    ) -> Result<(), CoordinatorError> {
+        let oldest = (time.now_utc() - window).unix_timestamp();
+        self.request_signatures.retain(|_, signatures| {
+            signatures.retain(|(t, _)| *t >= oldest);
+            !signatures.is_empty()
+        });
+
+        let signature = signature.to_ascii_lowercase();
+        let signatures = self.request_signatures.entry(pubkey.to_ascii_lowercase()).or_default();
+        if signatures.iter().any(|(_, s)| *s == signature) {
+            return Err(CoordinatorError::RequestReplayed);
+        }
+        signatures.push((timestamp, signature));
+
+        Ok(())
+    }
+
+    ///
+    /// Issues a single-use nonce to be signed by `pubkey` to log in. The expired nonces are forgotten.
+    ///
+    pub(super) fn issue_login_nonce(&mut self, pubkey: &str, time: &dyn TimeSource) -> String {
+        let now = time.now_utc();
+        self.runtime_state
+            .login_nonces
+            .retain(|_, (_, expires_at)| *expires_at > now);
+
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let nonce = hex::encode(bytes);
+        self.runtime_state
+            .login_nonces
+            .insert(nonce.clone(), (pubkey.to_string(), now + LOGIN_NONCE_VALIDITY));
+
+        nonce
+    }
+
+    ///
+    /// Redeems the login nonce issued to `pubkey`.
+    ///
+    /// Returns `CoordinatorError::LoginNonceInvalid` if the nonce is unknown, expired or was issued to another key.
+    ///
+    pub(super) fn redeem_login_nonce(
+        &mut self,
+        pubkey: &str,
+        nonce: &str,
+        time: &dyn TimeSource,
+    ) -> Result<(), CoordinatorError> {
+        match self.runtime_state.login_nonces.remove(nonce) {
+            Some((owner, expires_at)) if owner == pubkey && expires_at > time.now_utc() => Ok(()),
+            _ => Err(CoordinatorError::LoginNonceInvalid),
+        }
+    }
+
     ///
     /// Returns `true` if all participants in the current round have no more pending chunks.
     ///
@@ -1736,6 +2478,10 @@ impl CoordinatorState {
     ) -> Result<(), CoordinatorError> {
         // Check that the pariticipant IP is not known.
         if let Some(ip) = participant_ip {
+            if self.banned_ips.contains(ip) {
+                return Err(CoordinatorError::ParticipantBanned);
+            }
+
             if *IP_BAN && (self.blacklisted_ips.contains_key(ip) || self.runtime_state.current_ips.contains_key(ip)) {
                 return Err(CoordinatorError::ParticipantIpAlreadyAdded);
             }
@@ -1798,6 +2544,9 @@ impl CoordinatorState {
         time: &dyn TimeSource,
     ) -> Result<(), CoordinatorError> {
         // NOTE: safety checks are performed directly in the rest api, no need to duplicate them here
+        self.cooldown_checks(&participant, participant_ip.as_ref(), time)?;
+        self.suspension_checks(&participant, time)?;
+
         // Add the participant to the queue.
         self.queue.insert(
             participant.clone(),
@@ -1805,7 +2554,7 @@ impl CoordinatorState {
         );
 
         // Add ip (if env set and if any) to the set of currently known addresses
-        if *IP_BAN {
+        if *IP_BAN || self.environment.contributor_cooldown_ip() {
             if let Some(ip) = participant_ip {
                 self.runtime_state.current_ips.insert(ip, participant.clone());
             }
@@ -1898,6 +2647,58 @@ impl CoordinatorState {
         }
     }
 
+    ///
+    /// Records the hash of the challenge issued to the given contributor with its lock
+    /// on the given chunk ID.
+    ///
+    #[inline]
+    pub(super) fn record_challenge_hash(
+        &mut self,
+        participant: &Participant,
+        chunk_id: u64,
+        challenge_hash: String,
+    ) -> Result<(), CoordinatorError> {
+        let participant_info = self
+            .current_contributors
+            .get_mut(participant)
+            .ok_or_else(|| CoordinatorError::ParticipantNotFound(participant.clone()))?;
+        match participant_info.locked_chunks.get_mut(&chunk_id) {
+            Some(lock) => {
+                lock.challenge_hash = Some(challenge_hash);
+                Ok(())
+            }
+            None => Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant),
+        }
+    }
+
+    ///
+    /// Returns the hash of the challenge issued to the given contributor with its lock
+    /// on the given chunk ID, if any.
+    ///
+    #[inline]
+    pub(super) fn issued_challenge_hash(&self, participant: &Participant, chunk_id: u64) -> Option<&str> {
+        self.current_contributors
+            .get(participant)?
+            .locked_chunks
+            .get(&chunk_id)?
+            .challenge_hash()
+    }
+
+    ///
+    /// Returns the time at which the earliest lock held by the given contributor expires,
+    /// if it holds any.
+    ///
+    #[inline]
+    pub(super) fn lock_expiry(&self, participant: &Participant) -> Option<OffsetDateTime> {
+        let participant_lock_timeout = self.environment.participant_lock_timeout();
+        self.current_contributors
+            .get(participant)?
+            .locked_chunks
+            .values()
+            .map(|lock| lock.lock_time + participant_lock_timeout)
+            .min()
+    }
+
     ///
     /// Reverts the given (chunk ID, contribution ID) task to the list of assigned tasks
     /// from the list of pending tasks.
@@ -2129,9 +2930,103 @@ impl CoordinatorState {
             .remove(task)
             .ok_or(CoordinatorError::VerifierMissing)?;
 
+        // Discard the results of the external verifiers, the same task comes back in the next round
+        self.external_verifications.remove(task);
+        self.verifier_locks.retain(|_, locked_task| locked_task != task);
+
+        Ok(())
+    }
+
+    ///
+    /// Locks the next task pending verification for the given external verifier, skipping the tasks
+    /// it already verified and the ones on which the verifiers already agreed. If the verifier holds
+    /// a lock already, the locked task is returned.
+    ///
+    pub(super) fn lock_verification_task(&mut self, verifier: &str) -> Option<Task> {
+        if let Some(task) = self.verifier_locks.get(verifier) {
+            return Some(*task);
+        }
+
+        let mut tasks: Vec<Task> = self
+            .pending_verification
+            .keys()
+            .filter(|task| {
+                let verified = self
+                    .external_verifications
+                    .get(task)
+                    .map_or(false, |results| results.contains_key(verifier));
+                !verified && self.external_verification_status(task) == ExternalVerificationStatus::Pending
+            })
+            .cloned()
+            .collect();
+        tasks.sort_by_key(Task::to_tuple);
+
+        let task = tasks.first().cloned()?;
+        debug!("Locking {} for the external verifier {}", task, verifier);
+        self.verifier_locks.insert(verifier.to_string(), task);
+
+        Some(task)
+    }
+
+    ///
+    /// Returns the task locked by the given external verifier, if any.
+    ///
+    pub fn locked_verification_task(&self, verifier: &str) -> Option<Task> {
+        self.verifier_locks.get(verifier).cloned()
+    }
+
+    ///
+    /// Records the result of the given external verifier on the task it locked and releases the lock.
+    ///
+    pub(super) fn add_external_verification(
+        &mut self,
+        verifier: &str,
+        task: &Task,
+        verified: bool,
+    ) -> Result<(), CoordinatorError> {
+        if self.verifier_locks.get(verifier) != Some(task) {
+            return Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant);
+        }
+
+        info!(
+            "The external verifier {} {} (chunk {}, contribution {})",
+            verifier,
+            if verified { "verified" } else { "rejected" },
+            task.chunk_id(),
+            task.contribution_id()
+        );
+
+        self.verifier_locks.remove(verifier);
+        self.external_verifications
+            .entry(*task)
+            .or_default()
+            .insert(verifier.to_string(), verified);
+
         Ok(())
     }
 
+    ///
+    /// Returns the agreement of the external verifiers on the given task. The task is approved
+    /// when the external verification is disabled.
+    ///
+    pub fn external_verification_status(&self, task: &Task) -> ExternalVerificationStatus {
+        let quorum = self.environment.verification_quorum();
+        let (approvals, rejections) = self.external_verifications.get(task).map_or((0, 0), |results| {
+            let approvals = results.values().filter(|verified| **verified).count();
+            (approvals, results.len() - approvals)
+        });
+
+        if approvals >= quorum {
+            ExternalVerificationStatus::Approved
+        } else if rejections >= quorum {
+            ExternalVerificationStatus::Rejected
+        } else if approvals + rejections >= self.environment.external_verifiers().len() {
+            ExternalVerificationStatus::Disputed
+        } else {
+            ExternalVerificationStatus::Pending
+        }
+    }
+
     ///
     /// Adds the given (chunk ID, contribution ID) task to the completed tasks of the given participant,
     /// and removes the chunk ID from the locks held by the given participant.
@@ -2398,7 +3293,7 @@ impl CoordinatorState {
             }
 
             return Ok(DropParticipant::DropQueue(DropQueueParticipantData {
-                _participant: participant.clone(),
+                participant: participant.clone(),
             }));
         }
 
@@ -2455,6 +3350,7 @@ impl CoordinatorState {
                 // Set the participant as dropped.
                 let mut dropped_info = participant_info.clone();
                 dropped_info.drop(time)?;
+                self.record_slot_duration(&dropped_info);
 
                 // Fetch the number of chunks and number of contributors.
                 let number_of_chunks = self.environment.number_of_chunks() as u64;
@@ -2603,7 +3499,7 @@ impl CoordinatorState {
         };
 
         let drop_data = DropCurrentParticpantData {
-            _participant: participant.clone(),
+            participant: participant.clone(),
             storage_action: final_storage_action,
         };
 
@@ -2612,13 +3508,15 @@ impl CoordinatorState {
 
     ///
     /// Bans the given participant from the queue, precommit, and current round.
+    /// Participants that are not part of the ceremony yet can be banned too, in which
+    /// case no drop is returned.
     ///
     #[inline]
     pub(super) fn ban_participant(
         &mut self,
         participant: &Participant,
         time: &dyn TimeSource,
-    ) -> Result<DropParticipant, CoordinatorError> {
+    ) -> Result<Option<DropParticipant>, CoordinatorError> {
         // Check that the participant is not already banned from participating.
         if self.banned.contains(&participant) {
             return Err(CoordinatorError::ParticipantAlreadyBanned);
@@ -2628,7 +3526,14 @@ impl CoordinatorState {
         // Ban of a participant can only happen aftwerwards (during contribution verification), so no actions needed here
 
         // Drop the participant from the queue, precommit, and current round.
-        let drop = self.drop_participant(participant, time)?;
+        let drop = match self.queue.contains_key(participant)
+            || self.next.contains_key(participant)
+            || self.current_contributors.contains_key(participant)
+            || self.current_verifiers.contains_key(participant)
+        {
+            true => Some(self.drop_participant(participant, time)?),
+            false => None,
+        };
 
         // Add the participant to the banned list.
         self.banned.insert(participant.clone());
@@ -2657,6 +3562,40 @@ impl CoordinatorState {
         self.blacklisted_ips.retain(|_, part| part != participant);
     }
 
+    ///
+    /// Bans the given IP from joining the queue, and drops the participants
+    /// of the queue that joined from it.
+    ///
+    /// The IPs of the queue are only tracked if `NAMADA_MPC_IP_BAN` is set.
+    ///
+    #[inline]
+    pub(super) fn ban_ip(&mut self, ip: IpAddr, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+        let queued_participants: Vec<Participant> = self
+            .runtime_state
+            .current_ips
+            .iter()
+            .filter(|(current_ip, participant)| **current_ip == ip && self.queue.contains_key(participant))
+            .map(|(_, participant)| participant.clone())
+            .collect();
+
+        for participant in queued_participants {
+            self.drop_participant(&participant, time)?;
+        }
+
+        self.banned_ips.insert(ip);
+        info!("{} was banned from the ceremony", ip);
+
+        Ok(())
+    }
+
+    ///
+    /// Unbans the given IP from joining the queue.
+    ///
+    #[inline]
+    pub(super) fn unban_ip(&mut self, ip: &IpAddr) {
+        self.banned_ips.remove(ip);
+    }
+
     ///
     /// Adds a replacement contributor from the coordinator as a current contributor
     /// and assigns them tasks from the given starting bucket ID.
@@ -2757,23 +3696,22 @@ impl CoordinatorState {
             _ => return Err(CoordinatorError::RoundHeightNotSet),
         };
 
-        // Sort the participants in the queue by time joined.
-        let mut queue: Vec<_> = self
-            .queue
-            .clone()
-            .into_par_iter()
-            .map(|(p, (r, _, ls, j))| (p, r, ls, j))
-            .collect();
-        queue.par_sort_by(|a, b| (a.3).cmp(&b.3));
-
         // Parse the queue participants into contributors and verifiers,
         // and check that they are not banned participants.
-        let contributors: Vec<(_, _, _, _)> = queue
+        let mut contributors: Vec<QueueEntry> = self
+            .queue
             .clone()
             .into_par_iter()
+            .map(|(p, (r, _, ls, j))| (p, r, ls, j))
             .filter(|(p, _, _, _)| p.is_contributor() && !self.banned.contains(&p))
             .collect();
 
+        // Sort the contributors with the queue policy of the ceremony.
+        self.environment
+            .queue_selection()
+            .policy(&self.lottery_seed)
+            .sort(&mut contributors, next_round);
+
         // Fetch the permitted number of contributors
         let maximum_contributors = self.environment.maximum_contributors_per_round();
 
@@ -2849,6 +3787,10 @@ impl CoordinatorState {
         }
 
         trace!("Marking {} current contributors as finished", newly_finished.len());
+        for (contributor, finished_info) in newly_finished.iter() {
+            self.record_slot_duration(finished_info);
+            self.raise_reliability_score(contributor, COMPLETION_REWARD);
+        }
 
         // Update the map of finished contributors.
         match self.finished_contributors.get_mut(&current_round_height) {
@@ -2875,18 +3817,22 @@ impl CoordinatorState {
             .collect())
     }
 
-    pub(super) fn update_dropped_queued_participants(&mut self, time: &dyn TimeSource) -> Result<(), CoordinatorError> {
+    pub(super) fn update_dropped_queued_participants(
+        &mut self,
+        time: &dyn TimeSource,
+    ) -> Result<Vec<DropParticipant>, CoordinatorError> {
         let queue_seen_timeout = self.environment.queue_seen_timeout();
 
         let now = time.now_utc();
+        let mut drops = Vec::new();
 
         for (participant, (_, _, last_seen, _)) in self.queue.clone() {
             if now - last_seen > queue_seen_timeout {
-                let _ = self.drop_participant(&participant, time)?;
+                drops.push(self.drop_participant(&participant, time)?);
             }
         }
 
-        Ok(())
+        Ok(drops)
     }
 
     /// This will drop a participant (verifier or contributor) if it
@@ -2928,6 +3874,9 @@ impl CoordinatorState {
                         participant_lock_timeout.whole_seconds(),
                         exceeded_chunks_string,
                     );
+                    if participant.is_contributor() {
+                        self.lower_reliability_score(participant, TIMEOUT_PENALTY);
+                    }
                     Some(self.drop_participant(participant, time))
                 } else {
                     None
@@ -2966,7 +3915,7 @@ impl CoordinatorState {
                         elapsed.whole_seconds()
                     );
                     // Drop the participant.
-                    Some(self.drop_participant(participant, time))
+                    Some(self.drop_timed_out_contributor(participant, participant_info.reliability, time))
                 } else {
                     None
                 }
@@ -2974,6 +3923,76 @@ impl CoordinatorState {
             .collect()
     }
 
+    /// Drops a contributor which missed its heartbeats, lowering its reliability score, and, if the environment
+    /// allows it, puts it back at the end of the queue, unless it's going to be banned.
+    fn drop_timed_out_contributor(
+        &mut self,
+        participant: &Participant,
+        reliability: u8,
+        time: &dyn TimeSource,
+    ) -> Result<DropParticipant, CoordinatorError> {
+        self.lower_reliability_score(participant, TIMEOUT_PENALTY);
+        if !self.environment.requeue_timed_out_contributors() {
+            return self.drop_participant(participant, time);
+        }
+
+        self.drop_and_requeue(participant, reliability.saturating_sub(TIMEOUT_PENALTY), time)
+    }
+
+    ///
+    /// Drops a current contributor which gave up on its contribution, releasing its locks, and
+    /// puts it back at the end of the queue, unless it's going to be banned.
+    ///
+    pub(super) fn abandon_contribution(
+        &mut self,
+        participant: &Participant,
+        time: &dyn TimeSource,
+    ) -> Result<DropParticipant, CoordinatorError> {
+        let reliability = self
+            .current_contributors
+            .get(participant)
+            .ok_or_else(|| CoordinatorError::ParticipantNotFound(participant.clone()))?
+            .reliability;
+        info!("{} gave up on its contribution", participant);
+
+        self.drop_and_requeue(participant, reliability, time)
+    }
+
+    /// Drops a contributor and puts it back at the end of the queue with the given reliability score, and the token
+    /// and the ip it joined with, unless it's going to be banned.
+    fn drop_and_requeue(
+        &mut self,
+        participant: &Participant,
+        reliability: u8,
+        time: &dyn TimeSource,
+    ) -> Result<DropParticipant, CoordinatorError> {
+        // The drop releases the token and the ip of the participant, keep them to rejoin the queue. When tokens
+        // are not blacklisted they are not tracked, and the empty token is never stored
+        let token = self
+            .runtime_state
+            .tokens_in_use
+            .iter()
+            .find(|(_, p)| *p == participant)
+            .map(|(token, _)| token.clone())
+            .unwrap_or_default();
+        let ip = self
+            .runtime_state
+            .current_ips
+            .iter()
+            .find(|(_, p)| *p == participant)
+            .map(|(ip, _)| *ip);
+
+        let drop = self.drop_participant(participant, time)?;
+
+        let number_of_drops = self.dropped.iter().filter(|dropped| dropped.id == *participant).count();
+        if number_of_drops <= self.environment.participant_ban_threshold() as usize {
+            info!("Putting {} back at the end of the queue", participant);
+            self.add_to_queue(participant.clone(), ip, token, reliability, time)?;
+        }
+
+        Ok(drop)
+    }
+
     ///
     /// Updates the list of dropped participants for participants who
     /// meet the ban criteria of the coordinator.
@@ -3598,7 +4617,7 @@ pub enum CeremonyStorageAction {
 #[derive(Debug)]
 pub(crate) struct DropCurrentParticpantData {
     /// The participant being dropped.
-    _participant: Participant,
+    participant: Participant,
     /// Action to perform to update the round/storage after the drop
     /// to match the current coordinator state.
     pub storage_action: CeremonyStorageAction,
@@ -3607,7 +4626,7 @@ pub(crate) struct DropCurrentParticpantData {
 #[derive(Debug)]
 pub(crate) struct DropQueueParticipantData {
     /// The participant being dropped.
-    _participant: Participant,
+    participant: Participant,
 }
 
 /// Returns information/actions for the coordinator to perform in
@@ -3622,6 +4641,16 @@ pub(crate) enum DropParticipant {
     DropQueue(DropQueueParticipantData),
 }
 
+impl DropParticipant {
+    /// Returns the participant being dropped.
+    pub(crate) fn participant(&self) -> &Participant {
+        match self {
+            DropParticipant::DropCurrent(data) => &data.participant,
+            DropParticipant::DropQueue(data) => &data.participant,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::Ipv4Addr;
@@ -3629,6 +4658,7 @@ mod tests {
     use crate::{
         coordinator_state::*,
         environment::{Parameters, Testing},
+        queue_policy::QueueSelection,
         testing::prelude::*,
         CoordinatorState, MockTimeSource, SystemTimeSource,
     };
@@ -3665,6 +4695,257 @@ mod tests {
         assert_eq!(Some(current_round_height), state.current_round_height);
     }
 
+    #[test]
+    fn test_register_request_signature() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone());
+        let window = Duration::minutes(5);
+        let timestamp = time.now_utc().unix_timestamp();
+
+        state
+            .register_request_signature("pubkey", timestamp, "signature", window, &time)
+            .unwrap();
+        state
+            .register_request_signature("pubkey", timestamp, "other signature", window, &time)
+            .unwrap();
+
+        // Replay of the same request
+        assert!(matches!(
+            state.register_request_signature("pubkey", timestamp, "signature", window, &time),
+            Err(CoordinatorError::RequestReplayed)
+        ));
+
+        // Replay with the signature or the key in uppercase, which decode to the same bytes
+        assert!(matches!(
+            state.register_request_signature("pubkey", timestamp, "SIGNATURE", window, &time),
+            Err(CoordinatorError::RequestReplayed)
+        ));
+        assert!(matches!(
+            state.register_request_signature("PUBKEY", timestamp, "signature", window, &time),
+            Err(CoordinatorError::RequestReplayed)
+        ));
+
+        // The signatures are tracked across a restart of the coordinator
+        let mut state: CoordinatorState = serde_json::from_slice(&serde_json::to_vec(&state).unwrap()).unwrap();
+        assert!(matches!(
+            state.register_request_signature("pubkey", timestamp, "signature", window, &time),
+            Err(CoordinatorError::RequestReplayed)
+        ));
+
+        // Signatures out of the window are forgotten
+        time.update(|t| t + Duration::minutes(10));
+        state
+            .register_request_signature(
+                "other pubkey",
+                time.now_utc().unix_timestamp(),
+                "signature",
+                window,
+                &time,
+            )
+            .unwrap();
+        assert_eq!(1, state.request_signatures.len());
+    }
+
+    #[test]
+    fn test_estimated_wait_seconds() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone());
+        assert_eq!(None, state.estimated_wait_seconds(1, &time));
+
+        let contributor = Participant::new_contributor("contributor");
+        let mut info = ParticipantInfo::new(contributor.clone(), 1, 10, 0, &time);
+        info.started_at = Some(time.now_utc());
+        info.finished_at = Some(time.now_utc() + Duration::seconds(60));
+        state.record_slot_duration(&info);
+        info.finished_at = None;
+        info.dropped_at = Some(time.now_utc() + Duration::seconds(120));
+        state.record_slot_duration(&info);
+        assert_eq!(Some(90), state.average_slot_duration());
+        assert_eq!(Some(180), state.estimated_wait_seconds(2, &time));
+
+        // The elapsed part of the current slot is deducted
+        info.dropped_at = None;
+        state.current_contributors.insert(contributor, info);
+        time.update(|t| t + Duration::seconds(30));
+        assert_eq!(Some(150), state.estimated_wait_seconds(2, &time));
+
+        // Only the most recent slots are averaged
+        for _ in 0..SLOT_DURATION_SAMPLES {
+            let mut info = ParticipantInfo::new(Participant::new_contributor("other"), 1, 10, 0, &time);
+            info.started_at = Some(time.now_utc());
+            info.finished_at = Some(time.now_utc() + Duration::seconds(10));
+            state.record_slot_duration(&info);
+        }
+        assert_eq!(Some(10), state.average_slot_duration());
+    }
+
+    #[test]
+    fn test_ceremony_stats() {
+        let time = MockTimeSource::new(OffsetDateTime::from_unix_timestamp(3 * 3600).unwrap());
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone());
+        let stats = state.ceremony_stats();
+        assert!(stats.contributions_per_hour.is_empty());
+        assert_eq!(None, stats.median_contribution_seconds);
+        assert_eq!(0.0, stats.dropout_rate);
+
+        // Contributions of 60, 120 and 600 seconds, the last one finished in the next hour
+        for (round_height, (started, seconds)) in [(0, 60), (100, 120), (3000, 600)].iter().enumerate() {
+            let contributor = Participant::new_contributor(&format!("contributor {}", round_height));
+            let mut info = ParticipantInfo::new(contributor.clone(), round_height as u64, 10, 0, &time);
+            info.started_at = Some(time.now_utc() + Duration::seconds(*started));
+            info.finished_at = Some(time.now_utc() + Duration::seconds(started + seconds));
+            state
+                .finished_contributors
+                .entry(round_height as u64)
+                .or_default()
+                .insert(contributor, info);
+        }
+        let mut dropped = ParticipantInfo::new(Participant::new_contributor("dropped"), 3, 10, 0, &time);
+        dropped.started_at = Some(time.now_utc());
+        state.dropped.push(dropped);
+
+        let stats = state.ceremony_stats();
+        assert_eq!(
+            stats.contributions_per_hour,
+            vec![
+                HourlyContributions {
+                    hour: 3 * 3600,
+                    contributions: 2
+                },
+                HourlyContributions {
+                    hour: 4 * 3600,
+                    contributions: 1
+                },
+            ]
+        );
+        assert_eq!(Some(260), stats.average_contribution_seconds);
+        assert_eq!(Some(120), stats.median_contribution_seconds);
+        assert_eq!(0.25, stats.dropout_rate);
+        assert_eq!(0, stats.verification_backlog);
+    }
+
+    #[test]
+    fn test_login_nonces() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone());
+
+        let nonce = state.issue_login_nonce("pubkey", &time);
+        // Wrong, issued to another key
+        assert!(matches!(
+            state.redeem_login_nonce("other pubkey", &nonce, &time),
+            Err(CoordinatorError::LoginNonceInvalid)
+        ));
+
+        let nonce = state.issue_login_nonce("pubkey", &time);
+        state.redeem_login_nonce("pubkey", &nonce, &time).unwrap();
+        // Wrong, already redeemed
+        assert!(matches!(
+            state.redeem_login_nonce("pubkey", &nonce, &time),
+            Err(CoordinatorError::LoginNonceInvalid)
+        ));
+
+        // Wrong, expired
+        let nonce = state.issue_login_nonce("pubkey", &time);
+        time.update(|t| t + Duration::minutes(10));
+        assert!(matches!(
+            state.redeem_login_nonce("pubkey", &nonce, &time),
+            Err(CoordinatorError::LoginNonceInvalid)
+        ));
+    }
+
+    #[test]
+    fn test_invite_codes() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone());
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+
+        let expires_at = Some(time.now_utc() + Duration::hours(1));
+        let minted = state.mint_invite_codes(2, Some("partners".to_string()), expires_at, &time);
+        assert_eq!(2, minted.len());
+        assert_ne!(minted[0].code, minted[1].code);
+        assert_eq!(2, state.invite_codes().len());
+
+        // Unknown code
+        assert!(matches!(
+            state.invite_code_checks("unknown", &time),
+            Err(CoordinatorError::InviteCodeInvalid)
+        ));
+
+        // Redeem the first code only once
+        state.redeem_invite_code(&minted[0].code, &contributor, &time).unwrap();
+        assert!(matches!(
+            state.redeem_invite_code(&minted[0].code, &contributor, &time),
+            Err(CoordinatorError::InviteCodeAlreadyRedeemed)
+        ));
+        assert_eq!(Some(&contributor), state.invite_codes[&minted[0].code].redeemed_by.as_ref());
+
+        // The second code expires
+        time.update(|t| t + Duration::hours(2));
+        assert!(matches!(
+            state.invite_code_checks(&minted[1].code, &time),
+            Err(CoordinatorError::InviteCodeExpired)
+        ));
+    }
+
+    #[test]
+    fn test_priority_tokens() {
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone());
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+
+        state.priority_token_checks("00ff").unwrap();
+        state.redeem_priority_token("00ff", &contributor).unwrap();
+        assert!(matches!(
+            state.redeem_priority_token("00ff", &contributor),
+            Err(CoordinatorError::PriorityTokenAlreadyRedeemed)
+        ));
+        assert_eq!(Some(&contributor), state.redeemed_priority_tokens().get("00ff"));
+    }
+
+    #[test]
+    fn test_external_verifications() {
+        let verifiers = ["verifier_1".to_string(), "verifier_2".to_string(), "verifier_3".to_string()];
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .external_verifiers(&verifiers, 2)
+            .into();
+        let mut state = CoordinatorState::new(environment);
+        let task = Task::new(0, 1);
+        let other_task = Task::new(1, 1);
+
+        // Nothing to verify
+        assert_eq!(None, state.lock_verification_task(&verifiers[0]));
+
+        state.add_pending_verification(&task).unwrap();
+        state.add_pending_verification(&other_task).unwrap();
+        assert_eq!(ExternalVerificationStatus::Pending, state.external_verification_status(&task));
+
+        // The lock is kept until the result is sent
+        assert_eq!(Some(task), state.lock_verification_task(&verifiers[0]));
+        assert_eq!(Some(task), state.lock_verification_task(&verifiers[0]));
+        assert!(matches!(
+            state.add_external_verification(&verifiers[0], &other_task, true),
+            Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant)
+        ));
+        state.add_external_verification(&verifiers[0], &task, true).unwrap();
+        assert_eq!(None, state.locked_verification_task(&verifiers[0]));
+
+        // A verifier doesn't verify the same task twice
+        assert_eq!(Some(other_task), state.lock_verification_task(&verifiers[0]));
+
+        // The verifiers disagree, the third one decides
+        assert_eq!(Some(task), state.lock_verification_task(&verifiers[1]));
+        state.add_external_verification(&verifiers[1], &task, false).unwrap();
+        assert_eq!(ExternalVerificationStatus::Pending, state.external_verification_status(&task));
+        assert_eq!(Some(task), state.lock_verification_task(&verifiers[2]));
+        state.add_external_verification(&verifiers[2], &task, false).unwrap();
+        assert_eq!(ExternalVerificationStatus::Rejected, state.external_verification_status(&task));
+
+        // The results are discarded with the pending verification
+        state.remove_pending_verification(&task).unwrap();
+        state.remove_pending_verification(&other_task).unwrap();
+        assert!(state.external_verifications.is_empty());
+        assert!(state.verifier_locks.is_empty());
+    }
+
     #[test]
     fn test_add_to_queue_contributor() {
         let time = SystemTimeSource::new();
@@ -3709,6 +4990,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_contributor_cooldown() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .contributor_cooldown(2, Duration::hours(1), true)
+            .into();
+        let contributor = TEST_CONTRIBUTOR_ID.clone();
+        let contributor_2 = TEST_CONTRIBUTOR_ID_2.clone();
+        let contributor_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        let mut state = CoordinatorState::new(environment);
+        state.initialize(1);
+        state
+            .add_to_queue(contributor.clone(), Some(contributor_ip), String::from("test_token"), 10, &time)
+            .unwrap();
+        state.record_contribution(&contributor, &time);
+        state.drop_participant(&contributor, &time).unwrap();
+
+        // Wrong, the contributor and its ip are in cool-down
+        assert!(matches!(
+            state.cooldown_checks(&contributor, None, &time),
+            Err(CoordinatorError::ParticipantInCooldown)
+        ));
+        assert!(matches!(
+            state.cooldown_checks(&contributor_2, Some(&contributor_ip), &time),
+            Err(CoordinatorError::ParticipantInCooldown)
+        ));
+        assert!(state.cooldown_checks(&contributor_2, None, &time).is_ok());
+
+        // The duration has elapsed but not the rounds
+        time.update(|t| t + Duration::hours(2));
+        assert!(state.cooldown_checks(&contributor, None, &time).is_err());
+
+        // Both the rounds and the duration have elapsed
+        state.current_round_height = Some(3);
+        assert!(state.cooldown_checks(&contributor, Some(&contributor_ip), &time).is_ok());
+    }
+
     #[test]
     fn test_add_duplicate_ip_to_queue_contributor() {
         let time = SystemTimeSource::new();
@@ -3937,6 +5256,106 @@ mod tests {
         assert_eq!(Some(current_round_height), state.current_round_height);
     }
 
+    #[test]
+    fn test_update_queue_priority() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment = TEST_ENVIRONMENT.clone();
+        let mut state = CoordinatorState::new(environment.clone());
+        state.initialize(5);
+
+        // Fill the next round with contributors of the default reliability
+        let maximum_contributors_per_round = environment.maximum_contributors_per_round();
+        for id in 1..=maximum_contributors_per_round {
+            let contributor = Participant::Contributor(id.to_string());
+            let contributor_ip = IpAddr::V4(format!("0.0.0.{}", id).parse().unwrap());
+            let token = format!("test_token_{}", id);
+            state
+                .add_to_queue(contributor, Some(contributor_ip), token, 10, &time)
+                .unwrap();
+            time.update(|t| t + Duration::seconds(1));
+        }
+
+        // A contributor joining last with a higher reliability takes the slot of the last one who joined
+        let priority = Participant::Contributor("priority".to_string());
+        let priority_ip = IpAddr::V4("0.0.1.0".parse().unwrap());
+        let token = "test_token_priority".to_string();
+        state
+            .add_to_queue(priority.clone(), Some(priority_ip), token, 20, &time)
+            .unwrap();
+        state.update_queue().unwrap();
+
+        assert_eq!(Some(6), state.queue[&priority].1);
+        let last = Participant::Contributor(maximum_contributors_per_round.to_string());
+        assert_eq!(Some(7), state.queue[&last].1);
+        let first = Participant::Contributor(1.to_string());
+        assert_eq!(Some(6), state.queue[&first].1);
+    }
+
+    #[test]
+    fn test_update_queue_lottery() {
+        let time = SystemTimeSource::new();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .queue_selection(QueueSelection::Lottery)
+            .into();
+        let mut state = CoordinatorState::new(environment.clone());
+        state.initialize(5);
+
+        // Fill the next three rounds
+        let number_of_contributors = 3 * environment.maximum_contributors_per_round();
+        for id in 1..=number_of_contributors {
+            let contributor = Participant::Contributor(id.to_string());
+            let contributor_ip = IpAddr::V4(format!("0.0.0.{}", id).parse().unwrap());
+            let token = format!("test_token_{}", id);
+            state
+                .add_to_queue(contributor, Some(contributor_ip), token, 10, &time)
+                .unwrap();
+        }
+        state.update_queue().unwrap();
+        let draw: HashMap<_, _> = state
+            .queue
+            .iter()
+            .map(|(participant, (_, round, _, _))| (participant.clone(), *round))
+            .collect();
+        assert_eq!(number_of_contributors, draw.len());
+
+        // The draw holds across the updates of the queue and the restarts of the coordinator
+        let mut state: CoordinatorState = serde_json::from_slice(&serde_json::to_vec(&state).unwrap()).unwrap();
+        state.update_queue().unwrap();
+        for (participant, (_, round, _, _)) in state.queue.iter() {
+            assert_eq!(draw[participant], *round);
+        }
+    }
+
+    #[test]
+    fn test_reliability_scores() {
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone());
+        let contributor = Participant::new_contributor("contributor");
+        assert_eq!(DEFAULT_RELIABILITY_SCORE, state.reliability_score(&contributor));
+
+        // The completed contributions raise the score up to the highest earned one
+        for _ in 0..2 * MAXIMUM_EARNED_RELIABILITY_SCORE {
+            state.raise_reliability_score(&contributor, COMPLETION_REWARD);
+        }
+        assert_eq!(MAXIMUM_EARNED_RELIABILITY_SCORE, state.reliability_score(&contributor));
+
+        // The invalid contributions lower it, down to zero
+        state.record_invalid_contribution(&contributor);
+        assert_eq!(
+            MAXIMUM_EARNED_RELIABILITY_SCORE - INVALID_CONTRIBUTION_PENALTY,
+            state.reliability_score(&contributor)
+        );
+        for _ in 0..MAXIMUM_EARNED_RELIABILITY_SCORE {
+            state.lower_reliability_score(&contributor, TIMEOUT_PENALTY);
+        }
+        assert_eq!(0, state.reliability_score(&contributor));
+
+        // The scores are kept across the restarts of the coordinator
+        let state: CoordinatorState = serde_json::from_slice(&serde_json::to_vec(&state).unwrap()).unwrap();
+        assert_eq!(0, state.reliability_score(&contributor));
+        let other = Participant::new_contributor("other");
+        assert_eq!(DEFAULT_RELIABILITY_SCORE, state.reliability_score(&other));
+    }
+
     #[test]
     fn test_remove_from_queue_contributor() {
         let time = SystemTimeSource::new();
@@ -0,0 +1,304 @@
+//! Email notifications of the contributors who registered an address when joining the queue, sent once their turn is
+//! getting close and again when their contribution starts, for long queues not to end up in no-shows.
+//!
+//! The service is selected with `NAMADA_MPC_EMAIL_BACKEND`: the SendGrid API, see [SendGrid], or an SMTP relay with
+//! the `smtp` feature, see [Smtp]. The sender is set with `NAMADA_MPC_EMAIL_FROM`.
+
+use serde_json::json;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// Timeout of the requests to the email service
+pub const EMAIL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum length of an email address
+const MAX_ADDRESS_LEN: usize = 254;
+
+const SENDGRID_URL: &str = "https://api.sendgrid.com/v3/mail/send";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EmailError {
+    #[error("Invalid configuration of the email notifications: {0}")]
+    Configuration(String),
+    #[error("Invalid email address {0}")]
+    InvalidAddress(String),
+    #[error("The email service rejected the email: {0}")]
+    Rejected(String),
+    #[error("Error while sending the email: {0}")]
+    Request(String),
+}
+
+/// A service delivering the email notifications.
+#[rocket::async_trait]
+pub trait Mailer: Send + Sync {
+    /// Sends a plain text email to the given address.
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), EmailError>;
+}
+
+/// Connects to the email service selected by `NAMADA_MPC_EMAIL_BACKEND`, `sendgrid` or `smtp`. Returns [`None`] if
+/// the variable is not set, the notifications being disabled.
+pub fn mailer_from_env() -> Result<Option<Box<dyn Mailer>>, EmailError> {
+    let mailer: Box<dyn Mailer> = match std::env::var("NAMADA_MPC_EMAIL_BACKEND").as_deref() {
+        Err(_) => return Ok(None),
+        Ok("sendgrid") => Box::new(SendGrid::from_env()?),
+        #[cfg(feature = "smtp")]
+        Ok("smtp") => Box::new(Smtp::from_env()?),
+        Ok(other) => {
+            return Err(EmailError::Configuration(format!("unknown email backend {}", other)));
+        }
+    };
+
+    Ok(Some(mailer))
+}
+
+fn env_var(name: &str) -> Result<String, EmailError> {
+    std::env::var(name).map_err(|_| EmailError::Configuration(format!("missing {}", name)))
+}
+
+/// Checks the email address registered by a contributor, before it's used as a recipient.
+pub fn validate_address(address: &str) -> Result<(), EmailError> {
+    let valid_chars = !address
+        .chars()
+        .any(|c| c.is_whitespace() || c.is_control() || "<>()[],;:\\\"".contains(c));
+    let valid_parts = match address.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+        }
+        None => false,
+    };
+
+    if address.len() <= MAX_ADDRESS_LEN && valid_chars && valid_parts {
+        Ok(())
+    } else {
+        Err(EmailError::InvalidAddress(address.to_string()))
+    }
+}
+
+/// The [SendGrid](https://sendgrid.com) mail send API, authenticated with `NAMADA_MPC_SENDGRID_API_KEY`.
+pub struct SendGrid {
+    client: reqwest::Client,
+    api_key: String,
+    from: String,
+}
+
+impl SendGrid {
+    pub fn from_env() -> Result<Self, EmailError> {
+        Ok(Self {
+            client: reqwest::Client::new(),
+            api_key: env_var("NAMADA_MPC_SENDGRID_API_KEY")?,
+            from: env_var("NAMADA_MPC_EMAIL_FROM")?,
+        })
+    }
+}
+
+#[rocket::async_trait]
+impl Mailer for SendGrid {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), EmailError> {
+        let email = json!({
+            "personalizations": [{ "to": [{ "email": to }] }],
+            "from": { "email": self.from },
+            "subject": subject,
+            "content": [{ "type": "text/plain", "value": body }],
+        });
+        let response = self
+            .client
+            .post(SENDGRID_URL)
+            .timeout(EMAIL_TIMEOUT)
+            .bearer_auth(&self.api_key)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(email.to_string())
+            .send()
+            .await
+            .map_err(|e| EmailError::Request(e.to_string()))?;
+
+        response
+            .error_for_status()
+            .map(|_| ())
+            .map_err(|e| EmailError::Rejected(e.to_string()))
+    }
+}
+
+/// An SMTP relay reached with STARTTLS at `NAMADA_MPC_SMTP_HOST`, on `NAMADA_MPC_SMTP_PORT` if set, authenticated with
+/// `NAMADA_MPC_SMTP_USERNAME` and `NAMADA_MPC_SMTP_PASSWORD`.
+#[cfg(feature = "smtp")]
+pub struct Smtp {
+    transport: lettre::AsyncSmtpTransport<lettre::Tokio1Executor>,
+    from: lettre::message::Mailbox,
+}
+
+#[cfg(feature = "smtp")]
+impl Smtp {
+    pub fn from_env() -> Result<Self, EmailError> {
+        use lettre::transport::smtp::authentication::Credentials;
+
+        let host = env_var("NAMADA_MPC_SMTP_HOST")?;
+        let mut builder = lettre::AsyncSmtpTransport::<lettre::Tokio1Executor>::starttls_relay(&host)
+            .map_err(|e| EmailError::Configuration(e.to_string()))?
+            .credentials(Credentials::new(
+                env_var("NAMADA_MPC_SMTP_USERNAME")?,
+                env_var("NAMADA_MPC_SMTP_PASSWORD")?,
+            ));
+        if let Ok(port) = std::env::var("NAMADA_MPC_SMTP_PORT") {
+            let port = port
+                .parse()
+                .map_err(|_| EmailError::Configuration(format!("invalid smtp port {}", port)))?;
+            builder = builder.port(port);
+        }
+        let from = env_var("NAMADA_MPC_EMAIL_FROM")?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from: from
+                .parse()
+                .map_err(|_| EmailError::Configuration(format!("invalid sender {}", from)))?,
+        })
+    }
+}
+
+#[cfg(feature = "smtp")]
+#[rocket::async_trait]
+impl Mailer for Smtp {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), EmailError> {
+        use lettre::AsyncTransport;
+
+        let email = lettre::Message::builder()
+            .from(self.from.clone())
+            .to(to.parse().map_err(|_| EmailError::InvalidAddress(to.to_string()))?)
+            .subject(subject)
+            .body(body.to_string())
+            .map_err(|e| EmailError::Request(e.to_string()))?;
+
+        self.transport
+            .send(email)
+            .await
+            .map(|_| ())
+            .map_err(|e| EmailError::Rejected(e.to_string()))
+    }
+}
+
+/// A notification due to a contributor, see
+/// [`Coordinator::email_notifications`](crate::Coordinator::email_notifications).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EmailNotification {
+    /// The contributor is the given number of rounds away from its turn
+    ApproachingSlot {
+        address: String,
+        queue_position: u64,
+        estimated_wait_seconds: Option<u64>,
+    },
+    /// The contributor locked the challenge, its contribution started
+    LockAcquired { address: String },
+}
+
+impl EmailNotification {
+    /// Returns the address of the contributor.
+    pub fn address(&self) -> &str {
+        match self {
+            Self::ApproachingSlot { address, .. } | Self::LockAcquired { address } => address,
+        }
+    }
+
+    pub fn subject(&self) -> &'static str {
+        match self {
+            Self::ApproachingSlot { .. } => "Your turn in the Namada Trusted Setup is coming",
+            Self::LockAcquired { .. } => "Your contribution to the Namada Trusted Setup started",
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match self {
+            Self::ApproachingSlot {
+                queue_position,
+                estimated_wait_seconds,
+                ..
+            } => {
+                let wait = match estimated_wait_seconds {
+                    Some(seconds) => format!(", in about {} minutes", (seconds + 59) / 60),
+                    None => String::new(),
+                };
+                format!(
+                    "You are {} round(s) away from your turn to contribute to the Namada Trusted Setup{}.\n\nKeep \
+                     your client running: it locks the challenge as soon as your turn comes, otherwise your slot is \
+                     given to the next contributor.",
+                    queue_position, wait
+                )
+            }
+            Self::LockAcquired { .. } => String::from(
+                "Your client locked the challenge, your contribution to the Namada Trusted Setup started.\n\nKeep \
+                 it running until the contribution is uploaded.",
+            ),
+        }
+    }
+
+    /// Sends the notification. Failures are only logged, the notifications are not retried.
+    pub async fn send(&self, mailer: &dyn Mailer) {
+        match mailer.send(self.address(), self.subject(), &self.body()).await {
+            Ok(()) => debug!("Sent the email notification \"{}\"", self.subject()),
+            Err(e) => warn!(
+                "Error while sending the email notification \"{}\": {}",
+                self.subject(),
+                e
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_address() {
+        assert!(validate_address("contributor@example.com").is_ok());
+        assert!(validate_address("first.last+mpc@mail.example.org").is_ok());
+
+        // Wrong, not an address
+        for address in [
+            "",
+            "contributor",
+            "@example.com",
+            "contributor@",
+            "contributor@localhost",
+            "contributor@example.com.",
+            "a@b@example.com",
+            "contributor @example.com",
+            "contributor@example.com\r\nBcc: other@example.com",
+            "Contributor <contributor@example.com>",
+        ] {
+            assert_eq!(
+                validate_address(address),
+                Err(EmailError::InvalidAddress(address.to_string()))
+            );
+        }
+
+        // Wrong, too long
+        assert!(validate_address(&format!("{}@example.com", "a".repeat(MAX_ADDRESS_LEN))).is_err());
+    }
+
+    #[test]
+    fn test_notification_body() {
+        let notification = EmailNotification::ApproachingSlot {
+            address: String::from("contributor@example.com"),
+            queue_position: 2,
+            estimated_wait_seconds: Some(601),
+        };
+        assert_eq!(notification.address(), "contributor@example.com");
+        assert!(notification.body().starts_with(
+            "You are 2 round(s) away from your turn to contribute to the Namada Trusted Setup, in about 11 minutes."
+        ));
+
+        let notification = EmailNotification::ApproachingSlot {
+            address: String::from("contributor@example.com"),
+            queue_position: 1,
+            estimated_wait_seconds: None,
+        };
+        assert!(notification
+            .body()
+            .starts_with("You are 1 round(s) away from your turn to contribute to the Namada Trusted Setup."));
+    }
+}
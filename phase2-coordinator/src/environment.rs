@@ -1,9 +1,17 @@
-use crate::{authentication::KeyPair, objects::Participant, storage::Disk};
+use crate::{
+    authentication::{KeyPair, SignatureScheme, SigningBackend},
+    objects::Participant,
+    queue_policy::QueueSelection,
+    storage::Disk,
+};
 pub use phase2::{helpers::CurveKind, ContributionMode, ProvingSystem};
 use setup_utils::{CheckForCorrectness, UseCompression};
 
+use ipnet::IpNet;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
+use serde_with::DisplayFromStr;
+use std::net::IpAddr;
 
 type BatchSize = usize;
 type ChunkSize = usize;
@@ -219,6 +227,126 @@ impl Parameters {
     }
 }
 
+/// The maximum sizes in bytes of the bodies of the requests to the REST API, per category of route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BodyLimits {
+    /// The routes expecting a token or a number only, such as `/contributor/join_queue`
+    pub tiny: u64,
+    /// The other routes with a json body
+    pub json: u64,
+    /// The direct uploads of the contributions to `/contributor/upload_chunk`, whole or in parts
+    pub contribution: u64,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            tiny: 1 << 10,
+            json: 4 << 20,
+            contribution: 1 << 30,
+        }
+    }
+}
+
+impl BodyLimits {
+    /// Reads the limits from the `NAMADA_MPC_BODY_LIMIT_TINY`, `NAMADA_MPC_BODY_LIMIT_JSON` and
+    /// `NAMADA_MPC_BODY_LIMIT_CONTRIBUTION` env variables, the unset ones keeping their default.
+    pub fn from_env() -> Self {
+        let limit = |name: &str, default: u64| match std::env::var(name) {
+            Ok(bytes) => bytes.parse().unwrap(),
+            Err(_) => default,
+        };
+        let default = Self::default();
+
+        Self {
+            tiny: limit("NAMADA_MPC_BODY_LIMIT_TINY", default.tiny),
+            json: limit("NAMADA_MPC_BODY_LIMIT_JSON", default.json),
+            contribution: limit("NAMADA_MPC_BODY_LIMIT_CONTRIBUTION", default.contribution),
+        }
+    }
+}
+
+/// Reads a comma separated list of networks from an env variable, in the CIDR notation or as single addresses.
+fn networks_from_env(name: &str) -> Vec<IpNet> {
+    match std::env::var(name) {
+        Ok(networks) => networks
+            .split(',')
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(|n| match n.parse::<IpAddr>() {
+                Ok(ip) => IpNet::from(ip),
+                Err(_) => n.parse().unwrap(),
+            })
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// The circuits whose parameters are updated by the contributions, as generated by `masp-mpc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Circuits {
+    /// The number of circuits, a contribution appending a public key to the parameters of each of them
+    pub count: u64,
+    /// The size in bytes of the initial parameters of all the circuits
+    pub parameters_size: u64,
+}
+
+impl Circuits {
+    /// The MASP Spend, Output and Convert circuits.
+    pub const MASP: Self = Self {
+        count: 3,
+        parameters_size: 84_720_180,
+    };
+    /// The test circuit of `masp-mpc`, contributed to by the debug builds of the client.
+    pub const TEST: Self = Self {
+        count: 1,
+        parameters_size: 2_268,
+    };
+}
+
+impl Default for Circuits {
+    fn default() -> Self {
+        match cfg!(debug_assertions) {
+            true => Self::TEST,
+            false => Self::MASP,
+        }
+    }
+}
+
+/// The networks from which a category of routes of the REST API can be reached.
+#[serde_with::serde_as]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpFilter {
+    /// The networks allowed, any address being allowed if empty
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub allow: Vec<IpNet>,
+    /// The networks denied, even if they are part of the allowed ones
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub deny: Vec<IpNet>,
+}
+
+impl IpFilter {
+    /// Reads the networks from the `<prefix>_ALLOW` and `<prefix>_DENY` env variables.
+    pub fn from_env(prefix: &str) -> Self {
+        Self {
+            allow: networks_from_env(&format!("{}_ALLOW", prefix)),
+            deny: networks_from_env(&format!("{}_DENY", prefix)),
+        }
+    }
+
+    /// Whether a client with the given address is let through. A client whose address is unknown is only let through
+    /// if no network is explicitly allowed.
+    pub fn allows(&self, ip: Option<&IpAddr>) -> bool {
+        match ip {
+            Some(ip) => {
+                (self.allow.is_empty() || self.allow.iter().any(|net| net.contains(ip)))
+                    && !self.deny.iter().any(|net| net.contains(ip))
+            }
+            None => self.allow.is_empty(),
+        }
+    }
+}
+
 #[serde_with::serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Environment {
@@ -230,6 +358,9 @@ pub struct Environment {
     compressed_outputs: UseCompression,
     /// The input correctness check preference of the coordinator.
     check_input_for_correctness: CheckForCorrectness,
+    /// The circuits of the contribution files, whose size depends on them and on the curve of the parameters.
+    #[serde(default)]
+    circuits: Circuits,
 
     /// The minimum number of contributors permitted to participate in a round.
     minimum_contributors_per_round: usize,
@@ -257,6 +388,10 @@ pub struct Environment {
     participant_lock_timeout: time::Duration,
     /// The maximum duration a queued contributor can go without a heartbeat.
     queue_seen_timeout: time::Duration,
+    /// The setting to put back at the end of the queue, instead of just dropping them, the contributors
+    /// timed out because of missed heartbeats.
+    #[serde(default)]
+    requeue_timed_out_contributors: bool,
     /// The number of drops tolerated by a participant before banning them from future rounds.
     participant_ban_threshold: u16,
     /// The setting to allow current contributors to join the queue for the next round.
@@ -265,13 +400,97 @@ pub struct Environment {
     allow_current_verifiers_in_queue: bool,
     /// The minimum number of seconds to wait after aggregation before starting the next round.
     queue_wait_time: u64,
+    /// The number of rounds after contributing during which a contributor can't join the queue again.
+    #[serde(default)]
+    contributor_cooldown_rounds: u64,
+    /// The duration after contributing during which a contributor can't join the queue again.
+    #[serde(default)]
+    contributor_cooldown_duration: time::Duration,
+    /// The setting to apply the cool-down to the IP of the contributor too.
+    #[serde(default)]
+    contributor_cooldown_ip: bool,
+    /// The number of consecutive failed uploads or verifications after which a contributor is dropped, 0 to never
+    /// drop it for its failures.
+    #[serde(default)]
+    contributor_failure_threshold: u32,
+    /// The duration during which a contributor dropped for its failures can't join the queue again.
+    #[serde(default)]
+    contributor_failure_ban_duration: time::Duration,
+    /// The number of requests a client can burst to the rate-limited endpoints, 0 to disable the rate limiting.
+    #[serde(default)]
+    rate_limit_requests: u32,
+    /// The duration over which the requests allowed to a client are refilled.
+    #[serde(default)]
+    rate_limit_period: time::Duration,
+    /// The public keys of the external verifiers allowed to verify the contributions.
+    #[serde(default)]
+    external_verifiers: Vec<String>,
+    /// The number of external verifiers which must agree on a contribution before it's verified by the coordinator,
+    /// 0 to disable the external verification.
+    #[serde(default)]
+    verification_quorum: usize,
+    /// The maximum number of chunks verified in parallel by the coordinator, 0 for one per available core.
+    #[serde(default)]
+    verification_concurrency: usize,
+    /// The signature scheme of the keys of the contributors.
+    #[serde(default)]
+    signature_scheme: SignatureScheme,
+    /// The validity of the sessions opened by the participants at login, zero to disable the login.
+    #[serde(default)]
+    session_duration: time::Duration,
+    /// The number of most recent rounds whose contribution files are kept in the base directory, 0 to keep them all.
+    /// The states and the signatures of the rounds, which make the transcript, are always kept.
+    #[serde(default)]
+    retained_rounds: u64,
+    /// The directory where the contribution files of the rounds no longer retained are archived, if any.
+    #[serde(default)]
+    archive_directory: Option<String>,
+    /// The free space, in bytes, below which the storage refuses the uploads and the new challenges, 0 to disable.
+    #[serde(default)]
+    storage_low_water_mark: u64,
+    /// The policy selecting the contributors of the queue assigned to the next rounds.
+    #[serde(default)]
+    queue_selection: QueueSelection,
+    /// The paths of the PEM encoded certificate chain and private key with which the REST server terminates TLS, if
+    /// any. The files are read at launch: a certificate renewed through ACME, by certbot for instance, is picked up at
+    /// the next restart.
+    #[serde(default)]
+    tls_paths: Option<(String, String)>,
+    /// The origins of the browser frontends allowed to call the REST API, `*` for any origin. Empty to keep the
+    /// same-origin policy of the browsers.
+    #[serde(default)]
+    cors_allowed_origins: Vec<String>,
+    /// The maximum sizes of the bodies of the requests to the REST API.
+    #[serde(default)]
+    body_limits: BodyLimits,
+    /// The networks from which the contributor and verifier routes can be reached.
+    #[serde(default)]
+    contributor_ip_filter: IpFilter,
+    /// The networks from which the admin routes can be reached, on top of the `contributor_ip_filter`.
+    #[serde(default)]
+    admin_ip_filter: IpFilter,
+    /// The networks of the reverse proxies in front of the coordinator, whose `X-Forwarded-For` header gives the
+    /// address of the clients.
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde(default)]
+    trusted_proxies: Vec<IpNet>,
+    /// Whether the challenges are served with the signature of the coordinator, for the contributors to detect a
+    /// challenge swapped on its way to them.
+    #[serde(default)]
+    sign_challenges: bool,
 
     /// The contributors managed by the coordinator.
     coordinator_contributors: Vec<Participant>,
     /// The verifiers managed by the coordinator.
     coordinator_verifiers: Vec<Participant>,
+    /// The public keys of the operators allowed to call the admin endpoints, besides the default coordinator's verifier
+    #[serde(default)]
+    admin_public_keys: Vec<String>,
     /// The signing key used by the default coordinator's verifier
     default_verifier_signing_key: String,
+    /// The backend holding the signing key of the default coordinator's verifier
+    #[serde(default)]
+    signing_backend: SigningBackend,
 
     /// The software version number of the coordinator.
     software_version: u64,
@@ -279,6 +498,14 @@ pub struct Environment {
     deployment: Deployment,
     /// The base directory for disk storage of this coordinator.
     local_base_directory: String,
+    /// The URL of the PostgreSQL database holding the state of the ceremony instead of the base directory. Never
+    /// serialized, as it usually contains the credentials of the database.
+    #[serde(skip)]
+    state_database_url: Option<String>,
+    /// The [`hex`] encoded AES-256 key encrypting the contribution and state files of the base directory, if any.
+    /// Never serialized, the state being written to the storage it encrypts.
+    #[serde(skip)]
+    storage_encryption_key: Option<String>,
 
     disable_reliability_zeroing: bool,
 }
@@ -321,6 +548,13 @@ impl Environment {
         self.check_input_for_correctness
     }
 
+    ///
+    /// Returns the circuits whose parameters are updated by the contributions.
+    ///
+    pub const fn circuits(&self) -> Circuits {
+        self.circuits
+    }
+
     ///
     /// Returns the minimum number of contributors permitted to
     /// participate in a round.
@@ -388,6 +622,14 @@ impl Environment {
         self.queue_seen_timeout
     }
 
+    ///
+    /// Returns `true` if the contributors dropped for exceeding the
+    /// `contributor_seen_timeout` are put back at the end of the queue.
+    ///
+    pub const fn requeue_timed_out_contributors(&self) -> bool {
+        self.requeue_timed_out_contributors
+    }
+
     ///
     /// Returns the number of times the coordinator tolerates
     /// a dropped participant before banning them from future rounds.
@@ -396,6 +638,191 @@ impl Environment {
         self.participant_ban_threshold
     }
 
+    ///
+    /// Returns the number of rounds after contributing during
+    /// which a contributor can't join the queue again.
+    ///
+    pub const fn contributor_cooldown_rounds(&self) -> u64 {
+        self.contributor_cooldown_rounds
+    }
+
+    ///
+    /// Returns the duration after contributing during which
+    /// a contributor can't join the queue again.
+    ///
+    pub const fn contributor_cooldown_duration(&self) -> time::Duration {
+        self.contributor_cooldown_duration
+    }
+
+    ///
+    /// Returns `true` if the cool-down after contributing
+    /// also applies to the IP of the contributor.
+    ///
+    pub const fn contributor_cooldown_ip(&self) -> bool {
+        self.contributor_cooldown_ip
+    }
+
+    ///
+    /// Returns the number of consecutive failed uploads or verifications
+    /// after which a contributor is dropped, 0 if disabled.
+    ///
+    pub const fn contributor_failure_threshold(&self) -> u32 {
+        self.contributor_failure_threshold
+    }
+
+    ///
+    /// Returns the duration during which a contributor dropped for its
+    /// failures can't join the queue again.
+    ///
+    pub const fn contributor_failure_ban_duration(&self) -> time::Duration {
+        self.contributor_failure_ban_duration
+    }
+
+    ///
+    /// Returns the number of requests a client can burst to the
+    /// rate-limited endpoints, 0 if the rate limiting is disabled.
+    ///
+    pub const fn rate_limit_requests(&self) -> u32 {
+        self.rate_limit_requests
+    }
+
+    ///
+    /// Returns the duration over which the requests allowed
+    /// to a client are refilled.
+    ///
+    pub const fn rate_limit_period(&self) -> time::Duration {
+        self.rate_limit_period
+    }
+
+    ///
+    /// Returns the public keys of the external verifiers allowed
+    /// to verify the contributions.
+    ///
+    pub const fn external_verifiers(&self) -> &Vec<String> {
+        &self.external_verifiers
+    }
+
+    ///
+    /// Returns the number of external verifiers which must agree on
+    /// a contribution, 0 if the external verification is disabled.
+    ///
+    pub const fn verification_quorum(&self) -> usize {
+        self.verification_quorum
+    }
+
+    ///
+    /// Returns the maximum number of chunks verified in parallel
+    /// by the coordinator, 0 for one per available core.
+    ///
+    pub const fn verification_concurrency(&self) -> usize {
+        self.verification_concurrency
+    }
+
+    ///
+    /// Returns the signature scheme of the keys of the contributors.
+    ///
+    pub const fn signature_scheme(&self) -> SignatureScheme {
+        self.signature_scheme
+    }
+
+    ///
+    /// Returns the validity of the sessions opened by the participants
+    /// at login, zero if the login is disabled.
+    ///
+    pub const fn session_duration(&self) -> time::Duration {
+        self.session_duration
+    }
+
+    ///
+    /// Returns the number of most recent rounds whose contribution
+    /// files are kept, 0 if they're all kept.
+    ///
+    pub const fn retained_rounds(&self) -> u64 {
+        self.retained_rounds
+    }
+
+    ///
+    /// Returns the directory where the contribution files of the rounds
+    /// no longer retained are archived, if any.
+    ///
+    pub fn archive_directory(&self) -> Option<&str> {
+        self.archive_directory.as_deref()
+    }
+
+    ///
+    /// Returns the free space, in bytes, below which the storage refuses
+    /// new files, 0 if it's not checked.
+    ///
+    pub const fn storage_low_water_mark(&self) -> u64 {
+        self.storage_low_water_mark
+    }
+
+    ///
+    /// Returns the policy selecting the contributors of the queue
+    /// assigned to the next rounds.
+    ///
+    pub const fn queue_selection(&self) -> QueueSelection {
+        self.queue_selection
+    }
+
+    ///
+    /// Returns the paths of the certificate chain and of the private key
+    /// of the TLS termination of the REST server, if it's enabled.
+    ///
+    pub fn tls_paths(&self) -> Option<(&str, &str)> {
+        self.tls_paths
+            .as_ref()
+            .map(|(certificate, key)| (certificate.as_str(), key.as_str()))
+    }
+
+    ///
+    /// Returns the origins allowed to call the REST API from
+    /// a browser.
+    ///
+    pub const fn cors_allowed_origins(&self) -> &Vec<String> {
+        &self.cors_allowed_origins
+    }
+
+    ///
+    /// Returns the maximum sizes of the bodies of the requests
+    /// to the REST API.
+    ///
+    pub const fn body_limits(&self) -> BodyLimits {
+        self.body_limits
+    }
+
+    ///
+    /// Returns the networks from which the contributor
+    /// routes can be reached.
+    ///
+    pub const fn contributor_ip_filter(&self) -> &IpFilter {
+        &self.contributor_ip_filter
+    }
+
+    ///
+    /// Returns the networks from which the admin routes
+    /// can be reached.
+    ///
+    pub const fn admin_ip_filter(&self) -> &IpFilter {
+        &self.admin_ip_filter
+    }
+
+    ///
+    /// Returns the networks of the reverse proxies trusted
+    /// to forward the address of the clients.
+    ///
+    pub const fn trusted_proxies(&self) -> &Vec<IpNet> {
+        &self.trusted_proxies
+    }
+
+    ///
+    /// Returns the setting to sign the challenges served
+    /// to the contributors.
+    ///
+    pub const fn sign_challenges(&self) -> bool {
+        self.sign_challenges
+    }
+
     ///
     /// Returns the setting to allow current contributors to
     /// join the queue for the next round.
@@ -438,11 +865,21 @@ impl Environment {
         &self.coordinator_verifiers
     }
 
+    /// Returns the public keys of the operators allowed to call the admin endpoints of the coordinator.
+    pub const fn admin_public_keys(&self) -> &Vec<String> {
+        &self.admin_public_keys
+    }
+
     /// Returns the default verifiers' signing key.
     pub fn default_verifier_signing_key(&self) -> String {
         self.default_verifier_signing_key.clone()
     }
 
+    /// Returns the backend holding the default verifiers' signing key.
+    pub const fn signing_backend(&self) -> &SigningBackend {
+        &self.signing_backend
+    }
+
     ///
     /// Returns the software version number of the coordinator.
     ///
@@ -464,6 +901,16 @@ impl Environment {
         &self.local_base_directory
     }
 
+    /// Returns the URL of the database holding the state of the ceremony, if any.
+    pub fn state_database_url(&self) -> Option<&str> {
+        self.state_database_url.as_deref()
+    }
+
+    /// Returns the key encrypting the files of the storage at rest, if any.
+    pub fn storage_encryption_key(&self) -> Option<&str> {
+        self.storage_encryption_key.as_deref()
+    }
+
     ///
     /// Returns the appropriate number of chunks for the coordinator
     /// to run given a proof system, power and chunk size.
@@ -480,6 +927,13 @@ impl Environment {
     pub(crate) fn storage(&self) -> anyhow::Result<Disk> {
         Ok(Disk::load(self)?)
     }
+
+    /// Replaces the default verifier with the one of the given keypair, held locally.
+    pub(crate) fn rotate_verifier_key(&mut self, keypair: &KeyPair) {
+        self.coordinator_verifiers = vec![Participant::new_verifier(keypair.pubkey())];
+        self.default_verifier_signing_key = keypair.sigkey().to_owned();
+        self.signing_backend = SigningBackend::Local;
+    }
 }
 
 impl From<Testing> for Environment {
@@ -522,6 +976,12 @@ impl Testing {
         self
     }
 
+    /// Sets the directory holding the storage of the coordinator.
+    pub fn base_dir(mut self, base_dir: &str) -> Self {
+        self.environment.local_base_directory = base_dir.to_string();
+        self
+    }
+
     #[inline]
     pub fn coordinator_contributors(&self, contributors: &[Participant]) -> Self {
         // Check that all participants are contributors.
@@ -546,6 +1006,133 @@ impl Testing {
         deployment
     }
 
+    pub fn admin_public_keys(&self, public_keys: &[String]) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.admin_public_keys = public_keys.to_vec();
+        deployment
+    }
+
+    pub fn contributor_cooldown(&self, rounds: u64, duration: time::Duration, include_ip: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contributor_cooldown_rounds = rounds;
+        deployment.environment.contributor_cooldown_duration = duration;
+        deployment.environment.contributor_cooldown_ip = include_ip;
+        deployment
+    }
+
+    pub fn contributor_failure_threshold(&self, failures: u32, ban_duration: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contributor_failure_threshold = failures;
+        deployment.environment.contributor_failure_ban_duration = ban_duration;
+        deployment
+    }
+
+    pub fn rate_limit(&self, requests: u32, period: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.rate_limit_requests = requests;
+        deployment.environment.rate_limit_period = period;
+        deployment
+    }
+
+    pub fn external_verifiers(&self, verifiers: &[String], quorum: usize) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.external_verifiers = verifiers.to_vec();
+        deployment.environment.verification_quorum = quorum;
+        deployment
+    }
+
+    pub fn verification_concurrency(&self, concurrency: usize) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.verification_concurrency = concurrency;
+        deployment
+    }
+
+    pub fn signature_scheme(&self, scheme: SignatureScheme) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.signature_scheme = scheme;
+        deployment
+    }
+
+    pub fn session_duration(&self, duration: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.session_duration = duration;
+        deployment
+    }
+
+    pub fn retention(&self, retained_rounds: u64, archive_directory: Option<&str>) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.retained_rounds = retained_rounds;
+        deployment.environment.archive_directory = archive_directory.map(str::to_string);
+        deployment
+    }
+
+    pub fn storage_low_water_mark(&self, bytes: u64) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.storage_low_water_mark = bytes;
+        deployment
+    }
+
+    pub fn tls(&self, certificate_path: &str, key_path: &str) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.tls_paths = Some((certificate_path.to_string(), key_path.to_string()));
+        deployment
+    }
+
+    pub fn cors_allowed_origins(&self, origins: &[String]) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.cors_allowed_origins = origins.to_vec();
+        deployment
+    }
+
+    pub fn body_limits(&self, limits: BodyLimits) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.body_limits = limits;
+        deployment
+    }
+
+    pub fn ip_filters(&self, contributor: IpFilter, admin: IpFilter) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contributor_ip_filter = contributor;
+        deployment.environment.admin_ip_filter = admin;
+        deployment
+    }
+
+    pub fn trusted_proxies(&self, proxies: &[IpNet]) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.trusted_proxies = proxies.to_vec();
+        deployment
+    }
+
+    pub fn sign_challenges(&self, sign: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.sign_challenges = sign;
+        deployment
+    }
+
+    pub fn circuits(&self, circuits: Circuits) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.circuits = circuits;
+        deployment
+    }
+
+    pub fn queue_selection(&self, selection: QueueSelection) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.queue_selection = selection;
+        deployment
+    }
+
+    pub fn state_database(&self, url: &str) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.state_database_url = Some(url.to_string());
+        deployment
+    }
+
+    pub fn storage_encryption_key(&self, key: &str) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.storage_encryption_key = Some(key.to_string());
+        deployment
+    }
+
     pub fn contributor_seen_timeout(&self, contributor_timeout: time::Duration) -> Self {
         let mut deployment = self.clone();
         deployment.environment.contributor_seen_timeout = contributor_timeout;
@@ -564,6 +1151,12 @@ impl Testing {
         deployment
     }
 
+    pub fn requeue_timed_out_contributors(&self, requeue: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.requeue_timed_out_contributors = requeue;
+        deployment
+    }
+
     fn generate_namada_env(keypair: &KeyPair) -> Self {
         Self {
             environment: Environment {
@@ -576,6 +1169,7 @@ impl Testing {
                 compressed_inputs: UseCompression::No,
                 compressed_outputs: UseCompression::Yes,
                 check_input_for_correctness: CheckForCorrectness::No,
+                circuits: Circuits::default(),
 
                 minimum_contributors_per_round: 1,
                 maximum_contributors_per_round: 1,
@@ -587,18 +1181,46 @@ impl Testing {
                 verifier_seen_timeout: time::Duration::minutes(15),
                 participant_lock_timeout: time::Duration::minutes(20),
                 queue_seen_timeout: time::Duration::minutes(5),
+                requeue_timed_out_contributors: false,
                 participant_ban_threshold: 5,
                 allow_current_contributors_in_queue: true,
                 allow_current_verifiers_in_queue: true,
                 queue_wait_time: 0,
+                contributor_cooldown_rounds: 0,
+                contributor_cooldown_duration: time::Duration::ZERO,
+                contributor_cooldown_ip: false,
+                contributor_failure_threshold: 0,
+                contributor_failure_ban_duration: time::Duration::ZERO,
+                rate_limit_requests: 0,
+                rate_limit_period: time::Duration::ZERO,
+                external_verifiers: vec![],
+                verification_quorum: 0,
+                verification_concurrency: 0,
+                signature_scheme: SignatureScheme::Ed25519,
+                session_duration: time::Duration::minutes(15),
+                retained_rounds: 0,
+                archive_directory: None,
+                storage_low_water_mark: 0,
+                queue_selection: QueueSelection::default(),
+                tls_paths: None,
+                cors_allowed_origins: vec![],
+                body_limits: BodyLimits::default(),
+                contributor_ip_filter: IpFilter::default(),
+                admin_ip_filter: IpFilter::default(),
+                trusted_proxies: vec![],
+                sign_challenges: false,
 
                 coordinator_contributors: vec![Participant::new_contributor("testing-coordinator-contributor")],
                 coordinator_verifiers: vec![Participant::new_verifier(keypair.pubkey())],
+                admin_public_keys: vec![],
                 default_verifier_signing_key: keypair.sigkey().to_owned(),
+                signing_backend: SigningBackend::Local,
 
                 software_version: 1,
                 deployment: Deployment::Testing,
                 local_base_directory: "./transcript/testing".to_string(),
+                state_database_url: None,
+                storage_encryption_key: None,
 
                 disable_reliability_zeroing: false,
             },
@@ -667,6 +1289,12 @@ impl Development {
         self
     }
 
+    /// Sets the directory holding the storage of the coordinator.
+    pub fn base_dir(mut self, base_dir: &str) -> Self {
+        self.environment.local_base_directory = base_dir.to_string();
+        self
+    }
+
     #[inline]
     pub fn coordinator_contributors(&self, contributors: &[Participant]) -> Self {
         // Check that all participants are contributors.
@@ -703,6 +1331,7 @@ impl Development {
                 compressed_inputs: UseCompression::No,
                 compressed_outputs: UseCompression::Yes,
                 check_input_for_correctness: CheckForCorrectness::No,
+                circuits: Circuits::default(),
 
                 minimum_contributors_per_round: 1,
                 maximum_contributors_per_round: 1,
@@ -714,18 +1343,46 @@ impl Development {
                 verifier_seen_timeout: time::Duration::minutes(15),
                 participant_lock_timeout: time::Duration::minutes(20),
                 queue_seen_timeout: time::Duration::minutes(10),
+                requeue_timed_out_contributors: false,
                 participant_ban_threshold: 5,
                 allow_current_contributors_in_queue: true,
                 allow_current_verifiers_in_queue: true,
                 queue_wait_time: 60,
+                contributor_cooldown_rounds: 0,
+                contributor_cooldown_duration: time::Duration::ZERO,
+                contributor_cooldown_ip: false,
+                contributor_failure_threshold: 0,
+                contributor_failure_ban_duration: time::Duration::ZERO,
+                rate_limit_requests: 0,
+                rate_limit_period: time::Duration::ZERO,
+                external_verifiers: vec![],
+                verification_quorum: 0,
+                verification_concurrency: 0,
+                signature_scheme: SignatureScheme::Ed25519,
+                session_duration: time::Duration::minutes(15),
+                retained_rounds: 0,
+                archive_directory: None,
+                storage_low_water_mark: 0,
+                queue_selection: QueueSelection::default(),
+                tls_paths: None,
+                cors_allowed_origins: vec![],
+                body_limits: BodyLimits::default(),
+                contributor_ip_filter: IpFilter::default(),
+                admin_ip_filter: IpFilter::default(),
+                trusted_proxies: vec![],
+                sign_challenges: false,
 
                 coordinator_contributors: vec![Participant::new_contributor("development-coordinator-contributor")],
                 coordinator_verifiers: vec![Participant::new_verifier(keypair.pubkey())],
+                admin_public_keys: vec![],
                 default_verifier_signing_key: keypair.sigkey().to_owned(),
+                signing_backend: SigningBackend::Local,
 
                 software_version: 1,
                 deployment: Deployment::Development,
                 local_base_directory: "./transcript/development".to_string(),
+                state_database_url: None,
+                storage_encryption_key: None,
 
                 disable_reliability_zeroing: false,
             },
@@ -800,11 +1457,34 @@ impl Production {
         self
     }
 
+    pub fn requeue_timed_out_contributors(mut self, requeue: bool) -> Self {
+        self.environment.requeue_timed_out_contributors = requeue;
+        self
+    }
+
     pub fn disable_reliability_zeroing(mut self, disable_zeroing: bool) -> Self {
         self.environment.disable_reliability_zeroing = disable_zeroing;
         self
     }
 
+    /// Sets the directory holding the storage of the coordinator.
+    pub fn base_dir(mut self, base_dir: &str) -> Self {
+        self.environment.local_base_directory = base_dir.to_string();
+        self
+    }
+
+    /// Delegates the signatures of the default verifier to an external backend holding the key with the given
+    /// public key. The key never enters the coordinator: the signing key of the verifier becomes the name of the
+    /// key in the backend.
+    pub fn signing_backend(mut self, backend: SigningBackend, public_key: &str) -> Self {
+        if let SigningBackend::Vault { key_name, .. } = &backend {
+            self.environment.default_verifier_signing_key = key_name.clone();
+        }
+        self.environment.coordinator_verifiers = vec![Participant::new_verifier(public_key)];
+        self.environment.signing_backend = backend;
+        self
+    }
+
     #[inline]
     pub fn coordinator_contributors(&self, contributors: &[Participant]) -> Self {
         // Check that all participants are contributors.
@@ -829,11 +1509,143 @@ impl Production {
         deployment
     }
 
+    pub fn admin_public_keys(&self, public_keys: &[String]) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.admin_public_keys = public_keys.to_vec();
+        deployment
+    }
+
+    pub fn contributor_cooldown(&self, rounds: u64, duration: time::Duration, include_ip: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contributor_cooldown_rounds = rounds;
+        deployment.environment.contributor_cooldown_duration = duration;
+        deployment.environment.contributor_cooldown_ip = include_ip;
+        deployment
+    }
+
+    pub fn contributor_failure_threshold(&self, failures: u32, ban_duration: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contributor_failure_threshold = failures;
+        deployment.environment.contributor_failure_ban_duration = ban_duration;
+        deployment
+    }
+
+    pub fn rate_limit(&self, requests: u32, period: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.rate_limit_requests = requests;
+        deployment.environment.rate_limit_period = period;
+        deployment
+    }
+
+    pub fn external_verifiers(&self, verifiers: &[String], quorum: usize) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.external_verifiers = verifiers.to_vec();
+        deployment.environment.verification_quorum = quorum;
+        deployment
+    }
+
+    pub fn verification_concurrency(&self, concurrency: usize) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.verification_concurrency = concurrency;
+        deployment
+    }
+
+    pub fn signature_scheme(&self, scheme: SignatureScheme) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.signature_scheme = scheme;
+        deployment
+    }
+
+    pub fn session_duration(&self, duration: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.session_duration = duration;
+        deployment
+    }
+
+    pub fn retention(&self, retained_rounds: u64, archive_directory: Option<&str>) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.retained_rounds = retained_rounds;
+        deployment.environment.archive_directory = archive_directory.map(str::to_string);
+        deployment
+    }
+
+    pub fn storage_low_water_mark(&self, bytes: u64) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.storage_low_water_mark = bytes;
+        deployment
+    }
+
+    pub fn tls(&self, certificate_path: &str, key_path: &str) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.tls_paths = Some((certificate_path.to_string(), key_path.to_string()));
+        deployment
+    }
+
+    pub fn cors_allowed_origins(&self, origins: &[String]) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.cors_allowed_origins = origins.to_vec();
+        deployment
+    }
+
+    pub fn body_limits(&self, limits: BodyLimits) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.body_limits = limits;
+        deployment
+    }
+
+    pub fn ip_filters(&self, contributor: IpFilter, admin: IpFilter) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contributor_ip_filter = contributor;
+        deployment.environment.admin_ip_filter = admin;
+        deployment
+    }
+
+    pub fn trusted_proxies(&self, proxies: &[IpNet]) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.trusted_proxies = proxies.to_vec();
+        deployment
+    }
+
+    pub fn sign_challenges(&self, sign: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.sign_challenges = sign;
+        deployment
+    }
+
+    pub fn circuits(&self, circuits: Circuits) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.circuits = circuits;
+        deployment
+    }
+
+    pub fn queue_selection(&self, selection: QueueSelection) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.queue_selection = selection;
+        deployment
+    }
+
+    pub fn state_database(&self, url: &str) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.state_database_url = Some(url.to_string());
+        deployment
+    }
+
+    pub fn storage_encryption_key(&self, key: &str) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.storage_encryption_key = Some(key.to_string());
+        deployment
+    }
+
     fn generate_namada_env(keypair: &KeyPair) -> Self {
         let test_timeout = match std::env::var("NAMADA_MPC_TIMEOUT_SECONDS") {
             Ok(t) => Some(time::Duration::seconds(t.parse::<i64>().unwrap())),
             Err(_) => None,
         };
+        // Window of missed heartbeats after which the current contributor is dropped
+        let heartbeat_timeout = match std::env::var("NAMADA_MPC_HEARTBEAT_TIMEOUT_SECONDS") {
+            Ok(t) => Some(time::Duration::seconds(t.parse::<i64>().unwrap())),
+            Err(_) => None,
+        };
 
         Self {
             environment: Environment {
@@ -846,6 +1658,7 @@ impl Production {
                 compressed_inputs: UseCompression::No,
                 compressed_outputs: UseCompression::Yes,
                 check_input_for_correctness: CheckForCorrectness::No,
+                circuits: Circuits::default(),
 
                 minimum_contributors_per_round: 1,
                 maximum_contributors_per_round: 1,
@@ -853,22 +1666,118 @@ impl Production {
                 maximum_verifiers_per_round: 5,
                 contributor_lock_chunk_limit: 1,
                 verifier_lock_chunk_limit: 5,
-                contributor_seen_timeout: test_timeout.unwrap_or_else(|| time::Duration::seconds(150)),
+                contributor_seen_timeout: heartbeat_timeout
+                    .or(test_timeout)
+                    .unwrap_or_else(|| time::Duration::seconds(150)),
                 verifier_seen_timeout: test_timeout.unwrap_or_else(|| time::Duration::days(7)),
                 participant_lock_timeout: test_timeout.unwrap_or_else(|| time::Duration::minutes(20)),
                 queue_seen_timeout: test_timeout.unwrap_or_else(|| time::Duration::seconds(150)),
+                requeue_timed_out_contributors: matches!(
+                    std::env::var("NAMADA_MPC_REQUEUE_TIMED_OUT").as_deref(),
+                    Ok("true")
+                ),
                 participant_ban_threshold: 5,
                 allow_current_contributors_in_queue: false,
                 allow_current_verifiers_in_queue: true,
                 queue_wait_time: 5,
+                contributor_cooldown_rounds: match std::env::var("NAMADA_MPC_COOLDOWN_ROUNDS") {
+                    Ok(rounds) => rounds.parse().unwrap(),
+                    Err(_) => 0,
+                },
+                contributor_cooldown_duration: match std::env::var("NAMADA_MPC_COOLDOWN_HOURS") {
+                    Ok(hours) => time::Duration::hours(hours.parse().unwrap()),
+                    Err(_) => time::Duration::ZERO,
+                },
+                contributor_cooldown_ip: matches!(std::env::var("NAMADA_MPC_COOLDOWN_IP").as_deref(), Ok("true")),
+                contributor_failure_threshold: match std::env::var("NAMADA_MPC_FAILURE_THRESHOLD") {
+                    Ok(failures) => failures.parse().unwrap(),
+                    Err(_) => 0,
+                },
+                contributor_failure_ban_duration: match std::env::var("NAMADA_MPC_FAILURE_BAN_MINUTES") {
+                    Ok(minutes) => time::Duration::minutes(minutes.parse().unwrap()),
+                    Err(_) => time::Duration::ZERO,
+                },
+                rate_limit_requests: match std::env::var("NAMADA_MPC_RATE_LIMIT_REQUESTS") {
+                    Ok(requests) => requests.parse().unwrap(),
+                    Err(_) => 0,
+                },
+                rate_limit_period: match std::env::var("NAMADA_MPC_RATE_LIMIT_SECONDS") {
+                    Ok(seconds) => time::Duration::seconds(seconds.parse().unwrap()),
+                    Err(_) => time::Duration::minutes(1),
+                },
+                external_verifiers: match std::env::var("NAMADA_MPC_EXTERNAL_VERIFIERS") {
+                    Ok(verifiers) => verifiers
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|v| !v.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                    Err(_) => vec![],
+                },
+                verification_quorum: match std::env::var("NAMADA_MPC_VERIFICATION_QUORUM") {
+                    Ok(quorum) => quorum.parse().unwrap(),
+                    Err(_) => 0,
+                },
+                verification_concurrency: match std::env::var("NAMADA_MPC_VERIFICATION_CONCURRENCY") {
+                    Ok(concurrency) => concurrency.parse().unwrap(),
+                    Err(_) => 0,
+                },
+                signature_scheme: SignatureScheme::from_env(),
+                session_duration: match std::env::var("NAMADA_MPC_SESSION_SECONDS") {
+                    Ok(seconds) => time::Duration::seconds(seconds.parse().unwrap()),
+                    Err(_) => time::Duration::minutes(15),
+                },
+                retained_rounds: match std::env::var("NAMADA_MPC_RETAINED_ROUNDS") {
+                    Ok(rounds) => rounds.parse().unwrap(),
+                    Err(_) => 0,
+                },
+                archive_directory: std::env::var("NAMADA_MPC_ARCHIVE_DIRECTORY").ok(),
+                storage_low_water_mark: match std::env::var("NAMADA_MPC_STORAGE_LOW_WATER_MARK") {
+                    Ok(bytes) => bytes.parse().unwrap(),
+                    Err(_) => 0,
+                },
+                queue_selection: QueueSelection::from_env(),
+                tls_paths: match (
+                    std::env::var("NAMADA_MPC_TLS_CERT_PATH"),
+                    std::env::var("NAMADA_MPC_TLS_KEY_PATH"),
+                ) {
+                    (Ok(certificate), Ok(key)) => Some((certificate, key)),
+                    (Err(_), Err(_)) => None,
+                    _ => panic!("NAMADA_MPC_TLS_CERT_PATH and NAMADA_MPC_TLS_KEY_PATH must be set together"),
+                },
+                cors_allowed_origins: match std::env::var("NAMADA_MPC_CORS_ORIGINS") {
+                    Ok(origins) => origins
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|o| !o.is_empty())
+                        .map(|o| o.trim_end_matches('/').to_string())
+                        .collect(),
+                    Err(_) => vec![],
+                },
+                body_limits: BodyLimits::from_env(),
+                contributor_ip_filter: IpFilter::from_env("NAMADA_MPC_CONTRIBUTOR_IP"),
+                admin_ip_filter: IpFilter::from_env("NAMADA_MPC_ADMIN_IP"),
+                trusted_proxies: networks_from_env("NAMADA_MPC_TRUSTED_PROXIES"),
+                sign_challenges: matches!(std::env::var("NAMADA_MPC_SIGN_CHALLENGES").as_deref(), Ok("true")),
 
                 coordinator_contributors: vec![Participant::new_contributor("coordinator-contributor")],
                 coordinator_verifiers: vec![Participant::new_verifier(keypair.pubkey())],
+                admin_public_keys: match std::env::var("NAMADA_MPC_ADMIN_PUBKEYS") {
+                    Ok(keys) => keys
+                        .split(',')
+                        .map(|k| k.trim().to_string())
+                        .filter(|k| !k.is_empty())
+                        .collect(),
+                    Err(_) => vec![],
+                },
                 default_verifier_signing_key: keypair.sigkey().to_owned(),
+                signing_backend: SigningBackend::Local,
 
                 software_version: 1,
                 deployment: Deployment::Production,
                 local_base_directory: "./transcript".to_string(),
+                state_database_url: std::env::var("NAMADA_MPC_STATE_DATABASE_URL").ok(),
+                storage_encryption_key: std::env::var("NAMADA_MPC_STORAGE_KEY").ok(),
 
                 disable_reliability_zeroing: false,
             },
@@ -966,4 +1875,56 @@ mod tests {
         assert_eq!(ChunkSize::from(1639_usize), chunk_size);
         assert_eq!(number_of_chunks as u64, Testing::from(parameters).number_of_chunks());
     }
+
+    #[test]
+    fn test_contribution_file_size() {
+        use crate::storage::Object;
+
+        let environment: Environment = Testing::default().circuits(Circuits::MASP).into();
+        assert_eq!(84_720_244, Object::anoma_contribution_file_size(&environment, 0, 0));
+        // Each contribution appends a public key to the parameters of every circuit
+        assert_eq!(
+            84_720_244 + 3 * 544,
+            Object::anoma_contribution_file_size(&environment, 1, 1)
+        );
+
+        let environment: Environment = Testing::default().circuits(Circuits::TEST).into();
+        assert_eq!(
+            2_332 + 2 * 544,
+            Object::anoma_contribution_file_size(&environment, 2, 1)
+        );
+    }
+
+    #[test]
+    fn test_ip_filter() {
+        let filter = IpFilter {
+            allow: vec!["10.0.0.0/8".parse().unwrap(), "2001:db8::/32".parse().unwrap()],
+            deny: vec!["10.1.0.0/16".parse().unwrap()],
+        };
+        let allows = |ip: &str| filter.allows(Some(&ip.parse().unwrap()));
+
+        assert!(allows("10.0.0.1"));
+        assert!(allows("2001:db8::1"));
+        // Wrong, denied within an allowed network
+        assert!(!allows("10.1.2.3"));
+        // Wrong, not allowed
+        assert!(!allows("192.168.0.1"));
+        assert!(!filter.allows(None));
+
+        // Any address is allowed by default
+        assert!(IpFilter::default().allows(Some(&"192.168.0.1".parse().unwrap())));
+        assert!(IpFilter::default().allows(None));
+    }
+
+    #[test]
+    fn test_base_dir() {
+        let testing = Testing::default();
+        assert_eq!(testing.local_base_directory(), "./transcript/testing");
+
+        // Distinct instances can point to distinct directories
+        let testing = testing.base_dir("./transcript/testing_1");
+        assert_eq!(testing.local_base_directory(), "./transcript/testing_1");
+        let environment: Environment = testing.into();
+        assert_eq!(environment.local_base_directory(), "./transcript/testing_1");
+    }
 }
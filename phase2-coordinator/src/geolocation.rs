@@ -0,0 +1,103 @@
+//! Geolocation of the contributors from their IP address, for the dashboard statistics to count the countries the
+//! contributions came from. Only the number of countries is published, never the country of a contributor.
+//!
+//! With the `geoip` feature, `NAMADA_MPC_GEOIP_DB` gives the path of a country database in the MaxMind format, such
+//! as GeoLite2 Country, see [MaxMind].
+
+use std::{collections::HashSet, net::IpAddr};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum GeolocationError {
+    #[error("Invalid configuration of the geolocation: {0}")]
+    Configuration(String),
+}
+
+/// A service resolving the country of an IP address.
+pub trait Geolocation: Send + Sync {
+    /// Returns the ISO 3166-1 code of the country of the address, if known.
+    fn country(&self, ip: IpAddr) -> Option<String>;
+}
+
+/// The geolocation of the contributors, if enabled, managed by Rocket for the `/stats` endpoint.
+#[derive(Default)]
+pub struct Geolocator(pub Option<Box<dyn Geolocation>>);
+
+impl Geolocator {
+    /// Opens the country database at `NAMADA_MPC_GEOIP_DB`. The contributors are not geolocated if the variable is not
+    /// set.
+    pub fn from_env() -> Result<Self, GeolocationError> {
+        match std::env::var("NAMADA_MPC_GEOIP_DB") {
+            Err(_) => Ok(Self(None)),
+            #[cfg(feature = "geoip")]
+            Ok(path) => Ok(Self(Some(Box::new(MaxMind::open(&path)?)))),
+            #[cfg(not(feature = "geoip"))]
+            Ok(_) => Err(GeolocationError::Configuration(String::from(
+                "the coordinator was built without the geoip feature",
+            ))),
+        }
+    }
+
+    /// Returns the number of distinct countries of the given addresses, the unknown ones being left out, or [`None`]
+    /// if the geolocation is disabled.
+    pub fn unique_countries<'a>(&self, ips: impl IntoIterator<Item = &'a IpAddr>) -> Option<u64> {
+        let geolocation = self.0.as_ref()?;
+        let countries: HashSet<String> = ips.into_iter().filter_map(|ip| geolocation.country(*ip)).collect();
+
+        Some(countries.len() as u64)
+    }
+}
+
+/// A [MaxMind](https://www.maxmind.com) country database, loaded in memory.
+#[cfg(feature = "geoip")]
+pub struct MaxMind(maxminddb::Reader<Vec<u8>>);
+
+#[cfg(feature = "geoip")]
+impl MaxMind {
+    pub fn open(path: &str) -> Result<Self, GeolocationError> {
+        maxminddb::Reader::open_readfile(path)
+            .map(Self)
+            .map_err(|e| GeolocationError::Configuration(format!("can't open {}: {}", path, e)))
+    }
+}
+
+#[cfg(feature = "geoip")]
+impl Geolocation for MaxMind {
+    fn country(&self, ip: IpAddr) -> Option<String> {
+        let country: maxminddb::geoip2::Country = self.0.lookup(ip).ok()?;
+
+        country.country?.iso_code.map(str::to_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ByFirstOctet;
+
+    impl Geolocation for ByFirstOctet {
+        fn country(&self, ip: IpAddr) -> Option<String> {
+            match ip {
+                IpAddr::V4(ip) if ip.octets()[0] < 100 => Some(format!("C{}", ip.octets()[0] / 10)),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_unique_countries() {
+        let ips: Vec<IpAddr> = ["10.0.0.1", "11.0.0.1", "20.0.0.1", "200.0.0.1", "::1"]
+            .iter()
+            .map(|ip| ip.parse().unwrap())
+            .collect();
+
+        // The unknown addresses are not counted
+        let geolocator = Geolocator(Some(Box::new(ByFirstOctet)));
+        assert_eq!(geolocator.unique_countries(&ips), Some(2));
+        assert_eq!(geolocator.unique_countries(&[]), Some(0));
+
+        // Wrong, geolocation disabled
+        assert_eq!(Geolocator::default().unique_countries(&ips), None);
+    }
+}
@@ -28,10 +28,14 @@
 #[macro_use]
 pub mod macros;
 
+pub mod audit;
+
 pub mod authentication;
 
 pub mod commands;
 
+pub mod compression;
+
 pub mod coordinator;
 pub use coordinator::*;
 
@@ -51,12 +55,21 @@ pub mod io;
 pub mod objects;
 pub use objects::{ContributionFileSignature, ContributionState, Participant, Round};
 
+pub mod queue_policy;
+
 pub mod storage;
 
+pub mod openapi;
 pub mod rest;
 pub mod rest_utils;
 
-pub mod s3;
+pub mod object_storage;
+
+pub mod webhooks;
+
+pub mod email;
+
+pub mod geolocation;
 
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
@@ -1,9 +1,18 @@
 use phase2_coordinator::{
-    authentication::Production as ProductionSig,
+    authentication::KeyPair,
     io::{self, KeyPairUser},
+    commands::PtauImport,
+    email::{self, Mailer},
+    environment::Environment,
+    geolocation::Geolocator,
+    object_storage::{s3::REGION, ObjectStore},
     rest,
-    rest_utils::{self, ResponseError, TOKENS_PATH, TOKENS_ZIP_FILE, UPDATE_TIME},
-    s3::{S3Ctx, REGION},
+    rest_utils::{
+        self, ApiVersion, CeremonyEvent, Cors, IpAccess, RateLimit, Replica, ResponseError, TranscriptCache,
+        VerificationQueue, API_PREFIX, TIMESTAMP_CALENDAR, TOKENS_PATH, TOKENS_ZIP_FILE, UPDATE_TIME,
+        VERIFY_ATTESTATIONS,
+    },
+    webhooks::Webhook,
     Coordinator,
 };
 
@@ -11,13 +20,18 @@ use phase2_coordinator::{
 use phase2_coordinator::environment::Testing;
 
 #[cfg(not(debug_assertions))]
-use phase2_coordinator::environment::Production;
+use phase2_coordinator::{
+    authentication::SigningBackend,
+    environment::Production,
+};
 
 use rocket::{
     self, catchers, routes,
     tokio::{
         self,
         sync::{
+            broadcast::{self, error::RecvError},
+            mpsc,
             watch::{self, Receiver},
             RwLock,
         },
@@ -27,9 +41,51 @@ use rocket::{
 use anyhow::Result;
 use rand::Rng;
 use rusoto_ssm::{Ssm, SsmClient};
-use std::{convert::TryInto, io::Write, sync::Arc};
+use lazy_static::lazy_static;
+use std::{
+    convert::TryInto,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use tracing::{error, info, warn};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+lazy_static! {
+    static ref PARAMS_EXPORT_PATH: String =
+        std::env::var("NAMADA_MPC_PARAMS_EXPORT_PATH").unwrap_or_else(|_| "./params".to_string());
+    static ref MIRROR_PATH: Option<String> = std::env::var("NAMADA_MPC_MIRROR_PATH").ok();
+    /// Directory of the replica of the storage, on another disk or host
+    static ref REPLICA_PATH: Option<String> = std::env::var("NAMADA_MPC_REPLICA_PATH").ok();
+    /// Directory of the storage, to run several coordinators on the same host
+    static ref BASE_DIR: Option<String> = std::env::var("NAMADA_MPC_BASE_DIR").ok();
+    /// Whitespace separated webhooks notified of the events of the ceremony, see [`Webhook`]
+    static ref WEBHOOKS: Vec<Webhook> = match std::env::var("NAMADA_MPC_WEBHOOKS") {
+        Ok(list) => Webhook::parse_list(&list).expect("Invalid NAMADA_MPC_WEBHOOKS"),
+        Err(_) => vec![],
+    };
+    /// Comma separated names of the events posted to the webhooks, all of them if not set
+    static ref WEBHOOK_EVENTS: Option<Vec<String>> = std::env::var("NAMADA_MPC_WEBHOOK_EVENTS")
+        .ok()
+        .map(|events| events.split(',').map(|event| event.trim().to_string()).collect());
+    /// Number of rounds before their turn at which the contributors who registered an email are notified
+    static ref EMAIL_POSITIONS: u64 = match std::env::var("NAMADA_MPC_EMAIL_POSITIONS") {
+        Ok(positions) => positions.parse().expect("Invalid NAMADA_MPC_EMAIL_POSITIONS"),
+        Err(_) => 3,
+    };
+}
+
+/// Updates the static mirror bundle of the transcript, if enabled
+async fn update_mirror_bundle(coordinator: Arc<RwLock<Coordinator>>) -> Result<()> {
+    if let Some(path) = MIRROR_PATH.as_ref() {
+        let read_lock = coordinator.read_owned().await;
+        let index = tokio::task::spawn_blocking(move || read_lock.update_mirror_bundle(Path::new(path))).await??;
+        info!("Mirror bundle in {} updated up to round {:?}", path, index.latest_round_height);
+    }
+
+    Ok(())
+}
 
 /// Periodically updates the [`Coordinator`]
 async fn update_coordinator(coordinator: Arc<RwLock<Coordinator>>, recv: Receiver<bool>) -> Result<()> {
@@ -52,6 +108,18 @@ async fn update_coordinator(coordinator: Arc<RwLock<Coordinator>>, recv: Receive
             }
         }
 
+        // Publish the rounds finalized by the update
+        if let Err(e) = update_mirror_bundle(coordinator.clone()).await {
+            // Log the error and retry at the next iteration
+            warn!("Error while updating the mirror bundle: {}", e);
+        }
+
+        // Return Ok once the contribution in flight is verified, to shut the drained coordinator down
+        if coordinator.read().await.is_drained() {
+            info!("Coordinator drained, exiting update task");
+            return Ok(());
+        }
+
         // Return if shutdown signal has been received on the channel
         if *recv.borrow() {
             info!("Received shutdown signal, exiting update task");
@@ -60,26 +128,143 @@ async fn update_coordinator(coordinator: Arc<RwLock<Coordinator>>, recv: Receive
     }
 }
 
-/// Periodically verifies the pending contributions. Pending contributions are added to the queue by the try_contribute function,
-/// no need to call an update on the coordinator.
+/// Periodically copies the accepted contributions and a checkpoint of the state to the [`Replica`]. No replication
+/// starts once the shutdown signal has been received, for the final one to be the last
+async fn replicate_storage(coordinator: Arc<RwLock<Coordinator>>, replica: Replica, recv: Receiver<bool>) {
+    loop {
+        tokio::time::sleep(UPDATE_TIME).await;
+
+        // Return if shutdown signal has been received on the channel
+        if *recv.borrow() {
+            info!("Received shutdown signal, exiting replication task");
+            return;
+        }
+
+        if let Err(e) = replica.run(coordinator.clone()).await {
+            // Log the error and retry at the next iteration
+            warn!("Error while replicating the storage: {}", e);
+        }
+    }
+}
+
+/// Periodically checks the public attestations of the contributors, apart from the verification of the contributions
+/// for the downloads not to delay it
+async fn verify_attestations(coordinator: Arc<RwLock<Coordinator>>, recv: Receiver<bool>) {
+    loop {
+        tokio::time::sleep(UPDATE_TIME).await;
+
+        if let Err(e) = rest_utils::perform_verify_attestations(coordinator.clone()).await {
+            // Log the error and retry at the next iteration
+            warn!("Error while verifying attestations: {}", e);
+        }
+
+        // Return if shutdown signal has been received on the channel
+        if *recv.borrow() {
+            info!("Received shutdown signal, exiting attestation task");
+            return;
+        }
+    }
+}
+
+/// Posts the events of the ceremony to the [`WEBHOOKS`] as they're emitted. Runs until the coordinator is dropped.
+async fn notify_webhooks(mut events: broadcast::Receiver<CeremonyEvent>) {
+    let client = reqwest::Client::new();
+
+    loop {
+        match events.recv().await {
+            Ok(event) => {
+                let selected = WEBHOOK_EVENTS
+                    .as_ref()
+                    .map_or(true, |names| names.iter().any(|name| name == event.name()));
+                if selected {
+                    for webhook in WEBHOOKS.iter() {
+                        webhook.notify(&client, &event).await;
+                    }
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => warn!("The webhooks missed {} events", skipped),
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Periodically sends the email notifications due to the contributors approaching their turn or locking their chunk
+async fn notify_contributors(coordinator: Arc<RwLock<Coordinator>>, mailer: Box<dyn Mailer>, recv: Receiver<bool>) {
+    loop {
+        tokio::time::sleep(UPDATE_TIME).await;
+
+        let notifications = coordinator.write().await.email_notifications(*EMAIL_POSITIONS);
+        match notifications {
+            Ok(notifications) => {
+                for notification in notifications {
+                    notification.send(mailer.as_ref()).await;
+                }
+            }
+            // Log the error and retry at the next iteration
+            Err(e) => warn!("Error while collecting the email notifications: {}", e),
+        }
+
+        // Return if shutdown signal has been received on the channel
+        if *recv.borrow() {
+            info!("Received shutdown signal, exiting notification task");
+            return;
+        }
+    }
+}
+
+/// Drains the [`Coordinator`] on SIGTERM, like `GET /stop`. The update task shuts the coordinator down once drained
+#[cfg(unix)]
+async fn drain_on_sigterm(coordinator: Arc<RwLock<Coordinator>>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut sigterm) => {
+            sigterm.recv().await;
+            info!("Received SIGTERM, draining the coordinator before shutting down...");
+            coordinator.write().await.start_draining();
+        }
+        Err(e) => warn!("Error while listening for SIGTERM: {}", e),
+    }
+}
+
+/// Periodically verifies the pending contributions, or as soon as a verification is requested through the [`VerificationQueue`].
+/// Pending contributions are added to the queue by the try_contribute function, no need to call an update on the coordinator.
 /// NOTE: a possible improvement could be to perform the verification when the try_contribute function gets called, allowing us to remove this task and
 /// speed up the verification process. This would also allow us to immediately provide to a client the state of validity of its contribution. This improvement could
 /// be possible because we only have one contribution per round and one verifier (the coordinator's one). To implement this logic though, it would require a major rework of the phase2_coordinator logic.
-async fn verify_contributions(coordinator: Arc<RwLock<Coordinator>>, recv: Receiver<bool>) -> Result<()> {
-    let s3_ctx = S3Ctx::new().await?;
+async fn verify_contributions(
+    coordinator: Arc<RwLock<Coordinator>>,
+    verification_queue: VerificationQueue,
+    mut requests: mpsc::Receiver<()>,
+    recv: Receiver<bool>,
+) -> Result<()> {
+    let object_store = ObjectStore::new().await?;
+    let http_client = reqwest::Client::new();
 
     loop {
-        tokio::time::sleep(UPDATE_TIME).await;
+        tokio::select! {
+            _ = tokio::time::sleep(UPDATE_TIME) => (),
+            Some(()) = requests.recv() => info!("Received a verification request"),
+        }
 
         info!("Verifying contributions...");
         let start = std::time::Instant::now();
-        rest_utils::perform_verify_chunks(coordinator.clone(), &s3_ctx).await?;
+        verification_queue.run(coordinator.clone(), &mut requests, &object_store).await?;
         info!(
             "Verification of contributions completed in {:#?}. {:#?} to the next verification round...",
             start.elapsed(),
             UPDATE_TIME
         );
 
+        // Optionally timestamp the verified contributions
+        if let Some(calendar) = TIMESTAMP_CALENDAR.as_ref() {
+            if let Err(e) = rest_utils::perform_timestamp_contributions(coordinator.clone(), &http_client, calendar).await
+            {
+                // Log the error and retry at the next iteration
+                warn!("Error while timestamping contributions: {}", e);
+            }
+        }
+
         // Return if shutdown signal has been received on the channel
         if *recv.borrow() {
             info!("Received shutdown signal, exiting verify task");
@@ -100,15 +285,15 @@ macro_rules! print_env {
     };
 }
 
-/// Download tokens from S3, decompress and store them locally.
+/// Download tokens from the object storage, decompress and store them locally.
 async fn download_tokens() -> Result<()> {
-    let s3_ctx = S3Ctx::new().await?;
+    let object_store = ObjectStore::new().await?;
     let mut zip_file = std::fs::File::options()
         .read(true)
         .write(true)
         .create(true)
         .open(TOKENS_ZIP_FILE)?;
-    zip_file.write_all(&s3_ctx.get_tokens().await?)?;
+    zip_file.write_all(&object_store.get_tokens().await?)?;
 
     let mut zip = zip::ZipArchive::new(zip_file)?;
     zip.extract(TOKENS_PATH.as_str())?;
@@ -146,10 +331,60 @@ async fn generate_secret() -> Result<()> {
     Ok(())
 }
 
+/// Fetches the key encrypting the storage from the Amazon Parameter Store SecureString named by
+/// `NAMADA_MPC_STORAGE_KEY_PARAMETER`, if set, and exports it as `NAMADA_MPC_STORAGE_KEY`.
+async fn load_storage_key() -> Result<()> {
+    let name = match std::env::var("NAMADA_MPC_STORAGE_KEY_PARAMETER") {
+        Ok(name) => name,
+        Err(_) => return Ok(()),
+    };
+
+    let aws_client = SsmClient::new(REGION.clone());
+    let get_request = rusoto_ssm::GetParameterRequest {
+        name: name.clone(),
+        with_decryption: Some(true),
+    };
+    let key = aws_client
+        .get_parameter(get_request)
+        .await?
+        .parameter
+        .and_then(|parameter| parameter.value)
+        .ok_or_else(|| anyhow::anyhow!("Missing value of the parameter {}", name))?;
+    std::env::set_var("NAMADA_MPC_STORAGE_KEY", key);
+
+    Ok(())
+}
+
+/// Loads the keypair of the coordinator from the encrypted file at `NAMADA_MPC_KEYPAIR_FILE`, decrypted with the
+/// passphrase in `NAMADA_MPC_KEYPAIR_PASSPHRASE`, generating and saving a new one there on the first start. If the file
+/// is not set, generates a new keypair and saves its mnemonic to disk
+async fn generate_keypair() -> KeyPair {
+    tokio::task::spawn_blocking(|| -> Result<KeyPair> {
+        match std::env::var("NAMADA_MPC_KEYPAIR_FILE") {
+            Ok(path) => {
+                let passphrase = std::env::var("NAMADA_MPC_KEYPAIR_PASSPHRASE")
+                    .map_err(|_| anyhow::anyhow!("Missing required env NAMADA_MPC_KEYPAIR_PASSPHRASE"))?;
+                if Path::new(&path).exists() {
+                    return KeyPair::load_encrypted(path, &passphrase);
+                }
+
+                let keypair = KeyPair::new();
+                keypair.save_encrypted(&path, &passphrase)?;
+                info!("Generated a new keypair, saved to {}", path);
+                Ok(keypair)
+            }
+            Err(_) => Ok(io::generate_keypair(KeyPairUser::Coordinator)?),
+        }
+    })
+    .await
+    .unwrap()
+    .expect("Error while generating the keypair")
+}
+
 /// Perform the steps to finalize the ceremony state before shut down
-async fn finalize_ceremony(coordinator: Arc<RwLock<Coordinator>>) -> Result<()> {
+async fn finalize_ceremony(coordinator: Arc<RwLock<Coordinator>>, replica: Replica) -> Result<()> {
     info!("Performing last contribution verification (if any)...");
-    if let Err(e) = rest_utils::perform_verify_chunks(coordinator.clone(), &S3Ctx::new().await?).await {
+    if let Err(e) = rest_utils::perform_verify_chunks(coordinator.clone(), &ObjectStore::new().await?).await {
         // Log any error without interrupting the shutdown procedure
         warn!("Ignoring error while performing last verification: {}", e);
     }
@@ -160,55 +395,178 @@ async fn finalize_ceremony(coordinator: Arc<RwLock<Coordinator>>) -> Result<()>
         warn!("Ignoring error while performing last update: {}", e);
     }
 
+    if let Err(e) = update_mirror_bundle(coordinator.clone()).await {
+        // Log any error without interrupting the shutdown procedure
+        warn!("Ignoring error while updating the mirror bundle: {}", e);
+    }
+
     info!("Saving final coordinator state");
     coordinator.write().await.shutdown()?;
 
+    if let Err(e) = replica.run(coordinator.clone()).await {
+        // Log any error without interrupting the shutdown procedure
+        warn!("Ignoring error while replicating the storage: {}", e);
+    }
+
+    info!("Exporting final parameters to {}...", PARAMS_EXPORT_PATH.as_str());
+    let read_lock = coordinator.read_owned().await;
+    let export =
+        tokio::task::spawn_blocking(move || read_lock.export_parameters(Path::new(PARAMS_EXPORT_PATH.as_str())))
+            .await??;
+    for parameter in export.parameters {
+        info!("Exported {} with hash {}", parameter.file_name, parameter.hash);
+    }
+
     Ok(())
 }
 
 /// Rocket main function using the [`tokio`] runtime
 #[rocket::main]
 pub async fn main() {
+    // The closed spans are logged with their timing. The json output, enabled with NAMADA_MPC_LOG_FORMAT=json, carries
+    // the fields of the spans of each event
     let tracing_enable_color = std::env::var("RUST_LOG_COLOR").is_ok();
-    tracing_subscriber::fmt().with_ansi(tracing_enable_color).init();
+    let subscriber = tracing_subscriber::fmt().with_span_events(FmtSpan::CLOSE);
+    match std::env::var("NAMADA_MPC_LOG_FORMAT").as_deref() {
+        Ok("json") => subscriber.json().with_current_span(true).with_span_list(true).init(),
+        _ => subscriber.with_ansi(tracing_enable_color).init(),
+    }
     print_env!(
         "AWS_S3_PROD",
         "AWS_S3_BUCKET",
         "AWS_REGION",
         "NAMADA_MPC_IP_BAN",
         "NAMADA_MPC_TIMEOUT_SECONDS",
+        "NAMADA_MPC_HEARTBEAT_TIMEOUT_SECONDS",
+        "NAMADA_MPC_REQUEUE_TIMED_OUT",
+        "NAMADA_MPC_ADMIN_PUBKEYS",
+        "NAMADA_MPC_COOLDOWN_ROUNDS",
+        "NAMADA_MPC_COOLDOWN_HOURS",
+        "NAMADA_MPC_COOLDOWN_IP",
+        "NAMADA_MPC_RATE_LIMIT_REQUESTS",
+        "NAMADA_MPC_RATE_LIMIT_SECONDS",
+        "NAMADA_MPC_EXTERNAL_VERIFIERS",
+        "NAMADA_MPC_VERIFICATION_QUORUM",
+        "NAMADA_MPC_VERIFICATION_CONCURRENCY",
+        "NAMADA_MPC_SIGNATURE_SCHEME",
+        "NAMADA_MPC_SESSION_SECONDS",
         "HEALTH_PATH",
         "NAMADA_TOKENS_PATH",
         "CEREMONY_START_TIMESTAMP",
         "TOKENS_FILE_PREFIX",
         "NAMADA_COHORT_TIME",
-        "TOKEN_BLACKLIST"
+        "TOKEN_BLACKLIST",
+        "NAMADA_MPC_VERIFY_ATTESTATIONS",
+        "NAMADA_MPC_PARAMS_EXPORT_PATH",
+        "NAMADA_MPC_PTAU_PATH",
+        "NAMADA_MPC_MIRROR_PATH",
+        "NAMADA_MPC_REPLICA_PATH",
+        "NAMADA_MPC_DIRECT_UPLOAD",
+        "NAMADA_MPC_INVITE_ONLY",
+        "NAMADA_MPC_TIMESTAMP_CALENDAR",
+        "NAMADA_MPC_VAULT_ADDR",
+        "NAMADA_MPC_VAULT_MOUNT",
+        "NAMADA_MPC_VAULT_KEY",
+        "NAMADA_MPC_KEYPAIR_FILE",
+        "NAMADA_MPC_BASE_DIR",
+        "NAMADA_MPC_STORAGE_KEY_PARAMETER",
+        "NAMADA_MPC_WEBHOOK_EVENTS",
+        "NAMADA_MPC_EMAIL_BACKEND",
+        "NAMADA_MPC_EMAIL_FROM",
+        "NAMADA_MPC_EMAIL_POSITIONS",
+        "NAMADA_MPC_SMTP_HOST",
+        "NAMADA_MPC_SMTP_PORT",
+        "NAMADA_MPC_LOG_FORMAT",
+        "NAMADA_MPC_GEOIP_DB",
+        "NAMADA_MPC_TLS_CERT_PATH",
+        "NAMADA_MPC_TLS_KEY_PATH",
+        "NAMADA_MPC_CORS_ORIGINS",
+        "NAMADA_MPC_BODY_LIMIT_TINY",
+        "NAMADA_MPC_BODY_LIMIT_JSON",
+        "NAMADA_MPC_BODY_LIMIT_CONTRIBUTION",
+        "NAMADA_MPC_CONTRIBUTOR_IP_ALLOW",
+        "NAMADA_MPC_CONTRIBUTOR_IP_DENY",
+        "NAMADA_MPC_ADMIN_IP_ALLOW",
+        "NAMADA_MPC_ADMIN_IP_DENY",
+        "NAMADA_MPC_TRUSTED_PROXIES",
+        "NAMADA_MPC_SIGN_CHALLENGES"
     );
 
     // Generate, publish and export the secret token
     generate_secret().await.expect("Error while generating secret token");
 
-    // Set the environment
-    let keypair = tokio::task::spawn_blocking(|| io::generate_keypair(KeyPairUser::Coordinator))
-        .await
-        .unwrap()
-        .expect("Error while generating the keypair");
+    // Fetch the key of the storage before the environment reads it
+    load_storage_key().await.expect("Error while retrieving the storage key");
 
+    // Set the environment
     #[cfg(debug_assertions)]
     let environment: Testing = {
-        phase2_coordinator::testing::clear_test_storage(&Testing::default().into());
-        Testing::new(&keypair)
+        let environment = match BASE_DIR.as_deref() {
+            Some(base_dir) => Testing::new(&generate_keypair().await).base_dir(base_dir),
+            None => Testing::new(&generate_keypair().await),
+        };
+        phase2_coordinator::testing::clear_test_storage(&environment.clone().into());
+        environment
     };
 
+    // In production the signing key of the coordinator can be held by an external backend, to keep it off the disk
     #[cfg(not(debug_assertions))]
-    let environment: Production = { Production::new(&keypair) };
+    let environment: Production = {
+        let backend = SigningBackend::from_env();
+        let backend_clone = backend.clone();
+        let public_key = tokio::task::spawn_blocking(move || backend_clone.public_key())
+            .await
+            .unwrap()
+            .expect("Error while retrieving the public key of the coordinator");
+
+        let environment = match public_key {
+            Some(public_key) => {
+                info!("Signing with the key held by {:?}", backend);
+                Production::default().signing_backend(backend, &public_key)
+            }
+            None => Production::new(&generate_keypair().await),
+        };
 
+        match BASE_DIR.as_deref() {
+            Some(base_dir) => environment.base_dir(base_dir),
+            None => environment,
+        }
+    };
     // Always download token files from S3 to check for updates
     download_tokens().await.expect("Error while retrieving tokens");
 
     // Initialize the coordinator
-    let coordinator =
-        Coordinator::new(environment.into(), Arc::new(ProductionSig)).expect("Failed to instantiate coordinator");
+    let rate_limit = RateLimit::from_environment(&environment.clone().into());
+    let rocket_config = rest_utils::rocket_config(&environment.clone().into());
+    let cors = Cors::from_environment(&environment.clone().into());
+    let ip_access = IpAccess::from_environment(&environment.clone().into());
+    let environment: Environment = environment.into();
+    // The signing backend may block, so the coordinator is built off the runtime
+    let coordinator = tokio::task::spawn_blocking(move || {
+        let signature = environment
+            .signing_backend()
+            .signature()
+            .expect("Error while initializing the signing backend");
+
+        Coordinator::new(environment, signature)
+    })
+    .await
+    .expect("Coordinator instantiation task panicked")
+    .expect("Failed to instantiate coordinator");
+
+    // Import the Powers of Tau transcript, if provided, to generate the round 0 challenge in the storage
+    if let Ok(ptau_path) = std::env::var("NAMADA_MPC_PTAU_PATH") {
+        if coordinator.is_initialized() {
+            info!("Round 0 exists already, skipping the import of {}", ptau_path);
+        } else {
+            let output_dir = PathBuf::from(coordinator.environment().local_base_directory());
+            tokio::task::spawn_blocking(move || PtauImport::run(Path::new(&ptau_path), &output_dir))
+                .await
+                .expect("Ptau import task panicked")
+                .expect("Import of the ptau file failed");
+        }
+    }
+
     let coordinator: Arc<RwLock<Coordinator>> = Arc::new(RwLock::new(coordinator));
     let up_coordinator = coordinator.clone();
     let verify_coordinator = coordinator.clone();
@@ -222,53 +580,177 @@ pub async fn main() {
     // Build Rocket REST server
     #[cfg(debug_assertions)]
     let routes = routes![
+        rest::get_login_nonce,
+        rest::login,
         rest::join_queue,
         rest::lock_chunk,
+        rest::await_turn,
         rest::get_challenge_url,
+        rest::get_challenge,
         rest::get_contribution_url,
         rest::contribute_chunk,
+        rest::upload_chunk,
+        rest::get_upload_session,
+        rest::upload_chunk_part,
+        rest::complete_upload,
         rest::update_coordinator,
         rest::heartbeat,
+        rest::abandon_contribution,
+        rest::get_rejections,
         rest::stop_coordinator,
         rest::verify_chunks,
+        rest::request_verification,
+        rest::get_verification_status,
+        rest::get_replication_status,
+        rest::lock_verification_task,
+        rest::get_verification_challenge,
+        rest::get_verification_response,
+        rest::post_verification_result,
+        rest::get_ceremony_status,
+        rest::get_coordinator_identity,
+        rest::get_stats,
         rest::get_contributor_queue_status,
         rest::post_contribution_info,
         rest::get_contributions_info,
         rest::get_coordinator_state,
         rest::get_healthcheck,
+        rest::get_liveness,
+        rest::get_readiness,
+        rest::get_events,
+        rest::get_openapi,
+        rest::rate_limited,
+        rest::ip_not_allowed,
+        rest::unsupported_api_version,
         rest::update_cohorts,
-        rest::post_attestation
+        rest::apply_beacon,
+        rest::post_invite_codes,
+        rest::get_invite_codes,
+        rest::post_priority_tokens,
+        rest::rotate_key,
+        rest::ban,
+        rest::unban,
+        rest::get_ban_list,
+        rest::post_participant_priority,
+        rest::post_skip_current,
+        rest::post_snapshot,
+        rest::post_restore,
+        rest::get_storage_audit,
+        rest::get_audit_export,
+        rest::post_attestation,
+        rest::get_attestation_message,
+        rest::post_namada_address,
+        rest::post_identity_proof,
+        rest::get_feed,
+        rest::get_transcript,
+        rest::get_round_info,
+        rest::get_contributor_bundle,
+        rest::get_contribution_history,
+        rest::get_has_contributed,
+        rest::get_contributors
     ];
 
     #[cfg(not(debug_assertions))]
     let routes = routes![
+        rest::get_login_nonce,
+        rest::login,
         rest::join_queue,
         rest::lock_chunk,
+        rest::await_turn,
         rest::get_challenge_url,
+        rest::get_challenge,
         rest::get_contribution_url,
         rest::contribute_chunk,
+        rest::upload_chunk,
+        rest::get_upload_session,
+        rest::upload_chunk_part,
+        rest::complete_upload,
         rest::heartbeat,
+        rest::abandon_contribution,
+        rest::get_rejections,
         rest::stop_coordinator,
+        rest::request_verification,
+        rest::get_verification_status,
+        rest::get_replication_status,
+        rest::lock_verification_task,
+        rest::get_verification_challenge,
+        rest::get_verification_response,
+        rest::post_verification_result,
+        rest::get_ceremony_status,
+        rest::get_coordinator_identity,
+        rest::get_stats,
         rest::get_contributor_queue_status,
         rest::post_contribution_info,
         rest::get_coordinator_state,
         rest::get_healthcheck,
+        rest::get_liveness,
+        rest::get_readiness,
+        rest::get_events,
+        rest::get_openapi,
+        rest::rate_limited,
+        rest::ip_not_allowed,
+        rest::unsupported_api_version,
         rest::update_cohorts,
-        rest::post_attestation
+        rest::apply_beacon,
+        rest::post_invite_codes,
+        rest::get_invite_codes,
+        rest::post_priority_tokens,
+        rest::rotate_key,
+        rest::ban,
+        rest::unban,
+        rest::get_ban_list,
+        rest::post_participant_priority,
+        rest::post_skip_current,
+        rest::post_snapshot,
+        rest::post_restore,
+        rest::get_storage_audit,
+        rest::get_audit_export,
+        rest::post_attestation,
+        rest::get_attestation_message,
+        rest::post_namada_address,
+        rest::post_identity_proof,
+        rest::get_feed,
+        rest::get_transcript,
+        rest::get_round_info,
+        rest::get_contributor_bundle,
+        rest::get_contribution_history,
+        rest::get_has_contributed,
+        rest::get_contributors
     ];
 
-    let build_rocket = rocket::build().mount("/", routes).manage(coordinator.clone()).register(
-        "/",
-        catchers![
-            rest_utils::invalid_signature,
-            rest_utils::unauthorized,
-            rest_utils::missing_required_header,
-            rest_utils::io_error,
-            rest_utils::unprocessable_entity,
-            rest_utils::mismatching_checksum,
-            rest_utils::invalid_header
-        ],
-    );
+    let (verification_queue, verification_requests) = VerificationQueue::new();
+    let replica = Replica::new(REPLICA_PATH.as_ref().map(PathBuf::from));
+
+    // Each request is served in its own span
+    let routes = rest_utils::trace_routes(routes);
+
+    // The unversioned paths are kept for the clients predating the versioning of the API
+    let build_rocket = rocket::custom(rocket_config)
+        .mount(API_PREFIX, routes.clone())
+        .mount("/", routes)
+        .manage(coordinator.clone())
+        .manage(verification_queue.clone())
+        .manage(replica.clone())
+        .manage(TranscriptCache::default())
+        .manage(Geolocator::from_env().expect("Invalid configuration of the geolocation"))
+        .manage(ip_access.clone())
+        .attach(ApiVersion)
+        .attach(ip_access)
+        .attach(rate_limit)
+        .attach(cors)
+        .register(
+            "/",
+            catchers![
+                rest_utils::invalid_signature,
+                rest_utils::unauthorized,
+                rest_utils::missing_required_header,
+                rest_utils::io_error,
+                rest_utils::unprocessable_entity,
+                rest_utils::mismatching_checksum,
+                rest_utils::invalid_header,
+                rest_utils::payload_too_large,
+                rest_utils::ip_not_allowed
+            ],
+        );
     let ignite_rocket = build_rocket.ignite().await.expect("Coordinator server didn't ignite");
 
     // Sleep until ceremony start time has been reached
@@ -302,8 +784,38 @@ pub async fn main() {
     // Spawn task to update the coordinator periodically
     let mut update_handle = rocket::tokio::spawn(update_coordinator(up_coordinator, rx.clone()));
 
+    // Spawn task to replicate the storage periodically, if enabled. It's not awaited on shutdown, the final
+    // replication, done once the ceremony is finalized, waiting for the one in progress
+    if REPLICA_PATH.is_some() {
+        rocket::tokio::spawn(replicate_storage(coordinator.clone(), replica.clone(), rx.clone()));
+    }
+
+    // Spawn task to check the public attestations of the contributors periodically, if enabled
+    if *VERIFY_ATTESTATIONS {
+        rocket::tokio::spawn(verify_attestations(coordinator.clone(), rx.clone()));
+    }
+
+    // Spawn task to notify the webhooks of the events of the ceremony, if any
+    if !WEBHOOKS.is_empty() {
+        rocket::tokio::spawn(notify_webhooks(coordinator.read().await.subscribe()));
+    }
+
+    // Spawn task to email the contributors who registered an address, if a mailer is configured
+    if let Some(mailer) = email::mailer_from_env().expect("Invalid configuration of the email notifications") {
+        rocket::tokio::spawn(notify_contributors(coordinator.clone(), mailer, rx.clone()));
+    }
+
+    // Spawn task to drain the coordinator on SIGTERM, the update task then shutting it down
+    #[cfg(unix)]
+    rocket::tokio::spawn(drain_on_sigterm(coordinator.clone()));
+
     // Spawn task to verify the contributions periodically
-    let mut verify_handle = rocket::tokio::spawn(verify_contributions(verify_coordinator, rx));
+    let mut verify_handle = rocket::tokio::spawn(verify_contributions(
+        verify_coordinator,
+        verification_queue,
+        verification_requests,
+        rx,
+    ));
 
     // Spawn Rocket server task
     let mut rocket_handle = rocket::tokio::spawn(ignite_rocket.launch());
@@ -315,8 +827,8 @@ pub async fn main() {
         update_result = &mut update_handle => {
             match update_result.expect("Update task panicked") {
                 Ok(()) => {
-                    // Cohorts are over, terminate the ceremony
-                    info!("Cohorts are over, notifying rest server to shut down...");
+                    // Cohorts are over or the coordinator is drained, terminate the ceremony
+                    info!("Cohorts are over or coordinator drained, notifying rest server to shut down...");
 
                     // Cancel concurrent tasks
                     info!("Cancelling concurrent tasks...");
@@ -338,7 +850,7 @@ pub async fn main() {
 
                     info!("Concurrent tasks terminated");
 
-                    finalize_ceremony(coordinator).await.expect("Failed ceremony state finalize");
+                    finalize_ceremony(coordinator, replica).await.expect("Failed ceremony state finalize");
                 },
                 Err(e) => error!("Update of Coordinator failed: {}", e),
             }
@@ -374,7 +886,7 @@ pub async fn main() {
 
                     info!("Concurrent tasks terminated");
 
-                    finalize_ceremony(coordinator).await.expect("Failed ceremony state finalize");
+                    finalize_ceremony(coordinator, replica).await.expect("Failed ceremony state finalize");
                 },
                 Err(e) => error!("Rocket failed: {}", e)
             }
@@ -0,0 +1,174 @@
+use super::{check_response, hmac_sha256, uri_encode, ObjectStorage, ObjectStorageError, Result, REQUEST_URL_VALIDITY};
+
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Version of the Blob service REST API, which sets the format of the shared access signatures.
+const SERVICE_VERSION: &str = "2020-12-06";
+
+/// Header giving the type of the uploaded blobs, required by the Blob service.
+pub const BLOB_TYPE_HEADER: &str = "x-ms-blob-type";
+
+/// A container of Azure Blob Storage, accessed with a shared key of its storage account.
+///
+/// Every request goes through an url carrying a service shared access signature (SAS), the one of the urls given to
+/// the contributors, so the account key is never sent along with the requests. The blobs are uploaded as block
+/// blobs, with the [BLOB_TYPE_HEADER] the Blob service requires on each upload.
+pub struct Azure {
+    client: reqwest::Client,
+    account: String,
+    container: String,
+    key: Vec<u8>,
+}
+
+impl Azure {
+    /// Creates a client of the given container, with the [`base64`] encoded key of the storage account.
+    pub fn new(account: &str, container: &str, key: &str) -> Result<Self> {
+        let key = base64::decode(key)
+            .map_err(|e| ObjectStorageError::Configuration(format!("invalid account key: {}", e)))?;
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            account: account.to_owned(),
+            container: container.to_owned(),
+            key,
+        })
+    }
+
+    /// Reads the account from `NAMADA_MPC_AZURE_ACCOUNT`, its key from `NAMADA_MPC_AZURE_KEY` and the container
+    /// from `NAMADA_MPC_AZURE_CONTAINER`.
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| {
+            std::env::var(name).map_err(|_| ObjectStorageError::Configuration(format!("missing env {}", name)))
+        };
+
+        Self::new(
+            &var("NAMADA_MPC_AZURE_ACCOUNT")?,
+            &var("NAMADA_MPC_AZURE_CONTAINER")?,
+            &var("NAMADA_MPC_AZURE_KEY")?,
+        )
+    }
+
+    /// Returns the url granting the given permissions on the blob under `key` until the given expiry.
+    fn signed_url(&self, permissions: &str, key: &str, expiry: DateTime<Utc>) -> String {
+        let expiry = expiry.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let resource = format!("/blob/{}/{}/{}", self.account, self.container, key);
+
+        // The fields left empty are the start, the identifier, the allowed ips, the snapshot time, the encryption
+        // scope and the overridden response headers
+        let string_to_sign = format!(
+            "{}\n\n{}\n{}\n\n\nhttps\n{}\nb\n\n\n\n\n\n\n",
+            permissions, expiry, resource, SERVICE_VERSION
+        );
+        let signature = base64::encode(hmac_sha256(&self.key, string_to_sign.as_bytes()));
+
+        format!(
+            "https://{}.blob.core.windows.net/{}/{}?sv={}&se={}&sr=b&sp={}&spr=https&sig={}",
+            self.account,
+            self.container,
+            uri_encode(key, false),
+            SERVICE_VERSION,
+            uri_encode(&expiry, true),
+            permissions,
+            uri_encode(&signature, true)
+        )
+    }
+
+    /// Sends a request with `method` on the blob under `key`, through an url signed for it.
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        permissions: &str,
+        key: &str,
+        body: Option<Vec<u8>>,
+    ) -> Result<reqwest::Response> {
+        let url = self.signed_url(permissions, key, Self::expiry(REQUEST_URL_VALIDITY));
+        let mut request = self.client.request(method.clone(), url);
+        if let Some(body) = body {
+            request = request.header(BLOB_TYPE_HEADER, "BlockBlob").body(body);
+        }
+
+        let transient: fn(String) -> ObjectStorageError = match method {
+            reqwest::Method::PUT | reqwest::Method::DELETE => ObjectStorageError::UploadError,
+            _ => ObjectStorageError::DownloadError,
+        };
+        let response = request.send().await.map_err(|e| transient(e.to_string()))?;
+        check_response(key, &response, transient)?;
+
+        Ok(response)
+    }
+
+    /// Returns the expiry of an url valid for the given duration, which Azure doesn't bound.
+    fn expiry(expires_in: Duration) -> DateTime<Utc> {
+        Utc::now() + chrono::Duration::from_std(expires_in).unwrap_or_else(|_| chrono::Duration::days(7))
+    }
+}
+
+#[rocket::async_trait]
+impl ObjectStorage for Azure {
+    async fn put_object(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        self.send(reqwest::Method::PUT, "cw", key, Some(bytes)).await?;
+        Ok(())
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        let response = self.send(reqwest::Method::GET, "r", key, None).await?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| ObjectStorageError::DownloadError(e.to_string()))?;
+
+        Ok(bytes.to_vec())
+    }
+
+    async fn object_exists(&self, key: &str) -> Result<bool> {
+        match self.send(reqwest::Method::HEAD, "r", key, None).await {
+            Ok(_) => Ok(true),
+            Err(ObjectStorageError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        match self.send(reqwest::Method::DELETE, "d", key, None).await {
+            Ok(_) | Err(ObjectStorageError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn presigned_get_url(&self, key: &str, expires_in: Duration) -> String {
+        self.signed_url("r", key, Self::expiry(expires_in))
+    }
+
+    fn presigned_put_url(&self, key: &str, expires_in: Duration) -> String {
+        self.signed_url("cw", key, Self::expiry(expires_in))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_signed_url() {
+        let azure = Azure::new("namada", "ceremony", &base64::encode("key")).unwrap();
+        let expiry = Utc.ymd(2022, 10, 14).and_hms(12, 0, 0);
+        let url = azure.signed_url("cw", "round_1/chunk_0/contribution_1.unverified", expiry);
+
+        let signature = base64::encode(hmac_sha256(
+            b"key",
+            b"cw\n\n2022-10-14T12:00:00Z\n/blob/namada/ceremony/round_1/chunk_0/contribution_1.unverified\n\n\nhttps\n\
+              2020-12-06\nb\n\n\n\n\n\n\n",
+        ));
+        assert_eq!(
+            url,
+            format!(
+                "https://namada.blob.core.windows.net/ceremony/round_1/chunk_0/contribution_1.unverified?sv=2020-12-06&\
+                 se=2022-10-14T12%3A00%3A00Z&sr=b&sp=cw&spr=https&sig={}",
+                uri_encode(&signature, true)
+            )
+        );
+        assert!(Azure::new("namada", "ceremony", "not base64").is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use super::{check_response, hmac_sha256, uri_encode, ObjectStorage, ObjectStorageError, Result, REQUEST_URL_VALIDITY};
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+/// Host of the XML API of Google Cloud Storage.
+const GCS_HOST: &str = "storage.googleapis.com";
+
+/// The signing algorithm of the urls signed with an HMAC key.
+const ALGORITHM: &str = "GOOG4-HMAC-SHA256";
+
+/// The longest validity of a signed url allowed by Google Cloud Storage, seven days.
+const MAX_VALIDITY: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// A bucket of Google Cloud Storage, accessed with the HMAC key of a service account.
+///
+/// Every request goes through an url signed with the V4 signing process, the one of the urls given to the
+/// contributors, so the credentials are never sent along with the requests.
+pub struct Gcs {
+    client: reqwest::Client,
+    bucket: String,
+    access_id: String,
+    secret: String,
+}
+
+impl Gcs {
+    pub fn new(bucket: &str, access_id: &str, secret: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            bucket: bucket.to_owned(),
+            access_id: access_id.to_owned(),
+            secret: secret.to_owned(),
+        }
+    }
+
+    /// Reads the bucket from `NAMADA_MPC_GCS_BUCKET`, and the HMAC key from `NAMADA_MPC_GCS_ACCESS_ID` and
+    /// `NAMADA_MPC_GCS_SECRET`.
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| {
+            std::env::var(name).map_err(|_| ObjectStorageError::Configuration(format!("missing env {}", name)))
+        };
+
+        Ok(Self::new(
+            &var("NAMADA_MPC_GCS_BUCKET")?,
+            &var("NAMADA_MPC_GCS_ACCESS_ID")?,
+            &var("NAMADA_MPC_GCS_SECRET")?,
+        ))
+    }
+
+    /// Returns the url allowing `method` on the object under `key` for the given duration from `now`.
+    fn signed_url(&self, method: &str, key: &str, expires_in: Duration, now: DateTime<Utc>) -> String {
+        let date = now.format("%Y%m%d").to_string();
+        let timestamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let scope = format!("{}/auto/storage/goog4_request", date);
+        let path = format!("/{}/{}", self.bucket, uri_encode(key, false));
+
+        // The parameters are sorted by name
+        let query = format!(
+            "X-Goog-Algorithm={}&X-Goog-Credential={}&X-Goog-Date={}&X-Goog-Expires={}&X-Goog-SignedHeaders=host",
+            ALGORITHM,
+            uri_encode(&format!("{}/{}", self.access_id, scope), true),
+            timestamp,
+            expires_in.min(MAX_VALIDITY).as_secs(),
+        );
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\n\nhost\nUNSIGNED-PAYLOAD",
+            method, path, query, GCS_HOST
+        );
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}",
+            ALGORITHM,
+            timestamp,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let mut key = format!("GOOG4{}", self.secret).into_bytes();
+        for part in [date.as_str(), "auto", "storage", "goog4_request"].iter() {
+            key = hmac_sha256(&key, part.as_bytes());
+        }
+        let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+
+        format!("https://{}{}?{}&X-Goog-Signature={}", GCS_HOST, path, query, signature)
+    }
+
+    /// Sends a request with `method` on the object under `key`, through an url signed for it.
+    async fn send(&self, method: reqwest::Method, key: &str, body: Option<Vec<u8>>) -> Result<reqwest::Response> {
+        let url = self.signed_url(method.as_str(), key, REQUEST_URL_VALIDITY, Utc::now());
+        let mut request = self.client.request(method.clone(), url);
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+
+        let transient: fn(String) -> ObjectStorageError = match method {
+            reqwest::Method::PUT | reqwest::Method::DELETE => ObjectStorageError::UploadError,
+            _ => ObjectStorageError::DownloadError,
+        };
+        let response = request.send().await.map_err(|e| transient(e.to_string()))?;
+        check_response(key, &response, transient)?;
+
+        Ok(response)
+    }
+}
+
+#[rocket::async_trait]
+impl ObjectStorage for Gcs {
+    async fn put_object(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        self.send(reqwest::Method::PUT, key, Some(bytes)).await?;
+        Ok(())
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        let response = self.send(reqwest::Method::GET, key, None).await?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| ObjectStorageError::DownloadError(e.to_string()))?;
+
+        Ok(bytes.to_vec())
+    }
+
+    async fn object_exists(&self, key: &str) -> Result<bool> {
+        match self.send(reqwest::Method::HEAD, key, None).await {
+            Ok(_) => Ok(true),
+            Err(ObjectStorageError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        match self.send(reqwest::Method::DELETE, key, None).await {
+            Ok(_) | Err(ObjectStorageError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn presigned_get_url(&self, key: &str, expires_in: Duration) -> String {
+        self.signed_url("GET", key, expires_in, Utc::now())
+    }
+
+    fn presigned_put_url(&self, key: &str, expires_in: Duration) -> String {
+        self.signed_url("PUT", key, expires_in, Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_signed_url() {
+        let gcs = Gcs::new("namada-ceremony", "GOOG1EXAMPLE", "secret");
+        let now = Utc.ymd(2022, 10, 14).and_hms(12, 0, 0);
+        let key = "round_1/chunk_0/contribution_1.unverified";
+        let url = gcs.signed_url("PUT", key, MAX_VALIDITY * 2, now);
+
+        assert!(url.starts_with(
+            "https://storage.googleapis.com/namada-ceremony/round_1/chunk_0/contribution_1.unverified?\
+             X-Goog-Algorithm=GOOG4-HMAC-SHA256&X-Goog-Credential=GOOG1EXAMPLE%2F20221014%2Fauto%2Fstorage%2F\
+             goog4_request&X-Goog-Date=20221014T120000Z&X-Goog-Expires=604800&X-Goog-SignedHeaders=host&\
+             X-Goog-Signature="
+        ));
+        // The signature covers the method
+        assert_ne!(url, gcs.signed_url("GET", key, MAX_VALIDITY, now));
+        assert_eq!(url.rsplit('=').next().unwrap().len(), 64);
+    }
+}
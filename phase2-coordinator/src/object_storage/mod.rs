@@ -0,0 +1,320 @@
+//! The object storage service where the challenges and the contributions are exchanged with the contributors, through
+//! presigned urls, and where the summary of the contributions is published for the frontend.
+//!
+//! The service is selected with `NAMADA_MPC_OBJECT_STORAGE`: Amazon S3 by default, see [S3Ctx], Google Cloud
+//! Storage, see [Gcs], or Azure Blob Storage, see [Azure]. Each request is retried with an exponential backoff, see
+//! [RetryPolicy].
+
+pub mod azure;
+pub use azure::Azure;
+
+pub mod gcs;
+pub use gcs::Gcs;
+
+pub mod s3;
+pub use s3::S3Ctx;
+
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha2::Sha256;
+use std::{future::Future, time::Duration};
+use thiserror::Error;
+use tracing::warn;
+
+pub const TOKENS_ZIP_FILE: &str = "tokens.zip";
+
+/// Validity of the presigned urls returned to the contributors, unless given otherwise.
+const PRESIGNED_URL_VALIDITY: Duration = Duration::from_secs(600);
+
+/// Validity of the presigned urls through which the coordinator itself sends its requests.
+const REQUEST_URL_VALIDITY: Duration = Duration::from_secs(60);
+
+#[derive(Error, Debug)]
+pub enum ObjectStorageError {
+    #[error("Error while creating the http client: {0}")]
+    Client(String),
+    #[error("Invalid configuration of the object storage: {0}")]
+    Configuration(String),
+    #[error("Error while generating the credentials: {0}")]
+    Credentials(String),
+    #[error("Download of the object failed: {0}")]
+    DownloadError(String),
+    #[error("The object {0} is present but empty")]
+    EmptyObject(String),
+    #[error("Error in IO: {0}")]
+    IOError(#[from] std::io::Error),
+    #[error("The object {0} doesn't exist")]
+    NotFound(String),
+    #[error("The object storage rejected the request: {0}")]
+    Rejected(String),
+    #[error("Upload of the object failed: {0}")]
+    UploadError(String),
+}
+
+impl ObjectStorageError {
+    /// Returns `true` if the request may succeed once retried, the error being transient.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::DownloadError(_) | Self::IOError(_) | Self::UploadError(_))
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ObjectStorageError>;
+
+/// A bucket, or container, of an object storage service.
+#[rocket::async_trait]
+pub trait ObjectStorage: Send + Sync {
+    /// Uploads the object under the given key, replacing the previous one if any.
+    async fn put_object(&self, key: &str, bytes: Vec<u8>) -> Result<()>;
+
+    /// Downloads the object under the given key.
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>>;
+
+    /// Returns `true` if an object is stored under the given key.
+    async fn object_exists(&self, key: &str) -> Result<bool>;
+
+    /// Deletes the object under the given key, if any.
+    async fn delete_object(&self, key: &str) -> Result<()>;
+
+    /// Returns an url to download the object under the given key, valid for the given duration.
+    fn presigned_get_url(&self, key: &str, expires_in: Duration) -> String;
+
+    /// Returns an url to upload an object under the given key, valid for the given duration.
+    fn presigned_put_url(&self, key: &str, expires_in: Duration) -> String;
+}
+
+/// The retries of the failed requests to the object storage, with an exponential backoff and full jitter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The number of attempts of a request, the first one included
+    pub attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Reads the number of attempts from `NAMADA_MPC_OBJECT_STORAGE_ATTEMPTS`, if set.
+    pub fn from_env() -> Self {
+        match std::env::var("NAMADA_MPC_OBJECT_STORAGE_ATTEMPTS") {
+            Ok(attempts) => Self {
+                attempts: attempts.parse::<u32>().unwrap().max(1),
+                ..Default::default()
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Returns the delay before the retry following the given failed attempt, counted from 0.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .initial_backoff
+            .checked_mul(2u32.saturating_pow(attempt))
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff));
+        ceiling.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+    }
+
+    /// Runs the request built by `request`, retrying it while it fails with a transient error.
+    pub async fn run<T, F, Fut>(&self, operation: &str, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match request().await {
+                Err(e) if e.is_transient() && attempt + 1 < self.attempts => {
+                    let backoff = self.backoff(attempt);
+                    warn!("{} failed: {}. Retrying in {:?}", operation, e, backoff);
+                    rocket::tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// The object storage of the ceremony, whose requests are retried according to its [RetryPolicy].
+pub struct ObjectStore {
+    backend: Box<dyn ObjectStorage>,
+    retry: RetryPolicy,
+}
+
+impl ObjectStore {
+    /// Connects to the object storage selected by `NAMADA_MPC_OBJECT_STORAGE`, `s3`, `gcs` or `azure`.
+    pub async fn new() -> Result<Self> {
+        let backend: Box<dyn ObjectStorage> = match std::env::var("NAMADA_MPC_OBJECT_STORAGE").as_deref() {
+            Ok("s3") | Err(_) => Box::new(S3Ctx::new().await?),
+            Ok("gcs") => Box::new(Gcs::from_env()?),
+            Ok("azure") => Box::new(Azure::from_env()?),
+            Ok(other) => {
+                return Err(ObjectStorageError::Configuration(format!(
+                    "unknown object storage {}",
+                    other
+                )));
+            }
+        };
+
+        Ok(Self::with_backend(backend, RetryPolicy::from_env()))
+    }
+
+    pub fn with_backend(backend: Box<dyn ObjectStorage>, retry: RetryPolicy) -> Self {
+        Self { backend, retry }
+    }
+
+    /// Upload contributors.json file for the frontend
+    pub(crate) async fn upload_contributions_info(&self, contributions_info: Vec<u8>) -> Result<()> {
+        // First delete the old file to allow triggering the lambda
+        let key = "contributors.json";
+        self.retry
+            .run("Deletion of the contributions info", || self.backend.delete_object(key))
+            .await?;
+
+        self.retry
+            .run("Upload of the contributions info", || {
+                self.backend.put_object(key, contributions_info.clone())
+            })
+            .await
+    }
+
+    /// Get the url of a challenge, if it's been uploaded.
+    pub(crate) async fn get_challenge_url(&self, key: String) -> Option<String> {
+        match self.retry.run("Lookup of the challenge", || self.backend.object_exists(&key)).await {
+            Ok(true) => Some(self.backend.presigned_get_url(&key, PRESIGNED_URL_VALIDITY)),
+            _ => None,
+        }
+    }
+
+    /// Upload a challenge. Returns the presigned url to get it.
+    pub(crate) async fn upload_challenge(&self, key: String, challenge: Vec<u8>) -> Result<String> {
+        self.retry
+            .run("Upload of the challenge", || self.backend.put_object(&key, challenge.clone()))
+            .await?;
+
+        Ok(self.backend.presigned_get_url(&key, PRESIGNED_URL_VALIDITY))
+    }
+
+    /// Get the urls where to upload a contribution and its signature.
+    pub(crate) fn get_contribution_urls(&self, contrib_key: String, contrib_sig_key: String) -> (String, String) {
+        self.get_contribution_urls_expiring_in(contrib_key, contrib_sig_key, PRESIGNED_URL_VALIDITY)
+    }
+
+    /// Get the urls where to upload a contribution and its signature, valid for the given duration.
+    pub(crate) fn get_contribution_urls_expiring_in(
+        &self,
+        contrib_key: String,
+        contrib_sig_key: String,
+        expires_in: Duration,
+    ) -> (String, String) {
+        // NOTE: urls live for 5 minutes so we cannot cache them for reuse because there's a high chance they expired, we
+        //  need to regenerate them every time
+        (
+            self.backend.presigned_put_url(&contrib_key, expires_in),
+            self.backend.presigned_put_url(&contrib_sig_key, expires_in),
+        )
+    }
+
+    /// Download an object, failing if it's empty.
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        let object = self
+            .retry
+            .run(&format!("Download of {}", key), || self.backend.get_object(key))
+            .await?;
+        if object.is_empty() {
+            return Err(ObjectStorageError::EmptyObject(key.to_owned()));
+        }
+
+        Ok(object)
+    }
+
+    /// Retrieve a contribution and its signature.
+    pub(crate) async fn get_contribution(&self, round_height: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+        let contrib_key = format!("round_{}/chunk_0/contribution_1.unverified", round_height);
+        let contrib_sig_key = format!("round_{}/chunk_0/contribution_1.unverified.signature", round_height);
+
+        rocket::tokio::try_join!(self.get_object(&contrib_key), self.get_object(&contrib_sig_key))
+    }
+
+    /// Retrieve the compressed token folder.
+    pub async fn get_tokens(&self) -> Result<Vec<u8>> {
+        let key = match std::env::var("AWS_S3_PROD") {
+            Ok(t) if t == "true" => format!("production/{}", TOKENS_ZIP_FILE),
+            _ => format!("master/{}", TOKENS_ZIP_FILE),
+        };
+
+        self.get_object(&key).await
+    }
+}
+
+/// Percent encodes the given part of an url, all but the unreserved characters, and the slashes unless
+/// `encode_slash` is `false`.
+pub(crate) fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+/// Returns the HMAC-SHA256 of `data` with the given key.
+pub(crate) fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Returns the error of the given response of an object storage service to a request on the object under `key`, if
+/// it failed. The server errors and the throttling are transient, the other client errors aren't.
+pub(crate) fn check_response(
+    key: &str,
+    response: &reqwest::Response,
+    transient: fn(String) -> ObjectStorageError,
+) -> Result<()> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else if status == reqwest::StatusCode::NOT_FOUND {
+        Err(ObjectStorageError::NotFound(key.to_owned()))
+    } else if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        Err(transient(format!("{} answered {}", key, status)))
+    } else {
+        Err(ObjectStorageError::Rejected(format!("{} answered {}", key, status)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::default();
+        for attempt in 0..64 {
+            let ceiling = (policy.initial_backoff * 2u32.saturating_pow(attempt.min(31))).min(policy.max_backoff);
+            assert!(policy.backoff(attempt) <= ceiling);
+        }
+    }
+
+    #[test]
+    fn test_uri_encode() {
+        assert_eq!(
+            uri_encode("round_1/chunk_0/contribution 1~", false),
+            "round_1/chunk_0/contribution%201~"
+        );
+        assert_eq!(uri_encode("a/b+c", true), "a%2Fb%2Bc");
+    }
+}
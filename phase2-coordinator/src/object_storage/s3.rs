@@ -0,0 +1,144 @@
+use super::{ObjectStorage, ObjectStorageError, Result};
+
+use lazy_static::lazy_static;
+use rocket::tokio::io::AsyncReadExt;
+use rusoto_core::{region::Region, RusotoError};
+use rusoto_credential::{AwsCredentials, ChainProvider, ProvideAwsCredentials};
+use rusoto_s3::{
+    util::{PreSignedRequest, PreSignedRequestOption},
+    DeleteObjectRequest, GetObjectRequest, HeadObjectRequest, PutObjectRequest, S3Client, StreamingBody, S3,
+};
+use std::{str::FromStr, time::Duration};
+
+lazy_static! {
+    static ref BUCKET: String = std::env::var("AWS_S3_BUCKET").unwrap_or("bucket".to_string());
+    pub static ref REGION: Region = {
+        match std::env::var("AWS_REGION") {
+            Ok(region) => Region::from_str(&region).expect("Region must be a valid region"),
+            Err(_) => Region::EuWest1,
+        }
+    };
+    static ref S3_REGION: Region = Region::Custom {
+        name: REGION.name().to_string(),
+        endpoint: format!("{}.s3-accelerate.amazonaws.com", *BUCKET),
+    };
+}
+
+/// The bucket `AWS_S3_BUCKET` of Amazon S3, accessed through its transfer acceleration endpoint.
+pub struct S3Ctx {
+    client: S3Client,
+    bucket: &'static String,
+    region: &'static Region,
+    credentials: AwsCredentials,
+}
+
+impl S3Ctx {
+    pub async fn new() -> Result<Self> {
+        let provider = ChainProvider::new();
+        let credentials = provider
+            .credentials()
+            .await
+            .map_err(|e| ObjectStorageError::Credentials(e.to_string()))?;
+        let client = S3Client::new(S3_REGION.clone());
+
+        Ok(Self {
+            client,
+            bucket: &BUCKET,
+            region: &S3_REGION,
+            credentials,
+        })
+    }
+}
+
+#[rocket::async_trait]
+impl ObjectStorage for S3Ctx {
+    async fn put_object(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let put_object_request = PutObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.to_owned(),
+            body: Some(StreamingBody::from(bytes)),
+            ..Default::default()
+        };
+
+        self.client
+            .put_object(put_object_request)
+            .await
+            .map_err(|e| ObjectStorageError::UploadError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        let get_request = GetObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.to_owned(),
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        let stream = self
+            .client
+            .get_object(get_request)
+            .await
+            .map_err(|e| match e {
+                RusotoError::Service(_) => ObjectStorageError::NotFound(key.to_owned()),
+                e => ObjectStorageError::DownloadError(e.to_string()),
+            })?
+            .body
+            .ok_or_else(|| ObjectStorageError::EmptyObject(key.to_owned()))?;
+        stream.into_async_read().read_to_end(&mut buffer).await?;
+
+        Ok(buffer)
+    }
+
+    async fn object_exists(&self, key: &str) -> Result<bool> {
+        let head = HeadObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.to_owned(),
+            ..Default::default()
+        };
+
+        // The answer to a head request has no body, a missing object is only told by the status
+        match self.client.head_object(head).await {
+            Ok(_) => Ok(true),
+            Err(RusotoError::Service(_)) => Ok(false),
+            Err(RusotoError::Unknown(response)) if response.status.as_u16() == 404 => Ok(false),
+            Err(e) => Err(ObjectStorageError::DownloadError(e.to_string())),
+        }
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        let delete_object_request = DeleteObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.to_owned(),
+            ..Default::default()
+        };
+
+        self.client
+            .delete_object(delete_object_request)
+            .await
+            .map_err(|e| ObjectStorageError::UploadError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn presigned_get_url(&self, key: &str, expires_in: Duration) -> String {
+        let get = GetObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.to_owned(),
+            ..Default::default()
+        };
+
+        get.get_presigned_url(self.region, &self.credentials, &PreSignedRequestOption { expires_in })
+    }
+
+    fn presigned_put_url(&self, key: &str, expires_in: Duration) -> String {
+        let put = PutObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.to_owned(),
+            ..Default::default()
+        };
+
+        put.get_presigned_url(self.region, &self.credentials, &PreSignedRequestOption { expires_in })
+    }
+}
@@ -1,12 +1,28 @@
-use crate::authentication::{KeyPair, Production, Signature};
+use crate::{
+    authentication::{verify_hex_signature, KeyPair, Signature},
+    objects::IdentityProof,
+};
 
+use bech32::Variant;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 
+/// Number of hex characters of the public key displayed in the attestation fingerprint
+const FINGERPRINT_LEN: usize = 16;
+/// Maximum number of characters of the statement of an attestation
+pub const MAX_ATTESTATION_STATEMENT_LEN: usize = 2048;
+
+/// Returns the first characters of the public key, used to identify a contributor in public messages.
+fn fingerprint(public_key: &str) -> &str {
+    public_key.get(..FINGERPRINT_LEN).unwrap_or(public_key)
+}
+
 #[derive(Debug, Error)]
 pub enum ContributionInfoError {
+    #[error("Contribution file hash is not hex encoded: {0}")]
+    InvalidHash(String),
     #[error("Keypair doesn't match the pubkey")]
     InvalidSigKey,
     #[error("Error while serializing ContributionInfo: {0}")]
@@ -69,6 +85,147 @@ impl From<ContributionTimeStamps> for TrimmedContributionTimeStamps {
     }
 }
 
+/// A binding between the ceremony public key of a contributor and a Namada address, signed with the ceremony key.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NamadaAddressBinding {
+    /// Bech32m encoded Namada address
+    pub address: String,
+    /// Signature of the [`binding_message`](Self::binding_message), hex encoded
+    pub signature: String,
+}
+
+impl NamadaAddressBinding {
+    /// Signs the binding between the public key of the keypair and the given address with the signature scheme.
+    pub fn try_new(
+        signature: &dyn Signature,
+        keypair: &KeyPair,
+        address: String,
+    ) -> Result<Self, ContributionInfoError> {
+        let signature = signature
+            .sign(keypair.sigkey(), &Self::binding_message(keypair.pubkey(), &address))
+            .map_err(|e| ContributionInfoError::SignatureError(format!("{}", e)))?;
+
+        Ok(Self { address, signature })
+    }
+
+    /// Returns the message signed by the contributor to bind its public key to the address.
+    pub fn binding_message(public_key: &str, address: &str) -> String {
+        phase2_client_sdk::namada_address_binding_message(public_key, address)
+    }
+
+    /// Checks that the address is a valid bech32m string and that the binding was signed by the given public key.
+    pub fn verify(&self, public_key: &str) -> bool {
+        match bech32::decode(&self.address) {
+            Ok((_, _, Variant::Bech32m)) => (),
+            _ => return false,
+        }
+
+        verify_hex_signature(
+            public_key,
+            &Self::binding_message(public_key, &self.address),
+            &self.signature,
+        )
+    }
+}
+
+/// Magic bytes and version opening a detached OpenTimestamps proof
+const OTS_HEADER: &[u8] = b"\x00OpenTimestamps\x00\x00Proof\x00\xbf\x89\xe2\xe8\x84\xe8\x92\x94\x01";
+/// OpenTimestamps tag of the sha256 operation
+const OTS_SHA256_TAG: u8 = 0x08;
+
+/// An OpenTimestamps proof over the hash of a contribution file, giving independent evidence of when the
+/// contribution occurred.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TimestampProof {
+    /// Url of the calendar server which issued the proof
+    pub calendar: String,
+    /// Sha256 digest, hex encoded, of the decoded contribution file hash, i.e. the timestamped value
+    pub digest: String,
+    /// Base64 encoded detached `.ots` proof, pending until the calendar commits it to the Bitcoin blockchain
+    pub proof: String,
+    pub requested_at: DateTime<Utc>,
+}
+
+impl TimestampProof {
+    /// Returns the digest to submit to the calendar for the given contribution file hash.
+    pub fn digest(contribution_file_hash: &str) -> Result<[u8; 32], ContributionInfoError> {
+        let hash = hex::decode(contribution_file_hash).map_err(|e| ContributionInfoError::InvalidHash(e.to_string()))?;
+
+        Ok(Sha256::digest(&hash).into())
+    }
+
+    /// Builds the proof from the timestamp returned by the calendar for the digest. The proof can be upgraded and
+    /// checked with the `ots` client once the calendar has committed it.
+    pub fn new(calendar: String, digest: [u8; 32], calendar_timestamp: &[u8]) -> Self {
+        let mut proof = Vec::with_capacity(OTS_HEADER.len() + 1 + digest.len() + calendar_timestamp.len());
+        proof.extend_from_slice(OTS_HEADER);
+        proof.push(OTS_SHA256_TAG);
+        proof.extend_from_slice(&digest);
+        proof.extend_from_slice(calendar_timestamp);
+
+        Self {
+            calendar,
+            digest: hex::encode(digest),
+            proof: base64::encode(proof),
+            requested_at: Utc::now(),
+        }
+    }
+}
+
+/// A free-text attestation of a contribution, signed with the ceremony key of the contributor.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AttestationStatement {
+    pub statement: String,
+    /// Optional url of a public post of the attestation
+    pub url: Option<String>,
+    /// Signature of the [`statement_message`](Self::statement_message), hex encoded
+    pub signature: String,
+}
+
+impl AttestationStatement {
+    /// Signs the statement attesting the contribution of the given round with the keypair and the signature scheme.
+    pub fn try_new(
+        signature: &dyn Signature,
+        keypair: &KeyPair,
+        round_height: u64,
+        statement: String,
+        url: Option<String>,
+    ) -> Result<Self, ContributionInfoError> {
+        let signature = signature
+            .sign(
+                keypair.sigkey(),
+                &Self::statement_message(keypair.pubkey(), round_height, &statement),
+            )
+            .map_err(|e| ContributionInfoError::SignatureError(format!("{}", e)))?;
+
+        Ok(Self {
+            statement,
+            url,
+            signature,
+        })
+    }
+
+    /// Returns the message signed by the contributor to attest its contribution with the statement.
+    pub fn statement_message(public_key: &str, round_height: u64, statement: &str) -> String {
+        phase2_client_sdk::attestation_statement_message(public_key, round_height, statement)
+    }
+
+    /// Checks that the statement is not empty nor longer than [`MAX_ATTESTATION_STATEMENT_LEN`] and that it was
+    /// signed by the given public key for the given round.
+    pub fn verify(&self, public_key: &str, round_height: u64) -> bool {
+        let len = self.statement.chars().count();
+        if self.statement.trim().is_empty() || len > MAX_ATTESTATION_STATEMENT_LEN {
+            return false;
+        }
+
+        verify_hex_signature(
+            public_key,
+            &Self::statement_message(public_key, round_height, &self.statement),
+            &self.signature,
+        )
+    }
+}
+
 /// Summary info about the contribution
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct ContributionInfo {
@@ -96,6 +253,21 @@ pub struct ContributionInfo {
     pub contribution_file_signature: String,
     /// Url providing an attestation of the contribution
     pub attestation: Option<String>,
+    /// Whether the coordinator checked that the attestation contains the signed canonical message
+    #[serde(default)]
+    pub attestation_verified: bool,
+    /// Signed free-text attestation of the contribution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation_statement: Option<AttestationStatement>,
+    /// Optional binding between the ceremony public key and a Namada address of the contributor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namada_address: Option<NamadaAddressBinding>,
+    /// Verified proof linking the ceremony key to a Keybase or OpenPGP identity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_proof: Option<IdentityProof>,
+    /// External timestamp proof over the contribution file hash, obtained after the verification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_proof: Option<TimestampProof>,
     // Some timestamps to get performance metrics of the ceremony
     pub timestamps: ContributionTimeStamps,
     // Signature of this struct, computed on the json string encoding of all the other fields of this struct
@@ -122,8 +294,8 @@ impl ContributionInfo {
         Ok(format!("{:x?}", hasher.finalize()))
     }
 
-    /// Computes the signature of a json string encoding the struct.
-    pub fn try_sign(&mut self, keypair: &KeyPair) -> Result<(), ContributionInfoError> {
+    /// Computes the signature of a json string encoding the struct with the keypair and the signature scheme.
+    pub fn try_sign(&mut self, signature: &dyn Signature, keypair: &KeyPair) -> Result<(), ContributionInfoError> {
         let digest = self.hash_for_signature()?;
 
         // Compute signature
@@ -132,7 +304,7 @@ impl ContributionInfo {
             return Err(ContributionInfoError::InvalidSigKey);
         }
 
-        let contrib_info_signature = Production
+        let contrib_info_signature = signature
             .sign(keypair.sigkey(), digest.as_str())
             .map_err(|e| ContributionInfoError::SignatureError(format!("{}", e)))?;
         self.contributor_info_signature = contrib_info_signature;
@@ -140,12 +312,42 @@ impl ContributionInfo {
         Ok(())
     }
 
+    /// Returns a short fingerprint of the contributor's public key.
+    pub fn fingerprint(&self) -> &str {
+        fingerprint(&self.public_key)
+    }
+
+    /// Returns the canonical message that the contributor should post publicly to attest its contribution.
+    /// The message embeds the file hash exposed as `contribution_hash` in the contributions' summary, so that
+    /// third parties can mechanically match a public post with the corresponding transcript entry.
+    pub fn attestation_message(&self) -> String {
+        format!(
+            "I contributed to the Namada Trusted Setup ceremony at round {} with contribution hash {} (fingerprint {})",
+            self.ceremony_round,
+            self.contribution_file_hash,
+            self.fingerprint()
+        )
+    }
+
+    /// Checks that the content of a public attestation contains the canonical [`attestation_message`](Self::attestation_message)
+    /// immediately followed by its [`hex`] encoded signature, computed with the contributor's key.
+    pub fn verify_attestation_content(&self, content: &str) -> bool {
+        let message = self.attestation_message();
+
+        content.match_indices(message.as_str()).any(|(index, _)| {
+            match content[index + message.len()..].split_whitespace().next() {
+                Some(signature) => verify_hex_signature(self.public_key.as_str(), message.as_str(), signature),
+                None => false,
+            }
+        })
+    }
+
     /// Verifies the signature.
     #[cfg(test)]
-    fn verify_signature(&self) -> Result<bool, ContributionInfoError> {
+    pub(crate) fn verify_signature(&self) -> Result<bool, ContributionInfoError> {
         let serialized_contrib_info = self.hash_for_signature()?;
 
-        Ok(Production.verify(
+        Ok(verify_hex_signature(
             self.public_key.as_str(),
             serialized_contrib_info.as_str(),
             self.contributor_info_signature.as_str(),
@@ -165,6 +367,16 @@ pub struct TrimmedContributionInfo {
     contribution_hash: String,
     contribution_hash_signature: String,
     attestation: Option<String>,
+    #[serde(default)]
+    attestation_verified: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    attestation_statement: Option<AttestationStatement>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    namada_address: Option<NamadaAddressBinding>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    identity_proof: Option<IdentityProof>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp_proof: Option<TimestampProof>,
     timestamps: TrimmedContributionTimeStamps,
 }
 
@@ -180,12 +392,21 @@ impl From<ContributionInfo> for TrimmedContributionInfo {
             contribution_hash: parent.contribution_file_hash,
             contribution_hash_signature: parent.contribution_file_signature,
             attestation: parent.attestation,
+            attestation_verified: parent.attestation_verified,
+            attestation_statement: parent.attestation_statement,
+            namada_address: parent.namada_address,
+            identity_proof: parent.identity_proof,
+            timestamp_proof: parent.timestamp_proof,
             timestamps: parent.timestamps.into(),
         }
     }
 }
 
 impl TrimmedContributionInfo {
+    pub fn full_name(&self) -> Option<&str> {
+        self.full_name.as_deref()
+    }
+
     pub fn public_key(&self) -> &str {
         self.public_key.as_ref()
     }
@@ -194,6 +415,46 @@ impl TrimmedContributionInfo {
         self.ceremony_round
     }
 
+    pub fn fingerprint(&self) -> &str {
+        fingerprint(&self.public_key)
+    }
+
+    pub fn contribution_hash(&self) -> &str {
+        self.contribution_hash.as_ref()
+    }
+
+    pub fn start_contribution(&self) -> DateTime<Utc> {
+        self.timestamps.start_contribution
+    }
+
+    pub fn end_contribution(&self) -> DateTime<Utc> {
+        self.timestamps.end_contribution
+    }
+
+    pub fn attestation(&self) -> Option<&str> {
+        self.attestation.as_deref()
+    }
+
+    pub fn is_attestation_verified(&self) -> bool {
+        self.attestation_verified
+    }
+
+    pub fn attestation_statement(&self) -> Option<&AttestationStatement> {
+        self.attestation_statement.as_ref()
+    }
+
+    pub fn namada_address(&self) -> Option<&str> {
+        self.namada_address.as_ref().map(|b| b.address.as_str())
+    }
+
+    pub fn identity_proof(&self) -> Option<&IdentityProof> {
+        self.identity_proof.as_ref()
+    }
+
+    pub fn timestamp_proof(&self) -> Option<&TimestampProof> {
+        self.timestamp_proof.as_ref()
+    }
+
     #[cfg(debug_assertions)]
     pub fn is_another_machine(&self) -> bool {
         self.is_another_machine
@@ -207,9 +468,13 @@ impl TrimmedContributionInfo {
 
 #[cfg(test)]
 mod tests {
-    use crate::authentication::KeyPair;
+    use crate::authentication::{KeyPair, Production, Signature};
 
-    use super::ContributionInfo;
+    use super::{
+        AttestationStatement, ContributionInfo, NamadaAddressBinding, TimestampProof, MAX_ATTESTATION_STATEMENT_LEN,
+        OTS_HEADER,
+    };
+    use bech32::ToBase32;
 
     #[test]
     fn sign_and_verify() {
@@ -218,7 +483,7 @@ mod tests {
         let mut test_info = ContributionInfo::default();
         test_info.public_key = keypair.pubkey().to_owned();
 
-        test_info.try_sign(&keypair).unwrap();
+        test_info.try_sign(&Production, &keypair).unwrap();
         assert!(test_info.verify_signature().unwrap());
 
         // Test custom
@@ -230,7 +495,128 @@ mod tests {
         test_info.contribution_file_hash = String::from("Not a valid file hash");
         test_info.contribution_file_signature = String::from("Not a valid file signature");
 
-        test_info.try_sign(&keypair).unwrap();
+        test_info.try_sign(&Production, &keypair).unwrap();
         assert!(test_info.verify_signature().unwrap());
     }
+
+    #[test]
+    fn attestation_message() {
+        let keypair = KeyPair::new();
+        let mut test_info = ContributionInfo::default();
+        test_info.public_key = keypair.pubkey().to_owned();
+        test_info.ceremony_round = 3;
+        test_info.contribution_file_hash = String::from("abcdef0123456789");
+
+        let message = test_info.attestation_message();
+        assert_eq!(test_info.fingerprint(), &keypair.pubkey()[..16]);
+        assert!(message.contains("round 3"));
+        assert!(message.contains(&test_info.contribution_file_hash));
+        assert!(message.contains(test_info.fingerprint()));
+    }
+
+    #[test]
+    fn verify_attestation_content() {
+        let keypair = KeyPair::new();
+        let mut test_info = ContributionInfo::default();
+        test_info.public_key = keypair.pubkey().to_owned();
+        test_info.ceremony_round = 3;
+        test_info.contribution_file_hash = String::from("abcdef0123456789");
+
+        let message = test_info.attestation_message();
+        let signature = Production.sign(keypair.sigkey(), &message).unwrap();
+
+        // Ok, signed message embedded in a page
+        let content = format!("<p>{} {}</p>", message, signature);
+        assert!(test_info.verify_attestation_content(&content));
+
+        // Wrong, missing signature
+        assert!(!test_info.verify_attestation_content(&message));
+
+        // Wrong, malformed signature
+        let content = format!("{} not_a_signature", message);
+        assert!(!test_info.verify_attestation_content(&content));
+
+        // Wrong, signature from another key
+        let other_keypair = KeyPair::new();
+        let signature = Production.sign(other_keypair.sigkey(), &message).unwrap();
+        let content = format!("{} {}", message, signature);
+        assert!(!test_info.verify_attestation_content(&content));
+    }
+
+    #[test]
+    fn verify_namada_address_binding() {
+        let keypair = KeyPair::new();
+        let address = bech32::encode("atest", b"imp::test address".to_base32(), bech32::Variant::Bech32m).unwrap();
+        let binding = NamadaAddressBinding::try_new(&Production, &keypair, address.clone()).unwrap();
+        assert!(binding.verify(keypair.pubkey()));
+
+        // Wrong, binding signed by another key
+        let other_keypair = KeyPair::new();
+        assert!(!binding.verify(other_keypair.pubkey()));
+
+        // Wrong, not a bech32m address
+        let binding = NamadaAddressBinding::try_new(&Production, &keypair, String::from("not_an_address")).unwrap();
+        assert!(!binding.verify(keypair.pubkey()));
+
+        // Wrong, malformed signature
+        let binding = NamadaAddressBinding {
+            address,
+            signature: String::from("not_a_signature"),
+        };
+        assert!(!binding.verify(keypair.pubkey()));
+    }
+
+    #[test]
+    fn verify_attestation_statement() {
+        let keypair = KeyPair::new();
+        let attestation = AttestationStatement::try_new(
+            &Production,
+            &keypair,
+            3,
+            String::from("I contributed from an air-gapped machine"),
+            None,
+        )
+        .unwrap();
+        assert!(attestation.verify(keypair.pubkey(), 3));
+
+        // Wrong, statement signed for another round
+        assert!(!attestation.verify(keypair.pubkey(), 4));
+
+        // Wrong, statement signed by another key
+        let other_keypair = KeyPair::new();
+        assert!(!attestation.verify(other_keypair.pubkey(), 3));
+
+        // Wrong, empty or too long statement
+        let attestation = AttestationStatement::try_new(&Production, &keypair, 3, String::from(" "), None).unwrap();
+        assert!(!attestation.verify(keypair.pubkey(), 3));
+        let statement = "a".repeat(MAX_ATTESTATION_STATEMENT_LEN + 1);
+        let attestation = AttestationStatement::try_new(&Production, &keypair, 3, statement, None).unwrap();
+        assert!(!attestation.verify(keypair.pubkey(), 3));
+
+        // Wrong, malformed signature
+        let attestation = AttestationStatement {
+            statement: String::from("statement"),
+            url: None,
+            signature: String::from("not_a_signature"),
+        };
+        assert!(!attestation.verify(keypair.pubkey(), 3));
+    }
+
+    #[test]
+    fn build_timestamp_proof() {
+        let digest = TimestampProof::digest("abcdef0123456789").unwrap();
+        let calendar_timestamp = [0xf0, 0x10, 0x00];
+        let proof = TimestampProof::new(String::from("https://calendar"), digest, &calendar_timestamp);
+        assert_eq!(proof.digest, hex::encode(digest));
+
+        // The proof is made of the header, the sha256 tag, the digest and the timestamp of the calendar
+        let bytes = base64::decode(&proof.proof).unwrap();
+        assert!(bytes.starts_with(OTS_HEADER));
+        assert_eq!(bytes[OTS_HEADER.len()], 0x08);
+        assert_eq!(&bytes[OTS_HEADER.len() + 1..OTS_HEADER.len() + 33], &digest);
+        assert!(bytes.ends_with(&calendar_timestamp));
+
+        // Wrong, hash not hex encoded
+        assert!(TimestampProof::digest("not_an_hash").is_err());
+    }
 }
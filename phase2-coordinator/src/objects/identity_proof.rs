@@ -0,0 +1,187 @@
+use pgp::{Deserializable, Message, SignedPublicKey};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IdentityProofError {
+    #[error("Keybase username or OpenPGP fingerprint is not valid: {0}")]
+    InvalidIdentity(String),
+    #[error("Public key of the identity is not valid: {0}")]
+    InvalidPublicKey(String),
+    #[error("Signed statement is not a valid OpenPGP message: {0}")]
+    InvalidMessage(String),
+    #[error("Signature of the statement doesn't match the identity key")]
+    InvalidSignature,
+    #[error("Signed statement doesn't match the expected one")]
+    WrongStatement,
+}
+
+/// The public identity to which the contributor links its ceremony key.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum IdentityProvider {
+    /// Keybase username
+    Keybase(String),
+    /// Fingerprint of an OpenPGP key published on keys.openpgp.org
+    OpenPgp(String),
+}
+
+/// An OpenPGP signed statement linking the ceremony key of a contributor to an existing public identity.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IdentityProof {
+    pub provider: IdentityProvider,
+    /// Armored OpenPGP message containing the [`statement`](Self::statement), signed with the identity key
+    pub signed_statement: String,
+}
+
+impl IdentityProof {
+    /// Returns the statement that the contributor must sign with the key of its public identity.
+    pub fn statement(public_key: &str) -> String {
+        format!("I control the Namada Trusted Setup public key {}", public_key)
+    }
+
+    /// Returns the url from which to fetch the armored public key of the identity. The username must be made of
+    /// alphanumeric characters and underscores, and the fingerprint of hex digits, optionally separated by spaces.
+    pub fn key_url(&self) -> Result<String, IdentityProofError> {
+        match &self.provider {
+            IdentityProvider::Keybase(username) => {
+                if username.is_empty() || !username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    return Err(IdentityProofError::InvalidIdentity(username.clone()));
+                }
+
+                Ok(format!("https://keybase.io/{}/pgp_keys.asc", username))
+            }
+            IdentityProvider::OpenPgp(fingerprint) => {
+                let fingerprint = fingerprint.replace(' ', "").to_uppercase();
+                if fingerprint.is_empty() || !fingerprint.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(IdentityProofError::InvalidIdentity(fingerprint));
+                }
+
+                Ok(format!(
+                    "https://keys.openpgp.org/vks/v1/by-fingerprint/{}",
+                    fingerprint
+                ))
+            }
+        }
+    }
+
+    /// Checks that the signed statement links the given ceremony public key and that it has been signed
+    /// by the provided armored key of the identity, or by one of its subkeys.
+    pub fn verify(&self, armored_identity_key: &str, public_key: &str) -> Result<(), IdentityProofError> {
+        let (identity_key, _) = SignedPublicKey::from_string(armored_identity_key)
+            .map_err(|e| IdentityProofError::InvalidPublicKey(e.to_string()))?;
+        let (message, _) = Message::from_string(&self.signed_statement)
+            .map_err(|e| IdentityProofError::InvalidMessage(e.to_string()))?;
+
+        let is_signed = message.verify(&identity_key).is_ok()
+            || identity_key
+                .public_subkeys
+                .iter()
+                .any(|subkey| message.verify(&subkey.key).is_ok());
+        if !is_signed {
+            return Err(IdentityProofError::InvalidSignature);
+        }
+
+        let content = message
+            .get_content()
+            .map_err(|e| IdentityProofError::InvalidMessage(e.to_string()))?
+            .unwrap_or_default();
+        match String::from_utf8_lossy(&content).trim() == Self::statement(public_key) {
+            true => Ok(()),
+            false => Err(IdentityProofError::WrongStatement),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pgp::{crypto::hash::HashAlgorithm, KeyType, SecretKeyParamsBuilder};
+
+    #[test]
+    fn identity_proof() {
+        let proof = IdentityProof {
+            provider: IdentityProvider::OpenPgp(String::from("abcd 1234")),
+            signed_statement: String::from("not an armored message"),
+        };
+        assert_eq!(
+            proof.key_url().unwrap(),
+            "https://keys.openpgp.org/vks/v1/by-fingerprint/ABCD1234"
+        );
+        assert!(IdentityProof::statement("pubkey").ends_with("pubkey"));
+
+        // Wrong, malformed key
+        assert!(matches!(
+            proof.verify("not an armored key", "pubkey"),
+            Err(IdentityProofError::InvalidPublicKey(_))
+        ));
+    }
+
+    #[test]
+    fn key_url() {
+        let proof = |provider| IdentityProof {
+            provider,
+            signed_statement: String::new(),
+        };
+
+        assert_eq!(
+            proof(IdentityProvider::Keybase(String::from("namada_1")))
+                .key_url()
+                .unwrap(),
+            "https://keybase.io/namada_1/pgp_keys.asc"
+        );
+
+        // Wrong, the username or the fingerprint would alter the url
+        for provider in vec![
+            IdentityProvider::Keybase(String::new()),
+            IdentityProvider::Keybase(String::from("../admin")),
+            IdentityProvider::Keybase(String::from("user?redirect=https://example.com")),
+            IdentityProvider::OpenPgp(String::from(" ")),
+            IdentityProvider::OpenPgp(String::from("abcd/../1234")),
+            IdentityProvider::OpenPgp(String::from("abcg")),
+        ] {
+            assert!(matches!(
+                proof(provider).key_url(),
+                Err(IdentityProofError::InvalidIdentity(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn verify_signed_statement() {
+        let secret_key = SecretKeyParamsBuilder::default()
+            .key_type(KeyType::EdDSA)
+            .can_sign(true)
+            .primary_user_id(String::from("Contributor <contributor@example.com>"))
+            .build()
+            .unwrap()
+            .generate()
+            .unwrap()
+            .sign(String::new)
+            .unwrap();
+        let identity_key = secret_key
+            .public_key()
+            .sign(&secret_key, String::new)
+            .unwrap()
+            .to_armored_string(None)
+            .unwrap();
+        let sign = |statement: &str| {
+            Message::new_literal("statement", statement)
+                .sign(&secret_key, String::new, HashAlgorithm::SHA2_256)
+                .unwrap()
+                .to_armored_string(None)
+                .unwrap()
+        };
+
+        let proof = IdentityProof {
+            provider: IdentityProvider::Keybase(String::from("contributor")),
+            signed_statement: sign(&IdentityProof::statement("pubkey")),
+        };
+        proof.verify(&identity_key, "pubkey").unwrap();
+
+        // Wrong, statement for another ceremony key
+        assert!(matches!(
+            proof.verify(&identity_key, "other_pubkey"),
+            Err(IdentityProofError::WrongStatement)
+        ));
+    }
+}
@@ -10,6 +10,9 @@ pub use contribution_file_signature::*;
 pub mod contribution_info;
 pub use contribution_info::*;
 
+pub mod identity_proof;
+pub use identity_proof::*;
+
 pub mod participant;
 pub use participant::*;
 
@@ -18,3 +21,6 @@ pub use round::*;
 
 pub mod task;
 pub use task::Task;
+
+pub mod transcript;
+pub use transcript::*;
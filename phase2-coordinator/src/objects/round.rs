@@ -29,35 +29,7 @@ where
 }
 
 /// Locators for files that are locked by [Round::try_lock_chunk()]
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LockedLocators {
-    previous_contribution: ContributionLocator,
-    current_contribution: ContributionLocator,
-    next_contribution: ContributionLocator,
-    next_contribution_file_signature: ContributionSignatureLocator,
-}
-
-impl LockedLocators {
-    /// Get a reference previous contribution's locator.
-    pub fn previous_contribution(&self) -> ContributionLocator {
-        self.previous_contribution
-    }
-
-    /// Get a reference current contribution's locator.
-    pub fn current_contribution(&self) -> ContributionLocator {
-        self.current_contribution
-    }
-
-    /// Get a reference next contribution's locator.
-    pub fn next_contribution(&self) -> ContributionLocator {
-        self.next_contribution
-    }
-
-    /// Get a reference next contribution's signtature locator.
-    pub fn next_contribution_file_signature(&self) -> ContributionSignatureLocator {
-        self.next_contribution_file_signature
-    }
-}
+pub use phase2_client_sdk::LockedLocators;
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, SerdeDiff)]
 #[serde(rename_all = "camelCase")]
@@ -174,6 +146,18 @@ impl Round {
         self.height
     }
 
+    /// Returns the time at which the round started, if recorded.
+    #[inline]
+    pub fn started_at(&self) -> Option<OffsetDateTime> {
+        self.started_at
+    }
+
+    /// Returns the time at which the round finished, once complete.
+    #[inline]
+    pub fn finished_at(&self) -> Option<OffsetDateTime> {
+        self.finished_at
+    }
+
     /// Returns the number of contributors authorized for this round.
     #[inline]
     pub fn number_of_contributors(&self) -> u64 {
@@ -581,6 +565,7 @@ impl Round {
                     current_contribution,
                     next_contribution,
                     next_contribution_file_signature,
+                    expires_at: None,
                 }
             }
             Participant::Verifier(_) => {
@@ -615,6 +600,7 @@ impl Round {
                 storage.initialize(
                     Locator::ContributionFile(locked_locators.next_contribution.clone()),
                     Object::anoma_contribution_file_size(
+                        environment,
                         locked_locators.next_contribution.round_height(),
                         locked_locators.next_contribution.contribution_id(),
                     ),
@@ -638,7 +624,7 @@ impl Round {
     /// Initialize the files for the next challenge
     pub fn initialize_verifier_response_files(
         &self,
-        _environment: &Environment,
+        environment: &Environment,
         storage: &mut Disk,
         participant: &Participant,
         _chunk_id: u64,
@@ -651,6 +637,7 @@ impl Round {
         storage.initialize(
             Locator::ContributionFile(locators.next_contribution.clone()),
             Object::anoma_contribution_file_size(
+                environment,
                 locators.next_contribution.round_height(),
                 locators.next_contribution.contribution_id(),
             ),
@@ -730,6 +717,7 @@ impl Round {
             current_contribution,
             next_contribution,
             next_contribution_file_signature,
+            expires_at: None,
         })
     }
 
@@ -0,0 +1,317 @@
+use crate::authentication::{active_public_keys, verify_rotation_chain, Bls, ContributionReceipt, KeyRotation};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use time::OffsetDateTime;
+
+/// A contribution of the transcript, together with the signed hashes of the files it relates to.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TranscriptContribution {
+    pub contribution_id: u64,
+    /// Public key of the contributor, hex encoded
+    pub contributor_public_key: String,
+    /// Blake2b-512, hex encoded, hash of the challenge the contribution was computed on
+    pub challenge_hash: String,
+    /// Blake2b-512, hex encoded, hash of the response of the contributor
+    pub response_hash: String,
+    /// Signature of the contributor over the challenge and response hashes, hex encoded. Empty if the signature is
+    /// part of the [`aggregate_signature`](TranscriptRound::aggregate_signature) of the round.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub signature: String,
+    pub verified: bool,
+    /// Public key of the verifier, hex encoded, if the contribution has been verified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verifier_public_key: Option<String>,
+    /// Blake2b-512, hex encoded, hash of the next challenge produced by the verifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_challenge_hash: Option<String>,
+    /// Signature of the verifier over the response and next challenge hashes, hex encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verifier_signature: Option<String>,
+}
+
+impl TranscriptContribution {
+    /// Returns the message signed by the contributor, the serialized `ContributionState` of its hashes.
+    pub fn signature_message(&self) -> String {
+        json!({
+            "challengeHash": self.challenge_hash,
+            "responseHash": self.response_hash,
+        })
+        .to_string()
+    }
+}
+
+/// The contributions to a chunk, ordered by contribution id.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TranscriptChunk {
+    pub chunk_id: u64,
+    pub contributions: Vec<TranscriptContribution>,
+}
+
+/// The chunks of a round, ordered by chunk id.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TranscriptRound {
+    pub round_height: u64,
+    pub chunks: Vec<TranscriptChunk>,
+    /// Aggregate of the BLS signatures of the contributions of the round, hex encoded, if the contributors signed
+    /// with BLS keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregate_signature: Option<String>,
+}
+
+impl TranscriptRound {
+    /// Returns the contributions whose signature is part of the aggregate signature of the round.
+    pub fn aggregated_contributions(&self) -> impl Iterator<Item = &TranscriptContribution> {
+        self.chunks
+            .iter()
+            .flat_map(|chunk| chunk.contributions.iter())
+            .filter(|contribution| contribution.signature.is_empty())
+    }
+
+    /// Verifies the aggregate signature of the round against the hashes of the aggregated contributions. Returns
+    /// `true` if the round has no aggregate signature.
+    pub fn verify_aggregate_signature(&self) -> bool {
+        let aggregate = match &self.aggregate_signature {
+            Some(aggregate) => aggregate,
+            None => return true,
+        };
+
+        let (public_keys, messages): (Vec<&str>, Vec<String>) = self
+            .aggregated_contributions()
+            .map(|c| (c.contributor_public_key.as_str(), c.signature_message()))
+            .unzip();
+        let messages: Vec<&str> = messages.iter().map(String::as_str).collect();
+
+        Bls::aggregate_verify(&public_keys, &messages, aggregate)
+    }
+}
+
+/// The state of a chunk of a round, with the contributions signed so far.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RoundChunk {
+    pub chunk_id: u64,
+    /// Public key of the participant holding the lock on the chunk, hex encoded, if any
+    pub lock_holder: Option<String>,
+    /// The expected contributions have all been made and verified
+    pub complete: bool,
+    /// Contributions ordered by contribution id, with their individual signature
+    pub contributions: Vec<TranscriptContribution>,
+}
+
+/// The detail of a round, as served by `GET /round/<height>`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RoundInfo {
+    pub round_height: u64,
+    #[serde(with = "time::serde::timestamp::option")]
+    pub started_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::timestamp::option")]
+    pub finished_at: Option<OffsetDateTime>,
+    /// Public keys of the contributors of the round, hex encoded
+    pub contributors: Vec<String>,
+    /// Public keys of the verifiers of the round, hex encoded
+    pub verifiers: Vec<String>,
+    /// Every chunk of the round is complete
+    pub complete: bool,
+    /// Chunks ordered by chunk id
+    pub chunks: Vec<RoundChunk>,
+}
+
+/// A contribution made by a contributor, as listed in its history.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ContributionRecord {
+    pub round_height: u64,
+    pub chunk_id: u64,
+    pub contribution_id: u64,
+    /// Blake2b-512, hex encoded, hash of the challenge the contribution was computed on
+    pub challenge_hash: String,
+    /// Blake2b-512, hex encoded, hash of the response, the one to publish in the attestation
+    pub response_hash: String,
+    pub verified: bool,
+    /// Time at which the contributor started the contribution, as reported in its contribution info
+    #[serde(with = "time::serde::timestamp::option")]
+    pub started_at: Option<OffsetDateTime>,
+    /// Time at which the contributor completed the contribution, as reported in its contribution info
+    #[serde(with = "time::serde::timestamp::option")]
+    pub completed_at: Option<OffsetDateTime>,
+    /// Receipt of the contribution, signed by the coordinator
+    pub receipt: ContributionReceipt,
+}
+
+/// The final contribution of the ceremony, computed by the coordinator from a public random beacon.
+///
+/// Anyone can recompute it: the seed of the contribution is derived from the beacon by iterating
+/// sha256 `2^10` times, as done by `setup_utils::beacon_randomness`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BeaconContribution {
+    /// Height of the round whose verified challenge the beacon was applied to
+    pub round_height: u64,
+    /// Value of the beacon (e.g. a future block hash), hex encoded
+    pub beacon: String,
+    /// Blake2b-512, hex encoded, hash of the challenge the beacon was applied to
+    pub challenge_hash: String,
+    /// Blake2b-512, hex encoded, hash of the final parameters
+    pub response_hash: String,
+    /// Signature of the coordinator over the [`message`](Self::message), hex encoded
+    pub signature: String,
+}
+
+impl BeaconContribution {
+    /// Returns the message signed by the coordinator.
+    pub fn message(&self) -> String {
+        format!(
+            "beacon:{}:{}:{}:{}",
+            self.round_height, self.beacon, self.challenge_hash, self.response_hash
+        )
+    }
+}
+
+/// The public transcript of the ceremony, listing every contribution of every round.
+///
+/// The transcript only depends on the files in storage: exporting it twice
+/// from the same state produces the same document.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Transcript {
+    /// Current public key of the coordinator's verifier, hex encoded
+    pub coordinator_public_key: String,
+    /// Rounds ordered by height
+    pub rounds: Vec<TranscriptRound>,
+    /// Hand-off statements of the rotations of the coordinator key, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_rotations: Vec<KeyRotation>,
+    /// Final contribution of the ceremony, if the random beacon has been applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beacon: Option<BeaconContribution>,
+}
+
+impl Transcript {
+    /// Returns the contribution of the given participant, if any.
+    pub fn find_contribution(&self, public_key: &str) -> Option<(u64, &TranscriptContribution)> {
+        self.rounds.iter().find_map(|round| {
+            round
+                .chunks
+                .iter()
+                .flat_map(|chunk| chunk.contributions.iter())
+                .find(|contribution| contribution.contributor_public_key == public_key)
+                .map(|contribution| (round.round_height, contribution))
+        })
+    }
+
+    /// Returns the public keys of the coordinator active during the given round, in the order they were used.
+    pub fn coordinator_public_keys(&self, round_height: u64) -> Vec<&str> {
+        let initial_public_key = self
+            .key_rotations
+            .first()
+            .map_or(self.coordinator_public_key.as_str(), |r| r.old_public_key.as_str());
+
+        active_public_keys(initial_public_key, &self.key_rotations, round_height)
+    }
+
+    /// Verifies the chain of key rotations up to the current key of the coordinator, and that every response
+    /// verified by the coordinator was verified with a key active during its round.
+    pub fn verify_coordinator_keys(&self) -> bool {
+        if !verify_rotation_chain(&self.key_rotations) {
+            return false;
+        }
+        if let Some(last) = self.key_rotations.last() {
+            if last.new_public_key != self.coordinator_public_key {
+                return false;
+            }
+        }
+
+        let coordinator_keys: Vec<&str> = self
+            .key_rotations
+            .iter()
+            .map(|r| r.old_public_key.as_str())
+            .chain(std::iter::once(self.coordinator_public_key.as_str()))
+            .collect();
+        self.rounds.iter().all(|round| {
+            let active_keys = self.coordinator_public_keys(round.round_height);
+            round
+                .chunks
+                .iter()
+                .flat_map(|chunk| chunk.contributions.iter())
+                .filter_map(|contribution| contribution.verifier_public_key.as_deref())
+                .filter(|verifier| coordinator_keys.contains(verifier))
+                .all(|verifier| active_keys.contains(&verifier))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::{KeyPair, Production, Signature};
+
+    fn contribution(chunk_id: u8) -> (TranscriptContribution, String) {
+        let signing_key = Bls::signing_key(&[chunk_id; 32]).unwrap();
+        let contribution = TranscriptContribution {
+            contribution_id: 1,
+            contributor_public_key: Bls::public_key(&signing_key).unwrap(),
+            challenge_hash: hex::encode([chunk_id; 64]),
+            response_hash: hex::encode([chunk_id + 1; 64]),
+            signature: String::new(),
+            verified: true,
+            verifier_public_key: None,
+            next_challenge_hash: None,
+            verifier_signature: None,
+        };
+        let signature = Bls.sign(&signing_key, &contribution.signature_message()).unwrap();
+
+        (contribution, signature)
+    }
+
+    #[test]
+    fn verify_aggregate_signature() {
+        let (contributions, signatures): (Vec<_>, Vec<_>) = (0..3).map(contribution).unzip();
+        let aggregate = Bls::aggregate(&signatures.iter().map(String::as_str).collect::<Vec<_>>()).unwrap();
+
+        let mut round = TranscriptRound {
+            round_height: 1,
+            chunks: contributions
+                .into_iter()
+                .enumerate()
+                .map(|(chunk_id, contribution)| TranscriptChunk {
+                    chunk_id: chunk_id as u64,
+                    contributions: vec![contribution],
+                })
+                .collect(),
+            aggregate_signature: Some(aggregate),
+        };
+        assert!(round.verify_aggregate_signature());
+
+        // The aggregate doesn't cover a tampered response hash
+        round.chunks[1].contributions[0].response_hash = hex::encode([0u8; 64]);
+        assert!(!round.verify_aggregate_signature());
+    }
+
+    #[test]
+    fn verify_coordinator_keys() {
+        let (old, new) = (KeyPair::new(), KeyPair::new());
+        let rotation = KeyRotation::try_new(&Production, old.sigkey(), old.pubkey(), new.pubkey(), 2, 10).unwrap();
+        let round = |round_height: u64, verifier: &KeyPair| {
+            let (mut contribution, _) = contribution(0);
+            contribution.verifier_public_key = Some(verifier.pubkey().to_string());
+            TranscriptRound {
+                round_height,
+                chunks: vec![TranscriptChunk {
+                    chunk_id: 0,
+                    contributions: vec![contribution],
+                }],
+                aggregate_signature: None,
+            }
+        };
+
+        let mut transcript = Transcript {
+            coordinator_public_key: new.pubkey().to_string(),
+            rounds: vec![round(1, &old), round(2, &old), round(2, &new), round(3, &new)],
+            key_rotations: vec![rotation],
+            beacon: None,
+        };
+        assert_eq!(transcript.coordinator_public_keys(1), vec![old.pubkey()]);
+        assert_eq!(transcript.coordinator_public_keys(2), vec![old.pubkey(), new.pubkey()]);
+        assert!(transcript.verify_coordinator_keys());
+
+        // The old key was not active anymore
+        transcript.rounds.push(round(4, &old));
+        assert!(!transcript.verify_coordinator_keys());
+    }
+}
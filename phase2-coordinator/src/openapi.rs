@@ -0,0 +1,1157 @@
+//! Hand-maintained [OpenAPI](https://spec.openapis.org/oas/v3.0.3) specification of the REST API of the
+//! [Coordinator](`crate::Coordinator`), served at [`OPENAPI_PATH`].
+//!
+//! The schemas mirror the json encoding of the request and response types of [`rest_utils`](crate::rest_utils),
+//! [`objects`](crate::objects) and `phase2_client_sdk`: they must be updated together with these types and with the
+//! routes of [`rest`](crate::rest).
+
+use crate::rest_utils::{
+    ACCEPT_ENCODING_HEADER, ACCESS_SECRET_HEADER, API_PREFIX, API_VERSION, API_VERSION_HEADER, BODY_DIGEST_HEADER,
+    CHALLENGE_HASH_HEADER, CHALLENGE_SIGNATURE_HEADER, CONTENT_ENCODING_HEADER, CONTENT_RANGE_HEADER,
+    CONTRIBUTION_SIGNATURE_HEADER, EMAIL_HEADER, ESTIMATED_WAIT_HEADER, INVITE_CODE_HEADER, PRIORITY_TOKEN_HEADER,
+    PUBKEY_HEADER, SESSION_TOKEN_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+
+use serde_json::{json, Map, Value};
+
+/// Path at which the specification is served.
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+/// Authentication required by an endpoint.
+#[derive(Clone, Copy)]
+enum Auth {
+    /// Public endpoint
+    None,
+    /// Request signed by a participant, or carrying the token of its session
+    Signature,
+    /// Request signed by the coordinator or by an operator
+    Operator,
+    /// Request carrying the access secret of the coordinator
+    AccessSecret,
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer", "format": "int64", "minimum": 0 })
+}
+
+fn number() -> Value {
+    json!({ "type": "number", "format": "double", "minimum": 0 })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+fn date_time() -> Value {
+    json!({ "type": "string", "format": "date-time" })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn nullable(mut schema: Value) -> Value {
+    match schema.get("$ref").cloned() {
+        // Siblings of a reference are ignored, so the reference is wrapped
+        Some(_) => json!({ "allOf": [schema], "nullable": true }),
+        None => {
+            schema["nullable"] = Value::Bool(true);
+            schema
+        }
+    }
+}
+
+/// Tuple encoded as a fixed size json array.
+fn tuple(items: &[Value]) -> Value {
+    json!({
+        "type": "array",
+        "items": { "oneOf": items },
+        "minItems": items.len(),
+        "maxItems": items.len(),
+    })
+}
+
+/// Object with the given `required` and `optional` properties.
+fn object(required: &[(&str, Value)], optional: &[(&str, Value)]) -> Value {
+    let properties: Map<String, Value> = required
+        .iter()
+        .chain(optional.iter())
+        .map(|(name, schema)| (name.to_string(), schema.clone()))
+        .collect();
+    let required: Vec<&str> = required.iter().map(|(name, _)| *name).collect();
+
+    json!({ "type": "object", "required": required, "properties": properties })
+}
+
+fn json_content(schema: Value) -> Value {
+    json!({ "application/json": { "schema": schema } })
+}
+
+fn binary_content(content_type: &str) -> Value {
+    json!({ content_type: { "schema": { "type": "string", "format": "binary" } } })
+}
+
+fn header(name: &str, description: &str, required: bool) -> Value {
+    json!({ "name": name, "in": "header", "required": required, "description": description, "schema": string() })
+}
+
+fn path_parameter(name: &str, schema: Value) -> Value {
+    json!({ "name": name, "in": "path", "required": true, "schema": schema })
+}
+
+fn query_parameter(name: &str, description: &str, schema: Value) -> Value {
+    json!({ "name": name, "in": "query", "required": false, "description": description, "schema": schema })
+}
+
+/// Builder of the description of an endpoint.
+struct Operation {
+    value: Value,
+}
+
+impl Operation {
+    fn new(summary: &str, auth: Auth) -> Self {
+        let mut value = json!({
+            "summary": summary,
+            "responses": {
+                "200": { "description": "Success" },
+                "default": { "description": "Failure", "content": json_content(reference("ErrorResponse")) },
+            },
+        });
+        let security = match auth {
+            Auth::None => None,
+            Auth::Signature => Some(json!([{ "signature": [] }, { "session": [] }])),
+            Auth::Operator => Some(json!([{ "operatorSignature": [] }])),
+            Auth::AccessSecret => Some(json!([{ "accessSecret": [] }])),
+        };
+        if let Some(security) = security {
+            value["security"] = security;
+        }
+
+        Self { value }
+    }
+
+    fn json_request(mut self, schema: Value) -> Self {
+        self.value["requestBody"] = json!({ "required": true, "content": json_content(schema) });
+        self
+    }
+
+    fn binary_request(mut self, content_type: &str) -> Self {
+        self.value["requestBody"] = json!({ "required": true, "content": binary_content(content_type) });
+        self
+    }
+
+    fn json_response(mut self, schema: Value) -> Self {
+        self.value["responses"]["200"]["content"] = json_content(schema);
+        self
+    }
+
+    /// Stream of server-sent events, with the json encoding of the events as data.
+    fn event_stream_response(mut self, schema: Value) -> Self {
+        self.value["responses"]["200"]["content"] = json!({ "text/event-stream": { "schema": schema } });
+        self
+    }
+
+    fn binary_response(mut self, content_type: &str) -> Self {
+        self.value["responses"]["200"]["content"] = binary_content(content_type);
+        self
+    }
+
+    fn parameter(mut self, parameter: Value) -> Self {
+        match self.value["parameters"].as_array_mut() {
+            Some(parameters) => parameters.push(parameter),
+            None => self.value["parameters"] = json!([parameter]),
+        }
+        self
+    }
+
+    fn build(self) -> Value {
+        self.value
+    }
+}
+
+/// Returns the endpoints of the REST API, by path and method.
+fn paths() -> Value {
+    let round_height = || path_parameter("round_height", integer());
+    let pubkey = || path_parameter("pubkey", string());
+    let contribution_signature = || {
+        header(
+            CONTRIBUTION_SIGNATURE_HEADER,
+            "Base64 encoded json signature of the contribution",
+            true,
+        )
+    };
+
+    json!({
+        "/contributor/login": {
+            "get": Operation::new("Get a single-use nonce to sign to log in", Auth::Signature)
+                .json_response(string())
+                .build(),
+            "post": Operation::new("Open a session with the signed nonce", Auth::None)
+                .json_request(reference("LoginRequest"))
+                .json_response(reference("Session"))
+                .build(),
+        },
+        "/contributor/join_queue": {
+            "post": Operation::new("Join the queue with the token of the current cohort", Auth::Signature)
+                .parameter(header(INVITE_CODE_HEADER, "Invite code, required during the invite-only phases", false))
+                .parameter(header(PRIORITY_TOKEN_HEADER, "Priority token, granting a reserved slot", false))
+                .parameter(header(EMAIL_HEADER, "Email address notified when the turn is close", false))
+                .json_request(string())
+                .json_response(integer())
+                .build(),
+        },
+        "/contributor/lock_chunk": {
+            "get": Operation::new("Lock the challenge once it's the turn of the contributor", Auth::Signature)
+                .json_response(reference("LockedChunk"))
+                .build(),
+        },
+        "/contributor/await_turn": {
+            "get": Operation::new("Wait for the turn of the contributor, then lock its challenge", Auth::Signature)
+                .json_response(reference("LockedChunk"))
+                .build(),
+        },
+        "/contributor/challenge": {
+            "post": Operation::new("Get the url of the challenge of the given round", Auth::Signature)
+                .json_request(integer())
+                .json_response(string())
+                .build(),
+        },
+        "/contributor/challenge/{round_height}": {
+            "get": Operation::new("Download the challenge directly from the coordinator", Auth::Signature)
+                .parameter(round_height())
+                .parameter(header(ACCEPT_ENCODING_HEADER, "Accepted compressions, zstd or gzip", false))
+                .binary_response("application/octet-stream")
+                .build(),
+        },
+        "/upload/chunk": {
+            "post": Operation::new("Get the urls where to upload the contribution and its signature", Auth::Signature)
+                .json_request(integer())
+                .json_response(tuple(&[string(), string()]))
+                .build(),
+        },
+        "/contributor/contribute_chunk": {
+            "post": Operation::new("Notify the coordinator of an uploaded contribution", Auth::Signature)
+                .json_request(reference("PostChunkRequest"))
+                .json_response(reference("ContributionReceipt"))
+                .build(),
+        },
+        "/contributor/upload_chunk": {
+            "post": Operation::new("Upload the contribution directly to the coordinator", Auth::Signature)
+                .parameter(contribution_signature())
+                .parameter(header(CONTENT_ENCODING_HEADER, "Compression of the contribution, zstd or gzip", false))
+                .binary_request("application/octet-stream")
+                .json_response(reference("ContributionReceipt"))
+                .build(),
+        },
+        "/contributor/upload_chunk/session": {
+            "get": Operation::new("Get the progress of the resumable upload of the contribution", Auth::Signature)
+                .json_response(reference("UploadSession"))
+                .build(),
+        },
+        "/contributor/upload_chunk/part": {
+            "post": Operation::new("Upload the next part of the contribution", Auth::Signature)
+                .parameter(header(CONTENT_RANGE_HEADER, "Range of the part, as bytes <start>-<end>/<total>", true))
+                .binary_request("application/octet-stream")
+                .json_response(reference("UploadSession"))
+                .build(),
+        },
+        "/contributor/upload_chunk/complete": {
+            "post": Operation::new("Complete the resumable upload of the contribution", Auth::Signature)
+                .parameter(contribution_signature())
+                .json_response(reference("ContributionReceipt"))
+                .build(),
+        },
+        "/contributor/heartbeat": {
+            "post": Operation::new("Let the coordinator know that the participant is alive", Auth::Signature).build(),
+        },
+        "/contributor/abandon": {
+            "post": Operation::new("Give up on the contribution and go back to the queue", Auth::Signature).build(),
+        },
+        "/contributor/rejections": {
+            "get": Operation::new("List the rejected contributions of the participant", Auth::Signature)
+                .json_response(array(reference("ContributionRejection")))
+                .build(),
+        },
+        "/contributor/queue_status": {
+            "get": Operation::new("Get the status of the contributor", Auth::Signature)
+                .json_response(reference("ContributorStatus"))
+                .build(),
+        },
+        "/contributor/contribution_info": {
+            "post": Operation::new("Send the signed summary of the contribution", Auth::Signature)
+                .json_request(reference("ContributionInfo"))
+                .build(),
+        },
+        "/contributor/attestation": {
+            "post": Operation::new("Attest the contribution of the given round", Auth::Signature)
+                .json_request(tuple(&[integer(), reference("AttestationRequest")]))
+                .build(),
+        },
+        "/contributor/namada_address": {
+            "post": Operation::new("Bind the contribution of the given round to a Namada address", Auth::Signature)
+                .json_request(tuple(&[integer(), reference("NamadaAddressBinding")]))
+                .build(),
+        },
+        "/contributor/identity_proof": {
+            "post": Operation::new("Link the contribution of the given round to a public identity", Auth::Signature)
+                .json_request(tuple(&[integer(), reference("IdentityProof")]))
+                .build(),
+        },
+        "/contributor/attestation_message/{pubkey}": {
+            "get": Operation::new("Get the message to publish to attest the contribution of a contributor", Auth::None)
+                .parameter(pubkey())
+                .json_response(string())
+                .build(),
+        },
+        "/contributor/{pubkey}/bundle.zip": {
+            "get": Operation::new("Download the artifacts of the contribution of a contributor", Auth::None)
+                .parameter(pubkey())
+                .binary_response("application/zip")
+                .build(),
+        },
+        "/contributor/{pubkey}/history": {
+            "get": Operation::new("List the contributions of a contributor with their receipts", Auth::None)
+                .parameter(pubkey())
+                .json_response(array(reference("ContributionRecord")))
+                .build(),
+        },
+        "/contributor/{pubkey}/contributed": {
+            "get": Operation::new("Tell whether a public key contributed to the ceremony", Auth::None)
+                .parameter(pubkey())
+                .json_response(boolean())
+                .build(),
+        },
+        "/contributors": {
+            "get": Operation::new("List the contributors of the ceremony, by page", Auth::None)
+                .parameter(query_parameter("page", "Number of the page, starting from 1", integer()))
+                .parameter(query_parameter("per_page", "Number of contributors per page", integer()))
+                .json_response(reference("ContributorsPage"))
+                .build(),
+        },
+        "/events": {
+            "get": Operation::new("Stream the events of the ceremony as server-sent events", Auth::None)
+                .event_stream_response(reference("CeremonyEvent"))
+                .build(),
+        },
+        "/ceremony/status": {
+            "get": Operation::new("Get the public summary of the progress of the ceremony", Auth::None)
+                .json_response(reference("CeremonyStatus"))
+                .build(),
+        },
+        "/coordinator/identity": {
+            "get": Operation::new("Get the ceremony parameters signed by the public key of the coordinator", Auth::None)
+                .json_response(reference("CoordinatorIdentity"))
+                .build(),
+        },
+        "/stats": {
+            "get": Operation::new("Get the aggregates of the ceremony displayed by the dashboard", Auth::None)
+                .json_response(reference("CeremonyStats"))
+                .build(),
+        },
+        "/contribution_info": {
+            "get": Operation::new("Get the public summaries of the contributions", Auth::None)
+                .json_response(array(reference("TrimmedContributionInfo")))
+                .build(),
+        },
+        "/transcript": {
+            "get": Operation::new("Get the public transcript of the ceremony", Auth::None)
+                .json_response(reference("Transcript"))
+                .build(),
+        },
+        "/round/{round_height}": {
+            "get": Operation::new("Get the detail of a round, with its chunks and contributions", Auth::None)
+                .parameter(round_height())
+                .json_response(reference("RoundInfo"))
+                .build(),
+        },
+        "/feed.atom": {
+            "get": Operation::new("Atom feed of the accepted contributions", Auth::None)
+                .binary_response("application/atom+xml")
+                .build(),
+        },
+        "/healthcheck": {
+            "get": Operation::new("Get the health information of the coordinator", Auth::None)
+                .json_response(json!({ "type": "object" }))
+                .build(),
+        },
+        "/healthz": {
+            "get": Operation::new("Liveness probe, successful while the process serves requests", Auth::None).build(),
+        },
+        "/readyz": {
+            "get": Operation::new("Readiness probe, failing with a 503 until the coordinator can serve", Auth::None)
+                .json_response(reference("Readiness"))
+                .build(),
+        },
+        "/coordinator_status": {
+            "get": Operation::new("Get the full state of the coordinator", Auth::AccessSecret)
+                .json_response(json!({ "type": "object" }))
+                .build(),
+        },
+        "/update": {
+            "get": Operation::new("Update the state of the coordinator (debug builds only)", Auth::Operator).build(),
+        },
+        "/stop": {
+            "get": Operation::new(
+                "Drain the coordinator, then shut it down once the contribution in flight is verified",
+                Auth::Operator
+            )
+            .build(),
+        },
+        "/verify": {
+            "get": Operation::new("Verify the pending contributions (debug builds only)", Auth::Operator).build(),
+            "post": Operation::new("Request the verification of the pending contributions", Auth::Operator)
+                .json_response(reference("VerificationStatus"))
+                .build(),
+        },
+        "/verify/status": {
+            "get": Operation::new("Get the progress of the verification of the contributions", Auth::Operator)
+                .json_response(reference("VerificationStatus"))
+                .build(),
+        },
+        "/replication/status": {
+            "get": Operation::new("Get the progress and the lag of the replication of the storage", Auth::Operator)
+                .json_response(reference("ReplicationStatus"))
+                .build(),
+        },
+        "/verifier/lock_task": {
+            "get": Operation::new("Lock the next contribution to verify as an external verifier", Auth::Signature)
+                .json_response(nullable(reference("VerificationTask")))
+                .build(),
+        },
+        "/verifier/challenge": {
+            "get": Operation::new("Download the challenge of the locked contribution", Auth::Signature)
+                .parameter(header(ACCEPT_ENCODING_HEADER, "Accepted compressions, zstd or gzip", false))
+                .binary_response("application/octet-stream")
+                .build(),
+        },
+        "/verifier/response": {
+            "get": Operation::new("Download the response of the locked contribution", Auth::Signature)
+                .parameter(header(ACCEPT_ENCODING_HEADER, "Accepted compressions, zstd or gzip", false))
+                .binary_response("application/octet-stream")
+                .build(),
+        },
+        "/verifier/verification_result": {
+            "post": Operation::new("Send the signed result of the verification", Auth::Signature)
+                .json_request(reference("VerificationResult"))
+                .build(),
+        },
+        "/beacon": {
+            "post": Operation::new("Apply the hex encoded random beacon as the final contribution", Auth::Operator)
+                .json_request(string())
+                .json_response(reference("BeaconContribution"))
+                .build(),
+        },
+        "/update_cohorts": {
+            "post": Operation::new("Update the tokens of the cohorts with a zip archive", Auth::Operator)
+                .json_request(array(integer()))
+                .build(),
+        },
+        "/invite_codes": {
+            "get": Operation::new("List the invite codes", Auth::Operator)
+                .json_response(array(reference("InviteCode")))
+                .build(),
+            "post": Operation::new("Mint new invite codes", Auth::Operator)
+                .json_request(reference("MintInviteCodesRequest"))
+                .json_response(array(reference("InviteCode")))
+                .build(),
+        },
+        "/priority_tokens": {
+            "post": Operation::new("Issue new priority tokens", Auth::Operator)
+                .json_request(reference("IssuePriorityTokensRequest"))
+                .json_response(array(string()))
+                .build(),
+        },
+        "/rotate_key": {
+            "post": Operation::new("Rotate the signing key of the coordinator to a new, persisted one", Auth::Operator)
+                .json_response(reference("KeyRotation"))
+                .build(),
+        },
+        "/ban": {
+            "get": Operation::new("Get the ban list", Auth::Operator)
+                .json_response(reference("BanList"))
+                .build(),
+            "post": Operation::new("Ban a public key and/or an IP", Auth::Operator)
+                .json_request(reference("BanRequest"))
+                .build(),
+        },
+        "/unban": {
+            "post": Operation::new("Lift the ban of a public key and/or an IP", Auth::Operator)
+                .json_request(reference("BanRequest"))
+                .build(),
+        },
+        "/admin/participant/priority": {
+            "post": Operation::new("Set the reliability score of a contributor in the queue", Auth::Operator)
+                .json_request(reference("ParticipantPriorityRequest"))
+                .json_response(integer())
+                .build(),
+        },
+        "/admin/skip_current": {
+            "post": Operation::new("Skip the contributors of the current round", Auth::Operator)
+                .json_request(reference("SkipCurrentRequest"))
+                .json_response(array(string()))
+                .build(),
+        },
+        "/admin/snapshot": {
+            "post": Operation::new("Download a gzipped tarball of the state and the round files", Auth::Operator)
+                .binary_response("application/gzip")
+                .build(),
+        },
+        "/admin/restore": {
+            "post": Operation::new("Restore the ceremony from a snapshot", Auth::Operator)
+                .binary_request("application/octet-stream")
+                .json_response(reference("SnapshotManifest"))
+                .build(),
+        },
+        "/admin/storage_audit": {
+            "get": Operation::new("Audit the files of the completed rounds", Auth::Operator)
+                .json_response(reference("StorageAudit"))
+                .build(),
+        },
+        "/admin/audit/export": {
+            "get": Operation::new("Export the records of the audit log in a time range", Auth::Operator)
+                .parameter(json!({
+                    "name": "format",
+                    "in": "query",
+                    "required": true,
+                    "schema": { "type": "string", "enum": ["csv", "json"] },
+                }))
+                .parameter(query_parameter("from", "Unix timestamp of the first records", integer()))
+                .parameter(query_parameter("to", "Unix timestamp, excluded, of the last records", integer()))
+                .json_response(array(reference("AuditRecord")))
+                .build(),
+        },
+        OPENAPI_PATH: {
+            "get": Operation::new("Get this specification", Auth::None)
+                .json_response(json!({ "type": "object" }))
+                .build(),
+        },
+    })
+}
+
+/// Returns the schemas of the request and response types.
+fn schemas() -> Value {
+    let event_name = |name: &str| json!({ "type": "string", "enum": [name] });
+    let contribution_locator = object(
+        &[
+            ("round_height", integer()),
+            ("chunk_id", integer()),
+            ("contribution_id", integer()),
+            ("is_verified", boolean()),
+        ],
+        &[],
+    );
+    let attestation_fields = [
+        ("attestation", nullable(string())),
+        ("attestation_verified", boolean()),
+        ("attestation_statement", reference("AttestationStatement")),
+        ("namada_address", reference("NamadaAddressBinding")),
+        ("identity_proof", reference("IdentityProof")),
+        ("timestamp_proof", reference("TimestampProof")),
+    ];
+    let contribution_fields = [
+        ("full_name", nullable(string())),
+        ("public_key", string()),
+        ("is_another_machine", boolean()),
+        ("is_own_seed_of_randomness", boolean()),
+        ("joined_cohort", integer()),
+        ("ceremony_round", integer()),
+        ("contribution_hash", string()),
+        ("contribution_hash_signature", string()),
+    ];
+
+    json!({
+        "ErrorCode": {
+            "type": "string",
+            "enum": [
+                "already_contributed",
+                "already_in_queue",
+                "banned",
+                "blacklisted_token",
+                "ceremony_is_over",
+                "draining",
+                "dropped",
+                "internal",
+                "invalid_contribution",
+                "invalid_invite_code",
+                "invalid_priority_token",
+                "invalid_request",
+                "invalid_session",
+                "invalid_signature",
+                "invalid_token",
+                "lock_already_held",
+                "lock_expired",
+                "not_current_contributor",
+                "not_your_turn",
+                "payload_too_large",
+                "rate_limited",
+                "stale_challenge",
+                "storage_unavailable",
+                "suspended",
+                "token_already_in_use",
+                "unauthorized",
+                "unknown_contributor",
+                "unknown_round",
+                "unsupported_api_version",
+            ],
+        },
+        "ErrorResponse": object(
+            &[("code", reference("ErrorCode")), ("message", string())],
+            &[("retry_after", integer()), ("request_id", string())],
+        ),
+        "ContributorStatus": {
+            "description": "Either `{\"Queue\": [position, size]}` or one of the plain strings",
+            "oneOf": [
+                object(&[("Queue", tuple(&[integer(), integer()]))], &[]),
+                { "type": "string", "enum": ["Round", "Finished", "Banned", "Other"] },
+            ],
+        },
+        "CeremonyEvent": {
+            "description": "Data of a server-sent event, whose name is the value of the `event` property",
+            "oneOf": [
+                object(&[("event", event_name("round_advanced")), ("round_height", integer())], &[]),
+                object(
+                    &[
+                        ("event", event_name("contribution_accepted")),
+                        ("round_height", integer()),
+                        ("public_key", string()),
+                    ],
+                    &[],
+                ),
+                object(&[("event", event_name("contributor_dropped")), ("public_key", string())], &[]),
+                object(
+                    &[
+                        ("event", event_name("verification_finished")),
+                        ("round_height", integer()),
+                        ("contribution_id", integer()),
+                        ("verified", boolean()),
+                    ],
+                    &[],
+                ),
+                object(
+                    &[
+                        ("event", event_name("contribution_rejected")),
+                        ("round_height", integer()),
+                        ("public_key", string()),
+                        ("reason", reference("RejectionReason")),
+                    ],
+                    &[],
+                ),
+                object(&[("event", event_name("storage_low")), ("available_bytes", integer())], &[]),
+                object(&[("event", event_name("queue_empty")), ("round_height", integer())], &[]),
+            ],
+        },
+        "CeremonyStatus": object(
+            &[
+                ("round_height", integer()),
+                ("number_of_chunks", integer()),
+                ("queue_length", integer()),
+                ("current_contributor", nullable(string())),
+                ("verified_contributions", integer()),
+            ],
+            &[],
+        ),
+        "CeremonyParameters": object(
+            &[
+                ("contribution_mode", string()),
+                ("proving_system", string()),
+                ("curve", string()),
+                ("power", integer()),
+                ("batch_size", integer()),
+                ("chunk_size", integer()),
+                ("number_of_chunks", integer()),
+            ],
+            &[],
+        ),
+        "CoordinatorIdentity": object(
+            &[
+                ("coordinator_public_key", string()),
+                ("parameters", reference("CeremonyParameters")),
+                ("signed_challenges", boolean()),
+                ("timestamp", integer()),
+                ("signature", string()),
+            ],
+            &[],
+        ),
+        "HourlyContributions": object(&[("hour", integer()), ("contributions", integer())], &[]),
+        "CeremonyStats": object(
+            &[
+                ("contributions_per_hour", array(reference("HourlyContributions"))),
+                ("average_contribution_seconds", nullable(integer())),
+                ("median_contribution_seconds", nullable(integer())),
+                ("unique_countries", nullable(integer())),
+                ("dropout_rate", number()),
+                ("verification_backlog", integer()),
+            ],
+            &[],
+        ),
+        "Readiness": object(
+            &[
+                ("storage_reachable", boolean()),
+                ("round_loaded", boolean()),
+                ("verification_worker_alive", boolean()),
+                ("draining", boolean()),
+            ],
+            &[],
+        ),
+        "PublicContributor": object(
+            &[
+                ("public_key", string()),
+                ("display_name", nullable(string())),
+                ("round_height", integer()),
+                ("contribution_hash", string()),
+            ],
+            &[],
+        ),
+        "ContributorsPage": object(
+            &[
+                ("page", integer()),
+                ("per_page", integer()),
+                ("total", integer()),
+                ("contributors", array(reference("PublicContributor"))),
+            ],
+            &[],
+        ),
+        "ContributionLocator": contribution_locator.clone(),
+        "ContributionSignatureLocator": contribution_locator,
+        "LockedLocators": object(
+            &[
+                ("previous_contribution", reference("ContributionLocator")),
+                ("current_contribution", reference("ContributionLocator")),
+                ("next_contribution", reference("ContributionLocator")),
+                ("next_contribution_file_signature", reference("ContributionSignatureLocator")),
+            ],
+            &[("expires_at", integer())],
+        ),
+        "LockedChunk": {
+            "description": "The fields of LockedLocators, with the upload urls when direct upload is enabled",
+            "allOf": [
+                reference("LockedLocators"),
+                object(
+                    &[],
+                    &[
+                        ("upload_urls", tuple(&[string(), string()])),
+                        ("challenge_hash", string()),
+                        ("challenge_signature", string()),
+                    ],
+                ),
+            ],
+        },
+        "PostChunkRequest": object(
+            &[
+                ("round_height", integer()),
+                ("contribution_locator", reference("ContributionLocator")),
+                ("contribution_signature_locator", reference("ContributionSignatureLocator")),
+            ],
+            &[],
+        ),
+        "LoginRequest": object(&[("pubkey", string()), ("nonce", string()), ("signature", string())], &[]),
+        "Session": object(&[("token", string()), ("expires_at", date_time())], &[]),
+        "UploadSession": object(&[("received", integer()), ("total", integer())], &[]),
+        "ContributionReceipt": object(
+            &[
+                ("round_height", integer()),
+                ("chunk_id", integer()),
+                ("contribution_hash", string()),
+                ("timestamp", integer()),
+                ("coordinator_public_key", string()),
+                ("signature", string()),
+            ],
+            &[],
+        ),
+        "ContributionRecord": object(
+            &[
+                ("round_height", integer()),
+                ("chunk_id", integer()),
+                ("contribution_id", integer()),
+                ("challenge_hash", string()),
+                ("response_hash", string()),
+                ("verified", boolean()),
+                ("started_at", nullable(integer())),
+                ("completed_at", nullable(integer())),
+                ("receipt", reference("ContributionReceipt")),
+            ],
+            &[],
+        ),
+        "RejectionReason": {
+            "type": "string",
+            "enum": ["rejected_by_verifiers", "verification_failed"],
+        },
+        "ContributionRejection": object(
+            &[
+                ("round_height", integer()),
+                ("reason", reference("RejectionReason")),
+                ("message", string()),
+                ("rejected_at", integer()),
+            ],
+            &[],
+        ),
+        "KeyRotation": object(
+            &[
+                ("round_height", integer()),
+                ("old_public_key", string()),
+                ("new_public_key", string()),
+                ("timestamp", integer()),
+                ("signature", string()),
+            ],
+            &[],
+        ),
+        "AttestationStatement": object(
+            &[("statement", string()), ("url", nullable(string())), ("signature", string())],
+            &[],
+        ),
+        "AttestationRequest": {
+            "description": "Either the url of a public post or a signed statement",
+            "oneOf": [string(), reference("AttestationStatement")],
+        },
+        "NamadaAddressBinding": object(&[("address", string()), ("signature", string())], &[]),
+        "VerificationTask": object(
+            &[("round_height", integer()), ("chunk_id", integer()), ("contribution_id", integer())],
+            &[],
+        ),
+        "VerificationResult": object(
+            &[
+                ("task", reference("VerificationTask")),
+                ("response_hash", string()),
+                ("verified", boolean()),
+                ("signature", string()),
+            ],
+            &[],
+        ),
+        "IdentityProof": object(
+            &[
+                ("provider", {
+                    "description": "Either `{\"Keybase\": username}` or `{\"OpenPgp\": fingerprint}`",
+                    "oneOf": [object(&[("Keybase", string())], &[]), object(&[("OpenPgp", string())], &[])],
+                }),
+                ("signed_statement", string()),
+            ],
+            &[],
+        ),
+        "TimestampProof": object(
+            &[
+                ("calendar", string()),
+                ("digest", string()),
+                ("proof", string()),
+                ("requested_at", date_time()),
+            ],
+            &[],
+        ),
+        "ContributionInfo": {
+            "allOf": [
+                object(&contribution_fields, &attestation_fields),
+                object(
+                    &[
+                        ("email", nullable(string())),
+                        ("contribution_file_hash", string()),
+                        ("contribution_file_signature", string()),
+                        ("timestamps", object(
+                            &[
+                                ("start_contribution", date_time()),
+                                ("joined_queue", date_time()),
+                                ("challenge_locked", date_time()),
+                                ("challenge_downloaded", date_time()),
+                                ("start_computation", date_time()),
+                                ("end_computation", date_time()),
+                                ("end_contribution", date_time()),
+                            ],
+                            &[],
+                        )),
+                        ("contributor_info_signature", string()),
+                    ],
+                    &[],
+                ),
+            ],
+        },
+        "TrimmedContributionInfo": {
+            "allOf": [
+                object(&contribution_fields, &attestation_fields),
+                object(
+                    &[(
+                        "timestamps",
+                        object(&[("start_contribution", date_time()), ("end_contribution", date_time())], &[]),
+                    )],
+                    &[],
+                ),
+            ],
+        },
+        "BeaconContribution": object(
+            &[
+                ("round_height", integer()),
+                ("beacon", string()),
+                ("challenge_hash", string()),
+                ("response_hash", string()),
+                ("signature", string()),
+            ],
+            &[],
+        ),
+        "TranscriptContribution": object(
+            &[
+                ("contribution_id", integer()),
+                ("contributor_public_key", string()),
+                ("challenge_hash", string()),
+                ("response_hash", string()),
+                ("verified", boolean()),
+            ],
+            &[
+                ("signature", string()),
+                ("verifier_public_key", string()),
+                ("next_challenge_hash", string()),
+                ("verifier_signature", string()),
+            ],
+        ),
+        "RoundInfo": object(
+            &[
+                ("round_height", integer()),
+                ("started_at", nullable(integer())),
+                ("finished_at", nullable(integer())),
+                ("contributors", array(string())),
+                ("verifiers", array(string())),
+                ("complete", boolean()),
+                ("chunks", array(object(
+                    &[
+                        ("chunk_id", integer()),
+                        ("lock_holder", nullable(string())),
+                        ("complete", boolean()),
+                        ("contributions", array(reference("TranscriptContribution"))),
+                    ],
+                    &[],
+                ))),
+            ],
+            &[],
+        ),
+        "Transcript": object(
+            &[
+                ("coordinator_public_key", string()),
+                ("rounds", array(object(
+                    &[
+                        ("round_height", integer()),
+                        ("chunks", array(object(
+                            &[
+                                ("chunk_id", integer()),
+                                ("contributions", array(reference("TranscriptContribution"))),
+                            ],
+                            &[],
+                        ))),
+                    ],
+                    &[("aggregate_signature", string())],
+                ))),
+            ],
+            &[
+                ("key_rotations", array(reference("KeyRotation"))),
+                ("beacon", reference("BeaconContribution")),
+            ],
+        ),
+        "VerificationStatus": object(
+            &[
+                ("queued", boolean()),
+                ("running", boolean()),
+                ("pending_verifications", integer()),
+                ("last_started", nullable(date_time())),
+                ("last_completed", nullable(date_time())),
+                ("last_error", nullable(string())),
+            ],
+            &[],
+        ),
+        "ReplicationStatus": object(
+            &[
+                ("enabled", boolean()),
+                ("running", boolean()),
+                ("pending_files", integer()),
+                ("replicated_files", integer()),
+                ("last_started", nullable(date_time())),
+                ("last_completed", nullable(date_time())),
+                ("synced_at", nullable(date_time())),
+                ("lag_seconds", nullable(integer())),
+                ("last_error", nullable(string())),
+            ],
+            &[],
+        ),
+        "InviteCode": object(
+            &[
+                ("code", string()),
+                ("notes", nullable(string())),
+                ("created_at", date_time()),
+                ("expires_at", nullable(date_time())),
+                ("redeemed_by", {
+                    "description": "Participant as `<pubkey>.contributor`",
+                    "type": "string",
+                    "nullable": true,
+                }),
+                ("redeemed_at", nullable(date_time())),
+            ],
+            &[],
+        ),
+        "MintInviteCodesRequest": object(
+            &[("count", integer())],
+            &[("notes", nullable(string())), ("expires_in", nullable(integer()))],
+        ),
+        "IssuePriorityTokensRequest": object(
+            &[("count", integer()), ("reliability", integer()), ("expires_at", integer())],
+            &[("not_before", nullable(integer()))],
+        ),
+        "BanRequest": object(&[], &[("pubkey", nullable(string())), ("ip", nullable(string()))]),
+        "BanList": object(&[("pubkeys", array(string())), ("ips", array(string()))], &[]),
+        "ParticipantPriorityRequest": object(&[("pubkey", string()), ("reliability", integer())], &[]),
+        "SkipCurrentRequest": object(&[], &[("ban", boolean()), ("reliability", nullable(integer()))]),
+        "SnapshotManifest": object(
+            &[
+                ("round_height", integer()),
+                ("created_at", integer()),
+                ("files", json!({ "type": "object", "additionalProperties": string() })),
+            ],
+            &[],
+        ),
+        "AuditDiscrepancy": object(
+            &[
+                ("round_height", integer()),
+                ("chunk_id", integer()),
+                ("contribution_id", integer()),
+                (
+                    "issue",
+                    json!({
+                        "type": "string",
+                        "enum": ["missing", "hash_mismatch", "signature_malformed", "signature_invalid"],
+                    }),
+                ),
+            ],
+            &[("path", nullable(string()))],
+        ),
+        "StorageAudit": object(
+            &[
+                ("rounds", array(integer())),
+                ("files_checked", integer()),
+                ("discrepancies", array(reference("AuditDiscrepancy"))),
+            ],
+            &[],
+        ),
+        "AuditRecord": object(
+            &[
+                ("sequence", integer()),
+                ("timestamp", integer()),
+                ("event", json!({ "type": "object" })),
+                ("previous_hash", string()),
+                ("hash", string()),
+                ("coordinator_public_key", string()),
+                ("signature", string()),
+            ],
+            &[],
+        ),
+    })
+}
+
+/// Returns the authentication schemes of the REST API.
+fn security_schemes() -> Value {
+    let signature_description = format!(
+        "Ed25519 signature, hex encoded, of the message `<method>\\n<full path>\\n<{}>\\n<{}>`, followed by \
+         `\\n<Content-Length>\\n<{}>` for requests with a body, then by `\\n{}: <base64 sha-256 of its value>` for \
+         requests carrying it. The headers {} and {} must be sent too. The timestamp is in unix seconds and must be \
+         within the maximum request age of the clock of the coordinator.",
+        TIMESTAMP_HEADER,
+        PUBKEY_HEADER,
+        BODY_DIGEST_HEADER,
+        CONTRIBUTION_SIGNATURE_HEADER,
+        PUBKEY_HEADER,
+        TIMESTAMP_HEADER,
+    );
+
+    json!({
+        "signature": {
+            "type": "apiKey",
+            "in": "header",
+            "name": SIGNATURE_HEADER,
+            "description": format!("Request signed by a participant. {}", signature_description),
+        },
+        "operatorSignature": {
+            "type": "apiKey",
+            "in": "header",
+            "name": SIGNATURE_HEADER,
+            "description": format!("Request signed by the coordinator or by an operator. {}", signature_description),
+        },
+        "session": {
+            "type": "apiKey",
+            "in": "header",
+            "name": SESSION_TOKEN_HEADER,
+            "description": "Token of the session opened at /contributor/login, in place of the signature headers",
+        },
+        "accessSecret": {
+            "type": "apiKey",
+            "in": "header",
+            "name": ACCESS_SECRET_HEADER,
+        },
+    })
+}
+
+/// Returns the OpenAPI specification of the REST API.
+pub fn specification() -> Value {
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Namada Trusted Setup coordinator",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": format!(
+                "REST API of the coordinator of the Namada Trusted Setup ceremony, version {}. Clients may request \
+                 this version in the {} header, the paths are also served without the {} prefix for older clients. \
+                 The challenge download carries its Blake2b-512 hash, hex encoded, in the {} header, and the \
+                 signature of the coordinator in the {} header if it signs the challenges. The answers to the \
+                 contributors in the queue carry their estimated wait in seconds in the {} header, once it can be \
+                 told.",
+                API_VERSION,
+                API_VERSION_HEADER,
+                API_PREFIX,
+                CHALLENGE_HASH_HEADER,
+                CHALLENGE_SIGNATURE_HEADER,
+                ESTIMATED_WAIT_HEADER
+            ),
+        },
+        "servers": [{ "url": API_PREFIX }],
+        "paths": paths(),
+        "components": {
+            "schemas": schemas(),
+            "securitySchemes": security_schemes(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collects the targets of the `$ref` in the given value.
+    fn references(value: &Value, targets: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(target)) = map.get("$ref") {
+                    targets.push(target.clone());
+                }
+                map.values().for_each(|v| references(v, targets));
+            }
+            Value::Array(values) => values.iter().for_each(|v| references(v, targets)),
+            _ => (),
+        }
+    }
+
+    #[test]
+    fn test_specification_references() {
+        let specification = specification();
+        let mut targets = Vec::new();
+        references(&specification, &mut targets);
+        assert!(!targets.is_empty());
+
+        for target in targets {
+            let name = target.strip_prefix("#/components/schemas/").unwrap();
+            assert!(
+                specification["components"]["schemas"].get(name).is_some(),
+                "Missing schema {}",
+                name
+            );
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! The policies selecting which of the contributors waiting in the queue are assigned to the next rounds.
+//!
+//! The policy is set with [`QueueSelection`] in the [`Environment`](crate::environment::Environment): a plain
+//! [`Fifo`], the strict [`Priority`] order of the reliability scores, by default, the [`PriorityWeighted`] order
+//! trading the scores against the waiting time, or a [`Lottery`] drawing the contributors at random.
+
+use crate::objects::Participant;
+
+use blake2::{Blake2b512, Digest};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use time::OffsetDateTime;
+
+/// Default advance in the queue, in seconds, given by each point of reliability score with [`PriorityWeighted`].
+pub const DEFAULT_SECONDS_PER_POINT: u64 = 3600;
+
+/// A contributor waiting in the queue, with its reliability score, last seen time and time of joining.
+pub type QueueEntry = (Participant, u8, OffsetDateTime, OffsetDateTime);
+
+/// Orders the contributors waiting in the queue, the first ones being assigned to the next round.
+pub trait QueuePolicy {
+    /// Sorts the contributors for the assignment of the rounds starting at `next_round`.
+    fn sort(&self, queue: &mut [QueueEntry], next_round: u64);
+}
+
+/// Serves the contributors in the order they joined, regardless of their reliability score.
+pub struct Fifo;
+
+impl QueuePolicy for Fifo {
+    fn sort(&self, queue: &mut [QueueEntry], _next_round: u64) {
+        queue.sort_by_key(|(_, _, _, joined)| *joined);
+    }
+}
+
+/// Serves the contributors with the highest reliability score first, so that the tiers granted by the priority tokens
+/// are served before the others, then in the order they joined.
+pub struct Priority;
+
+impl QueuePolicy for Priority {
+    fn sort(&self, queue: &mut [QueueEntry], _next_round: u64) {
+        queue.sort_by_key(|(_, reliability, _, joined)| (Reverse(*reliability), *joined));
+    }
+}
+
+/// Serves the contributors in the order they joined, each point of reliability score moving a contributor ahead by
+/// `seconds_per_point`. Unlike a strict priority order, a contributor with a low score is still served once it has
+/// waited long enough.
+pub struct PriorityWeighted {
+    pub seconds_per_point: u64,
+}
+
+impl QueuePolicy for PriorityWeighted {
+    fn sort(&self, queue: &mut [QueueEntry], _next_round: u64) {
+        queue.sort_by_key(|(_, reliability, _, joined)| {
+            let advance = *reliability as i128 * self.seconds_per_point as i128;
+            (joined.unix_timestamp() as i128 - advance, *joined)
+        });
+    }
+}
+
+/// Draws the contributors at random, with odds proportional to their reliability score. The draw is derived from a
+/// secret seed of the ceremony, so that it can't be predicted nor ground with fresh keys, and from the round, so that
+/// it holds across the updates of the queue until the round starts.
+pub struct Lottery {
+    pub seed: String,
+}
+
+impl Lottery {
+    /// Returns the rank of the contributor in the draw for `next_round`, the highest drawn first. This is `u^(1/w)`,
+    /// for `u` uniform in `(0, 1]` and `w` the reliability score, as in the weighted sampling of Efraimidis and
+    /// Spirakis.
+    fn rank(&self, participant: &Participant, reliability: u8, next_round: u64) -> f64 {
+        let mut hasher = Blake2b512::new();
+        hasher.update(self.seed.as_bytes());
+        hasher.update(next_round.to_le_bytes());
+        hasher.update(participant.to_string().as_bytes());
+        let digest = hasher.finalize();
+
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        let uniform = ((u64::from_le_bytes(bytes) >> 11) + 1) as f64 / (1u64 << 53) as f64;
+
+        uniform.powf(1.0 / reliability.max(1) as f64)
+    }
+}
+
+impl QueuePolicy for Lottery {
+    fn sort(&self, queue: &mut [QueueEntry], next_round: u64) {
+        // The bits of a positive float are ordered as the float itself
+        queue.sort_by_cached_key(|(participant, reliability, _, joined)| {
+            (
+                Reverse(self.rank(participant, *reliability, next_round).to_bits()),
+                *joined,
+            )
+        });
+    }
+}
+
+/// The queue policy of the ceremony, see [`QueuePolicy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum QueueSelection {
+    /// The [`Fifo`] policy
+    Fifo,
+    /// The [`Priority`] policy
+    Priority,
+    /// The [`PriorityWeighted`] policy, with the advance given by each point of reliability score
+    PriorityWeighted { seconds_per_point: u64 },
+    /// The [`Lottery`] policy, seeded by the coordinator state
+    Lottery,
+}
+
+impl Default for QueueSelection {
+    fn default() -> Self {
+        Self::Priority
+    }
+}
+
+impl QueueSelection {
+    /// Reads the policy from the `NAMADA_MPC_QUEUE_POLICY` env variable, `fifo`, `priority` (default), `weighted` or
+    /// `lottery`, and the advance of the weighted order from `NAMADA_MPC_QUEUE_SECONDS_PER_POINT`.
+    pub fn from_env() -> Self {
+        match std::env::var("NAMADA_MPC_QUEUE_POLICY").as_deref() {
+            Ok("fifo") => Self::Fifo,
+            Ok("weighted") => Self::PriorityWeighted {
+                seconds_per_point: match std::env::var("NAMADA_MPC_QUEUE_SECONDS_PER_POINT") {
+                    Ok(seconds) => seconds.parse().unwrap(),
+                    Err(_) => DEFAULT_SECONDS_PER_POINT,
+                },
+            },
+            Ok("lottery") => Self::Lottery,
+            _ => Self::Priority,
+        }
+    }
+
+    /// Returns the policy, the [`Lottery`] drawing with the given seed.
+    pub fn policy(&self, seed: &str) -> Box<dyn QueuePolicy> {
+        match *self {
+            Self::Fifo => Box::new(Fifo),
+            Self::Priority => Box::new(Priority),
+            Self::PriorityWeighted { seconds_per_point } => Box::new(PriorityWeighted { seconds_per_point }),
+            Self::Lottery => Box::new(Lottery { seed: seed.to_owned() }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    /// Returns a queue of contributors joining one minute apart, with the given reliability scores.
+    fn queue(reliabilities: &[u8]) -> Vec<QueueEntry> {
+        let start = OffsetDateTime::now_utc();
+        reliabilities
+            .iter()
+            .enumerate()
+            .map(|(index, reliability)| {
+                let joined = start + Duration::minutes(index as i64);
+                (
+                    Participant::new_contributor(&index.to_string()),
+                    *reliability,
+                    joined,
+                    joined,
+                )
+            })
+            .collect()
+    }
+
+    fn order(queue: &[QueueEntry]) -> Vec<String> {
+        queue
+            .iter()
+            .map(|(participant, _, _, _)| participant.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_fifo() {
+        let mut contributors = queue(&[10, 20, 10]);
+        contributors.reverse();
+        Fifo.sort(&mut contributors, 1);
+        assert_eq!(order(&contributors), order(&queue(&[10, 20, 10])));
+    }
+
+    #[test]
+    fn test_priority() {
+        let mut contributors = queue(&[10, 20, 10, 20]);
+        Priority.sort(&mut contributors, 1);
+        assert_eq!(
+            order(&contributors),
+            vec!["1.contributor", "3.contributor", "0.contributor", "2.contributor"]
+        );
+        assert_eq!(QueueSelection::default(), QueueSelection::Priority);
+    }
+
+    #[test]
+    fn test_priority_weighted() {
+        let mut contributors = queue(&[10, 10, 20]);
+        PriorityWeighted { seconds_per_point: 60 }.sort(&mut contributors, 1);
+        assert_eq!(
+            order(&contributors),
+            vec!["2.contributor", "0.contributor", "1.contributor"]
+        );
+
+        // The contributors who waited longer than the advance of the higher score are served first
+        let mut contributors = queue(&[10, 10, 20]);
+        PriorityWeighted { seconds_per_point: 1 }.sort(&mut contributors, 1);
+        assert_eq!(
+            order(&contributors),
+            vec!["0.contributor", "1.contributor", "2.contributor"]
+        );
+    }
+
+    #[test]
+    fn test_lottery() {
+        let lottery = Lottery {
+            seed: "seed".to_string(),
+        };
+        let mut first_draw = queue(&[10; 32]);
+        lottery.sort(&mut first_draw, 1);
+
+        // The draw holds for a round, whatever the order of the queue
+        let mut second_draw = queue(&[10; 32]);
+        second_draw.reverse();
+        lottery.sort(&mut second_draw, 1);
+        assert_eq!(order(&first_draw), order(&second_draw));
+
+        // The draw differs across rounds and seeds
+        let mut next_round = queue(&[10; 32]);
+        lottery.sort(&mut next_round, 2);
+        assert_ne!(order(&first_draw), order(&next_round));
+        let mut other_seed = queue(&[10; 32]);
+        Lottery {
+            seed: "other".to_string(),
+        }
+        .sort(&mut other_seed, 1);
+        assert_ne!(order(&first_draw), order(&other_seed));
+
+        // The odds follow the reliability scores
+        let wins = (1..=100)
+            .filter(|round| {
+                let mut contributors = queue(&[1, 100]);
+                lottery.sort(&mut contributors, *round);
+                contributors[0].1 == 100
+            })
+            .count();
+        assert!(wins > 90);
+    }
+}
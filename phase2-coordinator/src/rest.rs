@@ -2,67 +2,256 @@
 
 use std::{
     collections::{HashMap, HashSet},
-    io::{Cursor, Read, Write},
+    convert::TryInto,
+    io::{Cursor, Read, Seek, Write},
+    time::Instant,
 };
-use tracing::warn;
+use tracing::{info, warn};
 
 use crate::{
-    objects::{ContributionInfo, LockedLocators},
+    audit::AuditExportFormat,
+    authentication::{ContributionReceipt, KeyPair, KeyRotation, PriorityToken, SigningBackend},
+    compression,
+    coordinator_state::{InviteCode, INVITE_ONLY},
+    email::validate_address,
+    geolocation::Geolocator,
+    object_storage::ObjectStore,
+    objects::{
+        BeaconContribution, ContributionInfo, ContributionRecord, IdentityProof, NamadaAddressBinding, RoundInfo,
+        Transcript,
+    },
+    openapi,
     rest_utils::{
-        self, ContributorStatus, Coordinator, CurrentContributor, LazyJson, NewParticipant, PostChunkRequest,
-        ResponseError, Result, Secret, ServerAuth, HEALTH_PATH, TOKENS_PATH, TOKENS_ZIP_FILE,
+        self, fetch_public, spawn_blocking, AcceptEncoding, AttestationRequest, BanList, BanRequest, CeremonyStats,
+        CeremonyStatus, ChallengeStream, ContributionPart, ContributionRejection, ContributionSignatureHeader,
+        ContributionUpload, ContributorStatus, ContributorsPage, Coordinator, CoordinatorIdentity, CurrentContributor,
+        ErrorCode, EstimatedWait, ExternalVerifier, IpNotAllowed, IssuePriorityTokensRequest, LazyJson, LockedChunk,
+        LoginRequest, MintInviteCodesRequest, NewParticipant, ParticipantPriorityRequest, PostChunkRequest, RateLimited,
+        Readiness, Replica, ReplicationStatus, ResponseError, Result, Secret, ServerAuth, Session, SkipCurrentRequest,
+        SnapshotUpload, TranscriptCache, UnsupportedApiVersion, UploadSession, VerificationQueue, VerificationResult,
+        VerificationStatus, VerificationTask, AWAIT_TURN_TIMEOUT, DEFAULT_CONTRIBUTORS_PER_PAGE, DIRECT_UPLOAD,
+        EMAIL_HEADER, HEALTH_PATH, IDENTITY_KEY_SIZE_LIMIT, MAX_CONTRIBUTORS_PER_PAGE, MAX_INVITE_CODES_PER_REQUEST,
+        MAX_INVITE_CODE_VALIDITY, TOKENS_PATH, TOKENS_ZIP_FILE, UPDATE_TIME,
     },
-    s3::S3Ctx,
-    storage::{Locator, Object},
-    CoordinatorState, Participant,
+    storage::{ContributionLocator, ContributionSignatureLocator, Locator, Object, SnapshotManifest, StorageAudit},
+    CoordinatorError, CoordinatorState, Participant,
 };
 use rocket::{
-    get, post,
+    get,
+    http::{ContentType, Status},
+    post,
+    response::stream::{Event, EventStream},
     serde::json::Json,
-    tokio::{fs, task},
+    tokio::{fs, select, sync::broadcast::error::RecvError, time::timeout},
     Shutdown, State,
 };
 
+use chrono::{TimeZone, Utc};
 use url::Url;
 
-/// Add the incoming contributor to the queue of contributors.
+/// Maps the errors of a failed login, the other errors of the coordinator being internal.
+fn login_error(error: CoordinatorError) -> ResponseError {
+    match error {
+        CoordinatorError::LoginDisabled
+        | CoordinatorError::LoginNonceInvalid
+        | CoordinatorError::LoginSignatureInvalid => ResponseError::InvalidLogin(error.to_string()),
+        error => ResponseError::CoordinatorError(error),
+    }
+}
+
+/// Get a single-use nonce, valid for a few minutes, to be signed by the participant to log in.
+#[get("/contributor/login")]
+pub async fn get_login_nonce(coordinator: &State<Coordinator>, participant: Participant) -> Result<Json<String>> {
+    let nonce = coordinator
+        .write()
+        .await
+        .login_nonce(&participant.address())
+        .map_err(login_error)?;
+
+    Ok(Json(nonce))
+}
+
+/// Open a session for the participant who signed the nonce. Until it expires, the returned token can be sent in
+/// place of the signature of the requests.
+#[post("/contributor/login", format = "json", data = "<request>")]
+pub async fn login(coordinator: &State<Coordinator>, request: LazyJson<LoginRequest>) -> Result<Json<Session>> {
+    let LoginRequest {
+        pubkey,
+        nonce,
+        signature,
+    } = request.0;
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    let token = spawn_blocking(move || write_lock.login(&pubkey, &nonce, &signature))
+        .await?
+        .map_err(login_error)?;
+
+    Ok(Json(Session {
+        token: token.encode(),
+        expires_at: Utc.timestamp(token.expires_at, 0),
+    }))
+}
+
+/// Add the incoming contributor to the queue of contributors. Returns the cohort of the token, with the estimated wait
+/// in seconds before the turn of the contributor in the [`rest_utils::ESTIMATED_WAIT_HEADER`] once it can be told.
+/// The contributor joins with the reliability score earned in the previous rounds. A priority token, issued with
+/// [`post_priority_tokens`], grants its own score instead so that the contributor is served ahead of the others, and
+/// stands in for the invite code during the invite-only phases.
+/// An email address given in the [`EMAIL_HEADER`] is notified when the turn of the contributor is getting close, and
+/// again when it locks its chunk.
 #[post("/contributor/join_queue", format = "json", data = "<token>")]
 pub async fn join_queue(
     coordinator: &State<Coordinator>,
     new_participant: NewParticipant,
     token: LazyJson<String>,
-) -> Result<Json<u64>> {
+) -> Result<EstimatedWait<Json<u64>>> {
     // NOTE: check on the token happens only here meaning that a contributor can join the ceremony at the very last moment of a cohort and
     // contribute effectively in the following cohort. Forcing the contribution to happen in the correct cohort would take more complicated checks
     // and could lower the amount of contributions received
     let cohort = rest_utils::token_check((*coordinator).clone(), token.as_str()).await?;
+
+    // During the invite-only phases a valid invite code is required, unless a priority token is given, otherwise it's
+    // ignored
+    let priority_token = new_participant.priority_token;
+    let invite_code = match (*INVITE_ONLY, new_participant.invite_code) {
+        (true, None) if priority_token.is_none() => {
+            return Err(ResponseError::InvalidInviteCode(String::from("missing invite code")));
+        }
+        (true, invite_code) => invite_code,
+        (false, _) => None,
+    };
+    let email = new_participant.email;
+    if let Some(email) = &email {
+        validate_address(email).map_err(|_| ResponseError::InvalidHeader(EMAIL_HEADER))?;
+    }
     let mut write_lock = (*coordinator).clone().write_owned().await;
+    let participant = new_participant.participant.clone();
 
-    task::spawn_blocking(move || {
-        write_lock.add_to_queue(
-            new_participant.participant,
-            new_participant.ip_address,
-            token.clone(),
-            10,
-        )
+    let estimated_wait_seconds = spawn_blocking(move || -> std::result::Result<_, CoordinatorError> {
+        // Check the priority token first, to avoid adding the participant if it can't be redeemed
+        let reliability = match &priority_token {
+            Some(priority_token) => write_lock.priority_token_checks(priority_token)?,
+            None => write_lock.reliability_score(&participant),
+        };
+
+        match invite_code {
+            Some(invite_code) => write_lock.add_to_queue_with_invite_code(
+                new_participant.participant,
+                new_participant.ip_address,
+                token.clone(),
+                &invite_code,
+                reliability,
+            ),
+            None => write_lock.add_to_queue(
+                new_participant.participant,
+                new_participant.ip_address,
+                token.clone(),
+                reliability,
+            ),
+        }?;
+        if let Some(priority_token) = priority_token {
+            write_lock.redeem_priority_token(&priority_token, &participant)?;
+        }
+        if let Some(email) = email {
+            write_lock.register_email(&participant, email)?;
+        }
+
+        let (queue_position, _) = write_lock.queue_position(&participant).unwrap_or_default();
+        Ok(write_lock.estimated_wait_seconds(queue_position))
     })
     .await?
-    .map_err(|e| ResponseError::CoordinatorError(e))?;
+    .map_err(|e| match e {
+        CoordinatorError::InviteCodeAlreadyRedeemed
+        | CoordinatorError::InviteCodeExpired
+        | CoordinatorError::InviteCodeInvalid => ResponseError::InvalidInviteCode(e.to_string()),
+        CoordinatorError::PriorityTokenAlreadyRedeemed
+        | CoordinatorError::PriorityTokenInactive
+        | CoordinatorError::PriorityTokenInvalid => ResponseError::InvalidPriorityToken(e.to_string()),
+        _ => ResponseError::CoordinatorError(e),
+    })?;
 
-    Ok(Json(cohort))
+    Ok(EstimatedWait(Json(cohort), estimated_wait_seconds))
 }
 
 /// Lock a [Chunk](`crate::objects::Chunk`) in the ceremony. This should be the first function called when attempting to contribute to a chunk. Once the chunk is locked, it is ready to be downloaded.
+/// If direct upload is enabled, the response also contains the urls where to upload the contribution, sparing the call to [`get_contribution_url`].
 #[get("/contributor/lock_chunk", format = "json")]
-pub async fn lock_chunk(
-    coordinator: &State<Coordinator>,
-    participant: CurrentContributor,
-) -> Result<Json<LockedLocators>> {
-    let mut write_lock = (*coordinator).clone().write_owned().await;
-    match task::spawn_blocking(move || write_lock.try_lock(&participant)).await? {
-        Ok((_, locked_locators)) => Ok(Json(locked_locators)),
-        Err(e) => Err(ResponseError::CoordinatorError(e)),
+pub async fn lock_chunk(coordinator: &State<Coordinator>, participant: CurrentContributor) -> Result<Json<LockedChunk>> {
+    try_lock_chunk(coordinator, (*participant).clone()).await.map(Json)
+}
+
+/// Wait until it's the turn of the contributor in the queue, then lock its [Chunk](`crate::objects::Chunk`) like [`lock_chunk`], sparing the polling of [`get_contributor_queue_status`].
+/// The request is held open for at most [`AWAIT_TURN_TIMEOUT`]: if the turn of the contributor hasn't come by then, it fails with [`ErrorCode::NotYourTurn`] and can be sent again. Waiting also counts as a heartbeat.
+#[get("/contributor/await_turn", format = "json")]
+pub async fn await_turn(coordinator: &State<Coordinator>, participant: Participant) -> Result<Json<LockedChunk>> {
+    let mut events = coordinator.read().await.subscribe();
+    let deadline = Instant::now() + AWAIT_TURN_TIMEOUT;
+
+    loop {
+        {
+            let mut write_lock = coordinator.write().await;
+            if write_lock.is_current_contributor(&participant) {
+                break;
+            }
+            if !write_lock.is_queue_contributor(&participant) {
+                return Err(ResponseError::UnknownContributor(participant.address()));
+            }
+            write_lock
+                .heartbeat(&participant)
+                .map_err(|e| ResponseError::CoordinatorError(e))?;
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(ResponseError::CoordinatorError(CoordinatorError::ParticipantNotReady));
+        }
+
+        // The turn of the contributor comes with a new round, while the queue also changes at every update of the
+        // coordinator
+        let _ = timeout(UPDATE_TIME.min(deadline - now), events.recv()).await;
     }
+
+    try_lock_chunk(coordinator, participant).await.map(Json)
+}
+
+/// Lock the [Chunk](`crate::objects::Chunk`) of the current contributor, with the direct upload urls if enabled.
+async fn try_lock_chunk(coordinator: &State<Coordinator>, participant: Participant) -> Result<LockedChunk> {
+    // The store is set up before locking the chunk, for a failure not to leave the contributor with a lock and no urls
+    let object_store = match *DIRECT_UPLOAD {
+        true => Some(ObjectStore::new().await?),
+        false => None,
+    };
+
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+    let (locked_locators, lock_timeout, challenge_hash, challenge_signature) =
+        spawn_blocking(move || -> std::result::Result<_, CoordinatorError> {
+            let lock_timeout = write_lock.environment().participant_lock_timeout();
+            let (chunk_id, locked_locators) = write_lock.try_lock(&participant)?;
+            let challenge_hash = write_lock.issued_challenge_hash(&participant, chunk_id);
+            let round_height = locked_locators.next_contribution().round_height();
+            let challenge_signature = match &challenge_hash {
+                Some(hash) => write_lock.challenge_signature(round_height, hash)?,
+                None => None,
+            };
+            Ok((locked_locators, lock_timeout, challenge_hash, challenge_signature))
+        })
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    let upload_urls = object_store.map(|object_store| {
+        let round_height = locked_locators.next_contribution().round_height();
+        let (contrib_key, contrib_sig_key) = contribution_keys(round_height);
+        let expires_in = std::time::Duration::from_secs(lock_timeout.whole_seconds().max(0) as u64);
+
+        object_store.get_contribution_urls_expiring_in(contrib_key, contrib_sig_key, expires_in)
+    });
+
+    Ok(LockedChunk {
+        locators: locked_locators,
+        upload_urls,
+        challenge_hash,
+        challenge_signature,
+    })
 }
 
 /// Get the challenge key on Amazon S3 from the [Coordinator](`crate::Coordinator`).
@@ -72,45 +261,93 @@ pub async fn get_challenge_url(
     _participant: CurrentContributor,
     round_height: LazyJson<u64>,
 ) -> Result<Json<String>> {
-    let s3_ctx = S3Ctx::new().await?;
+    let object_store = ObjectStore::new().await?;
     let key = format!("round_{}/chunk_0/contribution_0.verified", *round_height);
 
     // If challenge is already on S3 (round rollback) immediately return the key
-    if let Some(url) = s3_ctx.get_challenge_url(key.clone()).await {
+    if let Some(url) = object_store.get_challenge_url(key.clone()).await {
         return Ok(Json(url));
     }
 
     // Since we don't chunk the parameters, we have one chunk and one allowed contributor per round. Thus the challenge will always be located at round_{i}/chunk_0/contribution_0.verified
     // For example, the 1st challenge (after the initialization) is located at round_1/chunk_0/contribution_0.verified
     let read_lock = (*coordinator).clone().read_owned().await;
-    let challenge = match task::spawn_blocking(move || read_lock.get_challenge(*round_height, 0, 0, true)).await? {
+    let challenge = match spawn_blocking(move || read_lock.get_challenge(*round_height, 0, 0, true)).await? {
         Ok(challenge) => challenge,
         Err(e) => return Err(ResponseError::CoordinatorError(e)),
     };
 
     // Upload challenge to S3 and return url
-    let url = s3_ctx.upload_challenge(key, challenge).await?;
+    let url = object_store.upload_challenge(key, challenge).await?;
 
     Ok(Json(url))
 }
 
+/// Download the challenge of the given round directly from the [Coordinator](`crate::Coordinator`), as an alternative to the download from S3.
+/// The challenge is streamed from disk as a binary response carrying its hash, and its signature if the coordinator signs the challenges, in headers.
+/// If the client accepts it, the challenge is compressed with zstd or gzip.
+#[get("/contributor/challenge/<round_height>")]
+pub async fn get_challenge(
+    coordinator: &State<Coordinator>,
+    _participant: CurrentContributor,
+    accept_encoding: AcceptEncoding,
+    round_height: u64,
+) -> Result<ChallengeStream> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let encoding = accept_encoding.0;
+    let (file, hash, signature) = spawn_blocking(move || -> std::result::Result<_, CoordinatorError> {
+        let (path, hash) = read_lock.challenge_file(round_height)?;
+        let signature = read_lock.challenge_signature(round_height, &hash)?;
+        Ok((read_lock.serve_file(&path, encoding)?, hash, signature))
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(ChallengeStream {
+        file,
+        hash,
+        signature,
+        encoding,
+    })
+}
+
+/// Returns the S3 keys of the contribution of the given round and of its signature.
+fn contribution_keys(round_height: u64) -> (String, String) {
+    (
+        format!("round_{}/chunk_0/contribution_1.unverified", round_height),
+        format!("round_{}/chunk_0/contribution_1.unverified.signature", round_height),
+    )
+}
+
+/// Checks that the lock of the contributor hasn't expired, dropping it from the round otherwise.
+async fn check_lock_expiry(coordinator: &State<Coordinator>, participant: Participant) -> Result<()> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || write_lock.check_lock_expiry(&participant))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
 /// Request the urls where to upload a [Chunk](`crate::objects::Chunk`) contribution and the ContributionFileSignature.
+/// Fails if the lock of the contributor has expired.
 #[post("/upload/chunk", format = "json", data = "<round_height>")]
 pub async fn get_contribution_url(
-    _participant: CurrentContributor,
+    coordinator: &State<Coordinator>,
+    participant: CurrentContributor,
     round_height: LazyJson<u64>,
 ) -> Result<Json<(String, String)>> {
-    let contrib_key = format!("round_{}/chunk_0/contribution_1.unverified", *round_height);
-    let contrib_sig_key = format!("round_{}/chunk_0/contribution_1.unverified.signature", *round_height);
+    check_lock_expiry(coordinator, participant.clone()).await?;
+    let (contrib_key, contrib_sig_key) = contribution_keys(*round_height);
 
     // Prepare urls for the upload
-    let s3_ctx = S3Ctx::new().await?;
-    let urls = s3_ctx.get_contribution_urls(contrib_key, contrib_sig_key);
+    let object_store = ObjectStore::new().await?;
+    let urls = object_store.get_contribution_urls(contrib_key, contrib_sig_key);
 
     Ok(Json(urls))
 }
 
 /// Notify the [Coordinator](`crate::Coordinator`) of a finished and uploaded [Contribution](`crate::objects::Contribution`). This will unlock the given [Chunk](`crate::objects::Chunk`).
+/// Returns a [ContributionReceipt] signed by the coordinator, or fails if the lock of the contributor has expired.
 #[post(
     "/contributor/contribute_chunk",
     format = "json",
@@ -120,22 +357,177 @@ pub async fn contribute_chunk(
     coordinator: &State<Coordinator>,
     participant: CurrentContributor,
     contribute_chunk_request: LazyJson<PostChunkRequest>,
-) -> Result<()> {
+) -> Result<Json<ContributionReceipt>> {
+    check_lock_expiry(coordinator, participant.clone()).await?;
+
     // Download contribution and its signature from S3 to local disk from the provided Urls
-    let s3_ctx = S3Ctx::new().await?;
-    let (contribution, contribution_sig) = s3_ctx.get_contribution(contribute_chunk_request.round_height).await?;
+    let object_store = ObjectStore::new().await?;
+    let (contribution, contribution_sig) = object_store
+        .get_contribution(contribute_chunk_request.round_height)
+        .await?;
     let mut write_lock = (*coordinator).clone().write_owned().await;
 
-    task::spawn_blocking(move || {
+    spawn_blocking(move || {
+        let contribution_file_signature = serde_json::from_slice(&contribution_sig)?;
+        let checks = write_lock
+            .check_contribution_signature(&participant, &contribution_file_signature)
+            .and_then(|_| write_lock.check_challenge_hash(&participant, 0, &contribution_file_signature))
+            .and_then(|_| {
+                write_lock.validate_contribution(
+                    &contribute_chunk_request.contribution_locator,
+                    &mut contribution.as_slice(),
+                    &contribution_file_signature,
+                )
+            });
+        write_lock.record_upload_checks(&participant, checks)?;
         write_lock.write_contribution(contribute_chunk_request.contribution_locator, contribution)?;
         write_lock.write_contribution_file_signature(
             contribute_chunk_request.contribution_signature_locator,
-            serde_json::from_slice(&contribution_sig)?,
+            contribution_file_signature,
+        )?;
+        let locator = write_lock.try_contribute(&participant, 0)?; // Only 1 chunk per round, chunk_id is always 0
+        write_lock.issue_receipt(&participant, &locator)
+    })
+    .await?
+    .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |r| Ok(Json(r)))
+}
+
+/// Upload a finished [Contribution](`crate::objects::Contribution`) directly to the [Coordinator](`crate::Coordinator`), as an alternative to the upload on S3.
+/// The contribution is streamed to disk as the raw body of the request while its signature is passed in a header. This will unlock the given [Chunk](`crate::objects::Chunk`).
+/// The contribution may be compressed with zstd or gzip, as given by the `Content-Encoding` header.
+/// Returns a [ContributionReceipt] signed by the coordinator, or fails if the lock of the contributor has expired.
+#[post(
+    "/contributor/upload_chunk",
+    format = "application/octet-stream",
+    data = "<contribution>"
+)]
+pub async fn upload_chunk(
+    coordinator: &State<Coordinator>,
+    participant: CurrentContributor,
+    contribution: ContributionUpload,
+) -> Result<Json<ContributionReceipt>> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || {
+        write_lock.check_lock_expiry(&participant)?;
+        let checks = write_lock
+            .check_contribution_signature(&participant, &contribution.signature)
+            .and_then(|_| write_lock.check_challenge_hash(&participant, 0, &contribution.signature));
+        write_lock.record_upload_checks(&participant, checks)?;
+
+        // Since we don't chunk the parameters, we have one chunk and one allowed contributor per round
+        let round_height = write_lock.current_round_height()?;
+        let decompressed = match contribution.encoding {
+            Some(encoding) => Some(compression::decompress_file(
+                contribution.file.path(),
+                encoding,
+                Object::anoma_contribution_file_size(write_lock.environment(), round_height, 1),
+            )?),
+            None => None,
+        };
+        let path = decompressed.as_ref().unwrap_or(&contribution.file).path();
+        let locator = ContributionLocator::new(round_height, 0, 1, false);
+        let checks =
+            write_lock.validate_contribution(&locator, &mut std::fs::File::open(path)?, &contribution.signature);
+        write_lock.record_upload_checks(&participant, checks)?;
+        write_lock.write_contribution_from_file(locator, path)?;
+        write_lock.write_contribution_file_signature(
+            ContributionSignatureLocator::new(round_height, 0, 1, false),
+            contribution.signature,
         )?;
-        write_lock.try_contribute(&participant, 0) // Only 1 chunk per round, chunk_id is always 0
+        let locator = write_lock.try_contribute(&participant, 0)?;
+        write_lock.issue_receipt(&participant, &locator)
+    })
+    .await?
+    .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |r| Ok(Json(r)))
+}
+
+/// Get the progress of the resumable upload of the contribution to the current round, to know from which byte to resume
+/// an interrupted upload.
+#[get("/contributor/upload_chunk/session")]
+pub async fn get_upload_session(
+    coordinator: &State<Coordinator>,
+    participant: CurrentContributor,
+) -> Result<Json<UploadSession>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    spawn_blocking(move || read_lock.upload_session(&participant))
+        .await?
+        .map_or_else(
+            |e| Err(ResponseError::CoordinatorError(e)),
+            |(path, total)| Ok(Json(rest_utils::upload_session(&path, total))),
+        )
+}
+
+/// Upload the next part of a finished [Contribution](`crate::objects::Contribution`) directly to the [Coordinator](`crate::Coordinator`).
+/// The part must start at the first byte not yet received, as given by its `Content-Range` header. Returns the updated progress of the upload.
+#[post(
+    "/contributor/upload_chunk/part",
+    format = "application/octet-stream",
+    data = "<part>"
+)]
+pub async fn upload_chunk_part(
+    coordinator: &State<Coordinator>,
+    participant: CurrentContributor,
+    part: ContributionPart,
+) -> Result<Json<UploadSession>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    // The part is copied once the coordinator is released
+    spawn_blocking(move || {
+        let (path, total) = read_lock
+            .upload_session(&participant)
+            .map_err(ResponseError::CoordinatorError)?;
+        drop(read_lock);
+        part.append_to(&path, total)
     })
     .await?
-    .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |_| Ok(()))
+    .map(Json)
+}
+
+/// Complete the resumable upload of a [Contribution](`crate::objects::Contribution`) with its signature passed in a header. The assembled contribution
+/// must match the response hash of the signature. This will unlock the given [Chunk](`crate::objects::Chunk`).
+/// Returns a [ContributionReceipt] signed by the coordinator, or fails if the lock of the contributor has expired.
+#[post("/contributor/upload_chunk/complete")]
+pub async fn complete_upload(
+    coordinator: &State<Coordinator>,
+    participant: CurrentContributor,
+    signature: ContributionSignatureHeader,
+) -> Result<Json<ContributionReceipt>> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || {
+        write_lock.check_lock_expiry(&participant)?;
+        let checks = write_lock
+            .verify_upload_session(&participant, &signature.0)
+            .and_then(|path| {
+                write_lock.check_contribution_signature(&participant, &signature.0)?;
+                write_lock.check_challenge_hash(&participant, 0, &signature.0)?;
+                Ok(path)
+            });
+        // An invalid upload is restarted from the first byte, while an incomplete one is resumed
+        let path = match write_lock.record_upload_checks(&participant, checks) {
+            Ok(path) => path,
+            Err(error @ CoordinatorError::ContributionUploadIncomplete) => return Err(error),
+            Err(error) => {
+                write_lock.remove_upload_session(&participant)?;
+                return Err(error);
+            }
+        };
+
+        // Since we don't chunk the parameters, we have one chunk and one allowed contributor per round
+        let round_height = write_lock.current_round_height()?;
+        write_lock.write_contribution_from_file(ContributionLocator::new(round_height, 0, 1, false), &path)?;
+        write_lock.write_contribution_file_signature(
+            ContributionSignatureLocator::new(round_height, 0, 1, false),
+            signature.0,
+        )?;
+        let locator = write_lock.try_contribute(&participant, 0)?;
+        std::fs::remove_file(&path)?;
+        write_lock.issue_receipt(&participant, &locator)
+    })
+    .await?
+    .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |r| Ok(Json(r)))
 }
 
 /// Update the [Coordinator](`crate::Coordinator`) state. This endpoint is accessible only by the coordinator itself.
@@ -155,32 +547,184 @@ pub async fn heartbeat(coordinator: &State<Coordinator>, participant: Participan
         .map_err(|e| ResponseError::CoordinatorError(e))
 }
 
-/// Stop the [Coordinator](`crate::Coordinator`) and shuts the rest server down. This endpoint is accessible only by the coordinator itself.
+/// Give up on the contribution, releasing the locks of the current contributor, which is put back at the end of the
+/// queue, instead of holding up the ceremony until it times out.
+#[post("/contributor/abandon")]
+pub async fn abandon_contribution(coordinator: &State<Coordinator>, participant: CurrentContributor) -> Result<()> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || write_lock.abandon_contribution(&participant))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// List the contributions of the participant rejected at their verification, with the reason of each rejection. A
+/// rejected contributor is banned from the ceremony, but can still tell why.
+#[get("/contributor/rejections")]
+pub async fn get_rejections(
+    coordinator: &State<Coordinator>,
+    participant: Participant,
+) -> Json<Vec<ContributionRejection>> {
+    Json(coordinator.read().await.rejections(&participant))
+}
+
+/// Drain the [Coordinator](`crate::Coordinator`) before shutting the rest server down: contributors can no longer join the queue nor lock a chunk, while the contribution in flight and its verification complete.
+/// The state is then saved by the shutdown, once the coordinator is drained. This endpoint is accessible only by the coordinator itself.
 #[get("/stop")]
-pub async fn stop_coordinator(_auth: ServerAuth, shutdown: Shutdown) {
-    // Shut Rocket server down
-    shutdown.notify();
+pub async fn stop_coordinator(coordinator: &State<Coordinator>, auth: ServerAuth) -> Result<()> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || -> std::result::Result<(), CoordinatorError> {
+        write_lock.start_draining();
+        write_lock.record_admin_action(&auth.0, String::from("stop the coordinator"))
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))
 }
 
-/// Verify all the pending contributions. This endpoint is accessible only by the coordinator itself.
+/// Verify all the pending contributions, waiting for the verification to complete. This endpoint is accessible only by the coordinator itself.
+/// Outside of debug builds the verification is queued on the background worker with [`request_verification`].
 #[cfg(debug_assertions)]
 #[get("/verify")]
 pub async fn verify_chunks(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<()> {
-    rest_utils::perform_verify_chunks((*coordinator).clone(), &S3Ctx::new().await?).await
+    rest_utils::perform_verify_chunks((*coordinator).clone(), &ObjectStore::new().await?).await
+}
+
+/// Ask the background worker to verify the pending contributions, without waiting for the verification. The progress can be polled with [`get_verification_status`].
+/// This endpoint is accessible only by the coordinator itself.
+#[post("/verify")]
+pub async fn request_verification(
+    verification_queue: &State<VerificationQueue>,
+    _auth: ServerAuth,
+) -> Result<Json<VerificationStatus>> {
+    Ok(Json(verification_queue.request().await?))
+}
+
+/// Get the progress of the background verification of the contributions. This endpoint is accessible only by the coordinator itself.
+#[get("/verify/status")]
+pub async fn get_verification_status(
+    verification_queue: &State<VerificationQueue>,
+    _auth: ServerAuth,
+) -> Json<VerificationStatus> {
+    Json(verification_queue.status().await)
+}
+
+/// Get the progress of the replication of the storage, with its lag. This endpoint is accessible only by the coordinator itself.
+#[get("/replication/status")]
+pub async fn get_replication_status(replica: &State<Replica>, _auth: ServerAuth) -> Json<ReplicationStatus> {
+    Json(replica.status().await)
+}
+
+/// Lock the next contribution to verify as an external verifier. The lock is kept until the verifier sends its result.
+#[get("/verifier/lock_task")]
+pub async fn lock_verification_task(
+    coordinator: &State<Coordinator>,
+    verifier: ExternalVerifier,
+) -> Result<Json<Option<VerificationTask>>> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || write_lock.lock_verification_task(&verifier))
+        .await?
+        .map(Json)
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Streams the challenge, or the response, of the contribution locked by the external verifier, compressed with
+/// `encoding` if any.
+async fn verification_file(
+    coordinator: &State<Coordinator>,
+    verifier: ExternalVerifier,
+    response: bool,
+    encoding: Option<compression::ContentEncoding>,
+) -> Result<ChallengeStream> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let (file, hash) = spawn_blocking(move || -> std::result::Result<_, CoordinatorError> {
+        let (path, hash) = read_lock.verification_file(&verifier, response)?;
+        Ok((read_lock.serve_file(&path, encoding)?, hash))
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(ChallengeStream {
+        file,
+        hash,
+        signature: None,
+        encoding,
+    })
+}
+
+/// Download the challenge of the contribution locked by the external verifier, carrying its hash in a header.
+/// If the client accepts it, the challenge is compressed with zstd or gzip.
+#[get("/verifier/challenge")]
+pub async fn get_verification_challenge(
+    coordinator: &State<Coordinator>,
+    verifier: ExternalVerifier,
+    accept_encoding: AcceptEncoding,
+) -> Result<ChallengeStream> {
+    verification_file(coordinator, verifier, false, accept_encoding.0).await
+}
+
+/// Download the response of the contribution locked by the external verifier, carrying its hash in a header.
+/// If the client accepts it, the response is compressed with zstd or gzip.
+#[get("/verifier/response")]
+pub async fn get_verification_response(
+    coordinator: &State<Coordinator>,
+    verifier: ExternalVerifier,
+    accept_encoding: AcceptEncoding,
+) -> Result<ChallengeStream> {
+    verification_file(coordinator, verifier, true, accept_encoding.0).await
+}
+
+/// Record the signed result of the external verifier on the contribution it locked. The coordinator verifies the
+/// contribution itself once the quorum of external verifiers approved it.
+#[post("/verifier/verification_result", format = "json", data = "<result>")]
+pub async fn post_verification_result(
+    coordinator: &State<Coordinator>,
+    verifier: ExternalVerifier,
+    result: LazyJson<VerificationResult>,
+) -> Result<()> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || write_lock.add_verification_result(&verifier, &result))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Apply the public random `beacon` (e.g. a future block hash, hex encoded on 32 bytes) as the final contribution of the ceremony. The beacon is recorded in the transcript.
+/// This endpoint is accessible only by the coordinator itself.
+#[post("/beacon", format = "json", data = "<beacon>")]
+pub async fn apply_beacon(
+    coordinator: &State<Coordinator>,
+    auth: ServerAuth,
+    beacon: LazyJson<String>,
+) -> Result<Json<BeaconContribution>> {
+    let beacon: [u8; 32] = hex::decode(beacon.trim_start_matches("0x"))
+        .map_err(|e| ResponseError::InvalidBeacon(e.to_string()))?
+        .try_into()
+        .map_err(|_| ResponseError::InvalidBeacon("expected 32 bytes".to_string()))?;
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || -> std::result::Result<BeaconContribution, CoordinatorError> {
+        let contribution = write_lock.apply_beacon(beacon)?;
+        write_lock.record_admin_action(&auth.0, format!("apply the beacon {}", hex::encode(beacon)))?;
+        Ok(contribution)
+    })
+    .await?
+    .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |b| Ok(Json(b)))
 }
 
 /// Load new tokens to update the future cohorts. The `tokens` parameter is the serialized zip folder
 #[post("/update_cohorts", format = "json", data = "<tokens>")]
 pub async fn update_cohorts(
     coordinator: &State<Coordinator>,
-    _auth: ServerAuth,
+    auth: ServerAuth,
     tokens: LazyJson<Vec<u8>>,
 ) -> Result<()> {
     let reader = Cursor::new(tokens.clone());
     let mut zip = zip::ZipArchive::new(reader).map_err(|e| ResponseError::IoError(e.to_string()))?;
     let mut zip_clone = zip.clone();
 
-    let new_tokens = task::spawn_blocking(move || -> Result<Vec<HashSet<String>>> {
+    let new_tokens = spawn_blocking(move || -> Result<Vec<HashSet<String>>> {
         let mut cohorts: HashMap<String, Vec<u8>> = HashMap::new();
         let file_names: Vec<String> = zip_clone.file_names().map(|name| name.to_owned()).collect();
 
@@ -220,7 +764,7 @@ pub async fn update_cohorts(
 
     // Persist new tokens to disk
     // New tokens MUST be written to file in case of a coordinator restart
-    task::spawn_blocking(move || -> Result<()> {
+    spawn_blocking(move || -> Result<()> {
         let mut zip_file = std::fs::File::options()
             .read(true)
             .write(true)
@@ -246,52 +790,412 @@ pub async fn update_cohorts(
     .unwrap()?;
 
     // Update cohorts in coordinator's state
-    coordinator.write().await.update_tokens(new_tokens);
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || -> std::result::Result<(), CoordinatorError> {
+        write_lock.update_tokens(new_tokens);
+        write_lock.record_admin_action(&auth.0, String::from("update the cohorts"))
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Mint new single-use invite codes, to be distributed to the contributors of the invite-only phases of the ceremony.
+/// At most [`MAX_INVITE_CODES_PER_REQUEST`] codes are minted, valid for at most [`MAX_INVITE_CODE_VALIDITY`] seconds.
+#[post("/invite_codes", format = "json", data = "<request>")]
+pub async fn post_invite_codes(
+    coordinator: &State<Coordinator>,
+    auth: ServerAuth,
+    request: LazyJson<MintInviteCodesRequest>,
+) -> Result<Json<Vec<InviteCode>>> {
+    let MintInviteCodesRequest {
+        count,
+        notes,
+        expires_in,
+    } = request.0;
+    let count = count.min(MAX_INVITE_CODES_PER_REQUEST);
+    let expires_in = expires_in.map(|s| time::Duration::seconds(s.min(MAX_INVITE_CODE_VALIDITY) as i64));
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    let invite_codes = spawn_blocking(move || -> std::result::Result<Vec<InviteCode>, CoordinatorError> {
+        let invite_codes = write_lock.mint_invite_codes(count, notes, expires_in)?;
+        write_lock.record_admin_action(&auth.0, format!("mint {} invite codes", count))?;
+        Ok(invite_codes)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(invite_codes))
+}
+
+/// Get the minted invite codes together with their redemption status.
+#[get("/invite_codes")]
+pub async fn get_invite_codes(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Json<Vec<InviteCode>> {
+    let read_lock = coordinator.read().await;
+
+    Json(read_lock.state().invite_codes().into_iter().cloned().collect())
+}
+
+/// Issue new single-use priority tokens, to be distributed to the partner organizations whose contributors are granted
+/// a reserved slot in the queue during the validity window of the tokens. Returns the encoded tokens, to be sent in the
+/// [`PRIORITY_TOKEN_HEADER`](`crate::rest_utils::PRIORITY_TOKEN_HEADER`) of [`join_queue`].
+#[post("/priority_tokens", format = "json", data = "<request>")]
+pub async fn post_priority_tokens(
+    coordinator: &State<Coordinator>,
+    auth: ServerAuth,
+    request: LazyJson<IssuePriorityTokensRequest>,
+) -> Result<Json<Vec<String>>> {
+    let IssuePriorityTokensRequest {
+        count,
+        reliability,
+        not_before,
+        expires_at,
+    } = request.0;
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    let tokens = spawn_blocking(move || -> std::result::Result<Vec<PriorityToken>, CoordinatorError> {
+        let tokens = write_lock.issue_priority_tokens(count, reliability, not_before, expires_at)?;
+        write_lock.record_admin_action(&auth.0, format!("issue {} priority tokens", count))?;
+        Ok(tokens)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(tokens.iter().map(|token| token.encode()).collect()))
+}
+
+/// Rotate the signing key of the coordinator to a newly generated one, returning the hand-off statement signed by the
+/// old key. The new keypair replaces the one in `NAMADA_MPC_KEYPAIR_FILE`, encrypted with the passphrase in
+/// `NAMADA_MPC_KEYPAIR_PASSPHRASE`, to be loaded at the next restart. Fails with 409 if the key can't be persisted
+/// that way, either because the file is not configured or because the key is held by an external signing backend,
+/// which would be used again at the next restart.
+#[post("/rotate_key")]
+pub async fn rotate_key(coordinator: &State<Coordinator>, auth: ServerAuth) -> Result<Json<KeyRotation>> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    let backend = write_lock.environment().signing_backend();
+    if *backend != SigningBackend::Local {
+        return Err(ResponseError::KeyNotPersistable(format!(
+            "the key is held by {:?}",
+            backend
+        )));
+    }
+    let (keypair_file, passphrase) = match (
+        std::env::var("NAMADA_MPC_KEYPAIR_FILE"),
+        std::env::var("NAMADA_MPC_KEYPAIR_PASSPHRASE"),
+    ) {
+        (Ok(keypair_file), Ok(passphrase)) => (keypair_file, passphrase),
+        _ => {
+            return Err(ResponseError::KeyNotPersistable(String::from(
+                "NAMADA_MPC_KEYPAIR_FILE and NAMADA_MPC_KEYPAIR_PASSPHRASE must be set",
+            )))
+        }
+    };
+
+    let rotation = spawn_blocking(move || -> std::result::Result<KeyRotation, CoordinatorError> {
+        // The key is persisted first, so that a rotation is never published without the key to sign after it
+        let keypair = KeyPair::new();
+        keypair.save_encrypted(keypair_file, &passphrase)?;
+
+        let rotation = write_lock.rotate_signing_key(&keypair)?;
+        write_lock.record_admin_action(&auth.0, format!("rotate the key to {}", rotation.new_public_key))?;
+
+        Ok(rotation)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(rotation))
+}
+
+/// Ban the given contributor and/or IP from the ceremony. Banned contributors are dropped from the queue and the
+/// current round, banned IPs can't be used to join the queue anymore. The ban list is persisted with the coordinator state.
+#[post("/ban", format = "json", data = "<request>")]
+pub async fn ban(coordinator: &State<Coordinator>, auth: ServerAuth, request: LazyJson<BanRequest>) -> Result<()> {
+    let BanRequest { pubkey, ip } = request.0;
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || -> std::result::Result<(), CoordinatorError> {
+        if let Some(pubkey) = pubkey {
+            write_lock.ban_participant(&Participant::new_contributor(&pubkey))?;
+        }
+        if let Some(ip) = ip {
+            write_lock.ban_ip(ip)?;
+        }
 
-    Ok(())
+        write_lock.record_admin_action(&auth.0, String::from("ban"))
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))
 }
 
-/// Get the queue status of the contributor.
+/// Remove the given contributor and/or IP from the ban list.
+#[post("/unban", format = "json", data = "<request>")]
+pub async fn unban(coordinator: &State<Coordinator>, auth: ServerAuth, request: LazyJson<BanRequest>) -> Result<()> {
+    let BanRequest { pubkey, ip } = request.0;
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || -> std::result::Result<(), CoordinatorError> {
+        if let Some(pubkey) = pubkey {
+            write_lock.unban_participant(&Participant::new_contributor(&pubkey))?;
+        }
+        if let Some(ip) = ip {
+            write_lock.unban_ip(&ip)?;
+        }
+
+        write_lock.record_admin_action(&auth.0, String::from("unban"))
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Set the reliability score of the given contributor, moving it in the queue right away if it's waiting there. The
+/// score is kept for its next joins. Returns the previous score of the contributor.
+#[post("/admin/participant/priority", format = "json", data = "<request>")]
+pub async fn post_participant_priority(
+    coordinator: &State<Coordinator>,
+    auth: ServerAuth,
+    request: LazyJson<ParticipantPriorityRequest>,
+) -> Result<Json<u8>> {
+    let ParticipantPriorityRequest { pubkey, reliability } = request.0;
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    let participant = Participant::new_contributor(&pubkey);
+    let operator = auth.0.clone();
+    let previous = spawn_blocking(move || -> std::result::Result<u8, CoordinatorError> {
+        let previous = write_lock.set_reliability_score(&participant, reliability)?;
+        let action = format!("set the reliability score of {} to {}", participant, reliability);
+        write_lock.record_admin_action(&operator, action)?;
+        Ok(previous)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+    info!(
+        "Operator {} set the reliability score of {} from {} to {}",
+        auth.0, pubkey, previous, reliability
+    );
+
+    Ok(Json(previous))
+}
+
+/// Skip the stalled contributors of the current round: their locks are released and the round starts over with the
+/// next contributors of the queue. Returns the public keys of the skipped contributors.
+#[post("/admin/skip_current", format = "json", data = "<request>")]
+pub async fn post_skip_current(
+    coordinator: &State<Coordinator>,
+    auth: ServerAuth,
+    request: LazyJson<SkipCurrentRequest>,
+) -> Result<Json<Vec<String>>> {
+    let SkipCurrentRequest { ban, reliability } = request.0;
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    let operator = auth.0.clone();
+    let skipped = spawn_blocking(move || -> std::result::Result<Vec<Participant>, CoordinatorError> {
+        let skipped = write_lock.skip_current_contributors(ban, reliability)?;
+        let pubkeys: Vec<String> = skipped.iter().map(|participant| participant.address()).collect();
+        write_lock.record_admin_action(&operator, format!("skip the current contributors {:?}", pubkeys))?;
+        Ok(skipped)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+    let pubkeys: Vec<String> = skipped.iter().map(|participant| participant.address()).collect();
+    info!(
+        "Operator {} skipped the current contributors {:?} (ban: {}, reliability: {:?})",
+        auth.0, pubkeys, ban, reliability
+    );
+
+    Ok(Json(pubkeys))
+}
+
+/// Get the banned contributors and IPs.
+#[get("/ban")]
+pub async fn get_ban_list(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Json<BanList> {
+    let read_lock = coordinator.read().await;
+    let state = read_lock.state();
+
+    Json(BanList {
+        pubkeys: state.banned_participants().into_iter().map(|p| p.address()).collect(),
+        ips: state.banned_ips().into_iter().cloned().collect(),
+    })
+}
+
+/// Download a snapshot of the coordinator state and the round files, as a gzipped tarball ending with a manifest of
+/// the hashes of its files.
+#[post("/admin/snapshot")]
+pub async fn post_snapshot(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<(ContentType, fs::File)> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    // Build the snapshot in a temporary file once the coordinator is released, the file being then streamed to the
+    // client
+    let snapshot = spawn_blocking(move || -> std::result::Result<_, CoordinatorError> {
+        let pending = read_lock.snapshot()?;
+        drop(read_lock);
+        let mut file = tempfile::tempfile()?;
+        pending.run(&mut file)?;
+        file.rewind()?;
+        Ok(file)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok((ContentType::GZIP, fs::File::from_std(snapshot)))
+}
+
+/// Replace the ceremony with a snapshot from `/admin/snapshot`, sent as the raw body of the request. Nothing is
+/// replaced unless all the files of the snapshot match its manifest. The round is then recovered as on startup.
+#[post("/admin/restore", format = "application/octet-stream", data = "<snapshot>")]
+pub async fn post_restore(
+    coordinator: &State<Coordinator>,
+    transcript_cache: &State<TranscriptCache>,
+    auth: ServerAuth,
+    snapshot: SnapshotUpload,
+) -> Result<Json<SnapshotManifest>> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+    let transcript_cache = (*transcript_cache).clone();
+
+    spawn_blocking(move || -> std::result::Result<SnapshotManifest, CoordinatorError> {
+        let manifest = write_lock.restore(std::io::BufReader::new(snapshot.file.reopen()?))?;
+        // The completed rounds of the snapshot replace the exported ones
+        transcript_cache.rounds().clear();
+        let action = format!("restore the snapshot of round {}", manifest.round_height);
+        write_lock.record_admin_action(&auth.0, action)?;
+        Ok(manifest)
+    })
+    .await?
+    .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |m| Ok(Json(m)))
+}
+
+/// Check the hashes and the signatures of the stored files of the completed rounds.
+#[get("/admin/storage_audit")]
+pub async fn get_storage_audit(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<Json<StorageAudit>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    // The files are hashed once the coordinator is released
+    spawn_blocking(move || {
+        let pending = read_lock.audit_storage()?;
+        drop(read_lock);
+        pending.run()
+    })
+    .await?
+    .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |a| Ok(Json(a)))
+}
+
+/// Export the records of the audit log logged in the given time range, if any, in unix timestamps in seconds with `to`
+/// excluded. The records keep their hashes and signatures, for the export to be checked by third parties.
+#[get("/admin/audit/export?<format>&<from>&<to>")]
+pub async fn get_audit_export(
+    coordinator: &State<Coordinator>,
+    _auth: ServerAuth,
+    format: AuditExportFormat,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<(ContentType, Vec<u8>)> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    let export = spawn_blocking(move || read_lock.export_audit_log(format, from, to))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+    let content_type = match format {
+        AuditExportFormat::Csv => ContentType::CSV,
+        AuditExportFormat::Json => ContentType::JSON,
+    };
+
+    Ok((content_type, export))
+}
+
+/// Get the public summary of the progress of the ceremony.
+#[get("/ceremony/status")]
+pub async fn get_ceremony_status(coordinator: &State<Coordinator>) -> Result<Json<CeremonyStatus>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    let status = spawn_blocking(move || -> std::result::Result<CeremonyStatus, CoordinatorError> {
+        Ok(CeremonyStatus {
+            round_height: read_lock.state().current_round_height(),
+            number_of_chunks: read_lock.environment().number_of_chunks(),
+            queue_length: read_lock.number_of_queue_contributors() as u64,
+            current_contributor: read_lock
+                .current_contributors()
+                .first()
+                .map(|(participant, _)| participant.address()),
+            verified_contributions: read_lock.get_verified_contributions()?.len() as u64,
+        })
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(status))
+}
+
+/// Get the public key of the [Coordinator](`crate::Coordinator`) and the parameters of the ceremony, signed by that key. Clients check the key against the one
+/// published by the organizers before trusting the challenges and receipts of the coordinator.
+#[get("/coordinator/identity")]
+pub async fn get_coordinator_identity(coordinator: &State<Coordinator>) -> Result<Json<CoordinatorIdentity>> {
+    if let Some(identity) = coordinator.read().await.identity() {
+        return Ok(Json(identity.clone()));
+    }
+
+    // Signed on the first request, off the runtime as the signing backend may block
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+    let identity = spawn_blocking(move || write_lock.signed_identity())
+        .await?
+        .map_err(ResponseError::CoordinatorError)?;
+
+    Ok(Json(identity))
+}
+
+/// Get the aggregates of the ceremony displayed by the dashboard.
+#[get("/stats")]
+pub async fn get_stats(coordinator: &State<Coordinator>, geolocator: &State<Geolocator>) -> Json<CeremonyStats> {
+    let read_lock = coordinator.read().await;
+    let mut stats = read_lock.state().ceremony_stats();
+    stats.unique_countries = geolocator.unique_countries(read_lock.state().contribution_ips());
+
+    Json(stats)
+}
+
+/// Get the queue status of the contributor, with the estimated wait in seconds before its turn in the
+/// [`rest_utils::ESTIMATED_WAIT_HEADER`] while in the queue, once it can be told.
 #[get("/contributor/queue_status", format = "json")]
 pub async fn get_contributor_queue_status(
     coordinator: &State<Coordinator>,
     participant: Participant,
-) -> Json<ContributorStatus> {
+) -> EstimatedWait<Json<ContributorStatus>> {
     let contributor = participant.clone();
 
     let read_lock = (*coordinator).clone().read_owned().await;
     // Check that the contributor is authorized to lock a chunk in the current round.
-    if task::spawn_blocking(move || read_lock.is_current_contributor(&contributor))
+    if spawn_blocking(move || read_lock.is_current_contributor(&contributor))
         .await
         .unwrap()
     {
-        return Json(ContributorStatus::Round);
+        return EstimatedWait(Json(ContributorStatus::Round), None);
     }
 
     let read_lock = coordinator.read().await;
 
     if read_lock.is_queue_contributor(&participant) {
-        let queue_size = read_lock.number_of_queue_contributors() as u64;
-
-        let queue_position = match read_lock.state().queue_contributor_info(&participant) {
-            Some((_, Some(round), _, _)) => round - read_lock.state().current_round_height(),
-            Some((_, None, _, _)) => queue_size,
-            None => return Json(ContributorStatus::Other),
+        let (queue_position, queue_size) = match read_lock.queue_position(&participant) {
+            Some(position) => position,
+            None => return EstimatedWait(Json(ContributorStatus::Other), None),
         };
+        let estimated_wait_seconds = read_lock.estimated_wait_seconds(queue_position);
 
-        return Json(ContributorStatus::Queue(queue_position, queue_size));
+        return EstimatedWait(
+            Json(ContributorStatus::Queue(queue_position, queue_size)),
+            estimated_wait_seconds,
+        );
     }
 
     if read_lock.is_finished_contributor(&participant) {
-        return Json(ContributorStatus::Finished);
+        return EstimatedWait(Json(ContributorStatus::Finished), None);
     }
 
     if read_lock.is_banned_participant(&participant) {
-        return Json(ContributorStatus::Banned);
+        return EstimatedWait(Json(ContributorStatus::Banned), None);
     }
 
     // Not in the queue, not finished, nor in the current round
-    Json(ContributorStatus::Other)
+    EstimatedWait(Json(ContributorStatus::Other), None)
 }
 
 /// Write [`ContributionInfo`] to disk
@@ -310,6 +1214,15 @@ pub async fn post_contribution_info(
         )));
     }
 
+    if let Some(binding) = &request.namada_address {
+        if !binding.verify(&request.public_key) {
+            return Err(ResponseError::InvalidContributionInfo(format!(
+                "Binding to Namada address {} is not valid",
+                binding.address
+            )));
+        }
+    }
+
     let current_round_height = match coordinator.read().await.current_round_height() {
         Ok(r) => r,
         Err(e) => return Err(ResponseError::CoordinatorError(e)),
@@ -326,7 +1239,7 @@ pub async fn post_contribution_info(
     // Write contribution info and summary to file
     let mut write_lock = (*coordinator).clone().write_owned().await;
 
-    task::spawn_blocking(move || {
+    spawn_blocking(move || {
         write_lock.write_contribution_info(request.clone())?;
 
         write_lock.update_contribution_summary(request.0.into())
@@ -335,27 +1248,21 @@ pub async fn post_contribution_info(
     .map_err(|e| ResponseError::CoordinatorError(e))
 }
 
-/// Uploads the attestation for a contribution
-#[post("/contributor/attestation", format = "json", data = "<request>")]
-pub async fn post_attestation(
+/// Checks that the participant is a finished contributor and owns the contribution of the given round
+async fn check_contribution_owner(
     coordinator: &State<Coordinator>,
     participant: Participant,
-    request: LazyJson<(u64, String)>,
+    round: u64,
+    endpoint: &'static str,
 ) -> Result<()> {
-    let (round, attestation) = request.0;
-
-    // Check url format
-    if let Err(e) = Url::parse(attestation.as_str()) {
-        return Err(ResponseError::IoError(e.to_string()));
-    }
-
     let read_lock = (*coordinator).clone().read_owned().await;
-    task::spawn_blocking(move || {
+    spawn_blocking(move || {
         if !read_lock.is_finished_contributor_at_round(&participant, round) {
             // Only finished contributors are allowed to query this endpoint
             return Err(ResponseError::UnauthorizedParticipant(
+                ErrorCode::Unauthorized,
                 participant,
-                "/contributor/attestation".to_string(),
+                endpoint.to_string(),
                 "Not a current nor finished contributor".to_string(),
             ));
         }
@@ -378,22 +1285,246 @@ pub async fn post_attestation(
             _ => Err(ResponseError::CoordinatorError(crate::CoordinatorError::StorageFailed)),
         }
     })
-    .await??;
+    .await?
+}
+
+/// Uploads the attestation for a contribution, either the url of a public post or a free-text statement
+/// signed with the key of the contributor (with an optional url). Attestations are published in the summary
+/// of the contributions.
+#[post("/contributor/attestation", format = "json", data = "<request>")]
+pub async fn post_attestation(
+    coordinator: &State<Coordinator>,
+    participant: Participant,
+    request: LazyJson<(u64, AttestationRequest)>,
+) -> Result<()> {
+    let (round, attestation) = request.0;
+
+    // Check url format
+    let url = match &attestation {
+        AttestationRequest::Url(url) => Some(url),
+        AttestationRequest::Statement(statement) => statement.url.as_ref(),
+    };
+    if let Some(Err(e)) = url.map(|url| Url::parse(url.as_str())) {
+        return Err(ResponseError::IoError(e.to_string()));
+    }
+
+    if let AttestationRequest::Statement(ref statement) = attestation {
+        if !statement.verify(&participant.address(), round) {
+            return Err(ResponseError::InvalidAttestation(String::from(
+                "statement is empty, too long or not signed by the contributor",
+            )));
+        }
+    }
+
+    check_contribution_owner(coordinator, participant, round, "/contributor/attestation").await?;
 
     // Update the contribution info and the summary with the attestation
     let mut write_lock = (*coordinator).clone().write_owned().await;
 
-    task::spawn_blocking(move || write_lock.update_contribution_info_attestation(round, attestation))
+    spawn_blocking(move || match attestation {
+        AttestationRequest::Url(url) => write_lock.update_contribution_info_attestation(round, url),
+        AttestationRequest::Statement(statement) => {
+            write_lock.update_contribution_info_attestation_statement(round, statement)
+        }
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Binds a Namada address to a contribution. The binding must be signed with the key of the contributor.
+#[post("/contributor/namada_address", format = "json", data = "<request>")]
+pub async fn post_namada_address(
+    coordinator: &State<Coordinator>,
+    participant: Participant,
+    request: LazyJson<(u64, NamadaAddressBinding)>,
+) -> Result<()> {
+    let (round, binding) = request.0;
+
+    if !binding.verify(&participant.address()) {
+        return Err(ResponseError::InvalidNamadaAddress(binding.address));
+    }
+
+    check_contribution_owner(coordinator, participant, round, "/contributor/namada_address").await?;
+
+    // Update the contribution info and the summary with the binding
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || write_lock.update_contribution_info_namada_address(round, binding))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Links the contribution to a Keybase or OpenPGP identity. The public key of the identity is fetched
+/// from the provider to check the signature of the statement, only verified proofs are recorded.
+#[post("/contributor/identity_proof", format = "json", data = "<request>")]
+pub async fn post_identity_proof(
+    coordinator: &State<Coordinator>,
+    participant: Participant,
+    request: LazyJson<(u64, IdentityProof)>,
+) -> Result<()> {
+    let (round, identity_proof) = request.0;
+    let public_key = participant.address();
+
+    check_contribution_owner(coordinator, participant, round, "/contributor/identity_proof").await?;
+
+    // Fetch the identity key without holding the lock on the coordinator
+    let key_url = identity_proof
+        .key_url()
+        .map_err(|e| ResponseError::InvalidIdentityProof(e.to_string()))?;
+    let identity_key = fetch_public(&key_url, IDENTITY_KEY_SIZE_LIMIT)
+        .await
+        .map_err(|e| ResponseError::InvalidIdentityProof(e.to_string()))?;
+    identity_proof
+        .verify(&identity_key, &public_key)
+        .map_err(|e| ResponseError::InvalidIdentityProof(e.to_string()))?;
+
+    // Update the contribution info and the summary with the proof
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+
+    spawn_blocking(move || write_lock.update_contribution_info_identity_proof(round, identity_proof))
         .await?
         .map_err(|e| ResponseError::CoordinatorError(e))
 }
 
+/// Retrieve the canonical message that a contributor should post publicly to attest its contribution. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/contributor/attestation_message/<pubkey>", format = "json")]
+pub async fn get_attestation_message(coordinator: &State<Coordinator>, pubkey: String) -> Result<Json<String>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    spawn_blocking(move || match read_lock.get_contribution_info(&pubkey) {
+        Ok(Some(info)) => Ok(Json(info.attestation_message())),
+        Ok(None) => Err(ResponseError::UnknownContributor(pubkey)),
+        Err(e) => Err(ResponseError::CoordinatorError(e)),
+    })
+    .await?
+}
+
+/// Public transcript of the ceremony, listing every contribution with its signed challenge and response hashes. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/transcript")]
+pub async fn get_transcript(
+    coordinator: &State<Coordinator>,
+    transcript_cache: &State<TranscriptCache>,
+) -> Result<Json<Transcript>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let transcript_cache = (*transcript_cache).clone();
+
+    // Only the current round is read from the storage, the completed ones come from the cache
+    spawn_blocking(move || read_lock.export_transcript_cached(&mut transcript_cache.rounds()))
+        .await?
+        .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |t| Ok(Json(t)))
+}
+
+/// Detail of a round: its participants, the state of its chunks and the signed hashes of its contributions together with
+/// their verification status. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/round/<round_height>")]
+pub async fn get_round_info(coordinator: &State<Coordinator>, round_height: u64) -> Result<Json<RoundInfo>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+
+    spawn_blocking(move || read_lock.round_info(round_height))
+        .await?
+        .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |r| Ok(Json(r)))
+}
+
+/// Atom feed of the verified contributions. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/feed.atom")]
+pub async fn get_feed(coordinator: &State<Coordinator>) -> Result<(ContentType, String)> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let contributions = spawn_blocking(move || read_lock.get_verified_contributions())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok((
+        ContentType::new("application", "atom+xml"),
+        rest_utils::atom_feed(&contributions),
+    ))
+}
+
+/// Download a zip bundle with the artifacts of the contribution of the given contributor, so that they can archive the
+/// proof of their contribution. This endpoint is accessible by anyone and does not require a signed request.
+///
+/// Ranked after the other routes under `/contributor`, as `/contributor/challenge/<round_height>`, which it would
+/// collide with.
+#[get("/contributor/<pubkey>/bundle.zip", rank = 2)]
+pub async fn get_contributor_bundle(coordinator: &State<Coordinator>, pubkey: String) -> Result<(ContentType, fs::File)> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let public_key = pubkey.clone();
+
+    // Build the archive in a temporary file, which is then streamed to the client
+    let bundle = spawn_blocking(move || -> std::result::Result<_, CoordinatorError> {
+        match read_lock.write_contributor_bundle(&public_key, tempfile::tempfile()?)? {
+            Some(mut file) => {
+                file.rewind()?;
+                Ok(Some(file))
+            }
+            None => Ok(None),
+        }
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    match bundle {
+        Some(file) => Ok((ContentType::ZIP, fs::File::from_std(file))),
+        None => Err(ResponseError::UnknownContributor(pubkey)),
+    }
+}
+
+/// List every contribution made with the given public key, together with its hashes and a receipt signed by the
+/// coordinator, so that the contributor can confirm that it's been accepted. This endpoint is accessible by anyone and
+/// does not require a signed request.
+#[get("/contributor/<pubkey>/history", rank = 2)]
+pub async fn get_contribution_history(
+    coordinator: &State<Coordinator>,
+    pubkey: String,
+) -> Result<Json<Vec<ContributionRecord>>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let public_key = pubkey.clone();
+
+    let history = spawn_blocking(move || read_lock.contribution_history(&public_key))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    match history.is_empty() {
+        true => Err(ResponseError::UnknownContributor(pubkey)),
+        false => Ok(Json(history)),
+    }
+}
+
+/// Tell whether the given public key contributed to the ceremony, without the detail of its contributions. This
+/// endpoint is accessible by anyone and does not require a signed request.
+#[get("/contributor/<pubkey>/contributed", rank = 2)]
+pub async fn get_has_contributed(coordinator: &State<Coordinator>, pubkey: String) -> Json<bool> {
+    let participant = Participant::new_contributor(&pubkey);
+
+    Json(coordinator.read().await.has_contributed(&participant))
+}
+
+/// List the contributors of the ceremony, ordered by round height, with the names they chose and the hashes of their
+/// contributions. Pages are numbered from 1 and hold [`DEFAULT_CONTRIBUTORS_PER_PAGE`] contributors unless told
+/// otherwise, up to [`MAX_CONTRIBUTORS_PER_PAGE`]. This endpoint is accessible by anyone and does not require a signed
+/// request.
+#[get("/contributors?<page>&<per_page>")]
+pub async fn get_contributors(
+    coordinator: &State<Coordinator>,
+    page: Option<u64>,
+    per_page: Option<u64>,
+) -> Result<Json<ContributorsPage>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let page = page.unwrap_or(1).max(1);
+    let per_page = per_page
+        .unwrap_or(DEFAULT_CONTRIBUTORS_PER_PAGE)
+        .clamp(1, MAX_CONTRIBUTORS_PER_PAGE);
+
+    spawn_blocking(move || read_lock.contributors_page(page, per_page))
+        .await?
+        .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |p| Ok(Json(p)))
+}
+
 /// Retrieve the contributions' info. This endpoint is accessible by anyone and does not require a signed request.
 #[cfg(debug_assertions)]
 #[get("/contribution_info")]
 pub async fn get_contributions_info(coordinator: &State<Coordinator>) -> Result<Vec<u8>> {
     let read_lock = (*coordinator).clone().read_owned().await;
-    let summary = task::spawn_blocking(move || read_lock.storage().get_contributions_summary())
+    let summary = spawn_blocking(move || read_lock.storage().get_contributions_summary())
         .await?
         .map_err(|e| ResponseError::CoordinatorError(e))?;
 
@@ -404,7 +1535,7 @@ pub async fn get_contributions_info(coordinator: &State<Coordinator>) -> Result<
 #[get("/coordinator_status")]
 pub async fn get_coordinator_state(coordinator: &State<Coordinator>, _auth: Secret) -> Result<Vec<u8>> {
     let read_lock = (*coordinator).clone().read_owned().await;
-    let state = task::spawn_blocking(move || read_lock.storage().get_coordinator_state())
+    let state = spawn_blocking(move || read_lock.storage().get_coordinator_state())
         .await?
         .map_err(|e| ResponseError::CoordinatorError(e))?;
 
@@ -420,3 +1551,87 @@ pub async fn get_healthcheck() -> Result<String> {
 
     Ok(content)
 }
+
+/// Liveness probe, answering as long as the process serves requests. This endpoint is accessible by anyone and does
+/// not require a signed request.
+#[get("/healthz")]
+pub async fn get_liveness() -> Status {
+    Status::Ok
+}
+
+/// Readiness probe, failing with a `503 Service Unavailable` while the storage can't be reached, the current round
+/// can't be loaded or the verification worker is not running. This endpoint is accessible by anyone and does not
+/// require a signed request.
+#[get("/readyz")]
+pub async fn get_readiness(
+    coordinator: &State<Coordinator>,
+    verification_queue: &State<VerificationQueue>,
+) -> Result<(Status, Json<Readiness>)> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let verification_worker_alive = verification_queue.worker_alive();
+
+    let readiness = spawn_blocking(move || Readiness {
+        storage_reachable: read_lock.storage_reachable(),
+        round_loaded: read_lock.current_round().is_ok(),
+        verification_worker_alive,
+        draining: read_lock.is_draining(),
+    })
+    .await?;
+    let status = match readiness.is_ready() {
+        true => Status::Ok,
+        false => Status::ServiceUnavailable,
+    };
+
+    Ok((status, Json(readiness)))
+}
+
+/// Stream the [events](`crate::rest_utils::CeremonyEvent`) of the ceremony as server-sent events, named after the
+/// type of the event. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/events")]
+pub async fn get_events(coordinator: &State<Coordinator>, mut shutdown: Shutdown) -> EventStream![] {
+    let mut events = coordinator.read().await.subscribe();
+
+    EventStream! {
+        loop {
+            let event = select! {
+                event = events.recv() => match event {
+                    Ok(event) => event,
+                    Err(RecvError::Closed) => break,
+                    // Slow clients miss the events dropped from the buffer but keep receiving the next ones
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Events stream lagging, skipped {} events", skipped);
+                        continue;
+                    }
+                },
+                _ = &mut shutdown => break,
+            };
+
+            yield Event::json(&event).event(event.name());
+        }
+    }
+}
+
+/// Retrieve the OpenAPI specification of this API. This endpoint is accessible by anyone and does not require a signed
+/// request.
+#[get("/openapi.json")]
+pub fn get_openapi() -> Json<serde_json::Value> {
+    Json(openapi::specification())
+}
+
+/// Reject a request rerouted by the [`RateLimit`](`crate::rest_utils::RateLimit`) fairing.
+#[get("/rate_limited")]
+pub fn rate_limited(limited: RateLimited) -> Result<()> {
+    Err(ResponseError::RateLimited(limited.0))
+}
+
+/// Reject a request rerouted by the [`IpAccess`](`crate::rest_utils::IpAccess`) fairing.
+#[get("/ip_not_allowed")]
+pub fn ip_not_allowed(denied: IpNotAllowed) -> Result<()> {
+    Err(ResponseError::IpNotAllowed(denied.0, denied.1))
+}
+
+/// Reject a request rerouted by the [`ApiVersion`](`crate::rest_utils::ApiVersion`) fairing.
+#[get("/unsupported_api_version")]
+pub fn unsupported_api_version(version: UnsupportedApiVersion) -> Result<()> {
+    Err(ResponseError::UnsupportedApiVersion(version.0))
+}
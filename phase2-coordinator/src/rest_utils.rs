@@ -1,52 +1,123 @@
 // Utility types for the rest API
 
 use crate::{
-    authentication::{Production, Signature},
-    coordinator_state::TOKEN_BLACKLIST,
-    objects::Task,
-    s3::{S3Ctx, S3Error},
-    storage::{ContributionLocator, ContributionSignatureLocator},
-    CoordinatorError, Participant,
+    audit,
+    authentication::{SessionToken, SignatureScheme},
+    coordinator_state::{ExternalVerificationStatus, TOKEN_BLACKLIST},
+    environment::{BodyLimits, Environment, IpFilter},
+    object_storage::{ObjectStorageError, ObjectStore},
+    objects::{AttestationStatement, Task, TimestampProof, TranscriptRound, TrimmedContributionInfo},
+    storage::ServedFile,
+    ContributionFileSignature, CoordinatorError, Participant, StorageFailure, StorageMaintenance,
 };
 
-pub use crate::{coordinator_state::TOKENS_PATH, s3::TOKENS_ZIP_FILE};
+pub use crate::{coordinator_state::TOKENS_PATH, object_storage::TOKENS_ZIP_FILE};
 use blake2::Digest;
+use rand::RngCore;
 use rocket::{
     catch,
-    data::FromData,
+    data::{Data, FromData},
     error,
-    http::{ContentType, Status},
+    fairing::{Fairing, Info, Kind},
+    figment::Figment,
+    http::{uri::Origin, ContentType, Header, Method, Status},
     request::{FromRequest, Outcome, Request},
     response::{Responder, Response},
+    route::{self, Handler, Route},
     serde::{Deserialize, DeserializeOwned, Serialize},
-    tokio::{sync::RwLock, task},
+    tokio::{
+        fs::File,
+        io::{AsyncReadExt, AsyncWriteExt, DuplexStream},
+        runtime::Handle,
+        sync::{
+            mpsc::{self, error::TrySendError},
+            Mutex, RwLock,
+        },
+        task,
+    },
     State,
 };
 
 use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use ipnet::IpNet;
 
 use sha2::Sha256;
 use subtle::ConstantTimeEq;
+use tempfile::NamedTempFile;
 
 use lazy_static::lazy_static;
-use std::{borrow::Cow, convert::TryFrom, io::Cursor, net::IpAddr, ops::Deref, sync::Arc, time::Duration};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    convert::TryFrom,
+    io::{Cursor, Seek, SeekFrom},
+    net::{IpAddr, SocketAddr},
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 use thiserror::Error;
-use tracing::warn;
+use tracing::{warn, Instrument};
 
 #[cfg(debug_assertions)]
 pub const UPDATE_TIME: Duration = Duration::from_secs(5);
 #[cfg(not(debug_assertions))]
 pub const UPDATE_TIME: Duration = Duration::from_secs(60);
 
+/// Maximum time a request to `/contributor/await_turn` is held open, kept below the timeouts of the CDN.
+pub const AWAIT_TURN_TIMEOUT: Duration = Duration::from_secs(50);
+
+/// Number of contributors listed by `/contributors` when the size of the page isn't given, and its maximum.
+pub const DEFAULT_CONTRIBUTORS_PER_PAGE: u64 = 100;
+pub const MAX_CONTRIBUTORS_PER_PAGE: u64 = 1000;
+
+/// Maximum number of invite codes minted by a request to `/invite_codes`, and maximum validity of the codes in seconds.
+pub const MAX_INVITE_CODES_PER_REQUEST: usize = 1000;
+pub const MAX_INVITE_CODE_VALIDITY: u64 = 365 * 24 * 3600;
+
+/// Maximum size in bytes of a snapshot uploaded to `/admin/restore`.
+pub const SNAPSHOT_SIZE_LIMIT: u64 = 64 << 30;
+
+/// Maximum time of the download of a document published by a contributor, like an attestation or an identity key.
+pub const PUBLIC_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+/// Maximum size in bytes of a public attestation of a contributor, and of the public key of its identity.
+pub const ATTESTATION_SIZE_LIMIT: usize = 64 << 10;
+pub const IDENTITY_KEY_SIZE_LIMIT: usize = 1 << 20;
+
+/// Names of the [limits](rocket::data::Limits) of the Rocket configuration on the size of the bodies, set from the
+/// [`BodyLimits`] of the environment.
+pub const TINY_BODY_LIMIT: &str = "tiny";
+pub const JSON_BODY_LIMIT: &str = "json";
+pub const CONTRIBUTION_BODY_LIMIT: &str = "contribution";
+
+/// Endpoints whose json body is bounded by the [`TINY_BODY_LIMIT`], the others by the [`JSON_BODY_LIMIT`].
+pub const TINY_BODY_ENDPOINTS: [&str; 4] = [
+    "/contributor/join_queue",
+    "/contributor/heartbeat",
+    "/contributor/challenge",
+    "/upload/chunk",
+];
+
+/// Maximum length of the [`REQUEST_ID_HEADER`] accepted from the clients.
+pub const MAX_REQUEST_ID_LEN: usize = 128;
+
 pub const UNKNOWN: &str = "Unknown";
 pub const TOKEN_REGEX: &str = r"^[A-HJ-NP-Za-km-z1-9]{115}$";
 
-// Headers
-pub const BODY_DIGEST_HEADER: &str = "Digest";
-pub const PUBKEY_HEADER: &str = "ATS-Pubkey";
-pub const SIGNATURE_HEADER: &str = "ATS-Signature";
-pub const CONTENT_LENGTH_HEADER: &str = "Content-Length";
-pub const ACCESS_SECRET_HEADER: &str = "Access-Secret";
+// Headers and types shared with the clients
+pub use phase2_client_sdk::{
+    challenge_message, coordinator_identity_message, header_digest, login_message, verification_result_message,
+    versioned_endpoint, CeremonyEvent, CeremonyParameters, CeremonyStats, CeremonyStatus, ContentEncoding,
+    ContributionRejection, ContributorStatus, ContributorsPage, CoordinatorIdentity, ErrorCode, ErrorResponse,
+    LockedChunk, LoginRequest, PostChunkRequest, RejectionReason, Session, UploadSession, VerificationResult,
+    VerificationTask, ACCEPT_ENCODING_HEADER, ACCESS_SECRET_HEADER, API_PREFIX, API_VERSION, API_VERSION_HEADER,
+    BODY_DIGEST_HEADER, CHALLENGE_HASH_HEADER, CHALLENGE_SIGNATURE_HEADER, CONTENT_ENCODING_HEADER,
+    CONTENT_LENGTH_HEADER, CONTENT_RANGE_HEADER, CONTRIBUTION_SIGNATURE_HEADER, EMAIL_HEADER, ESTIMATED_WAIT_HEADER,
+    INVITE_CODE_HEADER, MAX_REQUEST_AGE, PRIORITY_TOKEN_HEADER, PUBKEY_HEADER, REQUEST_ID_HEADER, SESSION_TOKEN_HEADER,
+    SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
 
 lazy_static! {
     pub(crate) static ref HEALTH_PATH: String = match std::env::var("HEALTH_PATH") {
@@ -55,6 +126,15 @@ lazy_static! {
     };
     pub(crate) static ref ACCESS_SECRET: String =
         std::env::var("ACCESS_SECRET").expect("Missing required env ACCESS_SECRET");
+    pub static ref VERIFY_ATTESTATIONS: bool = match std::env::var("NAMADA_MPC_VERIFY_ATTESTATIONS") {
+        Ok(s) if s == "true" => true,
+        _ => false,
+    };
+    pub static ref TIMESTAMP_CALENDAR: Option<String> = std::env::var("NAMADA_MPC_TIMESTAMP_CALENDAR").ok();
+    pub(crate) static ref DIRECT_UPLOAD: bool = match std::env::var("NAMADA_MPC_DIRECT_UPLOAD") {
+        Ok(s) if s == "true" => true,
+        _ => false,
+    };
 }
 
 pub(crate) type Coordinator = Arc<RwLock<crate::Coordinator>>;
@@ -66,6 +146,10 @@ pub enum ResponseError {
     CeremonyIsOver,
     #[error("Coordinator failed: {0}")]
     CoordinatorError(CoordinatorError),
+    #[error("Attestation is not valid: {0}")]
+    InvalidAttestation(String),
+    #[error("Beacon is not valid: {0}")]
+    InvalidBeacon(String),
     #[error("Contribution info is not valid: {0}")]
     InvalidContributionInfo(String),
     #[error("The required access secret is either missing or invalid")]
@@ -74,24 +158,46 @@ pub enum ResponseError {
     InvalidHeader(&'static str),
     #[error("Updated tokens for current cohort don't match the old ones")]
     InvalidNewTokens,
+    #[error("Session token is invalid or expired")]
+    InvalidSessionToken,
     #[error("Request's signature is invalid")]
     InvalidSignature,
+    #[error("Identity proof is not valid: {0}")]
+    InvalidIdentityProof(String),
+    #[error("Invite code is not valid: {0}")]
+    InvalidInviteCode(String),
+    #[error("The address {0} is not allowed to access the endpoint {1}")]
+    IpNotAllowed(String, String),
+    #[error("Login failed: {0}")]
+    InvalidLogin(String),
+    #[error("Namada address binding is not valid: {0}")]
+    InvalidNamadaAddress(String),
+    #[error("Priority token is not valid: {0}")]
+    InvalidPriorityToken(String),
     #[error("Authentification token for cohort {0} is invalid")]
     InvalidToken(usize),
+    #[error("Range of the uploaded part is not valid, the upload must resume at byte {0}")]
+    InvalidUploadRange(u64),
     #[error("Io Error: {0}")]
     IoError(String),
+    #[error("The rotated key couldn't be persisted: {0}")]
+    KeyNotPersistable(String),
     #[error("Checksum of body doesn't match the expected one: expc {0}, act: {1}")]
     MismatchingChecksum(String, String),
     #[error("The required {0} header was missing from the incoming request")]
     MissingRequiredHeader(&'static str),
     #[error("Couldn't verify signature because of missing signing key")]
     MissingSigningKey,
+    #[error("Error with the object storage: {0}")]
+    ObjectStorageError(#[from] ObjectStorageError),
     #[error("Couldn't parse string to int: {0}")]
     ParseError(#[from] std::num::ParseIntError),
+    #[error("The body of the request exceeds the limit of {0} bytes")]
+    PayloadTooLarge(u64),
+    #[error("Too many requests, retry in {0} seconds")]
+    RateLimited(u64),
     #[error("Thread panicked: {0}")]
     RuntimeError(#[from] task::JoinError),
-    #[error("Error with S3: {0}")]
-    S3Error(#[from] S3Error),
     #[error("Error with Serde: {0}")]
     SerdeError(String),
     #[error("Error while terminating the ceremony: {0}")]
@@ -100,42 +206,188 @@ pub enum ResponseError {
     TokenAlreadyInUse,
     #[error("The provided token has already been used in the ceremony")]
     BlacklistedToken,
-    #[error("The participant {0} is not allowed to access the endpoint {1} because of: {2}")]
-    UnauthorizedParticipant(Participant, String, String),
+    #[error("The participant {1} is not allowed to access the endpoint {2} because of: {3}")]
+    UnauthorizedParticipant(ErrorCode, Participant, String, String),
     #[error("Could not find contributor with public key {0}")]
     UnknownContributor(String),
     #[error("Could not find the provided Task {0} in coordinator state")]
     UnknownTask(Task),
+    #[error("Version {0} of the API is not supported, the coordinator serves version {v}", v = API_VERSION)]
+    UnsupportedApiVersion(String),
     #[error("Digest of request's body is not base64 encoded: {0}")]
     WrongDigestEncoding(#[from] base64::DecodeError),
 }
 
+impl From<&CoordinatorError> for ErrorCode {
+    fn from(error: &CoordinatorError) -> Self {
+        match error {
+            CoordinatorError::CeremonyIsOver => ErrorCode::CeremonyIsOver,
+            CoordinatorError::ChallengeHashMismatch => ErrorCode::StaleChallenge,
+            CoordinatorError::ChunkLockAlreadyAcquired
+            | CoordinatorError::ChunkLockLimitReached
+            | CoordinatorError::ParticipantAlreadyHasLockedChunk
+            | CoordinatorError::ParticipantAlreadyHasLockedChunks
+            | CoordinatorError::ParticipantHasLockedMaximumChunks => ErrorCode::LockAlreadyHeld,
+            CoordinatorError::ChunkLockExpired => ErrorCode::LockExpired,
+            CoordinatorError::ContributionUploadIncomplete | CoordinatorError::InviteCodeExpirationInvalid => {
+                ErrorCode::InvalidRequest
+            }
+            CoordinatorError::CoordinatorDraining => ErrorCode::Draining,
+            CoordinatorError::ContributionFileSizeMismatch
+            | CoordinatorError::ContributionHashMismatch
+            | CoordinatorError::ContributionSignatureFileSizeMismatch
+            | CoordinatorError::ContributorSignatureInvalid
+            | CoordinatorError::VerificationFailed => ErrorCode::InvalidContribution,
+            CoordinatorError::ContributorAlreadyContributed
+            | CoordinatorError::ParticipantAlreadyFinished
+            | CoordinatorError::ParticipantInCooldown => ErrorCode::AlreadyContributed,
+            CoordinatorError::InviteCodeAlreadyRedeemed
+            | CoordinatorError::InviteCodeExpired
+            | CoordinatorError::InviteCodeInvalid => ErrorCode::InvalidInviteCode,
+            CoordinatorError::PriorityTokenAlreadyRedeemed
+            | CoordinatorError::PriorityTokenInactive
+            | CoordinatorError::PriorityTokenInvalid => ErrorCode::InvalidPriorityToken,
+            CoordinatorError::LoginNonceInvalid | CoordinatorError::LoginSignatureInvalid => {
+                ErrorCode::InvalidSignature
+            }
+            CoordinatorError::ParticipantAlreadyAdded
+            | CoordinatorError::ParticipantInCurrentRoundCannotJoinQueue
+            | CoordinatorError::ParticipantIpAlreadyAdded => ErrorCode::AlreadyInQueue,
+            CoordinatorError::ParticipantAlreadyBanned | CoordinatorError::ParticipantBanned => ErrorCode::Banned,
+            CoordinatorError::ParticipantSuspended => ErrorCode::Suspended,
+            CoordinatorError::ParticipantAlreadyDropped | CoordinatorError::ParticipantWasDropped => ErrorCode::Dropped,
+            CoordinatorError::ParticipantMissing | CoordinatorError::ParticipantNotFound(_) => {
+                ErrorCode::UnknownContributor
+            }
+            CoordinatorError::RoundDoesNotExist => ErrorCode::UnknownRound,
+            CoordinatorError::StorageSpaceLow => ErrorCode::StorageUnavailable,
+            CoordinatorError::ParticipantNotReady
+            | CoordinatorError::QueueWaitTimeIncomplete
+            | CoordinatorError::RoundNotReady => ErrorCode::NotYourTurn,
+            CoordinatorError::ChunkNotLockedOrByWrongParticipant
+            | CoordinatorError::LoginDisabled
+            | CoordinatorError::ParticipantUnauthorized
+            | CoordinatorError::ParticipantUnauthorizedForChunkId { .. }
+            | CoordinatorError::UnauthorizedChunkContributor => ErrorCode::Unauthorized,
+            _ => ErrorCode::Internal,
+        }
+    }
+}
+
+impl ResponseError {
+    /// Returns the stable [`ErrorCode`] sent to the client together with the message of the error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ResponseError::BlacklistedToken => ErrorCode::BlacklistedToken,
+            ResponseError::CeremonyIsOver => ErrorCode::CeremonyIsOver,
+            ResponseError::CoordinatorError(e) => e.into(),
+            ResponseError::InvalidAttestation(_)
+            | ResponseError::InvalidBeacon(_)
+            | ResponseError::InvalidContributionInfo(_)
+            | ResponseError::InvalidHeader(_)
+            | ResponseError::InvalidIdentityProof(_)
+            | ResponseError::InvalidNamadaAddress(_)
+            | ResponseError::InvalidNewTokens
+            | ResponseError::InvalidUploadRange(_)
+            | ResponseError::KeyNotPersistable(_)
+            | ResponseError::MismatchingChecksum(_, _)
+            | ResponseError::MissingRequiredHeader(_)
+            | ResponseError::MissingSigningKey
+            | ResponseError::ParseError(_)
+            | ResponseError::SerdeError(_)
+            | ResponseError::UnknownTask(_)
+            | ResponseError::WrongDigestEncoding(_) => ErrorCode::InvalidRequest,
+            ResponseError::InvalidInviteCode(_) => ErrorCode::InvalidInviteCode,
+            ResponseError::InvalidLogin(_) => ErrorCode::InvalidSignature,
+            ResponseError::InvalidPriorityToken(_) => ErrorCode::InvalidPriorityToken,
+            ResponseError::InvalidSecret => ErrorCode::Unauthorized,
+            ResponseError::IpNotAllowed(_, _) => ErrorCode::Unauthorized,
+            ResponseError::InvalidSessionToken => ErrorCode::InvalidSession,
+            ResponseError::InvalidSignature => ErrorCode::InvalidSignature,
+            ResponseError::InvalidToken(_) => ErrorCode::InvalidToken,
+            ResponseError::PayloadTooLarge(_) => ErrorCode::PayloadTooLarge,
+            ResponseError::RateLimited(_) => ErrorCode::RateLimited,
+            ResponseError::TokenAlreadyInUse => ErrorCode::TokenAlreadyInUse,
+            ResponseError::UnauthorizedParticipant(code, _, _, _) => *code,
+            ResponseError::UnknownContributor(_) => ErrorCode::UnknownContributor,
+            ResponseError::UnsupportedApiVersion(_) => ErrorCode::UnsupportedApiVersion,
+            _ => ErrorCode::Internal,
+        }
+    }
+
+    /// Returns the number of seconds after which the request can be retried, if the failure is temporary.
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            ResponseError::RateLimited(retry_after) => Some(*retry_after),
+            // The state of the ceremony changes at every update of the coordinator
+            _ if self.code() == ErrorCode::NotYourTurn => Some(UPDATE_TIME.as_secs()),
+            _ => None,
+        }
+    }
+}
+
 impl<'r> Responder<'r, 'static> for ResponseError {
-    fn respond_to(self, _request: &'r Request<'_>) -> rocket::response::Result<'static> {
-        let response = format!("{}", self);
+    fn respond_to(self, request: &'r Request<'_>) -> rocket::response::Result<'static> {
+        let request_id = RequestId::of(request);
+        let error_response = ErrorResponse {
+            code: self.code(),
+            message: self.to_string(),
+            retry_after: self.retry_after(),
+            request_id: Some(request_id.to_string()),
+        };
+        let response = serde_json::to_string(&error_response).map_err(|_| Status::InternalServerError)?;
         let mut builder = Response::build();
 
         let response_code = match self {
             ResponseError::BlacklistedToken => Status::Unauthorized,
             ResponseError::CeremonyIsOver => Status::Unauthorized,
+            ResponseError::CoordinatorError(CoordinatorError::ChallengeHashMismatch) => Status::Conflict,
+            ResponseError::CoordinatorError(CoordinatorError::ChunkLockExpired) => Status::Unauthorized,
+            ResponseError::CoordinatorError(CoordinatorError::ContributionUploadIncomplete) => Status::Conflict,
+            ResponseError::CoordinatorError(CoordinatorError::ContributorSignatureInvalid) => Status::BadRequest,
+            ResponseError::CoordinatorError(CoordinatorError::CoordinatorDraining) => Status::ServiceUnavailable,
+            ResponseError::CoordinatorError(CoordinatorError::InviteCodeExpirationInvalid) => Status::BadRequest,
+            ResponseError::CoordinatorError(CoordinatorError::ParticipantSuspended) => Status::Forbidden,
+            ResponseError::CoordinatorError(CoordinatorError::RoundDoesNotExist) => Status::NotFound,
+            ResponseError::CoordinatorError(CoordinatorError::StorageSpaceLow) => Status::ServiceUnavailable,
+            ResponseError::InvalidAttestation(_) => Status::BadRequest,
+            ResponseError::InvalidBeacon(_) => Status::BadRequest,
             ResponseError::InvalidHeader(_) => Status::BadRequest,
+            ResponseError::InvalidIdentityProof(_) => Status::BadRequest,
+            ResponseError::InvalidInviteCode(_) => Status::Unauthorized,
+            ResponseError::InvalidLogin(_) => Status::Unauthorized,
+            ResponseError::InvalidNamadaAddress(_) => Status::BadRequest,
+            ResponseError::InvalidPriorityToken(_) => Status::Unauthorized,
             ResponseError::InvalidSecret => Status::Unauthorized,
+            ResponseError::InvalidSessionToken => Status::Unauthorized,
             ResponseError::InvalidSignature => Status::BadRequest,
             ResponseError::InvalidToken(_) => Status::Unauthorized,
+            ResponseError::InvalidUploadRange(_) => Status::RangeNotSatisfiable,
+            ResponseError::IpNotAllowed(_, _) => Status::Forbidden,
+            ResponseError::KeyNotPersistable(_) => Status::Conflict,
             ResponseError::MismatchingChecksum(_, _) => Status::BadRequest,
             ResponseError::MissingRequiredHeader(h) if h == CONTENT_LENGTH_HEADER => Status::LengthRequired,
             ResponseError::MissingRequiredHeader(_) => Status::BadRequest,
             ResponseError::MissingSigningKey => Status::BadRequest,
+            ResponseError::PayloadTooLarge(_) => Status::PayloadTooLarge,
+            ResponseError::RateLimited(_) => Status::TooManyRequests,
             ResponseError::SerdeError(_) => Status::UnprocessableEntity,
             ResponseError::TokenAlreadyInUse => Status::Unauthorized,
-            ResponseError::UnauthorizedParticipant(_, _, _) => Status::Unauthorized,
+            ResponseError::UnauthorizedParticipant(_, _, _, _) => Status::Unauthorized,
+            ResponseError::UnknownContributor(_) => Status::NotFound,
+            ResponseError::UnsupportedApiVersion(_) => Status::BadRequest,
             ResponseError::WrongDigestEncoding(_) => Status::BadRequest,
             _ => Status::InternalServerError,
         };
 
+        if let Some(retry_after) = error_response.retry_after {
+            builder.raw_header("Retry-After", retry_after.to_string());
+        }
+        builder.raw_header(REQUEST_ID_HEADER, request_id.to_string());
+
         builder
             .status(response_code)
-            .header(ContentType::Text)
+            .header(ContentType::JSON)
             .sized_body(response.len(), Cursor::new(response))
             .ok()
     }
@@ -146,16 +398,20 @@ pub type Result<T> = std::result::Result<T, ResponseError>;
 // Custom catchers for Request/Data Guards. These remap custom error codes to the standard ones and call the ResponseError Responder to produce the response. The default catcher is mantained for non-custom errors
 
 #[catch(452)]
-pub fn invalid_signature() -> ResponseError {
-    ResponseError::InvalidSignature
+pub fn invalid_signature(req: &Request) -> ResponseError {
+    match req.local_cache(|| ErrorCode::InvalidSignature) {
+        ErrorCode::InvalidSession => ResponseError::InvalidSessionToken,
+        _ => ResponseError::InvalidSignature,
+    }
 }
 
 #[catch(453)]
 pub fn unauthorized(req: &Request) -> ResponseError {
     let participant = req.local_cache(|| Participant::new_contributor(UNKNOWN));
     let (endpoint, cause) = req.local_cache(|| (String::from(UNKNOWN), String::from(UNKNOWN)));
+    let code = req.local_cache(|| ErrorCode::Unauthorized);
 
-    ResponseError::UnauthorizedParticipant(participant.clone(), endpoint.to_owned(), cause.to_owned())
+    ResponseError::UnauthorizedParticipant(*code, participant.clone(), endpoint.to_owned(), cause.to_owned())
 }
 
 #[catch(454)]
@@ -182,6 +438,23 @@ pub fn invalid_header(req: &Request) -> ResponseError {
     ResponseError::InvalidHeader(header)
 }
 
+#[catch(458)]
+pub fn ip_not_allowed(req: &Request) -> ResponseError {
+    let DeniedIp(ip, endpoint) = req
+        .local_cache(|| DeniedIp(String::from(UNKNOWN), String::from(UNKNOWN)))
+        .clone();
+    ResponseError::IpNotAllowed(ip, endpoint)
+}
+
+/// Limit exceeded by the body of a request, cached for the [`payload_too_large`] catcher.
+struct ExceededBodyLimit(u64);
+
+#[catch(413)]
+pub fn payload_too_large(req: &Request) -> ResponseError {
+    let limit = req.local_cache(|| ExceededBodyLimit(0));
+    ResponseError::PayloadTooLarge(limit.0)
+}
+
 #[catch(512)]
 pub fn io_error(req: &Request) -> ResponseError {
     let message = req.local_cache(|| UNKNOWN.to_string());
@@ -230,34 +503,65 @@ impl<'a> RequestContent<'a> {
     }
 }
 
-/// The headers involved in the signature of the request.
+/// The headers involved in the signature of the request, together with the method and the path (including the
+/// query) of the request, so that a signature can't be reused for a different endpoint.
 #[derive(Default)]
 pub struct SignatureHeaders<'r> {
+    pub method: &'r str,
+    pub path: Cow<'r, str>,
+    /// Unix timestamp, in seconds, of the signature
+    pub timestamp: i64,
     pub pubkey: &'r str,
     pub content: Option<RequestContent<'r>>,
+    /// [`header_digest`] of the [`CONTRIBUTION_SIGNATURE_HEADER`], if the request carries it
+    pub contribution_signature: Option<String>,
     pub signature: Option<Cow<'r, str>>,
 }
 
 impl<'r> SignatureHeaders<'r> {
     /// Produces the message on which to compute the signature
     pub fn to_string(&self) -> Cow<'_, str> {
-        match &self.content {
-            Some(content) => format!("{}{}{}", self.pubkey, content.len, content.digest).into(),
-            None => self.pubkey.into(),
-        }
+        let content = self.content.as_ref().map(|c| (c.len, c.digest.as_ref()));
+
+        phase2_client_sdk::signature_message(
+            self.method,
+            &self.path,
+            self.timestamp,
+            self.pubkey,
+            content,
+            self.contribution_signature.as_deref(),
+        )
+        .into()
     }
 
-    pub fn new(pubkey: &'r str, content: Option<RequestContent<'r>>, signature: Option<Cow<'r, str>>) -> Self {
+    pub fn new(
+        method: &'r str,
+        path: Cow<'r, str>,
+        timestamp: i64,
+        pubkey: &'r str,
+        content: Option<RequestContent<'r>>,
+        signature: Option<Cow<'r, str>>,
+    ) -> Self {
         Self {
+            method,
+            path,
+            timestamp,
             pubkey,
             content,
+            contribution_signature: None,
             signature,
         }
     }
 
-    fn try_verify_signature(&self) -> Result<bool> {
+    /// Verifies the signature of the request with the given scheme. Signatures whose timestamp is more than
+    /// [`MAX_REQUEST_AGE`] seconds away from the clock of the coordinator are not valid.
+    fn try_verify_signature(&self, scheme: SignatureScheme) -> Result<bool> {
+        if (Utc::now().timestamp() - self.timestamp).abs() > MAX_REQUEST_AGE {
+            return Ok(false);
+        }
+
         match &self.signature {
-            Some(sig) => Ok(Production.verify(self.pubkey, &self.to_string(), &sig)),
+            Some(sig) => Ok(scheme.verify(self.pubkey, &self.to_string(), &sig)),
             None => Err(ResponseError::MissingSigningKey),
         }
     }
@@ -276,6 +580,10 @@ impl<'r> TryFrom<&'r Request<'_>> for SignatureHeaders<'r> {
         let sig = headers
             .get_one(SIGNATURE_HEADER)
             .ok_or(ResponseError::InvalidHeader(SIGNATURE_HEADER))?;
+        let timestamp = headers
+            .get_one(TIMESTAMP_HEADER)
+            .and_then(|t| t.parse().ok())
+            .ok_or(ResponseError::InvalidHeader(TIMESTAMP_HEADER))?;
 
         // If post request, also get the hash of body from header (if any and if base64 encoded)
         if request.method() == rocket::http::Method::Post {
@@ -289,34 +597,231 @@ impl<'r> TryFrom<&'r Request<'_>> for SignatureHeaders<'r> {
             }
         }
 
-        Ok(SignatureHeaders::new(pubkey, body, Some(sig.into())))
+        let mut signature_headers = SignatureHeaders::new(
+            request.method().as_str(),
+            request.uri().to_string().into(),
+            timestamp,
+            pubkey,
+            body,
+            Some(sig.into()),
+        );
+        // The signature of the contribution is signed with the request, for it not to be swapped
+        signature_headers.contribution_signature = headers.get_one(CONTRIBUTION_SIGNATURE_HEADER).map(header_digest);
+
+        Ok(signature_headers)
+    }
+}
+
+/// Returns the address of the client of the request. Behind one of the `trusted_proxies`, it's the last address of
+/// the `X-Forwarded-For` header not belonging to a trusted proxy, the addresses to its left being set by the client.
+pub fn client_address(request: &Request<'_>, trusted_proxies: &[IpNet]) -> Option<IpAddr> {
+    let peer = request.remote()?.ip();
+    let is_trusted = |ip: &IpAddr| trusted_proxies.iter().any(|net| net.contains(ip));
+    if !is_trusted(&peer) {
+        return Some(peer);
+    }
+
+    let forwarded: Vec<IpAddr> = request
+        .headers()
+        .get("X-Forwarded-For")
+        .flat_map(|header| header.split(','))
+        .filter_map(|ip| ip.trim().parse().ok())
+        .collect();
+
+    match forwarded.iter().rev().find(|ip| !is_trusted(ip)) {
+        Some(client) => Some(*client),
+        None => Some(forwarded.first().copied().unwrap_or(peer)),
+    }
+}
+
+/// Categories of routes filtered by the [`IpFilter`]s of the environment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RouteAccess {
+    /// The routes of the contributors and of the verifiers, filtered by the contributor filter
+    Contributor,
+    /// The routes of the operators, filtered by both the contributor and the admin filters
+    Admin,
+}
+
+/// Address refused by the [`IpAccess`] filters and the endpoint it requested, cached for the [`ip_not_allowed`]
+/// catcher and the [`IpNotAllowed`] guard.
+#[derive(Clone)]
+struct DeniedIp(String, String);
+
+/// Path of the route answering the requests rejected by the [`IpAccess`] fairing.
+pub const IP_NOT_ALLOWED_PATH: &str = "/ip_not_allowed";
+
+/// Prefixes of the paths of the contributor and verifier routes, filtered by the [`IpAccess`] fairing whether the
+/// route authenticates the request or not.
+const CONTRIBUTOR_PATH_PREFIXES: [&str; 3] = ["/contributor/", "/verifier/", "/upload/"];
+
+/// The [`IpFilter`]s and the trusted proxies of the environment, copied at startup so that the requests are filtered
+/// without taking the lock of the coordinator. Managed by Rocket for the request guards, and attached as a fairing
+/// rerouting the denied requests to the contributor and verifier routes to [`IP_NOT_ALLOWED_PATH`].
+#[derive(Clone, Debug, Default)]
+pub struct IpAccess {
+    contributor_filter: IpFilter,
+    admin_filter: IpFilter,
+    trusted_proxies: Vec<IpNet>,
+}
+
+impl IpAccess {
+    /// Builds the filters from the settings of the environment.
+    pub fn from_environment(environment: &Environment) -> Self {
+        Self {
+            contributor_filter: environment.contributor_ip_filter().clone(),
+            admin_filter: environment.admin_ip_filter().clone(),
+            trusted_proxies: environment.trusted_proxies().clone(),
+        }
+    }
+
+    /// Returns the address of the client of the request, see [`client_address`].
+    pub fn client_address(&self, request: &Request<'_>) -> Option<IpAddr> {
+        client_address(request, &self.trusted_proxies)
+    }
+
+    /// Checks that the client of the request is allowed to reach the routes of the given category.
+    fn check(&self, request: &Request<'_>, access: RouteAccess) -> std::result::Result<(), DeniedIp> {
+        let ip = self.client_address(request);
+        let allowed = self.contributor_filter.allows(ip.as_ref())
+            && (access == RouteAccess::Contributor || self.admin_filter.allows(ip.as_ref()));
+
+        if allowed {
+            return Ok(());
+        }
+
+        let ip = ip.map_or_else(|| String::from(UNKNOWN), |ip| ip.to_string());
+        warn!("Refused request to {} from {}", request.uri(), ip);
+        // Cache error data for the error catcher
+        Err(request.local_cache(|| DeniedIp(ip, request.uri().to_string())).clone())
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for IpAccess {
+    fn info(&self) -> Info {
+        Info {
+            name: "IP access",
+            kind: Kind::Request,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        let path = unversioned_path(request.uri().path().as_str());
+        if request.method() == Method::Options || !CONTRIBUTOR_PATH_PREFIXES.iter().any(|p| path.starts_with(p)) {
+            return;
+        }
+
+        if self.check(request, RouteAccess::Contributor).is_err() {
+            request.set_method(Method::Get);
+            request.set_uri(Origin::parse(IP_NOT_ALLOWED_PATH).expect("IP not allowed path should be a valid uri"));
+        }
     }
 }
 
-trait VerifySignature<'r> {
-    // Workaround to implement a single method on a foreign type instead of newtype pattern
-    fn verify_signature(&'r self) -> Result<&str>;
+/// Checks that the client of the request is allowed to reach the routes of the given category.
+async fn check_ip_access(
+    request: &Request<'_>,
+    access: RouteAccess,
+) -> std::result::Result<(), (Status, ResponseError)> {
+    let ip_access = request
+        .guard::<&State<IpAccess>>()
+        .await
+        .succeeded()
+        .expect("Managed state should always be retrievable");
+
+    ip_access
+        .check(request, access)
+        .map_err(|DeniedIp(ip, endpoint)| (Status::new(458), ResponseError::IpNotAllowed(ip, endpoint)))
 }
 
-impl<'r> VerifySignature<'r> for Request<'_> {
-    /// Check signature of request and return the pubkey of the participant
-    fn verify_signature(&'r self) -> Result<&str> {
-        let headers = SignatureHeaders::try_from(self)?;
+/// Request rerouted by the [`IpAccess`] fairing, carrying the refused address and the endpoint it requested. Requests
+/// sent directly to [`IP_NOT_ALLOWED_PATH`] are forwarded.
+pub struct IpNotAllowed(pub String, pub String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IpNotAllowed {
+    type Error = ResponseError;
 
-        match headers.try_verify_signature()? {
-            true => Ok(headers.pubkey),
-            false => Err(ResponseError::InvalidSignature),
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.local_cache(|| DeniedIp(String::new(), String::new())) {
+            DeniedIp(ip, endpoint) if !endpoint.is_empty() => Outcome::Success(Self(ip.clone(), endpoint.clone())),
+            _ => Outcome::Forward(()),
         }
     }
 }
 
+/// Check signature of request and return the pubkey of the participant. The signature is registered in the state of
+/// the [Coordinator](`crate::Coordinator`), so that the request can't be replayed.
+///
+/// If `accept_session`, a request carrying a [`SESSION_TOKEN_HEADER`] is authenticated by the token instead, which must
+/// be signed by the coordinator and not expired. The token is a bearer credential, neither bound to the body nor to a
+/// timestamp: it's only accepted on the contributor routes, the verifier and admin ones always require a signature.
+async fn verify_signature(request: &Request<'_>, accept_session: bool) -> Result<String> {
+    let coordinator = request
+        .guard::<&State<Coordinator>>()
+        .await
+        .succeeded()
+        .expect("Managed state should always be retrievable");
+
+    if let Some(token) = request
+        .headers()
+        .get_one(SESSION_TOKEN_HEADER)
+        .filter(|_| accept_session)
+    {
+        let coordinator_public_key = coordinator
+            .read()
+            .await
+            .environment()
+            .coordinator_verifiers()
+            .first()
+            .map(|verifier| verifier.address());
+
+        return match (SessionToken::decode(token), coordinator_public_key) {
+            (Some(token), Some(public_key)) if token.verify(&public_key, Utc::now().timestamp()) => Ok(token.pubkey),
+            _ => {
+                // Cache error data for the error catcher
+                request.local_cache(|| ErrorCode::InvalidSession);
+
+                Err(ResponseError::InvalidSessionToken)
+            }
+        };
+    }
+
+    let headers = SignatureHeaders::try_from(request)?;
+    let scheme = coordinator.read().await.environment().signature_scheme();
+
+    if !headers.try_verify_signature(scheme)? {
+        return Err(ResponseError::InvalidSignature);
+    }
+    // Safe to unwrap, the signature was checked above
+    let signature = headers.signature.as_deref().unwrap();
+
+    coordinator
+        .write()
+        .await
+        .register_request_signature(
+            headers.pubkey,
+            headers.timestamp,
+            signature,
+            time::Duration::seconds(MAX_REQUEST_AGE),
+        )
+        .map_err(ResponseError::CoordinatorError)?;
+
+    Ok(headers.pubkey.to_owned())
+}
+
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for Participant {
     type Error = ResponseError;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        match request.verify_signature() {
-            Ok(pubkey) => Outcome::Success(Participant::new_contributor(pubkey)),
+        if let Err(e) = check_ip_access(request, RouteAccess::Contributor).await {
+            return Outcome::Failure(e);
+        }
+
+        match verify_signature(request, true).await {
+            Ok(pubkey) => Outcome::Success(Participant::new_contributor(&pubkey)),
             Err(e) => Outcome::Failure((Status::new(452), e)),
         }
     }
@@ -326,6 +831,12 @@ impl<'r> FromRequest<'r> for Participant {
 pub struct NewParticipant {
     pub participant: Participant,
     pub ip_address: Option<IpAddr>,
+    /// The invite code provided in the [`INVITE_CODE_HEADER`], if any
+    pub invite_code: Option<String>,
+    /// The priority token provided in the [`PRIORITY_TOKEN_HEADER`], if any
+    pub priority_token: Option<String>,
+    /// The email address to notify of the turn of the contributor, provided in the [`EMAIL_HEADER`], if any
+    pub email: Option<String>,
 }
 
 #[rocket::async_trait]
@@ -333,7 +844,11 @@ impl<'r> FromRequest<'r> for NewParticipant {
     type Error = ResponseError;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let pubkey = match request.verify_signature() {
+        if let Err(e) = check_ip_access(request, RouteAccess::Contributor).await {
+            return Outcome::Failure(e);
+        }
+
+        let pubkey = match verify_signature(request, false).await {
             Ok(h) => h,
             Err(e) => return Outcome::Failure((Status::new(452), e)),
         };
@@ -344,8 +859,13 @@ impl<'r> FromRequest<'r> for NewParticipant {
             .await
             .succeeded()
             .expect("Managed state should always be retrievable");
-        let participant = Participant::new_contributor(pubkey);
-        let ip_address = request.client_ip();
+        let participant = Participant::new_contributor(&pubkey);
+        let ip_address = request
+            .guard::<&State<IpAccess>>()
+            .await
+            .succeeded()
+            .expect("Managed state should always be retrievable")
+            .client_address(request);
 
         if let Err(e) = coordinator
             .read()
@@ -354,18 +874,23 @@ impl<'r> FromRequest<'r> for NewParticipant {
             .add_to_queue_checks(&participant, ip_address.as_ref())
         {
             // Cache error data for the error catcher
+            let code = ErrorCode::from(&e);
             request.local_cache(|| participant.clone());
             request.local_cache(|| (request.uri().to_string(), e.to_string()));
+            request.local_cache(|| code);
 
             return Outcome::Failure((
                 Status::new(453),
-                ResponseError::UnauthorizedParticipant(participant, request.uri().to_string(), e.to_string()),
+                ResponseError::UnauthorizedParticipant(code, participant, request.uri().to_string(), e.to_string()),
             ));
         }
 
         Outcome::Success(Self {
             participant,
             ip_address,
+            invite_code: request.headers().get_one(INVITE_CODE_HEADER).map(ToOwned::to_owned),
+            priority_token: request.headers().get_one(PRIORITY_TOKEN_HEADER).map(ToOwned::to_owned),
+            email: request.headers().get_one(EMAIL_HEADER).map(ToOwned::to_owned),
         })
     }
 }
@@ -386,7 +911,11 @@ impl<'r> FromRequest<'r> for CurrentContributor {
     type Error = ResponseError;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let pubkey = match request.verify_signature() {
+        if let Err(e) = check_ip_access(request, RouteAccess::Contributor).await {
+            return Outcome::Failure(e);
+        }
+
+        let pubkey = match verify_signature(request, true).await {
             Ok(h) => h,
             Err(e) => return Outcome::Failure((Status::new(452), e)),
         };
@@ -397,28 +926,91 @@ impl<'r> FromRequest<'r> for CurrentContributor {
             .await
             .succeeded()
             .expect("Managed state should always be retrievable");
-        let participant = Participant::new_contributor(pubkey);
+        let participant = Participant::new_contributor(&pubkey);
 
         let read_lock = coordinator.read().await;
         if !read_lock.is_current_contributor(&participant) {
             // Cache error data for the error catcher
-            let error_msg = {
+            let (code, error_msg) = {
                 if read_lock.is_banned_participant(&participant) {
-                    String::from("Participant has been banned from the ceremony")
+                    (ErrorCode::Banned, String::from("Participant has been banned from the ceremony"))
                 } else if read_lock.is_dropped_participant(&participant) {
-                    String::from("Participant has been dropped from the ceremony")
+                    (ErrorCode::Dropped, String::from("Participant has been dropped from the ceremony"))
                 } else {
-                    String::from("Participant is not the current contributor")
+                    (ErrorCode::NotCurrentContributor, String::from("Participant is not the current contributor"))
                 }
             };
             drop(read_lock);
 
             request.local_cache(|| participant.clone());
             request.local_cache(|| (request.uri().to_string(), error_msg.clone()));
+            request.local_cache(|| code);
+
+            return Outcome::Failure((
+                Status::new(453),
+                ResponseError::UnauthorizedParticipant(code, participant, request.uri().to_string(), error_msg),
+            ));
+        }
+
+        Outcome::Success(Self(participant))
+    }
+}
+
+/// Implements the signature verification on the incoming external verifier request via [`FromRequest`]. The public
+/// key must be one of the [`external_verifiers`](Environment::external_verifiers) of the coordinator.
+pub struct ExternalVerifier(Participant);
+
+impl Deref for ExternalVerifier {
+    type Target = Participant;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ExternalVerifier {
+    type Error = ResponseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        if let Err(e) = check_ip_access(request, RouteAccess::Contributor).await {
+            return Outcome::Failure(e);
+        }
+
+        let pubkey = match verify_signature(request, false).await {
+            Ok(h) => h,
+            Err(e) => return Outcome::Failure((Status::new(452), e)),
+        };
+
+        let coordinator = request
+            .guard::<&State<Coordinator>>()
+            .await
+            .succeeded()
+            .expect("Managed state should always be retrievable");
+        let participant = Participant::new_verifier(&pubkey);
+
+        let registered = coordinator
+            .read()
+            .await
+            .environment()
+            .external_verifiers()
+            .iter()
+            .any(|verifier| *verifier == pubkey);
+        if !registered {
+            // Cache error data for the error catcher
+            let error_msg = String::from("Participant is not an external verifier");
+            request.local_cache(|| participant.clone());
+            request.local_cache(|| (request.uri().to_string(), error_msg.clone()));
+            request.local_cache(|| ErrorCode::Unauthorized);
 
             return Outcome::Failure((
                 Status::new(453),
-                ResponseError::UnauthorizedParticipant(participant, request.uri().to_string(), error_msg),
+                ResponseError::UnauthorizedParticipant(
+                    ErrorCode::Unauthorized,
+                    participant,
+                    request.uri().to_string(),
+                    error_msg,
+                ),
             ));
         }
 
@@ -435,6 +1027,10 @@ impl<'r> FromRequest<'r> for Secret {
     type Error = ResponseError;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        if let Err(e) = check_ip_access(request, RouteAccess::Admin).await {
+            return Outcome::Failure(e);
+        }
+
         match request.headers().get_one(ACCESS_SECRET_HEADER) {
             Some(secret) if secret.as_bytes().ct_eq(&*ACCESS_SECRET.as_bytes()).into() => Outcome::Success(Self),
             _ => Outcome::Failure((Status::new(401), ResponseError::InvalidSecret)),
@@ -442,41 +1038,126 @@ impl<'r> FromRequest<'r> for Secret {
     }
 }
 
-/// Implements the signature verification on the incoming server request via [`FromRequest`].
-pub struct ServerAuth;
+/// Implements the signature verification on the incoming server request via [`FromRequest`]. The request must be
+/// signed either with the key of the coordinator's verifier or with one of the operator keys listed in
+/// [`Environment::admin_public_keys`](crate::environment::Environment::admin_public_keys). Holds the public key of the
+/// signer, to tell the operators apart in the logs.
+pub struct ServerAuth(pub String);
 
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for ServerAuth {
     type Error = ResponseError;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let pubkey = match request.verify_signature() {
+        if let Err(e) = check_ip_access(request, RouteAccess::Admin).await {
+            return Outcome::Failure(e);
+        }
+
+        let pubkey = match verify_signature(request, false).await {
             Ok(h) => h,
             Err(e) => return Outcome::Failure((Status::new(452), e)),
         };
 
-        // Check that the signature comes from the coordinator by matching the default verifier key
+        // Check that the signature comes from the coordinator, matching the default verifier key, or from an operator
         let coordinator = request
             .guard::<&State<Coordinator>>()
             .await
             .succeeded()
             .expect("Managed state should always be retrievable");
-        let verifier = Participant::new_verifier(pubkey);
+        let verifier = Participant::new_verifier(&pubkey);
+
+        let is_authorized = {
+            let read_lock = coordinator.read().await;
+            let environment = read_lock.environment();
+            verifier == environment.coordinator_verifiers()[0]
+                || environment.admin_public_keys().iter().any(|key| *key == pubkey)
+        };
 
-        if verifier != coordinator.read().await.environment().coordinator_verifiers()[0] {
+        if !is_authorized {
             // Cache error data for the error catcher
-            let error_msg = String::from("Not the coordinator's verifier");
+            let error_msg = String::from("Not the coordinator's verifier nor an operator");
             request.local_cache(|| verifier.clone());
             request.local_cache(|| (request.uri().to_string(), error_msg.clone()));
 
             return Outcome::Failure((
                 Status::new(453),
-                ResponseError::UnauthorizedParticipant(verifier, request.uri().to_string(), error_msg),
+                ResponseError::UnauthorizedParticipant(
+                    ErrorCode::Unauthorized,
+                    verifier,
+                    request.uri().to_string(),
+                    error_msg,
+                ),
+            ));
+        }
+
+        Outcome::Success(Self(pubkey))
+    }
+}
+
+/// Reads the expected length and digest of the body from the headers of the request, covered by its signature.
+/// Returns the raw digest header together with the parsed content.
+fn expected_content<'r>(
+    req: &'r Request<'_>,
+) -> std::result::Result<(&'r str, RequestContent<'r>), (Status, ResponseError)> {
+    let headers = req.headers();
+    let expected_digest = match headers.get_one(BODY_DIGEST_HEADER) {
+        Some(h) => h,
+        None => {
+            // Cache error data for the error catcher
+            req.local_cache(|| BODY_DIGEST_HEADER.to_string());
+
+            return Err((
+                Status::new(454),
+                ResponseError::MissingRequiredHeader(BODY_DIGEST_HEADER),
+            ));
+        }
+    };
+
+    let content_length = match headers.get_one(CONTENT_LENGTH_HEADER) {
+        Some(h) => h,
+        None => {
+            // Cache error data for the error catcher
+            req.local_cache(|| CONTENT_LENGTH_HEADER.to_string());
+
+            return Err((
+                Status::new(454),
+                ResponseError::MissingRequiredHeader(CONTENT_LENGTH_HEADER),
             ));
         }
+    };
+
+    match RequestContent::try_from_header(content_length, expected_digest) {
+        Ok(c) => Ok((expected_digest, c)),
+        Err(e) => {
+            // Cache error data for the error catcher
+            let header = match e {
+                ResponseError::InvalidHeader(h) => h,
+                _ => UNKNOWN,
+            };
+            req.local_cache(|| header);
+
+            Err((Status::new(457), e))
+        }
+    }
+}
 
-        Outcome::Success(Self)
+/// Checks the announced length of the body against the limit of the Rocket configuration with the given name, or its
+/// default if the configuration doesn't set it. The body is then read up to its announced length only.
+fn check_body_limit(
+    req: &Request<'_>,
+    len: usize,
+    name: &str,
+    default: u64,
+) -> std::result::Result<(), (Status, ResponseError)> {
+    let limit = req.limits().get(name).map_or(default, |limit| limit.as_u64());
+    if len as u64 > limit {
+        // Cache error data for the error catcher
+        req.local_cache(|| ExceededBodyLimit(limit));
+
+        return Err((Status::PayloadTooLarge, ResponseError::PayloadTooLarge(limit)));
     }
+
+    Ok(())
 }
 
 /// Type to handle lazy deserialization of json encoded inputs.
@@ -500,48 +1181,21 @@ impl<T> std::ops::DerefMut for LazyJson<T> {
 impl<'r, T: DeserializeOwned> FromData<'r> for LazyJson<T> {
     type Error = ResponseError;
 
-    async fn from_data(req: &'r Request<'_>, data: rocket::data::Data<'r>) -> rocket::data::Outcome<'r, Self> {
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> rocket::data::Outcome<'r, Self> {
         // Check that digest of body is the expected one
-        let headers = req.headers();
-        let expected_digest = match headers.get_one(BODY_DIGEST_HEADER) {
-            Some(h) => h,
-            None => {
-                // Cache error data for the error catcher
-                req.local_cache(|| BODY_DIGEST_HEADER.to_string());
-
-                return rocket::data::Outcome::Failure((
-                    Status::new(454),
-                    ResponseError::MissingRequiredHeader(BODY_DIGEST_HEADER),
-                ));
-            }
-        };
-
-        let content_length = match headers.get_one(CONTENT_LENGTH_HEADER) {
-            Some(h) => h,
-            None => {
-                // Cache error data for the error catcher
-                req.local_cache(|| CONTENT_LENGTH_HEADER.to_string());
-
-                return rocket::data::Outcome::Failure((
-                    Status::new(454),
-                    ResponseError::MissingRequiredHeader(CONTENT_LENGTH_HEADER),
-                ));
-            }
-        };
-
-        let expected_content = match RequestContent::try_from_header(content_length, expected_digest) {
+        let (expected_digest, expected_content) = match expected_content(req) {
             Ok(c) => c,
-            Err(e) => {
-                // Cache error data for the error catcher
-                let header = match e {
-                    ResponseError::InvalidHeader(h) => h,
-                    _ => UNKNOWN,
-                };
-                req.local_cache(|| header);
+            Err(e) => return rocket::data::Outcome::Failure(e),
+        };
 
-                return rocket::data::Outcome::Failure((Status::new(457), e));
-            }
+        let defaults = BodyLimits::default();
+        let limit = match TINY_BODY_ENDPOINTS.contains(&unversioned_path(req.uri().path().as_str())) {
+            true => check_body_limit(req, expected_content.len, TINY_BODY_LIMIT, defaults.tiny),
+            false => check_body_limit(req, expected_content.len, JSON_BODY_LIMIT, defaults.json),
         };
+        if let Err(e) = limit {
+            return rocket::data::Outcome::Failure(e);
+        }
 
         let body = match data.open(expected_content.len.into()).into_bytes().await {
             Ok(bytes) => bytes.into_inner(),
@@ -578,112 +1232,1043 @@ impl<'r, T: DeserializeOwned> FromData<'r> for LazyJson<T> {
     }
 }
 
-/// The status of the contributor related to the current round.
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub enum ContributorStatus {
-    Queue(u64, u64),
-    Round,
-    Finished,
-    Banned,
-    Other,
+/// Reads the [`ContributionFileSignature`] passed base64 encoded in the [`CONTRIBUTION_SIGNATURE_HEADER`].
+fn contribution_signature(
+    req: &Request<'_>,
+) -> std::result::Result<ContributionFileSignature, (Status, ResponseError)> {
+    match req
+        .headers()
+        .get_one(CONTRIBUTION_SIGNATURE_HEADER)
+        .and_then(|h| base64::decode(h).ok())
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+    {
+        Some(signature) => Ok(signature),
+        None => {
+            // Cache error data for the error catcher
+            req.local_cache(|| CONTRIBUTION_SIGNATURE_HEADER);
+
+            Err((Status::new(457), ResponseError::InvalidHeader(CONTRIBUTION_SIGNATURE_HEADER)))
+        }
+    }
 }
 
-/// Request to post a [Chunk](`crate::objects::Chunk`).
-#[derive(Clone, Deserialize, Serialize)]
-pub struct PostChunkRequest {
-    pub round_height: u64,
-    pub contribution_locator: ContributionLocator,
-    pub contribution_signature_locator: ContributionSignatureLocator,
+/// A contribution uploaded as the raw body of the request, together with its [`ContributionFileSignature`] passed
+/// base64 encoded in the [`CONTRIBUTION_SIGNATURE_HEADER`]. The body is streamed to a temporary file while computing
+/// its digest, so that the contribution is never buffered in memory.
+pub struct ContributionUpload {
+    pub file: NamedTempFile,
+    pub signature: ContributionFileSignature,
+    /// Compression of the uploaded file, given by the [`CONTENT_ENCODING_HEADER`]
+    pub encoding: Option<ContentEncoding>,
 }
 
-impl PostChunkRequest {
-    pub fn new(
-        round_height: u64,
-        contribution_locator: ContributionLocator,
-        contribution_signature_locator: ContributionSignatureLocator,
-    ) -> Self {
-        Self {
-            round_height,
-            contribution_locator,
-            contribution_signature_locator,
+/// Size of the buffer used to stream the uploaded contributions to disk.
+const UPLOAD_BUFFER_SIZE: usize = 1 << 16;
+
+impl ContributionUpload {
+    /// Streams the body to a temporary file, returning the file and the base64 encoded sha256 digest of the body.
+    async fn stream_to_file(data: Data<'_>, limit: usize) -> std::io::Result<(NamedTempFile, String)> {
+        let tmp_file = NamedTempFile::new()?;
+        let mut file = File::from_std(tmp_file.reopen()?);
+        let mut stream = data.open(limit.into());
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0; UPLOAD_BUFFER_SIZE];
+
+        loop {
+            let read = stream.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            file.write_all(&buffer[..read]).await?;
         }
+        file.flush().await?;
+
+        Ok((tmp_file, base64::encode(hasher.finalize())))
     }
 }
 
-/// Checks the validity of the token for the ceremony.
-/// Returns the current cohort index
-pub(crate) async fn token_check(coordinator: Coordinator, token: &str) -> Result<u64> {
-    // Check that token is not in use nor blacklisted (only if env is set)
-    let read_lock = coordinator.read().await;
+#[rocket::async_trait]
+impl<'r> FromData<'r> for ContributionUpload {
+    type Error = ResponseError;
 
-    // Check that token is not in use nor blacklisted (only if env is set)
-    if *TOKEN_BLACKLIST {
-        if read_lock.state().is_token_in_use(token) {
-            return Err(ResponseError::TokenAlreadyInUse);
-        }
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> rocket::data::Outcome<'r, Self> {
+        let (expected_digest, expected_content) = match expected_content(req) {
+            Ok(c) => c,
+            Err(e) => return rocket::data::Outcome::Failure(e),
+        };
 
-        if read_lock.state().is_token_blacklisted(token) {
-            return Err(ResponseError::BlacklistedToken);
+        let contribution_limit = BodyLimits::default().contribution;
+        if let Err(e) = check_body_limit(req, expected_content.len, CONTRIBUTION_BODY_LIMIT, contribution_limit) {
+            return rocket::data::Outcome::Failure(e);
         }
-    }
 
-    // Check that the token is correct for the current cohort number
-    let cohort = read_lock.state().get_current_cohort_index();
-    let tokens = match read_lock.state().tokens(cohort) {
-        Some(t) => t,
-        None => return Err(ResponseError::CeremonyIsOver),
-    };
+        let signature = match contribution_signature(req) {
+            Ok(signature) => signature,
+            Err(e) => return rocket::data::Outcome::Failure(e),
+        };
 
-    if !tokens.contains(token) {
-        return Err(ResponseError::InvalidToken(cohort + 1));
-    }
+        let encoding = match req.headers().get_one(CONTENT_ENCODING_HEADER) {
+            None | Some("identity") => None,
+            Some(value) => match value.parse() {
+                Ok(encoding) => Some(encoding),
+                Err(_) => {
+                    // Cache error data for the error catcher
+                    req.local_cache(|| CONTENT_ENCODING_HEADER);
+
+                    return rocket::data::Outcome::Failure((
+                        Status::new(457),
+                        ResponseError::InvalidHeader(CONTENT_ENCODING_HEADER),
+                    ));
+                }
+            },
+        };
 
-    Ok((cohort + 1) as u64)
-}
+        let (file, digest) = match Self::stream_to_file(data, expected_content.len).await {
+            Ok(f) => f,
+            Err(e) => {
+                // Cache error data for the error catcher
+                req.local_cache(|| e.to_string());
 
-/// Performs the verification of the pending contributions
-///
-/// # Cancel safety
-///
-/// https://docs.rs/tokio/latest/tokio/macro.select.html#cancellation-safety
-///
+                return rocket::data::Outcome::Failure((Status::new(512), ResponseError::IoError(e.to_string())));
+            }
+        };
+
+        // The temporary file is removed on drop in case of mismatch
+        if digest != expected_content.digest {
+            // Cache error data for the error catcher
+            req.local_cache(|| (expected_digest.to_owned(), digest.clone()));
+
+            return rocket::data::Outcome::Failure((
+                Status::new(456),
+                ResponseError::MismatchingChecksum(expected_digest.to_owned(), digest),
+            ));
+        }
+
+        rocket::data::Outcome::Success(Self {
+            file,
+            signature,
+            encoding,
+        })
+    }
+}
+
+/// A snapshot of the coordinator uploaded as the raw body of the request, streamed to a temporary file once checked
+/// against the signed digest.
+pub struct SnapshotUpload {
+    pub file: NamedTempFile,
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for SnapshotUpload {
+    type Error = ResponseError;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> rocket::data::Outcome<'r, Self> {
+        let (expected_digest, expected_content) = match expected_content(req) {
+            Ok(c) => c,
+            Err(e) => return rocket::data::Outcome::Failure(e),
+        };
+
+        if expected_content.len as u64 > SNAPSHOT_SIZE_LIMIT {
+            let error_msg = String::from("The snapshot exceeds the size limit");
+            // Cache error data for the error catcher
+            req.local_cache(|| error_msg.clone());
+
+            return rocket::data::Outcome::Failure((Status::new(512), ResponseError::IoError(error_msg)));
+        }
+
+        let (file, digest) = match ContributionUpload::stream_to_file(data, expected_content.len).await {
+            Ok(f) => f,
+            Err(e) => {
+                // Cache error data for the error catcher
+                req.local_cache(|| e.to_string());
+
+                return rocket::data::Outcome::Failure((Status::new(512), ResponseError::IoError(e.to_string())));
+            }
+        };
+
+        if digest != expected_content.digest {
+            // Cache error data for the error catcher
+            req.local_cache(|| (expected_digest.to_owned(), digest.clone()));
+
+            return rocket::data::Outcome::Failure((
+                Status::new(456),
+                ResponseError::MismatchingChecksum(expected_digest.to_owned(), digest),
+            ));
+        }
+
+        rocket::data::Outcome::Success(Self { file })
+    }
+}
+
+/// A part of a contribution uploaded as the raw body of the request, at the byte range given by the
+/// [`CONTENT_RANGE_HEADER`] as `bytes <start>-<end>/<total>`. Parts are appended in order to the upload session of the
+/// contributor, so that an interrupted upload resumes from the last received byte instead of restarting from zero.
+pub struct ContributionPart {
+    pub start: u64,
+    pub total: u64,
+    pub file: NamedTempFile,
+}
+
+impl ContributionPart {
+    /// Parses the `start`, `end` (inclusive) and `total` of a [`CONTENT_RANGE_HEADER`].
+    fn parse_range(header: &str) -> Option<(u64, u64, u64)> {
+        let (range, total) = header.strip_prefix("bytes ")?.split_once('/')?;
+        let (start, end) = range.split_once('-')?;
+        let (start, end, total) = (start.parse().ok()?, end.parse().ok()?, total.parse().ok()?);
+
+        if start <= end && end < total {
+            Some((start, end, total))
+        } else {
+            None
+        }
+    }
+
+    /// Appends the part to the file of the upload session, if it starts right after the bytes already received. The
+    /// part is written at its start rather than at the end of the file, for a part sent twice at the same time to be
+    /// written twice at the same place.
+    pub(crate) fn append_to(&self, session_path: &Path, expected_total: u64) -> Result<UploadSession> {
+        let session = upload_session(session_path, expected_total);
+        if self.total != expected_total || self.start != session.received {
+            return Err(ResponseError::InvalidUploadRange(session.received));
+        }
+
+        let append = || -> std::io::Result<u64> {
+            let mut session_file = std::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .open(session_path)?;
+            session_file.seek(SeekFrom::Start(self.start))?;
+            std::io::copy(&mut self.file.reopen()?, &mut session_file)
+        };
+        let written = append().map_err(|e| ResponseError::IoError(e.to_string()))?;
+
+        Ok(UploadSession {
+            received: self.start + written,
+            total: expected_total,
+        })
+    }
+}
+
+/// Returns the progress of the upload session assembled at the given path.
+pub(crate) fn upload_session(session_path: &Path, total: u64) -> UploadSession {
+    UploadSession {
+        received: std::fs::metadata(session_path).map_or(0, |metadata| metadata.len()),
+        total,
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for ContributionPart {
+    type Error = ResponseError;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> rocket::data::Outcome<'r, Self> {
+        let (expected_digest, expected_content) = match expected_content(req) {
+            Ok(c) => c,
+            Err(e) => return rocket::data::Outcome::Failure(e),
+        };
+
+        let contribution_limit = BodyLimits::default().contribution;
+        if let Err(e) = check_body_limit(req, expected_content.len, CONTRIBUTION_BODY_LIMIT, contribution_limit) {
+            return rocket::data::Outcome::Failure(e);
+        }
+
+        // The range must cover the whole body
+        let (start, total) = match req.headers().get_one(CONTENT_RANGE_HEADER).and_then(Self::parse_range) {
+            Some((start, end, total)) if end - start + 1 == expected_content.len as u64 => (start, total),
+            _ => {
+                // Cache error data for the error catcher
+                req.local_cache(|| CONTENT_RANGE_HEADER);
+
+                return rocket::data::Outcome::Failure((
+                    Status::new(457),
+                    ResponseError::InvalidHeader(CONTENT_RANGE_HEADER),
+                ));
+            }
+        };
+
+        let (file, digest) = match ContributionUpload::stream_to_file(data, expected_content.len).await {
+            Ok(f) => f,
+            Err(e) => {
+                // Cache error data for the error catcher
+                req.local_cache(|| e.to_string());
+
+                return rocket::data::Outcome::Failure((Status::new(512), ResponseError::IoError(e.to_string())));
+            }
+        };
+
+        if digest != expected_content.digest {
+            // Cache error data for the error catcher
+            req.local_cache(|| (expected_digest.to_owned(), digest.clone()));
+
+            return rocket::data::Outcome::Failure((
+                Status::new(456),
+                ResponseError::MismatchingChecksum(expected_digest.to_owned(), digest),
+            ));
+        }
+
+        rocket::data::Outcome::Success(Self { start, total, file })
+    }
+}
+
+/// The [`ContributionFileSignature`] passed in the [`CONTRIBUTION_SIGNATURE_HEADER`] to complete a resumable upload.
+pub struct ContributionSignatureHeader(pub ContributionFileSignature);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ContributionSignatureHeader {
+    type Error = ResponseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match contribution_signature(request) {
+            Ok(signature) => Outcome::Success(Self(signature)),
+            Err(e) => Outcome::Failure(e),
+        }
+    }
+}
+
+/// A challenge streamed from disk as a binary response. The length of a file served as it's stored is sent in the
+/// `Content-Length` header, a decoded one being streamed in chunks. Its [`hex`] encoded Blake2b-512 hash is sent in
+/// the [`CHALLENGE_HASH_HEADER`], so that the client can check the download.
+/// When the challenge is compressed, the encoding is sent in the [`CONTENT_ENCODING_HEADER`] and the hash refers
+/// to the decompressed challenge. If the coordinator signs the challenges, the signature of the
+/// [`challenge_message`] is sent in the [`CHALLENGE_SIGNATURE_HEADER`].
+pub struct ChallengeStream {
+    pub file: ServedFile,
+    pub hash: String,
+    pub signature: Option<String>,
+    pub encoding: Option<ContentEncoding>,
+}
+
+impl<'r> Responder<'r, 'static> for ChallengeStream {
+    fn respond_to(self, _request: &'r Request<'_>) -> rocket::response::Result<'static> {
+        let mut response = Response::build();
+        response
+            .header(ContentType::Binary)
+            .header(Header::new(CHALLENGE_HASH_HEADER, self.hash))
+            .raw_header("Vary", ACCEPT_ENCODING_HEADER);
+        if let Some(signature) = self.signature {
+            response.header(Header::new(CHALLENGE_SIGNATURE_HEADER, signature));
+        }
+        if let Some(encoding) = self.encoding {
+            response.header(Header::new(CONTENT_ENCODING_HEADER, encoding.as_str()));
+        }
+
+        match self.file {
+            // The size of the body is computed by seeking the file
+            ServedFile::File(file) => response.sized_body(None, File::from_std(file)),
+            ServedFile::Decoded(reader) => response.streamed_body(blocking_stream(reader)),
+        };
+
+        response.ok()
+    }
+}
+
+/// Size of the buffer between a file decoded as it's read and the body of its response.
+const STREAM_BUFFER_SIZE: usize = 1 << 16;
+
+/// Returns the stream of the bytes read, in a blocking task, from the given reader, such as a file decrypted as it's
+/// read. The stream ends early if the reader fails, which the client detects by checking the hash of the download.
+fn blocking_stream(mut reader: Box<dyn std::io::Read + Send>) -> DuplexStream {
+    let (mut writer, stream) = rocket::tokio::io::duplex(STREAM_BUFFER_SIZE);
+    let runtime = Handle::current();
+    task::spawn_blocking(move || {
+        let mut buffer = vec![0; STREAM_BUFFER_SIZE];
+        loop {
+            let read = match std::io::Read::read(&mut reader, &mut buffer) {
+                Ok(0) => return,
+                Ok(read) => read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    warn!("Error while streaming a file: {}", e);
+                    return;
+                }
+            };
+            // The client went away
+            if runtime.block_on(writer.write_all(&buffer[..read])).is_err() {
+                return;
+            }
+        }
+    });
+
+    stream
+}
+
+/// Response to a contributor in the queue, carrying the estimated wait in seconds before its turn in the
+/// [`ESTIMATED_WAIT_HEADER`] once it can be told, the body keeping its shape for the older clients.
+pub struct EstimatedWait<R>(pub R, pub Option<u64>);
+
+impl<'r, R: Responder<'r, 'static>> Responder<'r, 'static> for EstimatedWait<R> {
+    fn respond_to(self, request: &'r Request<'_>) -> rocket::response::Result<'static> {
+        let mut response = self.0.respond_to(request)?;
+        if let Some(seconds) = self.1 {
+            response.set_header(Header::new(ESTIMATED_WAIT_HEADER, seconds.to_string()));
+        }
+
+        Ok(response)
+    }
+}
+
+/// The encoding of the response negotiated from the [`ACCEPT_ENCODING_HEADER`] of the request, if any.
+pub struct AcceptEncoding(pub Option<ContentEncoding>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AcceptEncoding {
+    type Error = ResponseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let accept_encoding = request
+            .headers()
+            .get(ACCEPT_ENCODING_HEADER)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        Outcome::Success(Self(ContentEncoding::negotiate(&accept_encoding)))
+    }
+}
+
+/// Path of the route answering the requests rejected by the [`RateLimit`] fairing.
+pub const RATE_LIMITED_PATH: &str = "/rate_limited";
+
+/// Endpoints polled by the contributors, subject to the [`RateLimit`] fairing.
+pub const RATE_LIMITED_ENDPOINTS: [&str; 3] = [
+    "/contributor/join_queue",
+    "/contributor/heartbeat",
+    "/contributor/queue_status",
+];
+
+/// Number of tracked clients above which the clients with a full bucket are forgotten.
+const MAX_RATE_LIMITED_CLIENTS: usize = 10_000;
+
+/// Client of the coordinator whose requests are limited.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum RateLimitKey {
+    Ip(IpAddr),
+    Pubkey(String),
+}
+
+/// Token bucket of a client: every request takes a token, tokens are refilled continuously.
+struct TokenBucket {
+    tokens: f64,
+    updated: std::time::Instant,
+}
+
+/// Fairing limiting the rate of the requests to the [`RATE_LIMITED_ENDPOINTS`], with a token bucket per client address
+/// and per public key. The public key of a request only counts if the request is correctly signed, so that a client
+/// can't exhaust the bucket of another participant. Rejected requests are rerouted to [`RATE_LIMITED_PATH`].
+pub struct RateLimit {
+    capacity: u32,
+    period: Duration,
+    signature_scheme: SignatureScheme,
+    trusted_proxies: Vec<IpNet>,
+    buckets: std::sync::Mutex<HashMap<RateLimitKey, TokenBucket>>,
+}
+
+impl RateLimit {
+    /// Allows bursts of `capacity` requests per client, refilled over `period`. A `capacity` of 0 disables the limit.
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        Self {
+            capacity,
+            period,
+            signature_scheme: SignatureScheme::default(),
+            trusted_proxies: vec![],
+            buckets: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Builds the fairing from the rate limit settings of the environment.
+    pub fn from_environment(environment: &Environment) -> Self {
+        let period = environment.rate_limit_period().whole_milliseconds().max(0) as u64;
+
+        Self {
+            signature_scheme: environment.signature_scheme(),
+            trusted_proxies: environment.trusted_proxies().clone(),
+            ..Self::new(environment.rate_limit_requests(), Duration::from_millis(period))
+        }
+    }
+
+    /// Takes a token from the bucket of every key, none being taken if a bucket is empty. Returns the number of
+    /// seconds after which a request will be allowed again in that case.
+    fn try_acquire(&self, keys: &[RateLimitKey]) -> std::result::Result<(), u64> {
+        let capacity = self.capacity as f64;
+        let refill_rate = capacity / self.period.as_secs_f64().max(f64::EPSILON);
+        let now = std::time::Instant::now();
+        let mut buckets = self.buckets.lock().expect("Rate limit buckets poisoned");
+
+        if buckets.len() >= MAX_RATE_LIMITED_CLIENTS {
+            let period = self.period;
+            buckets.retain(|_, bucket| now.duration_since(bucket.updated) < period);
+        }
+
+        let mut retry_after = None;
+        for key in keys {
+            let bucket = buckets.entry(key.clone()).or_insert(TokenBucket {
+                tokens: capacity,
+                updated: now,
+            });
+            let refilled = now.duration_since(bucket.updated).as_secs_f64() * refill_rate;
+            bucket.tokens = (bucket.tokens + refilled).min(capacity);
+            bucket.updated = now;
+
+            if bucket.tokens < 1.0 {
+                let wait = ((1.0 - bucket.tokens) / refill_rate).ceil() as u64;
+                retry_after = retry_after.max(Some(wait));
+            }
+        }
+        if let Some(retry_after) = retry_after {
+            return Err(retry_after);
+        }
+
+        for key in keys {
+            if let Some(bucket) = buckets.get_mut(key) {
+                bucket.tokens -= 1.0;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Retry delay of a request rejected by the [`RateLimit`] fairing.
+struct RetryAfter(Option<u64>);
+
+#[rocket::async_trait]
+impl Fairing for RateLimit {
+    fn info(&self) -> Info {
+        Info {
+            name: "Rate limit",
+            kind: Kind::Request,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        if self.capacity == 0
+            || request.method() == Method::Options
+            || !RATE_LIMITED_ENDPOINTS.contains(&unversioned_path(request.uri().path().as_str()))
+        {
+            return;
+        }
+
+        let mut keys: Vec<RateLimitKey> = client_address(request, &self.trusted_proxies)
+            .map(RateLimitKey::Ip)
+            .into_iter()
+            .collect();
+        if let Ok(headers) = SignatureHeaders::try_from(&*request) {
+            if let Ok(true) = headers.try_verify_signature(self.signature_scheme) {
+                keys.push(RateLimitKey::Pubkey(headers.pubkey.to_owned()));
+            }
+        }
+
+        if let Err(retry_after) = self.try_acquire(&keys) {
+            warn!("Rate limited request to {} from {:?}", request.uri(), keys);
+            request.local_cache(|| RetryAfter(Some(retry_after)));
+            request.set_method(Method::Get);
+            request.set_uri(Origin::parse(RATE_LIMITED_PATH).expect("Rate limited path should be a valid uri"));
+        }
+    }
+}
+
+/// Request rerouted by the [`RateLimit`] fairing, carrying the number of seconds after which it can be retried.
+/// Requests sent directly to [`RATE_LIMITED_PATH`] are forwarded.
+pub struct RateLimited(pub u64);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RateLimited {
+    type Error = ResponseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.local_cache(|| RetryAfter(None)) {
+            RetryAfter(Some(retry_after)) => Outcome::Success(Self(*retry_after)),
+            RetryAfter(None) => Outcome::Forward(()),
+        }
+    }
+}
+
+/// Path of the route answering the requests for a version of the API not served by the coordinator.
+pub const UNSUPPORTED_API_VERSION_PATH: &str = "/unsupported_api_version";
+
+/// Returns the path of the endpoint targeted by a request to `path`, either under [`API_PREFIX`] or unversioned.
+pub(crate) fn unversioned_path(path: &str) -> &str {
+    match path.strip_prefix(API_PREFIX) {
+        Some(endpoint) if endpoint.starts_with('/') => endpoint,
+        _ => path,
+    }
+}
+
+/// Whether a request targeted the unversioned paths, recorded before any rerouting.
+struct Unversioned(bool);
+
+/// Fairing negotiating the version of the REST API. The routes are mounted both under [`API_PREFIX`] and at the
+/// root, for the clients predating the versioning: the responses to the unversioned paths carry a `Deprecation`
+/// header. Requests asking for another version with the [`API_VERSION_HEADER`] are rerouted to
+/// [`UNSUPPORTED_API_VERSION_PATH`]. Every response carries the version served in the [`API_VERSION_HEADER`].
+///
+/// Must be attached before the other fairings rerouting the requests.
+pub struct ApiVersion;
+
+#[rocket::async_trait]
+impl Fairing for ApiVersion {
+    fn info(&self) -> Info {
+        Info {
+            name: "API version",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        let path = request.uri().path();
+        request.local_cache(|| Unversioned(unversioned_path(path.as_str()) == path.as_str()));
+
+        let requested = request.headers().get_one(API_VERSION_HEADER).map(str::to_owned);
+        if let Some(version) = requested {
+            if version.trim().parse::<u64>().ok() != Some(API_VERSION) {
+                warn!("Request to {} for unsupported API version {}", request.uri(), version);
+                request.local_cache(|| RequestedApiVersion(Some(version)));
+                request.set_method(Method::Get);
+                request.set_uri(
+                    Origin::parse(UNSUPPORTED_API_VERSION_PATH)
+                        .expect("Unsupported version path should be a valid uri"),
+                );
+            }
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        response.set_raw_header(API_VERSION_HEADER, API_VERSION.to_string());
+        if request.local_cache(|| Unversioned(false)).0 {
+            response.set_raw_header("Deprecation", "true");
+        }
+    }
+}
+
+/// Returns the configuration of the server: the defaults of Rocket, overridden by its environment variables, with the
+/// body limits and the TLS termination of the environment, if any. Rocket doesn't shut down on the signals, SIGTERM
+/// draining the coordinator first.
+pub fn rocket_config(environment: &Environment) -> Figment {
+    let body_limits = environment.body_limits();
+    let figment = rocket::Config::figment()
+        .merge(("shutdown.signals", Vec::<String>::new()))
+        .merge((format!("limits.{}", TINY_BODY_LIMIT), body_limits.tiny))
+        .merge((format!("limits.{}", JSON_BODY_LIMIT), body_limits.json))
+        .merge((format!("limits.{}", CONTRIBUTION_BODY_LIMIT), body_limits.contribution));
+
+    match environment.tls_paths() {
+        Some((certificate, key)) => figment.merge(("tls.certs", certificate)).merge(("tls.key", key)),
+        None => figment,
+    }
+}
+
+/// Response headers of the API readable by the browser frontends, on top of the ones safelisted by the browsers.
+const CORS_EXPOSED_HEADERS: &[&str] = &[
+    API_VERSION_HEADER,
+    CHALLENGE_HASH_HEADER,
+    CHALLENGE_SIGNATURE_HEADER,
+    CONTENT_ENCODING_HEADER,
+    CONTENT_RANGE_HEADER,
+    ESTIMATED_WAIT_HEADER,
+    REQUEST_ID_HEADER,
+    "Deprecation",
+    "Retry-After",
+];
+
+/// Number of seconds during which the browsers can cache the answer to a preflight request.
+const CORS_MAX_AGE: u64 = 3600;
+
+/// Fairing applying the cross-origin resource sharing policy of the REST API, for the browser frontends served from
+/// the allowed origins to call it directly. The preflight requests of these origins are answered with a `204 No
+/// Content` allowing the requested headers, the responses to the other origins are left untouched.
+pub struct Cors {
+    allowed_origins: Vec<String>,
+}
+
+impl Cors {
+    /// Allows the given origins, `*` allowing any of them. No origin is allowed if the list is empty.
+    pub fn new(allowed_origins: &[String]) -> Self {
+        Self {
+            allowed_origins: allowed_origins.to_vec(),
+        }
+    }
+
+    /// Builds the fairing from the origins allowed by the environment.
+    pub fn from_environment(environment: &Environment) -> Self {
+        Self::new(environment.cors_allowed_origins())
+    }
+
+    fn allows(&self, origin: &str) -> bool {
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed == origin)
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Cors {
+    fn info(&self) -> Info {
+        Info {
+            name: "CORS",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let origin = match request.headers().get_one("Origin") {
+            Some(origin) if self.allows(origin) => origin.to_owned(),
+            _ => return,
+        };
+        response.set_raw_header("Access-Control-Allow-Origin", origin);
+        response.adjoin_raw_header("Vary", "Origin");
+
+        // No route answers the OPTIONS requests: the preflight ones end up in the not found catcher
+        if request.method() == Method::Options && request.headers().contains("Access-Control-Request-Method") {
+            response.set_status(Status::NoContent);
+            response.remove_header("Content-Type");
+            response.set_sized_body(0, Cursor::new(""));
+            response.set_raw_header("Access-Control-Allow-Methods", "GET, POST");
+            if let Some(headers) = request.headers().get_one("Access-Control-Request-Headers") {
+                response.set_raw_header("Access-Control-Allow-Headers", headers.to_owned());
+            }
+            response.set_raw_header("Access-Control-Max-Age", CORS_MAX_AGE.to_string());
+        } else {
+            response.set_raw_header("Access-Control-Expose-Headers", CORS_EXPOSED_HEADERS.join(", "));
+        }
+    }
+}
+
+/// Handler running the handler of a route within a `request` span, which records the method, the path and the route
+/// of the request, then the status of the response, and is timed by the span events of the subscriber.
+#[derive(Clone)]
+struct TracedHandler(Box<dyn Handler>);
+
+#[rocket::async_trait]
+impl Handler for TracedHandler {
+    async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> route::Outcome<'r> {
+        let request_id = RequestId::of(request).to_string();
+        let span = tracing::info_span!(
+            "request",
+            %request_id,
+            method = %request.method(),
+            path = %request.uri().path(),
+            route = request.route().and_then(|route| route.name.as_deref()).unwrap_or_default(),
+            status = tracing::field::Empty,
+        );
+        let handler = self.0.handle(request, data).instrument(span.clone());
+        let mut outcome = audit::REQUEST_ID.scope(request_id.clone(), handler).await;
+
+        let status = match &mut outcome {
+            route::Outcome::Success(response) => {
+                response.set_raw_header(REQUEST_ID_HEADER, request_id);
+                Some(response.status())
+            }
+            route::Outcome::Failure(status) => Some(*status),
+            route::Outcome::Forward(_) => None,
+        };
+        if let Some(status) = status {
+            span.record("status", &status.code);
+        }
+
+        outcome
+    }
+}
+
+/// Wraps the handlers of the routes in a [`TracedHandler`], for each request to get its own span and id.
+pub fn trace_routes(routes: Vec<Route>) -> Vec<Route> {
+    routes
+        .into_iter()
+        .map(|mut route| {
+            route.handler = Box::new(TracedHandler(route.handler));
+            route
+        })
+        .collect()
+}
+
+/// Runs the blocking closure like [`task::spawn_blocking`], within the current span and with the current request id
+/// so that the events and the audit records of the coordinator are attributed to the request being served.
+pub fn spawn_blocking<F, R>(f: F) -> task::JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let span = tracing::Span::current();
+    let request_id = audit::current_request_id();
+    task::spawn_blocking(move || match request_id {
+        Some(request_id) => audit::REQUEST_ID.sync_scope(request_id, || span.in_scope(f)),
+        None => span.in_scope(f),
+    })
+}
+
+/// Id of a request, given by the client in the [`REQUEST_ID_HEADER`] or generated, returned in the responses.
+struct RequestId(String);
+
+impl RequestId {
+    /// Returns the id of the request, cached for all its handlers and catchers to share it.
+    fn of<'r>(request: &'r Request<'_>) -> &'r str {
+        &request
+            .local_cache(|| match request.headers().get_one(REQUEST_ID_HEADER) {
+                Some(id) if is_valid_request_id(id) => Self(id.to_string()),
+                _ => {
+                    let mut bytes = [0u8; 16];
+                    rand::thread_rng().fill_bytes(&mut bytes);
+                    Self(hex::encode(bytes))
+                }
+            })
+            .0
+    }
+}
+
+/// Accepts the ids given by the clients made of at most [`MAX_REQUEST_ID_LEN`] alphanumeric characters, dashes or
+/// underscores, for them to be safely logged.
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || "-_".contains(c))
+}
+
+/// Version of the API requested by a request rerouted by the [`ApiVersion`] fairing.
+struct RequestedApiVersion(Option<String>);
+
+/// Request rerouted by the [`ApiVersion`] fairing, carrying the requested version. Requests sent directly to
+/// [`UNSUPPORTED_API_VERSION_PATH`] are forwarded.
+pub struct UnsupportedApiVersion(pub String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for UnsupportedApiVersion {
+    type Error = ResponseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.local_cache(|| RequestedApiVersion(None)) {
+            RequestedApiVersion(Some(version)) => Outcome::Success(Self(version.clone())),
+            RequestedApiVersion(None) => Outcome::Forward(()),
+        }
+    }
+}
+
+/// Attestation of a contribution: either the url of a public post or a signed free-text statement.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum AttestationRequest {
+    Url(String),
+    Statement(AttestationStatement),
+}
+
+/// Request to ban, or unban, the public key of a contributor and/or an IP.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct BanRequest {
+    #[serde(default)]
+    pub pubkey: Option<String>,
+    #[serde(default)]
+    pub ip: Option<IpAddr>,
+}
+
+/// Request to set the reliability score of a contributor, which orders the queue.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ParticipantPriorityRequest {
+    pub pubkey: String,
+    pub reliability: u8,
+}
+
+/// Request to skip the contributors of the current round, banning them or setting their reliability score if asked to.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct SkipCurrentRequest {
+    #[serde(default)]
+    pub ban: bool,
+    #[serde(default)]
+    pub reliability: Option<u8>,
+}
+
+/// The persisted ban list of the ceremony.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BanList {
+    pub pubkeys: Vec<String>,
+    pub ips: Vec<IpAddr>,
+}
+
+/// Request to mint new [InviteCode](`crate::coordinator_state::InviteCode`)s. `expires_in` is expressed in seconds.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct MintInviteCodesRequest {
+    pub count: usize,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+}
+
+/// Request to issue new [PriorityToken](`crate::authentication::PriorityToken`)s granting the given reliability score
+/// in the queue. `not_before`, the time of the request by default, and `expires_at` are unix timestamps in seconds.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IssuePriorityTokensRequest {
+    pub count: usize,
+    pub reliability: u8,
+    #[serde(default)]
+    pub not_before: Option<i64>,
+    pub expires_at: i64,
+}
+
+/// Escapes the characters of `text` with a special meaning in XML.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Renders the Atom feed of the verified contributions, the fields of the contributors being escaped.
+pub(crate) fn atom_feed(contributions: &[TrimmedContributionInfo]) -> String {
+    let updated = contributions
+        .iter()
+        .map(|c| c.end_contribution())
+        .max()
+        .unwrap_or_else(chrono::Utc::now);
+
+    let entries: String = contributions
+        .iter()
+        .map(|c| {
+            format!(
+                r#"
+  <entry>
+    <id>urn:namada-trusted-setup:round:{round}:chunk:0</id>
+    <title>Round {round}, chunk 0: contribution of {fingerprint}</title>
+    <updated>{updated}</updated>
+    <author><name>{fingerprint}</name></author>
+    <summary>Contributor {fingerprint} contributed to round {round} (chunk 0) with response hash {hash}</summary>
+  </entry>"#,
+                round = c.ceremony_round(),
+                fingerprint = xml_escape(c.fingerprint()),
+                updated = c.end_contribution().to_rfc3339(),
+                hash = xml_escape(c.contribution_hash())
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>urn:namada-trusted-setup:contributions</id>
+  <title>Namada Trusted Setup - Verified contributions</title>
+  <updated>{}</updated>{}
+</feed>
+"#,
+        updated.to_rfc3339(),
+        entries
+    )
+}
+
+/// Checks the validity of the token for the ceremony.
+/// Returns the current cohort index
+pub(crate) async fn token_check(coordinator: Coordinator, token: &str) -> Result<u64> {
+    // Check that token is not in use nor blacklisted (only if env is set)
+    let read_lock = coordinator.read().await;
+
+    // Check that token is not in use nor blacklisted (only if env is set)
+    if *TOKEN_BLACKLIST {
+        if read_lock.state().is_token_in_use(token) {
+            return Err(ResponseError::TokenAlreadyInUse);
+        }
+
+        if read_lock.state().is_token_blacklisted(token) {
+            return Err(ResponseError::BlacklistedToken);
+        }
+    }
+
+    // Check that the token is correct for the current cohort number
+    let cohort = read_lock.state().get_current_cohort_index();
+    let tokens = match read_lock.state().tokens(cohort) {
+        Some(t) => t,
+        None => return Err(ResponseError::CeremonyIsOver),
+    };
+
+    if !tokens.contains(token) {
+        return Err(ResponseError::InvalidToken(cohort + 1));
+    }
+
+    Ok((cohort + 1) as u64)
+}
+
+/// Performs the verification of the pending contributions
+///
+/// # Cancel safety
+///
+/// https://docs.rs/tokio/latest/tokio/macro.select.html#cancellation-safety
+///
 /// Because of the use of [`tokio::sync::rwlock::RwLock::write_owned`], which is not cancel safe, and a spawned blocking
 /// task, which cannot be cancelled, this function is not cancel safe.
-pub async fn perform_verify_chunks(coordinator: Coordinator, s3_ctx: &S3Ctx) -> Result<()> {
+pub async fn perform_verify_chunks(coordinator: Coordinator, object_store: &ObjectStore) -> Result<()> {
     // Get all the pending verifications, loop on each one of them and perform verification
     // Technically, since we don't chunk contributions and we only have one contribution per round, we will always get
     // one pending verification at max.
     let mut write_lock = coordinator.write_owned().await;
 
-    // NOTE: we are going to rely on the single default verifier built in the coordinator itself,
-    //  no external verifiers
+    // The default verifier built in the coordinator verifies every contribution, once the external verifiers, if any,
+    // agreed on it
     let contributions_info = task::spawn_blocking(move || -> Result<Vec<u8>> {
+        let quorum = write_lock.environment().verification_quorum();
+        let mut verifications = vec![];
+        let mut tasks = vec![];
+        let mut approved = vec![];
         for (task, _) in write_lock.get_pending_verifications().to_owned() {
-            if let Err(e) = write_lock.default_verify(&task) {
-                warn!("Error while verifying a contribution: {}. Restarting the round...", e);
-                // FIXME: the verify_masp function may panic but the program doesn't shut down because we are executing it on a separate thread. It would be better though to make that function return a Result instead of panicking. Revert of round should be moved inside default_verify
-
-                // Get the participant who produced the contribution
-                let finished_contributor = write_lock
-                    .state()
-                    .current_round_finished_contributors()
-                    .unwrap()
-                    .first()
-                    .unwrap()
-                    .clone();
-
-                // Reset the round to prevent a coordinator stall (the corrupted contribution is not automatically dropped)
-                write_lock
-                    .reset_round()
-                    .map_err(|e| ResponseError::CoordinatorError(e))?;
-
-                // Ban the participant who produced the invalid contribution. Must be banned after the reset beacuse one can't ban a finished contributor
-                write_lock
-                    .ban_participant(&finished_contributor)
-                    .map_err(|e| ResponseError::CoordinatorError(e))?;
+            match write_lock.external_verification_status(&task) {
+                _ if quorum == 0 => tasks.push(task),
+                ExternalVerificationStatus::Pending => continue,
+                ExternalVerificationStatus::Rejected => verifications.push((
+                    RejectionReason::RejectedByVerifiers,
+                    Err(anyhow!("the external verifiers rejected {}", task)),
+                )),
+                status => {
+                    approved.push(status == ExternalVerificationStatus::Approved);
+                    tasks.push(task)
+                }
             }
         }
 
+        // The chunks are independent, so their contributions are verified in parallel
+        for (i, verification) in write_lock.default_verify_all(&tasks).into_iter().enumerate() {
+            // A failure of the storage says nothing of the contribution, which stays pending for the next verification
+            match &verification {
+                Err(e) if e.is::<StorageFailure>() => {
+                    warn!("Retrying the verification of {} later: {}", tasks[i], e);
+                    continue;
+                }
+                _ => (),
+            }
+            if verification.is_err() && approved.get(i).copied().unwrap_or(false) {
+                warn!("The external verifiers approved {} which failed the verification", tasks[i]);
+            }
+            verifications.push((RejectionReason::VerificationFailed, verification));
+        }
+
+        // A single reset of the round drops the other pending contributions as well
+        if let Some((reason, Err(e))) = verifications.into_iter().find(|(_, result)| result.is_err()) {
+            warn!("Error while verifying a contribution: {}. Restarting the round...", e);
+            // FIXME: the verify_masp function may panic but the program doesn't shut down because we are executing it on a separate thread. It would be better though to make that function return a Result instead of panicking. Revert of round should be moved inside default_verify
+
+            // Get the participant who produced the contribution
+            let finished_contributor = write_lock
+                .state()
+                .current_round_finished_contributors()
+                .unwrap()
+                .first()
+                .unwrap()
+                .clone();
+            let round_height = write_lock
+                .current_round_height()
+                .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+            // Reset the round to prevent a coordinator stall (the corrupted contribution is not automatically dropped)
+            write_lock
+                .reset_round()
+                .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+            // Ban the participant who produced the invalid contribution. Must be banned after the reset beacuse one can't ban a finished contributor
+            write_lock
+                .reject_contribution(&finished_contributor, round_height, reason, e.to_string())
+                .map_err(|e| ResponseError::CoordinatorError(e))?;
+            write_lock
+                .ban_participant(&finished_contributor)
+                .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+            // Issue the challenge to the next contributor in the queue right away
+            write_lock.update().map_err(|e| ResponseError::CoordinatorError(e))?;
+        }
+
         write_lock
             .storage()
             .get_contributions_summary()
@@ -691,13 +2276,426 @@ pub async fn perform_verify_chunks(coordinator: Coordinator, s3_ctx: &S3Ctx) ->
     })
     .await??;
 
-    // Upload json file to S3
-    s3_ctx
+    // Upload json file to the object storage
+    object_store
         .upload_contributions_info(contributions_info)
         .await
         .map_err(|e| ResponseError::CoordinatorError(CoordinatorError::Error(anyhow!(e.to_string()))))
 }
 
+/// Progress of the background verification of the contributions, as reported by `GET /verify/status`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct VerificationStatus {
+    /// A verification has been requested and the worker hasn't started it yet
+    pub queued: bool,
+    /// The worker is verifying the pending contributions
+    pub running: bool,
+    /// Number of contributions waiting to be verified, as of the last start or end of a verification
+    pub pending_verifications: u64,
+    pub last_started: Option<DateTime<Utc>>,
+    pub last_completed: Option<DateTime<Utc>>,
+    /// Error of the last verification, if it failed
+    pub last_error: Option<String>,
+}
+
+/// Queue of the verification requests served by the background verification worker. The worker also runs
+/// periodically, so a request only anticipates the next verification: requests received while another one is
+/// still queued are merged.
+#[derive(Clone)]
+pub struct VerificationQueue {
+    sender: mpsc::Sender<()>,
+    status: Arc<RwLock<VerificationStatus>>,
+}
+
+impl VerificationQueue {
+    /// Returns the queue together with the receiving end of the requests, to be passed to the worker.
+    pub fn new() -> (Self, mpsc::Receiver<()>) {
+        let (sender, receiver) = mpsc::channel(1);
+
+        (
+            Self {
+                sender,
+                status: Default::default(),
+            },
+            receiver,
+        )
+    }
+
+    /// Asks the worker to verify the pending contributions, without waiting for the verification to happen.
+    pub async fn request(&self) -> Result<VerificationStatus> {
+        let mut status = self.status.write().await;
+
+        match self.sender.try_send(()) {
+            // A full channel means that a verification is already queued
+            Ok(()) | Err(TrySendError::Full(())) => {
+                status.queued = true;
+                Ok(status.clone())
+            }
+            Err(TrySendError::Closed(())) => Err(ResponseError::ShutdownError(String::from(
+                "verification worker is not running",
+            ))),
+        }
+    }
+
+    /// Returns the progress of the verification.
+    pub async fn status(&self) -> VerificationStatus {
+        self.status.read().await.clone()
+    }
+
+    /// Returns `true` if the worker is still running, holding the receiving end of the requests.
+    pub fn worker_alive(&self) -> bool {
+        !self.sender.is_closed()
+    }
+
+    /// Verifies the pending contributions, keeping track of the progress. The queued requests are served by this
+    /// verification and removed from the `requests` of the worker.
+    pub async fn run(
+        &self,
+        coordinator: Coordinator,
+        requests: &mut mpsc::Receiver<()>,
+        object_store: &ObjectStore,
+    ) -> Result<()> {
+        let pending_verifications = coordinator.read().await.get_pending_verifications().len() as u64;
+        {
+            let mut status = self.status.write().await;
+            while requests.try_recv().is_ok() {}
+            status.queued = false;
+            status.running = true;
+            status.pending_verifications = pending_verifications;
+            status.last_started = Some(Utc::now());
+        }
+
+        let result = perform_verify_chunks(coordinator.clone(), object_store).await;
+
+        let pending_verifications = coordinator.read().await.get_pending_verifications().len() as u64;
+        let mut status = self.status.write().await;
+        status.running = false;
+        status.pending_verifications = pending_verifications;
+        status.last_completed = Some(Utc::now());
+        status.last_error = result.as_ref().err().map(|e| e.to_string());
+
+        result
+    }
+}
+
+/// The rounds of the transcript completed before the current one, as served by `GET /transcript`. The contributions
+/// of a completed round no longer change, so they are only read from the storage once.
+#[derive(Clone, Default)]
+pub struct TranscriptCache(Arc<std::sync::Mutex<Vec<TranscriptRound>>>);
+
+impl TranscriptCache {
+    /// Returns the completed rounds, to be passed to [`crate::Coordinator::export_transcript_cached`].
+    pub fn rounds(&self) -> std::sync::MutexGuard<'_, Vec<TranscriptRound>> {
+        // A poisoned cache is still consistent, the rounds are only pushed once read in full
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Readiness of the coordinator to serve the contributors, as reported by `GET /readyz`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Readiness {
+    pub storage_reachable: bool,
+    pub round_loaded: bool,
+    pub verification_worker_alive: bool,
+    /// The coordinator is draining before a shutdown, see `GET /stop`. It stays ready meanwhile, for the contribution
+    /// in flight to be completed.
+    pub draining: bool,
+}
+
+impl Readiness {
+    pub fn is_ready(&self) -> bool {
+        self.storage_reachable && self.round_loaded && self.verification_worker_alive
+    }
+}
+
+/// Progress of the replication of the storage, as reported by `GET /replication/status`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ReplicationStatus {
+    /// A replica is configured with `NAMADA_MPC_REPLICA_PATH`
+    pub enabled: bool,
+    /// The worker is updating the replica
+    pub running: bool,
+    /// Number of files found missing or outdated in the replica by the last replication
+    pub pending_files: u64,
+    /// Number of files held by the replica
+    pub replicated_files: u64,
+    pub last_started: Option<DateTime<Utc>>,
+    pub last_completed: Option<DateTime<Utc>>,
+    /// Start of the last successful replication, before which every accepted contribution is in the replica
+    pub synced_at: Option<DateTime<Utc>>,
+    /// Seconds elapsed since `synced_at`, the longest a contribution accepted since may have waited to be replicated
+    pub lag_seconds: Option<i64>,
+    /// Error of the last replication, if it failed
+    pub last_error: Option<String>,
+}
+
+/// The replica of the storage kept up to date by the background replication worker, in a directory on another
+/// disk or host, for the contributions and the state of the ceremony to outlive the loss of the storage.
+#[derive(Clone)]
+pub struct Replica {
+    directory: Option<PathBuf>,
+    status: Arc<RwLock<ReplicationStatus>>,
+    /// Held by the running replication, for the final one on shutdown to wait for the periodic one
+    run_lock: Arc<Mutex<()>>,
+}
+
+impl Replica {
+    /// Returns the replica in the given directory, disabled if `None`.
+    pub fn new(directory: Option<PathBuf>) -> Self {
+        let status = ReplicationStatus {
+            enabled: directory.is_some(),
+            ..Default::default()
+        };
+
+        Self {
+            directory,
+            status: Arc::new(RwLock::new(status)),
+            run_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Returns the progress of the replication, with its current lag.
+    pub async fn status(&self) -> ReplicationStatus {
+        let mut status = self.status.read().await.clone();
+        status.lag_seconds = status.synced_at.map(|synced_at| (Utc::now() - synced_at).num_seconds());
+
+        status
+    }
+
+    /// Copies to the replica the contributions accepted and the state changed since the last replication, keeping
+    /// track of the progress. Does nothing if the replica is disabled. The files are collected under the read lock of
+    /// the coordinator, and copied once it's released.
+    pub async fn run(&self, coordinator: Coordinator) -> Result<()> {
+        let directory = match &self.directory {
+            Some(directory) => directory.clone(),
+            None => return Ok(()),
+        };
+        let _running = self.run_lock.lock().await;
+        let started = Utc::now();
+        {
+            let mut status = self.status.write().await;
+            status.running = true;
+            status.last_started = Some(started);
+        }
+
+        let read_lock = coordinator.read_owned().await;
+        let result = task::spawn_blocking(move || {
+            let pending = read_lock.replicate(&directory)?;
+            drop(read_lock);
+            pending.run()
+        })
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e));
+
+        let mut status = self.status.write().await;
+        status.running = false;
+        status.last_completed = Some(Utc::now());
+        match &result {
+            Ok(report) => {
+                status.pending_files = report.pending_files;
+                status.replicated_files = report.total_files;
+                status.synced_at = Some(started);
+                status.last_error = None;
+            }
+            Err(e) => status.last_error = Some(e.to_string()),
+        }
+
+        result.map(|_| ())
+    }
+}
+
+/// Fetches the public attestations that haven't been verified yet and marks as verified those containing the
+/// canonical attestation message signed by the contributor. Attestations which can't be fetched or don't match
+/// are skipped and retried at the next call.
+///
+/// # Cancel safety
+///
+/// https://docs.rs/tokio/latest/tokio/macro.select.html#cancellation-safety
+///
+/// Because of the use of [`tokio::sync::rwlock::RwLock::write_owned`], which is not cancel safe, and a spawned blocking
+/// task, which cannot be cancelled, this function is not cancel safe.
+pub async fn perform_verify_attestations(coordinator: Coordinator) -> Result<()> {
+    let read_lock = coordinator.clone().read_owned().await;
+    let pending = task::spawn_blocking(move || read_lock.get_unverified_attestations())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    // Fetch the attestations without holding the lock on the coordinator
+    let mut verified_rounds = Vec::new();
+    for info in pending {
+        let url = match &info.attestation {
+            Some(url) => url,
+            None => continue,
+        };
+
+        let content = match fetch_public(url, ATTESTATION_SIZE_LIMIT).await {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Error while fetching attestation at {}: {}", url, e);
+                continue;
+            }
+        };
+
+        if info.verify_attestation_content(&content) {
+            verified_rounds.push(info.ceremony_round);
+        } else {
+            warn!(
+                "Attestation at {} doesn't contain the signed canonical message for round {}",
+                url, info.ceremony_round
+            );
+        }
+    }
+
+    if verified_rounds.is_empty() {
+        return Ok(());
+    }
+
+    let mut write_lock = coordinator.write_owned().await;
+
+    task::spawn_blocking(move || -> std::result::Result<(), CoordinatorError> {
+        for round in verified_rounds {
+            write_lock.set_contribution_attestation_verified(round)?;
+        }
+
+        Ok(())
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Returns `true` if the address is routable on the public internet.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || first == 0
+                || (first == 100 && second & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4() {
+            Some(ip) => is_public_ip(IpAddr::V4(ip)),
+            None => {
+                let segment = ip.segments()[0];
+                !(ip.is_multicast() || segment & 0xfe00 == 0xfc00 || segment & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
+
+/// Downloads the document published by a contributor at `url`, within the [`PUBLIC_FETCH_TIMEOUT`] and `size_limit`.
+/// The host must resolve to public addresses only, for the contributors not to reach the network of the coordinator,
+/// and redirections aren't followed.
+pub(crate) async fn fetch_public(url: &str, size_limit: usize) -> anyhow::Result<String> {
+    let url = reqwest::Url::parse(url)?;
+    let host = url.host_str().ok_or_else(|| anyhow!("missing host"))?.to_owned();
+    let port = url.port_or_known_default().ok_or_else(|| anyhow!("unknown port"))?;
+    let addresses: Vec<SocketAddr> = rocket::tokio::net::lookup_host((host.as_str(), port)).await?.collect();
+    match addresses.first() {
+        Some(address) if addresses.iter().all(|a| is_public_ip(a.ip())) => {
+            // The request is pinned to the checked address, which the host can't change in the meantime
+            let client = reqwest::Client::builder()
+                .timeout(PUBLIC_FETCH_TIMEOUT)
+                .redirect(reqwest::redirect::Policy::none())
+                .resolve(&host, *address)
+                .build()?;
+            let mut response = client.get(url).send().await?;
+            if !response.status().is_success() {
+                return Err(anyhow!("unexpected status {}", response.status()));
+            }
+
+            let mut content = Vec::new();
+            while let Some(chunk) = response.chunk().await? {
+                if content.len() + chunk.len() > size_limit {
+                    return Err(anyhow!("content larger than {} bytes", size_limit));
+                }
+                content.extend_from_slice(&chunk);
+            }
+
+            Ok(String::from_utf8(content)?)
+        }
+        Some(_) => Err(anyhow!("{} resolves to a non-public address", host)),
+        None => Err(anyhow!("{} doesn't resolve to any address", host)),
+    }
+}
+
+/// Requests to the OpenTimestamps `calendar` a timestamp over the hash of every verified contribution which doesn't
+/// have one yet, and records the resulting proofs. Contributions which can't be timestamped are skipped and retried
+/// at the next call.
+///
+/// # Cancel safety
+///
+/// https://docs.rs/tokio/latest/tokio/macro.select.html#cancellation-safety
+///
+/// Because of the use of [`tokio::sync::rwlock::RwLock::write_owned`], which is not cancel safe, and a spawned blocking
+/// task, which cannot be cancelled, this function is not cancel safe.
+pub async fn perform_timestamp_contributions(
+    coordinator: Coordinator,
+    client: &reqwest::Client,
+    calendar: &str,
+) -> Result<()> {
+    let read_lock = coordinator.clone().read_owned().await;
+    let pending: Vec<TrimmedContributionInfo> = task::spawn_blocking(move || read_lock.get_verified_contributions())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?
+        .into_iter()
+        .filter(|info| info.timestamp_proof().is_none())
+        .collect();
+
+    // Request the timestamps without holding the lock on the coordinator
+    let url = format!("{}/digest", calendar.trim_end_matches('/'));
+    let mut proofs = Vec::new();
+    for info in pending {
+        let digest = match TimestampProof::digest(info.contribution_hash()) {
+            Ok(digest) => digest,
+            Err(e) => {
+                warn!("Can't timestamp the contribution of round {}: {}", info.ceremony_round(), e);
+                continue;
+            }
+        };
+
+        let response = client
+            .post(&url)
+            .header(reqwest::header::ACCEPT, "application/vnd.opentimestamps.v1")
+            .body(digest.to_vec())
+            .send()
+            .await
+            .and_then(|r| r.error_for_status());
+
+        match response {
+            Ok(response) => match response.bytes().await {
+                Ok(timestamp) => proofs.push((
+                    info.ceremony_round(),
+                    TimestampProof::new(calendar.to_string(), digest, &timestamp),
+                )),
+                Err(e) => warn!("Error while reading the timestamp from {}: {}", url, e),
+            },
+            Err(e) => warn!("Error while requesting a timestamp to {}: {}", url, e),
+        }
+    }
+
+    if proofs.is_empty() {
+        return Ok(());
+    }
+
+    let mut write_lock = coordinator.write_owned().await;
+
+    task::spawn_blocking(move || -> std::result::Result<(), CoordinatorError> {
+        for (round, proof) in proofs {
+            write_lock.update_contribution_info_timestamp_proof(round, proof)?;
+        }
+
+        Ok(())
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
 /// Performs the update of the [Coordinator](`crate::Coordinator`)
 ///
 /// # Cancel safety
@@ -709,7 +2707,33 @@ pub async fn perform_verify_chunks(coordinator: Coordinator, s3_ctx: &S3Ctx) ->
 pub async fn perform_coordinator_update(coordinator: Coordinator) -> Result<()> {
     let mut write_lock = coordinator.write_owned().await;
 
-    task::spawn_blocking(move || write_lock.update())
-        .await?
-        .map_err(|e| ResponseError::CoordinatorError(e))
+    task::spawn_blocking(move || {
+        let result = write_lock.update();
+
+        // The files of the completed rounds are compressed and archived once the coordinator is released
+        let maintenance = write_lock.take_maintenance();
+        drop(write_lock);
+        maintenance.into_iter().for_each(StorageMaintenance::run);
+
+        result
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::ContributionInfo;
+
+    #[test]
+    fn atom_feed_escapes_fields() {
+        let mut info = ContributionInfo::default();
+        info.public_key = String::from("0123456789abcdef");
+        info.contribution_file_hash = String::from("</summary><script>alert(1)</script>&");
+
+        let feed = atom_feed(&[info.into()]);
+        assert!(!feed.contains("<script>"));
+        assert!(feed.contains("&lt;/summary&gt;&lt;script&gt;alert(1)&lt;/script&gt;&amp;"));
+    }
 }
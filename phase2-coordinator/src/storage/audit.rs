@@ -0,0 +1,56 @@
+use crate::{objects::Task, storage::LocatorPath};
+
+use serde::{Deserialize, Serialize};
+
+/// A problem found in a stored artifact of the ceremony.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditIssue {
+    /// The file is recorded in the round but missing from storage
+    Missing,
+    /// The hash of the file differs from the one signed for it
+    HashMismatch,
+    /// The signature file can't be parsed
+    SignatureMalformed,
+    /// The signature doesn't verify with the public key recorded in the round
+    SignatureInvalid,
+}
+
+/// A discrepancy in the artifacts of a contribution.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditDiscrepancy {
+    pub round_height: u64,
+    pub chunk_id: u64,
+    pub contribution_id: u64,
+    /// Path of the file at fault, if recorded in the round
+    pub path: Option<String>,
+    pub issue: AuditIssue,
+}
+
+/// The report of [Coordinator::audit_storage](crate::Coordinator::audit_storage).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageAudit {
+    /// Heights of the audited rounds
+    pub rounds: Vec<u64>,
+    /// Number of files whose hash has been recomputed
+    pub files_checked: u64,
+    pub discrepancies: Vec<AuditDiscrepancy>,
+}
+
+impl StorageAudit {
+    /// Returns `true` if no discrepancy has been found.
+    pub fn is_consistent(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+
+    /// Records a discrepancy in the artifacts of the given contribution.
+    pub(crate) fn report(&mut self, round_height: u64, task: &Task, path: Option<&LocatorPath>, issue: AuditIssue) {
+        self.discrepancies.push(AuditDiscrepancy {
+            round_height,
+            chunk_id: task.chunk_id(),
+            contribution_id: task.contribution_id(),
+            path: path.map(|path| path.to_string()),
+            issue,
+        });
+    }
+}
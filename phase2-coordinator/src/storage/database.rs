@@ -0,0 +1,186 @@
+use crate::{
+    storage::{Locator, StateStorage},
+    CoordinatorError,
+};
+
+use postgres::{Client, NoTls};
+use std::sync::{mpsc, Mutex};
+use tracing::{error, info, trace, warn};
+
+/// The migrations of the schema, applied in order. The index of a migration in the list is its version, so
+/// migrations must only ever be appended.
+const MIGRATIONS: &[&str] = &["CREATE TABLE state_objects (
+        locator TEXT PRIMARY KEY,
+        object BYTEA NOT NULL,
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    )"];
+
+/// Key of the advisory lock serializing the migrations of concurrent coordinators.
+const MIGRATIONS_LOCK: i64 = 0x6e616d61646120;
+
+/// Key of the advisory lock held by the coordinator running the ceremony, for as long as its connection is open.
+const LEADER_LOCK: i64 = 0x6e616d61646121;
+
+type Job = Box<dyn FnOnce(&mut Client) + Send>;
+
+/// A [StateStorage] backed by a PostgreSQL database, holding one row per state object.
+///
+/// Each write is a single statement, so that a crash never leaves a partially written object, and several
+/// coordinators can point to the same database to hand the ceremony over to each other. Only one of them runs the
+/// ceremony at a given time: the others wait on connection for its advisory lock, which is released once its
+/// connection is closed, and take over from the state it left.
+///
+/// The client blocks on its own runtime, which panics inside the runtime of the server: the queries are therefore
+/// run by a dedicated thread owning the connection.
+pub struct Postgres {
+    jobs: Mutex<mpsc::Sender<Job>>,
+}
+
+impl std::fmt::Debug for Postgres {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Postgres").finish_non_exhaustive()
+    }
+}
+
+impl Postgres {
+    /// Connects to the database at the given URL and applies the pending migrations, then waits for the coordinator
+    /// running the ceremony from the same database, if any, to be stopped.
+    pub fn connect(url: &str) -> Result<Self, CoordinatorError> {
+        trace!("Connecting to the state database");
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let (connected, connection) = mpsc::channel();
+        let url = url.to_owned();
+
+        std::thread::spawn(move || {
+            let mut client = match Client::connect(&url, NoTls).and_then(|mut client| {
+                Self::migrate(&mut client)?;
+                Self::lock_leader(&mut client)?;
+                Ok(client)
+            }) {
+                Ok(client) => {
+                    let _ = connected.send(Ok(()));
+                    client
+                }
+                Err(e) => {
+                    let _ = connected.send(Err(e));
+                    return;
+                }
+            };
+
+            // Stops once the storage, and so the sender, is dropped
+            for job in receiver {
+                job(&mut client);
+            }
+        });
+
+        connection
+            .recv()
+            .map_err(|_| CoordinatorError::StorageFailed)?
+            .map_err(anyhow::Error::from)?;
+        info!("Connected to the state database");
+
+        Ok(Self { jobs: Mutex::new(jobs) })
+    }
+
+    fn migrate(client: &mut Client) -> Result<(), postgres::Error> {
+        let mut transaction = client.transaction()?;
+        transaction.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATIONS_LOCK])?;
+        transaction.batch_execute("CREATE TABLE IF NOT EXISTS schema_migrations (version BIGINT PRIMARY KEY)")?;
+
+        let applied: i64 = transaction
+            .query_one("SELECT count(*) FROM schema_migrations", &[])?
+            .get(0);
+        for (version, migration) in MIGRATIONS.iter().enumerate().skip(applied as usize) {
+            info!("Applying migration {} of the state database", version);
+            transaction.batch_execute(migration)?;
+            transaction.execute(
+                "INSERT INTO schema_migrations (version) VALUES ($1)",
+                &[&(version as i64)],
+            )?;
+        }
+
+        transaction.commit()
+    }
+
+    fn lock_leader(client: &mut Client) -> Result<(), postgres::Error> {
+        let locked: bool = client
+            .query_one("SELECT pg_try_advisory_lock($1)", &[&LEADER_LOCK])?
+            .get(0);
+        if !locked {
+            warn!("Another coordinator is running the ceremony from the state database, waiting for it to stop");
+            client.execute("SELECT pg_advisory_lock($1)", &[&LEADER_LOCK])?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs the given query on the connection thread and waits for its result.
+    fn run<T, F>(&self, query: F) -> Result<T, CoordinatorError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Client) -> Result<T, postgres::Error> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        self.jobs
+            .lock()
+            .map_err(|_| CoordinatorError::StorageLockFailed)?
+            .send(Box::new(move |client| {
+                let _ = sender.send(query(client));
+            }))
+            .map_err(|_| {
+                error!("The connection to the state database is closed");
+                CoordinatorError::StorageFailed
+            })?;
+
+        match receiver.recv() {
+            Ok(result) => Ok(result.map_err(anyhow::Error::from)?),
+            Err(_) => Err(CoordinatorError::StorageFailed),
+        }
+    }
+}
+
+/// Returns the key of the row holding the object at the given locator.
+fn key(locator: &Locator) -> Result<String, CoordinatorError> {
+    Ok(serde_json::to_string(locator)?)
+}
+
+impl StateStorage for Postgres {
+    fn exists(&self, locator: &Locator) -> Result<bool, CoordinatorError> {
+        let key = key(locator)?;
+        self.run(move |client| {
+            let row = client.query_one(
+                "SELECT EXISTS (SELECT 1 FROM state_objects WHERE locator = $1)",
+                &[&key],
+            )?;
+            Ok(row.get(0))
+        })
+    }
+
+    fn get(&self, locator: &Locator) -> Result<Option<Vec<u8>>, CoordinatorError> {
+        let key = key(locator)?;
+        self.run(move |client| {
+            let row = client.query_opt("SELECT object FROM state_objects WHERE locator = $1", &[&key])?;
+            Ok(row.map(|row| row.get(0)))
+        })
+    }
+
+    fn put(&self, locator: &Locator, bytes: &[u8]) -> Result<(), CoordinatorError> {
+        let (key, bytes) = (key(locator)?, bytes.to_vec());
+        self.run(move |client| {
+            client.execute(
+                "INSERT INTO state_objects (locator, object) VALUES ($1, $2)
+                ON CONFLICT (locator) DO UPDATE SET object = EXCLUDED.object, updated_at = now()",
+                &[&key, &bytes],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn remove(&self, locator: &Locator) -> Result<(), CoordinatorError> {
+        let key = key(locator)?;
+        self.run(move |client| {
+            client.execute("DELETE FROM state_objects WHERE locator = $1", &[&key])?;
+            Ok(())
+        })
+    }
+}
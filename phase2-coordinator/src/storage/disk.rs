@@ -1,33 +1,127 @@
 use crate::{
+    audit::{AuditEvent, AuditLog, AuditRecord, AUDIT_LOG_FILE},
+    authentication::Signature,
+    compression::{self, ContentEncoding},
     environment::Environment,
     objects::{ContributionFileSignature, ContributionInfo, Round, TrimmedContributionInfo},
     storage::{
-        ContributionLocator, ContributionSignatureLocator, Locator, Object, ObjectReader, ObjectWriter, StorageLocator,
-        StorageObject,
+        encryption::{self, StorageCipher},
+        ContributionLocator, ContributionSignatureLocator, Locator, Object, ObjectReader, ObjectWriter,
+        SnapshotManifest, StateStorage, StateTransition, StorageLocator, StorageObject, WalRecord, WriteAheadLog,
+        SNAPSHOT_MANIFEST,
     },
     CoordinatorError, CoordinatorState,
 };
 
 use anyhow::Result;
+use blake2::{Blake2b512, Digest};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use fs_err::{self as fs, File, OpenOptions};
 use itertools::Itertools;
 use memmap::MmapOptions;
+use setup_utils::calculate_hash;
 
 use std::{
+    collections::{BTreeMap, HashMap, HashSet},
     convert::TryFrom,
-    io::{Read, Write},
+    io::{self, Read, Write},
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
     str::FromStr,
+    sync::{Mutex, MutexGuard},
+    time::SystemTime,
 };
-use tracing::{debug, error, trace};
+use time::OffsetDateTime;
+use tracing::{debug, error, info, trace};
 
 use super::{LocatorPath, StorageAction};
 
+/// Name of the file of the write-ahead log, in the base directory
+const WAL_FILE: &str = "wal.log";
+
+/// Name of the directory, in the base directory, where a snapshot is unpacked before being restored
+const RESTORE_DIRECTORY: &str = "snapshot.restore";
+
+/// Name of the directory, in the base directory, where the files of the snapshots being written are linked, see
+/// [Disk::snapshot]
+const SNAPSHOT_DIRECTORY: &str = "snapshot.staging";
+
+/// Extension of the temporary files written before being renamed over their locator, see [write_atomically]
+const TEMPORARY_EXTENSION: &str = "tmp";
+
+/// Name of the directory, in the base directory, where the contribution files are stored under their hash
+const CONTENTS_DIRECTORY: &str = "contents";
+
+/// Name of the directory, in the base directory, where the pruned files wait to be archived, see [Disk::prune_round]
+const PRUNED_DIRECTORY: &str = "pruned";
+
+/// Name of the directory, in the base directory, where the compressed copies of the files served plain are kept under
+/// their relative name, see [Disk::serve_file] and [Disk::prune_compressed_copies]
+const COMPRESSED_DIRECTORY: &str = "compressed";
+
+/// The storage of the coordinator on the local filesystem.
+///
+/// The state objects, see [Locator::is_state], are kept in a [StateStorage] instead of files when the
+/// environment configures a state database. Otherwise their writes go through a [WriteAheadLog], replayed
+/// when the storage is loaded. The objects are written to a temporary file renamed over the previous one, for a crash
+/// not to leave them truncated.
+///
+/// Once complete, the challenge and response files, see [Locator::is_content_addressed], are moved to the
+/// `contents` directory under the name of their hash, and their locators become links to it. Identical files,
+/// such as the response and the next challenge or a contribution uploaded twice, are thus stored once, and the
+/// expected hash of a file is known without reading it. The links are counted in a [ContentIndex], for a content to be
+/// removed with its last link without looking the others up. The contents of the completed rounds are compressed with
+/// zstd, see [Self::compress_round], and decompressed as they're read.
+///
+/// When the environment sets a storage encryption key, the files and the records of the log are encrypted as
+/// they're written, see [StorageCipher], bound to their path in the base directory, and decrypted as they're read.
+/// Whoever controls the disk can then neither read the files nor replace or swap them: a plain file is only read for
+/// a contribution file written in place through a writer, until it's sealed. A storage written before the key was
+/// set is encrypted by restoring a snapshot of it with the key, see [Self::restore].
+///
+/// The [AuditLog] is kept in the base directory as well, whatever the storage of the state objects.
 #[derive(Debug)]
 pub struct Disk {
     environment: Environment,
     resolver: DiskResolver,
+    state: Option<Box<dyn StateStorage>>,
+    wal: Option<WriteAheadLog>,
+    audit: AuditLog,
+    cipher: Option<StorageCipher>,
+    contents: Mutex<ContentIndex>,
+}
+
+/// The index of the contents of a [Disk], built when it's loaded.
+#[derive(Debug, Default)]
+struct ContentIndex {
+    /// Number of locators linking to each content, by hash
+    links: HashMap<String, usize>,
+    /// Size and modification time of the contents checked against their hash, by name, for them to be checked again
+    /// only once modified
+    checked: HashMap<String, (u64, SystemTime)>,
+}
+
+impl ContentIndex {
+    /// Counts the links to the contents in the given base directory.
+    fn load(base: &Path) -> Result<Self, CoordinatorError> {
+        let mut index = Self::default();
+        for name in list_files(base)? {
+            let path = base.join(&name);
+            if !fs::symlink_metadata(&path)?.file_type().is_symlink() {
+                continue;
+            }
+            let target = fs::read_link(&path)?;
+            match target.parent().and_then(Path::file_name) {
+                Some(directory) if directory == CONTENTS_DIRECTORY => {}
+                _ => continue,
+            }
+            if let Some(hash) = target.file_stem().and_then(|stem| stem.to_str()) {
+                *index.links.entry(hash.to_owned()).or_default() += 1;
+            }
+        }
+
+        Ok(index)
+    }
 }
 
 impl Disk {
@@ -36,6 +130,22 @@ impl Disk {
     where
         Self: Sized,
     {
+        let state: Option<Box<dyn StateStorage>> = match environment.state_database_url() {
+            #[cfg(feature = "postgres")]
+            Some(url) => Some(Box::new(super::Postgres::connect(url)?)),
+            #[cfg(not(feature = "postgres"))]
+            Some(_) => return Err(CoordinatorError::StateDatabaseUnsupported),
+            None => None,
+        };
+
+        Self::load_with_state(environment, state)
+    }
+
+    /// Loads a new instance of `Disk` keeping the state objects in the given [StateStorage], if any.
+    pub fn load_with_state(
+        environment: &Environment,
+        state: Option<Box<dyn StateStorage>>,
+    ) -> Result<Self, CoordinatorError> {
         trace!("Loading disk storage");
 
         // Check the base and contribution info directory exist.
@@ -45,11 +155,47 @@ impl Disk {
             fs::create_dir_all(contributors_dir).expect("unable to create the contributors directory");
         }
 
+        // The writes to a state storage are atomic already, and replaying a local log could overwrite the state
+        // written by another coordinator.
+        let (wal, records) = match state {
+            Some(_) => (None, vec![]),
+            None => {
+                let path = Path::new(environment.local_base_directory()).join(WAL_FILE);
+                let (wal, records) = WriteAheadLog::open(&path)?;
+                (Some(wal), records)
+            }
+        };
+
+        let audit = AuditLog::open(&Path::new(environment.local_base_directory()).join(AUDIT_LOG_FILE))?;
+
+        // Discard the temporary files of the writes interrupted by a crash, the files they replace being intact,
+        // and the files linked for the snapshots interrupted.
+        let base = Path::new(environment.local_base_directory());
+        if base.join(SNAPSHOT_DIRECTORY).exists() {
+            fs::remove_dir_all(base.join(SNAPSHOT_DIRECTORY))?;
+        }
+        for name in list_files(base)? {
+            if Path::new(&name).extension() == Some(TEMPORARY_EXTENSION.as_ref()) {
+                debug!("Removing the temporary file {} of an interrupted write", name);
+                fs::remove_file(base.join(&name))?;
+            }
+        }
+
         // Create a new `Storage` instance, and set the `Environment`.
+        let contents = ContentIndex::load(base)?;
         let mut storage = Self {
             environment: environment.clone(),
             resolver: DiskResolver::new(environment.local_base_directory()),
+            state,
+            wal,
+            audit,
+            cipher: environment
+                .storage_encryption_key()
+                .map(StorageCipher::from_hex)
+                .transpose()?,
+            contents: Mutex::new(contents),
         };
+        storage.replay(records)?;
 
         // Create the coordinator state locator if it does not exist yet.
         if !storage.exists(&Locator::CoordinatorState) {
@@ -85,6 +231,13 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorAlreadyExists);
         }
 
+        if let Some(state) = self.state_storage(&locator) {
+            state.put(&locator, &vec![0; size as usize])?;
+
+            trace!("Initialized {:?} in the state storage", locator);
+            return Ok(());
+        }
+
         // If the locator is a contribution file, initialize its directory.
         if let Locator::ContributionFile(contribution_locator) = locator {
             self.resolver
@@ -107,6 +260,13 @@ impl Disk {
 
     /// Checks whether the given locator exists in the storage or not.
     pub fn exists(&self, locator: &Locator) -> bool {
+        if let Some(state) = self.state_storage(locator) {
+            return state.exists(locator).unwrap_or_else(|e| {
+                error!("Could not check the state storage for {:?} - {}", locator, e);
+                false
+            });
+        }
+
         let path = match self.to_path(locator) {
             Ok(path) => path,
             Err(e) => {
@@ -133,9 +293,7 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
-        let path = self.to_path(&Locator::ContributionsInfoSummary)?;
-
-        Ok(fs::read(path)?)
+        self.read(&Locator::ContributionsInfoSummary)
     }
 
     /// Retrieve the json encoded state file
@@ -149,9 +307,7 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
-        let path = self.to_path(&Locator::CoordinatorState)?;
-
-        Ok(fs::read(path)?)
+        self.read(&Locator::CoordinatorState)
     }
 
     /// Returns a copy of an object at the given locator in storage, if it exists.
@@ -166,89 +322,9 @@ impl Disk {
         }
 
         // read the file to a byte array
-        let file_bytes = fs::read(path)?;
-
-        let object = match locator {
-            Locator::CoordinatorState => {
-                let coordinator_state: CoordinatorState = serde_json::from_slice(&file_bytes)?;
-                Ok(Object::CoordinatorState(coordinator_state))
-            }
-            Locator::RoundHeight => {
-                let round_height: u64 = serde_json::from_slice(&file_bytes)?;
-                Ok(Object::RoundHeight(round_height))
-            }
-            Locator::RoundState { round_height: _ } => {
-                let round: Round = serde_json::from_slice(&file_bytes)?;
-                Ok(Object::RoundState(round))
-            }
-            Locator::RoundFile { round_height } => {
-                // Check that the round size is correct.
-                let expected_size = Object::round_file_size(&self.environment);
-                let found_size = file_bytes.len() as u64;
-                debug!("Round {} filesize is {}", round_height, found_size);
-                if found_size == 0 || expected_size != found_size {
-                    error!("Round file size should be {} but found {}", expected_size, found_size);
-                    return Err(CoordinatorError::RoundFileSizeMismatch.into());
-                }
+        let file_bytes = self.read(locator)?;
 
-                Ok(Object::RoundFile(file_bytes))
-            }
-            Locator::ContributionFile(contribution_locator) => {
-                // Check that the contribution size is correct.
-                let expected_size = Object::anoma_contribution_file_size(
-                    contribution_locator.round_height(),
-                    contribution_locator.contribution_id(),
-                );
-                let found_size = file_bytes.len() as u64;
-                debug!(
-                    "Round {} chunk {} filesize is {}",
-                    contribution_locator.round_height(),
-                    contribution_locator.chunk_id(),
-                    found_size
-                );
-                if found_size == 0 || expected_size != found_size {
-                    error!(
-                        "Contribution file size should be {} but found {}",
-                        expected_size, found_size
-                    );
-                    return Err(CoordinatorError::ContributionFileSizeMismatch.into());
-                }
-
-                let mut contribution_file: Vec<u8> = Vec::with_capacity(expected_size as usize);
-                contribution_file.write_all(&file_bytes)?;
-                Ok(Object::ContributionFile(contribution_file))
-            }
-            Locator::ContributionFileSignature(contribution_locator) => {
-                // Check that the contribution file signature size is correct.
-                let expected_size = Object::contribution_file_signature_size(contribution_locator.is_verified());
-                let found_size = file_bytes.len() as u64;
-                debug!(
-                    "Round {} chunk {} contribution {} signature filesize is {}",
-                    contribution_locator.round_height(),
-                    contribution_locator.chunk_id(),
-                    contribution_locator.contribution_id(),
-                    found_size
-                );
-                if found_size == 0 || expected_size != found_size {
-                    error!(
-                        "Contribution signature file size should be {} but found {}",
-                        expected_size, found_size
-                    );
-                    return Err(CoordinatorError::ContributionSignatureFileSizeMismatch.into());
-                }
-
-                let contribution_file_signature: ContributionFileSignature = serde_json::from_slice(&file_bytes)?;
-                Ok(Object::ContributionFileSignature(contribution_file_signature))
-            }
-            Locator::ContributionInfoFile { round_height: _ } => {
-                let contribution_info: ContributionInfo = serde_json::from_slice(&file_bytes)?;
-                Ok(Object::ContributionInfoFile(contribution_info))
-            }
-            Locator::ContributionsInfoSummary => {
-                let summary: Vec<TrimmedContributionInfo> = serde_json::from_slice(&file_bytes)?;
-                Ok(Object::ContributionsInfoSummary(summary))
-            }
-        };
+        let object = deserialize_object(&self.environment, locator, file_bytes);
 
         trace!("Fetched {}", self.to_path(locator)?);
         object
@@ -264,11 +340,14 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorAlreadyExists);
         }
 
+        // Log the object before its file is created, for an interrupted insertion to be completed.
+        self.log(&locator, || WalRecord::update(locator, &object.to_bytes()))?;
+
         // Initialize the new file with the object size.
         self.initialize(locator.clone(), object.size())?;
 
         // Insert the object at the given locator.
-        self.update(&locator, object)?;
+        self.write(&locator, object)?;
 
         trace!("Inserted {}", self.to_path(&locator)?);
         Ok(())
@@ -285,13 +364,69 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
-        let mut file = OpenOptions::new().write(true).open(path)?;
-        file.set_len(object.size())?;
-        file.write_all(&object.to_bytes())?;
-        // Sync all in-memory data to disk.
-        file.flush()?;
+        self.log(locator, || WalRecord::update(*locator, &object.to_bytes()))?;
+        self.write(locator, object)?;
+
+        trace!("Updated {}", path);
+        Ok(())
+    }
+
+    /// Writes the object to the existing location of the given locator.
+    fn write(&mut self, locator: &Locator, object: Object) -> Result<(), CoordinatorError> {
+        if let Some(state) = self.state_storage(locator) {
+            return state.put(locator, &object.to_bytes());
+        }
+
+        // The stored content may be shared with other locators, it's only released once replaced
+        let content = self.content_hash(locator)?;
+        self.write_file(self.to_path(locator)?.as_path(), |writer| {
+            writer.write_all(&object.to_bytes())?;
+            Ok(())
+        })?;
+        if let Some(hash) = content {
+            self.release_content(&hash)?;
+        }
+
+        if locator.is_content_addressed() {
+            self.seal(locator)?;
+        }
+        self.checkpoint()
+    }
+
+    /// Updates an existing object for the given locator in storage, if it exists, with the content of the file
+    /// at the given path. The content is copied without being loaded in memory.
+    pub fn update_from_file(&mut self, locator: &Locator, source: &Path) -> Result<(), CoordinatorError> {
+        let path = self.to_path(locator)?;
+        trace!("Updating {} from {}", path, source.display());
+
+        // Check that the given locator exists in storage.
+        if !self.exists(locator) {
+            error!("Locator missing in call to update_from_file() in storage.");
+            return Err(CoordinatorError::StorageLocatorMissing);
+        }
+
+        if let Some(state) = self.state_storage(locator) {
+            return state.put(locator, &fs::read(source)?);
+        }
 
-        trace!("Updated {}", self.to_path(&locator)?);
+        if locator.is_state() && self.wal.is_some() {
+            let bytes = fs::read(source)?;
+            self.log(locator, || WalRecord::update(*locator, &bytes))?;
+        }
+        let content = self.content_hash(locator)?;
+        self.write_file(path.as_path(), |writer| {
+            io::copy(&mut File::open(source)?, writer)?;
+            Ok(())
+        })?;
+        if let Some(hash) = content {
+            self.release_content(&hash)?;
+        }
+        if locator.is_content_addressed() {
+            self.seal(locator)?;
+        }
+        self.checkpoint()?;
+
+        trace!("Updated {}", path);
         Ok(())
     }
 
@@ -315,6 +450,19 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorAlreadyExists);
         }
 
+        // The destination is linked to the content of the source instead of being copied
+        if source_locator.is_content_addressed() && destination_locator.is_content_addressed() {
+            let hash = self.seal(source_locator)?;
+            if let Locator::ContributionFile(contribution_locator) = destination_locator {
+                self.resolver
+                    .chunk_directory_init(contribution_locator.round_height(), contribution_locator.chunk_id());
+            }
+            self.link_content(destination_locator, &hash)?;
+
+            trace!("Linked {} to {}", self.to_path(destination_locator)?, hash);
+            return Ok(());
+        }
+
         // Fetch the source object.
         let source_object = self.get(source_locator)?;
 
@@ -324,46 +472,765 @@ impl Disk {
         // Update the destination locator with the copied source object.
         self.update(destination_locator, source_object)?;
 
-        trace!("Copied to {}", self.to_path(destination_locator)?);
-        Ok(())
+        trace!("Copied to {}", self.to_path(destination_locator)?);
+        Ok(())
+    }
+
+    /// Removes the object corresponding to the given locator from storage.
+    pub fn remove(&mut self, locator: &Locator) -> Result<(), CoordinatorError> {
+        let path = self.to_path(locator)?;
+        trace!("Removing {}", path);
+
+        // Check that the locator exists in storage.
+        if !self.exists(&locator) {
+            error!("Locator in call to remove() doesn't exist in storage.");
+            return Err(CoordinatorError::StorageLocatorMissing);
+        }
+
+        if let Some(state) = self.state_storage(locator) {
+            return state.remove(locator);
+        }
+        self.log(locator, || WalRecord::Remove { locator: *locator })?;
+
+        // TODO: if any of the locators are directories, make this
+        // detect whether the path is a directory of a file and call
+        // the appropriate function.
+        if !self.unlink_content(locator)? {
+            fs::remove_file(path.clone())?;
+        }
+        self.checkpoint()?;
+
+        trace!("Removed {}", path);
+        Ok(())
+    }
+
+    /// Returns the space, in bytes, available to the coordinator on the filesystem of the base directory.
+    #[cfg(unix)]
+    pub fn available_space(&self) -> Result<u64, CoordinatorError> {
+        let path = std::ffi::CString::new(self.environment.local_base_directory())
+            .map_err(|_| CoordinatorError::StorageLocatorFormatIncorrect)?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+    }
+
+    /// Returns the space, in bytes, available to the coordinator, which isn't checked outside unix systems.
+    #[cfg(not(unix))]
+    pub fn available_space(&self) -> Result<u64, CoordinatorError> {
+        Ok(u64::MAX)
+    }
+
+    /// Returns the size of the object stored at the given locator.
+    pub fn size(&self, locator: &Locator) -> Result<u64, CoordinatorError> {
+        let path = self.to_path(locator)?;
+        trace!("Fetching size of {}", path);
+
+        // Check that the given locator exists in storage.
+        if !self.exists(locator) {
+            error!("Locator missing in call to size() in storage.");
+            return Err(CoordinatorError::StorageLocatorMissing);
+        }
+
+        if self.state_storage(locator).is_some() {
+            return Ok(self.read(locator)?.len() as u64);
+        }
+        if self.is_compressed(locator)? {
+            return Ok(io::copy(&mut self.open(locator)?, &mut io::sink())?);
+        }
+
+        // Open the file.
+        let file = OpenOptions::new().read(true).write(true).open(path.clone())?;
+        let size = match is_encrypted_file(path.as_path())? {
+            true => encryption::plaintext_size(file.metadata()?.len()),
+            false => file.metadata()?.len(),
+        };
+
+        trace!("Fetched size of {}", path);
+        Ok(size)
+    }
+
+    /// Returns the state storage holding the object at the given locator, if it's not kept in a file.
+    fn state_storage(&self, locator: &Locator) -> Option<&dyn StateStorage> {
+        self.state.as_deref().filter(|_| locator.is_state())
+    }
+
+    /// Reads the bytes stored at the given locator.
+    pub(crate) fn read(&self, locator: &Locator) -> Result<Vec<u8>, CoordinatorError> {
+        match self.state_storage(locator) {
+            Some(state) => state.get(locator)?.ok_or(CoordinatorError::StorageLocatorMissing),
+            None => {
+                let mut data = vec![];
+                self.open(locator)?.read_to_end(&mut data)?;
+                Ok(data)
+            }
+        }
+    }
+
+    /// Opens the file at the given locator for reading, decrypting and decompressing it on the fly if it's stored
+    /// encrypted or compressed.
+    pub fn open(&self, locator: &Locator) -> Result<Box<dyn Read>, CoordinatorError> {
+        Ok(self.open_path(self.to_path(locator)?.as_path())?)
+    }
+
+    /// Opens the file at the given path in the base directory for reading, see [Self::open].
+    fn open_path(&self, path: &Path) -> Result<Box<dyn Read + Send>, CoordinatorError> {
+        open_file(
+            path,
+            self.cipher.as_ref(),
+            &self.encryption_name(path)?,
+            self.may_be_plain(path)?,
+        )
+    }
+
+    /// Returns the name the file at the given path in the base directory is encrypted under, see [encryption_name].
+    fn encryption_name(&self, path: &Path) -> Result<String, CoordinatorError> {
+        encryption_name(Path::new(self.environment.local_base_directory()), path)
+    }
+
+    /// Returns the file of the given locator, to be read with [StoredFile::open] once the storage is released.
+    pub fn stored_file(&self, locator: &Locator) -> Result<StoredFile, CoordinatorError> {
+        let path = self.to_path(locator)?;
+
+        Ok(StoredFile {
+            base: PathBuf::from(self.environment.local_base_directory()),
+            may_be_plain: self.may_be_plain(path.as_path())?,
+            path: path.as_path().to_path_buf(),
+            cipher: self.cipher.clone(),
+        })
+    }
+
+    /// Returns `true` if the file at the given path in the base directory may be read plain: the storage isn't
+    /// encrypted, the file isn't an object of the storage, or it's a contribution file written in place through a
+    /// writer, which isn't sealed yet.
+    fn may_be_plain(&self, path: &Path) -> Result<bool, CoordinatorError> {
+        if self.cipher.is_none() {
+            return Ok(true);
+        }
+
+        match self.to_locator(&LocatorPath::try_from(path)?) {
+            Ok(locator) => Ok(locator.is_content_addressed() && self.content_name(&locator)?.is_none()),
+            Err(_) => Ok(true),
+        }
+    }
+
+    /// Opens the file at the given path in the base directory, such as a challenge, to be streamed to a client,
+    /// compressed with `encoding` if any. A file encrypted or compressed at rest is decoded as it's streamed, for its
+    /// plaintext not to be written to the disk.
+    pub fn serve_file(&self, path: &Path, encoding: Option<ContentEncoding>) -> Result<ServedFile, CoordinatorError> {
+        if !is_encrypted_file(path)? && !is_compressed_file(path)? && self.may_be_plain(path)? {
+            let base = Path::new(self.environment.local_base_directory());
+            let path = match encoding {
+                Some(encoding) => compression::compressed_copy(
+                    path,
+                    &base.join(COMPRESSED_DIRECTORY),
+                    &relative_name(base, path)?,
+                    encoding,
+                )?,
+                None => path.to_path_buf(),
+            };
+            return Ok(ServedFile::File(File::open(path)?));
+        }
+
+        let reader = self.open_path(path)?;
+        match encoding {
+            Some(encoding) => Ok(ServedFile::Decoded(compression::encoder(reader, encoding)?)),
+            None => Ok(ServedFile::Decoded(reader)),
+        }
+    }
+
+    /// Writes atomically the file at the given path in the base directory with the bytes written by `write`,
+    /// encrypted if the storage is.
+    fn write_file<F>(&self, path: &Path, write: F) -> Result<(), CoordinatorError>
+    where
+        F: FnOnce(&mut dyn Write) -> Result<(), CoordinatorError>,
+    {
+        let name = relative_name(Path::new(self.environment.local_base_directory()), path)?;
+        write_encrypted(path, self.cipher.as_ref(), &name, write)
+    }
+
+    /// Moves the complete file at the given content addressed locator to the contents directory, under its hash, and
+    /// replaces it with a link to there. The file is dropped instead if an identical one is stored already. Returns
+    /// the [`hex`] encoded Blake2b-512 hash of the file.
+    pub fn seal(&mut self, locator: &Locator) -> Result<String, CoordinatorError> {
+        if !locator.is_content_addressed() {
+            error!("Locator {:?} in call to seal() isn't content addressed.", locator);
+            return Err(CoordinatorError::StorageFailed);
+        }
+        if let Some(hash) = self.content_hash(locator)? {
+            return Ok(hash);
+        }
+        if !self.exists(locator) {
+            error!("Locator missing in call to seal() in storage.");
+            return Err(CoordinatorError::StorageLocatorMissing);
+        }
+
+        let path = self.to_path(locator)?;
+        let mut hasher = Blake2b512::new();
+        io::copy(&mut self.open(locator)?, &mut hasher)?;
+        let hash = hex::encode(hasher.finalize());
+
+        let compressed = compressed_name(&hash);
+        let mut created = false;
+        let content = if self.content_path(&compressed).exists() {
+            debug!("{} is a duplicate of {}", path, hash);
+            compressed
+        } else if self.content_path(&hash).exists() {
+            debug!("{} is a duplicate of {}", path, hash);
+            hash.clone()
+        } else {
+            fs::create_dir_all(Path::new(self.environment.local_base_directory()).join(CONTENTS_DIRECTORY))?;
+            // The content is encrypted under its own name, the file being plain if it was written in place
+            match self.cipher.is_some() {
+                true => self.write_file(&self.content_path(&hash), |writer| {
+                    io::copy(&mut self.open(locator)?, writer)?;
+                    Ok(())
+                })?,
+                false => fs::hard_link(path.as_path(), self.content_path(&hash))?,
+            }
+            created = true;
+            hash.clone()
+        };
+        // The link replaces the file atomically, which is still stored under its hash if interrupted
+        self.link_content(locator, &content)?;
+        // The content written is the one just hashed
+        if created {
+            self.check_content(&content, |_| Ok(()))?;
+        }
+
+        trace!("Sealed {} as {}", path, hash);
+        Ok(hash)
+    }
+
+    /// Returns the [`hex`] encoded hash of the file at the given locator if it's stored under it, see [Self::seal].
+    pub fn content_hash(&self, locator: &Locator) -> Result<Option<String>, CoordinatorError> {
+        Ok(self
+            .content_name(locator)?
+            .map(|name| name.trim_end_matches(&compressed_name("")).to_owned()))
+    }
+
+    /// Returns `true` if the file at the given locator is stored compressed, see [Self::compress_round].
+    fn is_compressed(&self, locator: &Locator) -> Result<bool, CoordinatorError> {
+        Ok(self
+            .content_name(locator)?
+            .map_or(false, |name| name.ends_with(&compressed_name(""))))
+    }
+
+    /// Returns the name, in the contents directory, of the content the given locator links to, if any.
+    fn content_name(&self, locator: &Locator) -> Result<Option<String>, CoordinatorError> {
+        if !locator.is_content_addressed() {
+            return Ok(None);
+        }
+
+        let path = self.to_path(locator)?;
+        match fs::symlink_metadata(path.as_path()) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {}
+            _ => return Ok(None),
+        }
+        let target = fs::read_link(path.as_path())?;
+        match target.parent().and_then(Path::file_name) {
+            Some(directory) if directory == CONTENTS_DIRECTORY => {
+                Ok(target.file_name().and_then(|name| name.to_str()).map(str::to_owned))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the path of the content with the given name.
+    fn content_path(&self, name: &str) -> PathBuf {
+        Path::new(self.environment.local_base_directory())
+            .join(CONTENTS_DIRECTORY)
+            .join(name)
+    }
+
+    /// Returns the content addressed locators linking to a content, with the name of the content.
+    fn content_links(&self) -> Result<Vec<(Locator, String)>, CoordinatorError> {
+        let base = Path::new(self.environment.local_base_directory());
+        let mut links = vec![];
+        for name in list_files(base)? {
+            if let Ok(locator) = self.to_locator(&LocatorPath::try_from(base.join(&name).as_path())?) {
+                if let Some(content) = self.content_name(&locator)? {
+                    links.push((locator, content));
+                }
+            }
+        }
+
+        Ok(links)
+    }
+
+    /// Collects the contents linked by the files of the given completed round to be compressed with zstd by
+    /// [RoundCompression::run], except the ones also linked by a later round, such as the final challenge of the round
+    /// which is the first of the next one.
+    pub fn compress_round(&self, round_height: u64) -> Result<RoundCompression, CoordinatorError> {
+        let round_of = |locator: &Locator| match locator {
+            Locator::ContributionFile(contribution_locator) => contribution_locator.round_height(),
+            _ => 0,
+        };
+        let links = self.content_links()?;
+        let in_use: HashSet<&String> = links
+            .iter()
+            .filter(|(locator, _)| round_of(locator) > round_height)
+            .map(|(_, content)| content)
+            .collect();
+        let mut contents: BTreeMap<String, Vec<PathBuf>> = links
+            .iter()
+            .filter(|(locator, content)| {
+                round_of(locator) == round_height
+                    && !content.ends_with(&compressed_name(""))
+                    && !in_use.contains(content)
+            })
+            .map(|(_, content)| (content.clone(), vec![]))
+            .collect();
+        for (locator, content) in &links {
+            if let Some(paths) = contents.get_mut(content) {
+                paths.push(self.to_path(locator)?.as_path().to_path_buf());
+            }
+        }
+
+        Ok(RoundCompression {
+            round_height,
+            base: PathBuf::from(self.environment.local_base_directory()),
+            contents,
+            cipher: self.cipher.clone(),
+        })
+    }
+
+    /// Removes the contribution files of the given round, linking them first into the pruned directory if the
+    /// environment has an archive directory, for [PrunedArchive::run] to copy them there. The contents still linked by
+    /// a later round are kept, and so are the states and the signatures of the round, which make the transcript.
+    /// Returns the number of files removed.
+    pub fn prune_round(&mut self, round_height: u64) -> Result<usize, CoordinatorError> {
+        let base = PathBuf::from(self.environment.local_base_directory());
+        let directory = PathBuf::from(self.resolver.round_directory(round_height));
+        if !directory.exists() {
+            return Ok(0);
+        }
+
+        let mut pruned = 0;
+        for name in list_files(&directory)? {
+            let path = LocatorPath::try_from(directory.join(&name).as_path())?;
+            let locator = match self.to_locator(&path) {
+                Ok(locator @ Locator::ContributionFile(_)) => locator,
+                _ => continue,
+            };
+            let content = self.content_name(&locator)?;
+
+            if self.environment.archive_directory().is_some() {
+                // The compressed contents are archived as such
+                let mut archived = relative_name(&base, path.as_path())?;
+                let source = match &content {
+                    Some(content) => {
+                        if self.is_compressed(&locator)? {
+                            archived = compressed_name(&archived);
+                        }
+                        self.content_path(content)
+                    }
+                    None => path.as_path().to_path_buf(),
+                };
+                let staged = base.join(PRUNED_DIRECTORY).join(archived);
+                if let Some(parent) = staged.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                if staged.exists() {
+                    fs::remove_file(&staged)?;
+                }
+                fs::hard_link(&source, &staged)?;
+            }
+
+            fs::remove_file(path.as_path())?;
+            if let Some(content) = content {
+                self.release_content(content.trim_end_matches(&compressed_name("")))?;
+            }
+            pruned += 1;
+        }
+
+        info!("Pruned {} files of round {}", pruned, round_height);
+        Ok(pruned)
+    }
+
+    /// Removes the compressed copies of the files of the rounds before the given one, which aren't served anymore.
+    pub fn prune_compressed_copies(&self, round_height: u64) -> Result<(), CoordinatorError> {
+        let directory = Path::new(self.environment.local_base_directory()).join(COMPRESSED_DIRECTORY);
+        if !directory.exists() {
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&directory)? {
+            let entry = entry?;
+            let current = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("round_"))
+                .and_then(|height| height.parse::<u64>().ok())
+                .map_or(false, |height| height >= round_height);
+            if current {
+                continue;
+            }
+            trace!("Removing the compressed copies in {}", entry.path().display());
+            match entry.path().is_dir() {
+                true => fs::remove_dir_all(entry.path())?,
+                false => fs::remove_file(entry.path())?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the copy to the archive directory of the environment, if any, of the files pruned by
+    /// [Self::prune_round], to be run once the storage is released.
+    pub fn pruned_archive(&self) -> Option<PrunedArchive> {
+        self.environment.archive_directory().map(|archive| PrunedArchive {
+            pruned: Path::new(self.environment.local_base_directory()).join(PRUNED_DIRECTORY),
+            archive: PathBuf::from(archive),
+        })
+    }
+
+    /// Points the given locator to the content with the given name, replacing the file it holds if any. The link is
+    /// relative, for the base directory to be moved.
+    fn link_content(&self, locator: &Locator, content: &str) -> Result<(), CoordinatorError> {
+        link_to_content(
+            Path::new(self.environment.local_base_directory()),
+            self.to_path(locator)?.as_path(),
+            content,
+        )?;
+        *self
+            .content_index()
+            .links
+            .entry(content.trim_end_matches(&compressed_name("")).to_owned())
+            .or_default() += 1;
+
+        Ok(())
+    }
+
+    /// Removes the link of the given locator to its content, if any, and the content itself once no other locator
+    /// links to it. Returns `true` if there was a link.
+    fn unlink_content(&self, locator: &Locator) -> Result<bool, CoordinatorError> {
+        let hash = match self.content_hash(locator)? {
+            Some(hash) => hash,
+            None => return Ok(false),
+        };
+        fs::remove_file(self.to_path(locator)?.as_path())?;
+        self.release_content(&hash)?;
+
+        Ok(true)
+    }
+
+    /// Releases a link to the content with the given hash, once removed or replaced, and removes the content,
+    /// compressed or not, if no locator links to it anymore.
+    fn release_content(&self, hash: &str) -> Result<(), CoordinatorError> {
+        let mut index = self.content_index();
+        match index.links.get_mut(hash) {
+            Some(links) if *links > 1 => {
+                *links -= 1;
+                return Ok(());
+            }
+            _ => index.links.remove(hash),
+        };
+
+        trace!("Removing the content {}, no longer linked", hash);
+        for content in vec![hash.to_owned(), compressed_name(hash)] {
+            index.checked.remove(&content);
+            if self.content_path(&content).exists() {
+                fs::remove_file(self.content_path(&content))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the [ContentIndex] of the storage.
+    fn content_index(&self) -> MutexGuard<ContentIndex> {
+        self.contents.lock().expect("Content index poisoned")
+    }
+
+    /// Checks the content with the given name with `check` unless it has been checked already and not modified since.
+    fn check_content<F>(&self, content: &str, check: F) -> Result<(), CoordinatorError>
+    where
+        F: FnOnce(&str) -> Result<(), CoordinatorError>,
+    {
+        let metadata = fs::metadata(self.content_path(content))?;
+        let version = (metadata.len(), metadata.modified()?);
+        if self.content_index().checked.get(content) == Some(&version) {
+            return Ok(());
+        }
+
+        check(content.trim_end_matches(&compressed_name("")))?;
+        self.content_index().checked.insert(content.to_owned(), version);
+
+        Ok(())
+    }
+
+    /// Replaces the link of the given locator to its content, if any, or its encrypted file with a plain copy of the
+    /// content, for the file to be modified in place.
+    fn detach_content(&self, locator: &Locator) -> Result<(), CoordinatorError> {
+        if self.content_hash(locator)?.is_none() && !is_encrypted_file(self.to_path(locator)?.as_path())? {
+            return Ok(());
+        }
+
+        let path = self.to_path(locator)?;
+        let copy = format!("{}.detached", path);
+        io::copy(&mut self.open(locator)?, &mut File::create(&copy)?)?;
+        self.unlink_content(locator)?;
+        fs::rename(&copy, path.as_path())?;
+
+        Ok(())
+    }
+
+    /// Records the transition in the write-ahead log, if the state objects are kept in files.
+    pub fn log_transition(&mut self, transition: StateTransition) -> Result<(), CoordinatorError> {
+        info!(?transition, "State transition");
+        match &mut self.wal {
+            Some(wal) => wal.append(&WalRecord::Transition(transition)),
+            None => Ok(()),
+        }
+    }
+
+    /// Signs the event with the given key of the coordinator and appends it to the audit log.
+    pub fn log_audit_event(
+        &mut self,
+        signature: &dyn Signature,
+        signing_key: &str,
+        coordinator_public_key: &str,
+        timestamp: i64,
+        event: AuditEvent,
+    ) -> Result<AuditRecord, CoordinatorError> {
+        self.audit
+            .append(signature, signing_key, coordinator_public_key, timestamp, event)
+    }
+
+    /// Returns the records of the audit log, the oldest first.
+    pub fn audit_records(&self) -> Result<Vec<AuditRecord>, CoordinatorError> {
+        self.audit.records()
+    }
+
+    /// Appends the record to the write-ahead log if the given locator is a state object kept in a file.
+    fn log<F: FnOnce() -> WalRecord>(&mut self, locator: &Locator, record: F) -> Result<(), CoordinatorError> {
+        let base = Path::new(self.environment.local_base_directory());
+        let name = relative_name(base, self.to_path(locator)?.as_path())?;
+        match &mut self.wal {
+            Some(wal) if locator.is_state() => {
+                // The objects are logged as they're written to their file, for the replay to copy them as they are
+                let record = match (record(), &self.cipher) {
+                    (WalRecord::Update { locator, object }, Some(cipher)) => {
+                        let bytes = base64::decode(object).map_err(anyhow::Error::from)?;
+                        WalRecord::update(locator, &cipher.encrypt(&bytes, &name)?)
+                    }
+                    (record, _) => record,
+                };
+                wal.append(&record)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Truncates the write-ahead log once the write it records has been applied, for the log to only ever hold the
+    /// records of the write in progress.
+    fn checkpoint(&mut self) -> Result<(), CoordinatorError> {
+        match &mut self.wal {
+            Some(wal) if !wal.is_empty() => wal.checkpoint(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Applies the records left in the write-ahead log by the previous run, then truncates it.
+    fn replay(&mut self, records: Vec<WalRecord>) -> Result<(), CoordinatorError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        info!("Replaying {} records of the write-ahead log", records.len());
+        for record in records {
+            match record {
+                WalRecord::Transition(transition) => debug!("Replaying {:?}", transition),
+                WalRecord::Update { locator, object } => {
+                    let path = self.to_path(&locator)?;
+                    if let Some(parent) = path.as_path().parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(path, base64::decode(object).map_err(anyhow::Error::from)?)?;
+                }
+                WalRecord::Remove { locator } => {
+                    if self.exists(&locator) {
+                        fs::remove_file(self.to_path(&locator)?)?;
+                    }
+                }
+            }
+        }
+
+        match &mut self.wal {
+            Some(wal) => wal.checkpoint(),
+            None => Ok(()),
+        }
     }
 
-    /// Removes the object corresponding to the given locator from storage.
-    pub fn remove(&mut self, locator: &Locator) -> Result<(), CoordinatorError> {
-        let path = self.to_path(locator)?;
-        trace!("Removing {}", path);
+    /// Prepares the copy of the object at the given locator under the same path in the `replica` directory, to be
+    /// run once the storage is released. A sealed file is copied from its content, which never changes, and any other
+    /// object is read right away.
+    pub fn replica_copy(&self, locator: &Locator, replica: &Path) -> Result<ReplicaCopy, CoordinatorError> {
+        let base = Path::new(self.environment.local_base_directory());
+        let name = relative_name(base, self.to_path(locator)?.as_path())?;
+        let source = match self.content_name(locator)? {
+            Some(content) => {
+                let path = self.content_path(&content);
+                let content = relative_name(base, &path)?;
+                ReplicaSource::Content(path, content)
+            }
+            None => ReplicaSource::Bytes(self.read(locator)?),
+        };
 
-        // Check that the locator exists in storage.
-        if !self.exists(&locator) {
-            error!("Locator in call to remove() doesn't exist in storage.");
-            return Err(CoordinatorError::StorageLocatorMissing);
-        }
+        Ok(ReplicaCopy {
+            target: replica.join(&name),
+            name,
+            source,
+            cipher: self.cipher.clone(),
+        })
+    }
 
-        // TODO: if any of the locators are directories, make this
-        // detect whether the path is a directory of a file and call
-        // the appropriate function.
-        fs::remove_file(path.clone())?;
+    /// Collects the files of a snapshot of the storage, written by [PendingSnapshot::run] once the storage is released.
+    /// The files are linked in a staging directory as they are now: they're replaced by renames, so the links keep
+    /// them from the later writes. The state objects kept in a state storage are read right away.
+    pub fn snapshot(&self, created_at: OffsetDateTime) -> Result<PendingSnapshot, CoordinatorError> {
+        let base = Path::new(self.environment.local_base_directory());
+        let round_height = match self.get(&Locator::RoundHeight)? {
+            Object::RoundHeight(round_height) => round_height,
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
+        trace!("Taking a snapshot of round {}", round_height);
+
+        fs::create_dir_all(base.join(SNAPSHOT_DIRECTORY))?;
+        let staging = tempfile::tempdir_in(base.join(SNAPSHOT_DIRECTORY))?;
+        let mut files = vec![];
+        // The log only holds records already applied to the files, and the audit log goes on across the restores
+        for name in list_files(base)? {
+            // The contents are archived under the locators linking to them
+            if name != WAL_FILE
+                && name != AUDIT_LOG_FILE
+                && !name.starts_with(RESTORE_DIRECTORY)
+                && !name.starts_with(SNAPSHOT_DIRECTORY)
+                && !name.starts_with(CONTENTS_DIRECTORY)
+                && !name.starts_with(PRUNED_DIRECTORY)
+                && !name.starts_with(COMPRESSED_DIRECTORY)
+            {
+                let path = base.join(&name);
+                let mut staged = staging.path().join(files.len().to_string());
+                if is_compressed_file(&path)? {
+                    staged.set_extension(ContentEncoding::Zstd.as_str());
+                }
+                fs::hard_link(fs::canonicalize(&path)?, &staged)?;
+                files.push(SnapshotFile {
+                    encryption_name: self.encryption_name(&path)?,
+                    may_be_plain: self.may_be_plain(&path)?,
+                    name,
+                    path: staged,
+                });
+            }
+        }
+        let mut objects = vec![];
+        if self.state.is_some() {
+            for locator in state_locators(round_height) {
+                if self.exists(&locator) {
+                    objects.push((
+                        relative_name(base, self.to_path(&locator)?.as_path())?,
+                        self.read(&locator)?,
+                    ));
+                }
+            }
+        }
 
-        trace!("Removed {}", path);
-        Ok(())
+        Ok(PendingSnapshot {
+            round_height,
+            created_at,
+            staging,
+            files,
+            objects,
+            cipher: self.cipher.clone(),
+        })
     }
 
-    /// Returns the size of the object stored at the given locator.
-    pub fn size(&self, locator: &Locator) -> Result<u64, CoordinatorError> {
-        let path = self.to_path(locator)?;
-        trace!("Fetching size of {}", path);
+    /// Replaces the content of the storage with a snapshot written by [Self::snapshot], once each of its files has
+    /// been checked against its manifest. Returns the manifest.
+    pub fn restore<R: Read>(&mut self, reader: R) -> Result<SnapshotManifest, CoordinatorError> {
+        let base = PathBuf::from(self.environment.local_base_directory());
+        let staging = base.join(RESTORE_DIRECTORY);
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir_all(&staging)?;
 
-        // Check that the given locator exists in storage.
-        if !self.exists(locator) {
-            error!("Locator missing in call to size() in storage.");
-            return Err(CoordinatorError::StorageLocatorMissing);
+        let manifest = match unpack_snapshot(reader, &staging) {
+            Ok(manifest) => manifest,
+            Err(error) => {
+                fs::remove_dir_all(&staging)?;
+                return Err(error);
+            }
+        };
+        info!(
+            "Restoring the snapshot of round {} taken at {}",
+            manifest.round_height, manifest.created_at
+        );
+
+        if let Some(state) = self.state.as_deref() {
+            // Also remove the states of the rounds started after the snapshot
+            let round_height = match self.get(&Locator::RoundHeight) {
+                Ok(Object::RoundHeight(round_height)) => round_height.max(manifest.round_height),
+                _ => manifest.round_height,
+            };
+            for locator in state_locators(round_height) {
+                let path = staging.join(relative_name(&base, self.to_path(&locator)?.as_path())?);
+                if path.exists() {
+                    state.put(&locator, &fs::read(&path)?)?;
+                    fs::remove_file(&path)?;
+                } else if state.exists(&locator)? {
+                    state.remove(&locator)?;
+                }
+            }
         }
 
-        // Open the file.
-        let file = OpenOptions::new().read(true).write(true).open(path.clone())?;
+        // Swap the files of the snapshot in
+        for entry in fs::read_dir(&base)? {
+            let entry = entry?;
+            if entry.file_name() == WAL_FILE
+                || entry.file_name() == AUDIT_LOG_FILE
+                || entry.file_name() == RESTORE_DIRECTORY
+                || entry.file_name() == SNAPSHOT_DIRECTORY
+            {
+                continue;
+            }
+            match entry.path().is_dir() {
+                true => fs::remove_dir_all(entry.path())?,
+                false => fs::remove_file(entry.path())?,
+            }
+        }
+        for entry in fs::read_dir(&staging)? {
+            let entry = entry?;
+            fs::rename(entry.path(), base.join(entry.file_name()))?;
+        }
+        fs::remove_dir(&staging)?;
+        fs::create_dir_all(base.join("contributors"))?;
+        *self.content_index() = ContentIndex::load(&base)?;
+
+        // The archived contribution files are stored back under their hash, and the other objects encrypted again
+        for name in list_files(&base)? {
+            let path = base.join(&name);
+            if let Ok(locator) = self.to_locator(&LocatorPath::try_from(path.as_path())?) {
+                if locator.is_content_addressed() {
+                    self.seal(&locator)?;
+                } else if self.cipher.is_some() && !is_encrypted_file(&path)? {
+                    let bytes = fs::read(&path)?;
+                    self.write_file(&path, |writer| Ok(writer.write_all(&bytes)?))?;
+                }
+            }
+        }
 
-        trace!("Fetched size of {}", path);
-        Ok(file.metadata()?.len())
+        // The records left in the log belong to the replaced files
+        if let Some(wal) = &mut self.wal {
+            wal.checkpoint()?;
+        }
+
+        info!("Restored the snapshot of round {}", manifest.round_height);
+        Ok(manifest)
     }
 
     /// Process a [StorageAction] which mutates the storage.
@@ -468,6 +1335,566 @@ impl Disk {
     }
 }
 
+/// A file of the storage to be streamed to a client, see [Disk::serve_file].
+pub enum ServedFile {
+    /// A plain file, streamed as it is
+    File(File),
+    /// A file decrypted, decompressed or compressed as it's read
+    Decoded(Box<dyn Read + Send>),
+}
+
+impl Read for ServedFile {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::File(file) => file.read(buffer),
+            Self::Decoded(reader) => reader.read(buffer),
+        }
+    }
+}
+
+/// A file of the storage returned by [Disk::stored_file].
+pub struct StoredFile {
+    base: PathBuf,
+    path: PathBuf,
+    may_be_plain: bool,
+    cipher: Option<StorageCipher>,
+}
+
+impl StoredFile {
+    /// Opens the file for reading, decoded as [Disk::open] does. A link is followed to the content it links to when
+    /// opened, for a content compressed meanwhile to be read.
+    pub fn open(&self) -> Result<Box<dyn Read + Send>, CoordinatorError> {
+        open_file(
+            &self.path,
+            self.cipher.as_ref(),
+            &encryption_name(&self.base, &self.path)?,
+            self.may_be_plain,
+        )
+    }
+}
+
+/// A file of the storage linked in the staging directory of a [PendingSnapshot].
+struct SnapshotFile {
+    /// Path of the file relative to the base directory, under which it's archived
+    name: String,
+    /// Path of the link in the staging directory
+    path: PathBuf,
+    encryption_name: String,
+    may_be_plain: bool,
+}
+
+/// A snapshot of the storage collected by [Disk::snapshot].
+pub struct PendingSnapshot {
+    round_height: u64,
+    created_at: OffsetDateTime,
+    /// The directory of the linked files, removed once the snapshot is written
+    staging: tempfile::TempDir,
+    files: Vec<SnapshotFile>,
+    /// The state objects kept in a state storage, with their path relative to the base directory
+    objects: Vec<(String, Vec<u8>)>,
+    cipher: Option<StorageCipher>,
+}
+
+impl PendingSnapshot {
+    /// Writes to `writer` the snapshot: a gzipped tarball of the files, the state objects included, followed by a
+    /// [SnapshotManifest] with their hashes. Returns the manifest. The files are archived decrypted, for the snapshot
+    /// to be restored with another key, so it must be kept as safe as the storage itself. They're streamed, the ones
+    /// encrypted or compressed at rest being decoded twice, to hash them and know their size, then to archive them.
+    pub fn run<W: Write>(self, writer: W) -> Result<SnapshotManifest, CoordinatorError> {
+        let mut manifest = SnapshotManifest {
+            round_height: self.round_height,
+            created_at: self.created_at,
+            files: BTreeMap::new(),
+        };
+        let mut archive = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+        for file in &self.files {
+            let open = || {
+                open_file(
+                    &file.path,
+                    self.cipher.as_ref(),
+                    &file.encryption_name,
+                    file.may_be_plain,
+                )
+            };
+            let mut hasher = Blake2b512::new();
+            let size = io::copy(&mut open()?, &mut hasher)?;
+            if is_encrypted_file(&file.path)? || is_compressed_file(&file.path)? {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(size);
+                header.set_mode(0o644);
+                header.set_cksum();
+                archive.append_data(&mut header, &file.name, open()?)?;
+            } else {
+                archive.append_path_with_name(&file.path, &file.name)?;
+            }
+            manifest.files.insert(file.name.clone(), hex::encode(hasher.finalize()));
+        }
+        for (name, bytes) in &self.objects {
+            append_entry(&mut archive, name, bytes)?;
+            manifest.files.insert(name.clone(), hex::encode(calculate_hash(bytes)));
+        }
+
+        append_entry(&mut archive, SNAPSHOT_MANIFEST, &serde_json::to_vec_pretty(&manifest)?)?;
+        archive.into_inner()?.finish()?;
+        self.staging.close()?;
+
+        info!(
+            "Took a snapshot of round {} with {} files",
+            manifest.round_height,
+            manifest.files.len()
+        );
+        Ok(manifest)
+    }
+}
+
+/// The compression of the contents of a completed round, collected by [Disk::compress_round].
+pub struct RoundCompression {
+    round_height: u64,
+    base: PathBuf,
+    /// Names of the contents to compress, with the paths of the files linking to them
+    contents: BTreeMap<String, Vec<PathBuf>>,
+    cipher: Option<StorageCipher>,
+}
+
+impl RoundCompression {
+    /// Compresses the contents, then points the files linking to them to the compressed ones. A file which no longer
+    /// links to its content, having been pruned meanwhile, is left as is.
+    pub fn run(self) -> Result<(), CoordinatorError> {
+        let contents = self.base.join(CONTENTS_DIRECTORY);
+        for (content, paths) in &self.contents {
+            let source = contents.join(content);
+            let target = contents.join(compressed_name(content));
+            let mut reader = open_file(
+                &source,
+                self.cipher.as_ref(),
+                &relative_name(&self.base, &source)?,
+                false,
+            )?;
+            write_encrypted(
+                &target,
+                self.cipher.as_ref(),
+                &relative_name(&self.base, &target)?,
+                |writer| compression::compress(&mut reader, writer, ContentEncoding::Zstd),
+            )?;
+
+            // The uncompressed content is removed once no link points to it anymore
+            for path in paths {
+                let is_linked = fs::symlink_metadata(path).map_or(false, |metadata| metadata.file_type().is_symlink())
+                    && fs::read_link(path)?.file_name() == Some(content.as_ref());
+                if is_linked {
+                    link_to_content(&self.base, path, &compressed_name(content))?;
+                }
+            }
+            fs::remove_file(&source)?;
+            debug!("Compressed the content {} of round {}", content, self.round_height);
+        }
+
+        info!("Compressed the files of round {}", self.round_height);
+        Ok(())
+    }
+}
+
+/// The copy to the archive directory of the files pruned from the storage, see [Disk::pruned_archive].
+pub struct PrunedArchive {
+    pruned: PathBuf,
+    archive: PathBuf,
+}
+
+impl PrunedArchive {
+    /// Copies the pruned files to the archive directory, under their path in the storage, then removes them. The files
+    /// left by an interrupted run are copied by the next one.
+    pub fn run(self) -> Result<usize, CoordinatorError> {
+        if !self.pruned.exists() {
+            return Ok(0);
+        }
+
+        let names = list_files(&self.pruned)?;
+        for name in &names {
+            let destination = self.archive.join(name);
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(self.pruned.join(name), &destination)?;
+            fs::remove_file(self.pruned.join(name))?;
+        }
+
+        info!("Archived {} pruned files", names.len());
+        Ok(names.len())
+    }
+}
+
+/// The copy of an object of the storage to the replica, prepared by [Disk::replica_copy].
+pub struct ReplicaCopy {
+    target: PathBuf,
+    /// The path of the object relative to the base directory, which it's encrypted under
+    name: String,
+    source: ReplicaSource,
+    cipher: Option<StorageCipher>,
+}
+
+/// Where a [ReplicaCopy] takes the object from.
+enum ReplicaSource {
+    /// The object, read when the copy was prepared
+    Bytes(Vec<u8>),
+    /// The path of the content the object is sealed as, and the name it's encrypted under
+    Content(PathBuf, String),
+}
+
+impl ReplicaCopy {
+    /// Writes the object to the replica atomically, encrypted like the storage. Returns the size of the object, or
+    /// `None` if its content was released from the storage meanwhile.
+    pub fn run(self) -> Result<Option<u64>, CoordinatorError> {
+        let mut reader: Box<dyn Read + '_> = match &self.source {
+            ReplicaSource::Bytes(bytes) => Box::new(bytes.as_slice()),
+            ReplicaSource::Content(path, name) => match open_file(path, self.cipher.as_ref(), name, false) {
+                Ok(reader) => reader,
+                Err(CoordinatorError::IOError(e)) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => return Err(e),
+            },
+        };
+        if let Some(parent) = self.target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut size = 0;
+        write_encrypted(&self.target, self.cipher.as_ref(), &self.name, |writer| {
+            size = io::copy(&mut reader, writer)?;
+            Ok(())
+        })?;
+
+        Ok(Some(size))
+    }
+}
+
+/// Returns the locators of the state objects of the ceremony up to the given round.
+fn state_locators(round_height: u64) -> impl Iterator<Item = Locator> {
+    vec![Locator::CoordinatorState, Locator::RoundHeight]
+        .into_iter()
+        .chain((0..=round_height).map(|round_height| Locator::RoundState { round_height }))
+}
+
+/// Points the file at the given path in the `base` directory to the content with the given name, replacing the file
+/// if any. The link is relative, for the base directory to be moved.
+fn link_to_content(base: &Path, path: &Path, content: &str) -> Result<(), CoordinatorError> {
+    let depth = Path::new(&relative_name(base, path)?).components().count();
+    let target = std::iter::repeat("..")
+        .take(depth - 1)
+        .collect::<PathBuf>()
+        .join(CONTENTS_DIRECTORY)
+        .join(content);
+
+    let mut link = path.as_os_str().to_owned();
+    link.push(".link");
+    let link = PathBuf::from(link);
+    if link.exists() {
+        fs::remove_file(&link)?;
+    }
+    symlink(&target, &link)?;
+    fs::rename(&link, path)?;
+
+    Ok(())
+}
+
+/// Returns the path of the given file relative to `base`.
+fn relative_name(base: &Path, path: &Path) -> Result<String, CoordinatorError> {
+    path.strip_prefix(base)
+        .ok()
+        .and_then(Path::to_str)
+        .map(str::to_owned)
+        .ok_or(CoordinatorError::StorageLocatorFormatIncorrect)
+}
+
+/// Returns the name of the compressed content with the given name.
+fn compressed_name(content: &str) -> String {
+    format!("{}.{}", content, ContentEncoding::Zstd.as_str())
+}
+
+/// Opens the file at the given path for reading, decrypting it on the fly with `cipher` if it's encrypted under the
+/// given name, then decompressing it if it's or links to a compressed content. A plain file is only read without a
+/// cipher or if it `may_be_plain`.
+fn open_file(
+    path: &Path,
+    cipher: Option<&StorageCipher>,
+    name: &str,
+    may_be_plain: bool,
+) -> Result<Box<dyn Read + Send>, CoordinatorError> {
+    let file = io::BufReader::new(File::open(path)?);
+    let is_compressed = is_compressed_file(path)?;
+
+    let file: Box<dyn io::BufRead + Send> = match (is_encrypted_file(path)?, cipher) {
+        (true, Some(cipher)) => Box::new(io::BufReader::new(cipher.decryptor(file, name)?)),
+        (true, None) => return Err(CoordinatorError::StorageEncryptionKeyMissing),
+        (false, Some(_)) if !may_be_plain => {
+            error!("{} isn't encrypted although the storage is", path.display());
+            return Err(CoordinatorError::StorageFileUnencrypted);
+        }
+        (false, _) => Box::new(file),
+    };
+    match is_compressed {
+        true => Ok(Box::new(zstd::Decoder::with_buffer(file)?)),
+        false => Ok(Box::new(file)),
+    }
+}
+
+/// Returns the name the file at the given path in the base directory is encrypted under: its path relative to the
+/// base directory, or the one of its content if it links to one.
+fn encryption_name(base: &Path, path: &Path) -> Result<String, CoordinatorError> {
+    if fs::symlink_metadata(path)?.file_type().is_symlink() {
+        if let Some(content) = fs::read_link(path)?.file_name().and_then(|name| name.to_str()) {
+            return relative_name(base, &base.join(CONTENTS_DIRECTORY).join(content));
+        }
+    }
+
+    relative_name(base, path)
+}
+
+/// Returns `true` if the file at the given path is compressed at rest, being or linking to a compressed content.
+fn is_compressed_file(path: &Path) -> Result<bool, CoordinatorError> {
+    let target = match fs::symlink_metadata(path)?.file_type().is_symlink() {
+        true => fs::read_link(path)?,
+        false => path.to_path_buf(),
+    };
+    Ok(target.extension() == Some(ContentEncoding::Zstd.as_str().as_ref()))
+}
+
+/// Returns `true` if the file at the given path is encrypted, see [StorageCipher].
+fn is_encrypted_file(path: &Path) -> Result<bool, CoordinatorError> {
+    let mut head = vec![];
+    File::open(path)?
+        .take(encryption::MAGIC.len() as u64)
+        .read_to_end(&mut head)?;
+    Ok(encryption::is_encrypted(&head))
+}
+
+/// Writes atomically the file at the given path with the bytes written by `write`, encrypted under the given name
+/// with `cipher` if any.
+fn write_encrypted<F>(path: &Path, cipher: Option<&StorageCipher>, name: &str, write: F) -> Result<(), CoordinatorError>
+where
+    F: FnOnce(&mut dyn Write) -> Result<(), CoordinatorError>,
+{
+    write_atomically(path, |file| match cipher {
+        Some(cipher) => {
+            let mut encryptor = cipher.encryptor(io::BufWriter::new(file), name)?;
+            write(&mut encryptor)?;
+            encryptor.finish()?.flush()?;
+            Ok(())
+        }
+        None => write(file),
+    })
+}
+
+/// Writes the file at the given path atomically: `write` fills a temporary file next to it, which is synced and
+/// renamed over the path, and the directory is then synced for the rename to survive a crash. An interrupted write
+/// leaves the previous file untouched, and a stale temporary file removed on the next load.
+fn write_atomically<F>(path: &Path, write: F) -> Result<(), CoordinatorError>
+where
+    F: FnOnce(&mut File) -> Result<(), CoordinatorError>,
+{
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".");
+    temporary.push(TEMPORARY_EXTENSION);
+    let temporary = PathBuf::from(temporary);
+    let mut file = File::create(&temporary)?;
+    write(&mut file)?;
+    file.sync_all()?;
+    fs::rename(&temporary, path)?;
+
+    sync_directory(path.parent().unwrap_or_else(|| Path::new(".")))
+}
+
+/// Syncs the entries of the given directory to disk.
+#[cfg(unix)]
+fn sync_directory(directory: &Path) -> Result<(), CoordinatorError> {
+    File::open(directory)?.sync_all()?;
+    Ok(())
+}
+
+/// Syncs the entries of the given directory to disk, which Windows does along with the files.
+#[cfg(windows)]
+fn sync_directory(_directory: &Path) -> Result<(), CoordinatorError> {
+    Ok(())
+}
+
+/// Maps the file at the given path into memory, read only. The file must not be truncated while mapped, which the
+/// readers of the storage ensure by being dropped before it's written to again.
+fn map_file(path: &Path) -> Result<ReaderData, CoordinatorError> {
+    let file = File::open(path)?;
+    // Empty files can't be mapped
+    if file.metadata()?.len() == 0 {
+        return Ok(ReaderData::Owned(vec![]));
+    }
+    let memmap = unsafe { MmapOptions::new().map(&file.file())? };
+
+    Ok(ReaderData::Mapped { _file: file, memmap })
+}
+
+/// Creates a symbolic link at `link` pointing to the file at `target`.
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+/// Creates a symbolic link at `link` pointing to the file at `target`.
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link)
+}
+
+/// Returns the paths, relative to the given directory, of the files it contains, sorted.
+fn list_files(directory: &Path) -> Result<Vec<String>, CoordinatorError> {
+    let mut files = vec![];
+    let mut directories = vec![directory.to_path_buf()];
+    while let Some(current) = directories.pop() {
+        for entry in fs::read_dir(&current)? {
+            let path = entry?.path();
+            match path.is_dir() {
+                true => directories.push(path),
+                false => files.push(relative_name(directory, &path)?),
+            }
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+/// Appends a file with the given name and content to the archive.
+fn append_entry<W: Write>(archive: &mut tar::Builder<W>, name: &str, bytes: &[u8]) -> Result<(), CoordinatorError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    archive.append_data(&mut header, name, bytes)?;
+
+    Ok(())
+}
+
+/// Unpacks the snapshot to the given directory, then checks its files against its manifest.
+fn unpack_snapshot<R: Read>(reader: R, directory: &Path) -> Result<SnapshotManifest, CoordinatorError> {
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+    for entry in archive.entries()? {
+        // Entries escaping the directory are refused
+        if !entry?.unpack_in(directory)? {
+            error!("The snapshot has an entry outside of its directory");
+            return Err(CoordinatorError::SnapshotInvalid);
+        }
+    }
+
+    let manifest_path = directory.join(SNAPSHOT_MANIFEST);
+    if !manifest_path.exists() {
+        error!("The snapshot has no manifest");
+        return Err(CoordinatorError::SnapshotInvalid);
+    }
+    let manifest: SnapshotManifest = serde_json::from_slice(&fs::read(&manifest_path)?)?;
+    fs::remove_file(&manifest_path)?;
+
+    let files = list_files(directory)?;
+    for name in &files {
+        let hash = hex::encode(calculate_hash(&fs::read(directory.join(name))?));
+        if manifest.files.get(name) != Some(&hash) {
+            error!("The file {} of the snapshot doesn't match its manifest", name);
+            return Err(CoordinatorError::SnapshotInvalid);
+        }
+    }
+    if files.len() != manifest.files.len() {
+        error!("The snapshot is missing files of its manifest");
+        return Err(CoordinatorError::SnapshotInvalid);
+    }
+
+    Ok(manifest)
+}
+
+/// Deserializes the bytes stored at the given locator into an [Object], checking the size of the
+/// round and contribution files.
+fn deserialize_object(
+    environment: &Environment,
+    locator: &Locator,
+    file_bytes: Vec<u8>,
+) -> Result<Object, CoordinatorError> {
+    match locator {
+        Locator::CoordinatorState => {
+            let coordinator_state: CoordinatorState = serde_json::from_slice(&file_bytes)?;
+            Ok(Object::CoordinatorState(coordinator_state))
+        }
+        Locator::RoundHeight => {
+            let round_height: u64 = serde_json::from_slice(&file_bytes)?;
+            Ok(Object::RoundHeight(round_height))
+        }
+        Locator::RoundState { round_height: _ } => {
+            let round: Round = serde_json::from_slice(&file_bytes)?;
+            Ok(Object::RoundState(round))
+        }
+        Locator::RoundFile { round_height } => {
+            // Check that the round size is correct.
+            let expected_size = Object::round_file_size(environment);
+            let found_size = file_bytes.len() as u64;
+            debug!("Round {} filesize is {}", round_height, found_size);
+            if found_size == 0 || expected_size != found_size {
+                error!("Round file size should be {} but found {}", expected_size, found_size);
+                return Err(CoordinatorError::RoundFileSizeMismatch.into());
+            }
+
+            Ok(Object::RoundFile(file_bytes))
+        }
+        Locator::ContributionFile(contribution_locator) => {
+            // Check that the contribution size is correct.
+            let expected_size = Object::anoma_contribution_file_size(
+                environment,
+                contribution_locator.round_height(),
+                contribution_locator.contribution_id(),
+            );
+            let found_size = file_bytes.len() as u64;
+            debug!(
+                "Round {} chunk {} filesize is {}",
+                contribution_locator.round_height(),
+                contribution_locator.chunk_id(),
+                found_size
+            );
+            if found_size == 0 || expected_size != found_size {
+                error!(
+                    "Contribution file size should be {} but found {}",
+                    expected_size, found_size
+                );
+                return Err(CoordinatorError::ContributionFileSizeMismatch.into());
+            }
+
+            let mut contribution_file: Vec<u8> = Vec::with_capacity(expected_size as usize);
+            contribution_file.write_all(&file_bytes)?;
+            Ok(Object::ContributionFile(contribution_file))
+        }
+        Locator::ContributionFileSignature(contribution_locator) => {
+            // Check that the contribution file signature size is correct.
+            let expected_size = Object::contribution_file_signature_size(contribution_locator.is_verified());
+            let found_size = file_bytes.len() as u64;
+            debug!(
+                "Round {} chunk {} contribution {} signature filesize is {}",
+                contribution_locator.round_height(),
+                contribution_locator.chunk_id(),
+                contribution_locator.contribution_id(),
+                found_size
+            );
+            if found_size == 0 || expected_size != found_size {
+                error!(
+                    "Contribution signature file size should be {} but found {}",
+                    expected_size, found_size
+                );
+                return Err(CoordinatorError::ContributionSignatureFileSizeMismatch.into());
+            }
+
+            let contribution_file_signature: ContributionFileSignature = serde_json::from_slice(&file_bytes)?;
+            Ok(Object::ContributionFileSignature(contribution_file_signature))
+        }
+        Locator::ContributionInfoFile { round_height: _ } => {
+            let contribution_info: ContributionInfo = serde_json::from_slice(&file_bytes)?;
+            Ok(Object::ContributionInfoFile(contribution_info))
+        }
+        Locator::ContributionsInfoSummary => {
+            let summary: Vec<TrimmedContributionInfo> = serde_json::from_slice(&file_bytes)?;
+            Ok(Object::ContributionsInfoSummary(summary))
+        }
+    }
+}
+
 impl StorageLocator for Disk {
     #[inline]
     fn to_path(&self, locator: &Locator) -> Result<LocatorPath, CoordinatorError> {
@@ -480,8 +1907,25 @@ impl StorageLocator for Disk {
     }
 }
 
+/// The bytes of a [DiskObjectReader], in memory or mapped from the file.
+enum ReaderData {
+    Owned(Vec<u8>),
+    Mapped { _file: File, memmap: memmap::Mmap },
+}
+
+impl Deref for ReaderData {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Owned(data) => data,
+            Self::Mapped { memmap, .. } => memmap,
+        }
+    }
+}
+
 pub struct DiskObjectReader {
-    data: Vec<u8>,
+    data: ReaderData,
 }
 
 impl Deref for DiskObjectReader {
@@ -494,7 +1938,7 @@ impl Deref for DiskObjectReader {
 
 impl AsRef<[u8]> for DiskObjectReader {
     fn as_ref(&self) -> &[u8] {
-        self.data.as_ref()
+        &*self.data
     }
 }
 
@@ -546,13 +1990,40 @@ impl StorageObject for Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
-        let file = OpenOptions::new().read(true).open(path)?;
+        if self.state_storage(locator).is_some() {
+            return Ok(DiskObjectReader {
+                data: ReaderData::Owned(self.read(locator)?),
+            });
+        }
 
-        // Load the file into memory.
-        let mut data = vec![];
-        file.file()
-            .read_to_end(&mut data)
-            .map_err(|e| CoordinatorError::IOError(e))?;
+        // Map the file into memory, for the large contribution files not to be copied on the heap. The compressed
+        // and encrypted contents have to be decoded first.
+        let is_plain = !self.is_compressed(locator)? && !is_encrypted_file(path.as_path())?;
+        if is_plain && !self.may_be_plain(path.as_path())? {
+            error!("{} isn't encrypted although the storage is", path);
+            return Err(CoordinatorError::StorageFileUnencrypted);
+        }
+        let data = match is_plain {
+            true => map_file(path.as_path())?,
+            false => {
+                let mut data = vec![];
+                self.open(locator)?
+                    .read_to_end(&mut data)
+                    .map_err(|e| CoordinatorError::IOError(e))?;
+                ReaderData::Owned(data)
+            }
+        };
+
+        // A file stored under its hash is checked against it, once until it's modified
+        if let Some(content) = self.content_name(locator)? {
+            self.check_content(&content, |hash| match hex::encode(calculate_hash(&data)) == hash {
+                true => Ok(()),
+                false => {
+                    error!("The content of {} doesn't match its hash {}", path, hash);
+                    Err(CoordinatorError::StorageContentCorrupted)
+                }
+            })?;
+        }
 
         match locator {
             Locator::RoundFile { round_height } => {
@@ -571,6 +2042,7 @@ impl StorageObject for Disk {
             Locator::ContributionFile(contribution_locator) => {
                 // Check that the contribution size is correct.
                 let expected_size = Object::anoma_contribution_file_size(
+                    &self.environment,
                     contribution_locator.round_height(),
                     contribution_locator.contribution_id(),
                 );
@@ -606,6 +2078,14 @@ impl StorageObject for Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
+        // The state objects are only updated as a whole
+        if self.state_storage(locator).is_some() {
+            error!("Locator {} in call to writer() is in the state storage.", path);
+            return Err(CoordinatorError::StorageFailed);
+        }
+
+        // The stored content may be shared with other locators
+        self.detach_content(locator)?;
         let file = OpenOptions::new().read(true).write(true).open(path)?;
 
         // Load the file into memory.
@@ -627,6 +2107,7 @@ impl StorageObject for Disk {
             Locator::ContributionFile(contribution_locator) => {
                 // Check that the contribution size is correct.
                 let expected_size = Object::anoma_contribution_file_size(
+                    &self.environment,
                     contribution_locator.round_height(),
                     contribution_locator.contribution_id(),
                 );
@@ -648,13 +2129,13 @@ impl StorageObject for Disk {
 }
 
 #[derive(Debug)]
-struct DiskResolver {
+pub(super) struct DiskResolver {
     base: String,
 }
 
 impl DiskResolver {
     #[inline]
-    fn new(base: &str) -> Self {
+    pub(super) fn new(base: &str) -> Self {
         Self { base: base.to_string() }
     }
 }
@@ -901,8 +2382,377 @@ impl DiskResolver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        environment::{Parameters, Testing},
+        storage::MemoryStorage,
+    };
     // use crate::testing::prelude::*;
 
+    #[test]
+    fn test_wal_replay() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .base_dir(base_dir.path().to_str().unwrap())
+            .into();
+        let mut storage = Disk::load(&environment).unwrap();
+        storage.insert(Locator::RoundHeight, Object::RoundHeight(3)).unwrap();
+        drop(storage);
+
+        // Crash while rewriting the round height, after its record was logged
+        let (mut wal, _) = WriteAheadLog::open(&base_dir.path().join(WAL_FILE)).unwrap();
+        wal.append(&WalRecord::update(Locator::RoundHeight, b"4")).unwrap();
+        fs::write(base_dir.path().join("round_height"), b"").unwrap();
+
+        let mut storage = Disk::load(&environment).unwrap();
+        assert!(matches!(storage.get(&Locator::RoundHeight), Ok(Object::RoundHeight(4))));
+        assert_eq!(fs::metadata(base_dir.path().join(WAL_FILE)).unwrap().len(), 0);
+
+        // The log is truncated once the write is applied
+        storage.update(&Locator::RoundHeight, Object::RoundHeight(5)).unwrap();
+        assert_eq!(fs::metadata(base_dir.path().join(WAL_FILE)).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_atomic_write() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .base_dir(base_dir.path().to_str().unwrap())
+            .into();
+        let mut storage = Disk::load(&environment).unwrap();
+        storage.insert(Locator::RoundHeight, Object::RoundHeight(3)).unwrap();
+        storage.update(&Locator::RoundHeight, Object::RoundHeight(4)).unwrap();
+        let temporary = base_dir.path().join("round_height.tmp");
+        assert!(!temporary.exists());
+        drop(storage);
+
+        // Crash while writing the temporary file of the round height
+        fs::write(&temporary, b"5").unwrap();
+
+        let storage = Disk::load(&environment).unwrap();
+        assert!(matches!(storage.get(&Locator::RoundHeight), Ok(Object::RoundHeight(4))));
+        assert!(!temporary.exists());
+    }
+
+    #[test]
+    fn test_state_storage() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .base_dir(base_dir.path().to_str().unwrap())
+            .into();
+        let state = MemoryStorage::default();
+        let mut storage = Disk::load_with_state(&environment, Some(Box::new(state.clone()))).unwrap();
+
+        // The state objects are kept out of the base directory, unlike the contribution files
+        assert!(state.exists(&Locator::CoordinatorState).unwrap());
+        assert!(storage.exists(&Locator::CoordinatorState));
+        assert!(!base_dir.path().join("coordinator.json").exists());
+        assert!(!base_dir.path().join(WAL_FILE).exists());
+        assert!(storage.exists(&Locator::ContributionsInfoSummary));
+        assert!(!state.exists(&Locator::ContributionsInfoSummary).unwrap());
+
+        storage.insert(Locator::RoundHeight, Object::RoundHeight(3)).unwrap();
+        assert!(matches!(storage.get(&Locator::RoundHeight), Ok(Object::RoundHeight(3))));
+        storage.update(&Locator::RoundHeight, Object::RoundHeight(4)).unwrap();
+        assert!(matches!(storage.get(&Locator::RoundHeight), Ok(Object::RoundHeight(4))));
+        assert_eq!(state.get(&Locator::RoundHeight).unwrap().unwrap(), b"4");
+        assert_eq!(storage.size(&Locator::RoundHeight).unwrap(), 1);
+        assert!(storage.get_coordinator_state().is_ok());
+
+        // The state survives a new instance of the storage
+        let mut storage = Disk::load_with_state(&environment, Some(Box::new(state.clone()))).unwrap();
+        assert!(matches!(storage.get(&Locator::RoundHeight), Ok(Object::RoundHeight(4))));
+
+        storage.remove(&Locator::RoundHeight).unwrap();
+        assert!(!storage.exists(&Locator::RoundHeight));
+        assert!(!state.exists(&Locator::RoundHeight).unwrap());
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let (source_dir, target_dir) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let load = |dir: &tempfile::TempDir| {
+            let environment: Environment = Testing::from(Parameters::Test8Chunks)
+                .base_dir(dir.path().to_str().unwrap())
+                .into();
+            Disk::load(&environment).unwrap()
+        };
+        let contribution = Locator::ContributionFile(ContributionLocator::new(2, 0, 0, true));
+
+        let mut source = load(&source_dir);
+        source.insert(Locator::RoundHeight, Object::RoundHeight(2)).unwrap();
+        source.initialize(contribution, 8).unwrap();
+        let mut snapshot = vec![];
+        let pending = source.snapshot(OffsetDateTime::now_utc()).unwrap();
+
+        // The snapshot is written from the files as they were when collected
+        source.update(&Locator::RoundHeight, Object::RoundHeight(3)).unwrap();
+        let manifest = pending.run(&mut snapshot).unwrap();
+        let staging = source_dir.path().join(SNAPSHOT_DIRECTORY);
+        assert_eq!(fs::read_dir(staging).unwrap().count(), 0);
+        assert_eq!(manifest.round_height, 2);
+        assert!(manifest.files.contains_key("round_height"));
+        assert!(!manifest.files.contains_key(WAL_FILE));
+        assert!(!manifest.files.contains_key(AUDIT_LOG_FILE));
+
+        let mut target = load(&target_dir);
+        target.insert(Locator::RoundHeight, Object::RoundHeight(5)).unwrap();
+
+        // Wrong, no manifest
+        let mut archive = tar::Builder::new(GzEncoder::new(vec![], Compression::default()));
+        append_entry(&mut archive, "round_height", b"7").unwrap();
+        let invalid = archive.into_inner().unwrap().finish().unwrap();
+        assert!(matches!(
+            target.restore(invalid.as_slice()),
+            Err(CoordinatorError::SnapshotInvalid)
+        ));
+        assert!(matches!(target.get(&Locator::RoundHeight), Ok(Object::RoundHeight(5))));
+        assert!(!target_dir.path().join(RESTORE_DIRECTORY).exists());
+
+        assert_eq!(target.restore(snapshot.as_slice()).unwrap(), manifest);
+        assert!(matches!(target.get(&Locator::RoundHeight), Ok(Object::RoundHeight(2))));
+        assert_eq!(target.size(&contribution).unwrap(), 8);
+        assert!(!target_dir.path().join(RESTORE_DIRECTORY).exists());
+    }
+
+    #[test]
+    fn test_content_addressed_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .base_dir(dir.path().to_str().unwrap())
+            .into();
+        let mut storage = Disk::load(&environment).unwrap();
+        let contents = dir.path().join(CONTENTS_DIRECTORY);
+        let response = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false));
+        let next_challenge = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, true));
+
+        let upload = dir.path().join("upload");
+        fs::write(&upload, b"response").unwrap();
+        storage.initialize(response, 8).unwrap();
+        assert_eq!(storage.content_hash(&response).unwrap(), None);
+        storage.update_from_file(&response, &upload).unwrap();
+        let hash = storage.content_hash(&response).unwrap().unwrap();
+        assert_eq!(hash, hex::encode(calculate_hash(b"response")));
+
+        // The copy shares the content of its source
+        storage.copy(&response, &next_challenge).unwrap();
+        assert_eq!(storage.content_hash(&next_challenge).unwrap(), Some(hash.clone()));
+        assert_eq!(fs::read_dir(&contents).unwrap().count(), 1);
+
+        // Corrupted content is detected when read
+        fs::write(contents.join(&hash), b"corrupted").unwrap();
+        assert!(matches!(
+            storage.reader(&response),
+            Err(CoordinatorError::StorageContentCorrupted)
+        ));
+
+        // Updating a locator leaves the content shared with the others untouched
+        storage
+            .update(&next_challenge, Object::ContributionFile(b"challenge".to_vec()))
+            .unwrap();
+        assert_eq!(fs::read(contents.join(&hash)).unwrap(), b"corrupted");
+        assert_eq!(fs::read_dir(&contents).unwrap().count(), 2);
+
+        // The content is removed with the last locator linking to it, the links being counted again once reloaded
+        let duplicate = Locator::ContributionFile(ContributionLocator::new(1, 1, 1, false));
+        storage.copy(&response, &duplicate).unwrap();
+        drop(storage);
+        let mut storage = Disk::load(&environment).unwrap();
+        storage.remove(&response).unwrap();
+        assert!(contents.join(&hash).exists());
+        storage.remove(&duplicate).unwrap();
+        assert!(!contents.join(&hash).exists());
+        assert!(storage.exists(&next_challenge));
+    }
+
+    #[test]
+    fn test_encrypted_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .base_dir(dir.path().to_str().unwrap())
+            .storage_encryption_key(&hex::encode([1u8; 32]))
+            .into();
+        let mut storage = Disk::load(&environment).unwrap();
+        let response = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false));
+
+        // The state objects and the contents are encrypted on disk, and read back decrypted
+        let state = storage.to_path(&Locator::CoordinatorState).unwrap();
+        assert!(is_encrypted_file(state.as_path()).unwrap());
+        assert!(matches!(
+            storage.get(&Locator::CoordinatorState),
+            Ok(Object::CoordinatorState(_))
+        ));
+
+        let upload = dir.path().join("upload");
+        fs::write(&upload, b"response").unwrap();
+        storage.initialize(response, 8).unwrap();
+        storage.update_from_file(&response, &upload).unwrap();
+        let hash = storage.content_hash(&response).unwrap().unwrap();
+        assert_eq!(hash, hex::encode(calculate_hash(b"response")));
+        assert!(is_encrypted_file(&storage.content_path(&hash)).unwrap());
+        assert_eq!(storage.size(&response).unwrap(), 8);
+        assert_eq!(storage.read(&response).unwrap(), b"response");
+
+        let mut served = vec![];
+        let path = storage.to_path(&response).unwrap();
+        storage
+            .serve_file(path.as_path(), None)
+            .unwrap()
+            .read_to_end(&mut served)
+            .unwrap();
+        assert_eq!(served, b"response");
+
+        // An encrypted file copied over another, or a plain file replacing it, isn't read
+        let summary = storage.to_path(&Locator::ContributionsInfoSummary).unwrap();
+        fs::copy(state.as_path(), summary.as_path()).unwrap();
+        assert!(storage.get(&Locator::ContributionsInfoSummary).is_err());
+        fs::write(summary.as_path(), b"[]").unwrap();
+        assert!(matches!(
+            storage.get(&Locator::ContributionsInfoSummary),
+            Err(CoordinatorError::StorageFileUnencrypted)
+        ));
+
+        // The files can't be read without the key
+        drop(storage);
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .base_dir(dir.path().to_str().unwrap())
+            .into();
+        assert!(matches!(
+            Disk::load(&environment).unwrap().get(&Locator::CoordinatorState),
+            Err(CoordinatorError::StorageEncryptionKeyMissing)
+        ));
+    }
+
+    #[test]
+    fn test_compress_round() {
+        let dir = tempfile::tempdir().unwrap();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .base_dir(dir.path().to_str().unwrap())
+            .into();
+        let mut storage = Disk::load(&environment).unwrap();
+        let challenge = Locator::ContributionFile(ContributionLocator::new(1, 0, 0, true));
+        let response = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false));
+        let next_challenge = Locator::ContributionFile(ContributionLocator::new(2, 0, 0, true));
+        for (locator, bytes) in vec![(challenge, b"challenge".to_vec()), (response, b"response".to_vec())] {
+            storage.initialize(locator, 0).unwrap();
+            storage.update(&locator, Object::ContributionFile(bytes)).unwrap();
+        }
+        storage.copy(&response, &next_challenge).unwrap();
+        let read = |storage: &Disk, locator: &Locator| {
+            let mut bytes = vec![];
+            storage.open(locator).unwrap().read_to_end(&mut bytes).unwrap();
+            bytes
+        };
+
+        // The response is kept as is, being the challenge of round 2
+        storage.compress_round(1).unwrap().run().unwrap();
+        assert!(storage.is_compressed(&challenge).unwrap());
+        assert!(!storage.is_compressed(&response).unwrap());
+        let hash = hex::encode(calculate_hash(b"challenge"));
+        assert_eq!(storage.content_hash(&challenge).unwrap(), Some(hash.clone()));
+        assert!(!storage.content_path(&hash).exists());
+        assert_eq!(read(&storage, &challenge), b"challenge");
+        assert_eq!(storage.size(&challenge).unwrap(), 9);
+
+        let mut served = vec![];
+        let path = storage.to_path(&challenge).unwrap();
+        storage
+            .serve_file(path.as_path(), None)
+            .unwrap()
+            .read_to_end(&mut served)
+            .unwrap();
+        assert_eq!(served, b"challenge");
+
+        storage.compress_round(2).unwrap().run().unwrap();
+        assert!(storage.is_compressed(&response).unwrap());
+        assert!(storage.is_compressed(&next_challenge).unwrap());
+        assert_eq!(read(&storage, &response), b"response");
+    }
+
+    #[test]
+    fn test_serve_compressed_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .base_dir(dir.path().to_str().unwrap())
+            .into();
+        let mut storage = Disk::load(&environment).unwrap();
+        let challenge = Locator::ContributionFile(ContributionLocator::new(1, 0, 0, true));
+        let mut content = b"challenge".to_vec();
+        content.resize(1 << 16, 0);
+        storage.initialize(challenge, 0).unwrap();
+        storage
+            .update(&challenge, Object::ContributionFile(content.clone()))
+            .unwrap();
+        let path = storage.to_path(&challenge).unwrap();
+        let files = fs::read_dir(path.as_path().parent().unwrap()).unwrap().count();
+
+        // The compressed challenge decompresses to the original
+        let serve = |encoding| {
+            let mut served = vec![];
+            storage
+                .serve_file(path.as_path(), Some(encoding))
+                .unwrap()
+                .read_to_end(&mut served)
+                .unwrap();
+            served
+        };
+        let served = serve(ContentEncoding::Zstd);
+        assert!(served.len() < content.len());
+        assert_eq!(zstd::decode_all(served.as_slice()).unwrap(), content);
+        let mut decompressed = vec![];
+        GzDecoder::new(serve(ContentEncoding::Gzip).as_slice())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, content);
+
+        // The compressed copies are kept out of the round, in the base directory, until the round moves on
+        assert_eq!(fs::read_dir(path.as_path().parent().unwrap()).unwrap().count(), files);
+        let copies = dir.path().join(COMPRESSED_DIRECTORY).join("round_1").join("chunk_0");
+        assert_eq!(fs::read_dir(&copies).unwrap().count(), 2);
+        storage.prune_compressed_copies(1).unwrap();
+        assert_eq!(fs::read_dir(&copies).unwrap().count(), 2);
+        storage.prune_compressed_copies(2).unwrap();
+        assert!(!dir.path().join(COMPRESSED_DIRECTORY).join("round_1").exists());
+    }
+
+    #[test]
+    fn test_prune_round() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = tempfile::tempdir().unwrap();
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .base_dir(dir.path().to_str().unwrap())
+            .retention(1, archive.path().to_str())
+            .into();
+        let mut storage = Disk::load(&environment).unwrap();
+        let challenge = Locator::ContributionFile(ContributionLocator::new(1, 0, 0, true));
+        let response = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false));
+        let next_challenge = Locator::ContributionFile(ContributionLocator::new(2, 0, 0, true));
+        for (locator, bytes) in vec![(challenge, b"challenge".to_vec()), (response, b"response".to_vec())] {
+            storage.initialize(locator, 0).unwrap();
+            storage.update(&locator, Object::ContributionFile(bytes)).unwrap();
+        }
+        storage.copy(&response, &next_challenge).unwrap();
+        storage.compress_round(1).unwrap().run().unwrap();
+
+        assert_eq!(storage.prune_round(1).unwrap(), 2);
+        assert!(!storage.exists(&challenge));
+        assert!(!storage.exists(&response));
+        assert_eq!(storage.prune_round(1).unwrap(), 0);
+        assert_eq!(storage.pruned_archive().unwrap().run().unwrap(), 2);
+
+        // The response is still the challenge of round 2
+        let mut bytes = vec![];
+        storage.open(&next_challenge).unwrap().read_to_end(&mut bytes).unwrap();
+        assert_eq!(bytes, b"response");
+
+        let archived = |locator: &Locator| {
+            let path = storage.to_path(locator).unwrap();
+            archive.path().join(relative_name(dir.path(), path.as_path()).unwrap())
+        };
+        assert_eq!(fs::read(archived(&response)).unwrap(), b"response");
+        let compressed = compressed_name(archived(&challenge).to_str().unwrap());
+        assert_eq!(zstd::decode_all(File::open(compressed).unwrap()).unwrap(), b"challenge");
+    }
+
     #[test]
     fn test_to_path_coordinator_state() {
         let locator = DiskResolver::new("./transcript/test");
@@ -0,0 +1,295 @@
+//! Encryption at rest of the files of the storage, with AES-256-GCM.
+//!
+//! A file is encrypted in chunks of [CHUNK_SIZE] bytes, each sealed with its own nonce made of a random prefix
+//! drawn for the file, the index of the chunk and a flag set on the last one, so that chunks can't be reordered,
+//! dropped or truncated without the decryption failing. The chunks are authenticated along with the name of the file,
+//! its path in the storage, so that encrypted files can't be swapped with each other either. Encrypted files start
+//! with [MAGIC].
+
+use crate::CoordinatorError;
+
+use aes_gcm::{
+    aead::{Aead, NewAead, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use rand::RngCore;
+use std::{
+    fmt,
+    io::{self, Read, Write},
+};
+
+/// Header of the encrypted files.
+pub const MAGIC: &[u8; 8] = b"NMPCENC2";
+
+/// Size of the plaintext of each chunk but the last one.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Size of the authentication tag appended to each chunk.
+const TAG_SIZE: usize = 16;
+
+/// Size of the random prefix of the nonces, stored after [MAGIC].
+const NONCE_PREFIX_SIZE: usize = 7;
+
+/// Size of the header of the encrypted files.
+const HEADER_SIZE: usize = MAGIC.len() + NONCE_PREFIX_SIZE;
+
+/// The cipher of the files of the storage, with the key configured in the environment.
+#[derive(Clone)]
+pub struct StorageCipher {
+    cipher: Aes256Gcm,
+}
+
+impl fmt::Debug for StorageCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StorageCipher")
+    }
+}
+
+impl StorageCipher {
+    /// Creates the cipher from a [`hex`] encoded 256-bit key.
+    pub fn from_hex(key: &str) -> Result<Self, CoordinatorError> {
+        let key = hex::decode(key.trim()).map_err(|_| CoordinatorError::StorageEncryptionKeyInvalid)?;
+        if key.len() != 32 {
+            return Err(CoordinatorError::StorageEncryptionKeyInvalid);
+        }
+
+        Ok(Self {
+            cipher: Aes256Gcm::new(Key::from_slice(&key)),
+        })
+    }
+
+    /// Returns a writer encrypting into `inner` everything written to it, until [EncryptingWriter::finish], for the
+    /// file with the given name.
+    pub fn encryptor<W: Write>(&self, mut inner: W, name: &str) -> io::Result<EncryptingWriter<W>> {
+        let mut prefix = [0; NONCE_PREFIX_SIZE];
+        rand::thread_rng().fill_bytes(&mut prefix);
+        inner.write_all(MAGIC)?;
+        inner.write_all(&prefix)?;
+
+        Ok(EncryptingWriter {
+            cipher: self.cipher.clone(),
+            inner,
+            name: name.as_bytes().to_vec(),
+            prefix,
+            counter: 0,
+            buffer: Vec::with_capacity(CHUNK_SIZE),
+        })
+    }
+
+    /// Returns a reader decrypting the encrypted file with the given name read from `inner`, which must start with
+    /// [MAGIC].
+    pub fn decryptor<R: Read>(&self, mut inner: R, name: &str) -> io::Result<DecryptingReader<R>> {
+        let mut header = [0; HEADER_SIZE];
+        inner.read_exact(&mut header)?;
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "the file isn't encrypted"));
+        }
+        let mut prefix = [0; NONCE_PREFIX_SIZE];
+        prefix.copy_from_slice(&header[MAGIC.len()..]);
+
+        Ok(DecryptingReader {
+            cipher: self.cipher.clone(),
+            inner,
+            name: name.as_bytes().to_vec(),
+            prefix,
+            counter: 0,
+            encrypted: Vec::with_capacity(CHUNK_SIZE + TAG_SIZE + 1),
+            plaintext: vec![],
+            position: 0,
+            finished: false,
+        })
+    }
+
+    /// Encrypts at once the given bytes of the file with the given name.
+    pub fn encrypt(&self, bytes: &[u8], name: &str) -> io::Result<Vec<u8>> {
+        let mut encryptor = self.encryptor(vec![], name)?;
+        encryptor.write_all(bytes)?;
+        encryptor.finish()
+    }
+
+    /// Decrypts at once the given bytes of the encrypted file with the given name.
+    pub fn decrypt(&self, bytes: &[u8], name: &str) -> io::Result<Vec<u8>> {
+        let mut plaintext = vec![];
+        self.decryptor(bytes, name)?.read_to_end(&mut plaintext)?;
+        Ok(plaintext)
+    }
+}
+
+/// Returns `true` if the given bytes, the start of a file, are those of an encrypted file.
+pub fn is_encrypted(head: &[u8]) -> bool {
+    head.starts_with(MAGIC)
+}
+
+/// Returns the size of the plaintext of an encrypted file of the given size.
+pub fn plaintext_size(encrypted_size: u64) -> u64 {
+    let body = encrypted_size.saturating_sub(HEADER_SIZE as u64);
+    let chunks = (body + (CHUNK_SIZE + TAG_SIZE) as u64 - 1) / (CHUNK_SIZE + TAG_SIZE) as u64;
+    body.saturating_sub(chunks * TAG_SIZE as u64)
+}
+
+/// Returns the nonce of the chunk of the given index.
+fn nonce(prefix: &[u8; NONCE_PREFIX_SIZE], counter: u32, last: bool) -> [u8; 12] {
+    let mut nonce = [0; 12];
+    nonce[..NONCE_PREFIX_SIZE].copy_from_slice(prefix);
+    nonce[NONCE_PREFIX_SIZE..NONCE_PREFIX_SIZE + 4].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = last as u8;
+    nonce
+}
+
+/// A writer encrypting a file, see [StorageCipher::encryptor].
+pub struct EncryptingWriter<W: Write> {
+    cipher: Aes256Gcm,
+    inner: W,
+    /// The name of the file, authenticated with each chunk
+    name: Vec<u8>,
+    prefix: [u8; NONCE_PREFIX_SIZE],
+    counter: u32,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> EncryptingWriter<W> {
+    /// Encrypts the buffered plaintext as the chunk at the current index.
+    fn write_chunk(&mut self, last: bool) -> io::Result<()> {
+        let nonce = nonce(&self.prefix, self.counter, last);
+        let chunk = self
+            .cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &self.buffer,
+                    aad: &self.name,
+                },
+            )
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "the encryption failed"))?;
+        self.inner.write_all(&chunk)?;
+        self.buffer.clear();
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "the file is too large to be encrypted"))?;
+
+        Ok(())
+    }
+
+    /// Writes the last chunk and returns the inner writer. A file whose writer isn't finished can't be decrypted.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_chunk(true)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for EncryptingWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        // The chunk is only written once more bytes follow it, for the last one to be flagged as such
+        if self.buffer.len() == CHUNK_SIZE {
+            self.write_chunk(false)?;
+        }
+        let size = std::cmp::min(bytes.len(), CHUNK_SIZE - self.buffer.len());
+        self.buffer.extend_from_slice(&bytes[..size]);
+
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A reader decrypting a file, see [StorageCipher::decryptor].
+pub struct DecryptingReader<R: Read> {
+    cipher: Aes256Gcm,
+    inner: R,
+    /// The name of the file, authenticated with each chunk
+    name: Vec<u8>,
+    prefix: [u8; NONCE_PREFIX_SIZE],
+    counter: u32,
+    /// The encrypted bytes read ahead, up to a chunk and the first byte of the next one
+    encrypted: Vec<u8>,
+    plaintext: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl<R: Read> DecryptingReader<R> {
+    /// Reads and decrypts the next chunk.
+    fn read_chunk(&mut self) -> io::Result<()> {
+        // A byte past the chunk tells whether it's the last one
+        let mut buffer = [0; 8192];
+        while self.encrypted.len() < CHUNK_SIZE + TAG_SIZE + 1 {
+            let size = std::cmp::min(buffer.len(), CHUNK_SIZE + TAG_SIZE + 1 - self.encrypted.len());
+            match self.inner.read(&mut buffer[..size]) {
+                Ok(0) => break,
+                Ok(read) => self.encrypted.extend_from_slice(&buffer[..read]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        let last = self.encrypted.len() <= CHUNK_SIZE + TAG_SIZE;
+        let chunk_size = std::cmp::min(self.encrypted.len(), CHUNK_SIZE + TAG_SIZE);
+
+        let nonce = nonce(&self.prefix, self.counter, last);
+        self.plaintext = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &self.encrypted[..chunk_size],
+                    aad: &self.name,
+                },
+            )
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "the decryption of the file failed"))?;
+        self.encrypted.drain(..chunk_size);
+        self.position = 0;
+        self.counter += 1;
+        self.finished = last;
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptingReader<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.plaintext.len() {
+            if self.finished {
+                return Ok(0);
+            }
+            self.read_chunk()?;
+        }
+
+        let size = std::cmp::min(buffer.len(), self.plaintext.len() - self.position);
+        buffer[..size].copy_from_slice(&self.plaintext[self.position..self.position + size]);
+        self.position += size;
+
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encryption_roundtrip() {
+        let cipher = StorageCipher::from_hex(&hex::encode([7u8; 32])).unwrap();
+        for size in vec![0, 1, CHUNK_SIZE - 1, CHUNK_SIZE, CHUNK_SIZE + 1, 3 * CHUNK_SIZE] {
+            let plaintext: Vec<u8> = (0..size).map(|i| i as u8).collect();
+            let encrypted = cipher.encrypt(&plaintext, "round_height").unwrap();
+            assert!(is_encrypted(&encrypted));
+            assert_eq!(plaintext_size(encrypted.len() as u64), size as u64);
+            assert_eq!(cipher.decrypt(&encrypted, "round_height").unwrap(), plaintext);
+
+            // A truncated file fails to decrypt, even at the boundary of a chunk
+            let truncated = &encrypted[..encrypted.len() - TAG_SIZE - 1];
+            assert!(cipher.decrypt(truncated, "round_height").is_err());
+        }
+
+        // The plain files and the files encrypted under another name or key aren't read
+        assert!(cipher.decrypt(b"plain", "round_height").is_err());
+        let secret = cipher.encrypt(b"secret", "round_height").unwrap();
+        assert!(cipher.decrypt(&secret, "coordinator.json").is_err());
+        let other = StorageCipher::from_hex(&hex::encode([8u8; 32])).unwrap();
+        assert!(other.decrypt(&secret, "round_height").is_err());
+        assert!(StorageCipher::from_hex("00").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{
+    storage::{Locator, StateStorage},
+    CoordinatorError,
+};
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// A [StateStorage] keeping the state objects in memory, for the tests and the dry runs.
+///
+/// Nothing is written to the filesystem and the objects are lost once the last clone of the storage is dropped.
+/// The clones share their objects, so that a [Disk](super::Disk) loaded again on a clone sees the state left by
+/// the previous one, as after a restart. The contribution files are still written by the disk storage, as the
+/// computation and the verification map them from their files.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStorage {
+    objects: Arc<Mutex<HashMap<Locator, Vec<u8>>>>,
+}
+
+impl MemoryStorage {
+    /// Returns the number of objects in the storage.
+    pub fn len(&self) -> usize {
+        self.objects.lock().map(|objects| objects.len()).unwrap_or_default()
+    }
+
+    /// Returns `true` if the storage holds no object.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl StateStorage for MemoryStorage {
+    fn exists(&self, locator: &Locator) -> Result<bool, CoordinatorError> {
+        let objects = self.objects.lock().map_err(|_| CoordinatorError::StorageLockFailed)?;
+        Ok(objects.contains_key(locator))
+    }
+
+    fn get(&self, locator: &Locator) -> Result<Option<Vec<u8>>, CoordinatorError> {
+        let objects = self.objects.lock().map_err(|_| CoordinatorError::StorageLockFailed)?;
+        Ok(objects.get(locator).cloned())
+    }
+
+    fn put(&self, locator: &Locator, bytes: &[u8]) -> Result<(), CoordinatorError> {
+        let mut objects = self.objects.lock().map_err(|_| CoordinatorError::StorageLockFailed)?;
+        objects.insert(*locator, bytes.to_vec());
+        Ok(())
+    }
+
+    fn remove(&self, locator: &Locator) -> Result<(), CoordinatorError> {
+        let mut objects = self.objects.lock().map_err(|_| CoordinatorError::StorageLockFailed)?;
+        objects.remove(locator);
+        Ok(())
+    }
+}
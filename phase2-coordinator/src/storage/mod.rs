@@ -1,5 +1,25 @@
+pub mod audit;
+pub use audit::*;
+
+#[cfg(feature = "postgres")]
+pub mod database;
+#[cfg(feature = "postgres")]
+pub use database::*;
+
 pub mod disk;
 pub use disk::*;
 
+pub mod encryption;
+pub use encryption::StorageCipher;
+
+pub mod memory;
+pub use memory::*;
+
+pub mod snapshot;
+pub use snapshot::*;
+
 pub mod storage;
 pub use storage::*;
+
+pub mod wal;
+pub use wal::*;
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use time::OffsetDateTime;
+
+/// Name of the entry of a snapshot holding its [SnapshotManifest], the last one of the archive.
+pub const SNAPSHOT_MANIFEST: &str = "manifest.json";
+
+/// The manifest of a snapshot of the storage, against which its files are checked before being restored.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Height of the current round when the snapshot was taken
+    pub round_height: u64,
+    #[serde(with = "time::serde::timestamp")]
+    pub created_at: OffsetDateTime,
+    /// Hash of each file, hex encoded, by its path relative to the base directory of the storage
+    pub files: BTreeMap<String, String>,
+}
@@ -13,87 +13,20 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use super::Disk;
+pub use phase2_client_sdk::{ContributionLocator, ContributionSignatureLocator};
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
-pub struct ContributionLocator {
-    round_height: u64,
-    chunk_id: u64,
-    contribution_id: u64,
-    is_verified: bool,
-}
-
-// Parameters generated from `masp-mpc` crate have size 84_720_180, to this add the needed 64 bytes for the hash of the contribution that is placed at the head of the contribution file.
-#[cfg(not(debug_assertions))]
-pub const ANOMA_BASE_FILE_SIZE: u64 = 84_720_244; // 145_449_460 prod: 84_720_244, testing: 2_332
-#[cfg(debug_assertions)]
-pub const ANOMA_BASE_FILE_SIZE: u64 = 2_332; // prod: 84_720_244, testing: 2_332
-                                             // With `masp-mpc` the contribution file grows by 1632 bytes on each new contribution
-#[cfg(not(debug_assertions))]
-pub const ANOMA_PER_ROUND_FILE_SIZE_INCREASE: u64 = 1_632; // prod: 1_632, testing: 544
-#[cfg(debug_assertions)]
-pub const ANOMA_PER_ROUND_FILE_SIZE_INCREASE: u64 = 544; // prod: 1_632, testing: 544
-
-impl ContributionLocator {
-    pub fn new(round_height: u64, chunk_id: u64, contribution_id: u64, is_verified: bool) -> Self {
-        Self {
-            round_height,
-            chunk_id,
-            contribution_id,
-            is_verified,
-        }
-    }
-
-    pub fn round_height(&self) -> u64 {
-        self.round_height
-    }
-
-    pub fn chunk_id(&self) -> u64 {
-        self.chunk_id
-    }
-
-    pub fn contribution_id(&self) -> u64 {
-        self.contribution_id
-    }
-
-    pub fn is_verified(&self) -> bool {
-        self.is_verified
-    }
-}
-
-#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
-pub struct ContributionSignatureLocator {
-    round_height: u64,
-    chunk_id: u64,
-    contribution_id: u64,
-    is_verified: bool,
-}
-
-impl ContributionSignatureLocator {
-    pub fn new(round_height: u64, chunk_id: u64, contribution_id: u64, is_verified: bool) -> Self {
-        Self {
-            round_height,
-            chunk_id,
-            contribution_id,
-            is_verified,
-        }
-    }
-
-    pub fn round_height(&self) -> u64 {
-        self.round_height
-    }
-
-    pub fn chunk_id(&self) -> u64 {
-        self.chunk_id
-    }
+/// Size of the hash of the challenge placed at the head of each contribution file.
+pub const CONTRIBUTION_HASH_SIZE: u64 = 64;
 
-    pub fn contribution_id(&self) -> u64 {
-        self.contribution_id
-    }
+/// Returns the size of the public key appended by a contribution to the parameters of each circuit: three
+/// uncompressed G1 points, one uncompressed G2 point and the hash of the transcript.
+fn public_key_size(curve: CurveKind) -> u64 {
+    let (g1_size, g2_size) = match curve {
+        CurveKind::Bls12_381 | CurveKind::Bls12_377 => (96, 192),
+        CurveKind::BW6 => (192, 192),
+    };
 
-    pub fn is_verified(&self) -> bool {
-        self.is_verified
-    }
+    3 * g1_size + g2_size + CONTRIBUTION_HASH_SIZE
 }
 
 /// A data structure representing all possible types of keys in storage.
@@ -109,6 +42,23 @@ pub enum Locator {
     ContributionsInfoSummary,
 }
 
+impl Locator {
+    /// Returns `true` if the locator points to the state of the ceremony (the rounds, the queue and the
+    /// participants) rather than to a contribution file.
+    pub fn is_state(&self) -> bool {
+        matches!(
+            self,
+            Locator::CoordinatorState | Locator::RoundHeight | Locator::RoundState { .. }
+        )
+    }
+
+    /// Returns `true` if the locator points to a challenge or response file, which [Disk](super::Disk) stores under
+    /// its hash once complete.
+    pub fn is_content_addressed(&self) -> bool {
+        matches!(self, Locator::ContributionFile(_))
+    }
+}
+
 impl From<ContributionLocator> for Locator {
     fn from(locator: ContributionLocator) -> Self {
         Self::ContributionFile(locator)
@@ -177,7 +127,7 @@ impl Object {
 
         match settings.curve() {
             // TODO: change round_filesize
-            CurveKind::Bls12_381 => ANOMA_BASE_FILE_SIZE,
+            CurveKind::Bls12_381 => Self::anoma_contribution_file_size(environment, 0, 0),
             CurveKind::Bls12_377 => round_filesize!(Bls12_377, settings, compressed),
             CurveKind::BW6 => round_filesize!(BW6_761, settings, compressed),
         }
@@ -197,8 +147,8 @@ impl Object {
 
         match (curve, verified) {
             // TODO: add correct verified_contribution_size
-            (CurveKind::Bls12_381, true) => ANOMA_BASE_FILE_SIZE,
-            (CurveKind::Bls12_381, false) => ANOMA_BASE_FILE_SIZE,
+            (CurveKind::Bls12_381, true) => Self::anoma_contribution_file_size(environment, 0, 0),
+            (CurveKind::Bls12_381, false) => Self::anoma_contribution_file_size(environment, 0, 0),
             (CurveKind::Bls12_377, true) => verified_contribution_size!(Bls12_377, settings, chunk_id, compressed),
             (CurveKind::Bls12_377, false) => unverified_contribution_size!(Bls12_377, settings, chunk_id, compressed),
             (CurveKind::BW6, true) => verified_contribution_size!(BW6_761, settings, chunk_id, compressed),
@@ -206,12 +156,19 @@ impl Object {
         }
     }
 
-    /// Returns dynamically the expected file size of a contribution file.
-    pub fn anoma_contribution_file_size(round_height: u64, contribution_id: u64) -> u64 {
-        match round_height {
-            0 => ANOMA_BASE_FILE_SIZE,
-            _ => ANOMA_BASE_FILE_SIZE + (ANOMA_PER_ROUND_FILE_SIZE_INCREASE * (round_height + contribution_id - 1)),
-        }
+    /// Returns the expected file size of a contribution file: the hash of its challenge followed by the parameters
+    /// of the [`Circuits`](crate::environment::Circuits) of the environment, which grow by a public key on the curve
+    /// of the environment for each contribution made since the initial ones.
+    pub fn anoma_contribution_file_size(environment: &Environment, round_height: u64, contribution_id: u64) -> u64 {
+        let contributions = match round_height {
+            0 => 0,
+            _ => round_height + contribution_id - 1,
+        };
+        let circuits = environment.circuits();
+
+        CONTRIBUTION_HASH_SIZE
+            + circuits.parameters_size
+            + circuits.count * public_key_size(environment.parameters().curve()) * contributions
     }
 
     /// Returns the expected file size of a contribution signature.
@@ -301,14 +258,14 @@ pub enum LocatorOrPath {
 }
 
 impl LocatorOrPath {
-    pub fn try_into_locator(self, storage: &Disk) -> Result<Locator, CoordinatorError> {
+    pub fn try_into_locator(self, storage: &impl StorageLocator) -> Result<Locator, CoordinatorError> {
         match self {
             LocatorOrPath::Path(path) => storage.to_locator(&path),
             LocatorOrPath::Locator(locator) => Ok(locator),
         }
     }
 
-    pub fn try_into_path(self, storage: &Disk) -> Result<LocatorPath, CoordinatorError> {
+    pub fn try_into_path(self, storage: &impl StorageLocator) -> Result<LocatorPath, CoordinatorError> {
         match self {
             LocatorOrPath::Path(path) => Ok(path),
             LocatorOrPath::Locator(locator) => storage.to_path(&locator),
@@ -350,11 +307,11 @@ impl RemoveAction {
 
     /// Obtain the location of the item to be removed from [Storage]
     /// as a [Locator].
-    pub fn try_into_locator(self, storage: &Disk) -> Result<Locator, CoordinatorError> {
+    pub fn try_into_locator(self, storage: &impl StorageLocator) -> Result<Locator, CoordinatorError> {
         self.locator_or_path.try_into_locator(storage)
     }
 
-    pub fn try_into_path(self, storage: &Disk) -> Result<LocatorPath, CoordinatorError> {
+    pub fn try_into_path(self, storage: &impl StorageLocator) -> Result<LocatorPath, CoordinatorError> {
         self.locator_or_path.try_into_path(storage)
     }
 }
@@ -382,6 +339,22 @@ pub trait StorageLocator {
     fn to_locator(&self, path: &LocatorPath) -> Result<Locator, CoordinatorError>;
 }
 
+/// A store of the serialized state objects, see [Locator::is_state], which can outlive and be shared
+/// by several instances of the coordinator. Each write must be atomic.
+pub trait StateStorage: std::fmt::Debug + Send + Sync {
+    /// Returns `true` if an object is stored at the given locator.
+    fn exists(&self, locator: &Locator) -> Result<bool, CoordinatorError>;
+
+    /// Returns the bytes stored at the given locator, if any.
+    fn get(&self, locator: &Locator) -> Result<Option<Vec<u8>>, CoordinatorError>;
+
+    /// Inserts or replaces the bytes stored at the given locator.
+    fn put(&self, locator: &Locator, bytes: &[u8]) -> Result<(), CoordinatorError>;
+
+    /// Removes the bytes stored at the given locator.
+    fn remove(&self, locator: &Locator) -> Result<(), CoordinatorError>;
+}
+
 pub trait StorageObject {
     type Reader: ObjectReader;
     type Writer: ObjectWriter;
@@ -0,0 +1,171 @@
+use crate::{storage::Locator, CoordinatorError};
+
+use fs_err::{File, OpenOptions};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+use tracing::{trace, warn};
+
+/// A transition of the state of the ceremony, recorded in the log along with the writes of the objects it updates.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateTransition {
+    QueueAdd {
+        participant: String,
+    },
+    Lock {
+        participant: String,
+        chunk_id: u64,
+    },
+    ContributionAccepted {
+        participant: String,
+        chunk_id: u64,
+        contribution_id: u64,
+    },
+    VerificationResult {
+        verifier: String,
+        chunk_id: u64,
+        contribution_id: u64,
+        verified: bool,
+    },
+}
+
+/// A record of the [WriteAheadLog].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalRecord {
+    Transition(StateTransition),
+    /// The new content of the object at the locator, [`base64`] encoded
+    Update {
+        locator: Locator,
+        object: String,
+    },
+    Remove {
+        locator: Locator,
+    },
+}
+
+impl WalRecord {
+    pub fn update(locator: Locator, bytes: &[u8]) -> Self {
+        Self::Update {
+            locator,
+            object: base64::encode(bytes),
+        }
+    }
+}
+
+/// An append-only log of the writes of the state objects, one JSON record per line.
+///
+/// Each record is synced to disk before the write it describes is applied, so that replaying the log on startup
+/// restores any object left partially written by a crash. The log is truncated once the write is applied, so it only
+/// holds the records of the write in progress. Records are full objects, so that replaying a record already applied
+/// is harmless.
+#[derive(Debug)]
+pub struct WriteAheadLog {
+    file: File,
+    len: u64,
+}
+
+impl WriteAheadLog {
+    /// Opens the log at the given path, creating it if needed, and returns the records to replay. A record torn by
+    /// a crash while being appended, and so never applied, is discarded with anything after it.
+    pub fn open(path: &Path) -> Result<(Self, Vec<WalRecord>), CoordinatorError> {
+        let mut file = OpenOptions::new().read(true).write(true).create(true).open(path)?;
+        let mut bytes = vec![];
+        file.read_to_end(&mut bytes)?;
+
+        let mut records = vec![];
+        let mut len = 0;
+        while let Some(end) = bytes[len..].iter().position(|b| *b == b'\n') {
+            match serde_json::from_slice(&bytes[len..len + end]) {
+                Ok(record) => records.push(record),
+                Err(_) => break,
+            }
+            len += end + 1;
+        }
+        if len < bytes.len() {
+            warn!("Discarding the tail of the write-ahead log after {} bytes", len);
+            file.set_len(len as u64)?;
+        }
+        file.seek(SeekFrom::Start(len as u64))?;
+
+        trace!("Opened the write-ahead log with {} records", records.len());
+        Ok((Self { file, len: len as u64 }, records))
+    }
+
+    /// Appends the record and syncs it to disk.
+    pub fn append(&mut self, record: &WalRecord) -> Result<(), CoordinatorError> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        self.file.sync_data()?;
+        self.len += line.len() as u64;
+
+        Ok(())
+    }
+
+    /// Returns the size in bytes of the log.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the log holds no record.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Discards all the records, which must have been applied.
+    pub fn checkpoint(&mut self) -> Result<(), CoordinatorError> {
+        self.file.set_len(0)?;
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.sync_data()?;
+        self.len = 0;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wal_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wal.log");
+        let records = vec![
+            WalRecord::Transition(StateTransition::QueueAdd {
+                participant: String::from("contributor"),
+            }),
+            WalRecord::update(Locator::RoundHeight, b"1"),
+            WalRecord::Remove {
+                locator: Locator::RoundState { round_height: 0 },
+            },
+        ];
+
+        let (mut wal, replay) = WriteAheadLog::open(&path).unwrap();
+        assert!(replay.is_empty());
+        for record in &records {
+            wal.append(record).unwrap();
+        }
+        drop(wal);
+
+        // A record torn by a crash is discarded
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"update\":{\"loc")
+            .unwrap();
+        let (mut wal, replay) = WriteAheadLog::open(&path).unwrap();
+        assert_eq!(replay, records);
+        assert_eq!(wal.len(), std::fs::metadata(&path).unwrap().len());
+
+        wal.checkpoint().unwrap();
+        assert!(wal.is_empty());
+        let (_, replay) = WriteAheadLog::open(&path).unwrap();
+        assert!(replay.is_empty());
+    }
+}
@@ -10,7 +10,7 @@ use once_cell::sync::Lazy;
 use serde_diff::{Diff, SerdeDiff};
 #[cfg(test)]
 use serial_test::serial;
-use std::{path::Path, sync::Arc};
+use std::{ops::Deref, path::Path, sync::Arc};
 use time::{macros::datetime, OffsetDateTime};
 use tracing::*;
 
@@ -18,6 +18,10 @@ use fs_err as fs;
 use once_cell::sync::OnceCell;
 
 static INSTANCE: OnceCell<()> = OnceCell::new();
+static PHASE1_RADIX_INSTANCE: OnceCell<()> = OnceCell::new();
+
+/// Env variable with the directory holding the phase1radix files required by `masp_phase2`.
+pub const PHASE1_RADIX_PATH_ENV: &str = "NAMADA_MPC_PHASE1RADIX_PATH";
 
 /// Environment for testing purposes only.
 pub static TEST_ENVIRONMENT: Lazy<Environment> = Lazy::new(|| Testing::from(Parameters::Test8Chunks).into());
@@ -90,11 +94,71 @@ pub fn test_coordinator_verifier(environment: &Environment) -> anyhow::Result<Pa
         .clone())
 }
 
-pub fn initialize_test_environment(environment: &Environment) -> Environment {
+/// Environment of a single test, storing the transcript in a unique temporary directory
+/// which is removed on drop.
+pub struct TestEnvironment {
+    environment: Environment,
+    _base_dir: tempfile::TempDir,
+}
+
+impl Deref for TestEnvironment {
+    type Target = Environment;
+
+    fn deref(&self) -> &Self::Target {
+        &self.environment
+    }
+}
+
+/// Returns a copy of the given environment with its storage in a fresh temporary directory, so that
+/// tests using the returned environment can run in parallel.
+///
+/// The shared directory of the given environment is cleared too, for the tests that keep using it directly.
+pub fn initialize_test_environment(environment: &Environment) -> TestEnvironment {
     test_logger();
+    link_phase1radix_files();
 
     clear_test_storage(environment);
-    environment.clone()
+
+    let base_dir = tempfile::Builder::new()
+        .prefix("namada-mpc-test")
+        .tempdir()
+        .expect("Unable to create the test transcript directory");
+    let environment: Environment = Testing {
+        environment: environment.clone(),
+    }
+    .base_dir(&base_dir.path().to_string_lossy())
+    .into();
+
+    TestEnvironment {
+        environment,
+        _base_dir: base_dir,
+    }
+}
+
+/// Links the phase1radix files from the directory in [`PHASE1_RADIX_PATH_ENV`], if set,
+/// into the working directory where `masp_phase2` looks for them.
+fn link_phase1radix_files() {
+    PHASE1_RADIX_INSTANCE.get_or_init(|| {
+        let radix_dir = match std::env::var(PHASE1_RADIX_PATH_ENV) {
+            Ok(dir) => fs::canonicalize(dir).expect("Invalid phase1radix directory"),
+            Err(_) => return,
+        };
+
+        for entry in fs::read_dir(&radix_dir).expect("Unable to read the phase1radix directory") {
+            let path = entry.expect("Unable to read the phase1radix directory").path();
+            let file_name = match path.file_name() {
+                Some(name) if name.to_string_lossy().starts_with("phase1radix") => name.to_owned(),
+                _ => continue,
+            };
+
+            if !Path::new(&file_name).exists() {
+                #[cfg(unix)]
+                std::os::unix::fs::symlink(&path, &file_name).expect("Unable to link the phase1radix file");
+                #[cfg(not(unix))]
+                fs::copy(&path, &file_name).expect("Unable to copy the phase1radix file");
+            }
+        }
+    });
 }
 
 pub(crate) fn test_logger() {
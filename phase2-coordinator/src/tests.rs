@@ -1,11 +1,11 @@
 use crate::{
-    authentication::Dummy,
+    authentication::{Dummy, KeyPair, Production},
     commands::{Seed, SigningKey, SEED_LENGTH},
     environment::{Environment, Parameters, Settings, Testing},
     objects::Task,
-    storage::{Disk, StorageLocator},
+    storage::{ContributionLocator, Disk, Locator, MemoryStorage, StateStorage, StorageLocator},
     testing::prelude::*,
-    Coordinator, CoordinatorError, MockTimeSource, Participant, Round,
+    Coordinator, CoordinatorError, MockTimeSource, Participant, Round, SystemTimeSource,
 };
 use phase2::{helpers::CurveKind, ContributionMode, ProvingSystem};
 use time::OffsetDateTime;
@@ -99,7 +99,7 @@ fn execute_round(proving_system: ProvingSystem, curve: CurveKind) -> anyhow::Res
     let number_of_chunks = environment.number_of_chunks() as usize;
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -145,6 +145,52 @@ fn execute_round(proving_system: ProvingSystem, curve: CurveKind) -> anyhow::Res
     Ok(())
 }
 
+/// Verifies the contributions to all the chunks of a round at once.
+#[test]
+#[serial]
+fn coordinator_verify_chunks_in_parallel() -> anyhow::Result<()> {
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+    let testing_deployment = Testing::from(parameters).verification_concurrency(2);
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+    let number_of_chunks = environment.number_of_chunks() as usize;
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (contributor, contributor_signing_key, seed) = create_contributor("1");
+    let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+    let token = String::from("test_token");
+    coordinator.add_to_queue(contributor.clone(), Some(contributor_ip), token, 10)?;
+
+    // Update the ceremony to round 1.
+    coordinator.update()?;
+
+    // Contribute to every chunk before verifying any of them.
+    for _ in 0..number_of_chunks {
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+    }
+    let tasks: Vec<Task> = coordinator.get_pending_verifications().keys().cloned().collect();
+    assert_eq!(number_of_chunks, tasks.len());
+
+    for verification in coordinator.default_verify_all(&tasks) {
+        verification?;
+    }
+    assert!(coordinator.get_pending_verifications().is_empty());
+    assert!(coordinator.current_round()?.is_complete());
+
+    Ok(())
+}
+
 /*
     Drop Participant Tests
 
@@ -220,7 +266,7 @@ fn coordinator_drop_contributor_basic() {
     let number_of_chunks = environment.number_of_chunks() as usize;
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy)).unwrap();
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy)).unwrap();
 
     // Initialize the ceremony to round 0.
     coordinator.initialize().unwrap();
@@ -786,7 +832,7 @@ fn coordinator_drop_contributor_removes_contributions() -> anyhow::Result<()> {
     let number_of_chunks = environment.number_of_chunks() as usize;
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -1112,7 +1158,7 @@ fn coordinator_drop_contributor_removes_subsequent_contributions() -> anyhow::Re
     let number_of_chunks = environment.number_of_chunks() as usize;
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -1199,7 +1245,7 @@ fn coordinator_drop_contributor_and_release_locks() {
     let number_of_chunks = environment.number_of_chunks() as usize;
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy)).unwrap();
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy)).unwrap();
 
     // Initialize the ceremony to round 0.
     coordinator.initialize().unwrap();
@@ -1292,7 +1338,7 @@ fn coordinator_drop_several_contributors() {
     let environment = initialize_test_environment(&testing.into());
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy)).unwrap();
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy)).unwrap();
 
     // Initialize the ceremony to round 0.
     coordinator.initialize().unwrap();
@@ -1523,7 +1569,7 @@ fn coordinator_drop_contributor_and_update_verifier_tasks() {
     let number_of_chunks = environment.number_of_chunks() as usize;
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy)).unwrap();
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy)).unwrap();
 
     // Initialize the ceremony to round 0.
     coordinator.initialize().unwrap();
@@ -1766,7 +1812,7 @@ fn try_lock_blocked() -> anyhow::Result<()> {
     let number_of_chunks = environment.number_of_chunks() as usize;
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -1998,7 +2044,7 @@ fn drop_contributor_and_reassign_tasks() -> anyhow::Result<()> {
     let number_of_chunks = environment.number_of_chunks() as usize;
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -2082,7 +2128,7 @@ fn contributor_timeout_drop_test() -> anyhow::Result<()> {
     let environment = initialize_test_environment(&Environment::from(testing_deployment));
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+    let mut coordinator = Coordinator::new_with_time(environment.clone(), Arc::new(Dummy), time.clone())?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -2121,6 +2167,187 @@ fn contributor_timeout_drop_test() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test that a contributor which missed its heartbeats is put back at the end
+/// of the queue when [Environment::requeue_timed_out_contributors] is set.
+#[test]
+#[serial]
+fn contributor_timeout_requeue_test() -> anyhow::Result<()> {
+    let time = Arc::new(MockTimeSource::new(OffsetDateTime::now_utc()));
+
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let testing_deployment: Testing = Testing::from(parameters)
+        .contributor_seen_timeout(time::Duration::minutes(5))
+        .participant_lock_timeout(time::Duration::minutes(10))
+        .requeue_timed_out_contributors(true);
+
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new_with_time(environment.clone(), Arc::new(Dummy), time.clone())?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (contributor1, _contributor_signing_key1, _seed1) = create_contributor("1");
+    let token = String::from("test_token");
+    let contributor_1_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+    coordinator.add_to_queue(contributor1.clone(), Some(contributor_1_ip), token, 10)?;
+
+    // Update the ceremony to round 1.
+    coordinator.update()?;
+
+    assert_eq!(1, coordinator.current_contributors().len());
+    assert!(!coordinator.is_queue_contributor(&contributor1));
+
+    // push the time past the timout
+    time.update(|prev| prev + time::Duration::minutes(6));
+    coordinator.update()?;
+
+    // Check that contributor1 has been dropped from the round and is waiting in the queue again
+    assert!(coordinator.current_contributors().get(0).unwrap().0 != contributor1);
+    assert_eq!(&contributor1, coordinator.dropped_participants().get(0).unwrap().id());
+    assert!(coordinator.is_queue_contributor(&contributor1));
+
+    // The timeout lowered its reliability score
+    assert!(coordinator.reliability_score(&contributor1) < 10);
+    let (_, (reliability, _, _, _)) = coordinator
+        .queue_contributors()
+        .into_iter()
+        .find(|(participant, _)| *participant == contributor1)
+        .unwrap();
+    assert!(reliability < 10);
+
+    Ok(())
+}
+
+/// Test that the locks carry their expiry, past which the contributor is
+/// dropped from the round when trying to contribute.
+#[test]
+#[serial]
+fn contributor_lock_expiry_test() -> anyhow::Result<()> {
+    let start = OffsetDateTime::now_utc();
+    let time = Arc::new(MockTimeSource::new(start));
+
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let testing_deployment: Testing = Testing::from(parameters)
+        .contributor_seen_timeout(time::Duration::minutes(20))
+        .participant_lock_timeout(time::Duration::minutes(10));
+
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new_with_time(environment.clone(), Arc::new(Dummy), time.clone())?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (contributor1, _contributor_signing_key1, _seed1) = create_contributor("1");
+    let token = String::from("test_token");
+    let contributor_1_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+    coordinator.add_to_queue(contributor1.clone(), Some(contributor_1_ip), token, 10)?;
+
+    // Update the ceremony to round 1.
+    coordinator.update()?;
+
+    let (_, locked_locators) = coordinator.try_lock(&contributor1)?;
+    let expires_at = start + time::Duration::minutes(10);
+    assert_eq!(locked_locators.expires_at(), Some(expires_at.unix_timestamp()));
+    coordinator.check_lock_expiry(&contributor1)?;
+
+    // push the time past the expiry of the lock
+    time.update(|prev| prev + time::Duration::minutes(11));
+    assert!(matches!(
+        coordinator.check_lock_expiry(&contributor1),
+        Err(CoordinatorError::ChunkLockExpired)
+    ));
+
+    // Check that contributor1 has been dropped from the round
+    assert!(!coordinator.is_current_contributor(&contributor1));
+    assert_eq!(&contributor1, coordinator.dropped_participants().get(0).unwrap().id());
+
+    Ok(())
+}
+
+/// Test that a contributor repeatedly uploading invalid contributions is
+/// dropped from the round and can't join the queue again for a while.
+#[test]
+#[serial]
+fn contributor_failure_threshold_test() -> anyhow::Result<()> {
+    let time = Arc::new(MockTimeSource::new(OffsetDateTime::now_utc()));
+
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+
+    let testing_deployment: Testing =
+        Testing::from(parameters).contributor_failure_threshold(2, time::Duration::hours(1));
+
+    let environment = initialize_test_environment(&Environment::from(testing_deployment));
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new_with_time(environment.clone(), Arc::new(Dummy), time.clone())?;
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize()?;
+
+    let (contributor1, _contributor_signing_key1, _seed1) = create_contributor("1");
+    let token = String::from("test_token");
+    let contributor_1_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+    coordinator.add_to_queue(contributor1.clone(), Some(contributor_1_ip), token.clone(), 10)?;
+
+    // Update the ceremony to round 1.
+    coordinator.update()?;
+    coordinator.try_lock(&contributor1)?;
+
+    // The failures of the coordinator are not held against the contributor
+    let checks: Result<(), CoordinatorError> = Err(CoordinatorError::StorageFailed);
+    assert!(coordinator.record_upload_checks(&contributor1, checks).is_err());
+    let checks: Result<(), CoordinatorError> = Err(CoordinatorError::ContributionHashMismatch);
+    assert!(coordinator.record_upload_checks(&contributor1, checks).is_err());
+    assert!(coordinator.is_current_contributor(&contributor1));
+
+    // Check that contributor1 has been dropped from the round on its second invalid contribution
+    let checks: Result<(), CoordinatorError> = Err(CoordinatorError::ContributorSignatureInvalid);
+    assert!(coordinator.record_upload_checks(&contributor1, checks).is_err());
+    assert!(!coordinator.is_current_contributor(&contributor1));
+    assert_eq!(&contributor1, coordinator.dropped_participants().get(0).unwrap().id());
+
+    // Wrong, the contributor can't join the queue again until the end of the ban
+    assert!(matches!(
+        coordinator.add_to_queue(contributor1.clone(), Some(contributor_1_ip), token.clone(), 10),
+        Err(CoordinatorError::ParticipantSuspended)
+    ));
+
+    time.update(|prev| prev + time::Duration::minutes(61));
+    coordinator.add_to_queue(contributor1.clone(), Some(contributor_1_ip), token, 10)?;
+
+    Ok(())
+}
+
 /// Test that participant who is waiting for a verifier to verify
 /// chunks that it depends on is not dropped from the round.
 #[test]
@@ -2144,7 +2371,7 @@ fn contributor_wait_verifier_test() -> anyhow::Result<()> {
     let number_of_chunks = environment.number_of_chunks() as usize;
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+    let mut coordinator = Coordinator::new_with_time(environment.clone(), Arc::new(Dummy), time.clone())?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -2221,7 +2448,7 @@ fn participant_lock_timeout_drop_test() -> anyhow::Result<()> {
     let environment = initialize_test_environment(&Environment::from(testing_deployment));
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+    let mut coordinator = Coordinator::new_with_time(environment.clone(), Arc::new(Dummy), time.clone())?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -2288,7 +2515,7 @@ fn queue_seen_timeout_drop_test() -> anyhow::Result<()> {
     let environment = initialize_test_environment(&Environment::from(testing_deployment));
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+    let mut coordinator = Coordinator::new_with_time(environment.clone(), Arc::new(Dummy), time.clone())?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -2356,7 +2583,7 @@ fn queue_seen_timeout_heartbeat_test() -> anyhow::Result<()> {
     let environment = initialize_test_environment(&Environment::from(testing_deployment));
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+    let mut coordinator = Coordinator::new_with_time(environment.clone(), Arc::new(Dummy), time.clone())?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -2430,7 +2657,7 @@ fn rollback_locked_chunk() -> anyhow::Result<()> {
     let environment = initialize_test_environment(&Environment::from(testing_deployment));
 
     // Instantiate a coordinator.
-    let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+    let mut coordinator = Coordinator::new_with_time(environment.clone(), Arc::new(Dummy), time.clone())?;
 
     // Initialize the ceremony to round 0.
     coordinator.initialize()?;
@@ -2470,6 +2697,64 @@ fn rollback_locked_chunk() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+#[serial]
+fn rotated_key_signs_receipts() -> anyhow::Result<()> {
+    let environment = initialize_test_environment(&Environment::from(Testing::default()));
+
+    // Instantiate a coordinator signing with real keys, to verify its receipts.
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Production))?;
+    coordinator.initialize()?;
+
+    let locator = ContributionLocator::new(coordinator.current_round_height()?, 0, 0, true);
+    let old_public_key = coordinator.environment().coordinator_verifiers()[0].address();
+    assert!(coordinator.contribution_receipt(&locator)?.verify(&old_public_key));
+
+    let keypair = KeyPair::new();
+    let rotation = coordinator.rotate_signing_key(&keypair)?;
+    assert_eq!(rotation.new_public_key, keypair.pubkey());
+
+    // The receipts are signed by the new key only
+    let receipt = coordinator.contribution_receipt(&locator)?;
+    assert_eq!(receipt.coordinator_public_key, keypair.pubkey());
+    assert!(receipt.verify(keypair.pubkey()));
+    assert!(!receipt.verify(&old_public_key));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn beacon_closes_ceremony() -> anyhow::Result<()> {
+    let environment = initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
+    coordinator.initialize()?;
+    assert!(!coordinator.is_finalized());
+
+    coordinator.apply_beacon([1u8; 32])?;
+    assert!(coordinator.is_finalized());
+
+    // No contributor can join nor contribute anymore, and the update requests the shutdown
+    let (contributor, _, _) = create_contributor("1");
+    let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    assert!(matches!(
+        coordinator.add_to_queue(contributor.clone(), Some(ip), String::from("test_token"), 10),
+        Err(CoordinatorError::CeremonyIsOver)
+    ));
+    assert!(matches!(
+        coordinator.try_lock(&contributor),
+        Err(CoordinatorError::CeremonyIsOver)
+    ));
+    assert!(matches!(coordinator.update(), Err(CoordinatorError::CeremonyIsOver)));
+
+    // The ceremony stays closed after a restart
+    let coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+    assert!(coordinator.is_finalized());
+
+    Ok(())
+}
+
 #[test]
 #[serial]
 fn round_on_groth16_bls12_377() {
@@ -2487,3 +2772,86 @@ fn round_on_groth16_bw6_761() {
 fn round_on_marlin_bls12_377() {
     execute_round(ProvingSystem::Marlin, CurveKind::Bls12_377).unwrap();
 }
+
+/// Runs a round on a coordinator keeping its state in memory, and loads it again on the same memory.
+#[test]
+#[serial]
+fn round_on_memory_storage() -> anyhow::Result<()> {
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        7,  /* power */
+        32, /* batch_size */
+        32, /* chunk_size */
+    ));
+    let environment = initialize_test_environment(&Testing::from(parameters).into());
+    let number_of_chunks = environment.number_of_chunks() as usize;
+    let state = MemoryStorage::default();
+    let load = || -> anyhow::Result<Coordinator> {
+        let storage = Disk::load_with_state(&environment, Some(Box::new(state.clone())))?;
+        let time = Arc::new(SystemTimeSource::new());
+        Ok(Coordinator::new_with_storage(
+            environment.clone(),
+            Arc::new(Dummy),
+            time,
+            storage,
+        )?)
+    };
+
+    let mut coordinator = load()?;
+    coordinator.initialize()?;
+
+    let (contributor, contributor_signing_key, seed) = create_contributor("1");
+    let (verifier, verifier_signing_key) = create_verifier("1");
+    let ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+    coordinator.add_to_queue(contributor.clone(), Some(ip), String::from("test_token"), 10)?;
+    coordinator.update()?;
+    assert_eq!(1, coordinator.current_round_height()?);
+
+    for _ in 0..number_of_chunks {
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+        verify_task_if_available(&mut coordinator, &verifier, &verifier_signing_key)?;
+    }
+    coordinator.update()?;
+    assert_eq!(2, coordinator.current_round_height()?);
+
+    // The state objects were never written to the base directory
+    let storage = coordinator.storage();
+    for locator in [Locator::CoordinatorState, Locator::RoundHeight, Locator::RoundState {
+        round_height: 1,
+    }] {
+        assert!(state.exists(&locator)?);
+        assert!(!storage.to_path(&locator)?.as_path().exists());
+    }
+    drop(coordinator);
+
+    // A coordinator loaded on the same memory resumes the ceremony
+    let coordinator = load()?;
+    assert_eq!(2, coordinator.current_round_height()?);
+
+    Ok(())
+}
+
+/// Test that the signature of a request is still rejected as a replay by a coordinator restarted without
+/// an update in between.
+#[test]
+#[serial]
+fn request_signature_replay_after_restart() -> anyhow::Result<()> {
+    let environment = initialize_test_environment(&Testing::from(Parameters::Test8Chunks).into());
+    let window = time::Duration::minutes(5);
+    let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
+    coordinator.initialize()?;
+    coordinator.register_request_signature("pubkey", timestamp, "signature", window)?;
+    drop(coordinator);
+
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
+    assert!(matches!(
+        coordinator.register_request_signature("pubkey", timestamp, "signature", window),
+        Err(CoordinatorError::RequestReplayed)
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+//! Outbound notifications of the [events](CeremonyEvent) of the ceremony, posted to the webhooks of the operators so
+//! that they don't have to watch the logs. Discord and Slack webhooks receive a message to display, the generic ones
+//! the json encoding of the event.
+
+use phase2_client_sdk::{CeremonyEvent, RejectionReason};
+use serde_json::{json, Value};
+use std::{str::FromStr, time::Duration};
+use thiserror::Error;
+use tracing::{debug, warn};
+use url::Url;
+
+/// Timeout of the requests to the webhooks, for an unresponsive one not to hold the others back
+pub const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WebhookError {
+    #[error("unknown webhook kind {0}, expected discord, slack or generic")]
+    UnknownKind(String),
+    #[error("invalid webhook url {0}")]
+    InvalidUrl(String),
+}
+
+/// The service behind a webhook, setting the format of its payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebhookKind {
+    Discord,
+    Slack,
+    /// Any HTTP endpoint accepting the json encoding of the events
+    Generic,
+}
+
+impl FromStr for WebhookKind {
+    type Err = WebhookError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "discord" => Ok(Self::Discord),
+            "slack" => Ok(Self::Slack),
+            "generic" => Ok(Self::Generic),
+            _ => Err(WebhookError::UnknownKind(s.to_string())),
+        }
+    }
+}
+
+/// A webhook notified of the events of the ceremony.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Webhook {
+    pub kind: WebhookKind,
+    pub url: String,
+}
+
+impl FromStr for Webhook {
+    type Err = WebhookError;
+
+    /// Parses a webhook written `<kind>=<url>`, or its url only for a generic one.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, url) = match s.split_once('=') {
+            Some((kind, url)) if !kind.contains('/') => (kind.parse()?, url),
+            _ => (WebhookKind::Generic, s),
+        };
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(WebhookError::InvalidUrl(url.to_string()));
+        }
+
+        Ok(Self {
+            kind,
+            url: url.to_string(),
+        })
+    }
+}
+
+impl Webhook {
+    /// Parses a whitespace separated list of webhooks, see [`Webhook::from_str`].
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, WebhookError> {
+        list.split_whitespace().map(str::parse).collect()
+    }
+
+    /// Returns the kind and host of the webhook, to log it without the secret token held by the url of the chat
+    /// services.
+    pub fn description(&self) -> String {
+        let host = Url::parse(&self.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        format!("{:?} webhook at {}", self.kind, host)
+    }
+
+    /// Returns the body of the notification of the event.
+    pub fn payload(&self, event: &CeremonyEvent) -> Value {
+        match self.kind {
+            WebhookKind::Discord => json!({ "content": message(event) }),
+            WebhookKind::Slack => json!({ "text": message(event) }),
+            WebhookKind::Generic => json!(event),
+        }
+    }
+
+    /// Posts the notification of the event. Failures are only logged, the events are not retried.
+    pub async fn notify(&self, client: &reqwest::Client, event: &CeremonyEvent) {
+        let response = client
+            .post(&self.url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(self.payload(event).to_string())
+            .send()
+            .await
+            .and_then(|r| r.error_for_status());
+
+        match response {
+            Ok(_) => debug!("Notified the {} of the event {}", self.description(), event.name()),
+            Err(e) => warn!(
+                "Error while notifying the {} of the event {}: {}",
+                self.description(),
+                event.name(),
+                e.without_url()
+            ),
+        }
+    }
+}
+
+/// Returns the description of the event displayed in the chat services.
+pub fn message(event: &CeremonyEvent) -> String {
+    match event {
+        CeremonyEvent::RoundAdvanced { round_height } => format!("Round {} started", round_height),
+        CeremonyEvent::ContributionAccepted {
+            round_height,
+            public_key,
+        } => format!(
+            "Contribution of {} to round {} accepted, waiting for its verification",
+            public_key, round_height
+        ),
+        CeremonyEvent::ContributorDropped { public_key } => format!("Contributor {} dropped", public_key),
+        CeremonyEvent::VerificationFinished {
+            round_height,
+            contribution_id,
+            verified,
+        } => match verified {
+            true => format!("Contribution {} of round {} verified", contribution_id, round_height),
+            false => format!(
+                "Verification of contribution {} of round {} failed",
+                contribution_id, round_height
+            ),
+        },
+        CeremonyEvent::ContributionRejected {
+            round_height,
+            public_key,
+            reason,
+        } => {
+            let reason = match reason {
+                RejectionReason::RejectedByVerifiers => "rejected by the external verifiers",
+                RejectionReason::VerificationFailed => "failed its verification",
+            };
+            format!(
+                "Contribution of {} to round {} {}, the round restarts",
+                public_key, round_height, reason
+            )
+        }
+        CeremonyEvent::StorageLow { available_bytes } => format!(
+            "Storage low, {} bytes left: new contributions are refused until space is freed",
+            available_bytes
+        ),
+        CeremonyEvent::QueueEmpty { round_height } => {
+            format!("Queue empty, no contributor is waiting after round {}", round_height)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_webhooks() {
+        let webhooks = Webhook::parse_list(
+            "discord=https://discord.com/api/webhooks/1/a \n slack=https://hooks.slack.com/services/b \
+             https://example.com/hook?key=c",
+        )
+        .unwrap();
+        assert_eq!(
+            webhooks.iter().map(|webhook| webhook.kind).collect::<Vec<_>>(),
+            vec![WebhookKind::Discord, WebhookKind::Slack, WebhookKind::Generic]
+        );
+        assert_eq!(webhooks[2].url, "https://example.com/hook?key=c");
+
+        // Wrong, unknown kind
+        assert_eq!(
+            "teams=https://example.com".parse::<Webhook>(),
+            Err(WebhookError::UnknownKind(String::from("teams")))
+        );
+
+        // Wrong, not an url
+        assert!("discord=example.com".parse::<Webhook>().is_err());
+    }
+
+    #[test]
+    fn test_webhook_description() {
+        let webhook: Webhook = "discord=https://discord.com/api/webhooks/1/secret".parse().unwrap();
+        assert_eq!(webhook.description(), "Discord webhook at discord.com");
+        assert!(!webhook.description().contains("secret"));
+    }
+
+    #[test]
+    fn test_webhook_payload() {
+        let event = CeremonyEvent::RoundAdvanced { round_height: 3 };
+        let webhook = |kind| Webhook {
+            kind,
+            url: String::from("https://example.com"),
+        };
+
+        assert_eq!(
+            webhook(WebhookKind::Discord).payload(&event),
+            json!({ "content": "Round 3 started" })
+        );
+        assert_eq!(
+            webhook(WebhookKind::Slack).payload(&event),
+            json!({ "text": "Round 3 started" })
+        );
+        assert_eq!(
+            webhook(WebhookKind::Generic).payload(&event),
+            json!({ "event": "round_advanced", "round_height": 3 })
+        );
+    }
+}
@@ -1,29 +1,42 @@
-//  NOTE: these tests must be run with --test-threads=1 due to the disk storage
-//	being stored at the same path for all the test instances causing a conflict.
-//	It could be possible to define a separate location (base_dir) for every test
-//	but it's simpler to just run the tests sequentially.
-//  NOTE: these tests require the phase1radix files to be placed in the phase2-coordinator folder
+//  NOTE: these tests require the phase1radix files, either placed in the phase2-coordinator folder
+//	or in the directory given by the NAMADA_MPC_PHASE1RADIX_PATH env variable
 
 use std::{
     io::Write,
     net::{IpAddr, SocketAddr},
-    sync::Arc,
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
 };
 
+use bech32::ToBase32;
 use blake2::Digest;
+use ipnet::IpNet;
 use phase2_coordinator::{
-    authentication::{KeyPair, Production, Signature},
+    audit::{self, AuditRecord},
+    authentication::{ContributionReceipt, KeyPair, KeyRotation, Production, Signature},
     commands::{Computation, RandomSource},
-    coordinator_state::CoordinatorState,
-    environment::Testing,
-    objects::{ContributionInfo, LockedLocators, TrimmedContributionInfo},
+    coordinator_state::{CoordinatorState, InviteCode},
+    environment::{BodyLimits, Environment, IpFilter, Testing},
+    geolocation::Geolocator,
+    objects::{
+        AttestationStatement, ContributionInfo, ContributionRecord, IdentityProof, IdentityProvider, LockedLocators,
+        NamadaAddressBinding, RoundInfo, Transcript, TrimmedContributionInfo,
+    },
     rest,
     rest_utils::{
-        self, ContributorStatus, PostChunkRequest, ACCESS_SECRET_HEADER, BODY_DIGEST_HEADER, CONTENT_LENGTH_HEADER,
-        PUBKEY_HEADER, SIGNATURE_HEADER, TOKENS_ZIP_FILE,
+        self, challenge_message, coordinator_identity_message, header_digest, ApiVersion, BanList, BanRequest,
+        CeremonyStats, CeremonyStatus, ContributionRejection, ContributorStatus, ContributorsPage, CoordinatorIdentity,
+        Cors, ErrorCode, ErrorResponse, IpAccess, IssuePriorityTokensRequest, LoginRequest, MintInviteCodesRequest,
+        ParticipantPriorityRequest, PostChunkRequest, RateLimit, Readiness, RequestContent, Session, SignatureHeaders,
+        SkipCurrentRequest, TranscriptCache, VerificationQueue, VerificationResult, VerificationStatus,
+        VerificationTask, ACCEPT_ENCODING_HEADER, ACCESS_SECRET_HEADER, API_PREFIX, API_VERSION, API_VERSION_HEADER,
+        BODY_DIGEST_HEADER, CHALLENGE_HASH_HEADER, CHALLENGE_SIGNATURE_HEADER, CONTENT_ENCODING_HEADER,
+        CONTENT_LENGTH_HEADER, CONTENT_RANGE_HEADER, CONTRIBUTION_SIGNATURE_HEADER, ESTIMATED_WAIT_HEADER,
+        MAX_INVITE_CODES_PER_REQUEST, MAX_INVITE_CODE_VALIDITY, MAX_REQUEST_AGE, PRIORITY_TOKEN_HEADER, PUBKEY_HEADER,
+        REQUEST_ID_HEADER, SESSION_TOKEN_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER, TOKENS_ZIP_FILE,
     },
-    storage::{ContributionLocator, ContributionSignatureLocator, Object},
-    testing::coordinator,
+    storage::{ContributionLocator, ContributionSignatureLocator, Object, SnapshotManifest},
+    testing::coordinator::{self, TestEnvironment},
     ContributionFileSignature, ContributionState, Coordinator, Participant,
 };
 use reqwest::header::{HeaderValue, CONTENT_TYPE};
@@ -32,9 +45,10 @@ use rocket::{
     http::{ContentType, Header, Status},
     local::blocking::{Client, LocalRequest},
     routes,
-    tokio::sync::RwLock,
+    tokio::sync::{mpsc, RwLock},
     Build, Rocket,
 };
+use once_cell::sync::Lazy;
 use serde::Serialize;
 use sha2::Sha256;
 use zip::write::FileOptions;
@@ -53,38 +67,58 @@ struct TestCtx {
     contributors: Vec<TestParticipant>,
     unknown_participant: TestParticipant,
     coordinator: TestParticipant,
-    // Keep TempDir in scope for some tests
-    _tokens_tmp_dir: tempfile::TempDir,
+    operator: TestParticipant,
+    // Keep the receiving end in scope, as the worker would, to accept verification requests
+    verification_requests: mpsc::Receiver<()>,
+    // Keep the storage of the test in scope
+    environment: TestEnvironment,
+}
+
+/// Directory of the token files, shared by all the tests since `NAMADA_TOKENS_PATH` is read once
+static TOKENS_DIR: Lazy<PathBuf> = Lazy::new(|| std::env::temp_dir().join("my-temporary-dir"));
+
+/// Guards the token files and the [`TOKENS_ZIP_FILE`], shared by all the tests
+static TOKENS_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Locks the token files. Tests updating the cohorts must hold the lock until the end, after building their context.
+fn lock_tokens() -> MutexGuard<'static, ()> {
+    TOKENS_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
 }
 
 /// Build the rocket server for testing with the proper configuration.
 fn build_context() -> TestCtx {
+    build_context_with(Testing::default())
+}
+
+/// Build the rocket server for testing on top of the given environment.
+fn build_context_with(testing: Testing) -> TestCtx {
     std::env::set_var("TOKEN_BLACKLIST", "true");
     std::env::set_var("NAMADA_MPC_IP_BAN", "true");
 
-    // Reset storage to prevent state conflicts between tests and initialize test environment
-    let environment = coordinator::initialize_test_environment(&Testing::default().into());
-
-    // Create token file
-    // Need a fixed-name temp dir because of the lazy_static variables based on env
-    // Sometimes TempDir is not deleted correctly at drop, need to manually cancel the directory if it sill exists from a previous run
-    let os_temp_dir = std::env::temp_dir();
-    std::fs::remove_dir_all(os_temp_dir.join("my-temporary-dir")).ok();
-    let tmp_dir = tempfile::Builder::new()
-        .prefix("my-temporary-dir")
-        .rand_bytes(0)
-        .tempdir()
-        .unwrap();
+    // Initialize the test environment with a dedicated storage, to run the tests in parallel
+    let operator_keypair = KeyPair::new();
+    let environment = coordinator::initialize_test_environment(
+        &testing
+            .admin_public_keys(&[operator_keypair.pubkey().to_owned()])
+            .external_verifiers(&[operator_keypair.pubkey().to_owned()], 0)
+            .into(),
+    );
+
+    // Create token file, reset from the cohorts eventually updated by a previous test
+    let tokens_lock = lock_tokens();
+    std::fs::remove_dir_all(&*TOKENS_DIR).ok();
+    std::fs::create_dir_all(&*TOKENS_DIR).unwrap();
 
-    let file_path = tmp_dir.path().join("namada_tokens_cohort_1.json");
+    let file_path = TOKENS_DIR.join("namada_tokens_cohort_1.json");
     let mut token_file = std::fs::File::create(file_path).unwrap();
     token_file
         .write_all("[\"9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C\", \"9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek\", \"9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2\"]".as_bytes())
         .unwrap();
-    std::env::set_var("NAMADA_TOKENS_PATH", tmp_dir.path());
+    std::env::set_var("NAMADA_TOKENS_PATH", &*TOKENS_DIR);
 
     // Instantiate the coordinator
-    let mut coordinator = Coordinator::new(environment, Arc::new(Production)).unwrap();
+    let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Production)).unwrap();
+    drop(tokens_lock);
 
     let keypair1 = KeyPair::new();
     let keypair2 = KeyPair::new();
@@ -115,6 +149,12 @@ fn build_context() -> TestCtx {
         keypair: coordinator_keypair,
         locked_locators: None,
     };
+    let operator = TestParticipant {
+        _inner: Participant::new_verifier(operator_keypair.pubkey()),
+        address: coordinator_ip,
+        keypair: operator_keypair,
+        locked_locators: None,
+    };
 
     coordinator
         .add_to_queue(contributor1.clone(), Some(contributor1_ip.clone()), token, 10)
@@ -124,30 +164,87 @@ fn build_context() -> TestCtx {
     let (_, locked_locators) = coordinator.try_lock(&contributor1).unwrap();
 
     let coordinator: Arc<RwLock<Coordinator>> = Arc::new(RwLock::new(coordinator));
-
+    let (verification_queue, verification_requests) = VerificationQueue::new();
+
+    let routes = routes![
+        rest::get_login_nonce,
+        rest::login,
+        rest::join_queue,
+        rest::lock_chunk,
+        rest::await_turn,
+        rest::contribute_chunk,
+        rest::upload_chunk,
+        rest::get_upload_session,
+        rest::upload_chunk_part,
+        rest::complete_upload,
+        rest::update_coordinator,
+        rest::heartbeat,
+        rest::abandon_contribution,
+        rest::get_rejections,
+        rest::stop_coordinator,
+        rest::verify_chunks,
+        rest::request_verification,
+        rest::get_verification_status,
+        rest::lock_verification_task,
+        rest::get_verification_challenge,
+        rest::get_verification_response,
+        rest::post_verification_result,
+        rest::get_ceremony_status,
+        rest::get_coordinator_identity,
+        rest::get_stats,
+        rest::get_contributor_queue_status,
+        rest::post_contribution_info,
+        rest::get_contributions_info,
+        rest::get_healthcheck,
+        rest::get_liveness,
+        rest::get_readiness,
+        rest::get_events,
+        rest::get_openapi,
+        rest::rate_limited,
+        rest::ip_not_allowed,
+        rest::unsupported_api_version,
+        rest::get_contribution_url,
+        rest::get_challenge_url,
+        rest::get_challenge,
+        rest::get_coordinator_state,
+        rest::update_cohorts,
+        rest::apply_beacon,
+        rest::post_invite_codes,
+        rest::get_invite_codes,
+        rest::post_priority_tokens,
+        rest::rotate_key,
+        rest::ban,
+        rest::unban,
+        rest::get_ban_list,
+        rest::post_participant_priority,
+        rest::post_skip_current,
+        rest::post_snapshot,
+        rest::post_restore,
+        rest::get_storage_audit,
+        rest::get_audit_export,
+        rest::post_attestation,
+        rest::get_attestation_message,
+        rest::post_namada_address,
+        rest::post_identity_proof,
+        rest::get_feed,
+        rest::get_transcript,
+        rest::get_round_info,
+        rest::get_contributor_bundle,
+        rest::get_contribution_history,
+        rest::get_has_contributed,
+        rest::get_contributors
+    ];
+    let routes = rest_utils::trace_routes(routes);
     let rocket = rocket::build()
-        .mount(
-            "/",
-            routes![
-                rest::join_queue,
-                rest::lock_chunk,
-                rest::contribute_chunk,
-                rest::update_coordinator,
-                rest::heartbeat,
-                rest::stop_coordinator,
-                rest::verify_chunks,
-                rest::get_contributor_queue_status,
-                rest::post_contribution_info,
-                rest::get_contributions_info,
-                rest::get_healthcheck,
-                rest::get_contribution_url,
-                rest::get_challenge_url,
-                rest::get_coordinator_state,
-                rest::update_cohorts,
-                rest::post_attestation
-            ],
-        )
+        .mount(API_PREFIX, routes.clone())
+        .mount("/", routes)
         .manage(coordinator)
+        .manage(verification_queue)
+        .manage(TranscriptCache::default())
+        .manage(Geolocator::default())
+        .manage(IpAccess::from_environment(&environment))
+        .attach(ApiVersion)
+        .attach(IpAccess::from_environment(&environment))
         .register(
             "/",
             catchers![
@@ -157,7 +254,9 @@ fn build_context() -> TestCtx {
                 rest_utils::io_error,
                 rest_utils::unprocessable_entity,
                 rest_utils::mismatching_checksum,
-                rest_utils::invalid_header
+                rest_utils::invalid_header,
+                rest_utils::payload_too_large,
+                rest_utils::ip_not_allowed
             ],
         );
 
@@ -186,16 +285,36 @@ fn build_context() -> TestCtx {
         contributors: vec![test_participant1, test_participant2],
         unknown_participant,
         coordinator: coord_verifier,
-        _tokens_tmp_dir: tmp_dir,
+        operator,
+        verification_requests,
+        environment,
     }
 }
 
+/// Produces the signature of the request to be sent in the [`SIGNATURE_HEADER`], adding the timestamp header. The
+/// [`CONTRIBUTION_SIGNATURE_HEADER`], if any, must be added before.
+fn sign_request(req: &mut LocalRequest<'_>, keypair: &KeyPair, content: Option<RequestContent>) -> String {
+    let timestamp = chrono::Utc::now().timestamp();
+    req.add_header(Header::new(TIMESTAMP_HEADER, timestamp.to_string()));
+
+    let method = req.inner().method().as_str();
+    let path = req.inner().uri().to_string();
+    let mut headers = SignatureHeaders::new(method, path.into(), timestamp, keypair.pubkey(), content, None);
+    headers.contribution_signature = req
+        .inner()
+        .headers()
+        .get_one(CONTRIBUTION_SIGNATURE_HEADER)
+        .map(header_digest);
+
+    Production.sign(keypair.sigkey(), &headers.to_string()).unwrap()
+}
+
 /// Add headers and optional body to the request
 fn set_request<'a, T>(mut req: LocalRequest<'a>, keypair: &'a KeyPair, body: Option<&T>) -> LocalRequest<'a>
 where
     T: Serialize,
 {
-    let mut msg = keypair.pubkey().to_owned();
+    let mut content = None;
     req.add_header(Header::new(PUBKEY_HEADER, keypair.pubkey().to_owned()));
 
     if let Some(body) = body {
@@ -203,9 +322,9 @@ where
         let json_body = serde_json::to_string(body).unwrap();
         let mut hasher = Sha256::new();
         hasher.update(&json_body);
-        let digest = base64::encode(hasher.finalize());
-        msg = format!("{}{}{}", msg, json_body.len(), &digest);
-        req.add_header(Header::new(BODY_DIGEST_HEADER, format!("sha-256={}", digest)));
+        let digest = hasher.finalize();
+        content = Some(RequestContent::new(json_body.len(), digest));
+        req.add_header(Header::new(BODY_DIGEST_HEADER, format!("sha-256={}", base64::encode(&digest))));
 
         // Body length
         req.add_header(Header::new(CONTENT_LENGTH_HEADER, json_body.len().to_string()));
@@ -216,12 +335,28 @@ where
     }
 
     // Sign request
-    let signature = Production.sign(keypair.sigkey(), &msg).unwrap();
+    let signature = sign_request(&mut req, keypair, content);
     req.add_header(Header::new(SIGNATURE_HEADER, signature));
 
     req
 }
 
+/// Add the raw body and the signature headers to the request.
+fn set_raw_request<'a>(mut req: LocalRequest<'a>, keypair: &'a KeyPair, body: Vec<u8>) -> LocalRequest<'a> {
+    let mut hasher = Sha256::new();
+    hasher.update(&body);
+    let digest = hasher.finalize();
+
+    req.add_header(Header::new(PUBKEY_HEADER, keypair.pubkey().to_owned()));
+    req.add_header(Header::new(BODY_DIGEST_HEADER, format!("sha-256={}", base64::encode(&digest))));
+    req.add_header(Header::new(CONTENT_LENGTH_HEADER, body.len().to_string()));
+    req.add_header(ContentType::Binary);
+    let signature = sign_request(&mut req, keypair, Some(RequestContent::new(body.len(), digest)));
+    req.add_header(Header::new(SIGNATURE_HEADER, signature));
+
+    req.body(body)
+}
+
 #[test]
 fn get_status() {
     let access_token = "test-access_token";
@@ -268,6 +403,7 @@ fn get_serialized_tokens_zip(tokens: Vec<&str>) -> Vec<u8> {
 fn update_cohorts() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let _tokens_lock = lock_tokens();
 
     // Check tokens.zip file presence only when correct input
     // Remove tokens.zip file if present
@@ -309,235 +445,1434 @@ fn update_cohorts() {
 }
 
 #[test]
-fn stop_coordinator() {
+fn invite_codes() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let request = MintInviteCodesRequest {
+        count: 2,
+        notes: Some(String::from("partners")),
+        expires_in: Some(3600),
+    };
 
     // Wrong, request from non-coordinator participant
-    let mut req = client.get("/stop");
-    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let mut req = client.post("/invite_codes");
+    req = set_request::<MintInviteCodesRequest>(req, &ctx.contributors[0].keypair, Some(&request));
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Unauthorized);
     assert!(response.body().is_some());
 
-    // Shut the server down
-    req = client.get("/stop");
+    // Mint the codes
+    req = client.post("/invite_codes");
+    req = set_request::<MintInviteCodesRequest>(req, &ctx.coordinator.keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let minted: Vec<InviteCode> = response.into_json().unwrap();
+    assert_eq!(minted.len(), 2);
+    assert!(minted.iter().all(|c| c.notes.as_deref() == Some("partners") && c.expires_at.is_some()));
+
+    // Wrong, list from non-coordinator participant
+    req = client.get("/invite_codes");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // List the codes, none redeemed
+    req = client.get("/invite_codes");
     req = set_request::<()>(req, &ctx.coordinator.keypair, None);
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
-    assert!(response.body().is_none());
+    let listed: Vec<InviteCode> = response.into_json().unwrap();
+    assert_eq!(listed.len(), 2);
+    assert!(listed.iter().all(|c| c.redeemed_by.is_none()));
+
+    // The number and the validity of the minted codes are clamped
+    let request = MintInviteCodesRequest {
+        count: MAX_INVITE_CODES_PER_REQUEST + 1,
+        notes: None,
+        expires_in: Some(u64::MAX),
+    };
+    req = client.post("/invite_codes");
+    req = set_request::<MintInviteCodesRequest>(req, &ctx.coordinator.keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let minted: Vec<InviteCode> = response.into_json().unwrap();
+    assert_eq!(minted.len(), MAX_INVITE_CODES_PER_REQUEST);
+    let validity = MAX_INVITE_CODE_VALIDITY as i64;
+    assert!(minted
+        .iter()
+        .all(|c| (c.expires_at.unwrap() - c.created_at).whole_seconds() <= validity));
 }
 
 #[test]
-fn get_healthcheck() {
-    // Create status file
-    let mut status_file = tempfile::NamedTempFile::new_in(".").unwrap();
-    let file_content =
-        "{\"hash\":\"2e7f10b5a96f9f1e8c959acbce08483ccd9508e1\",\"timestamp\":\"Tue Jun 21 10:28:35 CEST 2022\"}";
-    status_file.write_all(file_content.as_bytes()).unwrap();
-    std::env::set_var("HEALTH_PATH", status_file.path());
-
+fn priority_tokens() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let request = IssuePriorityTokensRequest {
+        count: 1,
+        reliability: 20,
+        not_before: None,
+        expires_at: chrono::Utc::now().timestamp() + 3600,
+    };
 
-    let req = client.get("/healthcheck");
+    // Wrong, request from non-coordinator participant
+    let mut req = client.post("/priority_tokens");
+    req = set_request::<IssuePriorityTokensRequest>(req, &ctx.contributors[0].keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Issue the token
+    req = client.post("/priority_tokens");
+    req = set_request::<IssuePriorityTokensRequest>(req, &ctx.coordinator.keypair, Some(&request));
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
-    assert!(response.body().is_some());
+    let issued: Vec<String> = response.into_json().unwrap();
+    assert_eq!(issued.len(), 1);
 
-    // It's impossible to extract the String out of the Body struct of the response, need to pass through serde
-    let response_body: serde_json::Value = response.into_json().unwrap();
-    let response_str = serde_json::to_string(&response_body).unwrap();
-    if response_str != file_content {
-        panic!("JSON status content doesn't match the expected one")
-    }
+    // Redeem the token when joining the queue
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req.add_header(Header::new(PRIORITY_TOKEN_HEADER, issued[0].clone()));
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // Wrong, token already redeemed
+    let socket_address = SocketAddr::new(IpAddr::V4("0.0.0.4".parse().unwrap()), 8080);
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req.add_header(Header::new(PRIORITY_TOKEN_HEADER, issued[0].clone()));
+    req = set_request::<String>(
+        req,
+        &KeyPair::new(),
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(
+        response.into_json::<ErrorResponse>().unwrap().code,
+        ErrorCode::InvalidPriorityToken
+    );
 }
 
 #[test]
-fn get_contributor_queue_status() {
+fn rotate_key() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
 
-    // Non-existing contributor key
-    let mut req = client.get("/contributor/queue_status");
-    req = set_request::<()>(req, &ctx.unknown_participant.keypair, None);
+    // Wrong, request from non-coordinator participant
+    let mut req = client.post("/rotate_key");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Wrong, the rotated key would be lost at the next restart
+    std::env::remove_var("NAMADA_MPC_KEYPAIR_FILE");
+    req = client.post("/rotate_key");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+
+    // Rotate the key, the hand-off statement is signed by the old one
+    let keypair_file = tempfile::NamedTempFile::new_in(".").unwrap();
+    std::env::set_var("NAMADA_MPC_KEYPAIR_FILE", keypair_file.path());
+    std::env::set_var("NAMADA_MPC_KEYPAIR_PASSPHRASE", "passphrase");
+    req = client.post("/rotate_key");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
-    match response.into_json::<ContributorStatus>().unwrap() {
-        ContributorStatus::Other => (),
-        _ => panic!("Wrong ContributorStatus"),
-    }
+    let rotation: KeyRotation = response.into_json().unwrap();
+    assert_eq!(rotation.old_public_key, ctx.coordinator.keypair.pubkey());
+    assert!(rotation.verify());
 
-    // Ok
-    req = client.get("/contributor/queue_status");
-    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    // The new key is persisted for the next restart
+    let keypair = KeyPair::load_encrypted(keypair_file.path(), "passphrase").unwrap();
+    assert_eq!(keypair.pubkey(), rotation.new_public_key);
+
+    // Wrong, the old key is not the coordinator's anymore
+    req = client.post("/rotate_key");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // The rotation is published in the transcript
+    req = client.get("/transcript");
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
-    match response.into_json::<ContributorStatus>().unwrap() {
-        ContributorStatus::Round => (),
-        _ => panic!("Wrong ContributorStatus"),
-    }
+    let transcript: Transcript = response.into_json().unwrap();
+    assert_eq!(transcript.coordinator_public_key, rotation.new_public_key);
+    assert_eq!(transcript.key_rotations, vec![rotation.clone()]);
+    assert_eq!(
+        transcript.coordinator_public_keys(rotation.round_height),
+        vec![rotation.old_public_key.as_str(), rotation.new_public_key.as_str()]
+    );
+    assert!(transcript.verify_coordinator_keys());
 }
 
 #[test]
-fn heartbeat() {
+fn snapshot_restore() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
 
-    // Non-existing contributor key
-    let mut req = client.post("/contributor/heartbeat");
-    req = set_request::<()>(req, &ctx.unknown_participant.keypair, None);
+    // Wrong, request from non-coordinator participant
+    let mut req = client.post("/admin/snapshot");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
     let response = req.dispatch();
-    assert_eq!(response.status(), Status::InternalServerError);
-    assert!(response.body().is_some());
+    assert_eq!(response.status(), Status::Unauthorized);
 
-    // Ok
-    req = client.post("/contributor/heartbeat");
-    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    req = client.post("/admin/snapshot");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
-    assert!(response.body().is_none());
+    assert_eq!(response.content_type(), Some(ContentType::GZIP));
+    let snapshot = response.into_bytes().unwrap();
+
+    // Wrong, body not matching the signed digest
+    req = client.post("/admin/restore");
+    req = set_raw_request(req, &ctx.coordinator.keypair, snapshot.clone());
+    req = req.body("tampered");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Wrong, not a snapshot
+    req = client.post("/admin/restore");
+    req = set_raw_request(req, &ctx.coordinator.keypair, b"snapshot".to_vec());
+    let response = req.dispatch();
+    assert_ne!(response.status(), Status::Ok);
+
+    // Restore the snapshot, its state is the current one
+    req = client.post("/admin/restore");
+    req = set_raw_request(req, &ctx.coordinator.keypair, snapshot);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let manifest: SnapshotManifest = response.into_json().unwrap();
+    assert_eq!(manifest.round_height, 1);
+    assert!(manifest.files.contains_key("coordinator.json"));
+
+    req = client.get("/ceremony/status");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let status: CeremonyStatus = response.into_json().unwrap();
+    assert_eq!(status.round_height, 1);
 }
 
 #[test]
-fn update_coordinator() {
+fn audit_export() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
 
-    // Wrong, request comes from normal contributor
-    let mut req = client.get("/update");
+    // Wrong, request from non-coordinator participant
+    let mut req = client.get("/admin/audit/export?format=json");
     req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Unauthorized);
-    assert!(response.body().is_some());
 
-    // Ok, request comes from coordinator itself
-    req = client.get("/update");
+    // The whole log forms a chain signed by the coordinator
+    req = client.get("/admin/audit/export?format=json");
     req = set_request::<()>(req, &ctx.coordinator.keypair, None);
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
-    assert!(response.body().is_none());
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    let records: Vec<AuditRecord> = response.into_json().unwrap();
+    assert!(!records.is_empty());
+    assert_eq!(audit::verify_chain(&records), Ok(()));
+
+    req = client.get("/admin/audit/export?format=csv");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::CSV));
+    let csv = response.into_string().unwrap();
+    assert!(csv.starts_with("sequence,timestamp,event,details,"));
+    assert_eq!(csv.lines().count(), records.len() + 1);
+
+    // Only the header is left out of the time range
+    let from = records.last().unwrap().timestamp + 1;
+    req = client.get(format!("/admin/audit/export?format=csv&from={}", from));
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap().lines().count(), 1);
 }
 
 #[test]
-fn wrong_post_attestation() {
+fn request_id() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
 
-    // Wrong, missing contribution
-    let mut req = client.post("/contributor/attestation");
-    req = set_request::<(u64, String)>(
-        req,
-        &ctx.contributors[0].keypair,
-        Some(&(1, String::from("https://namada.net"))),
-    );
+    // The id given by the client is returned with the error
+    let mut req = client
+        .get("/contributor/lock_chunk")
+        .header(Header::new(REQUEST_ID_HEADER, "support-1234"));
+    req = set_request::<u8>(req, &ctx.contributors[1].keypair, None);
     let response = req.dispatch();
-    assert_eq!(response.status(), Status::InternalServerError);
-    assert!(response.body().is_some());
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.headers().get_one(REQUEST_ID_HEADER), Some("support-1234"));
+    let error = response.into_json::<ErrorResponse>().unwrap();
+    assert_eq!(error.request_id.as_deref(), Some("support-1234"));
+
+    // An invalid id is replaced by a generated one
+    let response = client
+        .get("/ceremony/status")
+        .header(Header::new(REQUEST_ID_HEADER, "not an id"))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let generated = response.headers().get_one(REQUEST_ID_HEADER).unwrap();
+    assert_eq!(generated.len(), 32);
+    assert!(generated.chars().all(|c| c.is_ascii_hexdigit()));
+
+    // The audit records carry the id of the request that caused them
+    let request = BanRequest {
+        pubkey: Some(ctx.unknown_participant.keypair.pubkey().to_owned()),
+        ip: None,
+    };
+    req = client.post("/ban").header(Header::new(REQUEST_ID_HEADER, "ban-1"));
+    req = set_request::<BanRequest>(req, &ctx.coordinator.keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    req = client.get("/admin/audit/export?format=json");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    let records: Vec<AuditRecord> = response.into_json().unwrap();
+    let ban = records
+        .iter()
+        .rev()
+        .find(|record| record.event.name() == "ban")
+        .unwrap();
+    assert_eq!(ban.request_id.as_deref(), Some("ban-1"));
+    assert_eq!(audit::verify_chain(&records), Ok(()));
 }
 
 #[test]
-fn join_queue() {
+fn ban_list() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let banned_ip = IpAddr::V4("0.0.0.9".parse().unwrap());
+    let request = BanRequest {
+        pubkey: Some(ctx.unknown_participant.keypair.pubkey().to_owned()),
+        ip: Some(banned_ip),
+    };
+    let token = "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek";
 
-    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
-
-    // Wrong request, invalid token
-    let mut req = client.post("/contributor/join_queue").remote(socket_address);
-    req = set_request::<String>(
-        req,
-        &ctx.unknown_participant.keypair,
-        Some(&format!(
-            "9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV3"
-        )),
-    );
+    // Wrong, request from non-coordinator participant
+    let mut req = client.post("/ban");
+    req = set_request::<BanRequest>(req, &ctx.contributors[0].keypair, Some(&request));
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Unauthorized);
-    assert!(response.body().is_some());
 
-    // Ok request
-    req = client.post("/contributor/join_queue").remote(socket_address);
-    req = set_request::<String>(
-        req,
-        &ctx.unknown_participant.keypair,
-        Some(&format!(
-            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
-        )),
-    );
+    // Ban the participant and the ip
+    req = client.post("/ban");
+    req = set_request::<BanRequest>(req, &ctx.coordinator.keypair, Some(&request));
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
-    assert!(response.body().is_some());
 
-    // Wrong request, IP already in queue
+    req = client.get("/ban");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let ban_list: BanList = response.into_json().unwrap();
+    assert_eq!(ban_list.pubkeys, vec![ctx.unknown_participant.keypair.pubkey().to_owned()]);
+    assert_eq!(ban_list.ips, vec![banned_ip]);
+
+    // Wrong, banned participant
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
     req = client.post("/contributor/join_queue").remote(socket_address);
-    req = set_request::<String>(
-        req,
-        &ctx.contributors[1].keypair,
-        Some(&format!(
-            "9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2"
-        )),
-    );
+    req = set_request::<String>(req, &ctx.unknown_participant.keypair, Some(&token.to_string()));
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Unauthorized);
-    assert!(response.body().is_some());
 
-    // Wrong request, token already in queue
-    let socket_address = SocketAddr::new(IpAddr::V4("0.0.0.4".parse().unwrap()), 8080);
-    req = client.post("/contributor/join_queue").remote(socket_address);
-    req = set_request::<String>(
-        req,
-        &ctx.contributors[1].keypair,
-        Some(&format!(
-            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
-        )),
-    );
+    // Wrong, banned ip
+    req = client.post("/contributor/join_queue").remote(SocketAddr::new(banned_ip, 8080));
+    req = set_request::<String>(req, &ctx.contributors[1].keypair, Some(&token.to_string()));
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Unauthorized);
-    assert!(response.body().is_some());
 
-    // Wrong request, already existing contributor
+    // Unban the participant, who can now join the queue
+    req = client.post("/unban");
+    req = set_request::<BanRequest>(req, &ctx.coordinator.keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
     req = client.post("/contributor/join_queue").remote(socket_address);
-    req = set_request::<String>(
-        req,
-        &ctx.unknown_participant.keypair,
-        Some(&format!(
-            "9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2"
-        )),
-    );
+    req = set_request::<String>(req, &ctx.unknown_participant.keypair, Some(&token.to_string()));
     let response = req.dispatch();
-    assert_eq!(response.status(), Status::Unauthorized);
-    assert!(response.body().is_some());
+    assert_eq!(response.status(), Status::Ok);
 }
 
-/// Test wrong usage of lock_chunk.
 #[test]
-fn wrong_lock_chunk() {
+fn participant_priority() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let token = "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek";
+    let mut request = ParticipantPriorityRequest {
+        pubkey: ctx.unknown_participant.keypair.pubkey().to_owned(),
+        reliability: 30,
+    };
 
-    // Wrong request, unknown participant
-    let mut req = client.get("/contributor/lock_chunk");
-    req = set_request::<u8>(req, &ctx.unknown_participant.keypair, None);
+    // Wrong, request from non-coordinator participant
+    let mut req = client.post("/admin/participant/priority");
+    req = set_request::<ParticipantPriorityRequest>(req, &ctx.contributors[0].keypair, Some(&request));
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Unauthorized);
-    assert!(response.body().is_some());
-}
 
-/// Test wrong usage of get_challenge.
-#[test]
-fn wrong_get_challenge() {
-    let ctx = build_context();
-    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
-
-    // Wrong request, non-json body
-    let req = client
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(req, &ctx.unknown_participant.keypair, Some(&token.to_string()));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // Raise the priority of the queued participant, then lower it
+    req = client.post("/admin/participant/priority");
+    req = set_request::<ParticipantPriorityRequest>(req, &ctx.coordinator.keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let previous: u8 = response.into_json().unwrap();
+    assert_eq!(previous, 10);
+
+    request.reliability = 5;
+    req = client.post("/admin/participant/priority");
+    req = set_request::<ParticipantPriorityRequest>(req, &ctx.coordinator.keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let previous: u8 = response.into_json().unwrap();
+    assert_eq!(previous, 30);
+}
+
+#[test]
+fn skip_current() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let request = SkipCurrentRequest {
+        ban: true,
+        reliability: Some(0),
+    };
+
+    // Wrong, request from non-coordinator participant
+    let mut req = client.post("/admin/skip_current");
+    req = set_request::<SkipCurrentRequest>(req, &ctx.contributors[1].keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Skip and ban the current contributor
+    req = client.post("/admin/skip_current");
+    req = set_request::<SkipCurrentRequest>(req, &ctx.coordinator.keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let skipped: Vec<String> = response.into_json().unwrap();
+    assert_eq!(skipped, vec![ctx.contributors[0].keypair.pubkey().to_owned()]);
+
+    req = client.get("/ban");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    let ban_list: BanList = response.into_json().unwrap();
+    assert_eq!(ban_list.pubkeys, skipped);
+
+    // The skipped contributor can't lock its chunk anymore
+    req = client.get("/contributor/lock_chunk");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_ne!(response.status(), Status::Ok);
+
+    // Nothing left to skip
+    req = client.post("/admin/skip_current");
+    req = set_request::<SkipCurrentRequest>(req, &ctx.coordinator.keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let skipped: Vec<String> = response.into_json().unwrap();
+    assert!(skipped.is_empty());
+}
+
+#[test]
+fn stop_coordinator() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, request from non-coordinator participant
+    let mut req = client.get("/stop");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Shut the server down
+    req = client.get("/stop");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.body().is_none());
+}
+
+#[test]
+fn stop_draining() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let mut req = client.get("/stop");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    assert_eq!(req.dispatch().status(), Status::Ok);
+
+    // Wrong, no one can join the queue of the draining coordinator
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.into_json::<ErrorResponse>().unwrap().code, ErrorCode::Draining);
+
+    // Wrong, a new contribution can't start
+    req = client.get("/contributor/lock_chunk");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    assert_eq!(req.dispatch().status(), Status::ServiceUnavailable);
+
+    // Still ready, for the contribution in flight to be completed, the draining being reported apart
+    let response = client.get("/readyz").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let readiness = response.into_json::<Readiness>().unwrap();
+    assert!(readiness.draining && readiness.is_ready());
+}
+
+#[test]
+fn get_healthcheck() {
+    // Create status file
+    let mut status_file = tempfile::NamedTempFile::new_in(".").unwrap();
+    let file_content =
+        "{\"hash\":\"2e7f10b5a96f9f1e8c959acbce08483ccd9508e1\",\"timestamp\":\"Tue Jun 21 10:28:35 CEST 2022\"}";
+    status_file.write_all(file_content.as_bytes()).unwrap();
+    std::env::set_var("HEALTH_PATH", status_file.path());
+
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let req = client.get("/healthcheck");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.body().is_some());
+
+    // It's impossible to extract the String out of the Body struct of the response, need to pass through serde
+    let response_body: serde_json::Value = response.into_json().unwrap();
+    let response_str = serde_json::to_string(&response_body).unwrap();
+    if response_str != file_content {
+        panic!("JSON status content doesn't match the expected one")
+    }
+}
+
+#[test]
+fn probes() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let response = client.get("/healthz").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get("/readyz").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.into_json::<Readiness>().unwrap().is_ready());
+
+    // Wrong, the verification worker stopped
+    drop(ctx.verification_requests);
+    let response = client.get("/readyz").dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    let readiness = response.into_json::<Readiness>().unwrap();
+    assert!(readiness.storage_reachable && readiness.round_loaded);
+    assert!(!readiness.verification_worker_alive);
+
+    // The process is still alive
+    let response = client.get("/healthz").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn get_ceremony_status() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Public endpoint, no signature required
+    let response = client.get("/ceremony/status").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let status = response.into_json::<CeremonyStatus>().unwrap();
+    assert_eq!(status.round_height, ROUND_HEIGHT);
+    assert_eq!(status.number_of_chunks, 1);
+    assert_eq!(status.queue_length, 0);
+    assert_eq!(status.current_contributor.as_deref(), Some(ctx.contributors[0].keypair.pubkey()));
+    assert_eq!(status.verified_contributions, 0);
+}
+
+#[test]
+fn coordinator_identity() {
+    let ctx = build_context_with(Testing::default().sign_challenges(true));
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let coordinator_pubkey = ctx.coordinator.keypair.pubkey();
+
+    // Public endpoint, signed by the key of the coordinator
+    let response = client.get("/coordinator/identity").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let identity = response.into_json::<CoordinatorIdentity>().unwrap();
+    assert_eq!(identity.coordinator_public_key, coordinator_pubkey);
+    assert_eq!(identity.parameters.number_of_chunks, 1);
+    assert!(identity.signed_challenges);
+    assert!(Production.verify(
+        coordinator_pubkey,
+        &coordinator_identity_message(&identity),
+        &identity.signature
+    ));
+
+    // Signed once, on the first request
+    let response = client.get("/coordinator/identity").dispatch();
+    assert_eq!(response.into_json::<CoordinatorIdentity>().unwrap().signature, identity.signature);
+
+    // Wrong, tampered parameters
+    let mut tampered = identity.clone();
+    tampered.parameters.power += 1;
+    assert!(!Production.verify(
+        coordinator_pubkey,
+        &coordinator_identity_message(&tampered),
+        &identity.signature
+    ));
+
+    // The challenge is served with its signature
+    let mut req = client.get(format!("/contributor/challenge/{}", ROUND_HEIGHT));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let hash = response.headers().get_one(CHALLENGE_HASH_HEADER).unwrap();
+    let signature = response.headers().get_one(CHALLENGE_SIGNATURE_HEADER).unwrap();
+    assert!(Production.verify(coordinator_pubkey, &challenge_message(ROUND_HEIGHT, hash), signature));
+}
+
+#[test]
+fn get_stats() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Public endpoint, the contributors are not geolocated by default
+    let response = client.get("/stats").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let stats = response.into_json::<CeremonyStats>().unwrap();
+    assert_eq!(stats.unique_countries, None);
+
+    // The first contributor is still contributing
+    assert!(stats.contributions_per_hour.is_empty());
+    assert_eq!(stats.average_contribution_seconds, None);
+    assert_eq!(stats.dropout_rate, 0.0);
+    assert_eq!(stats.verification_backlog, 0);
+}
+
+#[test]
+fn get_contributor_queue_status() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Non-existing contributor key
+    let mut req = client.get("/contributor/queue_status");
+    req = set_request::<()>(req, &ctx.unknown_participant.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    match response.into_json::<ContributorStatus>().unwrap() {
+        ContributorStatus::Other => (),
+        _ => panic!("Wrong ContributorStatus"),
+    }
+
+    // Ok
+    req = client.get("/contributor/queue_status");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    match response.into_json::<ContributorStatus>().unwrap() {
+        ContributorStatus::Round => (),
+        _ => panic!("Wrong ContributorStatus"),
+    }
+}
+
+#[test]
+fn abandon_contribution() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, not the current contributor
+    let mut req = client.post("/contributor/abandon");
+    req = set_request::<()>(req, &ctx.contributors[1].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Give up on the contribution, going back to the queue
+    req = client.post("/contributor/abandon");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    req = client.get("/contributor/queue_status");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    match response.into_json::<ContributorStatus>().unwrap() {
+        ContributorStatus::Queue(..) => (),
+        _ => panic!("Wrong ContributorStatus"),
+    }
+
+    // Wrong, no longer the current contributor
+    req = client.post("/contributor/abandon");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[test]
+fn get_rejections() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // No contribution rejected yet
+    let mut req = client.get("/contributor/rejections");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.into_json::<Vec<ContributionRejection>>().unwrap().is_empty());
+}
+
+#[test]
+fn login() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let keypair = &ctx.contributors[0].keypair;
+    let login_nonce = || {
+        let req = set_request::<()>(client.get("/contributor/login"), keypair, None);
+        let response = req.dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        response.into_json::<String>().unwrap()
+    };
+
+    // Wrong, nonce signed by another key
+    let nonce = login_nonce();
+    let request = LoginRequest {
+        pubkey: keypair.pubkey().to_owned(),
+        signature: Production
+            .sign(
+                ctx.unknown_participant.keypair.sigkey(),
+                &phase2_client_sdk::login_message(keypair.pubkey(), &nonce),
+            )
+            .unwrap(),
+        nonce,
+    };
+    let req = set_request(client.post("/contributor/login"), keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.into_json::<ErrorResponse>().unwrap().code, ErrorCode::InvalidSignature);
+
+    // Open the session
+    let nonce = login_nonce();
+    let request = LoginRequest {
+        pubkey: keypair.pubkey().to_owned(),
+        signature: Production
+            .sign(keypair.sigkey(), &phase2_client_sdk::login_message(keypair.pubkey(), &nonce))
+            .unwrap(),
+        nonce,
+    };
+    let req = set_request(client.post("/contributor/login"), keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let session: Session = response.into_json().unwrap();
+    assert!(session.expires_at > chrono::Utc::now());
+
+    // Wrong, the nonce is single-use
+    let req = set_request(client.post("/contributor/login"), keypair, Some(&request));
+    assert_eq!(req.dispatch().status(), Status::Unauthorized);
+
+    // The token replaces the signature of the request
+    let mut req = client.get("/contributor/queue_status");
+    req.add_header(Header::new(SESSION_TOKEN_HEADER, session.token.clone()));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    match response.into_json::<ContributorStatus>().unwrap() {
+        ContributorStatus::Round => (),
+        _ => panic!("Wrong ContributorStatus"),
+    }
+
+    // Wrong, tampered token
+    let mut req = client.get("/contributor/queue_status");
+    let token = session.token.replacen(keypair.pubkey(), ctx.contributors[1].keypair.pubkey(), 1);
+    req.add_header(Header::new(SESSION_TOKEN_HEADER, token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.into_json::<ErrorResponse>().unwrap().code, ErrorCode::InvalidSession);
+}
+
+#[test]
+fn session_on_admin_route() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let keypair = &ctx.coordinator.keypair;
+
+    let req = set_request::<()>(client.get("/contributor/login"), keypair, None);
+    let nonce = req.dispatch().into_json::<String>().unwrap();
+    let request = LoginRequest {
+        pubkey: keypair.pubkey().to_owned(),
+        signature: Production
+            .sign(
+                keypair.sigkey(),
+                &phase2_client_sdk::login_message(keypair.pubkey(), &nonce),
+            )
+            .unwrap(),
+        nonce,
+    };
+    let req = set_request(client.post("/contributor/login"), keypair, Some(&request));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let session: Session = response.into_json().unwrap();
+
+    // Wrong, the admin routes always require a signed request, even from the coordinator
+    let mut req = client.get("/ban");
+    req.add_header(Header::new(SESSION_TOKEN_HEADER, session.token));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    let error: ErrorResponse = response.into_json().unwrap();
+    assert_eq!(error.code, ErrorCode::InvalidSignature);
+}
+
+#[test]
+fn heartbeat() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Non-existing contributor key
+    let mut req = client.post("/contributor/heartbeat");
+    req = set_request::<()>(req, &ctx.unknown_participant.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert!(response.body().is_some());
+
+    // Ok
+    req = client.post("/contributor/heartbeat");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.body().is_none());
+}
+
+#[test]
+fn tls_config() {
+    // Plain http by default
+    let figment = rest_utils::rocket_config(&Testing::default().into());
+    assert!(figment.find_value("tls").is_err());
+
+    let figment = rest_utils::rocket_config(&Testing::default().tls("cert.pem", "key.pem").into());
+    assert_eq!(figment.extract_inner::<String>("tls.certs").unwrap(), "cert.pem");
+    assert_eq!(figment.extract_inner::<String>("tls.key").unwrap(), "key.pem");
+}
+
+#[test]
+fn body_limits() {
+    let limits = BodyLimits {
+        tiny: 16,
+        ..BodyLimits::default()
+    };
+    let figment = rest_utils::rocket_config(&Testing::default().body_limits(limits).into());
+    assert_eq!(figment.extract_inner::<u64>("limits.tiny").unwrap(), 16);
+    assert_eq!(
+        figment.extract_inner::<u64>("limits.contribution").unwrap(),
+        limits.contribution
+    );
+
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, the token exceeds the default limit of the tiny bodies
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+    let mut req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(req, &ctx.unknown_participant.keypair, Some(&"9".repeat(2048)));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+    let error: ErrorResponse = response.into_json().unwrap();
+    assert_eq!(error.code, ErrorCode::PayloadTooLarge);
+}
+
+#[test]
+fn ip_filters() {
+    let contributor_filter = IpFilter {
+        allow: vec![],
+        deny: vec!["192.0.2.0/24".parse().unwrap()],
+    };
+    let admin_filter = IpFilter {
+        allow: vec!["198.51.100.7/32".parse().unwrap()],
+        deny: vec![],
+    };
+    let proxy: IpNet = "10.0.0.0/8".parse().unwrap();
+    let ctx = build_context_with(
+        Testing::default()
+            .ip_filters(contributor_filter, admin_filter)
+            .trusted_proxies(&[proxy]),
+    );
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let address = |ip: &str| SocketAddr::new(ip.parse().unwrap(), 8080);
+
+    // Wrong, denied network
+    let mut req = client.get("/contributor/queue_status").remote(address("192.0.2.1"));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(
+        response.into_json::<ErrorResponse>().unwrap().code,
+        ErrorCode::Unauthorized
+    );
+
+    // Wrong, denied client behind the trusted proxy, the addresses on its left being set by the client
+    req = client
+        .get("/contributor/queue_status")
+        .remote(address("10.0.0.1"))
+        .header(Header::new("X-Forwarded-For", "198.51.100.7, 192.0.2.1"));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    assert_eq!(req.dispatch().status(), Status::Forbidden);
+
+    // Wrong, the routes not authenticating the request are filtered too
+    let response = client
+        .post("/contributor/login")
+        .remote(address("192.0.2.1"))
+        .json(&())
+        .dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    let uri = format!(
+        "{}/contributor/{}/history",
+        API_PREFIX,
+        ctx.contributors[0].keypair.pubkey()
+    );
+    let response = client.get(uri).remote(address("192.0.2.1")).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(
+        response.into_json::<ErrorResponse>().unwrap().code,
+        ErrorCode::Unauthorized
+    );
+
+    // Ok, address not denied
+    req = client.get("/contributor/queue_status").remote(address("203.0.113.1"));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    assert_eq!(req.dispatch().status(), Status::Ok);
+
+    // Wrong, the admin routes are only reachable from the allowed networks
+    req = client
+        .get("/admin/audit/export?format=json")
+        .remote(address("203.0.113.1"));
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    assert_eq!(req.dispatch().status(), Status::Forbidden);
+
+    // Wrong, the header is ignored if the peer is not a trusted proxy
+    req = client
+        .get("/admin/audit/export?format=json")
+        .remote(address("203.0.113.1"))
+        .header(Header::new("X-Forwarded-For", "198.51.100.7"));
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    assert_eq!(req.dispatch().status(), Status::Forbidden);
+
+    // Ok, allowed address forwarded by the trusted proxy
+    req = client
+        .get("/admin/audit/export?format=json")
+        .remote(address("10.0.0.1"))
+        .header(Header::new("X-Forwarded-For", "198.51.100.7"));
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    assert_eq!(req.dispatch().status(), Status::Ok);
+}
+
+#[test]
+fn rate_limit() {
+    let ctx = build_context();
+    let rocket = ctx.rocket.attach(RateLimit::new(2, std::time::Duration::from_secs(60)));
+    let client = Client::tracked(rocket).expect("Invalid rocket instance");
+
+    // Ok, within the burst
+    for _ in 0..2 {
+        let mut req = client.post("/contributor/heartbeat");
+        req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+        let response = req.dispatch();
+        assert_ne!(response.status(), Status::TooManyRequests);
+    }
+
+    // Wrong, bucket of the public key is empty
+    let mut req = client.post("/contributor/heartbeat");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::TooManyRequests);
+    assert!(response.headers().get_one("Retry-After").is_some());
+    let error: ErrorResponse = response.into_json().unwrap();
+    assert_eq!(error.code, ErrorCode::RateLimited);
+
+    // Ok, other participants have their own bucket
+    req = client.post("/contributor/heartbeat");
+    req = set_request::<()>(req, &ctx.contributors[1].keypair, None);
+    let response = req.dispatch();
+    assert_ne!(response.status(), Status::TooManyRequests);
+
+    // Wrong, the rejection route can't be reached directly
+    let response = client.get("/rate_limited").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn rate_limit_client_address() {
+    let ctx = build_context();
+    let proxy: IpNet = "10.0.0.0/8".parse().unwrap();
+    let environment: Environment = Testing::default()
+        .rate_limit(1, time::Duration::minutes(1))
+        .trusted_proxies(&[proxy])
+        .into();
+    let rocket = ctx.rocket.attach(RateLimit::from_environment(&environment));
+    let client = Client::tracked(rocket).expect("Invalid rocket instance");
+    let address = |ip: &str| SocketAddr::new(ip.parse().unwrap(), 8080);
+    let forwarded = |client_ip: &str| Header::new("X-Forwarded-For", client_ip.to_owned());
+
+    // Ok, the bucket of the client forwarded by the trusted proxy is full
+    let mut req = client
+        .post("/contributor/heartbeat")
+        .remote(address("10.0.0.1"))
+        .header(forwarded("203.0.113.1"));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    assert_ne!(req.dispatch().status(), Status::TooManyRequests);
+
+    // Wrong, bucket of the client address is empty, whichever proxy forwards it
+    req = client
+        .post("/contributor/heartbeat")
+        .remote(address("10.0.0.2"))
+        .header(forwarded("203.0.113.1"));
+    req = set_request::<()>(req, &ctx.contributors[1].keypair, None);
+    assert_eq!(req.dispatch().status(), Status::TooManyRequests);
+
+    // Wrong, bucket of the public key is empty, the one of the new client address is left untouched
+    req = client
+        .post("/contributor/heartbeat")
+        .remote(address("10.0.0.1"))
+        .header(forwarded("203.0.113.2"));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    assert_eq!(req.dispatch().status(), Status::TooManyRequests);
+
+    // Ok, the proxy itself is not limited and the rejections above took no token
+    req = client
+        .post("/contributor/heartbeat")
+        .remote(address("10.0.0.1"))
+        .header(forwarded("203.0.113.2"));
+    req = set_request::<()>(req, &ctx.contributors[1].keypair, None);
+    assert_ne!(req.dispatch().status(), Status::TooManyRequests);
+}
+
+#[test]
+fn cors() {
+    let ctx = build_context();
+    let origin = "https://contribute.namada.net";
+    let rocket = ctx.rocket.attach(Cors::new(&[origin.to_string()]));
+    let client = Client::tracked(rocket).expect("Invalid rocket instance");
+
+    // Ok, preflight of an allowed origin
+    let response = client
+        .options("/contributor/heartbeat")
+        .header(Header::new("Origin", origin))
+        .header(Header::new("Access-Control-Request-Method", "POST"))
+        .header(Header::new(
+            "Access-Control-Request-Headers",
+            "ats-pubkey, ats-signature",
+        ))
+        .dispatch();
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), Some(origin));
+    assert_eq!(
+        response.headers().get_one("Access-Control-Allow-Headers"),
+        Some("ats-pubkey, ats-signature")
+    );
+    assert!(response
+        .headers()
+        .get_one("Access-Control-Allow-Methods")
+        .unwrap()
+        .contains("POST"));
+    assert!(response.body().is_none());
+
+    // Ok, the actual request exposes the headers of the API
+    let response = client.get("/healthz").header(Header::new("Origin", origin)).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), Some(origin));
+    assert!(response
+        .headers()
+        .get_one("Access-Control-Expose-Headers")
+        .unwrap()
+        .contains(REQUEST_ID_HEADER));
+
+    // Wrong, origin not allowed
+    let response = client
+        .options("/contributor/heartbeat")
+        .header(Header::new("Origin", "https://example.com"))
+        .header(Header::new("Access-Control-Request-Method", "POST"))
+        .dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.headers().get_one("Access-Control-Allow-Origin").is_none());
+}
+
+#[test]
+fn openapi_specification() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Ok, public endpoint
+    let response = client.get("/openapi.json").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let specification: serde_json::Value = response.into_json().unwrap();
+    assert_eq!(specification["openapi"], "3.0.3");
+
+    // Every mounted route is documented
+    for route in client.rocket().routes() {
+        let path = route.uri.path().replace('<', "{").replace('>', "}");
+        let path = path.strip_prefix(API_PREFIX).unwrap_or(&path);
+        if path == "/rate_limited" || path == "/ip_not_allowed" || path == "/unsupported_api_version" {
+            continue;
+        }
+        let method = route.method.as_str().to_lowercase();
+        assert!(
+            specification["paths"][path].get(&method).is_some(),
+            "Undocumented route {} {}",
+            method,
+            path
+        );
+    }
+}
+
+#[test]
+fn api_version() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Ok, versioned path, signed with its prefix
+    let mut req = client
+        .post(format!("{}/contributor/heartbeat", API_PREFIX))
+        .header(Header::new(API_VERSION_HEADER, API_VERSION.to_string()));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.headers().get_one(API_VERSION_HEADER),
+        Some(API_VERSION.to_string().as_str())
+    );
+    assert!(response.headers().get_one("Deprecation").is_none());
+
+    // Ok, unversioned path, deprecated
+    let mut req = client.post("/contributor/heartbeat");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Deprecation"), Some("true"));
+
+    // Wrong, unsupported version
+    let mut req = client
+        .post(format!("{}/contributor/heartbeat", API_PREFIX))
+        .header(Header::new(API_VERSION_HEADER, "2"));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    let error: ErrorResponse = response.into_json().unwrap();
+    assert_eq!(error.code, ErrorCode::UnsupportedApiVersion);
+
+    // Wrong, the rejection route can't be reached directly
+    let response = client.get("/unsupported_api_version").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn update_coordinator() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, request comes from normal contributor
+    let mut req = client.get("/update");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Ok, request comes from coordinator itself
+    req = client.get("/update");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.body().is_none());
+}
+
+#[test]
+fn wrong_post_attestation() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, missing contribution
+    let mut req = client.post("/contributor/attestation");
+    req = set_request::<(u64, String)>(
+        req,
+        &ctx.contributors[0].keypair,
+        Some(&(1, String::from("https://namada.net"))),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert!(response.body().is_some());
+}
+
+#[test]
+fn wrong_get_attestation_message() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, participant has not contributed yet
+    let req = client.get(format!(
+        "/contributor/attestation_message/{}",
+        ctx.contributors[0].keypair.pubkey()
+    ));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.body().is_some());
+}
+
+#[test]
+fn wrong_post_namada_address() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let address = bech32::encode("atest", b"imp::test address".to_base32(), bech32::Variant::Bech32m).unwrap();
+
+    // Wrong, binding signed with another key
+    let binding = NamadaAddressBinding::try_new(&Production, &ctx.contributors[1].keypair, address.clone()).unwrap();
+    let mut req = client.post("/contributor/namada_address");
+    req = set_request::<(u64, NamadaAddressBinding)>(req, &ctx.contributors[0].keypair, Some(&(1, binding)));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.body().is_some());
+
+    // Wrong, missing contribution
+    let binding = NamadaAddressBinding::try_new(&Production, &ctx.contributors[0].keypair, address).unwrap();
+    let mut req = client.post("/contributor/namada_address");
+    req = set_request::<(u64, NamadaAddressBinding)>(req, &ctx.contributors[0].keypair, Some(&(1, binding)));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+}
+
+#[test]
+fn wrong_post_identity_proof() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let identity_proof = IdentityProof {
+        provider: IdentityProvider::Keybase(String::from("namada")),
+        signed_statement: String::from("not a signed statement"),
+    };
+
+    // Wrong, missing contribution
+    let mut req = client.post("/contributor/identity_proof");
+    req = set_request::<(u64, IdentityProof)>(req, &ctx.contributors[0].keypair, Some(&(1, identity_proof)));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+}
+
+#[test]
+fn get_feed() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Ok, empty feed
+    let req = client.get("/feed.atom");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let feed = response.into_string().unwrap();
+    assert!(feed.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+    assert!(!feed.contains("<entry>"));
+}
+
+#[test]
+fn get_contributor_bundle() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, no contribution
+    let req = client.get(format!("/contributor/{}/bundle.zip", ctx.contributors[0].keypair.pubkey()));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.body().is_some());
+}
+
+#[test]
+fn join_queue() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+
+    // Wrong request, invalid token
+    let mut req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV3"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Ok request
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    // No contribution slot ended yet to estimate the wait from, the body is the cohort of the token
+    assert!(response.headers().get_one(ESTIMATED_WAIT_HEADER).is_none());
+    assert!(response.into_json::<u64>().is_some());
+
+    // Wrong request, IP already in queue
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &ctx.contributors[1].keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Wrong request, token already in queue
+    let socket_address = SocketAddr::new(IpAddr::V4("0.0.0.4".parse().unwrap()), 8080);
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &ctx.contributors[1].keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Wrong request, already existing contributor
+    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+}
+
+/// Test wrong usage of lock_chunk.
+#[test]
+fn wrong_lock_chunk() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong request, unknown participant
+    let mut req = client.get("/contributor/lock_chunk");
+    req = set_request::<u8>(req, &ctx.unknown_participant.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Wrong request, not the current contributor
+    req = client.get("/contributor/lock_chunk");
+    req = set_request::<u8>(req, &ctx.contributors[1].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    let error = response.into_json::<ErrorResponse>().unwrap();
+    assert_eq!(error.code, ErrorCode::NotCurrentContributor);
+    assert!(error.retry_after.is_none());
+}
+
+/// Test wrong usage of await_turn.
+#[test]
+fn wrong_await_turn() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong request, participant not in the queue
+    let mut req = client.get("/contributor/await_turn");
+    req = set_request::<u8>(req, &ctx.unknown_participant.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    let error = response.into_json::<ErrorResponse>().unwrap();
+    assert_eq!(error.code, ErrorCode::UnknownContributor);
+
+    // Wrong request, the turn of the current contributor has come but its chunk is already locked
+    req = client.get("/contributor/await_turn");
+    req = set_request::<u8>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    let error = response.into_json::<ErrorResponse>().unwrap();
+    assert_eq!(error.code, ErrorCode::LockAlreadyHeld);
+}
+
+/// Test wrong usage of get_challenge.
+#[test]
+fn wrong_get_challenge() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong request, non-json body
+    let req = client
         .post("/contributor/challenge")
         .header(ContentType::Text)
         .body("Wrong parameter type");
     let response = req.dispatch();
-    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.body().is_some());
+}
+
+#[test]
+fn get_challenge() {
+    use setup_utils::calculate_hash;
+
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Non-current contributor
+    let mut req = client.get(format!("/contributor/challenge/{}", ROUND_HEIGHT));
+    req = set_request::<()>(req, &ctx.contributors[1].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
     assert!(response.body().is_some());
+
+    // Ok
+    req = client.get(format!("/contributor/challenge/{}", ROUND_HEIGHT));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::Binary));
+    // The challenges are not signed by default
+    assert_eq!(response.headers().get_one(CHALLENGE_SIGNATURE_HEADER), None);
+    let hash = response.headers().get_one(CHALLENGE_HASH_HEADER).unwrap().to_owned();
+    let challenge = response.into_bytes().unwrap();
+    assert_eq!(hex::encode(calculate_hash(&challenge)), hash);
+
+    // Ok, compressed
+    req = client.get(format!("/contributor/challenge/{}", ROUND_HEIGHT));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    req.add_header(Header::new(ACCEPT_ENCODING_HEADER, "gzip, zstd"));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one(CONTENT_ENCODING_HEADER), Some("zstd"));
+    assert_eq!(response.headers().get_one(CHALLENGE_HASH_HEADER), Some(hash.as_str()));
+    let compressed = response.into_bytes().unwrap();
+    assert!(compressed.len() < challenge.len());
+    assert_eq!(zstd::decode_all(compressed.as_slice()).unwrap(), challenge);
 }
 
 /// Test wrong usage of post_contribution_chunk.
@@ -612,6 +1947,359 @@ fn wrong_contribute_chunk() {
     assert!(response.body().is_some());
 }
 
+/// Test wrong usage of upload_chunk.
+#[test]
+fn wrong_upload_chunk() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong request, json body
+    let mut req = client.post("/contributor/upload_chunk");
+    req = set_request(req, &ctx.contributors[0].keypair, Some(&String::from("Unexpected string")));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.body().is_some());
+
+    // Non-current-contributor
+    req = client.post("/contributor/upload_chunk");
+    req = set_raw_request(req, &ctx.contributors[1].keypair, b"contribution".to_vec());
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Missing contribution signature
+    req = client.post("/contributor/upload_chunk");
+    req = set_raw_request(req, &ctx.contributors[0].keypair, b"contribution".to_vec());
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.body().is_some());
+
+    // Body not matching the signed digest
+    let contribution_state = ContributionState::new(vec![0; 64], vec![0; 64], None).unwrap();
+    let signature = Production
+        .sign(
+            ctx.contributors[0].keypair.sigkey(),
+            &contribution_state.signature_message().unwrap(),
+        )
+        .unwrap();
+    let contribution_file_signature = ContributionFileSignature::new(signature, contribution_state).unwrap();
+
+    req = client.post("/contributor/upload_chunk");
+    req.add_header(Header::new(
+        CONTRIBUTION_SIGNATURE_HEADER,
+        base64::encode(serde_json::to_vec(&contribution_file_signature).unwrap()),
+    ));
+    req = set_raw_request(req, &ctx.contributors[0].keypair, b"contribution".to_vec());
+    req = req.body("tampered");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.body().is_some());
+
+    // Unsupported content encoding
+    req = client.post("/contributor/upload_chunk");
+    req.add_header(Header::new(
+        CONTRIBUTION_SIGNATURE_HEADER,
+        base64::encode(serde_json::to_vec(&contribution_file_signature).unwrap()),
+    ));
+    req = set_raw_request(req, &ctx.contributors[0].keypair, b"contribution".to_vec());
+    req.add_header(Header::new(CONTENT_ENCODING_HEADER, "deflate"));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.body().is_some());
+
+    // Contribution signed by another participant
+    let contribution_state = ContributionState::new(vec![0; 64], vec![0; 64], None).unwrap();
+    let signature = Production
+        .sign(
+            ctx.contributors[1].keypair.sigkey(),
+            &contribution_state.signature_message().unwrap(),
+        )
+        .unwrap();
+    let foreign_signature = ContributionFileSignature::new(signature, contribution_state).unwrap();
+
+    req = client.post("/contributor/upload_chunk");
+    req.add_header(Header::new(
+        CONTRIBUTION_SIGNATURE_HEADER,
+        base64::encode(serde_json::to_vec(&foreign_signature).unwrap()),
+    ));
+    req = set_raw_request(req, &ctx.contributors[0].keypair, b"contribution".to_vec());
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(
+        response.into_json::<ErrorResponse>().unwrap().code,
+        ErrorCode::InvalidContribution
+    );
+
+    // Contribution signature swapped after the signature of the request
+    let mut signed = client.post("/contributor/upload_chunk");
+    signed.add_header(Header::new(
+        CONTRIBUTION_SIGNATURE_HEADER,
+        base64::encode(serde_json::to_vec(&foreign_signature).unwrap()),
+    ));
+    signed = set_raw_request(signed, &ctx.contributors[0].keypair, b"contribution".to_vec());
+    req = client.post("/contributor/upload_chunk");
+    for header in signed.inner().headers().iter() {
+        if header.name() != CONTRIBUTION_SIGNATURE_HEADER {
+            req.add_header(Header::new(header.name().to_string(), header.value().to_string()));
+        }
+    }
+    req.add_header(Header::new(
+        CONTRIBUTION_SIGNATURE_HEADER,
+        base64::encode(serde_json::to_vec(&contribution_file_signature).unwrap()),
+    ));
+    req = req.body(b"contribution".to_vec());
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(
+        response.into_json::<ErrorResponse>().unwrap().code,
+        ErrorCode::InvalidSignature
+    );
+
+    // Contribution computed against another challenge than the one issued with the lock
+    req = client.post("/contributor/upload_chunk");
+    req.add_header(Header::new(
+        CONTRIBUTION_SIGNATURE_HEADER,
+        base64::encode(serde_json::to_vec(&contribution_file_signature).unwrap()),
+    ));
+    req = set_raw_request(req, &ctx.contributors[0].keypair, b"contribution".to_vec());
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+    assert_eq!(
+        response.into_json::<ErrorResponse>().unwrap().code,
+        ErrorCode::StaleChallenge
+    );
+}
+
+/// Test wrong usage of the resumable upload.
+#[test]
+fn wrong_resumable_upload() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let part = b"contribution".to_vec();
+
+    // Non-current-contributor
+    let mut req = client.post("/contributor/upload_chunk/part");
+    req = set_raw_request(req, &ctx.contributors[1].keypair, part.clone());
+    req.add_header(Header::new(CONTENT_RANGE_HEADER, "bytes 0-11/100"));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
+    // Missing range
+    req = client.post("/contributor/upload_chunk/part");
+    req = set_raw_request(req, &ctx.contributors[0].keypair, part.clone());
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.body().is_some());
+
+    // Range not matching the length of the part
+    req = client.post("/contributor/upload_chunk/part");
+    req = set_raw_request(req, &ctx.contributors[0].keypair, part.clone());
+    req.add_header(Header::new(CONTENT_RANGE_HEADER, "bytes 0-5/100"));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.body().is_some());
+
+    // Part not starting at the first missing byte
+    req = client.post("/contributor/upload_chunk/part");
+    req = set_raw_request(req, &ctx.contributors[0].keypair, part.clone());
+    req.add_header(Header::new(CONTENT_RANGE_HEADER, "bytes 12-23/100"));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::RangeNotSatisfiable);
+    assert!(response.body().is_some());
+
+    // Nothing uploaded yet
+    req = client.get("/contributor/upload_chunk/session");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let session: rest_utils::UploadSession = response.into_json().unwrap();
+    assert_eq!(session.received, 0);
+    assert!(!session.is_complete());
+
+    // Completing an incomplete upload
+    let contribution_state = ContributionState::new(vec![0; 64], vec![0; 64], None).unwrap();
+    let signature = Production
+        .sign(
+            ctx.contributors[0].keypair.sigkey(),
+            &contribution_state.signature_message().unwrap(),
+        )
+        .unwrap();
+    let contribution_file_signature = ContributionFileSignature::new(signature, contribution_state).unwrap();
+
+    req = client.post("/contributor/upload_chunk/complete");
+    req.add_header(Header::new(
+        CONTRIBUTION_SIGNATURE_HEADER,
+        base64::encode(serde_json::to_vec(&contribution_file_signature).unwrap()),
+    ));
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+    assert!(response.body().is_some());
+}
+
+/// Test a contribution uploaded in parts.
+#[test]
+fn resumable_upload() {
+    use setup_utils::calculate_hash;
+
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let keypair = &ctx.contributors[0].keypair;
+
+    // Get challenge
+    let mut req = client.get(format!("/contributor/challenge/{}", ROUND_HEIGHT));
+    req = set_request::<()>(req, keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let challenge = response.into_bytes().unwrap();
+
+    // Compute the contribution
+    let challenge_hash = calculate_hash(challenge.as_ref());
+    let mut contribution: Vec<u8> = Vec::new();
+    contribution.write_all(challenge_hash.as_slice()).unwrap();
+    let entropy = RandomSource::Entropy(String::from("entropy"));
+    Computation::contribute_test_masp(&challenge, &mut contribution, &entropy);
+    let response_hash = calculate_hash(contribution.as_ref());
+
+    let contribution_state = ContributionState::new(challenge_hash.to_vec(), response_hash.to_vec(), None).unwrap();
+    let signature = Production
+        .sign(keypair.sigkey(), &contribution_state.signature_message().unwrap())
+        .unwrap();
+    let contribution_file_signature = ContributionFileSignature::new(signature, contribution_state).unwrap();
+    let complete = |client: &Client| {
+        let mut req = client.post("/contributor/upload_chunk/complete");
+        req.add_header(Header::new(
+            CONTRIBUTION_SIGNATURE_HEADER,
+            base64::encode(serde_json::to_vec(&contribution_file_signature).unwrap()),
+        ));
+        req = set_request::<()>(req, keypair, None);
+        req.dispatch()
+    };
+
+    // Upload the first half
+    let total = contribution.len();
+    let half = total / 2;
+    req = client.post("/contributor/upload_chunk/part");
+    req = set_raw_request(req, keypair, contribution[..half].to_vec());
+    req.add_header(Header::new(
+        CONTENT_RANGE_HEADER,
+        format!("bytes 0-{}/{}", half - 1, total),
+    ));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let session: rest_utils::UploadSession = response.into_json().unwrap();
+    assert_eq!(session.received, half as u64);
+    assert!(!session.is_complete());
+
+    // The incomplete upload can't be completed, but is kept to be resumed
+    let response = complete(&client);
+    assert_eq!(response.status(), Status::Conflict);
+    req = client.get("/contributor/upload_chunk/session");
+    req = set_request::<()>(req, keypair, None);
+    let session: rest_utils::UploadSession = req.dispatch().into_json().unwrap();
+    assert_eq!(session.received, half as u64);
+
+    // Resume from the first missing byte
+    req = client.post("/contributor/upload_chunk/part");
+    req = set_raw_request(req, keypair, contribution[half..].to_vec());
+    req.add_header(Header::new(
+        CONTENT_RANGE_HEADER,
+        format!("bytes {}-{}/{}", half, total - 1, total),
+    ));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let session: rest_utils::UploadSession = response.into_json().unwrap();
+    assert_eq!(session.received, total as u64);
+    assert!(session.is_complete());
+
+    // Complete the upload
+    let response = complete(&client);
+    assert_eq!(response.status(), Status::Ok);
+    let receipt: ContributionReceipt = response.into_json().unwrap();
+    assert!(receipt.verify(ctx.coordinator.keypair.pubkey()));
+    assert_eq!(receipt.round_height, ROUND_HEIGHT);
+    assert_eq!(receipt.contribution_hash, hex::encode(&response_hash));
+
+    // The file of the session is removed once completed
+    let uploads = PathBuf::from(ctx.environment.local_base_directory()).join("uploads");
+    assert_eq!(std::fs::read_dir(uploads).unwrap().count(), 0);
+}
+
+#[test]
+fn wrong_request_signature() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let keypair = &ctx.contributors[0].keypair;
+
+    // Wrong, signature of a request to another endpoint
+    let signed = set_request::<()>(client.post("/contributor/heartbeat"), keypair, None);
+    let mut req = client.get("/contributor/queue_status");
+    for header in signed.inner().headers().iter() {
+        req.add_header(Header::new(header.name().to_string(), header.value().to_string()));
+    }
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    let error: ErrorResponse = response.into_json().unwrap();
+    assert_eq!(error.code, ErrorCode::InvalidSignature);
+
+    // Wrong, replayed request
+    let signed = set_request::<()>(client.post("/contributor/heartbeat"), keypair, None);
+    let mut replayed = client.post("/contributor/heartbeat");
+    for header in signed.inner().headers().iter() {
+        replayed.add_header(Header::new(header.name().to_string(), header.value().to_string()));
+    }
+    assert_eq!(signed.dispatch().status(), Status::Ok);
+    assert_eq!(replayed.dispatch().status(), Status::BadRequest);
+
+    // Wrong, stale timestamp
+    let timestamp = chrono::Utc::now().timestamp() - 2 * MAX_REQUEST_AGE;
+    let mut req = client.get("/contributor/queue_status");
+    let msg = SignatureHeaders::new("GET", "/contributor/queue_status".into(), timestamp, keypair.pubkey(), None, None)
+        .to_string()
+        .into_owned();
+    req.add_header(Header::new(PUBKEY_HEADER, keypair.pubkey().to_owned()));
+    req.add_header(Header::new(TIMESTAMP_HEADER, timestamp.to_string()));
+    req.add_header(Header::new(SIGNATURE_HEADER, Production.sign(keypair.sigkey(), &msg).unwrap()));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Wrong, missing timestamp
+    let mut req = client.get("/contributor/queue_status");
+    req.add_header(Header::new(PUBKEY_HEADER, keypair.pubkey().to_owned()));
+    req.add_header(Header::new(SIGNATURE_HEADER, Production.sign(keypair.sigkey(), &msg).unwrap()));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn operator_auth() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, an unknown key is not an operator
+    let mut req = client.get("/verify/status");
+    req = set_request::<()>(req, &ctx.unknown_participant.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Ok, the operator key is accepted on the admin endpoints
+    req = client.get("/verify/status");
+    req = set_request::<()>(req, &ctx.operator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    req = client.get("/update");
+    req = set_request::<()>(req, &ctx.operator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    req = client.get("/stop");
+    req = set_request::<()>(req, &ctx.operator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
 #[test]
 fn wrong_verify() {
     let ctx = build_context();
@@ -625,6 +2313,112 @@ fn wrong_verify() {
     assert!(response.body().is_some());
 }
 
+/// Test wrong usage of the external verifier endpoints.
+#[test]
+fn wrong_verifier_requests() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong request, not an external verifier
+    let mut req = client.get("/verifier/lock_task");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    let error = response.into_json::<ErrorResponse>().unwrap();
+    assert_eq!(error.code, ErrorCode::Unauthorized);
+
+    // No task to lock, the quorum is disabled
+    req = client.get("/verifier/lock_task");
+    req = set_request::<()>(req, &ctx.operator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.into_json::<Option<VerificationTask>>().unwrap().is_none());
+
+    // Wrong request, no task locked
+    req = client.get("/verifier/response");
+    req = set_request::<()>(req, &ctx.operator.keypair, None);
+    let response = req.dispatch();
+    let error = response.into_json::<ErrorResponse>().unwrap();
+    assert_eq!(error.code, ErrorCode::Unauthorized);
+
+    // Wrong request, invalid signature of the result
+    let result = VerificationResult {
+        task: VerificationTask {
+            round_height: ROUND_HEIGHT,
+            chunk_id: 0,
+            contribution_id: 1,
+        },
+        response_hash: String::from("hash"),
+        verified: true,
+        signature: String::from("signature"),
+    };
+    req = client.post("/verifier/verification_result");
+    req = set_request::<VerificationResult>(req, &ctx.operator.keypair, Some(&result));
+    let response = req.dispatch();
+    let error = response.into_json::<ErrorResponse>().unwrap();
+    assert_eq!(error.code, ErrorCode::InvalidContribution);
+}
+
+#[test]
+fn wrong_beacon() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let beacon = hex::encode([1u8; 32]);
+
+    // Wrong, request from non-coordinator participant
+    let mut req = client.post("/beacon");
+    req = set_request::<String>(req, &ctx.contributors[0].keypair, Some(&beacon));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Wrong, malformed beacon
+    req = client.post("/beacon");
+    req = set_request::<String>(req, &ctx.coordinator.keypair, Some(&"0xabcd".to_string()));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.body().is_some());
+}
+
+#[test]
+fn request_verification() {
+    let mut ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong, request from non-coordinator participant
+    let mut req = client.post("/verify");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    req = client.get("/verify/status");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // No verification has been requested yet
+    req = client.get("/verify/status");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let status: VerificationStatus = response.into_json().unwrap();
+    assert!(!status.queued);
+    assert!(!status.running);
+    assert!(status.last_started.is_none());
+
+    // Queue a verification, the second request is merged with the first one
+    for _ in 0..2 {
+        req = client.post("/verify");
+        req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+        let response = req.dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let status: VerificationStatus = response.into_json().unwrap();
+        assert!(status.queued);
+    }
+
+    assert!(ctx.verification_requests.try_recv().is_ok());
+    assert!(ctx.verification_requests.try_recv().is_err());
+}
+
 #[test]
 fn wrong_post_contribution_info() {
     let ctx = build_context();
@@ -678,12 +2472,15 @@ fn wrong_post_contribution_info() {
 /// - post_contribution_chunk
 /// - verify_chunk
 /// - get_contributions_info
+/// - get_feed
+/// - get_contributor_bundle
 /// - Update cohorts' tokens
 /// - join_queue with already contributed Ip
 /// - join_queue with already contributed token
 /// - Skip to second cohort
 /// - Try joinin queue with expired token
 /// - Try attestation
+/// - Bind Namada address
 /// - Try joinin queue with correct token
 ///
 #[test]
@@ -694,6 +2491,7 @@ fn contribution() {
 
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let _tokens_lock = lock_tokens();
     let reqwest_client = reqwest::blocking::Client::new();
     let start_time = std::time::Instant::now();
 
@@ -735,9 +2533,11 @@ fn contribution() {
     let entropy = RandomSource::Entropy(String::from("entropy"));
     Computation::contribute_test_masp(&challenge, &mut contribution, &entropy);
 
-    // Initial contribution size is 2332 but the Coordinator expect ANOMA_BASE_FILE_SIZE. Extend to this size with trailing 0s
-    let contrib_size = Object::anoma_contribution_file_size(ROUND_HEIGHT, 1);
-    contribution.resize(contrib_size as usize, 0);
+    // The contribution has the size the coordinator expects, without padding
+    assert_eq!(
+        contribution.len() as u64,
+        Object::anoma_contribution_file_size(&ctx.environment, ROUND_HEIGHT, 1)
+    );
 
     let contribution_file_signature_locator = ContributionSignatureLocator::new(ROUND_HEIGHT, 0, 1, false);
 
@@ -774,7 +2574,9 @@ fn contribution() {
         .unwrap()
         .current_contribution()
         .round_height();
-    contrib_info.try_sign(&ctx.contributors[0].keypair).unwrap();
+    contrib_info
+        .try_sign(&Production, &ctx.contributors[0].keypair)
+        .unwrap();
 
     req = client.post("/contributor/contribution_info");
     req = set_request::<ContributionInfo>(req, &ctx.contributors[0].keypair, Some(&contrib_info));
@@ -789,7 +2591,14 @@ fn contribution() {
     req = set_request::<PostChunkRequest>(req, &ctx.contributors[0].keypair, Some(&post_chunk));
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
-    assert!(response.body().is_none());
+
+    // The receipt is signed by the coordinator over the hash of the stored contribution
+    let receipt: ContributionReceipt = response.into_json().unwrap();
+    assert!(receipt.verify(ctx.coordinator.keypair.pubkey()));
+    assert!(!receipt.verify(ctx.contributors[0].keypair.pubkey()));
+    assert_eq!(receipt.round_height, ROUND_HEIGHT);
+    assert_eq!(receipt.chunk_id, 0);
+    assert_eq!(receipt.contribution_hash, hex::encode(&response_hash));
 
     // Verify chunk
     req = client.get("/verify");
@@ -798,6 +2607,71 @@ fn contribution() {
     assert_eq!(response.status(), Status::Ok);
     assert!(response.body().is_none());
 
+    // The verified contribution is part of the public transcript
+    req = client.get("/transcript");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let transcript: Transcript = response.into_json().unwrap();
+    assert_eq!(transcript.coordinator_public_key, ctx.coordinator.keypair.pubkey());
+    let (round_height, contribution) = transcript
+        .find_contribution(ctx.contributors[0].keypair.pubkey())
+        .unwrap();
+    assert_eq!(round_height, ROUND_HEIGHT);
+    assert_eq!(contribution.challenge_hash, hex::encode(&challenge_hash));
+    assert_eq!(contribution.response_hash, hex::encode(&response_hash));
+    assert!(contribution.verified);
+
+    // The detail of the round tells the same contribution
+    req = client.get(format!("/round/{}", ROUND_HEIGHT));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let round: RoundInfo = response.into_json().unwrap();
+    assert_eq!(round.round_height, ROUND_HEIGHT);
+    let contributor = ctx.contributors[0].keypair.pubkey().to_owned();
+    assert!(round.contributors.contains(&contributor));
+    assert!(round.chunks[0].contributions.contains(contribution));
+
+    // The history of the contributor holds the contribution with the receipt issued at its acceptance
+    req = client.get(format!("/contributor/{}/history", contributor));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let history: Vec<ContributionRecord> = response.into_json().unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].round_height, ROUND_HEIGHT);
+    assert_eq!(history[0].response_hash, hex::encode(&response_hash));
+    assert!(history[0].verified);
+    assert!(history[0].receipt.verify(ctx.coordinator.keypair.pubkey()));
+    assert_eq!(history[0].receipt, receipt);
+
+    req = client.get(format!("/contributor/{}/history", ctx.unknown_participant.keypair.pubkey()));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    req = client.get(format!("/contributor/{}/contributed", contributor));
+    let response = req.dispatch();
+    assert!(response.into_json::<bool>().unwrap());
+    req = client.get(format!("/contributor/{}/contributed", ctx.unknown_participant.keypair.pubkey()));
+    let response = req.dispatch();
+    assert!(!response.into_json::<bool>().unwrap());
+
+    // The contributor is on the public roll call, under its chosen name
+    req = client.get("/contributors?page=1&per_page=10");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let page: ContributorsPage = response.into_json().unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.contributors[0].public_key, contributor);
+    assert_eq!(page.contributors[0].display_name.as_deref(), Some("Test Name"));
+
+    req = client.get("/contributors?page=2&per_page=10");
+    let page: ContributorsPage = req.dispatch().into_json().unwrap();
+    assert!(page.contributors.is_empty());
+
+    // An unknown round is not found
+    req = client.get(format!("/round/{}", ROUND_HEIGHT + 10));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
     // Get contributions info
     req = client.get("/contribution_info");
     let response = req.dispatch();
@@ -811,6 +2685,31 @@ fn contribution() {
     assert!(!summary[0].is_own_seed_of_randomness());
     assert_eq!(summary[0].ceremony_round(), 1);
 
+    // Get the feed of the verified contributions
+    req = client.get("/feed.atom");
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::new("application", "atom+xml")));
+    let feed = response.into_string().unwrap();
+    assert!(feed.contains(summary[0].fingerprint()));
+    assert!(feed.contains(summary[0].contribution_hash()));
+
+    // Download the bundle of the contribution
+    req = client.get(format!("/contributor/{}/bundle.zip", ctx.contributors[0].keypair.pubkey()));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::ZIP));
+    let bundle = zip::ZipArchive::new(std::io::Cursor::new(response.into_bytes().unwrap())).unwrap();
+    for file in [
+        "contribution.json",
+        "challenge.hash",
+        "response",
+        "response.signature",
+        "verification.signature",
+    ] {
+        assert!(bundle.file_names().any(|f| f == file));
+    }
+
     // Update cohorts
     assert!(std::fs::metadata(TOKENS_ZIP_FILE).is_err());
     let new_valid_tokens = get_serialized_tokens_zip(vec![
@@ -917,6 +2816,55 @@ fn contribution() {
     assert_eq!(response.status(), Status::Ok);
     assert!(response.body().is_none());
 
+    // Get the canonical attestation message
+    req = client.get(format!(
+        "/contributor/attestation_message/{}",
+        ctx.contributors[0].keypair.pubkey()
+    ));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let attestation_message: String = response.into_json().unwrap();
+    assert_eq!(attestation_message, contrib_info.attestation_message());
+
+    // Wrong, statement signed for another round
+    let statement = String::from("I contributed from an air-gapped machine");
+    let attestation =
+        AttestationStatement::try_new(&Production, &ctx.contributors[0].keypair, 2, statement.clone(), None).unwrap();
+    req = client.post("/contributor/attestation");
+    req = set_request::<(u64, AttestationStatement)>(req, &ctx.contributors[0].keypair, Some(&(1, attestation)));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Ok signed statement, published in the summary
+    let attestation =
+        AttestationStatement::try_new(&Production, &ctx.contributors[0].keypair, 1, statement.clone(), None).unwrap();
+    req = client.post("/contributor/attestation");
+    req = set_request::<(u64, AttestationStatement)>(req, &ctx.contributors[0].keypair, Some(&(1, attestation)));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    req = client.get("/contribution_info");
+    let response = req.dispatch();
+    let summary: Vec<TrimmedContributionInfo> = response.into_json().unwrap();
+    assert_eq!(
+        summary[0].attestation_statement().map(|a| a.statement.as_str()),
+        Some(statement.as_str())
+    );
+    assert_eq!(summary[0].attestation(), Some("https://namada.net"));
+
+    // Ok binding to a Namada address
+    let address = bech32::encode("atest", b"imp::test address".to_base32(), bech32::Variant::Bech32m).unwrap();
+    let binding = NamadaAddressBinding::try_new(&Production, &ctx.contributors[0].keypair, address.clone()).unwrap();
+    req = client.post("/contributor/namada_address");
+    req = set_request::<(u64, NamadaAddressBinding)>(req, &ctx.contributors[0].keypair, Some(&(1, binding)));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    req = client.get("/contribution_info");
+    let response = req.dispatch();
+    let summary: Vec<TrimmedContributionInfo> = response.into_json().unwrap();
+    assert_eq!(summary[0].namada_address(), Some(address.as_str()));
+
     // Try joining the queue with correct token
     req = client.post("/contributor/join_queue").remote(socket_address);
     req = set_request::<String>(
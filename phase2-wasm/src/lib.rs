@@ -0,0 +1,222 @@
+// Documentation
+#![doc = include_str!("../README.md")]
+
+use blake2::{Blake2b512, Digest};
+use ed25519_compact::{KeyPair as EdKeyPair, Seed};
+use masp_phase2::MPCParameters;
+use phase2_client_sdk::{header_digest, signature_message, KeyPair};
+use rand::{rngs::OsRng, RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+use serde::Serialize;
+use sha2::Sha256;
+use wasm_bindgen::prelude::*;
+
+use std::{convert::TryInto, ops::Deref};
+
+/// The domain separation tag of the KDF deriving the seed of a contribution, as in the coordinator
+pub const ENTROPY_KDF_DOMAIN: &[u8] = b"namada-trusted-setup/contribution-seed/v1";
+
+/// Length of the Blake2b-512 hash of the challenge at the beginning of the response file
+const HASH_LENGTH: usize = 64;
+
+/// The state of a contribution signed by its contributor, serialized as the `ContributionState` of the coordinator
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ContributionState {
+    challenge_hash: String,
+    response_hash: String,
+}
+
+/// The signature of a contribution, serialized as the `ContributionFileSignature` of the coordinator
+#[derive(Serialize)]
+struct ContributionFileSignature {
+    signature: String,
+    state: ContributionState,
+}
+
+/// The keypair of a contributor, signing its requests to the coordinator and its contribution
+#[wasm_bindgen]
+pub struct Contributor {
+    keypair: KeyPair,
+}
+
+#[wasm_bindgen]
+impl Contributor {
+    /// Generates a random keypair
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            keypair: KeyPair::new(),
+        }
+    }
+
+    /// Derives the keypair from the first 32 bytes of the seed, as the CLI does from the seed of a mnemonic
+    #[wasm_bindgen(js_name = fromSeed)]
+    pub fn from_seed(seed: &[u8]) -> Result<Contributor, JsError> {
+        let seed = seed
+            .get(0..32)
+            .ok_or_else(|| JsError::new("The seed is shorter than 32 bytes"))?;
+        let keypair = EdKeyPair::from_seed(Seed::from_slice(seed).map_err(|e| JsError::new(&e.to_string()))?);
+
+        Ok(Self {
+            keypair: KeyPair::from_hex(hex::encode(keypair.pk.deref()), hex::encode(keypair.sk.deref())),
+        })
+    }
+
+    /// The hex encoded public key, to be sent in the `ATS-Pubkey` header
+    #[wasm_bindgen(getter)]
+    pub fn pubkey(&self) -> String {
+        self.keypair.pubkey().to_string()
+    }
+
+    /// Signs the message, returns the hex encoded signature
+    pub fn sign(&self, message: &str) -> Result<String, JsError> {
+        self.keypair.sign(message).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Signs a request to `path` (including the query, if any) sent at the unix `timestamp` in seconds, returns the
+    /// value of the `ATS-Signature` header. The `Digest` header of a request with a body must be
+    /// `sha-256=` followed by [`body_digest`] of the body. The value of the `ATS-Contribution-Signature` header, if
+    /// the request carries it, is signed too.
+    #[wasm_bindgen(js_name = signRequest)]
+    pub fn sign_request(
+        &self,
+        method: &str,
+        path: &str,
+        timestamp: f64,
+        body: Option<Vec<u8>>,
+        contribution_signature: Option<String>,
+    ) -> Result<String, JsError> {
+        let digest = body.as_ref().map(|body| (body.len(), body_digest(body)));
+        let contribution_signature = contribution_signature.as_deref().map(header_digest);
+        let message = signature_message(
+            method,
+            path,
+            timestamp as i64,
+            self.keypair.pubkey(),
+            digest.as_ref().map(|(len, digest)| (*len, digest.as_str())),
+            contribution_signature.as_deref(),
+        );
+
+        self.sign(&message)
+    }
+}
+
+/// Returns the [`base64`] encoded sha-256 digest of the body of a request
+#[wasm_bindgen(js_name = bodyDigest)]
+pub fn body_digest(body: &[u8]) -> String {
+    base64::encode(Sha256::digest(body))
+}
+
+/// Returns the hex encoded Blake2b-512 hash of the bytes, as used for the challenge, response and contribution hashes
+#[wasm_bindgen]
+pub fn hash(bytes: &[u8]) -> String {
+    hex::encode(Blake2b512::digest(bytes))
+}
+
+/// Mixes the OS entropy with the one of the user, with the KDF of `Computation::mix_entropy` in the coordinator. The
+/// browser doesn't expose a hardware RNG, which is encoded as empty.
+pub fn mix_entropy(os_entropy: &[u8], user_entropy: &[u8]) -> [u8; 32] {
+    let mut h = Blake2b512::new();
+    h.update(ENTROPY_KDF_DOMAIN);
+    for entropy in [os_entropy, user_entropy, &[]] {
+        h.update(&(entropy.len() as u64).to_le_bytes());
+        h.update(entropy);
+    }
+
+    h.finalize()[0..32].try_into().unwrap()
+}
+
+/// Computes the contribution to the challenge with randomness derived from the browser RNG and the entropy given by
+/// the user. `circuits` is the number of MPC parameters in the challenge: 3 in production (Spend, Output and
+/// Convert), 1 for the test parameters.
+///
+/// Returns the response file to upload: the hash of the challenge followed by the updated parameters.
+#[wasm_bindgen]
+pub fn contribute(challenge: &[u8], entropy: &str, circuits: usize) -> Result<Vec<u8>, JsError> {
+    let mut os_entropy = [0u8; 64];
+    OsRng.fill_bytes(&mut os_entropy);
+    let mut rng = ChaChaRng::from_seed(mix_entropy(&os_entropy, entropy.as_bytes()));
+
+    let mut response = Blake2b512::digest(challenge).to_vec();
+    let mut challenge_reader = challenge
+        .get(HASH_LENGTH..)
+        .ok_or_else(|| JsError::new("The challenge is shorter than its hash"))?;
+    let progress_update_interval: u32 = 0;
+    for _ in 0..circuits {
+        let mut parameters =
+            MPCParameters::read(&mut challenge_reader, false).map_err(|e| JsError::new(&e.to_string()))?;
+        parameters.contribute(&mut rng, &progress_update_interval);
+        parameters
+            .write(&mut response)
+            .map_err(|e| JsError::new(&e.to_string()))?;
+    }
+
+    Ok(response)
+}
+
+/// Returns the message signed by the contributor over the hex encoded hashes of its challenge and response files
+pub fn contribution_state_message(challenge_hash: &str, response_hash: &str) -> String {
+    serde_json::to_string(&ContributionState {
+        challenge_hash: challenge_hash.to_string(),
+        response_hash: response_hash.to_string(),
+    })
+    .unwrap()
+}
+
+/// Signs the contribution, returns the json of the contribution file signature to upload with the response file
+#[wasm_bindgen(js_name = contributionFileSignature)]
+pub fn contribution_file_signature(
+    contributor: &Contributor,
+    challenge: &[u8],
+    response: &[u8],
+) -> Result<String, JsError> {
+    let state = ContributionState {
+        challenge_hash: hash(challenge),
+        response_hash: hash(response),
+    };
+    let signature = contributor.sign(&contribution_state_message(&state.challenge_hash, &state.response_hash))?;
+
+    serde_json::to_string(&ContributionFileSignature { signature, state }).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phase2_coordinator::{
+        authentication::{KeyPair as CoordinatorKeyPair, Production, Signature},
+        commands::Computation,
+        objects::ContributionState as CoordinatorContributionState,
+    };
+
+    #[test]
+    fn test_mix_entropy_matches_coordinator() {
+        assert_eq!(
+            mix_entropy(b"os", b"user"),
+            Computation::mix_entropy(b"os", b"user", None)
+        );
+    }
+
+    #[test]
+    fn test_contribution_state_message_matches_coordinator() {
+        let (challenge_hash, response_hash) = ([1u8; 64], [2u8; 64]);
+        let state = CoordinatorContributionState::new(challenge_hash.to_vec(), response_hash.to_vec(), None).unwrap();
+
+        assert_eq!(
+            contribution_state_message(&hex::encode(challenge_hash), &hex::encode(response_hash)),
+            state.signature_message().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_keypair_from_seed_matches_coordinator() {
+        let seed = [7u8; 64];
+        let contributor = Contributor::from_seed(&seed).unwrap();
+        let keypair = CoordinatorKeyPair::try_from_seed(&seed).unwrap();
+        assert_eq!(contributor.pubkey(), keypair.pubkey());
+
+        let signature = contributor.sign("message").unwrap();
+        assert!(Production.verify(keypair.pubkey(), "message", &signature));
+    }
+}
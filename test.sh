@@ -13,7 +13,7 @@ done
 # Coordinator test
 echo "Cleaning S3 bucket for coordinator test..."
 aws s3 rm s3://$AWS_S3_BUCKET --recursive
-cargo test --test test_coordinator -- --test-threads=1
+cargo test --test test_coordinator
 
 # e2e test
 echo "Cleaning S3 bucket for e2e test..."